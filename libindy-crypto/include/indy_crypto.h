@@ -3,5 +3,51 @@
 
 #include "indy_crypto_error.h"
 #include "indy_crypto_bls.h"
+#include "indy_crypto_cl.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+    /// Thread safety: every function may be called from any thread. Objects are referenced by handles
+    /// that may be created, used and freed on different threads, and a handle may be passed to any number
+    /// of concurrent calls that only read the object, e.g. the same verification key and generator
+    /// to many indy_crypto_bls_verify calls. Functions that change the object of a handle, e.g.
+    /// indy_crypto_bls_multi_signature_add, need exclusive use of that handle. Freeing a handle while
+    /// other call uses it is an error. The last error is stored per thread.
+
+    /// Returns version and build metadata of the library as static json string:
+    /// { "version": string, "features": [string], "curve": Optional<string> }.
+    extern indy_crypto_error_t indy_crypto_version(const char** version_json_p);
+
+    /// Initializes global state of the library. Optional, every function initializes
+    /// the state it uses on first call. Idempotent.
+    extern indy_crypto_error_t indy_crypto_init();
+
+    /// Flushes the logger, frees all objects given out by the library, so outstanding handles
+    /// are reported as freed, reseeds random generators and clears the last error of the calling thread.
+    /// No other call may be in progress. The library is initialized again on next use.
+    extern indy_crypto_error_t indy_crypto_terminate();
+
+    /// Returns details of the last error produced on the calling thread as json:
+    /// { "code": int, "message": string, "backtrace": Optional<string> }, or null if no error happened.
+    /// The string is owned by the library and must not be freed. It stays valid until
+    /// the next indy_crypto_get_current_error call on the same thread.
+    extern void indy_crypto_get_current_error(const char** error_json_p);
+
+    /// Replaces runtime config of the library with json:
+    /// { "verification_threads": Optional<int>, "strict_validation": Optional<bool>,
+    ///   "max_signers": Optional<int>, "max_message_size": Optional<int> }.
+    /// Missing keys take default values, unknown keys are ignored with a warning.
+    /// Invalid values are reported as CommonInvalidStructure.
+    extern indy_crypto_error_t indy_crypto_set_runtime_config(const char* config_json);
+
+    /// Deallocates string returned by the library, e.g. json of indy_crypto_bls_sign_json.
+    /// Strings owned by the library (indy_crypto_get_current_error, indy_crypto_version) must not be passed.
+    extern indy_crypto_error_t indy_crypto_string_free(const char* s);
+
+#ifdef __cplusplus
+}
+#endif
 
 #endif
@@ -1,12 +1,170 @@
 #ifndef __indy__crypto__bls__included__
 #define __indy__crypto__bls__included__
 
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
 
     /// TODO: FIXME: Provide list of interfaces for ffi/bls.rs
 
+    /// Byte size constants by name, e.g. "signature_size", "seed_size" or "point_g1_compressed_size".
+    /// Unknown name is an invalid param.
+    extern indy_crypto_error_t indy_crypto_bls_constant(const char* name, size_t* value_p);
+    /// Curve name, e.g. "BN254". Returned string is owned by the library and must not be deallocated.
+    extern indy_crypto_error_t indy_crypto_bls_curve_name(const char** curve_name_p);
+
+    /// Messages of sign and verify functions may be empty: zero message_len is an empty message whatever
+    /// the message pointer is, null message with non zero message_len is an invalid param.
+
+    /// Item of indy_crypto_bls_verify_batch: message with its signature and verification key.
+    typedef struct
+    {
+        const unsigned char* message;
+        size_t message_len;
+        const void* signature;
+        const void* ver_key;
+    } indy_crypto_bls_batch_item_t;
+
+    /// Verifies a batch of independent message signatures at once.
+    /// valid_p is set to true if all signatures are valid or false otherwise.
+    /// CommonInvalidParam1 is returned for the first item with null pointer or zero message len.
+    extern indy_crypto_error_t indy_crypto_bls_verify_batch(const indy_crypto_bls_batch_item_t* items,
+                                                            size_t items_len,
+                                                            const void* gen,
+                                                            bool* valid_p);
+
+    /// Write bytes representation of the instance into the caller allocated buffer.
+    /// Size of representation is always written to written_p, call with null buf and zero buf_len
+    /// to query it. CommonInvalidStructure is returned if buf_len is less than the size.
+    extern indy_crypto_error_t indy_crypto_bls_generator_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_sign_key_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_ver_key_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_pop_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_aggregated_ver_key_as_bytes_into(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+
+    /// Compressed representation: sign of y followed by x, 65 bytes for generators and verification
+    /// keys, 33 bytes for signatures and multi signatures. as_compressed_bytes writes it into the caller
+    /// allocated buffer like as_bytes_into, from_compressed_bytes requires bytes_len of exactly that size.
+    extern indy_crypto_error_t indy_crypto_bls_generator_as_compressed_bytes(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_generator_from_compressed_bytes(const unsigned char* bytes, size_t bytes_len, const void** gen_p);
+    extern indy_crypto_error_t indy_crypto_bls_ver_key_as_compressed_bytes(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_ver_key_from_compressed_bytes(const unsigned char* bytes, size_t bytes_len, const void** ver_key_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_as_compressed_bytes(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_from_compressed_bytes(const unsigned char* bytes, size_t bytes_len, const void** signature_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_as_compressed_bytes(const void* handle, unsigned char* buf, size_t buf_len, size_t* written_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_from_compressed_bytes(const unsigned char* bytes, size_t bytes_len, const void** multi_sig_p);
+
+    /// Hex variants of from_bytes and as_bytes. from_hex requires even length, hex digits only and
+    /// exactly the size of bytes representation. Returned hex must be deallocated by calling indy_crypto_string_free.
+    extern indy_crypto_error_t indy_crypto_bls_generator_from_hex(const char* hex, const void** gen_p);
+    extern indy_crypto_error_t indy_crypto_bls_generator_to_hex(const void* handle, const char** hex_p);
+    extern indy_crypto_error_t indy_crypto_bls_sign_key_from_hex(const char* hex, const void** sign_key_p);
+    extern indy_crypto_error_t indy_crypto_bls_sign_key_to_hex(const void* handle, const char** hex_p);
+    extern indy_crypto_error_t indy_crypto_bls_ver_key_from_hex(const char* hex, const void** ver_key_p);
+    extern indy_crypto_error_t indy_crypto_bls_ver_key_to_hex(const void* handle, const char** hex_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_from_hex(const char* hex, const void** signature_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_to_hex(const void* handle, const char** hex_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_from_hex(const char* hex, const void** multi_sig_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_to_hex(const void* handle, const char** hex_p);
+
+    /// Json variants of sign and verify: objects are passed as json of their serde representation.
+    /// Returned json must be deallocated by calling indy_crypto_string_free.
+    extern indy_crypto_error_t indy_crypto_bls_sign_json(const unsigned char* message, size_t message_len,
+                                                         const char* sign_key_json, const char** signature_json_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_json(const char* signature_json,
+                                                           const unsigned char* message, size_t message_len,
+                                                           const char* ver_key_json, const char* gen_json, bool* valid_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_json(const char* signatures_json, const char** multi_sig_json_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_multi_sig_json(const char* multi_sig_json,
+                                                                     const unsigned char* message, size_t message_len,
+                                                                     const char* ver_keys_json, const char* gen_json, bool* valid_p);
+
+    /// Sign and verify variants with domain separation tag of 1 to 255 bytes.
+    /// Zero dst_len signs and verifies without tag as the variants without dst do, dst may be null then.
+    extern indy_crypto_error_t indy_crypto_bls_sign_with_dst(const unsigned char* message, size_t message_len,
+                                                             const unsigned char* dst, size_t dst_len,
+                                                             const void* sign_key, const void** signature_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_with_dst(const void* signature,
+                                                               const unsigned char* message, size_t message_len,
+                                                               const unsigned char* dst, size_t dst_len,
+                                                               const void* ver_key, const void* gen, bool* valid_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_multi_sig_with_dst(const void* multi_sig,
+                                                                         const unsigned char* message, size_t message_len,
+                                                                         const unsigned char* dst, size_t dst_len,
+                                                                         const void* const* ver_keys, size_t ver_keys_len,
+                                                                         const void* gen, bool* valid_p);
+
+    /// Verify variants with error detail: on error error_detail_p is set to json
+    /// { "code": int, "name": string, "param": Optional<int>, "message": string }, null on success.
+    /// param is 1-based index of the invalid param. Returned json must be deallocated by calling indy_crypto_string_free.
+    extern indy_crypto_error_t indy_crypto_bls_verify_ext(const void* signature,
+                                                          const unsigned char* message, size_t message_len,
+                                                          const void* ver_key, const void* gen, bool* valid_p,
+                                                          const char** error_detail_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_multi_sig_ext(const void* multi_sig,
+                                                                    const unsigned char* message, size_t message_len,
+                                                                    const void* const* ver_keys, size_t ver_keys_len,
+                                                                    const void* gen, bool* valid_p,
+                                                                    const char** error_detail_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key_ext(const void* multi_sig,
+                                                                                            const unsigned char* message, size_t message_len,
+                                                                                            const void* agg_ver_key, const void* gen, bool* valid_p,
+                                                                                            const char** error_detail_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_batch_ext(const indy_crypto_bls_batch_item_t* items,
+                                                                size_t items_len,
+                                                                const void* gen,
+                                                                bool* valid_p,
+                                                                const char** error_detail_p);
+    typedef void (*indy_crypto_bls_verify_batch_cb_t)(int32_t command_handle, indy_crypto_error_t err, bool valid);
+
+    extern indy_crypto_error_t indy_crypto_bls_verify_batch_async(const indy_crypto_bls_batch_item_t* items,
+                                                                  size_t items_len,
+                                                                  const void* gen,
+                                                                  int32_t command_handle,
+                                                                  indy_crypto_bls_verify_batch_cb_t cb);
+    extern indy_crypto_error_t indy_crypto_bls_verify_pop_ext(const void* pop, const void* ver_key, const void* gen, bool* valid_p,
+                                                              const char** error_detail_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_json_ext(const char* signature_json,
+                                                               const unsigned char* message, size_t message_len,
+                                                               const char* ver_key_json, const char* gen_json, bool* valid_p,
+                                                               const char** error_detail_p);
+    extern indy_crypto_error_t indy_crypto_bls_verify_multi_sig_json_ext(const char* multi_sig_json,
+                                                                         const unsigned char* message, size_t message_len,
+                                                                         const char* ver_keys_json, const char* gen_json, bool* valid_p,
+                                                                         const char** error_detail_p);
+
+    /// Incremental multi signature: add signatures as they arrive or merge partial multi signatures.
+    /// The result has the same bytes as indy_crypto_bls_multi_signature_new of all signatures.
+    /// indy_crypto_bls_multi_signature_add mutates multi_sig, caller must not use the same
+    /// instance from other threads until the call returns.
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_new_empty(const void** multi_sig_p);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_add(const void* multi_sig, const void* signature);
+    extern indy_crypto_error_t indy_crypto_bls_multi_signature_merge(const void* a, const void* b, const void** multi_sig_p);
+
+    /// Threshold signing: any threshold of n sign key shares can sign.
+    /// shares_p is the caller allocated array of n pointers that is filled with sign key shares.
+    /// indy_crypto_bls_combine_signature_shares returns CryptoNotEnoughShares if less than threshold
+    /// shares are given and CommonInvalidStructure if shares don't belong together.
+    extern indy_crypto_error_t indy_crypto_bls_generate_shares(unsigned int n, unsigned int threshold,
+                                                               const unsigned char* seed, size_t seed_len,
+                                                               const void** sign_key_p, const void** shares_p);
+    extern indy_crypto_error_t indy_crypto_bls_sign_key_share_from_bytes(const unsigned char* bytes, size_t bytes_len, const void** share_p);
+    extern indy_crypto_error_t indy_crypto_bls_sign_key_share_as_bytes(const void* share, const unsigned char** bytes_p, size_t* bytes_len_p);
+    extern indy_crypto_error_t indy_crypto_bls_sign_key_share_free(const void* share);
+    extern indy_crypto_error_t indy_crypto_bls_sign_share(const unsigned char* message, size_t message_len,
+                                                          const void* share, const void** signature_share_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_share_from_bytes(const unsigned char* bytes, size_t bytes_len, const void** signature_share_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_share_as_bytes(const void* signature_share, const unsigned char** bytes_p, size_t* bytes_len_p);
+    extern indy_crypto_error_t indy_crypto_bls_signature_share_free(const void* signature_share);
+    extern indy_crypto_error_t indy_crypto_bls_combine_signature_shares(const void* const* signature_shares, size_t signature_shares_len,
+                                                                        const void** signature_p);
+
 #ifdef __cplusplus
 }
 #endif
@@ -1,12 +1,112 @@
 #ifndef __indy__crypto__cl__included__
 #define __indy__crypto__cl__included__
 
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
 
     /// TODO: FIXME: Provide list of interfaces for ffi/cl/mod.rs
 
+    /// Reads bytes representation of the tail with index idx into out (as stored in tails files,
+    /// out_len is 128 bytes) and returns Success. The callback is called only during the calls that use
+    /// the accessor, on their thread.
+    typedef indy_crypto_error_t (*indy_crypto_cl_tail_read_cb_t)(const void* context, uint32_t idx, unsigned char* out, size_t out_len);
+
+    /// Receives progress event of credential definition generation: 0 - prime candidate tested, 1 - prime found,
+    /// 2 - primary keys, 3 - revocation keys, 4 - key correctness proof finished. Returns false to cancel.
+    typedef bool (*indy_crypto_cl_key_gen_progress_cb_t)(const void* context, uint32_t event);
+
+    /// Encodes raw attribute value (may be empty) to decimal string of a credential value,
+    /// kind is "Auto" (de-facto Indy encoding), "Integer" or "Text". encoded_p must be freed by indy_crypto_string_free.
+    extern indy_crypto_error_t indy_crypto_cl_encode_attribute(const char* raw,
+                                                               const char* kind,
+                                                               const char** encoded_p);
+
+    /// Creates nonce deterministically from seed (not empty): the first 80 bits of SHA-256 hash of the seed
+    /// as big-endian integer. nonce_p must be freed by indy_crypto_cl_nonce_free.
+    extern indy_crypto_error_t indy_crypto_cl_new_nonce_from_seed(const unsigned char* seed,
+                                                                  size_t seed_len,
+                                                                  const void** nonce_p);
+
+    /// Creates credential definition like indy_crypto_cl_issuer_new_credential_def, calling progress_cb (may be null)
+    /// on the thread of the call. CommonCancelled is returned if progress_cb returns false.
+    extern indy_crypto_error_t indy_crypto_cl_issuer_new_credential_def_with_progress(const void* credential_schema,
+                                                                                      const void* non_credential_schema,
+                                                                                      bool support_revocation,
+                                                                                      const void* context,
+                                                                                      indy_crypto_cl_key_gen_progress_cb_t progress_cb,
+                                                                                      const void** credential_pub_key_p,
+                                                                                      const void** credential_priv_key_p,
+                                                                                      const void** credential_key_correctness_proof_p);
+
+    /// Tails accessor reads tails on demand, so the tails file never has to be loaded whole.
+    /// Context must stay valid until the accessor is freed by indy_crypto_cl_tails_accessor_free.
+    extern indy_crypto_error_t indy_crypto_cl_tails_accessor_create(const void* context,
+                                                                    indy_crypto_cl_tail_read_cb_t read_cb,
+                                                                    const void** accessor_p);
+    extern indy_crypto_error_t indy_crypto_cl_tails_accessor_free(const void* accessor);
+
+    extern indy_crypto_error_t indy_crypto_cl_witness_new_with_tails_accessor(uint32_t rev_idx,
+                                                                              uint32_t max_cred_num,
+                                                                              bool issuance_by_default,
+                                                                              const void* rev_reg_delta,
+                                                                              const void* tails_accessor,
+                                                                              const void** witness_p);
+    extern indy_crypto_error_t indy_crypto_cl_witness_update_with_tails_accessor(uint32_t rev_idx,
+                                                                                 uint32_t max_cred_num,
+                                                                                 const void* rev_reg_delta,
+                                                                                 void* witness,
+                                                                                 const void* tails_accessor);
+
+    /// Merges consecutive deltas into a new delta, that must be freed by indy_crypto_cl_revocation_registry_delta_free.
+    /// Deltas stay unchanged, CommonInvalidStructure is returned if other_revoc_reg_delta doesn't follow revoc_reg_delta.
+    extern indy_crypto_error_t indy_crypto_cl_issuer_merge_revocation_registry_deltas(const void* revoc_reg_delta,
+                                                                                      const void* other_revoc_reg_delta,
+                                                                                      const void** merged_revoc_reg_delta_p);
+
+    /// Extends capacity of a revocation registry to new_max_cred_num, rev_key_pub and rev_reg are updated in place.
+    /// rev_tails_generator_p generates only added tails, to be appended to the tails file.
+    extern indy_crypto_error_t indy_crypto_cl_issuer_resize_revocation_registry(const void* credential_pub_key,
+                                                                                const void* rev_key_pub,
+                                                                                const void* rev_key_priv,
+                                                                                const void* rev_reg,
+                                                                                uint32_t max_cred_num,
+                                                                                uint32_t new_max_cred_num,
+                                                                                bool issuance_by_default,
+                                                                                const void** rev_reg_delta_p,
+                                                                                const void** rev_tails_generator_p);
+
+    /// Adds two sided range predicate min <= attr_value <= max as pair of GE and LE predicates.
+    extern indy_crypto_error_t indy_crypto_cl_sub_proof_request_builder_add_range_predicate(const void* sub_proof_request_builder,
+                                                                                        const char* attr_name,
+                                                                                        int32_t min,
+                                                                                        int32_t max);
+
+    /// Index of the tail the generator generates next, a checkpoint for indy_crypto_cl_tails_generator_seek.
+    extern indy_crypto_error_t indy_crypto_cl_tails_generator_index(const void* rev_tails_generator,
+                                                                    uint32_t* index_p);
+    extern indy_crypto_error_t indy_crypto_cl_tails_generator_seek(const void* rev_tails_generator,
+                                                                   uint32_t index);
+
+    /// Writes next buf_len / 128 tails as bytes representation of 128 bytes each (as stored in tails files).
+    /// tails_count_p is set to number of tails written, 0 if all tails are generated.
+    extern indy_crypto_error_t indy_crypto_cl_tails_generator_next_chunk(const void* rev_tails_generator,
+                                                                         unsigned char* buf,
+                                                                         size_t buf_len,
+                                                                         size_t* tails_count_p);
+
+    /// Verifies proof like indy_crypto_cl_proof_verifier_verify and deallocates proof verifier.
+    /// report_json_p contains json report {"valid", "sub_proofs": [{"failures": [...]}], "challenge_mismatch"},
+    /// where every failure is tagged by "check": revealed_attrs, eq_proof, predicate or non_revocation.
+    extern indy_crypto_error_t indy_crypto_cl_proof_verifier_verify_with_diagnostics(const void* proof_verifier,
+                                                                                     const void* proof,
+                                                                                     const void* nonce,
+                                                                                     const char** report_json_p);
+
 #ifdef __cplusplus
 }
 #endif
@@ -51,6 +51,26 @@ typedef enum
 
     // IO Error
     CommonIOError = 114,
+
+    // Operation was cancelled by a callback of the library caller
+    CommonCancelled = 119,
+
+    // Crypto errors
+
+    // Point is not on the curve or has invalid encoding
+    CryptoInvalidPoint = 200,
+
+    // Signature (or proof of possession) is malformed
+    CryptoInvalidSignature = 201,
+
+    // Sign or verification key is malformed
+    CryptoInvalidKey = 202,
+
+    // Random number generator failed to produce entropy
+    CryptoRngFailure = 203,
+
+    // Fewer signature shares than the threshold were given to combine
+    CryptoNotEnoughShares = 204,
 } indy_crypto_error_t;
 
 #endif
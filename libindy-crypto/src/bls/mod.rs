@@ -1,19 +1,32 @@
-use crate::errors::IndyCryptoError;
+use crate::errors::{IndyCryptoError, ResultExt};
 use crate::pair::{GroupOrderElement, PointG2, PointG1, Pair};
+use crate::utils::config;
+use crate::utils::zeroize::zeroize;
 
 use crate::sha2::{Sha256, Digest};
 use crate::sha3::Keccak256;
 
+use std::collections::HashSet;
+use std::thread;
+
+pub mod threshold;
+
 /// BLS generator point.
 /// BLS algorithm requires choosing of generator point that must be known to all parties.
 /// The most of BLS methods require generator to be provided.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Generator {
     point: PointG2,
     bytes: Vec<u8>
 }
 
 impl Generator {
+    /// Size of bytes representation.
+    pub const BYTES_SIZE: usize = PointG2::BYTES_REPR_SIZE;
+
+    /// Size of compressed bytes representation.
+    pub const COMPRESSED_BYTES_SIZE: usize = PointG2::COMPRESSED_BYTES_REPR_SIZE;
+
     /// Creates and returns random generator point that satisfies BLS algorithm requirements.
     ///
     /// # Example
@@ -30,6 +43,26 @@ impl Generator {
         })
     }
 
+    /// Returns the standard generator: the fixed generator of G2 of the curve.
+    ///
+    /// Unlike `new` it's the same point on every call and in every build, so parties don't
+    /// have to exchange the generator before they can verify each other's signatures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::Generator;
+    /// let gen = Generator::standard().unwrap();
+    /// assert_eq!(gen.as_bytes(), Generator::standard().unwrap().as_bytes());
+    /// ```
+    pub fn standard() -> Result<Generator, IndyCryptoError> {
+        let point = PointG2::base();
+        Ok(Generator {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
     /// Returns BLS generator point bytes representation.
     ///
     /// # Example
@@ -57,11 +90,35 @@ impl Generator {
     pub fn from_bytes(bytes: &[u8]) -> Result<Generator, IndyCryptoError> {
         Ok(
             Generator {
-                point: PointG2::from_bytes(bytes)?,
+                point: PointG2::from_bytes(bytes).context("Invalid generator")?,
                 bytes: bytes.to_vec()
             }
         )
     }
+
+    /// Returns compressed bytes representation of generator, 65 bytes long:
+    /// the sign of y coordinate followed by x coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::Generator;
+    /// let gen = Generator::new().unwrap();
+    /// let bytes = gen.as_compressed_bytes().unwrap();
+    /// assert_eq!(Generator::COMPRESSED_BYTES_SIZE, bytes.len());
+    /// assert_eq!(gen.as_bytes(), Generator::from_compressed_bytes(&bytes).unwrap().as_bytes());
+    /// ```
+    pub fn as_compressed_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        self.point.to_compressed_bytes()
+    }
+
+    /// Creates and returns generator from compressed bytes representation.
+    ///
+    /// Decompressed generator is validated the same way as by `from_bytes`.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Generator, IndyCryptoError> {
+        let point = PointG2::from_compressed_bytes(bytes).context("Invalid generator")?;
+        Generator::from_bytes(&point.to_bytes()?)
+    }
 }
 
 /// BLS sign key.
@@ -72,6 +129,9 @@ pub struct SignKey {
 }
 
 impl SignKey {
+    /// Size of bytes representation of sign keys created by the library.
+    pub const BYTES_SIZE: usize = GroupOrderElement::BYTES_REPR_SIZE;
+
     /// Creates and returns random (or seeded from seed) BLS sign key algorithm requirements.
     ///
     /// # Example
@@ -82,7 +142,7 @@ impl SignKey {
     /// ```
     pub fn new(seed: Option<&[u8]>) -> Result<SignKey, IndyCryptoError> {
         let group_order_element = match seed {
-            Some(seed) => GroupOrderElement::new_from_seed(seed)?,
+            Some(seed) => GroupOrderElement::new_from_seed(seed).context("Invalid seed")?,
             _ => GroupOrderElement::new()?
         };
 
@@ -92,6 +152,35 @@ impl SignKey {
         })
     }
 
+    /// Creates and returns BLS sign key seeded from seed of any non zero length.
+    ///
+    /// Seeds of `GroupOrderElement::BYTES_REPR_SIZE` bytes are used as is, so they give the same key as `new`.
+    /// Seeds of other length are hashed with SHA-256 to the required size first.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed of the sign key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// SignKey::from_seed(&[1; 16]).unwrap();
+    /// ```
+    pub fn from_seed(seed: &[u8]) -> Result<SignKey, IndyCryptoError> {
+        if seed.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure("Seed is empty".to_string()));
+        }
+
+        if seed.len() == GroupOrderElement::BYTES_REPR_SIZE {
+            return SignKey::new(Some(seed));
+        }
+
+        let mut hasher = Sha256::default();
+        hasher.input(seed);
+        SignKey::new(Some(hasher.result().as_slice()))
+    }
+
     /// Returns BLS sign key bytes representation.
     ///
     /// # Example
@@ -113,11 +202,33 @@ impl SignKey {
     pub fn from_bytes(bytes: &[u8]) -> Result<SignKey, IndyCryptoError> {
         Ok(
             SignKey {
-                group_order_element: GroupOrderElement::from_bytes(bytes)?,
+                group_order_element: GroupOrderElement::from_bytes(bytes)
+                    .map_err(|err| IndyCryptoError::InvalidKey(format!("Invalid sign key: {}", err)))?,
                 bytes: bytes.to_vec()
             }
         )
     }
+
+    /// Overwrites the sign key with zeros. Sign key is zeroized on drop as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::SignKey;
+    /// let mut sign_key = SignKey::new(None).unwrap();
+    /// sign_key.zeroize();
+    /// assert!(sign_key.as_bytes().iter().all(|&b| b == 0));
+    /// ```
+    pub fn zeroize(&mut self) {
+        self.group_order_element.zeroize();
+        zeroize(&mut self.bytes);
+    }
+}
+
+impl Drop for SignKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
 }
 
 /// BLS verification key.
@@ -128,6 +239,12 @@ pub struct VerKey {
 }
 
 impl VerKey {
+    /// Size of bytes representation.
+    pub const BYTES_SIZE: usize = PointG2::BYTES_REPR_SIZE;
+
+    /// Size of compressed bytes representation.
+    pub const COMPRESSED_BYTES_SIZE: usize = PointG2::COMPRESSED_BYTES_REPR_SIZE;
+
     /// Creates and returns BLS ver key that corresponds to sign key.
     ///
     /// # Example
@@ -141,7 +258,7 @@ impl VerKey {
     /// VerKey::new(&gen, &sign_key).unwrap();
     /// ```
     pub fn new(gen: &Generator, sign_key: &SignKey) -> Result<VerKey, IndyCryptoError> {
-        let point = gen.point.mul(&sign_key.group_order_element)?;
+        let point = gen.point.mul_ct(&sign_key.group_order_element)?;
 
         Ok(VerKey {
             point: point,
@@ -168,7 +285,15 @@ impl VerKey {
     /// //TODO: Provide an example!
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<VerKey, IndyCryptoError> {
-        let point = PointG2::from_bytes(bytes)?;
+        VerKey::_from_bytes(bytes, &config::runtime_config())
+    }
+
+    fn _from_bytes(bytes: &[u8], config: &config::RuntimeConfig) -> Result<VerKey, IndyCryptoError> {
+        let point = PointG2::from_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidKey(format!("Invalid verification key: {}", err)))?;
+        if point.is_identity() && config.strict_validation {
+            return Err(IndyCryptoError::InvalidKey("Invalid verification key: identity point".to_string()));
+        }
         Ok(
             VerKey {
                 point,
@@ -176,6 +301,31 @@ impl VerKey {
             }
         )
     }
+
+    /// Returns compressed bytes representation of verification key, 65 bytes long:
+    /// the sign of y coordinate followed by x coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::{Generator, SignKey, VerKey};
+    /// let ver_key = VerKey::new(&Generator::new().unwrap(), &SignKey::new(None).unwrap()).unwrap();
+    /// let bytes = ver_key.as_compressed_bytes().unwrap();
+    /// assert_eq!(VerKey::COMPRESSED_BYTES_SIZE, bytes.len());
+    /// assert_eq!(ver_key.as_bytes(), VerKey::from_compressed_bytes(&bytes).unwrap().as_bytes());
+    /// ```
+    pub fn as_compressed_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        self.point.to_compressed_bytes()
+    }
+
+    /// Creates and returns verification key from compressed bytes representation.
+    ///
+    /// Decompressed verification key is validated the same way as by `from_bytes`.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<VerKey, IndyCryptoError> {
+        let point = PointG2::from_compressed_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidKey(format!("Invalid verification key: {}", err)))?;
+        VerKey::from_bytes(&point.to_bytes()?)
+    }
 }
 
 
@@ -212,6 +362,49 @@ impl ProofOfPossession {
         })
     }
 
+    /// Creates and returns BLS proof of possession of ver key over the challenge (e.g. verifier's nonce).
+    ///
+    /// Signs `len(challenge) || challenge || ver_key`, so the proof can't be replayed for another
+    /// challenge and differs from the static proof of `new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - Challenge issued by verifier, must not be empty
+    /// * `ver_key` - Ver key
+    /// * `sign_key` - Sign key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::{Bls, Generator, SignKey, VerKey, ProofOfPossession};
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let pop = ProofOfPossession::new_for_challenge(b"nonce", &ver_key, &sign_key).unwrap();
+    /// assert!(Bls::verify_proof_of_possession_for_challenge(&pop, b"nonce", &ver_key, &gen).unwrap());
+    /// ```
+    pub fn new_for_challenge(challenge: &[u8], ver_key: &VerKey, sign_key: &SignKey) -> Result<ProofOfPossession, IndyCryptoError> {
+        let message = ProofOfPossession::_challenge_message(challenge, ver_key)?;
+        let point = Bls::_gen_signature(&message, sign_key, Keccak256::default())?;
+
+        Ok(ProofOfPossession {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    fn _challenge_message(challenge: &[u8], ver_key: &VerKey) -> Result<Vec<u8>, IndyCryptoError> {
+        if challenge.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure("Challenge is empty".to_string()));
+        }
+
+        let mut message = Vec::with_capacity(4 + challenge.len() + ver_key.bytes.len());
+        message.extend_from_slice(&(challenge.len() as u32).to_be_bytes());
+        message.extend_from_slice(challenge);
+        message.extend_from_slice(&ver_key.bytes);
+        Ok(message)
+    }
+
     /// Returns BLS proof of possession to bytes representation.
     ///
     /// # Example
@@ -231,7 +424,8 @@ impl ProofOfPossession {
     /// //TODO: Provide an example!
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<ProofOfPossession, IndyCryptoError> {
-        let point = PointG1::from_bytes(bytes)?;
+        let point = PointG1::from_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidSignature(format!("Invalid proof of possession: {}", err)))?;
         Ok(ProofOfPossession {
             point,
             bytes: bytes.to_vec()
@@ -239,14 +433,76 @@ impl ProofOfPossession {
     }
 }
 
-/// BLS signature.
+/// BLS sign key together with its verification key and proof of possession.
 #[derive(Debug, Serialize, Deserialize)]
+pub struct KeyPair {
+    sign_key: SignKey,
+    ver_key: VerKey,
+    proof_of_possession: ProofOfPossession
+}
+
+impl KeyPair {
+    /// Creates and returns random (or seeded from seed) BLS sign key with its verification key
+    /// and proof of possession.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen` - Generator
+    /// * `seed` - Seed of the sign key, see `SignKey::from_seed`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::{Bls, Generator, KeyPair};
+    /// let gen = Generator::new().unwrap();
+    /// let key_pair = KeyPair::new(&gen, None).unwrap();
+    /// assert!(Bls::verify_proof_of_posession(key_pair.proof_of_possession(), key_pair.ver_key(), &gen).unwrap());
+    /// ```
+    pub fn new(gen: &Generator, seed: Option<&[u8]>) -> Result<KeyPair, IndyCryptoError> {
+        let sign_key = match seed {
+            Some(seed) => SignKey::from_seed(seed)?,
+            None => SignKey::new(None)?
+        };
+        let ver_key = VerKey::new(gen, &sign_key)?;
+        let proof_of_possession = ProofOfPossession::new(&ver_key, &sign_key)?;
+
+        Ok(KeyPair {
+            sign_key,
+            ver_key,
+            proof_of_possession
+        })
+    }
+
+    /// Returns BLS sign key of the pair.
+    pub fn sign_key(&self) -> &SignKey {
+        &self.sign_key
+    }
+
+    /// Returns BLS verification key of the pair.
+    pub fn ver_key(&self) -> &VerKey {
+        &self.ver_key
+    }
+
+    /// Returns proof of possession of the verification key.
+    pub fn proof_of_possession(&self) -> &ProofOfPossession {
+        &self.proof_of_possession
+    }
+}
+
+/// BLS signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Signature {
     point: PointG1,
     bytes: Vec<u8>,
 }
 
 impl Signature {
+    /// Size of bytes representation.
+    pub const BYTES_SIZE: usize = PointG1::BYTES_REPR_SIZE;
+
+    /// Size of compressed bytes representation.
+    pub const COMPRESSED_BYTES_SIZE: usize = PointG1::COMPRESSED_BYTES_REPR_SIZE;
+
     /// Returns BLS signature to bytes representation.
     ///
     /// # Example
@@ -266,7 +522,15 @@ impl Signature {
     /// //TODO: Provide an example!
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<Signature, IndyCryptoError> {
-        let point = PointG1::from_bytes(bytes)?;
+        Signature::_from_bytes(bytes, &config::runtime_config())
+    }
+
+    fn _from_bytes(bytes: &[u8], config: &config::RuntimeConfig) -> Result<Signature, IndyCryptoError> {
+        let point = PointG1::from_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidSignature(format!("Invalid signature: {}", err)))?;
+        if point.is_identity() && config.strict_validation {
+            return Err(IndyCryptoError::InvalidSignature("Invalid signature: identity point".to_string()));
+        }
         Ok(
             Signature {
                 point,
@@ -274,6 +538,31 @@ impl Signature {
             }
         )
     }
+
+    /// Returns compressed bytes representation of signature, 33 bytes long:
+    /// the sign of y coordinate followed by x coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::{Bls, SignKey, Signature};
+    /// let signature = Bls::sign(&[1, 2, 3], &SignKey::new(None).unwrap()).unwrap();
+    /// let bytes = signature.as_compressed_bytes().unwrap();
+    /// assert_eq!(Signature::COMPRESSED_BYTES_SIZE, bytes.len());
+    /// assert_eq!(signature.as_bytes(), Signature::from_compressed_bytes(&bytes).unwrap().as_bytes());
+    /// ```
+    pub fn as_compressed_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        self.point.to_compressed_bytes()
+    }
+
+    /// Creates and returns signature from compressed bytes representation.
+    ///
+    /// Decompressed signature is validated the same way as by `from_bytes`.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Signature, IndyCryptoError> {
+        let point = PointG1::from_compressed_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidSignature(format!("Invalid signature: {}", err)))?;
+        Signature::from_bytes(&point.to_bytes()?)
+    }
 }
 
 /// BLS multi signature.
@@ -284,6 +573,12 @@ pub struct MultiSignature {
 }
 
 impl MultiSignature {
+    /// Size of bytes representation.
+    pub const BYTES_SIZE: usize = PointG1::BYTES_REPR_SIZE;
+
+    /// Size of compressed bytes representation.
+    pub const COMPRESSED_BYTES_SIZE: usize = PointG1::COMPRESSED_BYTES_REPR_SIZE;
+
    /// Creates and returns multi signature for provided list of signatures.
    ///
    /// # Arguments
@@ -310,11 +605,30 @@ impl MultiSignature {
    /// MultiSignature::new(&signatures).unwrap();
    /// ```
     pub fn new(signatures: &[&Signature]) -> Result<MultiSignature, IndyCryptoError> {
-        let mut point = PointG1::new_inf()?;
+        config::check_signers(signatures.len())?;
+        let point: PointG1 = signatures.iter().map(|signature| signature.point).sum();
 
-        for signature in signatures {
-            point = point.add(&signature.point)?;
-        }
+        Ok(MultiSignature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Creates and returns multi signature of no signatures, to add signatures to as they arrive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+    ///
+    /// let mut multi_sig = MultiSignature::empty().unwrap();
+    /// multi_sig.add(&signature).unwrap();
+    /// assert_eq!(MultiSignature::new(&[&signature]).unwrap().as_bytes(), multi_sig.as_bytes());
+    /// ```
+    pub fn empty() -> Result<MultiSignature, IndyCryptoError> {
+        let point = PointG1::identity();
 
         Ok(MultiSignature {
             point,
@@ -322,6 +636,45 @@ impl MultiSignature {
         })
     }
 
+    /// Adds signature to the multi signature.
+    ///
+    /// The result is the same as of `new` for the list of all added signatures, in any order.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature to add
+    pub fn add(&mut self, signature: &Signature) -> Result<(), IndyCryptoError> {
+        self._add_point(&signature.point)
+    }
+
+    /// Adds all signatures of other multi signature to the multi signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Multi signature to merge
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let signature1 = Bls::sign(&[1, 2, 3], &SignKey::new(None).unwrap()).unwrap();
+    /// let signature2 = Bls::sign(&[1, 2, 3], &SignKey::new(None).unwrap()).unwrap();
+    ///
+    /// let mut multi_sig = MultiSignature::new(&[&signature1]).unwrap();
+    /// multi_sig.merge(&MultiSignature::new(&[&signature2]).unwrap()).unwrap();
+    /// assert_eq!(MultiSignature::new(&[&signature1, &signature2]).unwrap().as_bytes(), multi_sig.as_bytes());
+    /// ```
+    pub fn merge(&mut self, other: &MultiSignature) -> Result<(), IndyCryptoError> {
+        self._add_point(&other.point)
+    }
+
+    fn _add_point(&mut self, point: &PointG1) -> Result<(), IndyCryptoError> {
+        let point = self.point.add(point)?;
+        self.bytes = point.to_bytes()?;
+        self.point = point;
+        Ok(())
+    }
+
     /// Returns BLS multi signature bytes representation.
     ///
     /// # Example
@@ -341,7 +694,8 @@ impl MultiSignature {
     /// //TODO: Provide an example!
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<MultiSignature, IndyCryptoError> {
-        let point = PointG1::from_bytes(bytes)?;
+        let point = PointG1::from_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidSignature(format!("Invalid multi signature: {}", err)))?;
         Ok(
             MultiSignature {
                 point: point,
@@ -349,93 +703,424 @@ impl MultiSignature {
             }
         )
     }
+
+    /// Returns compressed bytes representation of multi signature, 33 bytes long:
+    /// the sign of y coordinate followed by x coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::{Bls, MultiSignature, SignKey};
+    /// let signature = Bls::sign(&[1, 2, 3], &SignKey::new(None).unwrap()).unwrap();
+    /// let multi_sig = MultiSignature::new(&[&signature]).unwrap();
+    /// let bytes = multi_sig.as_compressed_bytes().unwrap();
+    /// assert_eq!(MultiSignature::COMPRESSED_BYTES_SIZE, bytes.len());
+    /// assert_eq!(multi_sig.as_bytes(), MultiSignature::from_compressed_bytes(&bytes).unwrap().as_bytes());
+    /// ```
+    pub fn as_compressed_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        self.point.to_compressed_bytes()
+    }
+
+    /// Creates and returns multi signature from compressed bytes representation.
+    ///
+    /// Decompressed multi signature is validated the same way as by `from_bytes`.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<MultiSignature, IndyCryptoError> {
+        let point = PointG1::from_compressed_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidSignature(format!("Invalid multi signature: {}", err)))?;
+        MultiSignature::from_bytes(&point.to_bytes()?)
+    }
 }
 
-pub struct Bls {}
+/// Sum of BLS verification keys of signers of the same message.
+///
+/// Verifiers can cache it to check multi signatures of the same signers without adding the keys again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedVerKey {
+    point: PointG2,
+    bytes: Vec<u8>
+}
 
-impl Bls {
-    /// Signs the message and returns signature.
+impl AggregatedVerKey {
+    /// Creates and returns aggregated verification key for provided list of verification keys.
     ///
     /// # Arguments
     ///
-    /// * `message` - Message to sign
-    /// * `sign_key` - Sign key
+    /// * `ver_keys` - List of verification keys
     ///
     /// # Example
     ///
     /// ```
     /// use indy_crypto::bls::*;
-    /// let message = vec![1, 2, 3, 4, 5];
-    /// let sign_key = SignKey::new(None).unwrap();
-    /// Bls::sign(&message, &sign_key).unwrap();
+    /// let gen = Generator::new().unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+    ///
+    /// AggregatedVerKey::new(&[&ver_key1, &ver_key2]).unwrap();
     /// ```
-    pub fn sign(message: &[u8], sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
-        let point = Bls::_gen_signature(message, sign_key, Sha256::default())?;
+    pub fn new(ver_keys: &[&VerKey]) -> Result<AggregatedVerKey, IndyCryptoError> {
+        config::check_signers(ver_keys.len())?;
+        let point: PointG2 = ver_keys.iter().map(|ver_key| ver_key.point).sum();
 
-        Ok(Signature {
+        Ok(AggregatedVerKey {
             point,
             bytes: point.to_bytes()?
         })
     }
 
-    /// Verifies the message signature and returns true - if signature valid or false otherwise.
+    /// Adds verification key to the aggregated verification key.
+    ///
+    /// The result is the same as of `new` for the list of all added verification keys, in any order.
     ///
     /// # Arguments
     ///
-    /// * `signature` - Signature to verify
-    /// * `message` - Message to verify
-    /// * `ver_key` - Verification key
-    /// * `gen` - Generator point
+    /// * `ver_key` - Verification key to add
     ///
     /// # Example
     ///
     /// ```
     /// use indy_crypto::bls::*;
     /// let gen = Generator::new().unwrap();
-    /// let sign_key = SignKey::new(None).unwrap();
-    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
-    /// let message = vec![1, 2, 3, 4, 5];
-    /// let signature = Bls::sign(&message, &sign_key).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
     ///
-    /// let valid = Bls::verify(&signature, &message, &ver_key, &gen).unwrap();
-    /// assert!(valid);
+    /// let mut aggregated = AggregatedVerKey::new(&[&ver_key1]).unwrap();
+    /// aggregated.add(&ver_key2).unwrap();
+    /// assert_eq!(AggregatedVerKey::new(&[&ver_key1, &ver_key2]).unwrap().as_bytes(), aggregated.as_bytes());
     /// ```
-    pub fn verify(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
-        Bls::_verify_signature(&signature.point, message, &ver_key.point, gen, Sha256::default())
+    pub fn add(&mut self, ver_key: &VerKey) -> Result<(), IndyCryptoError> {
+        let point = self.point.add(&ver_key.point)?;
+        self.bytes = point.to_bytes()?;
+        self.point = point;
+        Ok(())
     }
 
-    /// Verifies the proof of possession and returns true - if valid or false otherwise.
-    ///
-    /// # Arguments
-    ///
-    /// * `pop` - Proof of possession
-    /// * `ver_key` - Verification key
-    /// * `gen` - Generator point
+    /// Returns aggregated verification key bytes representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns aggregated verification key from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AggregatedVerKey, IndyCryptoError> {
+        let point = PointG2::from_bytes(bytes)
+            .map_err(|err| IndyCryptoError::InvalidKey(format!("Invalid aggregated verification key: {}", err)))?;
+        Ok(
+            AggregatedVerKey {
+                point,
+                bytes: bytes.to_vec()
+            }
+        )
+    }
+}
+
+/// Hasher of message given in chunks, signs and verifies the same way as `Bls::sign` and `Bls::verify`
+/// do for the whole message, so the message doesn't have to be kept in memory.
+#[derive(Default)]
+pub struct MessageHasher {
+    hasher: Sha256
+}
+
+impl MessageHasher {
+    /// Creates and returns hasher of empty message.
     ///
     /// # Example
     ///
     /// ```
     /// use indy_crypto::bls::*;
-    /// let gen = Generator::new().unwrap();
     /// let sign_key = SignKey::new(None).unwrap();
-    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
-    /// let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
     ///
-    /// let valid = Bls::verify_proof_of_posession(&pop, &ver_key, &gen).unwrap();
-    /// assert!(valid);
+    /// let mut hasher = MessageHasher::new();
+    /// hasher.update(&[1, 2, 3]);
+    /// hasher.update(&[4, 5]);
+    ///
+    /// let signature = hasher.sign(&sign_key).unwrap();
+    /// assert_eq!(Bls::sign(&[1, 2, 3, 4, 5], &sign_key).unwrap().as_bytes(), signature.as_bytes());
     /// ```
-    pub fn verify_proof_of_posession(pop: &ProofOfPossession, ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
-        Bls::_verify_signature(&pop.point, &ver_key.bytes, &ver_key.point, gen, Keccak256::default())
+    pub fn new() -> MessageHasher {
+        MessageHasher::default()
     }
 
-    /// Verifies the message multi signature and returns true - if signature valid or false otherwise.
-    ///
-    /// # Arguments
-    ///
-    /// * `multi_sig` - Multi signature to verify
-    /// * `message` - Message to verify
-    /// * `ver_keys` - List of verification keys
-    /// * `gen` - Generator point
+    /// Appends the chunk to the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.input(chunk);
+    }
+
+    /// Signs the message and returns signature.
+    pub fn sign(self, sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
+        let point = PointG1::from_hash(self.hasher.result().as_slice())?.mul_ct(&sign_key.group_order_element)?;
+
+        Ok(Signature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Verifies the message signature and returns true - if signature valid or false otherwise.
+    pub fn verify(self, signature: &Signature, ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let h = PointG1::from_hash(self.hasher.result().as_slice())?;
+        Ok(Pair::pair(&signature.point, &gen.point)?.eq(&Pair::pair(&h, &ver_key.point)?))
+    }
+}
+
+pub struct Bls {}
+
+impl Bls {
+    /// Signs the message and returns signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `sign_key` - Sign key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// Bls::sign(&message, &sign_key).unwrap();
+    /// ```
+    pub fn sign(message: &[u8], sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
+        config::check_message_size(message)?;
+        let point = Bls::_gen_signature(message, sign_key, Sha256::default())?;
+
+        Ok(Signature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Verifies the message signature and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature = Bls::sign(&message, &sign_key).unwrap();
+    ///
+    /// let valid = Bls::verify(&signature, &message, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        config::check_message_size(message)?;
+        Bls::_verify_signature(&signature.point, message, &ver_key.point, gen, Sha256::default())
+    }
+
+    /// Signs the message hashed with `PointG1::map_to_curve` and returns signature.
+    ///
+    /// Such signatures can be verified only with `verify_v2`.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `sign_key` - Sign key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// Bls::sign_v2(&message, &sign_key).unwrap();
+    /// ```
+    pub fn sign_v2(message: &[u8], sign_key: &SignKey) -> Result<Signature, IndyCryptoError> {
+        let point = Bls::_hash_v2(message)?.mul_ct(&sign_key.group_order_element)?;
+
+        Ok(Signature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Verifies the message signature created by `sign_v2` and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature = Bls::sign_v2(&message, &sign_key).unwrap();
+    ///
+    /// let valid = Bls::verify_v2(&signature, &message, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_v2(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let h = Bls::_hash_v2(message)?;
+        Ok(Pair::pair(&signature.point, &gen.point)?.eq(&Pair::pair(&h, &ver_key.point)?))
+    }
+
+    /// Signs the message under the domain separation tag and returns signature.
+    ///
+    /// The message is hashed with `PointG1::map_to_curve` of `len(dst) || dst || message`, so signatures
+    /// of different domains don't verify for each other. Such signatures can be verified only with
+    /// `verify_with_dst` and `verify_multi_sig_with_dst` for the same tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `sign_key` - Sign key
+    /// * `dst` - Domain separation tag, 1 to 255 bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// Bls::sign_with_dst(&message, &sign_key, b"INDY-BLS-SIG").unwrap();
+    /// ```
+    pub fn sign_with_dst(message: &[u8], sign_key: &SignKey, dst: &[u8]) -> Result<Signature, IndyCryptoError> {
+        let point = Bls::_hash_with_dst(message, dst)?.mul_ct(&sign_key.group_order_element)?;
+
+        Ok(Signature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+
+    /// Verifies the message signature created by `sign_with_dst` under the same domain separation tag
+    /// and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    /// * `dst` - Domain separation tag, 1 to 255 bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature = Bls::sign_with_dst(&message, &sign_key, b"INDY-BLS-SIG").unwrap();
+    ///
+    /// let valid = Bls::verify_with_dst(&signature, &message, &ver_key, &gen, b"INDY-BLS-SIG").unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_with_dst(signature: &Signature, message: &[u8], ver_key: &VerKey, gen: &Generator, dst: &[u8]) -> Result<bool, IndyCryptoError> {
+        let h = Bls::_hash_with_dst(message, dst)?;
+        Ok(Pair::pair(&signature.point, &gen.point)?.eq(&Pair::pair(&h, &ver_key.point)?))
+    }
+
+    /// Verifies the message multi signature of signatures created by `sign_with_dst` under the same
+    /// domain separation tag and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_keys` - List of verification keys
+    /// * `gen` - Generator point
+    /// * `dst` - Domain separation tag, 1 to 255 bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature1 = Bls::sign_with_dst(&message, &sign_key1, b"INDY-BLS-SIG").unwrap();
+    /// let signature2 = Bls::sign_with_dst(&message, &sign_key2, b"INDY-BLS-SIG").unwrap();
+    /// let multi_sig = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+    ///
+    /// let valid = Bls::verify_multi_sig_with_dst(&multi_sig, &message, &[&ver_key1, &ver_key2], &gen, b"INDY-BLS-SIG").unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_multi_sig_with_dst(multi_sig: &MultiSignature, message: &[u8], ver_keys: &[&VerKey], gen: &Generator, dst: &[u8]) -> Result<bool, IndyCryptoError> {
+        let aggregated_ver_key = AggregatedVerKey::new(ver_keys)?;
+        let h = Bls::_hash_with_dst(message, dst)?;
+        Ok(Pair::pair(&multi_sig.point, &gen.point)?.eq(&Pair::pair(&h, &aggregated_ver_key.point)?))
+    }
+
+    /// Verifies the proof of possession and returns true - if valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `pop` - Proof of possession
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+    ///
+    /// let valid = Bls::verify_proof_of_posession(&pop, &ver_key, &gen).unwrap();
+    /// assert!(valid);
+    /// ```
+    pub fn verify_proof_of_posession(pop: &ProofOfPossession, ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        Bls::_verify_signature(&pop.point, &ver_key.bytes, &ver_key.point, gen, Keccak256::default())
+    }
+
+    /// Verifies the proof of possession created by `ProofOfPossession::new_for_challenge` for the challenge
+    /// and returns true - if valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `pop` - Proof of possession
+    /// * `challenge` - Challenge issued by verifier, must not be empty
+    /// * `ver_key` - Verification key
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    /// let sign_key = SignKey::new(None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    /// let pop = ProofOfPossession::new_for_challenge(b"nonce of verifier", &ver_key, &sign_key).unwrap();
+    ///
+    /// assert!(Bls::verify_proof_of_possession_for_challenge(&pop, b"nonce of verifier", &ver_key, &gen).unwrap());
+    /// assert!(!Bls::verify_proof_of_possession_for_challenge(&pop, b"other nonce", &ver_key, &gen).unwrap());
+    /// ```
+    pub fn verify_proof_of_possession_for_challenge(pop: &ProofOfPossession, challenge: &[u8], ver_key: &VerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let message = ProofOfPossession::_challenge_message(challenge, ver_key)?;
+        Bls::_verify_signature(&pop.point, &message, &ver_key.point, gen, Keccak256::default())
+    }
+
+    /// Verifies the message multi signature and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `message` - Message to verify
+    /// * `ver_keys` - List of verification keys
+    /// * `gen` - Generator point
     ///
     /// # Example
     ///
@@ -470,20 +1155,250 @@ impl Bls {
     pub fn verify_multi_sig(multi_sig: &MultiSignature, message: &[u8], ver_keys: &[&VerKey], gen: &Generator) -> Result<bool, IndyCryptoError> {
         // Since each signer (identified by a Verkey) has signed the same message, the public keys
         // can be added together to form the aggregated verkey
-        let mut aggregated_verkey = PointG2::new_inf()?;
-        for ver_key in ver_keys {
-            aggregated_verkey = aggregated_verkey.add(&ver_key.point)?;
+        let aggregated_ver_key = AggregatedVerKey::new(ver_keys)?;
+
+        // TODO: Expose verify_multi_sig_with_aggregated_ver_key using the C API.
+        Bls::verify_multi_sig_with_aggregated_ver_key(multi_sig, message, &aggregated_ver_key, gen)
+    }
+
+    /// Verifies the message multi signature against a cached aggregated verification key
+    /// and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `message` - Message to verify
+    /// * `aggregated_ver_key` - Aggregated verification key of the signers
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    /// let aggregated_ver_key = AggregatedVerKey::new(&[&ver_key1, &ver_key2]).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+    /// let multi_sig = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+    ///
+    /// let valid = Bls::verify_multi_sig_with_aggregated_ver_key(&multi_sig, &message, &aggregated_ver_key, &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_multi_sig_with_aggregated_ver_key(multi_sig: &MultiSignature, message: &[u8], aggregated_ver_key: &AggregatedVerKey, gen: &Generator) -> Result<bool, IndyCryptoError> {
+        config::check_message_size(message)?;
+        Bls::_verify_signature(&multi_sig.point, message, &aggregated_ver_key.point, gen, Sha256::default())
+    }
+
+    /// Verifies multi signature over distinct messages, one per signer, and returns true - if signature valid or false otherwise.
+    ///
+    /// Checks e(multi_sig, gen) == e(H(m_1), vk_1) * ... * e(H(m_n), vk_n). Messages must be distinct:
+    /// for a shared message a signer could pick its key as a function of the keys of others and forge
+    /// the multi signature alone, so signers of the same message have to be verified with `verify_multi_sig`
+    /// after their proofs of possession.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature to verify
+    /// * `items` - List of (message, verification key) of the signers
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message1 = vec![1, 2, 3, 4, 5];
+    /// let message2 = vec![6, 7, 8, 9, 10];
+    /// let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+    /// let multi_sig = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+    ///
+    /// let items = vec![
+    ///   (message1.as_slice(), &ver_key1),
+    ///   (message2.as_slice(), &ver_key2)
+    /// ];
+    ///
+    /// let valid = Bls::verify_aggregate(&multi_sig, &items, &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_aggregate(multi_sig: &MultiSignature, items: &[(&[u8], &VerKey)], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        if items.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure("List of messages to verify is empty".to_string()));
+        }
+        config::check_signers(items.len())?;
+        for &(message, _) in items {
+            config::check_message_size(message)?;
         }
 
-        // TODO: Add a new method that takes a message and an aggregated verkey and expose using
-        // the C API. Verifiers can thus cache the aggregated verkey and avoid several EC point additions.
-        // The code below should be moved to such method.
+        let mut messages = HashSet::new();
+        if let Some(index) = items.iter().position(|&(message, _)| !messages.insert(message)) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Message at index {} is not distinct", index)));
+        }
 
-        Bls::_verify_signature(&multi_sig.point, message, &aggregated_verkey, gen, Sha256::default())
+        let mut pairs: Option<Pair> = None;
+        for &(message, ver_key) in items {
+            let pair = Pair::pair(&Bls::_hash(message, Sha256::default())?, &ver_key.point)?;
+            pairs = Some(match pairs {
+                Some(pairs) => pairs.mul(&pair)?,
+                None => pair
+            });
+        }
+
+        let pairs = pairs.ok_or_else(|| IndyCryptoError::InvalidState("Aggregate pairing is absent".to_string()))?;
+        Ok(Pair::pair(&multi_sig.point, &gen.point)?.eq(&pairs))
+    }
+
+    /// Verifies a batch of independent message signatures at once and returns true - if all signatures valid or false otherwise.
+    ///
+    /// Signatures are combined with random coefficients, so the check costs one pairing per item plus one
+    /// instead of two pairings per item. Result doesn't tell which signature is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - List of (signature, message, verification key) to verify
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let sign_key1 = SignKey::new(None).unwrap();
+    /// let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+    /// let sign_key2 = SignKey::new(None).unwrap();
+    /// let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+    ///
+    /// let message1 = vec![1, 2, 3, 4, 5];
+    /// let message2 = vec![6, 7, 8, 9, 10];
+    ///
+    /// let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+    /// let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+    ///
+    /// let items = vec![
+    ///   (&signature1, message1.as_slice(), &ver_key1),
+    ///   (&signature2, message2.as_slice(), &ver_key2)
+    /// ];
+    ///
+    /// let valid = Bls::verify_batch(&items, &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_batch(items: &[(&Signature, &[u8], &VerKey)], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        for &(_, message, _) in items {
+            config::check_message_size(message)?;
+        }
+        let items: Vec<(&PointG1, &[u8], &PointG2)> = items
+            .iter()
+            .map(|&(signature, message, ver_key)| (&signature.point, message, &ver_key.point))
+            .collect();
+        Bls::_verify_batch::<Sha256>(&items, gen, &config::runtime_config())
+    }
+
+    /// Verifies a batch of proofs of possession at once and returns true - if all proofs valid or false otherwise.
+    ///
+    /// Uses the same random coefficients combination as `verify_batch`, so result doesn't tell which proof is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - List of (proof of possession, verification key) to verify
+    /// * `gen` - Generator point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// let gen = Generator::new().unwrap();
+    ///
+    /// let key_pair1 = KeyPair::new(&gen, None).unwrap();
+    /// let key_pair2 = KeyPair::new(&gen, None).unwrap();
+    ///
+    /// let items = vec![
+    ///   (key_pair1.proof_of_possession(), key_pair1.ver_key()),
+    ///   (key_pair2.proof_of_possession(), key_pair2.ver_key())
+    /// ];
+    ///
+    /// let valid = Bls::verify_proof_of_possession_batch(&items, &gen).unwrap();
+    /// assert!(valid)
+    /// ```
+    pub fn verify_proof_of_possession_batch(items: &[(&ProofOfPossession, &VerKey)], gen: &Generator) -> Result<bool, IndyCryptoError> {
+        let items: Vec<(&PointG1, &[u8], &PointG2)> = items
+            .iter()
+            .map(|&(pop, ver_key)| (&pop.point, ver_key.bytes.as_slice(), &ver_key.point))
+            .collect();
+        Bls::_verify_batch::<Keccak256>(&items, gen, &config::runtime_config())
+    }
+
+    fn _verify_batch<T>(items: &[(&PointG1, &[u8], &PointG2)], gen: &Generator, config: &config::RuntimeConfig) -> Result<bool, IndyCryptoError> where T: Digest + Default {
+        if items.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure("Batch to verify is empty".to_string()));
+        }
+        config::check_signers(items.len())?;
+
+        let threads = config.verification_threads.min(items.len());
+        let parts = if threads > 1 && cfg!(not(target_arch = "wasm32")) {
+            let chunk_size = items.len().div_ceil(threads);
+            thread::scope(|scope| {
+                let handles: Vec<_> = items.chunks(chunk_size)
+                    .map(|chunk| scope.spawn(move || Bls::_combine_batch::<T>(chunk)))
+                    .collect();
+                handles.into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|_|
+                        Err(IndyCryptoError::InvalidState("Batch verification thread panicked".to_string()))))
+                    .collect::<Result<Vec<(PointG1, Pair)>, IndyCryptoError>>()
+            })?
+        } else {
+            vec![Bls::_combine_batch::<T>(items)?]
+        };
+
+        let mut parts = parts.into_iter();
+        let (mut signatures, mut messages) = parts.next()
+            .ok_or_else(|| IndyCryptoError::InvalidState("Batch pairing is absent".to_string()))?;
+        for (part_signatures, part_messages) in parts {
+            signatures = signatures.add(&part_signatures)?;
+            messages = messages.mul(&part_messages)?;
+        }
+
+        Ok(Pair::pair(&signatures, &gen.point)?.eq(&messages))
+    }
+
+    /// Combines non empty part of the batch to the sum of signatures and the product of message pairings,
+    /// both taken with the same random coefficients.
+    fn _combine_batch<T>(items: &[(&PointG1, &[u8], &PointG2)]) -> Result<(PointG1, Pair), IndyCryptoError> where T: Digest + Default {
+        let mut signatures = PointG1::new_inf()?;
+        let mut messages: Option<Pair> = None;
+
+        for &(signature, message, ver_key) in items {
+            // Coefficients and all the points are public, so variable time multiplication is fine here
+            let coefficient = GroupOrderElement::new()?;
+            signatures = signatures.add(&signature.mul_vartime(&coefficient)?)?;
+
+            let h = Bls::_hash(message, T::default())?.mul_vartime(&coefficient)?;
+            let pair = Pair::pair(&h, ver_key)?;
+            messages = Some(match messages {
+                Some(messages) => messages.mul(&pair)?,
+                None => pair
+            });
+        }
+
+        let messages = messages.ok_or_else(|| IndyCryptoError::InvalidState("Batch pairing is absent".to_string()))?;
+        Ok((signatures, messages))
     }
 
     fn _gen_signature<T>(message: &[u8], sign_key: &SignKey, hasher: T) -> Result<PointG1, IndyCryptoError> where T: Digest {
-        Bls::_hash(message, hasher)?.mul(&sign_key.group_order_element)
+        Bls::_hash(message, hasher)?.mul_ct(&sign_key.group_order_element)
     }
 
     pub fn _verify_signature<T>(signature: &PointG1, message: &[u8], ver_key: &PointG2, gen: &Generator, hasher: T) -> Result<bool, IndyCryptoError> where T: Digest {
@@ -491,21 +1406,77 @@ impl Bls {
         Ok(Pair::pair(&signature, &gen.point)?.eq(&Pair::pair(&h, &ver_key)?))
     }
 
+    fn _hash_v2(message: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        config::check_message_size(message)?;
+        PointG1::map_to_curve(message)
+    }
+
+    fn _hash_with_dst(message: &[u8], dst: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        config::check_message_size(message)?;
+        if dst.is_empty() || dst.len() > u8::MAX as usize {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid len of domain separation tag: expected 1 to {} bytes, actual {}", u8::MAX, dst.len())));
+        }
+
+        let mut bytes = Vec::with_capacity(1 + dst.len() + message.len());
+        bytes.push(dst.len() as u8);
+        bytes.extend_from_slice(dst);
+        bytes.extend_from_slice(message);
+        PointG1::map_to_curve(&bytes)
+    }
+
     fn _hash<T>(message: &[u8], mut hasher: T) -> Result<PointG1, IndyCryptoError> where T: Digest {
         hasher.input(message);
         Ok(PointG1::from_hash(hasher.result().as_slice())?)
     }
 }
 
+// Deserialized objects keep `point` and `bytes` apart, the wasm layer checks that they agree
+#[cfg(feature = "wasm")]
+macro_rules! impl_point_bytes {
+    ($($type:ident),*) => {$(
+        impl $type {
+            pub(crate) fn point_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+                self.point.to_bytes()
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "wasm")]
+impl_point_bytes!(Generator, VerKey, AggregatedVerKey, Signature, MultiSignature, ProofOfPossession);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pair::PairMocksHelper;
+    use crate::errors::ErrorKind;
+
+    const STANDARD_GENERATOR_HEX: &str = "061a10bb519eb62feb8d8c7e8c61edb6a4648bbb4898bf0d91ee4224c803fb2b0516aaf9ba737833310aa78c5982aa5b1f4d746bae3784b70d8c34c1e7d54cf3021897a06baf93439a90e096698c822329bd0ae6bdbe09bd19f0e07891cd2b9a0ebb2b0e7c8b15268f6d4456f5f38d37b09006ffd739c9578a2d1aec6b3ace9b";
 
     #[test]
     fn generator_new_works() {
         Generator::new().unwrap();
     }
 
+    #[test]
+    fn generator_standard_works() {
+        let gen = Generator::standard().unwrap();
+        // x and y of the BN254 G2 generator, the bytes must not change between builds
+        assert_eq!(STANDARD_GENERATOR_HEX, crate::utils::hex::to_hex(gen.as_bytes()));
+        assert_eq!(gen.as_bytes(), Generator::from_bytes(gen.as_bytes()).unwrap().as_bytes());
+    }
+
+    #[test]
+    fn generator_standard_works_for_signatures() {
+        let gen = Generator::standard().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+        assert!(Bls::verify(&signature, &[1, 2, 3], &ver_key, &Generator::standard().unwrap()).unwrap());
+    }
+
     #[test]
     fn sign_key_new_works() {
         SignKey::new(None).unwrap();
@@ -517,6 +1488,29 @@ mod tests {
         SignKey::new(Some(&seed)).unwrap();
     }
 
+    #[test]
+    fn sign_key_from_seed_works() {
+        let seed16 = vec![1; 16];
+        let seed32 = vec![1; 32];
+        let seed64 = vec![1; 64];
+
+        let sign_key16 = SignKey::from_seed(&seed16).unwrap();
+        let sign_key32 = SignKey::from_seed(&seed32).unwrap();
+        let sign_key64 = SignKey::from_seed(&seed64).unwrap();
+
+        assert_eq!(sign_key16.as_bytes(), SignKey::from_seed(&seed16).unwrap().as_bytes());
+        assert_eq!(sign_key32.as_bytes(), SignKey::new(Some(&seed32)).unwrap().as_bytes());
+        assert_ne!(sign_key16.as_bytes(), sign_key32.as_bytes());
+        assert_ne!(sign_key32.as_bytes(), sign_key64.as_bytes());
+        assert_ne!(sign_key16.as_bytes(), sign_key64.as_bytes());
+    }
+
+    #[test]
+    fn sign_key_from_seed_works_for_empty_seed() {
+        let res = SignKey::from_seed(&[]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
     #[test]
     fn ver_key_new_works() {
         let gen = Generator::new().unwrap();
@@ -532,6 +1526,25 @@ mod tests {
         ProofOfPossession::new(&ver_key, &sign_key).unwrap();
     }
 
+    #[test]
+    fn key_pair_new_works() {
+        let gen = Generator::new().unwrap();
+        let key_pair = KeyPair::new(&gen, None).unwrap();
+
+        assert!(Bls::verify_proof_of_posession(key_pair.proof_of_possession(), key_pair.ver_key(), &gen).unwrap());
+    }
+
+    #[test]
+    fn key_pair_new_works_for_seed() {
+        let gen = Generator::new().unwrap();
+        let seed = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 2, 3, 4, 5, 6, 7, 8, 9, 10, 21, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31, 32];
+        let key_pair = KeyPair::new(&gen, Some(&seed)).unwrap();
+        let sign_key = SignKey::new(Some(&seed)).unwrap();
+
+        assert_eq!(key_pair.sign_key().as_bytes(), sign_key.as_bytes());
+        assert_eq!(key_pair.ver_key().as_bytes(), VerKey::new(&gen, &sign_key).unwrap().as_bytes());
+    }
+
     #[test]
     fn bls_sign_works() {
         let sign_key = SignKey::new(None).unwrap();
@@ -558,6 +1571,43 @@ mod tests {
         MultiSignature::new(&signatures).unwrap();
     }
 
+    #[test]
+    fn multi_signature_add_and_merge_work() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let signatures: Vec<Signature> = (0..4)
+            .map(|_| Bls::sign(&message, &SignKey::new(None).unwrap()).unwrap())
+            .collect();
+
+        let mut multi_sig1 = MultiSignature::empty().unwrap();
+        multi_sig1.add(&signatures[2]).unwrap();
+        multi_sig1.add(&signatures[0]).unwrap();
+
+        let mut multi_sig2 = MultiSignature::new(&[&signatures[3]]).unwrap();
+        multi_sig2.merge(&MultiSignature::empty().unwrap()).unwrap();
+        multi_sig2.add(&signatures[1]).unwrap();
+        multi_sig1.merge(&multi_sig2).unwrap();
+
+        let expected = MultiSignature::new(&signatures.iter().collect::<Vec<_>>()).unwrap();
+        assert_eq!(expected.as_bytes(), multi_sig1.as_bytes());
+        assert_eq!(expected.as_bytes(), MultiSignature::from_bytes(multi_sig1.as_bytes()).unwrap().as_bytes());
+    }
+
+    #[test]
+    fn multi_signature_empty_works() {
+        let empty = MultiSignature::empty().unwrap();
+
+        assert_eq!(MultiSignature::new(&[]).unwrap().as_bytes(), empty.as_bytes());
+        assert_eq!(empty.as_bytes(), MultiSignature::from_bytes(empty.as_bytes()).unwrap().as_bytes());
+
+        let json = serde_json::to_string(&empty).unwrap();
+        let empty: MultiSignature = serde_json::from_str(&json).unwrap();
+        let signature = Bls::sign(&[1, 2, 3], &SignKey::new(None).unwrap()).unwrap();
+        let mut multi_sig = empty;
+        multi_sig.add(&signature).unwrap();
+        assert_eq!(MultiSignature::new(&[&signature]).unwrap().as_bytes(), multi_sig.as_bytes());
+    }
+
     #[test]
     fn verify_works() {
         let message = vec![1, 2, 3, 4, 5];
@@ -571,6 +1621,24 @@ mod tests {
         assert!(valid)
     }
 
+    #[test]
+    fn verify_works_for_empty_message() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign(&[], &sign_key).unwrap();
+        assert!(Bls::verify(&signature, &[], &ver_key, &gen).unwrap());
+        assert!(!Bls::verify(&signature, &[0], &ver_key, &gen).unwrap());
+
+        let multi_sig = MultiSignature::new(&[&signature]).unwrap();
+        assert!(Bls::verify_multi_sig(&multi_sig, &[], &[&ver_key], &gen).unwrap());
+
+        let signature_v2 = Bls::sign_v2(&[], &sign_key).unwrap();
+        assert!(Bls::verify_v2(&signature_v2, &[], &ver_key, &gen).unwrap());
+        assert_ne!(signature.as_bytes(), signature_v2.as_bytes());
+    }
+
     #[test]
     fn verify_pop_works() {
         let gen = Generator::new().unwrap();
@@ -582,6 +1650,34 @@ mod tests {
         assert!(valid)
     }
 
+    #[test]
+    fn verify_pop_for_challenge_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let pop = ProofOfPossession::new_for_challenge(b"nonce1", &ver_key, &sign_key).unwrap();
+
+        assert!(Bls::verify_proof_of_possession_for_challenge(&pop, b"nonce1", &ver_key, &gen).unwrap());
+        assert!(!Bls::verify_proof_of_possession_for_challenge(&pop, b"nonce2", &ver_key, &gen).unwrap());
+        assert!(!Bls::verify_proof_of_posession(&pop, &ver_key, &gen).unwrap());
+
+        let static_pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+        assert!(!Bls::verify_proof_of_possession_for_challenge(&static_pop, b"nonce1", &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_pop_for_challenge_works_for_empty_challenge() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        assert_eq!(ErrorKind::InvalidStructure, ProofOfPossession::new_for_challenge(&[], &ver_key, &sign_key).unwrap_err().kind());
+
+        let pop = ProofOfPossession::new(&ver_key, &sign_key).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure,
+                   Bls::verify_proof_of_possession_for_challenge(&pop, &[], &ver_key, &gen).unwrap_err().kind());
+    }
+
     #[test]
     fn verify_works_for_invalid_message() {
         let message = vec![1, 2, 3, 4, 5];
@@ -639,6 +1735,241 @@ mod tests {
         assert!(valid)
     }
 
+    #[test]
+    fn verify_multi_sig_with_aggregated_ver_key_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let aggregated_ver_key = AggregatedVerKey::new(&[&ver_key1, &ver_key2]).unwrap();
+        let aggregated_ver_key = AggregatedVerKey::from_bytes(aggregated_ver_key.as_bytes()).unwrap();
+
+        for message in &[vec![1, 2, 3], vec![4, 5, 6]] {
+            let signature1 = Bls::sign(message, &sign_key1).unwrap();
+            let signature2 = Bls::sign(message, &sign_key2).unwrap();
+            let multi_signature = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+
+            assert!(Bls::verify_multi_sig_with_aggregated_ver_key(&multi_signature, message, &aggregated_ver_key, &gen).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_multi_sig_with_aggregated_ver_key_works_for_missed_signer() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let ver_key2 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+
+        let aggregated_ver_key = AggregatedVerKey::new(&[&ver_key1, &ver_key2]).unwrap();
+        let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+        let multi_signature = MultiSignature::new(&[&signature1]).unwrap();
+
+        assert!(!Bls::verify_multi_sig_with_aggregated_ver_key(&multi_signature, &message, &aggregated_ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn aggregated_ver_key_add_works() {
+        let gen = Generator::new().unwrap();
+        let ver_keys: Vec<VerKey> = (0..3)
+            .map(|_| VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap())
+            .collect();
+
+        let mut aggregated_ver_key = AggregatedVerKey::new(&[]).unwrap();
+        aggregated_ver_key.add(&ver_keys[2]).unwrap();
+        aggregated_ver_key.add(&ver_keys[0]).unwrap();
+        aggregated_ver_key.add(&ver_keys[1]).unwrap();
+
+        let expected = AggregatedVerKey::new(&ver_keys.iter().collect::<Vec<_>>()).unwrap();
+        assert_eq!(expected.as_bytes(), aggregated_ver_key.as_bytes());
+    }
+
+    #[test]
+    fn add_works_for_empty_aggregates() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+
+        // identity point of empty aggregates isn't validated on add
+        let mut multi_sig = MultiSignature::empty().unwrap();
+        multi_sig.add(&signature).unwrap();
+        let mut aggregated_ver_key = AggregatedVerKey::new(&[]).unwrap();
+        aggregated_ver_key.add(&ver_key).unwrap();
+
+        assert_eq!(signature.as_bytes(), multi_sig.as_bytes());
+        assert_eq!(ver_key.as_bytes(), aggregated_ver_key.as_bytes());
+    }
+
+    #[test]
+    fn aggregated_ver_key_from_bytes_works_for_invalid_len() {
+        let res = AggregatedVerKey::from_bytes(&[1, 2, 3]);
+        assert_eq!(ErrorKind::InvalidKey, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn ver_key_from_bytes_works_for_off_curve_point() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let mut bytes = VerKey::new(&gen, &sign_key).unwrap().as_bytes().to_vec();
+        bytes[10] ^= 0x01;
+
+        let res = VerKey::from_bytes(&bytes);
+        assert_eq!(ErrorKind::InvalidKey, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_compressed_bytes_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let signature = Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+        let multi_sig = MultiSignature::new(&[&signature]).unwrap();
+
+        let bytes = gen.as_compressed_bytes().unwrap();
+        assert_eq!(gen.as_bytes(), Generator::from_compressed_bytes(&bytes).unwrap().as_bytes());
+        let bytes = ver_key.as_compressed_bytes().unwrap();
+        assert_eq!(ver_key.as_bytes(), VerKey::from_compressed_bytes(&bytes).unwrap().as_bytes());
+        let bytes = signature.as_compressed_bytes().unwrap();
+        assert_eq!(signature.as_bytes(), Signature::from_compressed_bytes(&bytes).unwrap().as_bytes());
+        let bytes = multi_sig.as_compressed_bytes().unwrap();
+        assert_eq!(multi_sig.as_bytes(), MultiSignature::from_compressed_bytes(&bytes).unwrap().as_bytes());
+    }
+
+    #[test]
+    fn from_compressed_bytes_works_for_invalid_len() {
+        let bytes = Bls::sign(&[1, 2, 3], &SignKey::new(None).unwrap()).unwrap().as_compressed_bytes().unwrap();
+
+        let res = Signature::from_compressed_bytes(&bytes[..bytes.len() - 1]);
+        assert_eq!(IndyCryptoError::InvalidSignature("Invalid signature: Invalid point: Invalid len of compressed bytes representation".to_string()), res.unwrap_err());
+        assert_eq!(ErrorKind::InvalidKey, VerKey::from_compressed_bytes(&bytes).unwrap_err().kind());
+    }
+
+    #[test]
+    fn signature_from_bytes_works_for_invalid_len() {
+        let sign_key = SignKey::new(None).unwrap();
+        let bytes = Bls::sign(&[1, 2, 3], &sign_key).unwrap().as_bytes().to_vec();
+
+        let res = Signature::from_bytes(&bytes[..bytes.len() - 1]);
+        assert_eq!(IndyCryptoError::InvalidSignature("Invalid signature: Invalid point: Invalid len of bytes representation".to_string()), res.unwrap_err());
+    }
+
+    #[test]
+    fn verify_v2_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign_v2(&message, &sign_key).unwrap();
+        assert!(Bls::verify_v2(&signature, &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_v2_works_for_v1_signature() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+        assert!(!Bls::verify_v2(&signature, &message, &ver_key, &gen).unwrap());
+        assert!(!Bls::verify(&Bls::sign_v2(&message, &sign_key).unwrap(), &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_with_dst_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign_with_dst(&message, &sign_key, b"DST-A").unwrap();
+        assert!(Bls::verify_with_dst(&signature, &message, &ver_key, &gen, b"DST-A").unwrap());
+        assert!(!Bls::verify_with_dst(&signature, &message, &ver_key, &gen, b"DST-B").unwrap());
+        assert!(!Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+        assert!(!Bls::verify_v2(&signature, &message, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_with_dst_works_for_tag_and_message_boundary() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+
+        let signature = Bls::sign_with_dst(b"BC", &sign_key, b"A").unwrap();
+        assert!(!Bls::verify_with_dst(&signature, b"C", &ver_key, &gen, b"AB").unwrap());
+    }
+
+    #[test]
+    fn verify_multi_sig_with_dst_works() {
+        let message = vec![1, 2, 3, 4, 5];
+
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let multi_sig = MultiSignature::new(&[
+            &Bls::sign_with_dst(&message, &sign_key1, b"DST-A").unwrap(),
+            &Bls::sign_with_dst(&message, &sign_key2, b"DST-A").unwrap()
+        ]).unwrap();
+
+        assert!(Bls::verify_multi_sig_with_dst(&multi_sig, &message, &[&ver_key1, &ver_key2], &gen, b"DST-A").unwrap());
+        assert!(!Bls::verify_multi_sig_with_dst(&multi_sig, &message, &[&ver_key1, &ver_key2], &gen, b"DST-B").unwrap());
+    }
+
+    #[test]
+    fn sign_with_dst_works_for_invalid_tag_len() {
+        let sign_key = SignKey::new(None).unwrap();
+
+        assert_eq!(ErrorKind::InvalidStructure, Bls::sign_with_dst(&[1, 2, 3], &sign_key, &[]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, Bls::sign_with_dst(&[1, 2, 3], &sign_key, &[1; 256]).unwrap_err().kind());
+        assert!(Bls::sign_with_dst(&[1, 2, 3], &sign_key, &[1; 255]).is_ok());
+    }
+
+    #[test]
+    fn verify_multi_sig_works_for_scripted_mocks() {
+        fn multi_sig_flow() -> (Vec<u8>, bool) {
+            let message = vec![1, 2, 3, 4, 5];
+
+            PairMocksHelper::inject_scalars(&[
+                "1C4E5A8F2D3B6C7E9A0B1C2D3E4F5A6B7C8D9E0F1A2B3C4D5E6F7A8B9C0D1E2F",
+                "0A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F9",
+                "1F2E3D4C5B6A79880716253443526170F1E2D3C4B5A69788796A5B4C3D2E1F00",
+            ]);
+            let gen = Generator::new().unwrap();
+            let sign_key1 = SignKey::new(None).unwrap();
+            let sign_key2 = SignKey::new(None).unwrap();
+            PairMocksHelper::clear();
+
+            let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+            let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+            let signature1 = Bls::sign(&message, &sign_key1).unwrap();
+            let signature2 = Bls::sign(&message, &sign_key2).unwrap();
+
+            let multi_signature = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+            let valid = Bls::verify_multi_sig(&multi_signature, &message, &[&ver_key1, &ver_key2], &gen).unwrap();
+
+            (multi_signature.as_bytes().to_vec(), valid)
+        }
+
+        let (multi_signature1, valid1) = multi_sig_flow();
+        let (multi_signature2, valid2) = multi_sig_flow();
+
+        assert!(valid1);
+        assert!(valid2);
+        assert_eq!(multi_signature1, multi_signature2);
+    }
+
     #[test]
     fn verify_multi_sig_works_for_invalid_message() {
         let message = vec![1, 2, 3, 4, 5];
@@ -698,4 +2029,201 @@ mod tests {
 
         assert!(!valid)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn message_hasher_works_for_chunks() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let message: Vec<u8> = (0..100).collect();
+
+        let mut hasher = MessageHasher::new();
+        for chunk in message.chunks(7) {
+            hasher.update(chunk);
+        }
+        let signature = hasher.sign(&sign_key).unwrap();
+        assert_eq!(Bls::sign(&message, &sign_key).unwrap().as_bytes(), signature.as_bytes());
+
+        let mut hasher = MessageHasher::new();
+        hasher.update(&message);
+        assert!(hasher.verify(&signature, &ver_key, &gen).unwrap());
+
+        let mut hasher = MessageHasher::new();
+        hasher.update(&message[1..]);
+        assert!(!hasher.verify(&signature, &ver_key, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_aggregate_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+        let multi_sig = MultiSignature::new(&[&signature1, &signature2]).unwrap();
+
+        let items = vec![
+            (message1.as_slice(), &ver_key1),
+            (message2.as_slice(), &ver_key2)
+        ];
+        assert!(Bls::verify_aggregate(&multi_sig, &items, &gen).unwrap());
+
+        let swapped_items = vec![
+            (message2.as_slice(), &ver_key1),
+            (message1.as_slice(), &ver_key2)
+        ];
+        assert!(!Bls::verify_aggregate(&multi_sig, &swapped_items, &gen).unwrap());
+    }
+
+    #[test]
+    fn verify_aggregate_works_for_duplicate_message() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+        let multi_sig = MultiSignature::new(&[&Bls::sign(&message, &sign_key).unwrap()]).unwrap();
+
+        let res = Bls::verify_aggregate(&multi_sig, &[(message.as_slice(), &ver_key), (message.as_slice(), &ver_key)], &gen);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn verify_aggregate_works_for_empty_items() {
+        let gen = Generator::new().unwrap();
+        let multi_sig = MultiSignature::new(&[&Bls::sign(&[1], &SignKey::new(None).unwrap()).unwrap()]).unwrap();
+
+        let res = Bls::verify_aggregate(&multi_sig, &[], &gen);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn verify_batch_works() {
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &sign_key2).unwrap();
+
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+
+        let items = vec![
+            (&signature1, message1.as_slice(), &ver_key1),
+            (&signature2, message2.as_slice(), &ver_key2)
+        ];
+
+        let valid = Bls::verify_batch(&items, &gen).unwrap();
+        assert!(valid)
+    }
+
+    #[test]
+    fn verify_batch_works_for_invalid_signature() {
+        let gen = Generator::new().unwrap();
+        let sign_key1 = SignKey::new(None).unwrap();
+        let ver_key1 = VerKey::new(&gen, &sign_key1).unwrap();
+        let sign_key2 = SignKey::new(None).unwrap();
+        let ver_key2 = VerKey::new(&gen, &SignKey::new(None).unwrap()).unwrap();
+
+        let message1 = vec![1, 2, 3, 4, 5];
+        let message2 = vec![6, 7, 8, 9, 10];
+
+        let signature1 = Bls::sign(&message1, &sign_key1).unwrap();
+        let signature2 = Bls::sign(&message2, &sign_key2).unwrap();
+
+        let items = vec![
+            (&signature1, message1.as_slice(), &ver_key1),
+            (&signature2, message2.as_slice(), &ver_key2)
+        ];
+
+        let valid = Bls::verify_batch(&items, &gen).unwrap();
+        assert!(!valid)
+    }
+
+    #[test]
+    fn verify_batch_works_for_empty_batch() {
+        let gen = Generator::new().unwrap();
+        let res = Bls::verify_batch(&[], &gen);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn verify_batch_works_for_verification_threads() {
+        let gen = Generator::new().unwrap();
+        let key_pairs: Vec<KeyPair> = (0..5).map(|_| KeyPair::new(&gen, None).unwrap()).collect();
+        let messages: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 5]).collect();
+        let signatures: Vec<Signature> = key_pairs.iter().zip(messages.iter())
+            .map(|(key_pair, message)| Bls::sign(message, key_pair.sign_key()).unwrap())
+            .collect();
+
+        let mut items: Vec<(&PointG1, &[u8], &PointG2)> = signatures.iter().zip(messages.iter()).zip(key_pairs.iter())
+            .map(|((signature, message), key_pair)| (&signature.point, message.as_slice(), &key_pair.ver_key().point))
+            .collect();
+        let threads_config = config::RuntimeConfig { verification_threads: 3, ..config::RuntimeConfig::default() };
+        let valid = Bls::_verify_batch::<Sha256>(&items, &gen, &threads_config).unwrap();
+
+        items[4].2 = &key_pairs[0].ver_key().point;
+        let invalid = Bls::_verify_batch::<Sha256>(&items, &gen, &threads_config).unwrap();
+
+        assert!(valid);
+        assert!(!invalid);
+    }
+
+    #[test]
+    fn ver_key_from_bytes_works_for_strict_validation() {
+        let identity = PointG2::identity().to_bytes().unwrap();
+        VerKey::from_bytes(&identity).unwrap();
+
+        let strict_config = config::RuntimeConfig { strict_validation: true, ..config::RuntimeConfig::default() };
+        let res = VerKey::_from_bytes(&identity, &strict_config);
+        let signature_res = Signature::_from_bytes(&PointG1::identity().to_bytes().unwrap(), &strict_config);
+
+        assert_eq!(ErrorKind::InvalidKey, res.unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidSignature, signature_res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn verify_proof_of_possession_batch_works() {
+        let gen = Generator::new().unwrap();
+        let key_pair1 = KeyPair::new(&gen, None).unwrap();
+        let key_pair2 = KeyPair::new(&gen, None).unwrap();
+
+        let items = vec![
+            (key_pair1.proof_of_possession(), key_pair1.ver_key()),
+            (key_pair2.proof_of_possession(), key_pair2.ver_key())
+        ];
+
+        let valid = Bls::verify_proof_of_possession_batch(&items, &gen).unwrap();
+        assert!(valid)
+    }
+
+    #[test]
+    fn verify_proof_of_possession_batch_works_for_invalid_proof() {
+        let gen = Generator::new().unwrap();
+        let key_pair1 = KeyPair::new(&gen, None).unwrap();
+        let key_pair2 = KeyPair::new(&gen, None).unwrap();
+
+        let items = vec![
+            (key_pair1.proof_of_possession(), key_pair1.ver_key()),
+            (key_pair1.proof_of_possession(), key_pair2.ver_key())
+        ];
+
+        let valid = Bls::verify_proof_of_possession_batch(&items, &gen).unwrap();
+        assert!(!valid)
+    }
+
+    #[test]
+    fn verify_proof_of_possession_batch_works_for_empty_batch() {
+        let gen = Generator::new().unwrap();
+        let res = Bls::verify_proof_of_possession_batch(&[], &gen);
+        assert!(res.is_err());
+    }
+}
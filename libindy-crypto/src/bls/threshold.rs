@@ -0,0 +1,368 @@
+use crate::errors::IndyCryptoError;
+use crate::pair::{GroupOrderElement, PointG1};
+use crate::sha2::{Sha256, Digest};
+use crate::utils::config;
+use crate::utils::zeroize::zeroize;
+
+use super::{Bls, SignKey, Signature};
+
+use std::collections::HashSet;
+
+const HEADER_SIZE: usize = 8;
+
+/// Share of BLS sign key for `threshold` of `n` signing.
+///
+/// Share is the point of degree `threshold - 1` polynomial at `index`, sign key is the point at zero.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignKeyShare {
+    index: u32,
+    threshold: u32,
+    group_order_element: GroupOrderElement,
+    bytes: Vec<u8>
+}
+
+impl SignKeyShare {
+    /// Returns index of the share, indexes start from 1.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns number of shares required to combine the signature.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns sign key share bytes representation: index and threshold as big endian u32 followed by the element bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns sign key share from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SignKeyShare, IndyCryptoError> {
+        let (index, threshold) = _parse_header(bytes)?;
+
+        if bytes.len() != HEADER_SIZE + GroupOrderElement::BYTES_REPR_SIZE {
+            return Err(IndyCryptoError::InvalidKey("Invalid sign key share: invalid len of bytes representation".to_string()));
+        }
+
+        Ok(SignKeyShare {
+            index,
+            threshold,
+            group_order_element: GroupOrderElement::from_bytes(&bytes[HEADER_SIZE..])?,
+            bytes: bytes.to_vec()
+        })
+    }
+
+    /// Overwrites the sign key share with zeros. Share is zeroized on drop as well.
+    pub fn zeroize(&mut self) {
+        self.group_order_element.zeroize();
+        zeroize(&mut self.bytes);
+    }
+
+    fn new(index: u32, threshold: u32, group_order_element: GroupOrderElement) -> Result<SignKeyShare, IndyCryptoError> {
+        Ok(SignKeyShare {
+            index,
+            threshold,
+            group_order_element,
+            bytes: _with_header(index, threshold, &group_order_element.to_bytes()?)
+        })
+    }
+}
+
+impl Drop for SignKeyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Signature of message made with sign key share.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignatureShare {
+    index: u32,
+    threshold: u32,
+    point: PointG1,
+    bytes: Vec<u8>
+}
+
+impl SignatureShare {
+    /// Returns index of the sign key share the signature is made with.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns number of shares required to combine the signature.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns signature share bytes representation: index and threshold as big endian u32 followed by the point bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Creates and returns signature share from bytes representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SignatureShare, IndyCryptoError> {
+        let (index, threshold) = _parse_header(bytes)?;
+        let point = PointG1::from_bytes(&bytes[HEADER_SIZE..])
+            .map_err(|err| IndyCryptoError::InvalidSignature(format!("Invalid signature share: {}", err)))?;
+
+        Ok(SignatureShare {
+            index,
+            threshold,
+            point,
+            bytes: bytes.to_vec()
+        })
+    }
+}
+
+pub struct Threshold {}
+
+impl Threshold {
+    /// Splits random (or seeded from seed) sign key to `n` shares so that any `threshold` of them can sign.
+    /// Returns the sign key and its shares with indexes from 1 to `n`.
+    ///
+    /// Sign key is returned to the dealer to derive the verification key of the group, it isn't needed to sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of shares
+    /// * `threshold` - Number of shares required to combine the signature
+    /// * `seed` - Optional seed of any non zero length, see `SignKey::from_seed`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::bls::*;
+    /// use indy_crypto::bls::threshold::Threshold;
+    /// let gen = Generator::new().unwrap();
+    /// let (sign_key, shares) = Threshold::generate_shares(3, 2, None).unwrap();
+    /// let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+    ///
+    /// let message = vec![1, 2, 3, 4, 5];
+    /// let share1 = Threshold::sign_share(&message, &shares[0]).unwrap();
+    /// let share3 = Threshold::sign_share(&message, &shares[2]).unwrap();
+    ///
+    /// let signature = Threshold::combine_signature_shares(&[&share1, &share3]).unwrap();
+    /// assert!(Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+    /// ```
+    pub fn generate_shares(n: u32, threshold: u32, seed: Option<&[u8]>) -> Result<(SignKey, Vec<SignKeyShare>), IndyCryptoError> {
+        if threshold == 0 || threshold > n {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Invalid threshold: expected from 1 to {}, actual {}", n, threshold)));
+        }
+
+        let sign_key = match seed {
+            Some(seed) => SignKey::from_seed(seed)?,
+            None => SignKey::new(None)?
+        };
+
+        let mut coefficients = vec![sign_key.group_order_element];
+        for i in 1..threshold {
+            coefficients.push(match seed {
+                Some(_) => {
+                    let mut hasher = Sha256::default();
+                    hasher.input(sign_key.as_bytes());
+                    hasher.input(&i.to_be_bytes());
+                    GroupOrderElement::new_from_seed(hasher.result().as_slice())?
+                }
+                None => GroupOrderElement::new()?
+            });
+        }
+
+        let shares = (1..=n)
+            .map(|index| {
+                let x = _element(index)?;
+                let mut y = *coefficients.last().unwrap();
+                for coefficient in coefficients.iter().rev().skip(1) {
+                    y = y.mul_mod(&x)?.add_mod(coefficient)?;
+                }
+                SignKeyShare::new(index, threshold, y)
+            })
+            .collect::<Result<Vec<SignKeyShare>, IndyCryptoError>>();
+
+        for coefficient in coefficients.iter_mut() {
+            coefficient.zeroize();
+        }
+
+        Ok((sign_key, shares?))
+    }
+
+    /// Signs the message with sign key share.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign
+    /// * `share` - Sign key share
+    pub fn sign_share(message: &[u8], share: &SignKeyShare) -> Result<SignatureShare, IndyCryptoError> {
+        config::check_message_size(message)?;
+        let point = Bls::_hash(message, Sha256::default())?.mul_ct(&share.group_order_element)?;
+
+        Ok(SignatureShare {
+            index: share.index,
+            threshold: share.threshold,
+            point,
+            bytes: _with_header(share.index, share.threshold, &point.to_bytes()?)
+        })
+    }
+
+    /// Combines signature shares of the same message to the signature verifiable with the verification key of the group.
+    /// Only the first `threshold` shares are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `shares` - Signature shares with distinct indexes, at least `threshold` of them
+    pub fn combine_signature_shares(shares: &[&SignatureShare]) -> Result<Signature, IndyCryptoError> {
+        let threshold = match shares.first() {
+            Some(share) => share.threshold,
+            None => return Err(IndyCryptoError::InvalidStructure("List of signature shares is empty".to_string()))
+        };
+
+        if let Some(share) = shares.iter().find(|share| share.threshold != threshold) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Signature share {} has threshold {}, expected {}", share.index, share.threshold, threshold)));
+        }
+
+        let mut indexes = HashSet::new();
+        if let Some(share) = shares.iter().find(|share| !indexes.insert(share.index)) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Duplicate signature share index {}", share.index)));
+        }
+
+        if shares.len() < threshold as usize {
+            return Err(IndyCryptoError::NotEnoughShares(
+                format!("Expected at least {} signature shares, actual {}", threshold, shares.len())));
+        }
+
+        let shares = &shares[..threshold as usize];
+        let xs = shares.iter().map(|share| _element(share.index)).collect::<Result<Vec<GroupOrderElement>, IndyCryptoError>>()?;
+
+        let mut point: Option<PointG1> = None;
+        for (i, share) in shares.iter().enumerate() {
+            // Lagrange coefficient of the share at zero: prod x_j / (x_j - x_i) for j != i
+            let mut numerator = _element(1)?;
+            let mut denominator = _element(1)?;
+            for (j, x) in xs.iter().enumerate() {
+                if i != j {
+                    numerator = numerator.mul_mod(x)?;
+                    denominator = denominator.mul_mod(&x.sub_mod(&xs[i])?)?;
+                }
+            }
+
+            let term = share.point.mul(&numerator.mul_mod(&denominator.inverse()?)?)?;
+            point = Some(match point {
+                Some(point) => point.add(&term)?,
+                None => term
+            });
+        }
+
+        let point = point.ok_or_else(|| IndyCryptoError::InvalidState("Combined signature is absent".to_string()))?;
+        Ok(Signature {
+            point,
+            bytes: point.to_bytes()?
+        })
+    }
+}
+
+fn _element(value: u32) -> Result<GroupOrderElement, IndyCryptoError> {
+    GroupOrderElement::from_bytes(&value.to_be_bytes())
+}
+
+fn _with_header(index: u32, threshold: u32, bytes: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(HEADER_SIZE + bytes.len());
+    result.extend_from_slice(&index.to_be_bytes());
+    result.extend_from_slice(&threshold.to_be_bytes());
+    result.extend_from_slice(bytes);
+    result
+}
+
+fn _parse_header(bytes: &[u8]) -> Result<(u32, u32), IndyCryptoError> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(IndyCryptoError::InvalidStructure("Invalid len of share bytes representation".to_string()));
+    }
+
+    let index = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let threshold = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+
+    if index == 0 || threshold == 0 {
+        return Err(IndyCryptoError::InvalidStructure("Share index and threshold must not be zero".to_string()));
+    }
+
+    Ok((index, threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bls::{Generator, VerKey};
+    use crate::errors::ErrorKind;
+
+    #[test]
+    fn combine_signature_shares_works_for_any_threshold_subset() {
+        let gen = Generator::new().unwrap();
+        let (sign_key, shares) = Threshold::generate_shares(3, 2, None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let message = vec![1, 2, 3, 4, 5];
+
+        let signature_shares: Vec<SignatureShare> = shares.iter().map(|share| Threshold::sign_share(&message, share).unwrap()).collect();
+
+        for (a, b) in [(0, 1), (0, 2), (2, 1)] {
+            let signature = Threshold::combine_signature_shares(&[&signature_shares[a], &signature_shares[b]]).unwrap();
+            assert!(Bls::verify(&signature, &message, &ver_key, &gen).unwrap());
+            assert_eq!(Bls::sign(&message, &sign_key).unwrap().as_bytes(), signature.as_bytes());
+        }
+    }
+
+    #[test]
+    fn generate_shares_works_for_seed() {
+        let (sign_key1, shares1) = Threshold::generate_shares(3, 2, Some(&[1, 2, 3])).unwrap();
+        let (sign_key2, shares2) = Threshold::generate_shares(3, 2, Some(&[1, 2, 3])).unwrap();
+
+        assert_eq!(sign_key1.as_bytes(), sign_key2.as_bytes());
+        assert_eq!(shares1.iter().map(|share| share.as_bytes().to_vec()).collect::<Vec<_>>(),
+                   shares2.iter().map(|share| share.as_bytes().to_vec()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn generate_shares_works_for_invalid_threshold() {
+        for (n, threshold) in [(3, 0), (3, 4), (0, 0)] {
+            let res = Threshold::generate_shares(n, threshold, None);
+            assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+    }
+
+    #[test]
+    fn combine_signature_shares_works_for_insufficient_shares() {
+        let (_, shares) = Threshold::generate_shares(3, 2, None).unwrap();
+        let share = Threshold::sign_share(&[1, 2, 3], &shares[0]).unwrap();
+
+        let res = Threshold::combine_signature_shares(&[&share]);
+        assert_eq!(ErrorKind::NotEnoughShares, res.unwrap_err().kind());
+
+        let res = Threshold::combine_signature_shares(&[]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn combine_signature_shares_works_for_duplicate_index() {
+        let (_, shares) = Threshold::generate_shares(3, 2, None).unwrap();
+        let share = Threshold::sign_share(&[1, 2, 3], &shares[0]).unwrap();
+        let same_share = SignatureShare::from_bytes(share.as_bytes()).unwrap();
+
+        let res = Threshold::combine_signature_shares(&[&share, &same_share]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn shares_work_for_bytes_round_trip() {
+        let (_, shares) = Threshold::generate_shares(3, 2, None).unwrap();
+        let share = SignKeyShare::from_bytes(shares[1].as_bytes()).unwrap();
+        assert_eq!(2, share.index());
+        assert_eq!(2, share.threshold());
+        assert_eq!(shares[1].as_bytes(), share.as_bytes());
+
+        let signature_share = Threshold::sign_share(&[1, 2, 3], &share).unwrap();
+        assert_eq!(signature_share.as_bytes(), SignatureShare::from_bytes(signature_share.as_bytes()).unwrap().as_bytes());
+
+        assert_eq!(ErrorKind::InvalidStructure, SignKeyShare::from_bytes(&[0; 4]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidKey, SignKeyShare::from_bytes(&shares[1].as_bytes()[..20]).unwrap_err().kind());
+    }
+}
@@ -1,11 +1,16 @@
 use crate::errors::IndyCryptoError;
+use crate::utils::zeroize::SecretBytes;
+
+use foreign_types::ForeignType;
 
 use int_traits::IntTraits;
 
 use openssl::bn::{BigNum, BigNumRef, BigNumContext, MsbOption};
-use openssl::hash::{hash, MessageDigest, Hasher};
 use openssl::error::ErrorStack;
+use openssl::hash::{hash, MessageDigest, Hasher};
+use openssl_sys::{BIGNUM, BN_CTX, BN_GENCB, BN_MONT_CTX};
 
+#[cfg(feature = "serialization")]
 #[cfg(feature = "serialization")]
 use serde::ser::{Serialize, Serializer, Error as SError};
 
@@ -13,8 +18,135 @@ use serde::ser::{Serialize, Serializer, Error as SError};
 use serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
 
 use std::fmt;
-use std::cmp::Ord;
+use std::cmp::{max, Ord};
 use std::cmp::Ordering;
+use std::os::raw::{c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+// Callback machinery of BN_generate_prime_ex, available since OpenSSL 1.1.0
+extern "C" {
+    fn BN_GENCB_new() -> *mut BN_GENCB;
+    fn BN_GENCB_free(cb: *mut BN_GENCB);
+    fn BN_GENCB_set(gencb: *mut BN_GENCB,
+                    callback: extern "C" fn(c_int, c_int, *mut BN_GENCB) -> c_int,
+                    cb_arg: *mut c_void);
+    fn BN_GENCB_get_arg(cb: *mut BN_GENCB) -> *mut c_void;
+}
+
+// Montgomery multiplication, not wrapped by openssl crate either
+extern "C" {
+    fn BN_MONT_CTX_new() -> *mut BN_MONT_CTX;
+    fn BN_MONT_CTX_free(mont: *mut BN_MONT_CTX);
+    fn BN_MONT_CTX_set(mont: *mut BN_MONT_CTX, modulus: *const BIGNUM, ctx: *mut BN_CTX) -> c_int;
+    fn BN_mod_mul_montgomery(r: *mut BIGNUM, a: *const BIGNUM, b: *const BIGNUM, mont: *mut BN_MONT_CTX, ctx: *mut BN_CTX) -> c_int;
+    fn BN_to_montgomery(r: *mut BIGNUM, a: *const BIGNUM, mont: *mut BN_MONT_CTX, ctx: *mut BN_CTX) -> c_int;
+    fn BN_from_montgomery(r: *mut BIGNUM, a: *const BIGNUM, mont: *mut BN_MONT_CTX, ctx: *mut BN_CTX) -> c_int;
+}
+
+// Largest window of `multi_mod_exp`, a table of 2^6 - 1 powers per base
+const MAX_EXP_WINDOW: usize = 6;
+
+// Residues modulo odd modulus in Montgomery form, that are multiplied without division by the modulus
+struct Montgomery {
+    mont: *mut BN_MONT_CTX
+}
+
+impl Montgomery {
+    fn new(modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<Montgomery, IndyCryptoError> {
+        let mont = unsafe { BN_MONT_CTX_new() };
+        if mont.is_null() {
+            return Err(ErrorStack::get().into());
+        }
+        let montgomery = Montgomery { mont };
+
+        _check(unsafe { BN_MONT_CTX_set(mont, modulus.openssl_bn.as_ptr(), ctx.openssl_bn_context.as_ptr()) })?;
+        Ok(montgomery)
+    }
+
+    // `a` must be reduced modulo the modulus
+    fn to_montgomery(&self, a: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let bn = BigNumber::new()?;
+        _check(unsafe { BN_to_montgomery(bn.openssl_bn.as_ptr(), a.openssl_bn.as_ptr(), self.mont, ctx.openssl_bn_context.as_ptr()) })?;
+        Ok(bn)
+    }
+
+    fn to_residue(&self, a: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let bn = BigNumber::new()?;
+        _check(unsafe { BN_from_montgomery(bn.openssl_bn.as_ptr(), a.openssl_bn.as_ptr(), self.mont, ctx.openssl_bn_context.as_ptr()) })?;
+        Ok(bn)
+    }
+
+    // r = r * a
+    fn mul_assign(&self, r: &mut BigNumber, a: &BigNumber, ctx: &mut BigNumberContext) -> Result<(), IndyCryptoError> {
+        let r = r.openssl_bn.as_ptr();
+        _check(unsafe { BN_mod_mul_montgomery(r, r, a.openssl_bn.as_ptr(), self.mont, ctx.openssl_bn_context.as_ptr()) })
+    }
+
+    // r = r * r
+    fn sqr_assign(&self, r: &mut BigNumber, ctx: &mut BigNumberContext) -> Result<(), IndyCryptoError> {
+        let r = r.openssl_bn.as_ptr();
+        _check(unsafe { BN_mod_mul_montgomery(r, r, r, self.mont, ctx.openssl_bn_context.as_ptr()) })
+    }
+}
+
+impl Drop for Montgomery {
+    fn drop(&mut self) {
+        unsafe { BN_MONT_CTX_free(self.mont) };
+    }
+}
+
+fn _check(res: c_int) -> Result<(), IndyCryptoError> {
+    if res == 1 {
+        Ok(())
+    } else {
+        Err(ErrorStack::get().into())
+    }
+}
+
+// Window that takes the fewest multiplications per base: 2^window - 1 powers in the table and one per window
+fn _exp_window(exp_bits: usize) -> usize {
+    (1..=MAX_EXP_WINDOW)
+        .min_by_key(|&window| (1 << window) + exp_bits.div_ceil(window))
+        .unwrap_or(1)
+}
+
+// Bits `bit..bit + window` of big endian magnitude of exponent
+fn _exp_window_digit(exp: &[u8], bit: usize, window: usize) -> usize {
+    let mut digit = 0;
+    for b in (bit..bit + window).rev() {
+        let set = b / 8 < exp.len() && (exp[exp.len() - 1 - b / 8] >> (b % 8)) & 1 == 1;
+        digit = (digit << 1) | set as usize;
+    }
+    digit
+}
+
+struct PrimeSearch<'a> {
+    on_candidate: &'a mut dyn FnMut() -> Result<(), IndyCryptoError>,
+    err: Option<IndyCryptoError>
+}
+
+// Event 0 is reported for every candidate that passed the sieve, 1 and 2 for rounds of primality tests
+extern "C" fn _prime_search_cb(event: c_int, _n: c_int, cb: *mut BN_GENCB) -> c_int {
+    if event != 0 {
+        return 1;
+    }
+
+    let search = unsafe { &mut *(BN_GENCB_get_arg(cb) as *mut PrimeSearch) };
+
+    // Unwinding must not cross OpenSSL frames
+    match panic::catch_unwind(AssertUnwindSafe(|| (search.on_candidate)())) {
+        Ok(Ok(())) => 1,
+        Ok(Err(err)) => {
+            search.err = Some(err);
+            0
+        }
+        Err(_) => {
+            search.err = Some(IndyCryptoError::InvalidState("Prime candidate callback panicked".to_string()));
+            0
+        }
+    }
+}
 
 pub struct BigNumberContext {
     openssl_bn_context: BigNumContext
@@ -52,6 +184,51 @@ impl BigNumber {
         Ok(bn)
     }
 
+    /// Generates safe prime like `generate_safe_prime`, calling `on_candidate` for every tested candidate.
+    ///
+    /// Search stops with the error returned by `on_candidate`, panic of `on_candidate` stops it with InvalidState.
+    pub fn generate_safe_prime_with_callback(size: usize,
+                                             on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
+        let mut search = PrimeSearch { on_candidate, err: None };
+
+        let bytes = unsafe {
+            let gencb = BN_GENCB_new();
+            if gencb.is_null() {
+                return Err(ErrorStack::get().into());
+            }
+            BN_GENCB_set(gencb, _prime_search_cb, &mut search as *mut PrimeSearch as *mut c_void);
+
+            let bn: *mut BIGNUM = openssl_sys::BN_new();
+            let generated = !bn.is_null() &&
+                openssl_sys::BN_generate_prime_ex(bn, (size + 1) as c_int, 1, ptr::null(), ptr::null(), gencb) == 1;
+
+            let bytes = if generated {
+                let mut bytes = vec![0u8; (openssl_sys::BN_num_bits(bn) as usize).div_ceil(8)];
+                openssl_sys::BN_bn2bin(bn, bytes.as_mut_ptr());
+                Some(bytes)
+            } else {
+                None
+            };
+
+            openssl_sys::BN_clear_free(bn);
+            BN_GENCB_free(gencb);
+            bytes
+        };
+
+        match bytes {
+            Some(mut bytes) => {
+                let prime = BigNumber::from_bytes(&bytes);
+                bytes.iter_mut().for_each(|byte| *byte = 0);
+                prime
+            }
+            None => {
+                // Also drains errors queued by the aborted search
+                let stack = ErrorStack::get();
+                Err(search.err.take().unwrap_or_else(|| stack.into()))
+            }
+        }
+    }
+
     pub fn generate_prime_in_range(start: &BigNumber, end: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
         let mut prime;
         let mut iteration = 0;
@@ -290,6 +467,94 @@ impl BigNumber {
         Ok(bn)
     }
 
+    /// Computes `self^a mod b` like `mod_exp` by fixed window exponentiation in Montgomery form, the result is the same.
+    pub fn mod_exp_window(&self, a: &BigNumber, b: &BigNumber, ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, IndyCryptoError> {
+        BigNumber::multi_mod_exp(&[self], &[a], b, ctx)
+    }
+
+    /// Computes product of `bases[i]^exps[i] mod modulus`, that is the same as the product of `mod_exp` results.
+    ///
+    /// Powers are computed simultaneously by fixed windows of all exponents in Montgomery form, so squarings are
+    /// shared by all bases and a product of k powers takes about as many squarings as a single `mod_exp`.
+    /// Negative exponents are exponents of inverse bases like in `mod_exp`, even modulus is left to `mod_exp`.
+    /// Like `mod_exp`, time of computation depends on exponents.
+    pub fn multi_mod_exp(bases: &[&BigNumber], exps: &[&BigNumber], modulus: &BigNumber, ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, IndyCryptoError> {
+        match ctx {
+            Some(context) => BigNumber::_multi_mod_exp(bases, exps, modulus, context),
+            None => {
+                let mut ctx = BigNumber::new_context()?;
+                BigNumber::_multi_mod_exp(bases, exps, modulus, &mut ctx)
+            }
+        }
+    }
+
+    fn _multi_mod_exp(bases: &[&BigNumber], exps: &[&BigNumber], modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        if bases.len() != exps.len() {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Numbers of bases {} and exponents {} are different", bases.len(), exps.len())));
+        }
+
+        let one = BigNumber::from_u32(1)?.modulus(modulus, Some(ctx))?;
+
+        // Montgomery form needs odd modulus
+        if modulus.is_negative() || !modulus.is_bit_set(0)? {
+            let mut result = one;
+            for (base, exp) in bases.iter().zip(exps) {
+                result = result.mod_mul(&base._mod_exp(exp, modulus, ctx)?, modulus, Some(ctx))?;
+            }
+            return Ok(result);
+        }
+
+        let mont = Montgomery::new(modulus, ctx)?;
+
+        let mut exp_bits = 0;
+        for exp in exps {
+            exp_bits = max(exp_bits, exp.num_bits()? as usize);
+        }
+        let window = _exp_window(exp_bits);
+
+        // Powers base^1..base^(2^window - 1) of every base in Montgomery form
+        let mut tables = Vec::with_capacity(bases.len());
+        let mut exp_bytes = Vec::with_capacity(exps.len());
+        for (base, exp) in bases.iter().zip(exps) {
+            let base = if exp.is_negative() {
+                base.inverse(modulus, Some(ctx))?
+            } else {
+                base.modulus(modulus, Some(ctx))?
+            };
+
+            let mut table = vec![mont.to_montgomery(&base, ctx)?];
+            while table.len() < (1 << window) - 1 {
+                let mut power = table[table.len() - 1].clone()?;
+                mont.mul_assign(&mut power, &table[0], ctx)?;
+                table.push(power);
+            }
+
+            tables.push(table);
+            exp_bytes.push(SecretBytes::new(exp.to_bytes()?));
+        }
+
+        let windows = exp_bits.div_ceil(window);
+        let mut result = mont.to_montgomery(&one, ctx)?;
+
+        for window_idx in (0..windows).rev() {
+            if window_idx + 1 != windows {
+                for _ in 0..window {
+                    mont.sqr_assign(&mut result, ctx)?;
+                }
+            }
+
+            for (table, exp) in tables.iter().zip(&exp_bytes) {
+                let digit = _exp_window_digit(exp, window_idx * window, window);
+                if digit != 0 {
+                    mont.mul_assign(&mut result, &table[digit - 1], ctx)?;
+                }
+            }
+        }
+
+        mont.to_residue(&result, ctx)
+    }
+
     pub fn modulus(&self, a: &BigNumber, ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, IndyCryptoError> {
         let mut bn = BigNumber::new()?;
         match ctx {
@@ -338,8 +603,13 @@ impl BigNumber {
         self.openssl_bn.is_negative()
     }
 
+    /// Erases the memory of the number (with OpenSSL cleanse, that is not optimized away) and sets it to zero.
+    pub fn zeroize(&mut self) {
+        self.openssl_bn.clear();
+    }
+
     pub fn increment(&self) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNum::from_slice(&self.openssl_bn.to_vec())?;
+        let mut bn = self.openssl_bn.to_owned()?;
         bn.add_word(1)?;
         Ok(BigNumber {
             openssl_bn: bn
@@ -347,7 +617,7 @@ impl BigNumber {
     }
 
     pub fn decrement(&self) -> Result<BigNumber, IndyCryptoError> {
-        let mut bn = BigNum::from_slice(&self.openssl_bn.to_vec())?;
+        let mut bn = self.openssl_bn.to_owned()?;
         bn.sub_word(1)?;
         Ok(BigNumber {
             openssl_bn: bn
@@ -401,8 +671,9 @@ impl BigNumber {
     }
 
     pub fn clone(&self) -> Result<BigNumber, IndyCryptoError> {
+        // to_owned keeps the sign, unlike bytes of the number
         Ok(BigNumber {
-            openssl_bn: BigNum::from_slice(&self.openssl_bn.to_vec()[..])?
+            openssl_bn: self.openssl_bn.to_owned()?
         })
     }
 
@@ -440,6 +711,12 @@ impl PartialEq for BigNumber {
 #[cfg(feature = "serialization")]
 impl Serialize for BigNumber {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        // Not human readable formats (CBOR) take sign byte (1 for negative) followed by big-endian magnitude
+        if !serializer.is_human_readable() {
+            let mut bytes = vec![self.is_negative() as u8];
+            bytes.extend_from_slice(&self.to_bytes().map_err(SError::custom)?);
+            return serializer.serialize_bytes(&bytes);
+        }
         serializer.serialize_newtype_struct("BigNumber", &self.to_dec().map_err(SError::custom)?)
     }
 }
@@ -461,16 +738,26 @@ impl<'a> Deserialize<'a> for BigNumber {
             {
                 Ok(BigNumber::from_dec(value).map_err(DError::custom)?)
             }
-        }
 
-        deserializer.deserialize_str(BigNumberVisitor)
-    }
-}
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<BigNumber, E>
+                where E: DError
+            {
+                match value.split_first() {
+                    Some((&sign, magnitude)) if sign <= 1 => {
+                        BigNumber::from_bytes(magnitude)
+                            .and_then(|bn| if sign == 1 { bn.set_negative(true) } else { Ok(bn) })
+                            .map_err(DError::custom)
+                    }
+                    _ => Err(DError::custom("Invalid sign of BigNumber bytes"))
+                }
+            }
+        }
 
-impl From<ErrorStack> for IndyCryptoError {
-    fn from(err: ErrorStack) -> IndyCryptoError {
-        // TODO: FIXME: Analyze ErrorStack and split invalid structure errors from other errors
-        IndyCryptoError::InvalidStructure(err.to_string())
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BigNumberVisitor)
+        } else {
+            deserializer.deserialize_bytes(BigNumberVisitor)
+        }
     }
 }
 
@@ -489,6 +776,7 @@ lazy_static! {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::ErrorKind;
 
     use serde_json;
 
@@ -563,6 +851,46 @@ mod tests {
         assert!(prime.is_safe_prime(None).unwrap());
     }
 
+    #[test]
+    fn generate_safe_prime_with_callback_works() {
+        let mut candidates = 0;
+        let prime = BigNumber::generate_safe_prime_with_callback(256, &mut || {
+            candidates += 1;
+            Ok(())
+        }).unwrap();
+        assert!(candidates > 0);
+        assert_eq!(257, prime.num_bits().unwrap());
+        assert!(prime.is_safe_prime(None).unwrap());
+    }
+
+    #[test]
+    fn generate_safe_prime_with_callback_works_for_callback_error() {
+        let mut candidates = 0;
+        let res = BigNumber::generate_safe_prime_with_callback(1024, &mut || {
+            candidates += 1;
+            if candidates == 3 { Err(IndyCryptoError::InvalidStructure("stop".to_string())) } else { Ok(()) }
+        });
+        assert_eq!(IndyCryptoError::InvalidStructure("stop".to_string()), res.unwrap_err());
+        assert_eq!(3, candidates);
+    }
+
+    #[test]
+    fn generate_safe_prime_with_callback_works_for_panicking_callback() {
+        let res = BigNumber::generate_safe_prime_with_callback(1024, &mut || panic!("callback panic"));
+        assert_eq!(IndyCryptoError::InvalidState("Prime candidate callback panicked".to_string()), res.unwrap_err());
+
+        // Library stays usable after the aborted search
+        assert!(BigNumber::generate_safe_prime_with_callback(128, &mut || Ok(())).unwrap().is_safe_prime(None).unwrap());
+    }
+
+    #[test]
+    fn zeroize_works() {
+        let mut bn = BigNumber::from_dec("123456789123456789").unwrap();
+        bn.zeroize();
+        assert_eq!(BigNumber::from_u32(0).unwrap(), bn);
+        assert_eq!("0", bn.to_dec().unwrap());
+    }
+
     #[test]
     fn decrement_works() {
         let num = BigNumber::from_u32(1000).unwrap();
@@ -575,6 +903,14 @@ mod tests {
         assert_eq!(num.increment().unwrap(), num.add(&BIGNUMBER_1).unwrap());
     }
 
+    #[test]
+    fn clone_works_for_negative() {
+        let num = BigNumber::from_dec("-2147483648").unwrap();
+        assert_eq!(num, num.clone().unwrap());
+        assert_eq!("-2147483647", num.increment().unwrap().to_dec().unwrap());
+        assert_eq!("-2147483649", num.decrement().unwrap().to_dec().unwrap());
+    }
+
     #[test]
     fn rshift1_works() {
         let num = BigNumber::from_u32(1000).unwrap();
@@ -596,6 +932,137 @@ mod tests {
         assert_eq!(num.lshift1().unwrap(), BigNumber::from_u32(2000).unwrap());
     }
 
+    // Square and multiply by bits of the exponent
+    fn _naive_mod_exp(base: &BigNumber, exp: &BigNumber, modulus: &BigNumber) -> BigNumber {
+        let mut ctx = BigNumber::new_context().unwrap();
+        let base = if exp.is_negative() {
+            base.inverse(modulus, Some(&mut ctx)).unwrap()
+        } else {
+            base.modulus(modulus, Some(&mut ctx)).unwrap()
+        };
+
+        let mut result = BigNumber::from_u32(1).unwrap().modulus(modulus, Some(&mut ctx)).unwrap();
+        for bit in (0..exp.num_bits().unwrap()).rev() {
+            result = result.mod_mul(&result, modulus, Some(&mut ctx)).unwrap();
+            if exp.is_bit_set(bit).unwrap() {
+                result = result.mod_mul(&base, modulus, Some(&mut ctx)).unwrap();
+            }
+        }
+        result
+    }
+
+    fn _rand_signed(bits: usize, negative: bool) -> BigNumber {
+        match bits {
+            0 => BigNumber::new().unwrap(),
+            bits => BigNumber::rand(bits).unwrap().set_negative(negative).unwrap()
+        }
+    }
+
+    #[test]
+    fn mod_exp_window_works() {
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        for &modulus_bits in &[2usize, 64, 521, 1024] {
+            let mut modulus = BigNumber::rand(modulus_bits).unwrap();
+            modulus.set_bit(0).unwrap();
+            modulus.set_bit(modulus_bits as i32 - 1).unwrap();
+
+            for &exp_bits in &[0usize, 1, 7, 64, 300, 1100] {
+                for &negative in &[false, true] {
+                    // bases coprime to the modulus, as negative exponents take the inverse
+                    let base = BigNumber::generate_prime(modulus_bits + 8).unwrap();
+                    let exp = _rand_signed(exp_bits, negative);
+
+                    let expected = base.mod_exp(&exp, &modulus, Some(&mut ctx)).unwrap();
+                    assert_eq!(expected, _naive_mod_exp(&base, &exp, &modulus));
+                    assert_eq!(expected, base.mod_exp_window(&exp, &modulus, Some(&mut ctx)).unwrap(),
+                               "{} {} {}", modulus_bits, exp_bits, negative);
+                    assert_eq!(expected.to_bytes().unwrap(), base.mod_exp_window(&exp, &modulus, None).unwrap().to_bytes().unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mod_exp_window_works_for_edge_cases() {
+        let modulus = BigNumber::from_u32(1_000_003).unwrap();
+        let exp = BigNumber::from_u32(65_537).unwrap();
+
+        for base in &[BigNumber::new().unwrap(), BigNumber::from_u32(1).unwrap(),
+                      BigNumber::from_dec("-12345").unwrap(), BigNumber::from_dec("123456789012345678901234567890").unwrap()] {
+            assert_eq!(base.mod_exp(&exp, &modulus, None).unwrap(), base.mod_exp_window(&exp, &modulus, None).unwrap());
+        }
+
+        // modulus 1 and even modulus
+        let base = BigNumber::from_u32(7).unwrap();
+        for modulus in &[BigNumber::from_u32(1).unwrap(), BigNumber::from_u32(1 << 20).unwrap(), BigNumber::from_u32(1_000_002).unwrap()] {
+            assert_eq!(base.mod_exp(&exp, modulus, None).unwrap(), base.mod_exp_window(&exp, modulus, None).unwrap());
+        }
+
+        assert!(base.mod_exp_window(&exp, &BigNumber::new().unwrap(), None).is_err());
+    }
+
+    #[test]
+    fn multi_mod_exp_works() {
+        let mut ctx = BigNumber::new_context().unwrap();
+        let modulus = BigNumber::generate_safe_prime(256).unwrap()
+            .mul(&BigNumber::generate_safe_prime(256).unwrap(), None).unwrap();
+
+        for count in 0..6 {
+            // odd bases are coprime to the even modulus below too
+            let bases = (0..count).map(|_| {
+                let mut base = BigNumber::rand(600).unwrap();
+                base.set_bit(0).unwrap();
+                base
+            }).collect::<Vec<BigNumber>>();
+            let exps = (0..count).map(|i| _rand_signed(100 * i, i % 2 == 1)).collect::<Vec<BigNumber>>();
+
+            let mut expected = BigNumber::from_u32(1).unwrap();
+            for (base, exp) in bases.iter().zip(&exps) {
+                expected = expected.mod_mul(&_naive_mod_exp(base, exp, &modulus), &modulus, Some(&mut ctx)).unwrap();
+            }
+
+            let bases = bases.iter().collect::<Vec<&BigNumber>>();
+            let exps = exps.iter().collect::<Vec<&BigNumber>>();
+            assert_eq!(expected, BigNumber::multi_mod_exp(&bases, &exps, &modulus, Some(&mut ctx)).unwrap(), "{}", count);
+
+            // even modulus is computed by mod_exp
+            let even_modulus = modulus.lshift1().unwrap();
+            let mut expected = BigNumber::from_u32(1).unwrap();
+            for (base, exp) in bases.iter().zip(&exps) {
+                expected = expected.mod_mul(&base.mod_exp(exp, &even_modulus, None).unwrap(), &even_modulus, None).unwrap();
+            }
+            assert_eq!(expected, BigNumber::multi_mod_exp(&bases, &exps, &even_modulus, None).unwrap());
+        }
+
+        let base = BigNumber::from_u32(2).unwrap();
+        let err = BigNumber::multi_mod_exp(&[&base, &base], &[&base], &modulus, None).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+    }
+
+    #[test]
+    fn multi_mod_exp_works_for_large_random_inputs() {
+        let mut ctx = BigNumber::new_context().unwrap();
+        let mut modulus = BigNumber::rand(2050).unwrap();
+        modulus.set_bit(0).unwrap();
+        modulus.set_bit(2049).unwrap();
+
+        let bases = (0..6).map(|_| BigNumber::rand(2048).unwrap()).collect::<Vec<BigNumber>>();
+        let exps = (0..6).map(|_| BigNumber::rand(2724).unwrap()).collect::<Vec<BigNumber>>();
+
+        let mut expected = BigNumber::from_u32(1).unwrap();
+        for (base, exp) in bases.iter().zip(&exps) {
+            expected = expected.mod_mul(&base.mod_exp(exp, &modulus, Some(&mut ctx)).unwrap(), &modulus, Some(&mut ctx)).unwrap();
+        }
+
+        let product = BigNumber::multi_mod_exp(&bases.iter().collect::<Vec<&BigNumber>>(), &exps.iter().collect::<Vec<&BigNumber>>(),
+                                               &modulus, Some(&mut ctx)).unwrap();
+
+        assert_eq!(expected, product);
+        assert_eq!(bases[0].mod_exp(&exps[0], &modulus, Some(&mut ctx)).unwrap(),
+                   bases[0].mod_exp_window(&exps[0], &modulus, Some(&mut ctx)).unwrap());
+    }
+
     #[cfg(feature = "serialization")]
     #[derive(Serialize, Deserialize)]
     struct Test {
@@ -1,6 +1,114 @@
+//! Commitments to vectors of numbers of the form `r_1^m_1 * ... * r_k^m_k * s^v mod n` over the
+//! parameters of issuer public key, the same as credential signatures and proofs use, so external
+//! protocols (attribute escrow, verifiable encryption) can commit to credential attributes.
+//!
+//! Values and blinding factors must be non negative. Commitments are hiding for blinding factors of
+//! `LARGE_VPRIME` bits as `new_blinding_factor` creates, and binding for everyone who doesn't know
+//! the factorization of `n` and discrete logarithms of the bases, that is everyone except the creator of the key.
 use crate::bn::{BigNumber, BigNumberContext};
+use crate::cl::*;
+use crate::cl::constants::{LARGE_PRIME, LARGE_VPRIME};
+use crate::cl::helpers::*;
 use crate::errors::IndyCryptoError;
 
+/// Modulus `n`, blinding base `s` and the bases of committed values, all of them quadratic residues modulo `n`.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct CommitmentKey {
+    n: BigNumber,
+    s: BigNumber,
+    bases: Vec<BigNumber>
+}
+
+impl CommitmentKey {
+    /// Creates key of issuer public key: its modulus, its base `s` for blinding and its bases
+    /// of `attrs` in the given order for values, as in credential signatures.
+    ///
+    /// # Arguments
+    /// * `p_pub_key` - Primary public key of issuer, see `CredentialPublicKey::get_primary_key`.
+    /// * `attrs` - Attributes of credential schema (or non credential schema), one for every committed value.
+    pub fn from_primary_public_key(p_pub_key: &CredentialPrimaryPublicKey, attrs: &[&str]) -> Result<CommitmentKey, IndyCryptoError> {
+        trace!("CommitmentKey::from_primary_public_key: >>> p_pub_key: {:?}, attrs: {:?}", p_pub_key, attrs);
+
+        if attrs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure("Commitment key must have one base at least".to_string()));
+        }
+
+        let bases = attrs.iter()
+            .map(|attr| p_pub_key.r.get(*attr)
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Attribute '{}' isn't in the public key", attr)))
+                .and_then(BigNumber::clone))
+            .collect::<Result<Vec<BigNumber>, IndyCryptoError>>()?;
+
+        let key = CommitmentKey { n: p_pub_key.n.clone()?, s: p_pub_key.s.clone()?, bases };
+
+        trace!("CommitmentKey::from_primary_public_key: <<< key: {:?}", key);
+
+        Ok(key)
+    }
+
+    /// Creates new key as issuer creates its public key: modulus of two safe primes, random quadratic
+    /// residue `s` and the bases as random powers of `s`. The factorization of the modulus and the powers
+    /// are dropped, still the caller could keep them, so the key is binding for others only.
+    ///
+    /// # Arguments
+    /// * `bases_count` - Number of committed values.
+    pub fn new(bases_count: usize) -> Result<CommitmentKey, IndyCryptoError> {
+        trace!("CommitmentKey::new: >>> bases_count: {:?}", bases_count);
+
+        if bases_count == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Commitment key must have one base at least".to_string()));
+        }
+
+        let mut ctx = BigNumber::new_context()?;
+
+        let mut p_safe = generate_safe_prime_with_callback(LARGE_PRIME, &mut || Ok(()))?;
+        let mut q_safe = generate_safe_prime_with_callback(LARGE_PRIME, &mut || Ok(()))?;
+        let mut p = p_safe.rshift1()?;
+        let mut q = q_safe.rshift1()?;
+
+        let n = p_safe.mul(&q_safe, Some(&mut ctx))?;
+        let s = random_qr(&n)?;
+
+        let mut bases = Vec::with_capacity(bases_count);
+        for _ in 0..bases_count {
+            let mut x = gen_x(&p, &q)?;
+            bases.push(s.mod_exp(&x, &n, Some(&mut ctx))?);
+            x.zeroize();
+        }
+
+        p_safe.zeroize();
+        q_safe.zeroize();
+        p.zeroize();
+        q.zeroize();
+
+        let key = CommitmentKey { n, s, bases };
+
+        trace!("CommitmentKey::new: <<< key: {:?}", key);
+
+        Ok(key)
+    }
+
+    pub fn n(&self) -> &BigNumber {
+        &self.n
+    }
+
+    pub fn s(&self) -> &BigNumber {
+        &self.s
+    }
+
+    pub fn bases(&self) -> &[BigNumber] {
+        &self.bases
+    }
+
+    pub fn try_clone(&self) -> Result<CommitmentKey, IndyCryptoError> {
+        Ok(CommitmentKey {
+            n: self.n.clone()?,
+            s: self.s.clone()?,
+            bases: self.bases.iter().map(BigNumber::clone).collect::<Result<Vec<BigNumber>, IndyCryptoError>>()?
+        })
+    }
+}
+
 /// Generate a pedersen commitment to a given number
 ///
 /// # Arguments
@@ -13,11 +121,156 @@ use crate::errors::IndyCryptoError;
 ///
 /// # Result
 /// Return the pedersen commitment, i.e `(gen_1^m)*(gen_2^r)`
-pub fn get_pedersen_commitment(gen_1: &BigNumber, m: &BigNumber,
-                               gen_2: &BigNumber, r: &BigNumber,
-                               modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
-    let commitment = gen_1.mod_exp(m, modulus, Some(ctx))?
-        .mod_mul(&gen_2.mod_exp(r, modulus, Some(ctx))?,
-                 modulus, Some(ctx))?;
+pub(crate) fn get_pedersen_commitment(gen_1: &BigNumber, m: &BigNumber,
+                                      gen_2: &BigNumber, r: &BigNumber,
+                                      modulus: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+    BigNumber::multi_mod_exp(&[gen_1, gen_2], &[m, r], modulus, Some(ctx))
+}
+
+/// Creates random blinding factor of `LARGE_VPRIME` bits, as of blinded credential secrets.
+pub fn new_blinding_factor() -> Result<BigNumber, IndyCryptoError> {
+    bn_rand(LARGE_VPRIME)
+}
+
+/// Returns commitment `bases[0]^values[0] * ... * bases[k]^values[k] * s^blinding mod n`.
+///
+/// # Arguments
+/// * `values` - Committed values, as many as bases of the key.
+/// * `blinding` - Blinding factor, see `new_blinding_factor`.
+/// * `key` - Commitment key.
+pub fn commit(values: &[BigNumber], blinding: &BigNumber, key: &CommitmentKey) -> Result<BigNumber, IndyCryptoError> {
+    trace!("commitment::commit: >>> values: {:?}, blinding: {:?}, key: {:?}", secret!(values), secret!(blinding), key);
+
+    if values.len() != key.bases.len() {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Number of values {} doesn't match number of bases {}", values.len(), key.bases.len())));
+    }
+    if blinding.is_negative() || values.iter().any(BigNumber::is_negative) {
+        return Err(IndyCryptoError::InvalidStructure("Committed values and blinding factor must be non negative".to_string()));
+    }
+
+    let mut ctx = BigNumber::new_context()?;
+
+    let bases = Some(&key.s).into_iter().chain(&key.bases).collect::<Vec<&BigNumber>>();
+    let exps = Some(blinding).into_iter().chain(values).collect::<Vec<&BigNumber>>();
+    let commitment = BigNumber::multi_mod_exp(&bases, &exps, &key.n, Some(&mut ctx))?;
+
+    trace!("commitment::commit: <<< commitment: {:?}", commitment);
+
     Ok(commitment)
 }
+
+/// Checks that `values` and `blinding` open `commitment`.
+pub fn verify_opening(commitment: &BigNumber,
+                      values: &[BigNumber],
+                      blinding: &BigNumber,
+                      key: &CommitmentKey) -> Result<bool, IndyCryptoError> {
+    Ok(*commitment == commit(values, blinding, key)?)
+}
+
+/// Returns product of commitments, that is commitment to the sums of their values with the sum of their blinding factors.
+pub fn add(commitment: &BigNumber, other_commitment: &BigNumber, key: &CommitmentKey) -> Result<BigNumber, IndyCryptoError> {
+    commitment.mod_mul(other_commitment, &key.n, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cl::issuer;
+    use crate::errors::ErrorKind;
+
+    fn _key() -> CommitmentKey {
+        CommitmentKey::from_primary_public_key(&issuer::mocks::credential_primary_public_key(), &["name", "age"]).unwrap()
+    }
+
+    fn _values(values: &[u32]) -> Vec<BigNumber> {
+        values.iter().map(|value| BigNumber::from_u32(*value as usize).unwrap()).collect()
+    }
+
+    #[test]
+    fn commit_works() {
+        let key = _key();
+        let values = _values(&[28, 175]);
+        let blinding = new_blinding_factor().unwrap();
+
+        let commitment = commit(&values, &blinding, &key).unwrap();
+        assert!(verify_opening(&commitment, &values, &blinding, &key).unwrap());
+
+        let p_pub_key = issuer::mocks::credential_primary_public_key();
+        let mut ctx = BigNumber::new_context().unwrap();
+        let expected = p_pub_key.r["name"].mod_exp(&values[0], &p_pub_key.n, Some(&mut ctx)).unwrap()
+            .mod_mul(&p_pub_key.r["age"].mod_exp(&values[1], &p_pub_key.n, Some(&mut ctx)).unwrap(), &p_pub_key.n, Some(&mut ctx)).unwrap()
+            .mod_mul(&p_pub_key.s.mod_exp(&blinding, &p_pub_key.n, Some(&mut ctx)).unwrap(), &p_pub_key.n, Some(&mut ctx)).unwrap();
+        assert_eq!(expected, commitment);
+    }
+
+    #[test]
+    fn verify_opening_works_for_wrong_opening() {
+        let key = _key();
+        let values = _values(&[28, 175]);
+        let blinding = new_blinding_factor().unwrap();
+        let commitment = commit(&values, &blinding, &key).unwrap();
+
+        assert!(!verify_opening(&commitment, &_values(&[28, 176]), &blinding, &key).unwrap());
+        assert!(!verify_opening(&commitment, &_values(&[175, 28]), &blinding, &key).unwrap());
+        assert!(!verify_opening(&commitment, &values, &blinding.increment().unwrap(), &key).unwrap());
+        assert!(!verify_opening(&commitment.increment().unwrap(), &values, &blinding, &key).unwrap());
+        assert_eq!(ErrorKind::InvalidStructure, verify_opening(&commitment, &_values(&[28]), &blinding, &key).unwrap_err().kind());
+    }
+
+    #[test]
+    fn add_works() {
+        let key = _key();
+        let (values, other_values) = (_values(&[28, 175]), _values(&[14, 5]));
+        let (blinding, other_blinding) = (new_blinding_factor().unwrap(), new_blinding_factor().unwrap());
+
+        let sum = add(&commit(&values, &blinding, &key).unwrap(),
+                      &commit(&other_values, &other_blinding, &key).unwrap(), &key).unwrap();
+
+        assert!(verify_opening(&sum, &_values(&[42, 180]), &blinding.add(&other_blinding).unwrap(), &key).unwrap());
+        assert!(!verify_opening(&sum, &values, &blinding, &key).unwrap());
+    }
+
+    #[test]
+    fn commit_works_for_invalid_values() {
+        let key = _key();
+        let blinding = new_blinding_factor().unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, commit(&_values(&[1, 2, 3]), &blinding, &key).unwrap_err().kind());
+
+        let mut values = _values(&[1, 2]);
+        values[0] = values[0].set_negative(true).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, commit(&values, &blinding, &key).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, commit(&_values(&[1, 2]), &values[0], &key).unwrap_err().kind());
+    }
+
+    #[test]
+    fn commitment_key_from_primary_public_key_works_for_unknown_attr() {
+        let p_pub_key = issuer::mocks::credential_primary_public_key();
+        assert_eq!(ErrorKind::InvalidStructure, CommitmentKey::from_primary_public_key(&p_pub_key, &["name", "unknown"]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, CommitmentKey::from_primary_public_key(&p_pub_key, &[]).unwrap_err().kind());
+    }
+
+    #[test]
+    fn commitment_key_serialization_works() {
+        let key = _key();
+        let json = serde_json::to_string(&key).unwrap();
+        let deserialized: CommitmentKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(key, deserialized);
+        assert_eq!(key, key.try_clone().unwrap());
+    }
+
+    #[test]
+    fn commitment_key_new_works() {
+        let key = CommitmentKey::new(3).unwrap();
+        assert_eq!(3, key.bases().len());
+        // safe primes of LARGE_PRIME + 1 bits as of issuer keys
+        assert_eq!(2 * LARGE_PRIME + 2, key.n().num_bits().unwrap() as usize);
+
+        let values = _values(&[1, 2, 3]);
+        let blinding = new_blinding_factor().unwrap();
+        let commitment = commit(&values, &blinding, &key).unwrap();
+        assert!(verify_opening(&commitment, &values, &blinding, &key).unwrap());
+
+        assert_eq!(ErrorKind::InvalidStructure, CommitmentKey::new(0).unwrap_err().kind());
+    }
+}
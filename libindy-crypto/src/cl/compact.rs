@@ -0,0 +1,234 @@
+//! Compact binary encoding of revocation entities.
+//!
+//! Encoded entity starts with `COMPACT_FORMAT_VERSION` byte. Numbers are unsigned LEB128 varints,
+//! sets of indices are encoded as runs of consecutive indices in ascending order: number of runs,
+//! then for every run its gap from the end of the previous run (or from 0) and its length - 1.
+use crate::errors::IndyCryptoError;
+
+use std::collections::HashSet;
+
+/// Version of the encoding, the first byte of encoded entity.
+pub const COMPACT_FORMAT_VERSION: u8 = 1;
+
+// Sets are expanded on decoding, so a few bytes of corrupted runs could take all memory.
+// Registries are far smaller: tails of 2^24 credentials take 4 GB
+const MAX_INDICES: u64 = 1 << 24;
+
+// Varint of u32 takes 5 bytes at most
+const MAX_VARINT_LEN: usize = 5;
+
+pub struct CompactWriter {
+    bytes: Vec<u8>
+}
+
+impl CompactWriter {
+    pub fn new() -> CompactWriter {
+        CompactWriter { bytes: vec![COMPACT_FORMAT_VERSION] }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn write_u32(&mut self, mut value: u32) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    pub fn write_indices(&mut self, indices: &HashSet<u32>) {
+        let mut sorted = indices.iter().cloned().collect::<Vec<u32>>();
+        sorted.sort_unstable();
+
+        let mut runs: Vec<(u32, u32)> = Vec::new(); /* start, length - 1 */
+        for index in sorted {
+            match runs.last_mut() {
+                Some(&mut (start, ref mut len)) if start as u64 + *len as u64 + 1 == index as u64 => *len += 1,
+                _ => runs.push((index, 0))
+            }
+        }
+
+        self.write_u32(runs.len() as u32);
+        let mut prev_end = 0u64;
+        for (start, len) in runs {
+            self.write_u32((start as u64 - prev_end) as u32);
+            self.write_u32(len);
+            prev_end = start as u64 + len as u64 + 1;
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub struct CompactReader<'a> {
+    bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> CompactReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<CompactReader<'a>, IndyCryptoError> {
+        let mut reader = CompactReader { bytes, pos: 0 };
+        match reader.read_u8()? {
+            COMPACT_FORMAT_VERSION => Ok(reader),
+            version => Err(_invalid(&format!("unsupported format version {}", version)))
+        }
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, IndyCryptoError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], IndyCryptoError> {
+        if len > self.bytes.len() - self.pos {
+            return Err(_invalid("unexpected end of input"));
+        }
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, IndyCryptoError> {
+        let mut value = 0u64;
+        for i in 0..MAX_VARINT_LEN {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return if value > u32::MAX as u64 { Err(_invalid("number is out of range")) } else { Ok(value as u32) };
+            }
+        }
+        Err(_invalid("number is too long"))
+    }
+
+    pub fn read_indices(&mut self) -> Result<HashSet<u32>, IndyCryptoError> {
+        let runs = self.read_u32()?;
+        // Every run takes 2 bytes at least
+        if runs as usize > (self.bytes.len() - self.pos) / 2 {
+            return Err(_invalid("number of runs exceeds input"));
+        }
+
+        let mut indices = HashSet::new();
+        let mut prev_end = 0u64;
+        for _ in 0..runs {
+            let start = prev_end + self.read_u32()? as u64;
+            let end = start + self.read_u32()? as u64 + 1;
+            if end > u32::MAX as u64 + 1 {
+                return Err(_invalid("index is out of range"));
+            }
+            if indices.len() as u64 + end - start > MAX_INDICES {
+                return Err(_invalid("too many indices"));
+            }
+
+            indices.extend(start as u32..=(end - 1) as u32);
+            prev_end = end;
+        }
+
+        Ok(indices)
+    }
+
+    pub fn finalize(self) -> Result<(), IndyCryptoError> {
+        if self.pos != self.bytes.len() {
+            return Err(_invalid(&format!("{} trailing bytes", self.bytes.len() - self.pos)));
+        }
+        Ok(())
+    }
+}
+
+fn _invalid(err: &str) -> IndyCryptoError {
+    IndyCryptoError::InvalidStructure(format!("Invalid compact bytes: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorKind;
+
+    fn _round_trip(indices: &HashSet<u32>) -> Vec<u8> {
+        let mut writer = CompactWriter::new();
+        writer.write_indices(indices);
+        let bytes = writer.finalize();
+
+        let mut reader = CompactReader::new(&bytes).unwrap();
+        assert_eq!(indices, &reader.read_indices().unwrap());
+        reader.finalize().unwrap();
+        bytes
+    }
+
+    fn _read_indices(bytes: &[u8]) -> Result<HashSet<u32>, IndyCryptoError> {
+        let mut reader = CompactReader::new(bytes)?;
+        let indices = reader.read_indices()?;
+        reader.finalize()?;
+        Ok(indices)
+    }
+
+    #[test]
+    fn write_u32_works() {
+        for &(value, ref expected) in &[(0u32, vec![0x00u8]), (127, vec![0x7f]), (128, vec![0x80, 0x01]),
+                                        (300, vec![0xac, 0x02]), (u32::MAX, vec![0xff, 0xff, 0xff, 0xff, 0x0f])] {
+            let mut writer = CompactWriter::new();
+            writer.write_u32(value);
+            let bytes = writer.finalize();
+            assert_eq!(expected.as_slice(), &bytes[1..]);
+
+            let mut reader = CompactReader::new(&bytes).unwrap();
+            assert_eq!(value, reader.read_u32().unwrap());
+        }
+    }
+
+    #[test]
+    fn write_indices_works() {
+        // 2 runs: 1..=3 and 7
+        assert_eq!(vec![COMPACT_FORMAT_VERSION, 2, 1, 2, 3, 0], _round_trip(&hashset![1, 2, 3, 7]));
+        assert_eq!(vec![COMPACT_FORMAT_VERSION, 0], _round_trip(&HashSet::new()));
+    }
+
+    #[test]
+    fn read_indices_works_for_patterns() {
+        // dense run, isolated indices, mixed runs and extreme values
+        assert!(_round_trip(&(1..100_001).collect()).len() < 8);
+        assert!(_round_trip(&(1..10_001).map(|i| i * 2).collect()).len() < 3 * 10_000 + 8);
+        _round_trip(&(1..1000).filter(|i| i % 7 < 3 || i % 100 == 50).collect());
+        _round_trip(&hashset![0]);
+        _round_trip(&hashset![u32::MAX]);
+        _round_trip(&hashset![0, 1, u32::MAX - 1, u32::MAX]);
+    }
+
+    #[test]
+    fn read_indices_works_for_corrupted_input() {
+        let bytes = _round_trip(&hashset![1, 2, 3, 7, 300]);
+        for len in 0..bytes.len() {
+            assert_eq!(ErrorKind::InvalidStructure, _read_indices(&bytes[..len]).unwrap_err().kind());
+        }
+
+        // unknown version, trailing byte
+        assert_eq!(ErrorKind::InvalidStructure, _read_indices(&[COMPACT_FORMAT_VERSION + 1, 0]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, _read_indices(&[COMPACT_FORMAT_VERSION, 0, 0]).unwrap_err().kind());
+
+        // too long and out of range numbers
+        assert_eq!(ErrorKind::InvalidStructure, _read_indices(&[COMPACT_FORMAT_VERSION, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, _read_indices(&[COMPACT_FORMAT_VERSION, 0xff, 0xff, 0xff, 0xff, 0x1f]).unwrap_err().kind());
+
+        // number of runs exceeding input
+        assert_eq!(ErrorKind::InvalidStructure, _read_indices(&[COMPACT_FORMAT_VERSION, 2, 1, 0]).unwrap_err().kind());
+
+        // run past u32::MAX
+        assert_eq!(ErrorKind::InvalidStructure,
+                   _read_indices(&[COMPACT_FORMAT_VERSION, 1, 0xfe, 0xff, 0xff, 0xff, 0x0f, 2]).unwrap_err().kind());
+    }
+
+    #[test]
+    fn read_indices_works_for_too_many_indices() {
+        let mut writer = CompactWriter::new();
+        writer.write_u32(1);
+        writer.write_u32(1);
+        writer.write_u32(MAX_INDICES as u32);
+        assert_eq!(ErrorKind::InvalidStructure, _read_indices(&writer.finalize()).unwrap_err().kind());
+    }
+}
@@ -1,11 +1,21 @@
-use crate::bn::{BigNumber, BIGNUMBER_1};
+use crate::bn::{BigNumber, BigNumberContext};
 use crate::cl::*;
 use crate::errors::IndyCryptoError;
-use crate::pair::GroupOrderElement;
+use crate::pair::{GroupOrderElement, PointG1, PointG2};
 use super::constants::*;
 
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
+use std::ptr;
+
+#[cfg(feature = "parallel")]
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "parallel")]
+use std::sync::mpsc;
+#[cfg(feature = "parallel")]
+use std::thread;
 
 #[cfg(test)]
 use std::cell::RefCell;
@@ -163,33 +173,144 @@ pub fn _generate_prime_in_range(start: &BigNumber, end: &BigNumber) -> Result<Bi
 }
 
 #[cfg(test)]
-pub fn generate_safe_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
+pub fn generate_safe_prime_with_callback(size: usize,
+                                         on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
     if MockHelper::is_injected() {
+        on_candidate()?;
         match size {
-            LARGE_PRIME => return Ok(BigNumber::from_dec("298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623")?),
+            LARGE_PRIME => return BigNumber::from_dec("298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623"),
             _ => {
                 panic!("Uncovered case: {}", size);
             }
         }
     }
-    _generate_safe_prime(size)
+    _generate_safe_prime_with_callback(size, on_candidate)
 }
 
 #[cfg(not(test))]
-pub fn generate_safe_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
-    _generate_safe_prime(size)
+pub fn generate_safe_prime_with_callback(size: usize,
+                                         on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
+    _generate_safe_prime_with_callback(size, on_candidate)
 }
 
-pub fn _generate_safe_prime(size: usize) -> Result<BigNumber, IndyCryptoError> {
-    trace!("Helpers::generate_safe_prime: >>> size: {:?}", size);
+pub fn _generate_safe_prime_with_callback(size: usize,
+                                          on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
+    trace!("Helpers::generate_safe_prime_with_callback: >>> size: {:?}", size);
 
-    let safe_prime = BigNumber::generate_safe_prime(size)?;
+    #[cfg(feature = "parallel")]
+    let safe_prime = _generate_safe_prime_in_parallel(size, on_candidate)?;
+    #[cfg(not(feature = "parallel"))]
+    let safe_prime = BigNumber::generate_safe_prime_with_callback(size, on_candidate)?;
 
-    trace!("Helpers::generate_safe_prime: <<< safe_prime: {:?}", secret!(&safe_prime));
+    trace!("Helpers::generate_safe_prime_with_callback: <<< safe_prime: {:?}", secret!(&safe_prime));
 
     Ok(safe_prime)
 }
 
+/// Source of random values of issuer keys, so keys are generated the same way from any source.
+pub trait KeyGenRandomness {
+    fn safe_prime(&mut self, size: usize,
+                  on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError>;
+    fn random_qr(&mut self, n: &BigNumber) -> Result<BigNumber, IndyCryptoError>;
+    fn gen_x(&mut self, p: &BigNumber, q: &BigNumber) -> Result<BigNumber, IndyCryptoError>;
+    fn group_order_element(&mut self) -> Result<GroupOrderElement, IndyCryptoError>;
+    fn point_g1(&mut self) -> Result<PointG1, IndyCryptoError>;
+    fn point_g2(&mut self) -> Result<PointG2, IndyCryptoError>;
+}
+
+/// Randomness of OpenSSL and AMCL, used for all keys except seeded ones.
+pub struct SystemRandomness;
+
+impl KeyGenRandomness for SystemRandomness {
+    fn safe_prime(&mut self, size: usize,
+                  on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
+        generate_safe_prime_with_callback(size, on_candidate)
+    }
+
+    fn random_qr(&mut self, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        random_qr(n)
+    }
+
+    fn gen_x(&mut self, p: &BigNumber, q: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        gen_x(p, q)
+    }
+
+    fn group_order_element(&mut self) -> Result<GroupOrderElement, IndyCryptoError> {
+        GroupOrderElement::new()
+    }
+
+    fn point_g1(&mut self) -> Result<PointG1, IndyCryptoError> {
+        PointG1::new()
+    }
+
+    fn point_g2(&mut self) -> Result<PointG2, IndyCryptoError> {
+        PointG2::new()
+    }
+}
+
+#[cfg(feature = "parallel")]
+enum SafePrimeSearchEvent {
+    Candidate,
+    Finished(Result<BigNumber, IndyCryptoError>)
+}
+
+// Runs a search per thread of rayon pool and takes the first prime found. Every candidate starts from
+// fresh randomness of OpenSSL DRBG, that is separately seeded for every thread, so the first prime found
+// by any worker is distributed as the prime of a single search.
+// Workers are scoped threads rather than jobs of the pool, as this thread calls `on_candidate` meanwhile.
+#[cfg(feature = "parallel")]
+fn _generate_safe_prime_in_parallel(size: usize,
+                                    on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
+    let workers = rayon::current_num_threads();
+    if workers < 2 {
+        return BigNumber::generate_safe_prime_with_callback(size, on_candidate);
+    }
+
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let stop = &stop;
+            scope.spawn(move || {
+                let res = BigNumber::generate_safe_prime_with_callback(size, &mut || {
+                    if stop.load(Ordering::Relaxed) {
+                        return Err(IndyCryptoError::Cancelled("Safe prime is found by other worker".to_string()));
+                    }
+                    let _ = sender.send(SafePrimeSearchEvent::Candidate);
+                    Ok(())
+                });
+                let _ = sender.send(SafePrimeSearchEvent::Finished(res));
+            });
+        }
+        drop(sender);
+
+        // Ends when all workers are finished
+        let mut res: Option<Result<BigNumber, IndyCryptoError>> = None;
+        for event in receiver {
+            match (event, res.is_some()) {
+                (SafePrimeSearchEvent::Candidate, false) => {
+                    let candidate_res = panic::catch_unwind(AssertUnwindSafe(&mut *on_candidate))
+                        .unwrap_or_else(|_| Err(IndyCryptoError::InvalidState("Prime candidate callback panicked".to_string())));
+                    if let Err(err) = candidate_res {
+                        stop.store(true, Ordering::Relaxed);
+                        res = Some(Err(err));
+                    }
+                }
+                (SafePrimeSearchEvent::Finished(worker_res), false) => {
+                    stop.store(true, Ordering::Relaxed);
+                    res = Some(worker_res);
+                }
+                (SafePrimeSearchEvent::Finished(Ok(mut prime)), true) => prime.zeroize(),
+                _ => {}
+            }
+        }
+
+        res.unwrap_or_else(|| Err(IndyCryptoError::InvalidState("Safe prime search has no workers".to_string())))
+    })
+}
+
 #[cfg(test)]
 pub fn gen_x(p: &BigNumber, q: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
     if MockHelper::is_injected() {
@@ -297,28 +418,20 @@ pub fn calc_teq(p_pub_key: &CredentialPrimaryPublicKey,
     trace!("Helpers::calc_teq: >>> p_pub_key: {:?}, p_pub_key: {:?}, e: {:?}, v: {:?}, m_tilde: {:?}, m2tilde: {:?}, \
     unrevealed_attrs: {:?}", p_pub_key, a_prime, e, v, m_tilde, m2tilde, unrevealed_attrs);
 
-    let mut ctx = BigNumber::new_context()?;
-    let mut result: BigNumber = a_prime
-        .mod_exp(&e, &p_pub_key.n, Some(&mut ctx))?;
+    let mut bases = vec![a_prime, &p_pub_key.s, &p_pub_key.rctxt];
+    let mut exps = vec![e, v, m2tilde];
 
     for k in unrevealed_attrs.iter() {
         let cur_r = p_pub_key.r.get(k)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", k)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", k)))?;
         let cur_m = m_tilde.get(k)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in m_tilde", k)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in m_tilde", k)))?;
 
-        result = cur_r
-            .mod_exp(&cur_m, &p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+        bases.push(cur_r);
+        exps.push(cur_m);
     }
 
-    result = p_pub_key.s
-        .mod_exp(&v, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
-
-    result = p_pub_key.rctxt
-        .mod_exp(&m2tilde, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&result, &p_pub_key.n, Some(&mut ctx))?;
+    let result = BigNumber::multi_mod_exp(&bases, &exps, &p_pub_key.n, None)?;
 
     trace!("Helpers::calc_teq: <<< t: {:?}", result);
 
@@ -339,53 +452,41 @@ pub fn calc_tne(p_pub_key: &CredentialPrimaryPublicKey,
 
     for i in 0..ITERATION {
         let cur_u = u.get(&i.to_string())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
         let cur_r = r.get(&i.to_string())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", i)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", i)))?;
 
-        let t_tau = p_pub_key.z
-            .mod_exp(&cur_u, &p_pub_key.n, Some(&mut ctx))?
-            .mod_mul(
-                &p_pub_key.s.mod_exp(&cur_r, &p_pub_key.n, Some(&mut ctx))?,
-                &p_pub_key.n, Some(&mut ctx)
-            )?;
+        let t_tau = BigNumber::multi_mod_exp(&[&p_pub_key.z, &p_pub_key.s], &[cur_u, cur_r], &p_pub_key.n, Some(&mut ctx))?;
 
         tau_list.push(t_tau);
     }
 
     let delta = r.get("DELTA")
-        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", "DELTA")))?;
+        .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in r", "DELTA")))?;
     let delta_predicate = if is_less {
         delta.set_negative(true)?
     } else {
         delta.clone()?
     };
 
-    let t_tau = p_pub_key.z
-        .mod_exp(&mj, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(
-            &p_pub_key.s.mod_exp(&delta_predicate, &p_pub_key.n, Some(&mut ctx))?,
-            &p_pub_key.n, Some(&mut ctx)
-        )?;
+    let t_tau = BigNumber::multi_mod_exp(&[&p_pub_key.z, &p_pub_key.s], &[mj, &delta_predicate], &p_pub_key.n, Some(&mut ctx))?;
 
     tau_list.push(t_tau);
 
-    let mut q: BigNumber = BIGNUMBER_1.clone()?;
+    let mut bases = vec![&p_pub_key.s];
+    let mut exps = vec![alpha];
 
     for i in 0..ITERATION {
         let cur_t = t.get(&i.to_string())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in t", i)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in t", i)))?;
         let cur_u = u.get(&i.to_string())
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u", i)))?;
 
-        q = cur_t
-            .mod_exp(&cur_u, &p_pub_key.n, Some(&mut ctx))?
-            .mul(&q, Some(&mut ctx))?;
+        bases.push(cur_t);
+        exps.push(cur_u);
     }
 
-    q = p_pub_key.s
-        .mod_exp(&alpha, &p_pub_key.n, Some(&mut ctx))?
-        .mod_mul(&q, &p_pub_key.n, Some(&mut ctx))?;
+    let q = BigNumber::multi_mod_exp(&bases, &exps, &p_pub_key.n, Some(&mut ctx))?;
 
     tau_list.push(q);
 
@@ -394,21 +495,42 @@ pub fn calc_tne(p_pub_key: &CredentialPrimaryPublicKey,
     Ok(tau_list)
 }
 
-fn largest_square_less_than(delta: usize) -> usize {
-    (delta as f64).sqrt().floor() as usize
+// Largest delta of predicates: difference of two i32 values
+const MAX_FOUR_SQUARES_DELTA: i64 = u32::MAX as i64;
+
+fn largest_square_less_than(delta: u64) -> u64 {
+    // f64 is exact for delta below 2^53, the root is corrected for rounding anyway
+    let mut root = (delta as f64).sqrt().floor() as u64;
+    while root * root > delta {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= delta {
+        root += 1;
+    }
+    root
 }
 
 //Express the natural number `delta` as a sum of four integer squares,
 // i.e `delta = a^2 + b^2 + c^2 + d^2` using Lagrange's four-square theorem
-pub fn four_squares(delta: i32) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
+pub fn four_squares(delta: i64) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
     trace!("Helpers::four_squares: >>> delta: {:?}", delta);
 
     if delta < 0 {
         return Err(IndyCryptoError::InvalidStructure(format!("Cannot express a negative number as sum of four squares {} ", delta)));
     }
+    if delta > MAX_FOUR_SQUARES_DELTA {
+        return Err(IndyCryptoError::InvalidStructure(format!("Number {} is out of range of predicate deltas", delta)));
+    }
 
-    let d = delta as usize;
-    let mut roots: [usize; 4] = [largest_square_less_than(d), 0, 0, 0];
+    // Roots of 4^k * m are 2^k times roots of m, the search below is slow for multiples of large powers of 4
+    let mut d = delta as u64;
+    let mut scale = 1u64;
+    while d != 0 && d.is_multiple_of(4) {
+        d /= 4;
+        scale *= 2;
+    }
+
+    let mut roots: [u64; 4] = [largest_square_less_than(d), 0, 0, 0];
 
     'outer: for i in (1..roots[0] + 1).rev() {
         roots[0] = i;
@@ -441,6 +563,10 @@ pub fn four_squares(delta: i32) -> Result<HashMap<String, BigNumber>, IndyCrypto
         }
     }
 
+    for root in roots.iter_mut() {
+        *root *= scale;
+    }
+
     let res = hashmap![
         "0".to_string() => BigNumber::from_dec(&roots[0].to_string()[..])?,
         "1".to_string() => BigNumber::from_dec(&roots[1].to_string()[..])?,
@@ -461,7 +587,33 @@ pub fn bignum_to_group_element(num: &BigNumber) -> Result<GroupOrderElement, Ind
     Ok(GroupOrderElement::from_bytes(&num.to_bytes()?)?)
 }
 
+/// Pairings of `CredentialRevocationPublicKey` terms of `create_tau_list_values`, that are the same
+/// for all non revocation proofs. Both prover and verifier need them.
+#[derive(Debug)]
+pub struct RevocationPublicKeyPairings {
+    htilde_h_cap: Pair,
+    htilde_y: Pair,
+    htilde_u: Pair,
+    h1_h_cap: Pair,
+    h2_h_cap: Pair,
+    g_neg_h_cap: Pair
+}
+
+impl RevocationPublicKeyPairings {
+    pub fn new(r_pub_key: &CredentialRevocationPublicKey) -> Result<RevocationPublicKeyPairings, IndyCryptoError> {
+        Ok(RevocationPublicKeyPairings {
+            htilde_h_cap: Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?,
+            htilde_y: Pair::pair(&r_pub_key.htilde, &r_pub_key.y)?,
+            htilde_u: Pair::pair(&r_pub_key.htilde, &r_pub_key.u)?,
+            h1_h_cap: Pair::pair(&r_pub_key.h1, &r_pub_key.h_cap)?,
+            h2_h_cap: Pair::pair(&r_pub_key.h2, &r_pub_key.h_cap)?,
+            g_neg_h_cap: Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?
+        })
+    }
+}
+
 pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey,
+                                       g_g_dash: &Pair,
                                        rev_reg: &RevocationRegistry,
                                        rev_acc_pub_key: &RevocationKeyPublic,
                                        proof_c: &NonRevocProofCList) -> Result<NonRevocProofTauList, IndyCryptoError> {
@@ -477,7 +629,7 @@ pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey
     let t5 = proof_c.d;
     let t6 = PointG1::new_inf()?;
     let t7 = Pair::pair(&r_pub_key.pk.add(&proof_c.g)?, &proof_c.s)?
-        .mul(&Pair::pair(&r_pub_key.g, &r_pub_key.g_dash)?.inverse()?)?;
+        .mul(&g_g_dash.inverse()?)?;
     let t8 = Pair::pair(&proof_c.g, &r_pub_key.u)?
         .mul(&Pair::pair(&r_pub_key.g, &proof_c.u)?.inverse()?)?;
 
@@ -498,6 +650,7 @@ pub fn create_tau_list_expected_values(r_pub_key: &CredentialRevocationPublicKey
 }
 
 pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
+                              r_pub_key_pairings: &RevocationPublicKeyPairings,
                               rev_reg: &RevocationRegistry,
                               params: &NonRevocProofXList,
                               proof_c: &NonRevocProofCList) -> Result<NonRevocProofTauList, IndyCryptoError> {
@@ -512,14 +665,14 @@ pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
         t2 = PointG1::new_inf()?;
     }
     let t3 = Pair::pair(&proof_c.a, &r_pub_key.h_cap)?.pow(&params.c)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.r)?)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.y)?.pow(&params.rho)?
-            .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.m)?)?
-            .mul(&Pair::pair(&r_pub_key.h1, &r_pub_key.h_cap)?.pow(&params.m2)?)?
-            .mul(&Pair::pair(&r_pub_key.h2, &r_pub_key.h_cap)?.pow(&params.s)?)?.inverse()?)?;
+        .mul(&r_pub_key_pairings.htilde_h_cap.pow(&params.r)?)?
+        .mul(&r_pub_key_pairings.htilde_y.pow(&params.rho)?
+            .mul(&r_pub_key_pairings.htilde_h_cap.pow(&params.m)?)?
+            .mul(&r_pub_key_pairings.h1_h_cap.pow(&params.m2)?)?
+            .mul(&r_pub_key_pairings.h2_h_cap.pow(&params.s)?)?.inverse()?)?;
     let t4 = Pair::pair(&r_pub_key.htilde, &rev_reg.accum)?
         .pow(&params.r)?
-        .mul(&Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?.pow(&params.r_prime)?)?;
+        .mul(&r_pub_key_pairings.g_neg_h_cap.pow(&params.r_prime)?)?;
     let t5 = r_pub_key.g.mul(&params.r)?.add(&r_pub_key.htilde.mul(&params.o_prime)?)?;
     let mut t6 = proof_c.d.mul(&params.r_prime_prime)?
         .add(&r_pub_key.g.mul(&params.m_prime.mod_neg()?)?)?
@@ -528,10 +681,10 @@ pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
         t6 = PointG1::new_inf()?;
     }
     let t7 = Pair::pair(&r_pub_key.pk.add(&proof_c.g)?, &r_pub_key.h_cap)?.pow(&params.r_prime_prime)?
-        .mul(&Pair::pair(&r_pub_key.htilde, &r_pub_key.h_cap)?.pow(&params.m_prime.mod_neg()?)?)?
+        .mul(&r_pub_key_pairings.htilde_h_cap.pow(&params.m_prime.mod_neg()?)?)?
         .mul(&Pair::pair(&r_pub_key.htilde, &proof_c.s)?.pow(&params.r)?)?;
-    let t8 = Pair::pair(&r_pub_key.htilde, &r_pub_key.u)?.pow(&params.r)?
-        .mul(&Pair::pair(&r_pub_key.g.neg()?, &r_pub_key.h_cap)?.pow(&params.r_prime_prime_prime)?)?;
+    let t8 = r_pub_key_pairings.htilde_u.pow(&params.r)?
+        .mul(&r_pub_key_pairings.g_neg_h_cap.pow(&params.r_prime_prime_prime)?)?;
 
     let non_revoc_proof_tau_list = NonRevocProofTauList {
         t1,
@@ -549,10 +702,153 @@ pub fn create_tau_list_values(r_pub_key: &CredentialRevocationPublicKey,
     Ok(non_revoc_proof_tau_list)
 }
 
+// Bits of exponent per precomputed power of `FixedBaseExp`
+const FIXED_BASE_WINDOW: usize = 4;
+
+/// Exponentiation with fixed base and modulus by precomputed powers `base^(16^i)`
+/// (Brickell-Gordon-McCurley-Wilson method), that takes about 4 times less multiplications than `mod_exp`.
+/// Negative exponents and exponents longer than precomputed are computed by `mod_exp`.
+pub struct FixedBaseExp {
+    modulus: BigNumber,
+    powers: Vec<BigNumber>
+}
+
+impl FixedBaseExp {
+    pub fn new(base: &BigNumber, modulus: &BigNumber, max_exp_bits: usize, ctx: &mut BigNumberContext) -> Result<FixedBaseExp, IndyCryptoError> {
+        let mut powers = vec![base.modulus(modulus, Some(ctx))?];
+
+        for _ in 1..max_exp_bits.div_ceil(FIXED_BASE_WINDOW) {
+            let mut power = powers[powers.len() - 1].clone()?;
+            for _ in 0..FIXED_BASE_WINDOW {
+                power = power.mod_mul(&power, modulus, Some(ctx))?;
+            }
+            powers.push(power);
+        }
+
+        Ok(FixedBaseExp { modulus: modulus.clone()?, powers })
+    }
+
+    pub fn exp(&self, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        if exp.is_negative() || exp.num_bits()? as usize > self.powers.len() * FIXED_BASE_WINDOW {
+            return self.powers[0].mod_exp(exp, &self.modulus, Some(ctx));
+        }
+
+        // Little endian digits of the exponent
+        let digits: Vec<u8> = exp.to_bytes()?
+            .iter()
+            .rev()
+            .flat_map(|byte| vec![byte & 0x0f, byte >> 4])
+            .collect();
+
+        // Power of every digit value is multiplied to the result as many times, as the value is
+        let mut result: Option<BigNumber> = None;
+        let mut digit_product: Option<BigNumber> = None;
+
+        for digit in (1..1 << FIXED_BASE_WINDOW).rev() {
+            for (power, _) in self.powers.iter().zip(&digits).filter(|&(_, &d)| d == digit) {
+                digit_product = Some(match digit_product {
+                    Some(product) => product.mod_mul(power, &self.modulus, Some(ctx))?,
+                    None => power.clone()?
+                });
+            }
+
+            if let Some(ref product) = digit_product {
+                result = Some(match result {
+                    Some(result) => result.mod_mul(product, &self.modulus, Some(ctx))?,
+                    None => product.clone()?
+                });
+            }
+        }
+
+        match result {
+            Some(result) => Ok(result),
+            None => BigNumber::from_u32(1)?.modulus(&self.modulus, Some(ctx))
+        }
+    }
+}
+
+/// Exponentiation modulo `p * q` with known primes by Chinese remainder theorem, that is about
+/// 3 times faster than `mod_exp` for equal sized primes. Base must be coprime to the modulus.
+pub struct CrtModExp {
+    p: BigNumber,
+    q: BigNumber,
+    p_minus_one: BigNumber,
+    q_minus_one: BigNumber,
+    q_inverse: BigNumber
+}
+
+impl CrtModExp {
+    pub fn new(p: &BigNumber, q: &BigNumber, ctx: &mut BigNumberContext) -> Result<CrtModExp, IndyCryptoError> {
+        Ok(CrtModExp {
+            p: p.clone()?,
+            q: q.clone()?,
+            p_minus_one: p.decrement()?,
+            q_minus_one: q.decrement()?,
+            q_inverse: q.inverse(p, Some(ctx))?
+        })
+    }
+
+    pub fn exp(&self, base: &BigNumber, exp: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let exp_p = exp.modulus(&self.p_minus_one, Some(ctx))?;
+        let exp_q = exp.modulus(&self.q_minus_one, Some(ctx))?;
+
+        let res_p = base.modulus(&self.p, Some(ctx))?.mod_exp(&exp_p, &self.p, Some(ctx))?;
+        let res_q = base.modulus(&self.q, Some(ctx))?.mod_exp(&exp_q, &self.q, Some(ctx))?;
+
+        // Garner's formula: res_q + q * ((res_p - res_q) * q^-1 mod p)
+        let h = res_p.mod_sub(&res_q, &self.p, Some(ctx))?
+            .mod_mul(&self.q_inverse, &self.p, Some(ctx))?;
+
+        h.mul(&self.q, Some(ctx))?.add(&res_q)
+    }
+}
+
+impl Drop for CrtModExp {
+    fn drop(&mut self) {
+        self.p.zeroize();
+        self.q.zeroize();
+        self.p_minus_one.zeroize();
+        self.q_minus_one.zeroize();
+        self.q_inverse.zeroize();
+    }
+}
+
+/// Checks indices of sub proofs linked by an attribute: two distinct indices at least, all of added sub proofs.
+pub fn check_linked_sub_proof_indices(sub_proofs_count: usize, sub_proof_indices: &[usize]) -> Result<(), IndyCryptoError> {
+    if sub_proof_indices.len() < 2 {
+        return Err(IndyCryptoError::InvalidStructure("Two sub proofs at least must be linked".to_string()));
+    }
+
+    let mut indices = HashSet::new();
+    for &idx in sub_proof_indices {
+        if idx >= sub_proofs_count {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Sub proof {} isn't added, {} sub proofs are added", idx, sub_proofs_count)));
+        }
+        if !indices.insert(idx) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Sub proof {} is linked twice", idx)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Compares bytes in time that depends only on their lengths, not on the position of the first difference.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y));
+    unsafe { ptr::read_volatile(&diff) == 0 }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bn::BIGNUMBER_1;
     use crate::cl::{issuer, prover};
+    use crate::errors::ErrorKind;
 
     #[test]
     fn encode_attribute_works() {
@@ -579,7 +875,7 @@ mod tests {
 
     #[test]
     fn four_squares_works() {
-        let res = four_squares(107 as i32);
+        let res = four_squares(107);
         let res_data = res.unwrap();
 
         assert_eq!("9".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
@@ -587,16 +883,17 @@ mod tests {
         assert_eq!("1".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
         assert_eq!("0".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
 
-        let res = four_squares(112 as i32);
+        let res = four_squares(112);
         let res_data = res.unwrap();
 
-        assert_eq!("10".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("1").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
-        assert_eq!("2".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
+        // 112 = 16 * 7, 7 = 2^2 + 1^2 + 1^2 + 1^2
+        assert_eq!("8".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
+        assert_eq!("4".to_string(), res_data.get("1").unwrap().to_dec().unwrap());
+        assert_eq!("4".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
+        assert_eq!("4".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
 
 
-        let res = four_squares(253 as i32);
+        let res = four_squares(253);
         let res_data = res.unwrap();
 
         assert_eq!("14".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
@@ -604,7 +901,7 @@ mod tests {
         assert_eq!("2".to_string(), res_data.get("2").unwrap().to_dec().unwrap());
         assert_eq!("2".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
 
-        let res = four_squares(1506099439 as i32);
+        let res = four_squares(1506099439);
         let res_data = res.unwrap();
 
         assert_eq!("38807".to_string(), res_data.get("0").unwrap().to_dec().unwrap());
@@ -613,6 +910,23 @@ mod tests {
         assert_eq!("11".to_string(), res_data.get("3").unwrap().to_dec().unwrap());
     }
 
+    #[test]
+    fn four_squares_works_for_range_bounds() {
+        for &delta in &[0i64, 1, 1 << 25, 3 << 28, i32::MAX as i64, i32::MAX as i64 + 1, u32::MAX as i64 - 3, u32::MAX as i64] {
+            let res = four_squares(delta).unwrap();
+            let sum = (0..4).fold(0u64, |sum, i| {
+                let root = res[&i.to_string()].to_dec().unwrap().parse::<u64>().unwrap();
+                sum + root * root
+            });
+            assert_eq!(delta as u64, sum, "{}", delta);
+        }
+
+        assert_eq!(ErrorKind::InvalidStructure, four_squares(-1).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, four_squares(u32::MAX as i64 + 1).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, four_squares(i64::MAX).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, four_squares(i64::MIN).unwrap_err().kind());
+    }
+
     #[test]
     fn transform_u32_to_array_of_u8_works() {
         let int = 0x74BA7445;
@@ -697,4 +1011,87 @@ mod tests {
         23513431038543455953776634428397886712772493416769127589401268192936635379602026733723945633\
         53933943790374230983129060596346889726181201177754774157687114812348019929279", res.unwrap().to_dec().unwrap());
     }
+
+    #[test]
+    fn fixed_base_exp_works() {
+        let pk = issuer::mocks::credential_primary_public_key();
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        let fixed_base_exp = FixedBaseExp::new(&pk.s, &pk.n, 600, &mut ctx).unwrap();
+
+        let mut exps = vec![BigNumber::from_u32(0).unwrap(),
+                            BigNumber::from_u32(1).unwrap(),
+                            BigNumber::from_u32(15).unwrap(),
+                            BigNumber::from_u32(16).unwrap(),
+                            BigNumber::from_u32(0xffffffff).unwrap(),
+                            BIGNUMBER_1.lshift1().unwrap().exp(&BigNumber::from_u32(600).unwrap(), None).unwrap().decrement().unwrap(),
+                            // Longer than precomputed
+                            BIGNUMBER_1.lshift1().unwrap().exp(&BigNumber::from_u32(600).unwrap(), None).unwrap()];
+        for bits in &[8, 256, 599, 600, 601, 2724] {
+            exps.push(BigNumber::rand(*bits).unwrap());
+        }
+
+        for exp in &exps {
+            assert_eq!(pk.s.mod_exp(exp, &pk.n, Some(&mut ctx)).unwrap(),
+                       fixed_base_exp.exp(exp, &mut ctx).unwrap(), "{:?}", exp);
+        }
+
+        // Without precomputed powers
+        let fixed_base_exp = FixedBaseExp::new(&pk.s, &pk.n, 0, &mut ctx).unwrap();
+        for exp in &exps {
+            assert_eq!(pk.s.mod_exp(exp, &pk.n, Some(&mut ctx)).unwrap(),
+                       fixed_base_exp.exp(exp, &mut ctx).unwrap(), "{:?}", exp);
+        }
+    }
+
+    #[test]
+    fn crt_mod_exp_works() {
+        let pk = issuer::mocks::credential_primary_public_key();
+        let sk = issuer::mocks::credential_primary_private_key();
+        let mut ctx = BigNumber::new_context().unwrap();
+
+        let crt_mod_exp = CrtModExp::new(&sk.p.lshift1().unwrap().increment().unwrap(),
+                                         &sk.q.lshift1().unwrap().increment().unwrap(),
+                                         &mut ctx).unwrap();
+
+        for bits in &[0, 1, 256, 2046, 2724] {
+            let exp = BigNumber::rand(*bits).unwrap();
+            for base in &[&pk.s, &pk.z, &BigNumber::from_u32(1).unwrap()] {
+                assert_eq!(base.mod_exp(&exp, &pk.n, Some(&mut ctx)).unwrap(),
+                           crt_mod_exp.exp(base, &exp, &mut ctx).unwrap(), "{:?}", exp);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn generate_safe_prime_in_parallel_works() {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+        let mut candidates = 0;
+        let prime = pool.install(|| _generate_safe_prime_with_callback(256, &mut || {
+            candidates += 1;
+            Ok(())
+        })).unwrap();
+        assert!(candidates > 0);
+        assert_eq!(257, prime.num_bits().unwrap());
+        assert!(prime.is_safe_prime(None).unwrap());
+
+        let mut candidates = 0;
+        let res = pool.install(|| _generate_safe_prime_with_callback(LARGE_PRIME, &mut || {
+            candidates += 1;
+            if candidates == 10 { Err(IndyCryptoError::Cancelled("stop".to_string())) } else { Ok(()) }
+        }));
+        assert_eq!(IndyCryptoError::Cancelled("stop".to_string()), res.unwrap_err());
+        assert_eq!(10, candidates);
+    }
+
+    #[test]
+    fn constant_time_eq_works() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(constant_time_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[0, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2]));
+    }
 }
@@ -8,6 +8,13 @@ use crate::cl::commitment::get_pedersen_commitment;
 use crate::cl::hash::get_hash_as_int;
 
 use std::collections::{HashMap, HashSet};
+use std::panic::{self, AssertUnwindSafe};
+
+#[cfg(feature = "seeded_keys")]
+use crate::cl::seeded::SeededRandomness;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Trust source that provides credentials to prover.
 pub struct Issuer {}
@@ -64,12 +71,136 @@ impl Issuer {
                                                                    CredentialKeyCorrectnessProof), IndyCryptoError> {
         trace!("Issuer::new_credential_def: >>> credential_schema: {:?}, support_revocation: {:?}", credential_schema, support_revocation);
 
+        let res = Issuer::new_credential_def_with_progress(credential_schema, non_credential_schema, support_revocation, &mut |_| true)?;
+
+        trace!("Issuer::new_credential_def: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               res.0, secret!(&res.1), res.2);
+
+        Ok(res)
+    }
+
+    /// Creates credential definition like `new_credential_def`, reporting progress of the generation.
+    ///
+    /// Search of the safe primes of the modulus takes most of the time, it may last minutes on slow hardware.
+    /// `progress` is called on the same thread with every event and returns false to cancel the generation,
+    /// then `Cancelled` error is returned. Panic of `progress` cancels the generation the same way.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `non_credential_schema` - Non credential schema entity.
+    /// * `support_revocation` - If true non revocation part of keys will be generated.
+    /// * `progress` - Callback called with progress events, returns true to continue.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::{Issuer, KeyGenProgress};
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+    /// non_credential_schema_builder.add_attr("master_secret").unwrap();
+    /// let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut primes_found = 0;
+    /// let (_cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_with_progress(&credential_schema, &non_credential_schema, false, &mut |event| {
+    ///         if event == KeyGenProgress::PrimeFound {
+    ///             primes_found += 1;
+    ///         }
+    ///         true
+    ///     }).unwrap();
+    /// assert_eq!(2, primes_found);
+    /// ```
+    pub fn new_credential_def_with_progress(credential_schema: &CredentialSchema,
+                                            non_credential_schema: &NonCredentialSchema,
+                                            support_revocation: bool,
+                                            progress: &mut dyn FnMut(KeyGenProgress) -> bool) -> Result<(CredentialPublicKey,
+                                                                                                        CredentialPrivateKey,
+                                                                                                        CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::new_credential_def_with_progress: >>> credential_schema: {:?}, support_revocation: {:?}", credential_schema, support_revocation);
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, non_credential_schema, support_revocation,
+                                        &mut KeyGenProgressReporter { progress }, &mut SystemRandomness)?;
+
+        trace!("Issuer::new_credential_def_with_progress: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               cred_pub_key, secret!(&cred_priv_key), cred_key_correctness_proof);
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    /// Creates credential definition like `new_credential_def` with all random values derived from `seed`,
+    /// so the same seed gives the same keys on all platforms.
+    ///
+    /// **Test/dev only — do not use in production.** Anyone who knows or guesses the seed has the private key.
+    /// Available with `seeded_keys` feature only, so production builds can't call it by mistake.
+    ///
+    /// Random bytes are SHA-256 in counter mode: `key = SHA-256("indy-crypto seeded issuer keys v1" || seed)`,
+    /// block `i` is `SHA-256(key || i)` with `i` as 8 bytes big-endian. Primes and exponents are taken
+    /// from the bytes in fixed order, attributes are processed in sorted order.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema entity.
+    /// * `non_credential_schema` - Non credential schema entity.
+    /// * `support_revocation` - If true non revocation part of keys will be generated.
+    /// * `seed` - Seed of keys, 32 bytes at least.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+    /// non_credential_schema_builder.add_attr("master_secret").unwrap();
+    /// let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+    ///
+    /// let seed = [1u8; 32];
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) =
+    ///     Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, false, &seed).unwrap();
+    /// let (same_cred_pub_key, _, _) =
+    ///     Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, false, &seed).unwrap();
+    /// assert_eq!(cred_pub_key, same_cred_pub_key);
+    /// ```
+    #[cfg(feature = "seeded_keys")]
+    pub fn new_credential_def_from_seed(credential_schema: &CredentialSchema,
+                                        non_credential_schema: &NonCredentialSchema,
+                                        support_revocation: bool,
+                                        seed: &[u8]) -> Result<(CredentialPublicKey,
+                                                                CredentialPrivateKey,
+                                                                CredentialKeyCorrectnessProof), IndyCryptoError> {
+        trace!("Issuer::new_credential_def_from_seed: >>> credential_schema: {:?}, support_revocation: {:?}", credential_schema, support_revocation);
+
+        let mut rng = SeededRandomness::new(seed)?;
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::_new_credential_def(credential_schema, non_credential_schema, support_revocation,
+                                        &mut KeyGenProgressReporter { progress: &mut |_| true }, &mut rng)?;
+
+        trace!("Issuer::new_credential_def_from_seed: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
+               cred_pub_key, secret!(&cred_priv_key), cred_key_correctness_proof);
+
+        Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
+    }
+
+    fn _new_credential_def(credential_schema: &CredentialSchema,
+                           non_credential_schema: &NonCredentialSchema,
+                           support_revocation: bool,
+                           progress: &mut KeyGenProgressReporter,
+                           rng: &mut dyn KeyGenRandomness) -> Result<(CredentialPublicKey,
+                                                                      CredentialPrivateKey,
+                                                                      CredentialKeyCorrectnessProof), IndyCryptoError> {
         let (p_pub_key, p_priv_key, p_key_meta) =
-            Issuer::_new_credential_primary_keys(credential_schema, non_credential_schema)?;
+            Issuer::_new_credential_primary_keys(credential_schema, non_credential_schema, progress, rng)?;
+        progress.notify(KeyGenProgress::ComponentFinished(KeyGenComponent::PrimaryKeys))?;
 
         let (r_pub_key, r_priv_key) = if support_revocation {
-            Issuer::_new_credential_revocation_keys()
-                .map(|(r_pub_key, r_priv_key)| (Some(r_pub_key), Some(r_priv_key)))?
+            let (r_pub_key, r_priv_key) = Issuer::_new_credential_revocation_keys(rng)?;
+            progress.notify(KeyGenProgress::ComponentFinished(KeyGenComponent::RevocationKeys))?;
+            (Some(r_pub_key), Some(r_priv_key))
         } else {
             (None, None)
         };
@@ -79,10 +210,9 @@ impl Issuer {
         let cred_key_correctness_proof =
             Issuer::_new_credential_key_correctness_proof(&cred_pub_key.p_key,
                                                           &cred_priv_key.p_key,
-                                                          &p_key_meta)?;
-
-        trace!("Issuer::new_credential_def: <<< cred_pub_key: {:?}, cred_priv_key: {:?}, cred_key_correctness_proof: {:?}",
-               cred_pub_key, secret!(&cred_priv_key), cred_key_correctness_proof);
+                                                          &p_key_meta,
+                                                          rng)?;
+        progress.notify(KeyGenProgress::ComponentFinished(KeyGenComponent::KeyCorrectnessProof))?;
 
         Ok((cred_pub_key, cred_priv_key, cred_key_correctness_proof))
     }
@@ -119,26 +249,64 @@ impl Issuer {
                                                                              RevocationKeyPrivate,
                                                                              RevocationRegistry,
                                                                              RevocationTailsGenerator), IndyCryptoError> {
-        trace!("Issuer::new_revocation_registry_def: >>> credential_pub_key: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}",
-               credential_pub_key, max_cred_num, issuance_by_default);
+        Issuer::new_revocation_registry_def_with_issuance_type(credential_pub_key, max_cred_num, IssuanceType::from(issuance_by_default))
+    }
+
+    /// Creates and returns revocation registry definition as `new_revocation_registry_def`.
+    ///
+    /// Type of issuance is stored in revocation key public, so provers can create witnesses
+    /// by `Witness::new_for_registry` without knowing it.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key entity.
+    /// * `max_cred_num` - Max credential number in generated registry.
+    /// * `issuance_type` - Type of issuance.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::IssuanceType;
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+    /// non_credential_schema_builder.add_attr("master_secret").unwrap();
+    /// let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+    ///
+    /// let (rev_key_pub, _rev_key_priv, _rev_reg, _rev_tails_generator) =
+    ///     Issuer::new_revocation_registry_def_with_issuance_type(&cred_pub_key, 5, IssuanceType::IssuanceOnDemand).unwrap();
+    /// assert_eq!(Some(IssuanceType::IssuanceOnDemand), rev_key_pub.issuance_type());
+    /// ```
+    pub fn new_revocation_registry_def_with_issuance_type(credential_pub_key: &CredentialPublicKey,
+                                                          max_cred_num: u32,
+                                                          issuance_type: IssuanceType) -> Result<(RevocationKeyPublic,
+                                                                                                  RevocationKeyPrivate,
+                                                                                                  RevocationRegistry,
+                                                                                                  RevocationTailsGenerator), IndyCryptoError> {
+        trace!("Issuer::new_revocation_registry_def_with_issuance_type: >>> credential_pub_key: {:?}, max_cred_num: {:?}, issuance_type: {:?}",
+               credential_pub_key, max_cred_num, issuance_type);
 
         let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
             .as_ref()
-            .ok_or(IndyCryptoError::InvalidStructure(format!("There are not revocation keys in the credential public key.")))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("There are not revocation keys in the credential public key.")))?;
 
-        let (rev_key_pub, rev_key_priv) = Issuer::_new_revocation_registry_keys(cred_rev_pub_key, max_cred_num)?;
+        let (rev_key_pub, rev_key_priv) = Issuer::_new_revocation_registry_keys(cred_rev_pub_key, max_cred_num, issuance_type)?;
 
         let rev_reg = Issuer::_new_revocation_registry(cred_rev_pub_key,
                                                        &rev_key_priv,
                                                        max_cred_num,
-                                                       issuance_by_default)?;
+                                                       issuance_type.is_by_default())?;
 
         let rev_tails_generator = RevocationTailsGenerator::new(
             max_cred_num,
             rev_key_priv.gamma.clone(),
             cred_rev_pub_key.g_dash.clone());
 
-        trace!("Issuer::new_revocation_registry_def: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
+        trace!("Issuer::new_revocation_registry_def_with_issuance_type: <<< rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, rev_tails_generator: {:?}",
                rev_key_pub, secret!(&rev_key_priv), rev_reg, rev_tails_generator);
 
         Ok((rev_key_pub, rev_key_priv, rev_reg, rev_tails_generator))
@@ -239,28 +407,15 @@ impl Issuer {
                                             credential_pub_key,
                                             secret!(credential_priv_key));
 
-        Issuer::_check_blinded_credential_secrets_correctness_proof(blinded_credential_secrets,
-                                                               blinded_credential_secrets_correctness_proof,
-                                                               credential_nonce,
-                                                               &credential_pub_key.p_key)?;
-
-        // In the anoncreds whitepaper, `credential context` is denoted by `m2`
-        let cred_context = Issuer::_gen_credential_context(prover_id, None)?;
-
-        let (p_cred, q) = Issuer::_new_primary_credential(&cred_context,
-                                                          credential_pub_key,
-                                                          credential_priv_key,
-                                                          blinded_credential_secrets,
-                                                          credential_values)?;
-
-        let cred_signature = CredentialSignature { p_credential: p_cred, r_credential: None };
-
-        let signature_correctness_proof = Issuer::_new_signature_correctness_proof(&credential_pub_key.p_key,
-                                                                                   &credential_priv_key.p_key,
-                                                                                   &cred_signature.p_credential,
-                                                                                   &q,
-                                                                                   credential_issuance_nonce)?;
+        let signing_context = PrimarySigningContext::new(credential_pub_key, credential_priv_key, false)?;
 
+        let (cred_signature, signature_correctness_proof) = Issuer::_sign_credential(&signing_context,
+                                                                                     prover_id,
+                                                                                     blinded_credential_secrets,
+                                                                                     blinded_credential_secrets_correctness_proof,
+                                                                                     credential_nonce,
+                                                                                     credential_issuance_nonce,
+                                                                                     credential_values)?;
 
         trace!("Issuer::sign_credential: <<< cred_signature: {:?}, signature_correctness_proof: {:?}",
                secret!(&cred_signature), signature_correctness_proof);
@@ -268,6 +423,88 @@ impl Issuer {
         Ok((cred_signature, signature_correctness_proof))
     }
 
+    /// Signs batch of credentials with primary keys only, like `sign_credential` for every request.
+    ///
+    /// Exponentiations with the credential keys, that are the same for all credentials, are precomputed
+    /// once per batch, so batch of more than a few credentials is signed faster.
+    /// With `parallel` feature credentials are signed by rayon thread pool.
+    ///
+    /// Returns result of signing for every request in the same order, so an invalid request doesn't fail
+    /// the whole batch.
+    ///
+    /// # Arguments
+    /// * `requests` - Requests of credential signatures.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `credential_priv_key` - Credential private key.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::new_nonce;
+    /// use indy_crypto::cl::issuer::{Issuer, CredentialSignRequest};
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+    /// non_credential_schema_builder.add_attr("master_secret").unwrap();
+    /// let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (credential_pub_key, credential_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+    ///
+    /// let master_secret = Prover::new_master_secret().unwrap();
+    ///
+    /// let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+    /// credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap());
+    /// credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+    /// let credential_values = credential_values_builder.finalize().unwrap();
+    ///
+    /// let credential_nonce = new_nonce().unwrap();
+    /// let (blinded_credential_secrets, _, blinded_credential_secrets_correctness_proof) =
+    ///      Prover::blind_credential_secrets(&credential_pub_key, &cred_key_correctness_proof, &credential_values, &credential_nonce).unwrap();
+    ///
+    /// let credential_issuance_nonce = new_nonce().unwrap();
+    ///
+    /// let request = CredentialSignRequest {
+    ///     prover_id: "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+    ///     blinded_credential_secrets: &blinded_credential_secrets,
+    ///     blinded_credential_secrets_correctness_proof: &blinded_credential_secrets_correctness_proof,
+    ///     credential_nonce: &credential_nonce,
+    ///     credential_issuance_nonce: &credential_issuance_nonce,
+    ///     credential_values: &credential_values,
+    /// };
+    ///
+    /// let signatures = Issuer::sign_credentials_batch(&[request], &credential_pub_key, &credential_priv_key).unwrap();
+    /// let (_credential_signature, _signature_correctness_proof) = signatures.into_iter().next().unwrap().unwrap();
+    /// ```
+    pub fn sign_credentials_batch(requests: &[CredentialSignRequest],
+                                  credential_pub_key: &CredentialPublicKey,
+                                  credential_priv_key: &CredentialPrivateKey)
+                                  -> Result<Vec<CredentialSignResult>, IndyCryptoError> {
+        trace!("Issuer::sign_credentials_batch: >>> requests: {:?}, credential_pub_key: {:?}, credential_priv_key: {:?}",
+               requests.len(), credential_pub_key, secret!(credential_priv_key));
+
+        let signing_context = PrimarySigningContext::new(credential_pub_key, credential_priv_key, true)?;
+
+        let sign = |request: &CredentialSignRequest| Issuer::_sign_credential(&signing_context,
+                                                                              request.prover_id,
+                                                                              request.blinded_credential_secrets,
+                                                                              request.blinded_credential_secrets_correctness_proof,
+                                                                              request.credential_nonce,
+                                                                              request.credential_issuance_nonce,
+                                                                              request.credential_values);
+
+        #[cfg(feature = "parallel")]
+        let signatures: Vec<_> = requests.par_iter().map(sign).collect();
+        #[cfg(not(feature = "parallel"))]
+        let signatures: Vec<_> = requests.iter().map(sign).collect();
+
+        trace!("Issuer::sign_credentials_batch: <<< signed: {:?}", signatures.iter().filter(|res| res.is_ok()).count());
+
+        Ok(signatures)
+    }
+
     /// Signs credential values with both primary and revocation keys.
     ///
     /// # Arguments
@@ -281,6 +518,8 @@ impl Issuer {
     /// * `credential_priv_key` - Credential private key.
     /// * `rev_idx` - User index in revocation accumulator. Required for non-revocation credential_signature part generation.
     /// * `max_cred_num` - Max credential number in generated registry.
+    /// * `issuance_by_default` - Type of issuance the registry was created with, it isn't checked here,
+    ///   see `sign_credential_with_revoc_for_registry` to take it from the revocation key public.
     /// * `rev_reg` - Revocation registry.
     /// * `rev_key_priv` - Revocation registry private key.
     /// * `rev_tails_accessor` - Revocation registry tails accessor.
@@ -358,17 +597,18 @@ impl Issuer {
                prover_id, blinded_credential_secrets, blinded_credential_secrets_correctness_proof, credential_nonce, secret!(credential_values), credential_issuance_nonce,
                credential_pub_key, secret!(credential_priv_key), secret!(rev_idx), max_cred_num, rev_reg, secret!(rev_key_priv));
 
-        Issuer::_check_blinded_credential_secrets_correctness_proof(blinded_credential_secrets,
+        let signing_context = PrimarySigningContext::new(credential_pub_key, credential_priv_key, false)?;
+
+        Issuer::_check_blinded_credential_secrets_correctness_proof(&signing_context,
+                                                                    blinded_credential_secrets,
                                                                     blinded_credential_secrets_correctness_proof,
-                                                                    credential_nonce,
-                                                                    &credential_pub_key.p_key)?;
+                                                                    credential_nonce)?;
 
         // In the anoncreds whitepaper, `credential context` is denoted by `m2`
         let cred_context = Issuer::_gen_credential_context(prover_id, Some(rev_idx))?;
 
-        let (p_cred, q) = Issuer::_new_primary_credential(&cred_context,
-                                                          credential_pub_key,
-                                                          credential_priv_key,
+        let (p_cred, q) = Issuer::_new_primary_credential(&signing_context,
+                                                          &cred_context,
                                                           blinded_credential_secrets,
                                                           credential_values)?;
 
@@ -385,8 +625,7 @@ impl Issuer {
 
         let cred_signature = CredentialSignature { p_credential: p_cred, r_credential: Some(r_cred) };
 
-        let signature_correctness_proof = Issuer::_new_signature_correctness_proof(&credential_pub_key.p_key,
-                                                                                   &credential_priv_key.p_key,
+        let signature_correctness_proof = Issuer::_new_signature_correctness_proof(&signing_context,
                                                                                    &cred_signature.p_credential,
                                                                                    &q,
                                                                                    credential_issuance_nonce)?;
@@ -398,6 +637,106 @@ impl Issuer {
         Ok((cred_signature, signature_correctness_proof, rev_reg_delta))
     }
 
+    /// Signs credential values as `sign_credential_with_revoc` with type of issuance stored in the revocation key public.
+    ///
+    /// Returns `InvalidStructure` if the key was serialized without type of issuance.
+    pub fn sign_credential_with_revoc_for_registry<RTA>(prover_id: &str,
+                                                        blinded_credential_secrets: &BlindedCredentialSecrets,
+                                                        blinded_credential_secrets_correctness_proof: &BlindedCredentialSecretsCorrectnessProof,
+                                                        credential_nonce: &Nonce,
+                                                        credential_issuance_nonce: &Nonce,
+                                                        credential_values: &CredentialValues,
+                                                        credential_pub_key: &CredentialPublicKey,
+                                                        credential_priv_key: &CredentialPrivateKey,
+                                                        rev_idx: u32,
+                                                        max_cred_num: u32,
+                                                        rev_key_pub: &RevocationKeyPublic,
+                                                        rev_reg: &mut RevocationRegistry,
+                                                        rev_key_priv: &RevocationKeyPrivate,
+                                                        rev_tails_accessor: &RTA)
+                                                        -> Result<(CredentialSignature, SignatureCorrectnessProof, Option<RevocationRegistryDelta>),
+                                                            IndyCryptoError> where RTA: RevocationTailsAccessor {
+        Issuer::sign_credential_with_revoc(prover_id,
+                                           blinded_credential_secrets,
+                                           blinded_credential_secrets_correctness_proof,
+                                           credential_nonce,
+                                           credential_issuance_nonce,
+                                           credential_values,
+                                           credential_pub_key,
+                                           credential_priv_key,
+                                           rev_idx,
+                                           max_cred_num,
+                                           rev_key_pub._issuance_by_default()?,
+                                           rev_reg,
+                                           rev_key_priv,
+                                           rev_tails_accessor)
+    }
+
+    /// Signs credential values like `sign_credential_with_revoc_for_registry`, choosing revocation index
+    /// by `index_allocation` instead of taking it from the caller.
+    ///
+    /// The index is marked used in `rev_idx_allocator` only if the credential is signed, it's returned
+    /// together with the signature and is kept in the signature, see `CredentialSignature::extract_index`.
+    ///
+    /// # Arguments
+    /// * `prover_id` - Prover identifier.
+    /// * `blinded_credential_secrets` - Blinded credential secrets generated by Prover.
+    /// * `blinded_credential_secrets_correctness_proof` - Blinded credential secrets correctness proof.
+    /// * `credential_nonce` - Nonce used for verification of blinded_credential_secrets_correctness_proof.
+    /// * `credential_issuance_nonce` - Nonce used for creation of signature_correctness_proof.
+    /// * `credential_values` - Credential values to be signed.
+    /// * `credential_pub_key` - Credential public key.
+    /// * `credential_priv_key` - Credential private key.
+    /// * `index_allocation` - Strategy of choosing revocation index.
+    /// * `rev_idx_allocator` - Used indices of revocation registry, its max credential number is max credential number of the registry.
+    /// * `rev_key_pub` - Revocation registry public key, that stores type of issuance of the registry.
+    /// * `rev_reg` - Revocation registry.
+    /// * `rev_key_priv` - Revocation registry private key.
+    /// * `rev_tails_accessor` - Revocation registry tails accessor.
+    pub fn sign_credential_with_revoc_index_allocation<RTA>(prover_id: &str,
+                                                            blinded_credential_secrets: &BlindedCredentialSecrets,
+                                                            blinded_credential_secrets_correctness_proof: &BlindedCredentialSecretsCorrectnessProof,
+                                                            credential_nonce: &Nonce,
+                                                            credential_issuance_nonce: &Nonce,
+                                                            credential_values: &CredentialValues,
+                                                            credential_pub_key: &CredentialPublicKey,
+                                                            credential_priv_key: &CredentialPrivateKey,
+                                                            index_allocation: &IndexAllocation,
+                                                            rev_idx_allocator: &mut RevocationIndexAllocator,
+                                                            rev_key_pub: &RevocationKeyPublic,
+                                                            rev_reg: &mut RevocationRegistry,
+                                                            rev_key_priv: &RevocationKeyPrivate,
+                                                            rev_tails_accessor: &RTA)
+                                                            -> Result<(CredentialSignature, SignatureCorrectnessProof, Option<RevocationRegistryDelta>, u32),
+                                                                IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Issuer::sign_credential_with_revoc_index_allocation: >>> index_allocation: {:?}, rev_idx_allocator: {:?}",
+               index_allocation, secret!(&rev_idx_allocator));
+
+        let rev_idx = rev_idx_allocator.next_index(index_allocation)?;
+
+        let (cred_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc_for_registry(prover_id,
+                                                            blinded_credential_secrets,
+                                                            blinded_credential_secrets_correctness_proof,
+                                                            credential_nonce,
+                                                            credential_issuance_nonce,
+                                                            credential_values,
+                                                            credential_pub_key,
+                                                            credential_priv_key,
+                                                            rev_idx,
+                                                            rev_idx_allocator.max_cred_num(),
+                                                            rev_key_pub,
+                                                            rev_reg,
+                                                            rev_key_priv,
+                                                            rev_tails_accessor)?;
+
+        rev_idx_allocator.reserve(rev_idx)?;
+
+        trace!("Issuer::sign_credential_with_revoc_index_allocation: <<< rev_idx: {:?}", secret!(rev_idx));
+
+        Ok((cred_signature, signature_correctness_proof, rev_reg_delta, rev_idx))
+    }
+
     /// Revokes a credential by a rev_idx in a given revocation registry.
     ///
     /// # Arguments
@@ -572,8 +911,114 @@ impl Issuer {
         Ok(rev_reg_delta)
     }
 
+    /// Extends capacity of a full revocation registry, so issuance continues in the same registry
+    /// instead of a new one. Revocation key public and registry are updated in place.
+    ///
+    /// Returns delta from the registry before resize and tails generator that generates only tails
+    /// added by resize, they are to be appended to the tails file. Verifiers use the updated
+    /// revocation key public. Provers create witnesses anew by `Witness::new` with `new_max_cred_num`
+    /// and the delta since registry creation, merged with the resize delta, then update them as usual.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Credential public key entity.
+    /// * `rev_key_pub` - Revocation key public.
+    /// * `rev_key_priv` - Revocation key private.
+    /// * `rev_reg` - Revocation registry.
+    /// * `max_cred_num` - Max credential number in revocation registry.
+    /// * `new_max_cred_num` - Max credential number after resize, must be greater than `max_cred_num`.
+    /// * `issuance_by_default` - Type of issuance of revocation registry, added indices are issued if true.
+    ///   Must match type of issuance stored in `rev_key_pub` if any.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+    /// non_credential_schema_builder.add_attr("master_secret").unwrap();
+    /// let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+    ///
+    /// let (cred_pub_key, _cred_priv_key, _cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+    ///
+    /// let (mut rev_key_pub, rev_key_priv, mut rev_reg, _rev_tails_generator) = Issuer::new_revocation_registry_def(&cred_pub_key, 5, false).unwrap();
+    ///
+    /// let (_rev_reg_delta, rev_tails_generator) =
+    ///     Issuer::resize_revocation_registry(&cred_pub_key, &mut rev_key_pub, &rev_key_priv, &mut rev_reg, 5, 10, false).unwrap();
+    /// assert_eq!(10, rev_tails_generator.count());
+    /// ```
+    pub fn resize_revocation_registry(credential_pub_key: &CredentialPublicKey,
+                                      rev_key_pub: &mut RevocationKeyPublic,
+                                      rev_key_priv: &RevocationKeyPrivate,
+                                      rev_reg: &mut RevocationRegistry,
+                                      max_cred_num: u32,
+                                      new_max_cred_num: u32,
+                                      issuance_by_default: bool) -> Result<(RevocationRegistryDelta, RevocationTailsGenerator), IndyCryptoError> {
+        trace!("Issuer::resize_revocation_registry: >>> credential_pub_key: {:?}, rev_key_pub: {:?}, rev_key_priv: {:?}, rev_reg: {:?}, \
+        max_cred_num: {:?}, new_max_cred_num: {:?}, issuance_by_default: {:?}",
+               credential_pub_key, rev_key_pub, secret!(rev_key_priv), rev_reg, max_cred_num, new_max_cred_num, issuance_by_default);
+
+        if new_max_cred_num <= max_cred_num {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Revocation registry can only grow: max credential number {}, new max credential number {}", max_cred_num, new_max_cred_num)));
+        }
+
+        if let Some(issuance_type) = rev_key_pub.issuance_type() {
+            if issuance_type.is_by_default() != issuance_by_default {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Revocation registry was created with issuance type {:?}", issuance_type)));
+            }
+        }
+
+        let cred_rev_pub_key: &CredentialRevocationPublicKey = credential_pub_key.r_key
+            .as_ref()
+            .ok_or_else(|| IndyCryptoError::InvalidStructure("There are not revocation keys in the credential public key.".to_string()))?;
+
+        // Tails don't depend on max credential number, z and tail indices of the accumulator
+        // shift by gamma^(new_max_cred_num - max_cred_num) as if the registry was created with new_max_cred_num
+        let shift = rev_key_priv.gamma
+            .pow_mod(&GroupOrderElement::from_bytes(&transform_u32_to_array_of_u8(new_max_cred_num - max_cred_num))?)?;
+
+        let z = rev_key_pub.z.pow(&shift)?;
+        // Empty accumulator stays empty, multiplication doesn't support the point at infinity
+        let mut accum = if rev_reg.accum.is_inf()? { rev_reg.accum } else { rev_reg.accum.mul(&shift)? };
+
+        let mut issued = HashSet::new();
+        if issuance_by_default {
+            for i in max_cred_num + 1..new_max_cred_num + 1 {
+                let index = Issuer::_get_index(new_max_cred_num, i);
+                accum = accum.add(&Tail::new_tail(index, &cred_rev_pub_key.g_dash, &rev_key_priv.gamma)?)?;
+                issued.insert(i);
+            }
+        }
+
+        let mut rev_tails_generator = RevocationTailsGenerator::new(
+            new_max_cred_num,
+            rev_key_priv.gamma,
+            cred_rev_pub_key.g_dash);
+        rev_tails_generator.seek(2 * max_cred_num + 1)?;
+
+        let rev_reg_delta = RevocationRegistryDelta {
+            prev_accum: Some(rev_reg.accum),
+            accum,
+            issued,
+            revoked: HashSet::new()
+        };
+
+        rev_key_pub.z = z;
+        rev_reg.accum = accum;
+
+        trace!("Issuer::resize_revocation_registry: <<< rev_reg_delta: {:?}, rev_tails_generator: {:?}", rev_reg_delta, secret!(&rev_tails_generator));
+
+        Ok((rev_reg_delta, rev_tails_generator))
+    }
+
     fn _new_credential_primary_keys(credential_schema: &CredentialSchema,
-                                    non_credential_schema: &NonCredentialSchema) ->
+                                    non_credential_schema: &NonCredentialSchema,
+                                    progress: &mut KeyGenProgressReporter,
+                                    rng: &mut dyn KeyGenRandomness) ->
                                                                           Result<(CredentialPrimaryPublicKey,
                                                                                   CredentialPrimaryPrivateKey,
                                                                                   CredentialPrimaryPublicKeyMetadata), IndyCryptoError> {
@@ -585,23 +1030,25 @@ impl Issuer {
             return Err(IndyCryptoError::InvalidStructure(format!("List of attributes is empty")));
         }
 
-        let p_safe = generate_safe_prime(LARGE_PRIME)?;
-        let q_safe = generate_safe_prime(LARGE_PRIME)?;
+        let p_safe = rng.safe_prime(LARGE_PRIME, &mut || progress.notify(KeyGenProgress::CandidateTested))?;
+        progress.notify(KeyGenProgress::PrimeFound)?;
+        let q_safe = rng.safe_prime(LARGE_PRIME, &mut || progress.notify(KeyGenProgress::CandidateTested))?;
+        progress.notify(KeyGenProgress::PrimeFound)?;
 
         let p = p_safe.rshift1()?;
         let q = q_safe.rshift1()?;
 
         let n = p_safe.mul(&q_safe, Some(&mut ctx))?;
-        let s = random_qr(&n)?;
-        let xz = gen_x(&p, &q)?;
+        let s = rng.random_qr(&n)?;
+        let xz = rng.gen_x(&p, &q)?;
 
         let mut xr = HashMap::new();
         for non_schema_element in &non_credential_schema.attrs {
-            xr.insert(non_schema_element.to_string(), gen_x(&p, &q)?);
+            xr.insert(non_schema_element.to_string(), rng.gen_x(&p, &q)?);
         }
 
         for attribute in &credential_schema.attrs {
-            xr.insert(attribute.to_string(), gen_x(&p, &q)?);
+            xr.insert(attribute.to_string(), rng.gen_x(&p, &q)?);
         }
 
         let mut r = HashMap::new();
@@ -611,7 +1058,7 @@ impl Issuer {
 
         let z = s.mod_exp(&xz, &n, Some(&mut ctx))?;
 
-        let rctxt = s.mod_exp(&gen_x(&p, &q)?, &n, Some(&mut ctx))?;
+        let rctxt = s.mod_exp(&rng.gen_x(&p, &q)?, &n, Some(&mut ctx))?;
 
         let cred_pr_pub_key = CredentialPrimaryPublicKey { n, s, rctxt, r, z };
         let cred_pr_priv_key = CredentialPrimaryPrivateKey { p, q };
@@ -623,23 +1070,23 @@ impl Issuer {
         Ok((cred_pr_pub_key, cred_pr_priv_key, cred_pr_pub_key_metadata))
     }
 
-    fn _new_credential_revocation_keys() -> Result<(CredentialRevocationPublicKey,
-                                                    CredentialRevocationPrivateKey), IndyCryptoError> {
+    fn _new_credential_revocation_keys(rng: &mut dyn KeyGenRandomness) -> Result<(CredentialRevocationPublicKey,
+                                                                                  CredentialRevocationPrivateKey), IndyCryptoError> {
         trace!("Issuer::_new_credential_revocation_keys: >>>");
 
-        let h = PointG1::new()?;
-        let h0 = PointG1::new()?;
-        let h1 = PointG1::new()?;
-        let h2 = PointG1::new()?;
-        let htilde = PointG1::new()?;
-        let g = PointG1::new()?;
+        let h = rng.point_g1()?;
+        let h0 = rng.point_g1()?;
+        let h1 = rng.point_g1()?;
+        let h2 = rng.point_g1()?;
+        let htilde = rng.point_g1()?;
+        let g = rng.point_g1()?;
 
-        let u = PointG2::new()?;
-        let h_cap = PointG2::new()?;
+        let u = rng.point_g2()?;
+        let h_cap = rng.point_g2()?;
 
-        let x = GroupOrderElement::new()?;
-        let sk = GroupOrderElement::new()?;
-        let g_dash = PointG2::new()?;
+        let x = rng.group_order_element()?;
+        let sk = rng.group_order_element()?;
+        let g_dash = rng.point_g2()?;
 
         let pk = g.mul(&sk)?;
         let y = h_cap.mul(&x)?;
@@ -654,17 +1101,22 @@ impl Issuer {
 
     fn _new_credential_key_correctness_proof(cred_pr_pub_key: &CredentialPrimaryPublicKey,
                                              cred_pr_priv_key: &CredentialPrimaryPrivateKey,
-                                             cred_pr_pub_key_meta: &CredentialPrimaryPublicKeyMetadata) -> Result<CredentialKeyCorrectnessProof, IndyCryptoError> {
+                                             cred_pr_pub_key_meta: &CredentialPrimaryPublicKeyMetadata,
+                                             rng: &mut dyn KeyGenRandomness) -> Result<CredentialKeyCorrectnessProof, IndyCryptoError> {
         trace!("Issuer::_new_credential_key_correctness_proof: >>> cred_pr_pub_key: {:?}, cred_pr_priv_key: {:?}, cred_pr_pub_key_meta: {:?}",
                cred_pr_pub_key, secret!(cred_pr_priv_key), cred_pr_pub_key_meta);
 
         let mut ctx = BigNumber::new_context()?;
 
-        let xz_tilda = gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?;
+        let xz_tilda = rng.gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?;
+
+        // Sorted, so seeded randomness gives the same proof
+        let mut ordered_attrs = cred_pr_pub_key.r.keys().cloned().collect::<Vec<String>>();
+        ordered_attrs.sort();
 
         let mut xr_tilda = HashMap::new();
-        for key in cred_pr_pub_key.r.keys() {
-            xr_tilda.insert(key.to_string(), gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?);
+        for key in ordered_attrs.iter() {
+            xr_tilda.insert(key.to_string(), rng.gen_x(&cred_pr_priv_key.p, &cred_pr_priv_key.q)?);
         }
 
         let z_tilda = cred_pr_pub_key.s.mod_exp(&xz_tilda, &cred_pr_pub_key.n, Some(&mut ctx))?;
@@ -675,11 +1127,9 @@ impl Issuer {
         }
 
         let mut values: Vec<u8> = Vec::new();
-        let mut ordered_attrs: Vec<String> = Vec::new();
         values.extend_from_slice(&cred_pr_pub_key.z.to_bytes()?);
-        for (key, val) in cred_pr_pub_key.r.iter() {
-            values.extend_from_slice(&val.to_bytes()?);
-            ordered_attrs.push(key.to_owned());
+        for attr in &ordered_attrs {
+            values.extend_from_slice(&cred_pr_pub_key.r[attr].to_bytes()?);
         }
         values.extend_from_slice(&z_tilda.to_bytes()?);
         for attr in &ordered_attrs {
@@ -735,9 +1185,10 @@ impl Issuer {
     }
 
     fn _new_revocation_registry_keys(cred_rev_pub_key: &CredentialRevocationPublicKey,
-                                     max_cred_num: u32) -> Result<(RevocationKeyPublic, RevocationKeyPrivate), IndyCryptoError> {
-        trace!("Issuer::_new_revocation_registry_keys: >>> cred_rev_pub_key: {:?}, max_cred_num: {:?}",
-               cred_rev_pub_key, max_cred_num);
+                                     max_cred_num: u32,
+                                     issuance_type: IssuanceType) -> Result<(RevocationKeyPublic, RevocationKeyPrivate), IndyCryptoError> {
+        trace!("Issuer::_new_revocation_registry_keys: >>> cred_rev_pub_key: {:?}, max_cred_num: {:?}, issuance_type: {:?}",
+               cred_rev_pub_key, max_cred_num, issuance_type);
 
         let gamma = GroupOrderElement::new()?;
 
@@ -746,7 +1197,7 @@ impl Issuer {
         pow = gamma.pow_mod(&pow)?;
         z = z.pow(&pow)?;
 
-        let rev_key_pub = RevocationKeyPublic { z };
+        let rev_key_pub = RevocationKeyPublic { z, issuance_type: Some(issuance_type) };
         let rev_key_priv = RevocationKeyPrivate { gamma };
 
         trace!("Issuer::_new_revocation_registry_keys: <<< rev_key_pub: {:?}, rev_key_priv: {:?}", rev_key_pub, secret!(&rev_key_priv));
@@ -754,10 +1205,42 @@ impl Issuer {
         Ok((rev_key_pub, rev_key_priv))
     }
 
-    fn _check_blinded_credential_secrets_correctness_proof(blinded_cred_secrets: &BlindedCredentialSecrets,
+    fn _sign_credential(signing_context: &PrimarySigningContext,
+                        prover_id: &str,
+                        blinded_credential_secrets: &BlindedCredentialSecrets,
+                        blinded_credential_secrets_correctness_proof: &BlindedCredentialSecretsCorrectnessProof,
+                        credential_nonce: &Nonce,
+                        credential_issuance_nonce: &Nonce,
+                        credential_values: &CredentialValues) -> Result<(CredentialSignature, SignatureCorrectnessProof), IndyCryptoError> {
+        Issuer::_check_blinded_credential_secrets_correctness_proof(signing_context,
+                                                                    blinded_credential_secrets,
+                                                                    blinded_credential_secrets_correctness_proof,
+                                                                    credential_nonce)?;
+
+        // In the anoncreds whitepaper, `credential context` is denoted by `m2`
+        let cred_context = Issuer::_gen_credential_context(prover_id, None)?;
+
+        let (p_cred, q) = Issuer::_new_primary_credential(signing_context,
+                                                          &cred_context,
+                                                          blinded_credential_secrets,
+                                                          credential_values)?;
+
+        let cred_signature = CredentialSignature { p_credential: p_cred, r_credential: None };
+
+        let signature_correctness_proof = Issuer::_new_signature_correctness_proof(signing_context,
+                                                                                   &cred_signature.p_credential,
+                                                                                   &q,
+                                                                                   credential_issuance_nonce)?;
+
+        Ok((cred_signature, signature_correctness_proof))
+    }
+
+    fn _check_blinded_credential_secrets_correctness_proof(signing_context: &PrimarySigningContext,
+                                                           blinded_cred_secrets: &BlindedCredentialSecrets,
                                                            blinded_cred_secrets_correctness_proof: &BlindedCredentialSecretsCorrectnessProof,
-                                                           nonce: &Nonce,
-                                                           cred_pr_pub_key: &CredentialPrimaryPublicKey) -> Result<(), IndyCryptoError> {
+                                                           nonce: &Nonce) -> Result<(), IndyCryptoError> {
+        let cred_pr_pub_key = signing_context.p_pub_key;
+
         trace!("Issuer::_check_blinded_credential_secrets_correctness_proof: >>> blinded_cred_secrets: {:?}, blinded_cred_secrets_correctness_proof: {:?},\
          nonce: {:?}, cred_pr_pub_key: {:?}", blinded_cred_secrets, blinded_cred_secrets_correctness_proof, nonce, cred_pr_pub_key);
 
@@ -770,16 +1253,14 @@ impl Issuer {
                                                     .inverse(&cred_pr_pub_key.n, Some(&mut ctx))?
                                                     .mod_exp(&blinded_cred_secrets_correctness_proof.c, &cred_pr_pub_key.n, Some(&mut ctx))?
                                                     .mod_mul(
-                                                        &cred_pr_pub_key.s.mod_exp(&blinded_cred_secrets_correctness_proof.v_dash_cap, &cred_pr_pub_key.n, Some(&mut ctx))?,
+                                                        &signing_context.s.exp(&blinded_cred_secrets_correctness_proof.v_dash_cap, &mut ctx)?,
                                                         &cred_pr_pub_key.n,
                                                         Some(&mut ctx)
                                                     ),
                                               |acc, attr| {
-                                                  let pk_r = cred_pr_pub_key.r
-                                                                    .get(&attr.clone())
-                                                                    .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_pr_pub_key.r", attr)))?;
+                                                  let pk_r = signing_context.r(attr)?;
                                                   let m_cap = &blinded_cred_secrets_correctness_proof.m_caps[attr];
-                                                  acc?.mod_mul(&pk_r.mod_exp(&m_cap, &cred_pr_pub_key.n, Some(&mut ctx))?,
+                                                  acc?.mod_mul(&pk_r.exp(&m_cap, &mut ctx)?,
                                                                &cred_pr_pub_key.n, Some(&mut ctx))
                                               })?;
 
@@ -820,8 +1301,8 @@ impl Issuer {
 
         let rev_idx = rev_idx.map(|i| i as i32).unwrap_or(-1);
 
-        let prover_id_bn = encode_attribute(prover_id, ByteOrder::Little)?;
-        let rev_idx_bn = encode_attribute(&rev_idx.to_string(), ByteOrder::Little)?;
+        let prover_id_bn = helpers::encode_attribute(prover_id, ByteOrder::Little)?;
+        let rev_idx_bn = helpers::encode_attribute(&rev_idx.to_string(), ByteOrder::Little)?;
 
         let mut values: Vec<u8> = Vec::new();
         values.extend_from_slice(&prover_id_bn.to_bytes()?);
@@ -834,18 +1315,17 @@ impl Issuer {
         Ok(credential_context)
     }
 
-    fn _new_primary_credential(credential_context: &BigNumber,
-                               cred_pub_key: &CredentialPublicKey,
-                               cred_priv_key: &CredentialPrivateKey,
+    fn _new_primary_credential(signing_context: &PrimarySigningContext,
+                               credential_context: &BigNumber,
                                blinded_credential_secrets: &BlindedCredentialSecrets,
                                cred_values: &CredentialValues) -> Result<(PrimaryCredentialSignature, BigNumber), IndyCryptoError> {
-        trace!("Issuer::_new_primary_credential: >>> credential_context: {:?}, cred_pub_key: {:?}, cred_priv_key: {:?}, blinded_ms: {:?},\
-         cred_values: {:?}", secret!(credential_context), cred_pub_key, secret!(cred_priv_key), blinded_credential_secrets, secret!(cred_values));
+        trace!("Issuer::_new_primary_credential: >>> credential_context: {:?}, blinded_ms: {:?}, cred_values: {:?}",
+               secret!(credential_context), blinded_credential_secrets, secret!(cred_values));
 
         let v = generate_v_prime_prime()?;
 
         let e = generate_prime_in_range(&LARGE_E_START_VALUE, &LARGE_E_END_RANGE_VALUE)?;
-        let (a, q) = Issuer::_sign_primary_credential(cred_pub_key, cred_priv_key, &credential_context, &cred_values, &v, blinded_credential_secrets, &e)?;
+        let (a, q) = Issuer::_sign_primary_credential(signing_context, &credential_context, &cred_values, &v, blinded_credential_secrets, &e)?;
 
         let pr_cred_sig = PrimaryCredentialSignature { m_2: credential_context.clone()?, a, e, v };
 
@@ -854,70 +1334,62 @@ impl Issuer {
         Ok((pr_cred_sig, q))
     }
 
-    fn _sign_primary_credential(cred_pub_key: &CredentialPublicKey,
-                                cred_priv_key: &CredentialPrivateKey,
+    fn _sign_primary_credential(signing_context: &PrimarySigningContext,
                                 cred_context: &BigNumber,
                                 cred_values: &CredentialValues,
                                 v: &BigNumber,
                                 blinded_cred_secrets: &BlindedCredentialSecrets,
                                 e: &BigNumber) -> Result<(BigNumber, BigNumber), IndyCryptoError> {
-        trace!("Issuer::_sign_primary_credential: >>> cred_pub_key: {:?}, \
-                                                      cred_priv_key: {:?}, \
-                                                      cred_context: {:?}, \
+        trace!("Issuer::_sign_primary_credential: >>> cred_context: {:?}, \
                                                       cred_values: {:?}, \
                                                       v: {:?},\
                                                       blinded_cred_secrets: {:?}, \
-                                                      e: {:?}", cred_pub_key, secret!(cred_priv_key), secret!(cred_context), secret!(cred_values),
+                                                      e: {:?}", secret!(cred_context), secret!(cred_values),
                                                                 secret!(v), blinded_cred_secrets, secret!(e));
 
-        let p_pub_key = &cred_pub_key.p_key;
-        let p_priv_key = &cred_priv_key.p_key;
+        let p_pub_key = signing_context.p_pub_key;
 
         let mut context = BigNumber::new_context()?;
 
-        let mut rx = p_pub_key.s.mod_exp(&v, &p_pub_key.n, Some(&mut context))?;
+        let mut rx = signing_context.s.exp(&v, &mut context)?;
 
         if blinded_cred_secrets.u != BigNumber::from_u32(0)? {
             rx = rx.mod_mul(&blinded_cred_secrets.u, &p_pub_key.n, Some(&mut context))?;
         }
 
-        rx = rx.mod_mul(&p_pub_key.rctxt.mod_exp(&cred_context, &p_pub_key.n, Some(&mut context))?, &p_pub_key.n, Some(&mut context))?;
+        rx = rx.mod_mul(&signing_context.rctxt.exp(&cred_context, &mut context)?, &p_pub_key.n, Some(&mut context))?;
 
         for (key, attr) in cred_values.attrs_values.iter().filter(|&(_, v)| v.is_known()) {
-            let pk_r = p_pub_key.r
-                .get(key)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", key)))?;
+            let pk_r = signing_context.r(key)?;
 
-            rx = pk_r.mod_exp(attr.value(), &p_pub_key.n, Some(&mut context))?
+            rx = pk_r.exp(attr.value(), &mut context)?
                      .mod_mul(&rx, &p_pub_key.n, Some(&mut context))?;
         }
 
         let q = p_pub_key.z.mod_div(&rx, &p_pub_key.n, Some(&mut context))?;
 
-        let n = p_priv_key.p.mul(&p_priv_key.q, Some(&mut context))?;
-        let e_inverse = e.inverse(&n, Some(&mut context))?;
+        let e_inverse = e.inverse(&signing_context.order, Some(&mut context))?;
 
-        let a = q.mod_exp(&e_inverse, &p_pub_key.n, Some(&mut context))?;
+        let a = signing_context.crt.exp(&q, &e_inverse, &mut context)?;
 
         trace!("Issuer::_sign_primary_credential: <<< a: {:?}, q: {:?}", secret!(&a), secret!(&q));
 
         Ok((a, q))
     }
 
-    fn _new_signature_correctness_proof(p_pub_key: &CredentialPrimaryPublicKey,
-                                        p_priv_key: &CredentialPrimaryPrivateKey,
+    fn _new_signature_correctness_proof(signing_context: &PrimarySigningContext,
                                         p_cred_signature: &PrimaryCredentialSignature,
                                         q: &BigNumber,
                                         nonce: &BigNumber) -> Result<SignatureCorrectnessProof, IndyCryptoError> {
-        trace!("Issuer::_new_signature_correctness_proof: >>> p_pub_key: {:?}, p_priv_key: {:?}, p_cred_signature: {:?}, q: {:?}, nonce: {:?}",
-               p_pub_key, secret!(p_priv_key), secret!(p_cred_signature), secret!(q), nonce);
+        trace!("Issuer::_new_signature_correctness_proof: >>> p_cred_signature: {:?}, q: {:?}, nonce: {:?}",
+               secret!(p_cred_signature), secret!(q), nonce);
 
         let mut ctx = BigNumber::new_context()?;
 
-        let n = p_priv_key.p.mul(&p_priv_key.q, Some(&mut ctx))?;
-        let r = bn_rand_range(&n)?;
+        let n = &signing_context.order;
+        let r = bn_rand_range(n)?;
 
-        let a_cap = q.mod_exp(&r, &p_pub_key.n, Some(&mut ctx))?;
+        let a_cap = signing_context.crt.exp(q, &r, &mut ctx)?;
 
         let mut values: Vec<u8> = Vec::new();
         values.extend_from_slice(&q.to_bytes()?);
@@ -928,8 +1400,8 @@ impl Issuer {
         let c = get_hash_as_int(&mut vec![values])?;
 
         let se = r.mod_sub(
-            &c.mod_mul(&p_cred_signature.e.inverse(&n, Some(&mut ctx))?, &n, Some(&mut ctx))?,
-            &n,
+            &c.mod_mul(&p_cred_signature.e.inverse(n, Some(&mut ctx))?, n, Some(&mut ctx))?,
+            n,
             Some(&mut ctx)
         )?;
 
@@ -960,16 +1432,21 @@ impl Issuer {
                secret!(rev_idx), secret!(cred_context), blinded_credential_secrets, cred_pub_key, secret!(cred_priv_key), max_cred_num,
                issuance_by_default, rev_reg, secret!(rev_key_priv));
 
+        if rev_idx > max_cred_num {
+            return Err(IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(
+                format!("Credential index exceeds max credential number {}", max_cred_num)));
+        }
+
         let ur = blinded_credential_secrets.ur
-            .ok_or(IndyCryptoError::InvalidStructure(format!("No revocation part present in blinded master secret.")))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("No revocation part present in blinded master secret.")))?;
 
         let r_pub_key: &CredentialRevocationPublicKey = cred_pub_key.r_key
             .as_ref()
-            .ok_or(IndyCryptoError::InvalidStructure(format!("No revocation part present in credential revocation public key.")))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("No revocation part present in credential revocation public key.")))?;
 
         let r_priv_key: &CredentialRevocationPrivateKey = cred_priv_key.r_key
             .as_ref()
-            .ok_or(IndyCryptoError::InvalidStructure(format!("No revocation part present in credential revocation private key.")))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("No revocation part present in credential revocation private key.")))?;
 
         let vr_prime_prime = GroupOrderElement::new()?;
         let c = GroupOrderElement::new()?;
@@ -1041,11 +1518,109 @@ impl Issuer {
     }
 }
 
+/// Progress event of `Issuer::new_credential_def_with_progress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyGenProgress {
+    /// Candidate for a safe prime of the modulus was tested
+    CandidateTested,
+    /// Safe prime of the modulus was found, there are two of them
+    PrimeFound,
+    /// Component of the credential definition was finished
+    ComponentFinished(KeyGenComponent),
+}
+
+/// Component of the credential definition, finished in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyGenComponent {
+    PrimaryKeys,
+    /// Generated only if revocation is supported
+    RevocationKeys,
+    KeyCorrectnessProof,
+}
+
+struct KeyGenProgressReporter<'a> {
+    progress: &'a mut dyn FnMut(KeyGenProgress) -> bool
+}
+
+impl<'a> KeyGenProgressReporter<'a> {
+    fn notify(&mut self, event: KeyGenProgress) -> Result<(), IndyCryptoError> {
+        // Panic is caught here, state of the generation is local, so it's dropped as after cancellation
+        match panic::catch_unwind(AssertUnwindSafe(|| (self.progress)(event))) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(IndyCryptoError::Cancelled("Credential definition generation is cancelled by progress callback".to_string())),
+            Err(_) => Err(IndyCryptoError::Cancelled("Progress callback of credential definition generation panicked".to_string()))
+        }
+    }
+}
+
+/// Request of a credential signature in `Issuer::sign_credentials_batch`, fields are the same as
+/// arguments of `Issuer::sign_credential`.
+pub struct CredentialSignRequest<'a> {
+    pub prover_id: &'a str,
+    pub blinded_credential_secrets: &'a BlindedCredentialSecrets,
+    pub blinded_credential_secrets_correctness_proof: &'a BlindedCredentialSecretsCorrectnessProof,
+    pub credential_nonce: &'a Nonce,
+    pub credential_issuance_nonce: &'a Nonce,
+    pub credential_values: &'a CredentialValues,
+}
+
+/// Result of a credential signature in `Issuer::sign_credentials_batch`.
+pub type CredentialSignResult = Result<(CredentialSignature, SignatureCorrectnessProof), IndyCryptoError>;
+
+// Credential primary keys prepared for signing. Exponentiations with the bases of the public key
+// are precomputed for batches only, as precomputation costs about as much as a signature.
+struct PrimarySigningContext<'a> {
+    p_pub_key: &'a CredentialPrimaryPublicKey,
+    s: FixedBaseExp,
+    rctxt: FixedBaseExp,
+    r: HashMap<String, FixedBaseExp>,
+    // Order of the group of quadratic residues: p' * q'
+    order: BigNumber,
+    // Exponentiation modulo n = (2p' + 1) * (2q' + 1)
+    crt: CrtModExp
+}
+
+impl<'a> PrimarySigningContext<'a> {
+    fn new(cred_pub_key: &'a CredentialPublicKey,
+           cred_priv_key: &CredentialPrivateKey,
+           precompute: bool) -> Result<PrimarySigningContext<'a>, IndyCryptoError> {
+        let p_pub_key = &cred_pub_key.p_key;
+        let p_priv_key = &cred_priv_key.p_key;
+
+        let mut ctx = BigNumber::new_context()?;
+
+        // Exponents of s are v'' and v_dash_cap, exponents of r and rctxt are m_caps, values and credential context
+        let (s_exp_bits, r_exp_bits) = if precompute { (LARGE_VPRIME_PRIME, LARGE_MTILDE + 1) } else { (0, 0) };
+
+        let mut r = HashMap::new();
+        for (attr, pk_r) in &p_pub_key.r {
+            r.insert(attr.clone(), FixedBaseExp::new(pk_r, &p_pub_key.n, r_exp_bits, &mut ctx)?);
+        }
+
+        Ok(PrimarySigningContext {
+            p_pub_key,
+            s: FixedBaseExp::new(&p_pub_key.s, &p_pub_key.n, s_exp_bits, &mut ctx)?,
+            rctxt: FixedBaseExp::new(&p_pub_key.rctxt, &p_pub_key.n, r_exp_bits, &mut ctx)?,
+            r,
+            order: p_priv_key.p.mul(&p_priv_key.q, Some(&mut ctx))?,
+            crt: CrtModExp::new(&p_priv_key.p.lshift1()?.increment()?,
+                                &p_priv_key.q.lshift1()?.increment()?,
+                                &mut ctx)?
+        })
+    }
+
+    fn r(&self, attr: &str) -> Result<&FixedBaseExp, IndyCryptoError> {
+        self.r.get(attr)
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in pk.r", attr)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cl::issuer::{Issuer, mocks};
     use crate::cl::helpers::MockHelper;
+    use crate::errors::ErrorKind;
     use self::prover::mocks as prover_mocks;
     use self::prover::Prover;
 
@@ -1084,6 +1659,78 @@ mod tests {
         assert!(credential_values.attrs_values.get("age").is_none());
     }
 
+    fn _assert_invalid_attr<T: ::std::fmt::Debug>(res: Result<T, IndyCryptoError>, attr: &str) {
+        match res {
+            Err(IndyCryptoError::InvalidStructure(ref message)) => assert!(message.contains(&format!("'{}'", attr)), "{}", message),
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn credential_schema_builder_works_for_invalid_attrs() {
+        for &(attrs, invalid_attr) in &[(&["name", ""][..], ""),
+                                        (&["name", " \t"][..], " \t"),
+                                        (&["name", "age", "name"][..], "name"),
+                                        (&["Name", "name"][..], "name")] {
+            let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+            let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+            for attr in attrs {
+                credential_schema_builder.add_attr(attr).unwrap();
+                non_credential_schema_builder.add_attr(attr).unwrap();
+            }
+
+            _assert_invalid_attr(credential_schema_builder.finalize(), invalid_attr);
+            _assert_invalid_attr(non_credential_schema_builder.finalize(), invalid_attr);
+        }
+    }
+
+    #[test]
+    fn credential_values_builder_works_for_invalid_attrs() {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_dec_known("name", "1").unwrap();
+        credential_values_builder.add_dec_hidden("name", "2").unwrap();
+        _assert_invalid_attr(credential_values_builder.finalize(), "name");
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_dec_known("", "1").unwrap();
+        _assert_invalid_attr(credential_values_builder.finalize(), "");
+    }
+
+    #[test]
+    fn credential_values_builder_finalize_for_schema_works() {
+        let credential_schema = mocks::credential_schema();
+        let non_credential_schema = mocks::non_credential_schema();
+
+        let credential_values_builder = |attrs: &[&str]| {
+            let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+            for attr in attrs {
+                credential_values_builder.add_dec_known(attr, "1").unwrap();
+            }
+            credential_values_builder
+        };
+
+        let schema_attrs = credential_schema.attrs.union(&non_credential_schema.attrs).map(String::as_str).collect::<Vec<&str>>();
+        let credential_values = credential_values_builder(&schema_attrs).finalize_for_schema(&credential_schema, &non_credential_schema).unwrap();
+        assert_eq!(schema_attrs.len(), credential_values.attrs_values.len());
+
+        let mut attrs = schema_attrs.clone();
+        attrs.push("weight");
+        _assert_invalid_attr(credential_values_builder(&attrs).finalize_for_schema(&credential_schema, &non_credential_schema), "weight");
+
+        let attrs = schema_attrs.iter().cloned().filter(|attr| *attr != "master_secret").collect::<Vec<&str>>();
+        _assert_invalid_attr(credential_values_builder(&attrs).finalize_for_schema(&credential_schema, &non_credential_schema), "master_secret");
+    }
+
+    #[test]
+    fn credential_values_try_from_pairs_works() {
+        let credential_values = CredentialValues::try_from_pairs(&[("name", "1139481716457488690172217916278103335"), ("age", "28")]).unwrap();
+        assert!(credential_values.attrs_values["age"].is_known());
+        assert_eq!(BigNumber::from_dec("28").unwrap(), *credential_values.attrs_values["age"].value());
+
+        _assert_invalid_attr(CredentialValues::try_from_pairs(&[("age", "28"), ("age", "29")]), "age");
+        assert!(CredentialValues::try_from_pairs(&[("age", "twenty eight")]).is_err());
+    }
+
     #[test]
     fn issuer_new_credential_def_works() {
         MockHelper::inject();
@@ -1107,6 +1754,96 @@ mod tests {
         Prover::check_credential_key_correctness_proof(&pub_key.p_key, &key_correctness_proof).unwrap();
     }
 
+    #[test]
+    fn issuer_new_credential_def_with_progress_works() {
+        MockHelper::inject();
+
+        let mut events = Vec::new();
+        let (pub_key, _, key_correctness_proof) =
+            Issuer::new_credential_def_with_progress(&mocks::credential_schema(), &mocks::non_credential_schema(), true, &mut |event| {
+                events.push(event);
+                true
+            }).unwrap();
+
+        assert_eq!(vec![KeyGenProgress::CandidateTested,
+                        KeyGenProgress::PrimeFound,
+                        KeyGenProgress::CandidateTested,
+                        KeyGenProgress::PrimeFound,
+                        KeyGenProgress::ComponentFinished(KeyGenComponent::PrimaryKeys),
+                        KeyGenProgress::ComponentFinished(KeyGenComponent::RevocationKeys),
+                        KeyGenProgress::ComponentFinished(KeyGenComponent::KeyCorrectnessProof)], events);
+        Prover::check_credential_key_correctness_proof(&pub_key.p_key, &key_correctness_proof).unwrap();
+    }
+
+    // Mocked key generation stays sequential and reproducible with any features
+    #[test]
+    fn issuer_new_credential_def_works_for_mocked_primes() {
+        MockHelper::inject();
+
+        let (pub_key, priv_key, _) = Issuer::new_credential_def(&mocks::credential_schema(), &mocks::non_credential_schema(), false).unwrap();
+
+        // Both safe primes are the mocked one
+        let safe_prime = BigNumber::from_dec("298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623").unwrap();
+        assert_eq!(safe_prime.rshift1().unwrap(), priv_key.p_key.p);
+        assert_eq!(safe_prime.rshift1().unwrap(), priv_key.p_key.q);
+        assert_eq!(safe_prime.mul(&safe_prime, None).unwrap(), pub_key.p_key.n);
+        assert_eq!(BigNumber::from_dec("64684820421150545443421261645532741305438158267230326415141505826951816460650437611148133267480407958360035501128469885271549378871140475869904030424615175830170939416512594291641188403335834762737251794282186335118831803135149622404791467775422384378569231649224208728902565541796896860352464500717052768431523703881746487372385032277847026560711719065512366600220045978358915680277126661923892187090579302197390903902744925313826817940566429968987709582805451008234648959429651259809188953915675063700676546393568304468609062443048457324721450190021552656280473128156273976008799243162970386898307404395608179975243").unwrap(),
+                   pub_key.p_key.s);
+    }
+
+    #[test]
+    fn issuer_new_credential_def_with_progress_works_for_counting_callback() {
+        let mut candidates = 0;
+        let mut primes = 0;
+        let mut components = 0;
+        Issuer::new_credential_def_with_progress(&mocks::credential_schema(), &mocks::non_credential_schema(), false, &mut |event| {
+            match event {
+                KeyGenProgress::CandidateTested => candidates += 1,
+                KeyGenProgress::PrimeFound => primes += 1,
+                KeyGenProgress::ComponentFinished(_) => components += 1
+            }
+            true
+        }).unwrap();
+
+        assert!(candidates >= 2);
+        assert_eq!(2, primes);
+        assert_eq!(2, components);
+    }
+
+    #[test]
+    fn issuer_new_credential_def_with_progress_works_for_cancelling_callback() {
+        let mut candidates = 0;
+        let res = Issuer::new_credential_def_with_progress(&mocks::credential_schema(), &mocks::non_credential_schema(), true, &mut |event| {
+            assert_eq!(KeyGenProgress::CandidateTested, event);
+            candidates += 1;
+            candidates < 5
+        });
+
+        assert_eq!(ErrorKind::Cancelled, res.unwrap_err().kind());
+        assert_eq!(5, candidates);
+    }
+
+    #[test]
+    fn issuer_new_credential_def_with_progress_works_for_cancelling_after_prime_found() {
+        MockHelper::inject();
+
+        let res = Issuer::new_credential_def_with_progress(&mocks::credential_schema(), &mocks::non_credential_schema(), true,
+                                                           &mut |event| event != KeyGenProgress::ComponentFinished(KeyGenComponent::PrimaryKeys));
+        assert_eq!(ErrorKind::Cancelled, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn issuer_new_credential_def_with_progress_works_for_panicking_callback() {
+        let res = Issuer::new_credential_def_with_progress(&mocks::credential_schema(), &mocks::non_credential_schema(), true,
+                                                           &mut |_| panic!("progress panic"));
+        assert_eq!(ErrorKind::Cancelled, res.unwrap_err().kind());
+
+        MockHelper::inject();
+        let res = Issuer::new_credential_def_with_progress(&mocks::credential_schema(), &mocks::non_credential_schema(), true,
+                                                           &mut |event| if event == KeyGenProgress::PrimeFound { panic!("progress panic") } else { true });
+        assert_eq!(ErrorKind::Cancelled, res.unwrap_err().kind());
+    }
+
     #[test]
     fn issuer_new_credential_works_for_empty_attributes() {
         let cred_attrs = CredentialSchema { attrs: BTreeSet::new() };
@@ -1123,6 +1860,79 @@ mod tests {
         Issuer::new_revocation_registry_def(&pub_key, 100, false).unwrap();
     }
 
+    #[test]
+    fn resize_revocation_registry_works() {
+        let cred_pub_key = mocks::credential_public_key();
+        let cred_rev_pub_key = cred_pub_key.r_key.clone().unwrap();
+
+        for &issuance_by_default in [false, true].iter() {
+            let (mut rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+                Issuer::new_revocation_registry_def(&cred_pub_key, 4, issuance_by_default).unwrap();
+            let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+            let mut issued: HashSet<u32> = if issuance_by_default { (1..5).collect() } else { HashSet::new() };
+            if issuance_by_default {
+                Issuer::revoke_credential(&mut rev_reg, 4, 2, &simple_tail_accessor).unwrap();
+                issued.remove(&2);
+            } else {
+                Issuer::recovery_credential(&mut rev_reg, 4, 1, &simple_tail_accessor).unwrap();
+                Issuer::recovery_credential(&mut rev_reg, 4, 3, &simple_tail_accessor).unwrap();
+                issued.extend(&[1, 3]);
+            }
+
+            // Type of issuance must match the one stored in revocation key public
+            let res = Issuer::resize_revocation_registry(&cred_pub_key, &mut rev_key_pub, &rev_key_priv, &mut rev_reg, 4, 8, !issuance_by_default);
+            assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+            let prev_accum = rev_reg.accum.clone();
+            let (rev_reg_delta, mut new_tails_generator) =
+                Issuer::resize_revocation_registry(&cred_pub_key, &mut rev_key_pub, &rev_key_priv, &mut rev_reg, 4, 8, issuance_by_default).unwrap();
+            if issuance_by_default {
+                issued.extend(5..9);
+            }
+
+            // Existing tails are kept, generated tails complete tails of registry of 8 credentials
+            assert_eq!(9, new_tails_generator.index());
+            assert_eq!(8, new_tails_generator.count());
+            let mut tails = simple_tail_accessor.tails.clone();
+            tails.extend(SimpleTailsAccessor::new(&mut new_tails_generator).unwrap().tails);
+            let expected_tails = SimpleTailsAccessor::new(
+                &mut RevocationTailsGenerator::new(8, rev_key_priv.gamma.clone(), cred_rev_pub_key.g_dash.clone())).unwrap().tails;
+            assert_eq!(expected_tails.iter().map(|tail| tail.to_bytes().unwrap()).collect::<Vec<Vec<u8>>>(),
+                       tails.iter().map(|tail| tail.to_bytes().unwrap()).collect::<Vec<Vec<u8>>>());
+            let simple_tail_accessor = SimpleTailsAccessor { tails };
+
+            assert_eq!(Pair::pair(&cred_rev_pub_key.g, &simple_tail_accessor.tails[9]).unwrap().to_bytes().unwrap(),
+                       rev_key_pub.z.to_bytes().unwrap());
+
+            assert_eq!(prev_accum.to_bytes().unwrap(), rev_reg_delta.prev_accum.unwrap().to_bytes().unwrap());
+            assert_eq!(rev_reg.accum.to_bytes().unwrap(), rev_reg_delta.accum.to_bytes().unwrap());
+            assert_eq!(if issuance_by_default { (5..9).collect() } else { HashSet::new() }, rev_reg_delta.issued);
+            assert!(rev_reg_delta.revoked.is_empty());
+
+            if !issuance_by_default {
+                Issuer::recovery_credential(&mut rev_reg, 8, 6, &simple_tail_accessor).unwrap();
+                Issuer::recovery_credential(&mut rev_reg, 8, 7, &simple_tail_accessor).unwrap();
+                issued.extend(&[6, 7]);
+            }
+            Issuer::revoke_credential(&mut rev_reg, 8, 7, &simple_tail_accessor).unwrap();
+            issued.remove(&7);
+
+            let mut expected_accum = Accumulator::new_inf().unwrap();
+            for j in issued.iter() {
+                expected_accum = expected_accum.add(&simple_tail_accessor.tails[(9 - j) as usize]).unwrap();
+            }
+            assert_eq!(expected_accum.to_bytes().unwrap(), rev_reg.accum.to_bytes().unwrap());
+
+            // Shrinking is rejected and leaves the registry unchanged
+            let z = rev_key_pub.z.to_bytes().unwrap();
+            assert!(Issuer::resize_revocation_registry(&cred_pub_key, &mut rev_key_pub, &rev_key_priv, &mut rev_reg, 8, 6, issuance_by_default).is_err());
+            assert!(Issuer::resize_revocation_registry(&cred_pub_key, &mut rev_key_pub, &rev_key_priv, &mut rev_reg, 8, 8, issuance_by_default).is_err());
+            assert_eq!(z, rev_key_pub.z.to_bytes().unwrap());
+            assert_eq!(expected_accum.to_bytes().unwrap(), rev_reg.accum.to_bytes().unwrap());
+        }
+    }
+
     #[test]
     fn sign_primary_credential_works() {
         MockHelper::inject();
@@ -1135,7 +1945,8 @@ mod tests {
 
         let expected_q = primary_credential.a.mod_exp(&primary_credential.e, &pub_key.p_key.n, None).unwrap();
 
-        let (credential_signature, q) = Issuer::_sign_primary_credential(&pub_key, &secret_key, &context_attribute, &credential_values, &primary_credential.v, &prover_mocks::blinded_credential_secrets(), &primary_credential.e).unwrap();
+        let signing_context = PrimarySigningContext::new(&pub_key, &secret_key, false).unwrap();
+        let (credential_signature, q) = Issuer::_sign_primary_credential(&signing_context, &context_attribute, &credential_values, &primary_credential.v, &prover_mocks::blinded_credential_secrets(), &primary_credential.e).unwrap();
         assert_eq!(primary_credential.a, credential_signature);
         assert_eq!(expected_q, q);
     }
@@ -1174,6 +1985,71 @@ mod tests {
         assert_eq!(expected_signature_correctness_proof, signature_correctness_proof);
     }
 
+    // Signatures are checked by the prover instead of compared to mocks: mocks are injected into
+    // the thread of the test only, rayon threads of the parallel feature would generate random values
+    #[test]
+    fn sign_credentials_batch_works() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (pub_key, priv_key, key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &Prover::new_master_secret().unwrap().value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        let credential_values = credential_values_builder.finalize().unwrap();
+
+        let blinded_credential_secrets_nonce = new_nonce().unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&pub_key, &key_correctness_proof, &credential_values, &blinded_credential_secrets_nonce).unwrap();
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let other_nonce = new_nonce().unwrap();
+
+        let request = |credential_nonce| CredentialSignRequest {
+            prover_id: prover_mocks::PROVER_DID,
+            blinded_credential_secrets: &blinded_credential_secrets,
+            blinded_credential_secrets_correctness_proof: &blinded_credential_secrets_correctness_proof,
+            credential_nonce,
+            credential_issuance_nonce: &credential_issuance_nonce,
+            credential_values: &credential_values,
+        };
+
+        let signatures = Issuer::sign_credentials_batch(&[request(&blinded_credential_secrets_nonce),
+                                                            request(&other_nonce),
+                                                            request(&blinded_credential_secrets_nonce)],
+                                                          &pub_key,
+                                                          &priv_key).unwrap();
+        assert_eq!(3, signatures.len());
+
+        match signatures[1] {
+            Err(IndyCryptoError::InvalidStructure(_)) => (),
+            ref res => panic!("Unexpected result: {:?}", res)
+        }
+
+        for res in signatures.into_iter().step_by(2) {
+            let (mut credential_signature, signature_correctness_proof) = res.unwrap();
+            assert!(credential_signature.r_credential.is_none());
+
+            Prover::process_credential_signature(&mut credential_signature,
+                                                 &credential_values,
+                                                 &signature_correctness_proof,
+                                                 &credential_secrets_blinding_factors,
+                                                 &pub_key,
+                                                 &credential_issuance_nonce,
+                                                 None,
+                                                 None,
+                                                 None).unwrap();
+        }
+
+        assert!(Issuer::sign_credentials_batch(&[], &pub_key, &priv_key).unwrap().is_empty());
+    }
+
     #[test]
     #[ignore]
     fn generate_mocks() {
@@ -1440,7 +2316,8 @@ pub mod mocks {
 
     pub fn revocation_key_public() -> RevocationKeyPublic {
         RevocationKeyPublic {
-            z: Pair::from_string("BAF4F6C1044467 B355263E5FED41 8C0AF4C3EB94AF 3DE0C83ACA9928 2D6A7C6 FDF167021A1737 F7663EE5B2767B C5C4D3E69D387 34AA472296FCC7 B1660F7 C4741C69824558 CE22B92C952568 BB8179722E1BE7 1036505FEC026E 1C07F9FD DEAB5ECFD267CE 2E372388203E8D 973CB3DFAED87A EAB1BCFACB147E 12AC5746 BA65AD126B3FA5 1E1CF9FFC748E9 6017A982889E18 7AC0602B49C5E4 BAF574F 6CF7E2221ABC1 C4ABDFD08A7CD4 5CF4AB327CE15 3135590EE8EFC4 8192962 4FFCD9C89ABC45 3E0764B6CD0CF7 228E1021AA539B 8BA7447BCE3D7F F203473 DFB3E31073CDBD 7924EAC9D036C1 716066DCE76DC9 87B72FD4831A7 7296BA1 F417B8E0DAA939 9CA99939CB747E C79AC00D77664D D5C8F4836CDC28 1C615963 FD093CEBD6DED8 D16D939D4144E6 D209EEB27A2D40 E10AC83BFD60E4 4221B1A 535859DCF661A3 4A2F9EA4995F28 F9E4ECB0F4A21F CCB9D054387AF6 1B0A327E 20BF74410EF2D0 878F7EC03EA36B 76029AEF058F80 D988F4E307EC0E B9001C").unwrap()
+            z: Pair::from_string("BAF4F6C1044467 B355263E5FED41 8C0AF4C3EB94AF 3DE0C83ACA9928 2D6A7C6 FDF167021A1737 F7663EE5B2767B C5C4D3E69D387 34AA472296FCC7 B1660F7 C4741C69824558 CE22B92C952568 BB8179722E1BE7 1036505FEC026E 1C07F9FD DEAB5ECFD267CE 2E372388203E8D 973CB3DFAED87A EAB1BCFACB147E 12AC5746 BA65AD126B3FA5 1E1CF9FFC748E9 6017A982889E18 7AC0602B49C5E4 BAF574F 6CF7E2221ABC1 C4ABDFD08A7CD4 5CF4AB327CE15 3135590EE8EFC4 8192962 4FFCD9C89ABC45 3E0764B6CD0CF7 228E1021AA539B 8BA7447BCE3D7F F203473 DFB3E31073CDBD 7924EAC9D036C1 716066DCE76DC9 87B72FD4831A7 7296BA1 F417B8E0DAA939 9CA99939CB747E C79AC00D77664D D5C8F4836CDC28 1C615963 FD093CEBD6DED8 D16D939D4144E6 D209EEB27A2D40 E10AC83BFD60E4 4221B1A 535859DCF661A3 4A2F9EA4995F28 F9E4ECB0F4A21F CCB9D054387AF6 1B0A327E 20BF74410EF2D0 878F7EC03EA36B 76029AEF058F80 D988F4E307EC0E B9001C").unwrap(),
+            issuance_type: None
         }
     }
 
@@ -1477,3 +2354,4 @@ pub mod mocks {
         BigNumber::from_dec("69500003785041890145270364348670634122591474903142468939711692725859480163330").unwrap()
     }
 }
+
@@ -13,6 +13,7 @@ use crate::errors::IndyCryptoError;
 use self::libc::{c_void, c_char};
 use std::ffi::CString;
 use std::ptr;
+use std::sync::RwLock;
 
 pub type EnabledCB = extern fn(context: *const c_void,
                                level: u32,
@@ -28,6 +29,7 @@ pub type LogCB = extern fn(context: *const c_void,
 
 pub type FlushCB = extern fn(context: *const c_void);
 
+#[derive(Clone, Copy)]
 pub struct IndyCryptoLogger {
     context: *const c_void,
     enabled: Option<EnabledCB>,
@@ -45,7 +47,10 @@ impl log::Log for IndyCryptoLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         if let Some(enabled_cb) = self.enabled {
             let level = metadata.level() as u32;
-            let target = CString::new(metadata.target()).unwrap();
+            let target = match CString::new(metadata.target()) {
+                Ok(target) => target,
+                Err(_) => return false
+            };
 
             enabled_cb(self.context,
                        level,
@@ -55,14 +60,37 @@ impl log::Log for IndyCryptoLogger {
     }
 
     fn log(&self, record: &Record) {
+        // Records that can't be passed to C (interior NUL) are dropped, a logger has nowhere to report it
+        let _ = self.try_log(record);
+    }
+
+    fn flush(&self) {
+        if let Some(flush) = self.flush {
+            flush(self.context)
+        }
+    }
+}
+
+unsafe impl Sync for IndyCryptoLogger {}
+
+unsafe impl Send for IndyCryptoLogger {}
+
+impl IndyCryptoLogger {
+    fn try_log(&self, record: &Record) -> Result<(), IndyCryptoError> {
         let log_cb = self.log;
 
         let level = record.level() as u32;
-        let target = CString::new(record.target()).unwrap();
-        let message = CString::new(record.args().to_string()).unwrap();
-
-        let module_path = record.module_path().map(|a| CString::new(a).unwrap());
-        let file = record.file().map(|a| CString::new(a).unwrap());
+        let target = CString::new(record.target())?;
+        let message = CString::new(record.args().to_string())?;
+
+        let module_path = match record.module_path() {
+            Some(module_path) => Some(CString::new(module_path)?),
+            None => None
+        };
+        let file = match record.file() {
+            Some(file) => Some(CString::new(file)?),
+            None => None
+        };
         let line = record.line().unwrap_or(0);
 
         log_cb(self.context,
@@ -72,29 +100,32 @@ impl log::Log for IndyCryptoLogger {
                module_path.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
                file.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
                line,
-        )
-    }
+        );
 
-    fn flush(&self) {
-        if let Some(flush) = self.flush {
-            flush(self.context)
-        }
+        Ok(())
     }
-}
 
-unsafe impl Sync for IndyCryptoLogger {}
-
-unsafe impl Send for IndyCryptoLogger {}
-
-impl IndyCryptoLogger {
     pub fn init(context: *const c_void, enabled: Option<EnabledCB>, log: LogCB, flush: Option<FlushCB>) -> Result<(), IndyCryptoError> {
         let logger = IndyCryptoLogger::new(context, enabled, log, flush);
 
         log::set_boxed_logger(Box::new(logger))?;
         log::set_max_level(LevelFilter::Trace);
 
+        *LOGGER.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(logger);
+
         Ok(())
     }
+
+    /// Returns context and callbacks of the logger installed by `init`, if any.
+    pub fn get() -> Option<(*const c_void, Option<EnabledCB>, LogCB, Option<FlushCB>)> {
+        LOGGER.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .map(|logger| (logger.context, logger.enabled, logger.log, logger.flush))
+    }
+}
+
+lazy_static! {
+    // Callbacks stay available after the logger is moved into `log`, so users can chain them
+    static ref LOGGER: RwLock<Option<IndyCryptoLogger>> = RwLock::new(None);
 }
 
 pub struct IndyCryptoDefaultLogger;
@@ -123,4 +154,37 @@ macro_rules! secret {
 #[macro_export]
 macro_rules! secret {
     ($val:expr) => {{ "_" }};
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{ErrorCode, ToErrorCode};
+    use crate::log::{Level, Log};
+    use std::cell::Cell;
+
+    thread_local! {
+        static LOGGED: Cell<usize> = Cell::new(0);
+    }
+
+    extern fn _log(_context: *const c_void, _level: u32, _target: *const c_char, _message: *const c_char,
+                   _module_path: *const c_char, _file: *const c_char, _line: u32) {
+        LOGGED.with(|logged| logged.set(logged.get() + 1));
+    }
+
+    #[test]
+    fn log_works_for_interior_nul() {
+        let logger = IndyCryptoLogger::new(ptr::null(), None, _log, None);
+
+        let record = Record::builder().level(Level::Info).target("indy_crypto").args(format_args!("{}", "mes\0sage")).build();
+        let err = logger.try_log(&record).unwrap_err();
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
+
+        logger.log(&record);
+        assert_eq!(0, LOGGED.with(|logged| logged.get()));
+
+        let record = Record::builder().level(Level::Info).target("indy_crypto").args(format_args!("message")).build();
+        logger.log(&record);
+        assert_eq!(1, LOGGED.with(|logged| logged.get()));
+    }
+}
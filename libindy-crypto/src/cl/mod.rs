@@ -1,23 +1,31 @@
 #[macro_use]
 pub mod logger;
-mod commitment;
+pub mod commitment;
 mod constants;
 #[macro_use]
 mod datastructures;
 #[macro_use]
 mod helpers;
 mod hash;
+mod compact;
+#[cfg(feature = "seeded_keys")]
+mod seeded;
 pub mod issuer;
 pub mod prover;
+pub mod tails;
 pub mod verifier;
 
 use crate::bn::BigNumber;
 use crate::errors::IndyCryptoError;
 use crate::pair::*;
+#[cfg(feature = "cbor")]
+use crate::utils::cbor;
 
 use std::collections::{HashMap, HashSet, BTreeSet, BTreeMap};
 use std::hash::Hash;
 
+use rand::RngCore;
+
 /// Creates random nonce
 ///
 /// # Example
@@ -30,6 +38,117 @@ pub fn new_nonce() -> Result<Nonce, IndyCryptoError> {
     Ok(helpers::bn_rand(constants::LARGE_NONCE)?)
 }
 
+/// Creates nonce deterministically from seed (e.g. entropy taken from HSM or fixed seed of a test).
+///
+/// Nonce is the first 80 bits of SHA-256 hash of the seed as big-endian integer,
+/// so the same seed always gives the same nonce. Seed must be unpredictable for production use.
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::new_nonce_from_seed;
+///
+/// let nonce = new_nonce_from_seed(b"indy").unwrap();
+/// assert_eq!("400727458142521792328963", nonce.to_dec().unwrap());
+/// ```
+pub fn new_nonce_from_seed(seed: &[u8]) -> Result<Nonce, IndyCryptoError> {
+    let hash = BigNumber::hash(seed)?;
+    BigNumber::from_bytes(&hash[..constants::LARGE_NONCE / 8])
+}
+
+/// Creates random nonce with the given random number generator instead of the internal one.
+///
+/// # Example
+/// ```
+/// extern crate rand;
+/// extern crate indy_crypto;
+///
+/// use indy_crypto::cl::new_nonce_with_rng;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let nonce = new_nonce_with_rng(&mut StdRng::seed_from_u64(42)).unwrap();
+/// assert_eq!(nonce, new_nonce_with_rng(&mut StdRng::seed_from_u64(42)).unwrap());
+/// ```
+pub fn new_nonce_with_rng(rng: &mut dyn RngCore) -> Result<Nonce, IndyCryptoError> {
+    let mut bytes = [0u8; constants::LARGE_NONCE / 8];
+    rng.try_fill_bytes(&mut bytes)
+        .map_err(|err| IndyCryptoError::RngFailure(format!("Unable to fill nonce: {}", err)))?;
+    BigNumber::from_bytes(&bytes)
+}
+
+/// Checks that nonce is non negative number of at most 80 bits, like nonces created by this module.
+pub fn check_nonce(nonce: &Nonce) -> Result<(), IndyCryptoError> {
+    if nonce.is_negative() || nonce.num_bits()? > constants::LARGE_NONCE as i32 {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Nonce must be non negative number of at most {} bits", constants::LARGE_NONCE)));
+    }
+    Ok(())
+}
+
+/// How a raw attribute value is encoded by `encode_attribute`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeKind {
+    /// De-facto Indy encoding: decimal integer within i32 range is passed through, any other value is hashed.
+    Auto,
+    /// Value must be decimal integer within i32 range (e.g. to be used in predicates).
+    Integer,
+    /// Value is hashed even if it looks like an integer (e.g. "01234" zip code, that would lose leading zero).
+    Text
+}
+
+/// Encodes raw attribute value to decimal string of a credential value.
+///
+/// Decimal integer within i32 range (optional sign and digits) is encoded as its canonical decimal string.
+/// Any other value is encoded as SHA-256 hash of its UTF-8 bytes, read as 256-bit big-endian integer.
+///
+/// # Example
+/// ```
+/// use indy_crypto::cl::{encode_attribute, AttributeKind};
+///
+/// assert_eq!("28", encode_attribute("28", AttributeKind::Auto).unwrap());
+/// assert_eq!("99262857098057710338306967609588410025648622308394250666849665532448612202874",
+///            encode_attribute("Alex", AttributeKind::Auto).unwrap());
+/// ```
+pub fn encode_attribute(raw: &str, kind: AttributeKind) -> Result<String, IndyCryptoError> {
+    let int_value = match kind {
+        AttributeKind::Text => None,
+        AttributeKind::Auto | AttributeKind::Integer => raw.parse::<i32>().ok()
+    };
+
+    match (int_value, kind) {
+        (Some(int_value), _) => Ok(int_value.to_string()),
+        (None, AttributeKind::Integer) =>
+            Err(IndyCryptoError::InvalidStructure(format!("Attribute value '{}' isn't integer within i32 range", raw))),
+        (None, _) => BigNumber::from_bytes(&BigNumber::hash(raw.as_bytes())?)?.to_dec()
+    }
+}
+
+/// Checks that encoded attribute value is canonical output of `encode_attribute`.
+///
+/// Returns integer value for encoded integer or None for encoded hash.
+pub fn decode_check(encoded: &str) -> Result<Option<i32>, IndyCryptoError> {
+    let digits = encoded.strip_prefix('-').unwrap_or(encoded);
+
+    let canonical = !digits.is_empty() &&
+        digits.bytes().all(|b| b.is_ascii_digit()) &&
+        (digits == "0" || !digits.starts_with('0')) &&
+        encoded != "-0";
+
+    if !canonical {
+        return Err(IndyCryptoError::InvalidStructure(format!("Encoded attribute value '{}' isn't canonical decimal", encoded)));
+    }
+
+    if let Ok(int_value) = encoded.parse::<i32>() {
+        return Ok(Some(int_value));
+    }
+
+    if encoded.starts_with('-') || BigNumber::from_dec(encoded)?.num_bits()? > 256 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Encoded attribute value '{}' is out of range", encoded)));
+    }
+
+    Ok(None)
+}
+
 /// A list of attributes a Credential is based on.
 #[derive(Debug, Clone)]
 pub struct CredentialSchema {
@@ -39,21 +158,24 @@ pub struct CredentialSchema {
 /// A Builder of `Credential Schema`.
 #[derive(Debug)]
 pub struct CredentialSchemaBuilder {
-    attrs: BTreeSet<String>, /* attr names */
+    attrs: Vec<String>, /* attr names in order of adding */
 }
 
 impl CredentialSchemaBuilder {
     pub fn new() -> Result<CredentialSchemaBuilder, IndyCryptoError> {
-        Ok(CredentialSchemaBuilder { attrs: BTreeSet::new() })
+        Ok(CredentialSchemaBuilder { attrs: Vec::new() })
     }
 
     pub fn add_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
-        self.attrs.insert(attr.to_owned());
+        self.attrs.push(attr.to_owned());
         Ok(())
     }
 
+    /// Returns `InvalidStructure` if some attribute name is empty, whitespace only or duplicated
+    /// (see `check_attr_names`).
     pub fn finalize(self) -> Result<CredentialSchema, IndyCryptoError> {
-        Ok(CredentialSchema { attrs: self.attrs })
+        check_attr_names(self.attrs.iter())?;
+        Ok(CredentialSchema { attrs: self.attrs.into_iter().collect() })
     }
 }
 
@@ -64,24 +186,48 @@ pub struct NonCredentialSchema {
 
 #[derive(Debug)]
 pub struct NonCredentialSchemaBuilder {
-    attrs: BTreeSet<String>,
+    attrs: Vec<String>,
 }
 
 impl NonCredentialSchemaBuilder {
     pub fn new() -> Result<NonCredentialSchemaBuilder, IndyCryptoError> {
         Ok(NonCredentialSchemaBuilder {
-            attrs: BTreeSet::new(),
+            attrs: Vec::new(),
         })
     }
 
     pub fn add_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
-        self.attrs.insert(attr.to_owned());
+        self.attrs.push(attr.to_owned());
         Ok(())
     }
 
+    /// Returns `InvalidStructure` if some attribute name is empty, whitespace only or duplicated
+    /// (see `check_attr_names`).
     pub fn finalize(self) -> Result<NonCredentialSchema, IndyCryptoError> {
-        Ok(NonCredentialSchema { attrs: self.attrs })
+        check_attr_names(self.attrs.iter())?;
+        Ok(NonCredentialSchema { attrs: self.attrs.into_iter().collect() })
+    }
+}
+
+/// Checks names of attributes added to a builder.
+///
+/// Names are matched case sensitively everywhere in this module, but names differing only in case
+/// are rejected as duplicates: Indy wrappers canonicalize attribute names by lowercasing them,
+/// so such attributes can't be told apart in proof requests.
+fn check_attr_names<'a, I>(attrs: I) -> Result<(), IndyCryptoError> where I: Iterator<Item=&'a String> {
+    let mut canonical_attrs = HashSet::new();
+
+    for attr in attrs {
+        if attr.trim().is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute name '{}' is empty", attr)));
+        }
+
+        if !canonical_attrs.insert(attr.to_lowercase()) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' is duplicated", attr)));
+        }
     }
+
+    Ok(())
 }
 
 /// The m value for attributes,
@@ -146,7 +292,7 @@ impl CredentialValue {
 }
 
 /// Values of attributes from `Claim Schema` (must be integers).
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CredentialValues {
     attrs_values: BTreeMap<String, CredentialValue>,
 }
@@ -157,32 +303,49 @@ impl CredentialValues {
             attrs_values: clone_credential_value_map(&self.attrs_values)?
         })
     }
+
+    /// Creates credential values of known attributes from pairs of attribute name and decimal value
+    /// (see `encode_attribute` to get decimal value of a raw value).
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::CredentialValues;
+    ///
+    /// let _credential_values = CredentialValues::try_from_pairs(&[("name", "1139481716457488690172217916278103335"), ("age", "28")]).unwrap();
+    /// ```
+    pub fn try_from_pairs(pairs: &[(&str, &str)]) -> Result<CredentialValues, IndyCryptoError> {
+        let mut credential_values_builder = CredentialValuesBuilder::new()?;
+        for &(attr, value) in pairs {
+            credential_values_builder.add_dec_known(attr, value)?;
+        }
+        credential_values_builder.finalize()
+    }
 }
 
 /// A Builder of `Credential Values`.
 #[derive(Debug)]
 pub struct CredentialValuesBuilder {
-    attrs_values: BTreeMap<String, CredentialValue>, /* attr_name -> int representation of value */
+    attrs_values: Vec<(String, CredentialValue)>, /* attr_name -> int representation of value in order of adding */
 }
 
 impl CredentialValuesBuilder {
     pub fn new() -> Result<CredentialValuesBuilder, IndyCryptoError> {
-        Ok(CredentialValuesBuilder { attrs_values: BTreeMap::new() })
+        Ok(CredentialValuesBuilder { attrs_values: Vec::new() })
     }
 
     pub fn add_dec_known(&mut self, attr: &str, value: &str) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(
+        self.attrs_values.push((
             attr.to_owned(),
             CredentialValue::Known { value: BigNumber::from_dec(value)? },
-        );
+        ));
         Ok(())
     }
 
     pub fn add_dec_hidden(&mut self, attr: &str, value: &str) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(
+        self.attrs_values.push((
             attr.to_owned(),
             CredentialValue::Hidden { value: BigNumber::from_dec(value)? },
-        );
+        ));
         Ok(())
     }
 
@@ -192,13 +355,13 @@ impl CredentialValuesBuilder {
         value: &str,
         blinding_factor: &str,
     ) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(
+        self.attrs_values.push((
             attr.to_owned(),
             CredentialValue::Commitment {
                 value: BigNumber::from_dec(value)?,
                 blinding_factor: BigNumber::from_dec(blinding_factor)?,
             },
-        );
+        ));
         Ok(())
     }
 
@@ -207,10 +370,10 @@ impl CredentialValuesBuilder {
         attr: &str,
         value: &BigNumber,
     ) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(
+        self.attrs_values.push((
             attr.to_owned(),
             CredentialValue::Known { value: value.clone()? },
-        );
+        ));
         Ok(())
     }
 
@@ -219,10 +382,10 @@ impl CredentialValuesBuilder {
         attr: &str,
         value: &BigNumber,
     ) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(
+        self.attrs_values.push((
             attr.to_owned(),
             CredentialValue::Hidden { value: value.clone()? },
-        );
+        ));
         Ok(())
     }
 
@@ -232,18 +395,41 @@ impl CredentialValuesBuilder {
         value: &BigNumber,
         blinding_factor: &BigNumber,
     ) -> Result<(), IndyCryptoError> {
-        self.attrs_values.insert(
+        self.attrs_values.push((
             attr.to_owned(),
             CredentialValue::Commitment {
                 value: value.clone()?,
                 blinding_factor: blinding_factor.clone()?,
             },
-        );
+        ));
         Ok(())
     }
 
+    /// Returns `InvalidStructure` if some attribute name is empty, whitespace only or duplicated
+    /// (see `check_attr_names`).
     pub fn finalize(self) -> Result<CredentialValues, IndyCryptoError> {
-        Ok(CredentialValues { attrs_values: self.attrs_values })
+        check_attr_names(self.attrs_values.iter().map(|(attr, _)| attr))?;
+        Ok(CredentialValues { attrs_values: self.attrs_values.into_iter().collect() })
+    }
+
+    /// Finalizes credential values like `finalize` and checks, that there is value for every attribute
+    /// of credential and non credential schemas and there are no values of other attributes.
+    pub fn finalize_for_schema(self,
+                               credential_schema: &CredentialSchema,
+                               non_credential_schema: &NonCredentialSchema) -> Result<CredentialValues, IndyCryptoError> {
+        let credential_values = self.finalize()?;
+
+        let schema_attrs = credential_schema.attrs.union(&non_credential_schema.attrs).collect::<BTreeSet<&String>>();
+
+        if let Some(attr) = credential_values.attrs_values.keys().find(|attr| !schema_attrs.contains(attr)) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Value of attribute '{}' isn't in credential schema", attr)));
+        }
+
+        if let Some(attr) = schema_attrs.iter().find(|attr| !credential_values.attrs_values.contains_key(attr.as_str())) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Value of credential schema attribute '{}' is missing", attr)));
+        }
+
+        Ok(credential_values)
     }
 }
 
@@ -385,6 +571,9 @@ pub struct CredentialRevocationPrivateKey {
 
 pub type Accumulator = PointG2;
 
+// Flag of compact delta that previous accumulator follows
+const COMPACT_PREV_ACCUM_FLAG: u8 = 0x01;
+
 /// `Revocation Registry` contains accumulator.
 /// Must be published by Issuer on a tamper-evident and highly available storage
 /// Used by prover to prove that a credential hasn't revoked by the issuer
@@ -428,9 +617,21 @@ impl RevocationRegistryDelta {
         }
     }
 
+    /// Merges `other_delta`, the delta that follows this one, into this delta.
+    ///
+    /// The result is the delta from `prev_accum` of this delta to `accum` of `other_delta`.
+    /// Only the net change of indices is kept: an index that is revoked and issued again is in
+    /// neither set, as the accumulator has its tail as before, so witness update doesn't add it twice.
+    /// The delta is left unchanged on error.
     pub fn merge(&mut self, other_delta: &RevocationRegistryDelta) -> Result<(), IndyCryptoError> {
-        if other_delta.prev_accum.is_none() || self.accum != other_delta.prev_accum.unwrap() {
-            return Err(IndyCryptoError::InvalidStructure(format!("Deltas can not be merged.")));
+        let other_prev_accum = other_delta.prev_accum
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                "Deltas can not be merged: the other delta has no previous accumulator".to_string()))?;
+
+        // Compared as affine points, equal accumulators may differ in projective coordinates
+        if self.accum.to_bytes()? != other_prev_accum.to_bytes()? {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Deltas can not be merged: previous accumulator of the other delta is not accumulator of this delta".to_string()));
         }
 
         self.accum = other_delta.accum;
@@ -451,13 +652,387 @@ impl RevocationRegistryDelta {
 
         Ok(())
     }
+
+    /// Returns this delta merged with `other_delta` without changing either of them, see `merge`.
+    pub fn merged(&self, other_delta: &RevocationRegistryDelta) -> Result<RevocationRegistryDelta, IndyCryptoError> {
+        let mut merged = self.clone();
+        merged.merge(other_delta)?;
+        Ok(merged)
+    }
+
+    /// Returns compact binary representation of delta, much smaller than json for large sets of indices.
+    ///
+    /// Accumulators are stored as compressed points, sets of indices as sorted runs of consecutive
+    /// indices, so a dense set takes a few bytes whatever its size. Json representation is not affected.
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut writer = compact::CompactWriter::new();
+
+        writer.write_u8(if self.prev_accum.is_some() { COMPACT_PREV_ACCUM_FLAG } else { 0 });
+        if let Some(ref prev_accum) = self.prev_accum {
+            writer.write_bytes(&prev_accum.to_compressed_bytes()?);
+        }
+        writer.write_bytes(&self.accum.to_compressed_bytes()?);
+        writer.write_indices(&self.issued);
+        writer.write_indices(&self.revoked);
+
+        Ok(writer.finalize())
+    }
+
+    /// Creates delta from bytes of `to_compact_bytes`, malformed bytes are `InvalidStructure`,
+    /// accumulators that are not points of the curve are `InvalidPoint`.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<RevocationRegistryDelta, IndyCryptoError> {
+        let mut reader = compact::CompactReader::new(bytes)?;
+
+        let flags = reader.read_u8()?;
+        if flags & !COMPACT_PREV_ACCUM_FLAG != 0 {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid compact bytes: unknown flags {:#04x}", flags)));
+        }
+
+        let prev_accum = if flags & COMPACT_PREV_ACCUM_FLAG != 0 {
+            Some(Accumulator::from_compressed_bytes(reader.read_bytes(PointG2::COMPRESSED_BYTES_REPR_SIZE)?)?)
+        } else {
+            None
+        };
+        let accum = Accumulator::from_compressed_bytes(reader.read_bytes(PointG2::COMPRESSED_BYTES_REPR_SIZE)?)?;
+        let issued = reader.read_indices()?;
+        let revoked = reader.read_indices()?;
+        reader.finalize()?;
+
+        Ok(RevocationRegistryDelta { prev_accum, accum, issued, revoked })
+    }
+}
+
+/// `Accumulator Checkpoint` is a snapshot of revocation registry: its accumulator and net changes of
+/// indices since creation of the registry. The registry at any delta is restored from the nearest
+/// checkpoint by applying the following deltas, without replay of all deltas from creation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccumulatorCheckpoint {
+    seq_no: u64,
+    accum: Accumulator,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    #[serde(default)]
+    issued: HashSet<u32>,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    #[serde(default)]
+    revoked: HashSet<u32>
+}
+
+impl AccumulatorCheckpoint {
+    /// Creates checkpoint of just created revocation registry, its sequence number is 0.
+    pub fn new(rev_reg: &RevocationRegistry) -> AccumulatorCheckpoint {
+        AccumulatorCheckpoint {
+            seq_no: 0,
+            accum: rev_reg.accum,
+            issued: HashSet::new(),
+            revoked: HashSet::new()
+        }
+    }
+
+    /// Number of deltas applied since creation of the registry.
+    pub fn seq_no(&self) -> u64 {
+        self.seq_no
+    }
+
+    /// Indices issued since creation of the registry.
+    pub fn issued(&self) -> &HashSet<u32> {
+        &self.issued
+    }
+
+    /// Indices revoked since creation of the registry.
+    pub fn revoked(&self) -> &HashSet<u32> {
+        &self.revoked
+    }
+
+    pub fn revocation_registry(&self) -> RevocationRegistry {
+        RevocationRegistry { accum: self.accum }
+    }
+
+    /// Applies `rev_reg_delta`, the delta that follows the checkpoint, and increments sequence number.
+    ///
+    /// Returns `InvalidStructure` with continuity violation if the delta has no previous accumulator
+    /// or it isn't accumulator of the checkpoint. The checkpoint is left unchanged on error.
+    pub fn apply_delta(&mut self, rev_reg_delta: &RevocationRegistryDelta) -> Result<(), IndyCryptoError> {
+        let seq_no = self.seq_no + 1;
+        self._apply(rev_reg_delta, seq_no)
+    }
+
+    /// Applies `diff` of this checkpoint to another one, the sequence number becomes the one of the other checkpoint.
+    ///
+    /// Returns `InvalidStructure` with continuity violation if the diff starts from other sequence number
+    /// or accumulator. The checkpoint is left unchanged on error.
+    pub fn apply_diff(&mut self, diff: &AccumulatorCheckpointDiff) -> Result<(), IndyCryptoError> {
+        if diff.from_seq_no != self.seq_no {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Delta continuity violation: the diff starts from checkpoint {}, not {}", diff.from_seq_no, self.seq_no)));
+        }
+
+        self._apply(&diff.delta, diff.to_seq_no)
+    }
+
+    fn _apply(&mut self, rev_reg_delta: &RevocationRegistryDelta, seq_no: u64) -> Result<(), IndyCryptoError> {
+        let prev_accum = rev_reg_delta.prev_accum
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                "Delta continuity violation: the delta has no previous accumulator".to_string()))?;
+
+        // Compared as affine points, equal accumulators may differ in projective coordinates
+        if self.accum.to_bytes()? != prev_accum.to_bytes()? {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Delta continuity violation: previous accumulator of the delta is not accumulator of checkpoint {}", self.seq_no)));
+        }
+
+        let mut changes = RevocationRegistryDelta {
+            prev_accum: None,
+            accum: self.accum,
+            issued: self.issued.clone(),
+            revoked: self.revoked.clone()
+        };
+        changes.merge(rev_reg_delta)?;
+
+        self.seq_no = seq_no;
+        self.accum = changes.accum;
+        self.issued = changes.issued;
+        self.revoked = changes.revoked;
+
+        Ok(())
+    }
+
+    /// Returns the diff from this checkpoint to `other`, a later or an earlier checkpoint of the same registry.
+    ///
+    /// Indices are the net changes as in merged deltas, so the delta of the diff updates witnesses as the deltas between the checkpoints.
+    pub fn diff(&self, other: &AccumulatorCheckpoint) -> AccumulatorCheckpointDiff {
+        let mut issued = other.issued.difference(&self.issued).cloned().collect::<HashSet<u32>>();
+        issued.extend(self.revoked.difference(&other.revoked));
+
+        let mut revoked = other.revoked.difference(&self.revoked).cloned().collect::<HashSet<u32>>();
+        revoked.extend(self.issued.difference(&other.issued));
+
+        AccumulatorCheckpointDiff {
+            from_seq_no: self.seq_no,
+            to_seq_no: other.seq_no,
+            delta: RevocationRegistryDelta {
+                prev_accum: Some(self.accum),
+                accum: other.accum,
+                issued,
+                revoked
+            }
+        }
+    }
+}
+
+/// Delta between two checkpoints of the same registry together with their sequence numbers.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccumulatorCheckpointDiff {
+    from_seq_no: u64,
+    to_seq_no: u64,
+    delta: RevocationRegistryDelta
+}
+
+impl AccumulatorCheckpointDiff {
+    /// Sequence number of the checkpoint the diff starts from.
+    pub fn from_seq_no(&self) -> u64 {
+        self.from_seq_no
+    }
+
+    /// Sequence number of the checkpoint the diff leads to.
+    pub fn to_seq_no(&self) -> u64 {
+        self.to_seq_no
+    }
+
+    /// Delta between the checkpoints, e.g. to update witnesses.
+    pub fn delta(&self) -> &RevocationRegistryDelta {
+        &self.delta
+    }
+}
+
+/// Type of issuance of revocation registry, serialized as in revocation registry definition of Indy ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum IssuanceType {
+    /// All indices are issued on creation of registry, deltas of signing are empty, revocation deltas contain revoked indices.
+    #[serde(rename = "ISSUANCE_BY_DEFAULT")]
+    IssuanceByDefault,
+    /// Nothing is issued on creation of registry, deltas of signing contain issued indices.
+    #[serde(rename = "ISSUANCE_ON_DEMAND")]
+    IssuanceOnDemand
+}
+
+impl IssuanceType {
+    /// Value of `issuance_by_default` arguments of registry operations.
+    pub fn is_by_default(&self) -> bool {
+        *self == IssuanceType::IssuanceByDefault
+    }
+}
+
+impl From<bool> for IssuanceType {
+    fn from(issuance_by_default: bool) -> IssuanceType {
+        if issuance_by_default { IssuanceType::IssuanceByDefault } else { IssuanceType::IssuanceOnDemand }
+    }
+}
+
+/// Strategy of choosing revocation index of a new credential, see `RevocationIndexAllocator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum IndexAllocation {
+    /// The lowest index that isn't used.
+    Sequential,
+    /// The given index, it must be within `1..=max_cred_num` and not used.
+    Explicit(u32),
+    /// Random index that isn't used.
+    Random
+}
+
+// Random indices are drawn from the whole range while the registry is sparse,
+// a nearly full registry takes random one of the remaining indices instead
+const RANDOM_ALLOCATION_ATTEMPTS: u32 = 32;
+
+/// `Revocation Index Allocator` keeps indices of credentials signed in a revocation registry
+/// and chooses index of every new credential by `IndexAllocation`, so issuers can mix their own
+/// indices (e.g. of reserved ranges or shards) with indices chosen by the allocator.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationIndexAllocator {
+    max_cred_num: u32,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    #[serde(default)]
+    used: HashSet<u32>,
+    // All indices below are used
+    #[serde(default = "RevocationIndexAllocator::first_index")]
+    next_sequential: u32
+}
+
+impl RevocationIndexAllocator {
+    /// Creates allocator of registry of `max_cred_num` credentials without used indices.
+    pub fn new(max_cred_num: u32) -> RevocationIndexAllocator {
+        RevocationIndexAllocator {
+            max_cred_num,
+            used: HashSet::new(),
+            next_sequential: RevocationIndexAllocator::first_index()
+        }
+    }
+
+    fn first_index() -> u32 {
+        1
+    }
+
+    pub fn max_cred_num(&self) -> u32 {
+        self.max_cred_num
+    }
+
+    /// Indices of signed credentials.
+    pub fn used(&self) -> &HashSet<u32> {
+        &self.used
+    }
+
+    /// Returns index of new credential by `allocation` without marking it used.
+    ///
+    /// Returns `AnoncredsInvalidRevocationAccumulatorIndex` for explicit index out of `1..=max_cred_num`
+    /// or already used, `AnoncredsRevocationAccumulatorIsFull` if all indices are used.
+    pub fn next_index(&self, allocation: &IndexAllocation) -> Result<u32, IndyCryptoError> {
+        match *allocation {
+            IndexAllocation::Explicit(rev_idx) => {
+                self._check_index(rev_idx)?;
+                Ok(rev_idx)
+            }
+            IndexAllocation::Sequential => {
+                self._check_not_full()?;
+                (self.next_sequential..=self.max_cred_num)
+                    .find(|rev_idx| !self.used.contains(rev_idx))
+                    .ok_or_else(|| self._full())
+            }
+            IndexAllocation::Random => {
+                self._check_not_full()?;
+                let max_cred_num = BigNumber::from_u32(self.max_cred_num as usize)?;
+
+                for _ in 0..RANDOM_ALLOCATION_ATTEMPTS {
+                    let rev_idx = _rand_index(&max_cred_num)? + 1;
+                    if !self.used.contains(&rev_idx) {
+                        return Ok(rev_idx);
+                    }
+                }
+
+                let free = BigNumber::from_u32((self.max_cred_num as usize) - self.used.len())?;
+                let nth = _rand_index(&free)? as usize;
+                (1..=self.max_cred_num)
+                    .filter(|rev_idx| !self.used.contains(rev_idx))
+                    .nth(nth)
+                    .ok_or_else(|| self._full())
+            }
+        }
+    }
+
+    /// Marks `rev_idx` used, e.g. index of credential signed with `Issuer::sign_credential_with_revoc`.
+    ///
+    /// Returns `AnoncredsInvalidRevocationAccumulatorIndex` for index out of `1..=max_cred_num` or already used.
+    pub fn reserve(&mut self, rev_idx: u32) -> Result<(), IndyCryptoError> {
+        self._check_index(rev_idx)?;
+        self.used.insert(rev_idx);
+        while self.used.contains(&self.next_sequential) {
+            self.next_sequential += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns index of new credential by `allocation` and marks it used.
+    pub fn allocate(&mut self, allocation: &IndexAllocation) -> Result<u32, IndyCryptoError> {
+        let rev_idx = self.next_index(allocation)?;
+        self.reserve(rev_idx)?;
+        Ok(rev_idx)
+    }
+
+    fn _check_index(&self, rev_idx: u32) -> Result<(), IndyCryptoError> {
+        if rev_idx == 0 || rev_idx > self.max_cred_num {
+            return Err(IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(
+                format!("Index {} is out of range 1..={}", rev_idx, self.max_cred_num)));
+        }
+        if self.used.contains(&rev_idx) {
+            return Err(IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(
+                format!("Index {} is already used", rev_idx)));
+        }
+        Ok(())
+    }
+
+    fn _check_not_full(&self) -> Result<(), IndyCryptoError> {
+        if self.used.len() as u64 >= self.max_cred_num as u64 {
+            return Err(self._full());
+        }
+        Ok(())
+    }
+
+    fn _full(&self) -> IndyCryptoError {
+        IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(format!("All {} indices are used", self.max_cred_num))
+    }
+}
+
+// Random number in range [0, bound)
+fn _rand_index(bound: &BigNumber) -> Result<u32, IndyCryptoError> {
+    bound.rand_range()?
+        .to_dec()?
+        .parse::<u32>()
+        .map_err(|err| IndyCryptoError::InvalidState(format!("Random index is out of range: {}", err)))
 }
 
 /// `Revocation Key Public` Accumulator public key.
 /// Must be published together with Accumulator
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RevocationKeyPublic {
-    z: Pair
+    z: Pair,
+    // Absent in keys created before the type was stored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issuance_type: Option<IssuanceType>
+}
+
+impl RevocationKeyPublic {
+    /// Type of issuance the registry was created with, `None` for keys serialized without it.
+    pub fn issuance_type(&self) -> Option<IssuanceType> {
+        self.issuance_type
+    }
+
+    // Value of `issuance_by_default` arguments of registry operations for the registry of the key
+    fn _issuance_by_default(&self) -> Result<bool, IndyCryptoError> {
+        self.issuance_type
+            .map(|issuance_type| issuance_type.is_by_default())
+            .ok_or_else(|| IndyCryptoError::InvalidStructure("Type of issuance isn't stored in revocation key public".to_string()))
+    }
 }
 
 /// `Revocation Key Private` Accumulator primate key.
@@ -501,6 +1076,22 @@ impl RevocationTailsGenerator {
         self.size - self.current_index
     }
 
+    /// Returns index of the tail `next` generates, a checkpoint to resume generation from by `seek`.
+    pub fn index(&self) -> u32 {
+        self.current_index
+    }
+
+    /// Moves generation to the tail with `index`, from 0 up to `index() + count()` that ends generation.
+    pub fn seek(&mut self, index: u32) -> Result<(), IndyCryptoError> {
+        if index > self.size {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Tail index is out of range: expected at most {}, actual {}", self.size, index)));
+        }
+
+        self.current_index = index;
+        Ok(())
+    }
+
     pub fn next(&mut self) -> Result<Option<Tail>, IndyCryptoError> {
         if self.current_index >= self.size {
             return Ok(None);
@@ -512,6 +1103,54 @@ impl RevocationTailsGenerator {
 
         Ok(Some(tail))
     }
+
+    /// Generates next `chunk_size` tails in index order, fewer for the last chunk and none if all
+    /// tails are generated. Tails can be written out chunk by chunk without keeping them all in memory.
+    pub fn next_chunk(&mut self, chunk_size: usize) -> Result<Vec<Tail>, IndyCryptoError> {
+        if chunk_size == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Chunk size must be positive".to_string()));
+        }
+
+        let mut chunk = Vec::with_capacity(chunk_size.min(self.count() as usize));
+        while chunk.len() < chunk_size {
+            match self.next()? {
+                Some(tail) => chunk.push(tail),
+                None => break
+            }
+        }
+        Ok(chunk)
+    }
+
+    /// Returns iterator over chunks of `next_chunk`, it ends when all tails are generated or after an error.
+    pub fn chunks(&mut self, chunk_size: usize) -> RevocationTailsChunks<'_> {
+        RevocationTailsChunks { rev_tails_generator: self, chunk_size, failed: false }
+    }
+}
+
+/// Iterator over chunks of `Tail's`, see `RevocationTailsGenerator::chunks`.
+pub struct RevocationTailsChunks<'a> {
+    rev_tails_generator: &'a mut RevocationTailsGenerator,
+    chunk_size: usize,
+    failed: bool
+}
+
+impl<'a> Iterator for RevocationTailsChunks<'a> {
+    type Item = Result<Vec<Tail>, IndyCryptoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        match self.rev_tails_generator.next_chunk(self.chunk_size) {
+            Ok(ref chunk) if chunk.is_empty() => None,
+            Ok(chunk) => Some(Ok(chunk)),
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
 }
 
 pub trait RevocationTailsAccessor {
@@ -587,6 +1226,10 @@ pub struct Witness {
 }
 
 impl Witness {
+    /// Creates witness of the credential with `rev_idx` in the registry state given by `rev_reg_delta`.
+    ///
+    /// `issuance_by_default` must be the type of issuance the registry was created with, it isn't checked here,
+    /// use `new_for_registry` to take it from the revocation key public.
     pub fn new<RTA>(rev_idx: u32,
                     max_cred_num: u32,
                     issuance_by_default: bool,
@@ -619,6 +1262,17 @@ impl Witness {
         Ok(witness)
     }
 
+    /// Creates witness as `Witness::new` with type of issuance stored in the revocation key public.
+    ///
+    /// Returns `InvalidStructure` if the key was serialized without type of issuance.
+    pub fn new_for_registry<RTA>(rev_idx: u32,
+                                 max_cred_num: u32,
+                                 rev_key_pub: &RevocationKeyPublic,
+                                 rev_reg_delta: &RevocationRegistryDelta,
+                                 rev_tails_accessor: &RTA) -> Result<Witness, IndyCryptoError> where RTA: RevocationTailsAccessor {
+        Witness::new(rev_idx, max_cred_num, rev_key_pub._issuance_by_default()?, rev_reg_delta, rev_tails_accessor)
+    }
+
     pub fn update<RTA>(&mut self,
                        rev_idx: u32,
                        max_cred_num: u32,
@@ -655,48 +1309,277 @@ impl Witness {
 
         Ok(())
     }
-}
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct WitnessSignature {
-    sigma_i: PointG2,
-    u_i: PointG2,
-    g_i: PointG1
-}
+    /// Updates the witness across ordered consecutive deltas at once, the result is the same as of
+    /// `update` with every delta in turn.
+    ///
+    /// The deltas are merged first, so each affected tail is read once and indices that cancel out
+    /// across the deltas are not read at all. The witness is left unchanged on error.
+    pub fn update_with_deltas<RTA>(&mut self,
+                                   rev_idx: u32,
+                                   max_cred_num: u32,
+                                   rev_reg_deltas: &[RevocationRegistryDelta],
+                                   rev_tails_accessor: &RTA) -> Result<(), IndyCryptoError> where RTA: RevocationTailsAccessor {
+        trace!("Witness::update_with_deltas: >>> rev_idx: {:?}, max_cred_num: {:?}, rev_reg_deltas: {:?}",
+               rev_idx, max_cred_num, rev_reg_deltas);
+
+        let (first, rest) = match rev_reg_deltas.split_first() {
+            Some(split) => split,
+            None => return Ok(())
+        };
 
-/// Secret key encoded in a credential that is used to prove that prover owns the credential; can be used to
-/// prove linkage across credentials.
-/// Prover blinds master secret, generating `BlindedCredentialSecrets` and `CredentialSecretsBlindingFactors` (blinding factors)
-/// and sends the `BlindedCredentialSecrets` to Issuer who then encodes it credential creation.
-/// The blinding factors are used by Prover for post processing of issued credentials.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct MasterSecret {
-    ms: BigNumber,
-}
+        let mut merged = first.clone();
+        for rev_reg_delta in rest {
+            merged.merge(rev_reg_delta)?;
+        }
 
-impl MasterSecret {
-    pub fn clone(&self) -> Result<MasterSecret, IndyCryptoError> {
-        Ok(MasterSecret { ms: self.ms.clone()? })
+        self.update(rev_idx, max_cred_num, &merged, rev_tails_accessor)?;
+
+        trace!("Witness::update_with_deltas: <<<");
+
+        Ok(())
     }
 
-    pub fn value(&self) -> Result<BigNumber, IndyCryptoError> {
-        Ok(self.ms.clone()?)
+    /// Returns compact binary representation of witness with `omega` as compressed point.
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut writer = compact::CompactWriter::new();
+        writer.write_bytes(&self.omega.to_compressed_bytes()?);
+        Ok(writer.finalize())
     }
-}
 
-/// Blinded Master Secret uses by Issuer in credential creation.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct BlindedCredentialSecrets {
-    u: BigNumber,
-    ur: Option<PointG1>,
-    hidden_attributes: BTreeSet<String>,
-    committed_attributes: BTreeMap<String, BigNumber>
+    /// Creates witness from bytes of `to_compact_bytes`, malformed bytes are `InvalidStructure`,
+    /// `omega` that is not a point of the curve is `InvalidPoint`.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Witness, IndyCryptoError> {
+        let mut reader = compact::CompactReader::new(bytes)?;
+        let omega = PointG2::from_compressed_bytes(reader.read_bytes(PointG2::COMPRESSED_BYTES_REPR_SIZE)?)?;
+        reader.finalize()?;
+        Ok(Witness { omega })
+    }
 }
 
-/// `CredentialSecretsBlindingFactors` used by Prover for post processing of credentials received from Issuer.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct CredentialSecretsBlindingFactors {
-    v_prime: BigNumber,
+/// Witness computation driven by the caller, that supplies the tails required by the delta one by one
+/// in any order instead of giving access to all tails, e.g. as they are fetched by HTTP range requests:
+///
+/// ```text
+/// let mut updater = WitnessUpdater::new_for_update(&witness, rev_idx, max_cred_num, &rev_reg_delta)?;
+/// while let Some(index) = updater.next_required_index() {
+///     updater.supply_entry(index, &fetch_tail(index)?)?;
+/// }
+/// let witness = updater.finish()?;
+/// ```
+///
+/// The result is the same as of `Witness::new` or `Witness::update` with a tails accessor.
+#[derive(Debug)]
+pub struct WitnessUpdater {
+    omega: PointG2,
+    omega_num: PointG2,
+    omega_denom: PointG2,
+    // Tails that are not supplied yet, `true` for tails of issued credentials, `false` for revoked ones
+    required: BTreeMap<u32, bool>
+}
+
+impl WitnessUpdater {
+    /// Starts computation of a new witness, arguments are the same as of `Witness::new`.
+    pub fn new(rev_idx: u32,
+               max_cred_num: u32,
+               issuance_by_default: bool,
+               rev_reg_delta: &RevocationRegistryDelta) -> Result<WitnessUpdater, IndyCryptoError> {
+        trace!("WitnessUpdater::new: >>> rev_idx: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, rev_reg_delta: {:?}",
+               rev_idx, max_cred_num, issuance_by_default, rev_reg_delta);
+
+        let issued = if issuance_by_default {
+            (1..max_cred_num + 1).collect::<HashSet<u32>>()
+                .difference(&rev_reg_delta.revoked).cloned().collect::<HashSet<u32>>()
+        } else {
+            rev_reg_delta.issued.clone()
+        };
+
+        let witness_updater = WitnessUpdater::_new(PointG2::new_inf()?, rev_idx, max_cred_num, &issued, &HashSet::new())?;
+
+        trace!("WitnessUpdater::new: <<< witness_updater: {:?}", witness_updater);
+
+        Ok(witness_updater)
+    }
+
+    /// Starts update of `witness`, arguments are the same as of `Witness::update`.
+    pub fn new_for_update(witness: &Witness,
+                          rev_idx: u32,
+                          max_cred_num: u32,
+                          rev_reg_delta: &RevocationRegistryDelta) -> Result<WitnessUpdater, IndyCryptoError> {
+        trace!("WitnessUpdater::new_for_update: >>> witness: {:?}, rev_idx: {:?}, max_cred_num: {:?}, rev_reg_delta: {:?}",
+               witness, rev_idx, max_cred_num, rev_reg_delta);
+
+        let witness_updater = WitnessUpdater::_new(witness.omega, rev_idx, max_cred_num, &rev_reg_delta.issued, &rev_reg_delta.revoked)?;
+
+        trace!("WitnessUpdater::new_for_update: <<< witness_updater: {:?}", witness_updater);
+
+        Ok(witness_updater)
+    }
+
+    fn _new(omega: PointG2,
+            rev_idx: u32,
+            max_cred_num: u32,
+            issued: &HashSet<u32>,
+            revoked: &HashSet<u32>) -> Result<WitnessUpdater, IndyCryptoError> {
+        let mut required = BTreeMap::new();
+        for (cred_indices, is_issued) in [(issued, true), (revoked, false)] {
+            for &j in cred_indices.iter().filter(|&&j| j != rev_idx) {
+                if j == 0 || j > max_cred_num {
+                    return Err(IndyCryptoError::InvalidStructure(
+                        format!("Index of credential in revocation registry delta is out of range: {}", j)));
+                }
+
+                // Tails of credentials both issued and revoked cancel out
+                let index = max_cred_num + 1 - j + rev_idx;
+                if required.remove(&index).is_none() {
+                    required.insert(index, is_issued);
+                }
+            }
+        }
+
+        Ok(WitnessUpdater {
+            omega,
+            omega_num: PointG2::new_inf()?,
+            omega_denom: PointG2::new_inf()?,
+            required
+        })
+    }
+
+    /// Returns the smallest index of a tail that is required but not supplied yet, `None` when all are supplied.
+    pub fn next_required_index(&self) -> Option<u32> {
+        self.required.keys().next().cloned()
+    }
+
+    /// Returns indices of all tails that are required but not supplied yet in ascending order,
+    /// e.g. to fetch them in parallel.
+    pub fn required_indices(&self) -> Vec<u32> {
+        self.required.keys().cloned().collect()
+    }
+
+    /// Supplies tail `index` as bytes of `Tail::to_bytes`.
+    pub fn supply_entry(&mut self, index: u32, bytes: &[u8]) -> Result<(), IndyCryptoError> {
+        self.supply_tail(index, &Tail::from_bytes(bytes)?)
+    }
+
+    /// Supplies tail `index`, it must be required and not supplied yet.
+    pub fn supply_tail(&mut self, index: u32, tail: &Tail) -> Result<(), IndyCryptoError> {
+        let is_issued = self.required.remove(&index)
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Tail {} is not required or already supplied", index)))?;
+
+        if is_issued {
+            self.omega_num = self.omega_num.add(tail)?;
+        } else {
+            self.omega_denom = self.omega_denom.add(tail)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the witness when all required tails are supplied.
+    pub fn finish(self) -> Result<Witness, IndyCryptoError> {
+        trace!("WitnessUpdater::finish: >>> required: {:?}", self.required);
+
+        if let Some(index) = self.next_required_index() {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("{} required tails are not supplied, the first is {}", self.required.len(), index)));
+        }
+
+        let witness = Witness { omega: self.omega.add(&self.omega_num.sub(&self.omega_denom)?)? };
+
+        trace!("WitnessUpdater::finish: <<< witness: {:?}", witness);
+
+        Ok(witness)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WitnessSignature {
+    sigma_i: PointG2,
+    u_i: PointG2,
+    g_i: PointG1
+}
+
+/// Secret key encoded in a credential that is used to prove that prover owns the credential; can be used to
+/// prove linkage across credentials.
+/// Prover blinds master secret, generating `BlindedCredentialSecrets` and `CredentialSecretsBlindingFactors` (blinding factors)
+/// and sends the `BlindedCredentialSecrets` to Issuer who then encodes it credential creation.
+/// The blinding factors are used by Prover for post processing of issued credentials.
+///
+/// Master secret is wiped on drop and its value is never printed by `Debug`.
+#[derive(Deserialize, Serialize)]
+pub struct MasterSecret {
+    ms: BigNumber,
+}
+
+impl MasterSecret {
+    pub fn clone(&self) -> Result<MasterSecret, IndyCryptoError> {
+        Ok(MasterSecret { ms: self.ms.clone()? })
+    }
+
+    /// Returns copy of the value, that isn't wiped with the master secret.
+    pub fn value(&self) -> Result<BigNumber, IndyCryptoError> {
+        Ok(self.ms.clone()?)
+    }
+
+    /// Overwrites the master secret with zero. Master secret is wiped on drop as well.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::prover::Prover;
+    ///
+    /// let mut master_secret = Prover::new_master_secret().unwrap();
+    /// master_secret.wipe();
+    /// assert_eq!("0", master_secret.value().unwrap().to_dec().unwrap());
+    /// ```
+    pub fn wipe(&mut self) {
+        self.ms.zeroize();
+    }
+}
+
+impl Drop for MasterSecret {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl ::std::fmt::Debug for MasterSecret {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("MasterSecret")
+            .field("ms", &"_")
+            .finish()
+    }
+}
+
+macro_rules! impl_json {
+    ($($entity:ident),*) => {
+        $(
+            impl $entity {
+                /// Returns json representation of entity, the same as serde_json produces.
+                pub fn to_json(&self) -> Result<String, IndyCryptoError> {
+                    serde_json::to_string(self).map_err(IndyCryptoError::from)
+                }
+
+                /// Creates entity from json, malformed json is `JsonError`.
+                pub fn from_json(json: &str) -> Result<$entity, IndyCryptoError> {
+                    serde_json::from_str(json).map_err(IndyCryptoError::from)
+                }
+            }
+        )*
+    }
+}
+
+/// Blinded Master Secret uses by Issuer in credential creation.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BlindedCredentialSecrets {
+    u: BigNumber,
+    ur: Option<PointG1>,
+    hidden_attributes: BTreeSet<String>,
+    committed_attributes: BTreeMap<String, BigNumber>
+}
+
+/// `CredentialSecretsBlindingFactors` used by Prover for post processing of credentials received from Issuer.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialSecretsBlindingFactors {
+    v_prime: BigNumber,
     vr_prime: Option<GroupOrderElement>
 }
 
@@ -722,12 +1605,25 @@ pub struct BlindedCredentialSecretsCorrectnessProof {
     r_caps: BTreeMap<String, BigNumber>, // Blinding values for m_caps
 }
 
+// Prover keeps blinding state between `Prover::blind_credential_secrets` and `Prover::process_credential_signature`,
+// the state can be stored as json to complete issuance after restart
+impl_json!(CredentialValues, BlindedCredentialSecrets, CredentialSecretsBlindingFactors, BlindedCredentialSecretsCorrectnessProof);
+
 /// “Sub Proof Request” - input to create a Proof for a credential;
-/// Contains attributes to be revealed and predicates.
+/// Contains attributes to be revealed, predicates and equality predicates to attributes of earlier sub proofs.
 #[derive(Debug, Clone)]
 pub struct SubProofRequest {
     revealed_attrs: BTreeSet<String>,
     predicates: BTreeSet<Predicate>,
+    revealed_attr_groups: BTreeMap<String /* group name */, BTreeSet<String>>,
+    equality_predicates: BTreeSet<EqualityPredicate>,
+}
+
+impl SubProofRequest {
+    /// Named groups of revealed attributes added by `SchemaSubProofRequestBuilder`.
+    pub fn revealed_attr_groups(&self) -> &BTreeMap<String, BTreeSet<String>> {
+        &self.revealed_attr_groups
+    }
 }
 
 /// Builder of “Sub Proof Request”.
@@ -741,7 +1637,9 @@ impl SubProofRequestBuilder {
         Ok(SubProofRequestBuilder {
             value: SubProofRequest {
                 revealed_attrs: BTreeSet::new(),
-                predicates: BTreeSet::new()
+                predicates: BTreeSet::new(),
+                revealed_attr_groups: BTreeMap::new(),
+                equality_predicates: BTreeSet::new()
             }
         })
     }
@@ -751,6 +1649,10 @@ impl SubProofRequestBuilder {
         Ok(())
     }
 
+    /// Adds predicate `attr_value p_type value`, `p_type` is one of "GE", "LE", "GT" and "LT".
+    ///
+    /// Attribute values of predicates are integers within i32 range, as `encode_attribute` encodes them,
+    /// so "GT" of `i32::MAX` and "LT" of `i32::MIN` can't be satisfied and are rejected with `InvalidStructure`.
     pub fn add_predicate(&mut self, attr_name: &str, p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
         let p_type = match p_type {
             "GE" => PredicateType::GE,
@@ -760,6 +1662,13 @@ impl SubProofRequestBuilder {
             p_type => return Err(IndyCryptoError::InvalidStructure(format!("Invalid predicate type: {:?}", p_type)))
         };
 
+        match (&p_type, value) {
+            (PredicateType::GT, i32::MAX) | (PredicateType::LT, i32::MIN) =>
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Threshold of predicate {:?} {} is out of range of attribute values", p_type, value))),
+            _ => {}
+        }
+
         let predicate = Predicate {
             attr_name: attr_name.to_owned(),
             p_type,
@@ -770,12 +1679,167 @@ impl SubProofRequestBuilder {
         Ok(())
     }
 
+    /// Adds two sided range predicate min <= attr_value <= max as pair of `GE` and `LE` predicates.
+    pub fn add_range_predicate(&mut self, attr_name: &str, min: i32, max: i32) -> Result<(), IndyCryptoError> {
+        if min > max {
+            return Err(IndyCryptoError::InvalidStructure(format!("Invalid range of predicate: {} > {}", min, max)));
+        }
+
+        self.add_predicate(attr_name, "GE", min)?;
+        self.add_predicate(attr_name, "LE", max)
+    }
+
+    /// Adds predicate that hidden attribute `attr_name` has the same value as hidden attribute `other_attr_name`
+    /// of sub proof `other_sub_proof_idx` (in order of adding), e.g. the same passport number in two credentials.
+    ///
+    /// The other sub proof must be added to proof builder and proof verifier before this one.
+    /// The value is not revealed: the prover blinds both attributes with the same m_tilde,
+    /// so their responses are equal only for equal values.
+    pub fn add_equality_predicate(&mut self, attr_name: &str, other_sub_proof_idx: usize, other_attr_name: &str) -> Result<(), IndyCryptoError> {
+        let predicate = EqualityPredicate {
+            attr_name: attr_name.to_owned(),
+            other_sub_proof_idx,
+            other_attr_name: other_attr_name.to_owned()
+        };
+
+        self.value.equality_predicates.insert(predicate);
+        Ok(())
+    }
+
     pub fn finalize(self) -> Result<SubProofRequest, IndyCryptoError> {
         Ok(self.value)
     }
 }
 
+/// Builder of “Sub Proof Request” checked against a credential schema.
+///
+/// Unlike `SubProofRequestBuilder` every revealed and predicate attribute must be in the schema.
+/// Attribute names are matched case sensitively as everywhere in this module, a name differing from
+/// a schema attribute only in case is rejected with the schema spelling in the error message.
+/// An attribute can't be both revealed and a subject of predicates: a predicate over a revealed value
+/// proves nothing and the prover doesn't hide such attribute.
+///
+/// Revealed attributes can be organized in named groups. Groups don't change the proof,
+/// they are returned by `SubProofRequest::revealed_attr_groups` for presentation of revealed values.
+#[derive(Debug)]
+pub struct SchemaSubProofRequestBuilder {
+    schema_attrs: BTreeSet<String>,
+    builder: SubProofRequestBuilder,
+    revealed_attr_groups: BTreeMap<String, BTreeSet<String>>
+}
+
+impl SchemaSubProofRequestBuilder {
+    pub fn new(credential_schema: &CredentialSchema) -> Result<SchemaSubProofRequestBuilder, IndyCryptoError> {
+        Ok(SchemaSubProofRequestBuilder {
+            schema_attrs: credential_schema.attrs.clone(),
+            builder: SubProofRequestBuilder::new()?,
+            revealed_attr_groups: BTreeMap::new()
+        })
+    }
+
+    pub fn add_revealed_attr(&mut self, attr: &str) -> Result<(), IndyCryptoError> {
+        self._check_revealed_attr(attr)?;
+        self.builder.add_revealed_attr(attr)
+    }
+
+    /// Adds revealed attributes as a named group, group name must be unique and an attribute can be in one group only.
+    pub fn add_revealed_attr_group(&mut self, group_name: &str, attrs: &[&str]) -> Result<(), IndyCryptoError> {
+        if group_name.trim().is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Group name '{}' is empty", group_name)));
+        }
+
+        if self.revealed_attr_groups.contains_key(group_name) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Group '{}' is duplicated", group_name)));
+        }
+
+        if attrs.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure(format!("Group '{}' is empty", group_name)));
+        }
+
+        for attr in attrs {
+            self._check_revealed_attr(attr)?;
+
+            if let Some((other_group_name, _)) = self.revealed_attr_groups.iter().find(|(_, group)| group.contains(*attr)) {
+                return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' is already in group '{}'", attr, other_group_name)));
+            }
+        }
+
+        for attr in attrs {
+            self.builder.add_revealed_attr(attr)?;
+        }
+
+        self.revealed_attr_groups.insert(group_name.to_owned(), attrs.iter().map(|attr| attr.to_string()).collect());
+        Ok(())
+    }
+
+    pub fn add_predicate(&mut self, attr_name: &str, p_type: &str, value: i32) -> Result<(), IndyCryptoError> {
+        self._check_predicate_attr(attr_name)?;
+        self.builder.add_predicate(attr_name, p_type, value)
+    }
+
+    /// Adds two sided range predicate min <= attr_value <= max as pair of `GE` and `LE` predicates.
+    pub fn add_range_predicate(&mut self, attr_name: &str, min: i32, max: i32) -> Result<(), IndyCryptoError> {
+        self._check_predicate_attr(attr_name)?;
+        self.builder.add_range_predicate(attr_name, min, max)
+    }
+
+    /// Adds equality predicate as `SubProofRequestBuilder::add_equality_predicate`, `attr_name` must be in the schema.
+    pub fn add_equality_predicate(&mut self, attr_name: &str, other_sub_proof_idx: usize, other_attr_name: &str) -> Result<(), IndyCryptoError> {
+        self._check_predicate_attr(attr_name)?;
+        self.builder.add_equality_predicate(attr_name, other_sub_proof_idx, other_attr_name)
+    }
+
+    /// Returns `InvalidStructure` if neither revealed attributes nor predicates were added.
+    pub fn finalize(self) -> Result<SubProofRequest, IndyCryptoError> {
+        let mut sub_proof_request = self.builder.finalize()?;
+
+        if sub_proof_request.revealed_attrs.is_empty() && sub_proof_request.predicates.is_empty() &&
+            sub_proof_request.equality_predicates.is_empty() {
+            return Err(IndyCryptoError::InvalidStructure("Sub proof request is empty".to_string()));
+        }
+
+        sub_proof_request.revealed_attr_groups = self.revealed_attr_groups;
+        Ok(sub_proof_request)
+    }
+
+    fn _check_schema_attr(&self, attr: &str) -> Result<(), IndyCryptoError> {
+        if self.schema_attrs.contains(attr) {
+            return Ok(());
+        }
+
+        let err = match self.schema_attrs.iter().find(|schema_attr| schema_attr.to_lowercase() == attr.to_lowercase()) {
+            Some(schema_attr) => format!("Attribute '{}' is not in credential schema, schema contains '{}'", attr, schema_attr),
+            None => format!("Attribute '{}' is not in credential schema", attr)
+        };
+        Err(IndyCryptoError::InvalidStructure(err))
+    }
+
+    fn _check_revealed_attr(&self, attr: &str) -> Result<(), IndyCryptoError> {
+        self._check_schema_attr(attr)?;
+
+        if self.builder.value.predicates.iter().any(|predicate| predicate.attr_name == attr) ||
+            self.builder.value.equality_predicates.iter().any(|predicate| predicate.attr_name == attr) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' is a subject of predicate and can't be revealed", attr)));
+        }
+
+        Ok(())
+    }
+
+    fn _check_predicate_attr(&self, attr_name: &str) -> Result<(), IndyCryptoError> {
+        self._check_schema_attr(attr_name)?;
+
+        if self.builder.value.revealed_attrs.contains(attr_name) {
+            return Err(IndyCryptoError::InvalidStructure(format!("Attribute '{}' is revealed and can't be a subject of predicate", attr_name)));
+        }
+
+        Ok(())
+    }
+}
+
 /// Some condition that must be satisfied.
+///
+/// Attribute values and thresholds are integers within i32 range. Deltas and bounds are computed
+/// in i64, so they don't wrap at the ends of the range: delta is within `[-2^32, 2^32 - 1]`.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
 pub struct Predicate {
     attr_name: String,
@@ -784,21 +1848,25 @@ pub struct Predicate {
 }
 
 impl Predicate {
-    pub fn get_delta(&self, attr_value: i32) -> i32 {
+    /// Returns the difference between attribute value and the bound, that is non negative if the predicate is satisfied.
+    pub fn get_delta(&self, attr_value: i32) -> i64 {
+        let (attr_value, value) = (attr_value as i64, self.value as i64);
         match self.p_type {
-            PredicateType::GE => attr_value - self.value,
-            PredicateType::GT => attr_value - self.value - 1,
-            PredicateType::LE => self.value - attr_value,
-            PredicateType::LT => self.value - attr_value - 1
+            PredicateType::GE => attr_value - value,
+            PredicateType::GT => attr_value - value - 1,
+            PredicateType::LE => value - attr_value,
+            PredicateType::LT => value - attr_value - 1
         }
     }
 
+    /// Returns the inclusive bound of attribute value, it may be out of i32 range for "GT" of `i32::MAX` and "LT" of `i32::MIN`.
     pub fn get_delta_prime(&self) -> Result<BigNumber, IndyCryptoError> {
+        let value = self.value as i64;
         match self.p_type {
-            PredicateType::GE => BigNumber::from_dec(&self.value.to_string()),
-            PredicateType::GT => BigNumber::from_dec(&(self.value + 1).to_string()),
-            PredicateType::LE => BigNumber::from_dec(&self.value.to_string()),
-            PredicateType::LT => BigNumber::from_dec(&(self.value - 1).to_string())
+            PredicateType::GE => BigNumber::from_dec(&value.to_string()),
+            PredicateType::GT => BigNumber::from_dec(&(value + 1).to_string()),
+            PredicateType::LE => BigNumber::from_dec(&value.to_string()),
+            PredicateType::LT => BigNumber::from_dec(&(value - 1).to_string())
         }
     }
 
@@ -819,6 +1887,15 @@ pub enum PredicateType {
     LT
 }
 
+/// Condition that hidden attribute `attr_name` of a sub proof has the same value as hidden attribute
+/// `other_attr_name` of the earlier sub proof `other_sub_proof_idx`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct EqualityPredicate {
+    attr_name: String,
+    other_sub_proof_idx: usize,
+    other_attr_name: String,
+}
+
 /// Proof is complex crypto structure created by prover over multiple credentials that allows to prove that prover:
 /// 1) Knows signature over credentials issued with specific issuer keys (identified by key id)
 /// 2) Credential contains attributes with specific values that prover wants to disclose
@@ -829,10 +1906,36 @@ pub struct Proof {
     aggregated_proof: AggregatedProof,
 }
 
+// CBOR is an alternative to json for constrained channels (QR codes, NFC) of presentation
+#[cfg(feature = "cbor")]
+macro_rules! impl_cbor {
+    ($($entity:ident),*) => {
+        $(
+            impl $entity {
+                /// Returns compact binary (CBOR) representation, see `utils::cbor`.
+                pub fn to_cbor(&self) -> Result<Vec<u8>, IndyCryptoError> {
+                    cbor::to_vec(self)
+                }
+
+                /// Creates entity from CBOR representation produced by `to_cbor`.
+                pub fn from_cbor(bytes: &[u8]) -> Result<$entity, IndyCryptoError> {
+                    cbor::from_slice(bytes)
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl_cbor!(Proof, CredentialSignature, SignatureCorrectnessProof, RevocationKeyPublic, RevocationRegistry, RevocationRegistryDelta, Witness);
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SubProof {
     primary_proof: PrimaryProof,
-    non_revoc_proof: Option<NonRevocProof>
+    non_revoc_proof: Option<NonRevocProof>,
+    // Absent in proofs without equality predicates, so proofs of earlier versions are read as before
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    equality_predicates: BTreeSet<EqualityPredicate>
 }
 
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
@@ -1187,144 +2290,659 @@ fn clone_credential_value_map<K: Clone + Eq + Ord>(other: &BTreeMap<K, Credentia
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::errors::ErrorKind;
     use serde_json;
+    use std::cell::RefCell;
     use self::issuer::Issuer;
     use self::prover::Prover;
     use self::verifier::Verifier;
+    use self::hash::get_hash_as_int;
     
-    #[test]
-    fn multiple_predicates() {
+    fn _schema_sub_proof_request_builder() -> SchemaSubProofRequestBuilder {
         let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
-        credential_schema_builder.add_attr("funds_sold_and_securities_purchased").unwrap();
-        credential_schema_builder.add_attr("other_earning_assets").unwrap();
-        credential_schema_builder.add_attr("cash").unwrap();
-        credential_schema_builder.add_attr("allowance").unwrap();
-        credential_schema_builder.add_attr("total_assets").unwrap();
-        credential_schema_builder.add_attr("domestic_interest_bearing_deposits").unwrap();
-        credential_schema_builder.add_attr("funds_purchased").unwrap();
-        credential_schema_builder.add_attr("long_term_debt").unwrap();
-        credential_schema_builder.add_attr("non_interest_bearing_liabilities").unwrap();
-        credential_schema_builder.add_attr("shareholder_equity").unwrap();
-        credential_schema_builder.add_attr("total_liabilities").unwrap();
-
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
         let credential_schema = credential_schema_builder.finalize().unwrap();
 
-        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
-        non_credential_schema_builder.add_attr("master_secret").unwrap();
-        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+        Verifier::new_schema_sub_proof_request_builder(&credential_schema).unwrap()
+    }
 
-        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+    #[test]
+    fn schema_sub_proof_request_builder_works() {
+        let mut builder = _schema_sub_proof_request_builder();
+        builder.add_revealed_attr_group("identity", &["name", "sex"]).unwrap();
+        builder.add_range_predicate("age", 18, 65).unwrap();
+        let sub_proof_request = builder.finalize().unwrap();
 
-        let master_secret = Prover::new_master_secret().unwrap();
-        let credential_nonce = new_nonce().unwrap();
+        assert_eq!(btreeset!["name".to_string(), "sex".to_string()], sub_proof_request.revealed_attrs);
+        assert_eq!(2, sub_proof_request.predicates.len());
+        assert_eq!(&btreemap!["identity".to_string() => btreeset!["name".to_string(), "sex".to_string()]],
+                   sub_proof_request.revealed_attr_groups());
+    }
 
-        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
-        credential_values_builder.add_dec_known("funds_sold_and_securities_purchased", "50").unwrap();
-        credential_values_builder.add_dec_known("other_earning_assets", "60").unwrap();
-        credential_values_builder.add_dec_known("cash", "70").unwrap();
-        credential_values_builder.add_dec_known("allowance", "80").unwrap();
-        credential_values_builder.add_dec_known("total_assets", "260").unwrap();
-
-        credential_values_builder.add_dec_known("domestic_interest_bearing_deposits", "10").unwrap();
-        credential_values_builder.add_dec_known("funds_purchased", "20").unwrap();
-        credential_values_builder.add_dec_known("long_term_debt", "30").unwrap();
-        credential_values_builder.add_dec_known("non_interest_bearing_liabilities", "40").unwrap();
-        credential_values_builder.add_dec_known("shareholder_equity", "50").unwrap();
-        credential_values_builder.add_dec_known("total_liabilities", "150").unwrap();
-        let cred_values = credential_values_builder.finalize().unwrap();
+    #[test]
+    fn schema_sub_proof_request_builder_works_for_attr_not_in_schema() {
+        let mut builder = _schema_sub_proof_request_builder();
 
-        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
-            Prover::blind_credential_secrets(&cred_pub_key,
-                                        &cred_key_correctness_proof,
-                                        &cred_values,
-                                        &credential_nonce).unwrap();
+        let err = builder.add_revealed_attr("height").unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'height'"));
 
-        let cred_issuance_nonce = new_nonce().unwrap();
+        let err = builder.add_predicate("height", "GE", 150).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'height'"));
 
-        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("b977afe22b5b446109797ad925d9f133fc33c1914081071295d2ac1ddce3385d",
-                                                                                        &blinded_credential_secrets,
-                                                                                        &blinded_credential_secrets_correctness_proof,
-                                                                                        &credential_nonce,
-                                                                                        &cred_issuance_nonce,
-                                                                                        &cred_values,
-                                                                                        &cred_pub_key,
-                                                                                        &cred_priv_key).unwrap();
+        let err = builder.add_range_predicate("height", 150, 200).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
 
-        Prover::process_credential_signature(&mut cred_signature,
-                                             &cred_values,
-                                             &signature_correctness_proof,
-                                             &credential_secrets_blinding_factors,
-                                             &cred_pub_key,
-                                             &cred_issuance_nonce,
-                                             None,
-                                             None,
-                                             None).unwrap();
+        let err = builder.add_revealed_attr_group("body", &["sex", "height"]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'height'"));
+    }
 
-        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
-        sub_proof_request_builder.add_revealed_attr("total_liabilities").unwrap();
-
-        sub_proof_request_builder.add_predicate("funds_sold_and_securities_purchased", "LT", 100).unwrap();
-        sub_proof_request_builder.add_predicate("funds_sold_and_securities_purchased", "GT", 0).unwrap();
-        sub_proof_request_builder.add_predicate("other_earning_assets", "LT", 100).unwrap();
-        sub_proof_request_builder.add_predicate("cash", "LT", 100).unwrap();
-        sub_proof_request_builder.add_predicate("allowance", "LT", 100).unwrap();
-        sub_proof_request_builder.add_predicate("total_assets", "GT", 100).unwrap();
-
-        sub_proof_request_builder.add_predicate("domestic_interest_bearing_deposits", "LE", 100).unwrap();
-        sub_proof_request_builder.add_predicate("funds_purchased", "LE", 100).unwrap();
-        sub_proof_request_builder.add_predicate("long_term_debt", "LE", 100).unwrap();
-        sub_proof_request_builder.add_predicate("non_interest_bearing_liabilities", "LE", 100).unwrap();
-        sub_proof_request_builder.add_predicate("shareholder_equity", "LE", 100).unwrap();
-        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    #[test]
+    fn schema_sub_proof_request_builder_works_for_attr_in_other_case() {
+        let mut builder = _schema_sub_proof_request_builder();
 
-        let mut proof_builder = Prover::new_proof_builder().unwrap();
-        proof_builder.add_common_attribute("master_secret").unwrap();
-        proof_builder.add_sub_proof_request(&sub_proof_request,
-                                            &credential_schema,
-                                            &non_credential_schema,
-                                            &cred_signature,
-                                            &cred_values,
-                                            &cred_pub_key,
-                                            None,
-                                            None).unwrap();
+        let err = builder.add_revealed_attr("Name").unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'Name' is not in credential schema, schema contains 'name'"));
 
-        let proof_request_nonce = new_nonce().unwrap();
-        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+        let err = builder.add_predicate("AGE", "GE", 18).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("schema contains 'age'"));
+    }
 
-        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
-                                             &non_credential_schema,
-                                             &cred_pub_key,
-                                             None,
-                                             None).unwrap();
-        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    #[test]
+    fn schema_sub_proof_request_builder_works_for_revealed_attr_in_predicate() {
+        let mut builder = _schema_sub_proof_request_builder();
+        builder.add_revealed_attr("name").unwrap();
+        builder.add_predicate("age", "GE", 18).unwrap();
+
+        let err = builder.add_predicate("name", "GE", 18).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'name'"));
+
+        let err = builder.add_revealed_attr("age").unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'age'"));
+
+        let err = builder.add_revealed_attr_group("personal", &["sex", "age"]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'age'"));
     }
 
     #[test]
-    fn credential_primary_public_key_conversion_works() {
-        let string1 = r#"{
-                 "n":"94752773003676215520340390286428145970577435379747248974837494389412082076547661891067434652276048522392442077335235388384984508621151996372559370276527598415204914831299768834758349425880859567795461321350412568232531440683627330032285846734752711268206613305069973750567165548816744023441650243801226580089078611213688037852063937259593837571943085718154394160122127891902723469618952030300431400181642597638732611518885616750614674142486169255034160093153314427704384760404032620300207070597238445621198019686315730573836193179483581719638565112589368474184957790046080767607443902003396643479910885086397579016949",
-                 "s":"69412039600361800795429063472749802282903100455399422661844374992112119187258494682747330126416608111152308407310993289705267392969490079422545377823004584691698371089275086755756916575365439635768831063415050875440259347714303092581127338698890829662982679857654396534761554232914231213603075653629534596880597317047082696083166437821687405393805812336036647064899914817619861844092002636340952247588092904075021313598848481976631171767602864723880294787434756140969093416957086578979859382777377267118038126527549503876861370823520292585383483415337137062969402135540724590433024573312636828352734474276871187481042",
-                 "r":{
-                    "age":"90213462228557102785520674066817329607065098280886260103565465379328385444439123494955469500769864345819799623656302322427095342533906338563811194606234218499052997878891037890681314502037670093285650999142741875494918117023196753133733183769000368858655309319559871473827485381905587653145346258174022279515774231018893119774525087260785417971477049379955435611260162822960318458092151247522911151421981946748062572207451174079699745404644326303405628719711440096340436702151418321760375229323874027809433387030362543124015034968644213166988773750220839778654632868402703075643503247560457217265822566406481434257658",
-                    "height":"5391629214047043372090966654120333203094518833743674393685635640778311836867622750170495792524304436281896432811455146477306501487333852472234525296058562723428516533641819658096275918819548576029252844651857904411902677509566190811985500618327955392620642519618001469964706236997279744030829811760566269297728600224591162795849338756438466021999870256717098048301453122263380103723520670896747657149140787953289875480355961166269553534983692005983375091110745903845958291035125718192228291126861666488320123420563113398593180368102996188897121307947248313167444374640621348136184583596487812048321382789134349482978",
-                    "name":"77620276231641170120118188540269028385259155493880444038204934044861538875241492581309232702380290690573764595644801264135299029620031922004969464948925209245961139274806949465303313280327009910224580146266877846633558282936147503639084871235301887617650455108586169172459479774206351621894071684884758716731250212971549835402948093455393537573942251389197338609379019568250835525301455105289583537704528678164781839386485243301381405947043141406604458853106372019953011725448481499511842635580639867624862131749700424467221215201558826025502015289693451254344465767556321748122037274143231500322140291667454975911415",
-                    "sex":"9589127953934298285127566793382980040568251918610023890115614786922171891298122457059996745443282235104668609426602496632245081143706804923757991602521162900045665258654877250328921570207935035808607238170708932487500434929591458680514420504595293934408583558084774019418964434729989362874165849497341625769388145344718883550286508846516335790153998186614300493752317413537864956171451048868305380731285315760405126912629495204641829764230906698870575251861738847175174907714361155400020318026100833368698707674675548636610079631382774152211885405135045997623813094890524761824654025566099289284433567918244183562578"
-                 },
-                 "rms": "51663676247842478814965591806476166314018329779100758392678204435864101706276421100107118776199283981546682625125866769910726045178868995629346547166162207336629797340989495021248125384357605197654315399409367101440127312902706857104045262430326903112478154165057770802221835566137181123204394005042244715693211063132775814710986488082414421678086296488865286754803461178476006057306298883090062534704773627985221339716152111236985859907502262026150818487846053415153813804554830872575193396851274528558072704096323791923604931528594861707067370303707070124331485728734993074005001622035563911923643592706985074084035",
-                 "rctxt":"60293229766149238310917923493206871325969738638348535857162249827595080348039120693847207728852550647187915587987334466582959087190830489258423645708276339586344792464665557038628519694583193692804909304334143467285824750999826903922956158114736424517794036832742439893595716442609416914557200249087236453529632524328334442017327755310827841619727229956823928475210644630763245343116656886668444813463622336899670813312626960927341115875144198394937398391514458462051400588820774593570752884252721428948286332429715774158007033348855655388287735570407811513582431434394169600082273657382209764160600063473877124656503",
-                 "z":"70486542646006986754234343446999146345523665952265004264483059055307042644604796098478326629348068818272043688144751523020343994424262034067120716287162029288580118176972850899641747743901392814182335879624697285262287085187745166728443417803755667806532945136078671895589773743252882095592683767377435647759252676700424432160196120135306640079450582642553870190550840243254909737360996391470076977433525925799327058405911708739601511578904084479784054523375804238021939950198346585735956776232824298799161587408330541161160988641895300133750453032202142977745163418534140360029475702333980267724847703258887949227842"
-              }"#;
+    fn schema_sub_proof_request_builder_works_for_invalid_groups() {
+        let mut builder = _schema_sub_proof_request_builder();
+        builder.add_revealed_attr_group("identity", &["name"]).unwrap();
 
-        let string2 = r#"{
-                 "n":"94752773003676215520340390286428145970577435379747248974837494389412082076547661891067434652276048522392442077335235388384984508621151996372559370276527598415204914831299768834758349425880859567795461321350412568232531440683627330032285846734752711268206613305069973750567165548816744023441650243801226580089078611213688037852063937259593837571943085718154394160122127891902723469618952030300431400181642597638732611518885616750614674142486169255034160093153314427704384760404032620300207070597238445621198019686315730573836193179483581719638565112589368474184957790046080767607443902003396643479910885086397579016949",
-                 "s":"69412039600361800795429063472749802282903100455399422661844374992112119187258494682747330126416608111152308407310993289705267392969490079422545377823004584691698371089275086755756916575365439635768831063415050875440259347714303092581127338698890829662982679857654396534761554232914231213603075653629534596880597317047082696083166437821687405393805812336036647064899914817619861844092002636340952247588092904075021313598848481976631171767602864723880294787434756140969093416957086578979859382777377267118038126527549503876861370823520292585383483415337137062969402135540724590433024573312636828352734474276871187481042",
-                 "r":{
-                    "age":"90213462228557102785520674066817329607065098280886260103565465379328385444439123494955469500769864345819799623656302322427095342533906338563811194606234218499052997878891037890681314502037670093285650999142741875494918117023196753133733183769000368858655309319559871473827485381905587653145346258174022279515774231018893119774525087260785417971477049379955435611260162822960318458092151247522911151421981946748062572207451174079699745404644326303405628719711440096340436702151418321760375229323874027809433387030362543124015034968644213166988773750220839778654632868402703075643503247560457217265822566406481434257658",
-                    "height":"5391629214047043372090966654120333203094518833743674393685635640778311836867622750170495792524304436281896432811455146477306501487333852472234525296058562723428516533641819658096275918819548576029252844651857904411902677509566190811985500618327955392620642519618001469964706236997279744030829811760566269297728600224591162795849338756438466021999870256717098048301453122263380103723520670896747657149140787953289875480355961166269553534983692005983375091110745903845958291035125718192228291126861666488320123420563113398593180368102996188897121307947248313167444374640621348136184583596487812048321382789134349482978",
-                    "name":"77620276231641170120118188540269028385259155493880444038204934044861538875241492581309232702380290690573764595644801264135299029620031922004969464948925209245961139274806949465303313280327009910224580146266877846633558282936147503639084871235301887617650455108586169172459479774206351621894071684884758716731250212971549835402948093455393537573942251389197338609379019568250835525301455105289583537704528678164781839386485243301381405947043141406604458853106372019953011725448481499511842635580639867624862131749700424467221215201558826025502015289693451254344465767556321748122037274143231500322140291667454975911415",
+        let err = builder.add_revealed_attr_group("identity", &["sex"]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'identity'"));
+
+        let err = builder.add_revealed_attr_group("personal", &["sex", "name"]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("'name' is already in group 'identity'"));
+
+        assert_eq!(ErrorKind::InvalidStructure, builder.add_revealed_attr_group("personal", &[]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, builder.add_revealed_attr_group(" ", &["sex"]).unwrap_err().kind());
+
+        // failed groups don't reveal attributes
+        let sub_proof_request = builder.finalize().unwrap();
+        assert_eq!(btreeset!["name".to_string()], sub_proof_request.revealed_attrs);
+        assert_eq!(1, sub_proof_request.revealed_attr_groups().len());
+    }
+
+    #[test]
+    fn schema_sub_proof_request_builder_works_for_empty_request() {
+        let builder = _schema_sub_proof_request_builder();
+        assert_eq!(ErrorKind::InvalidStructure, builder.finalize().unwrap_err().kind());
+    }
+
+    #[test]
+    fn predicate_get_delta_works_for_range_bounds() {
+        let predicate = |p_type: PredicateType, value: i32| Predicate { attr_name: "age".to_string(), p_type, value };
+
+        assert_eq!(u32::MAX as i64, predicate(PredicateType::GE, i32::MIN).get_delta(i32::MAX));
+        assert_eq!(u32::MAX as i64, predicate(PredicateType::LE, i32::MAX).get_delta(i32::MIN));
+        assert_eq!(-(u32::MAX as i64) - 1, predicate(PredicateType::GT, i32::MAX).get_delta(i32::MIN));
+        assert_eq!(-(u32::MAX as i64) - 1, predicate(PredicateType::LT, i32::MIN).get_delta(i32::MAX));
+
+        // bounds past the ends of i32 range don't wrap to the other end
+        assert_eq!("2147483648", predicate(PredicateType::GT, i32::MAX).get_delta_prime().unwrap().to_dec().unwrap());
+        assert_eq!("-2147483649", predicate(PredicateType::LT, i32::MIN).get_delta_prime().unwrap().to_dec().unwrap());
+        assert_eq!("2147483647", predicate(PredicateType::LE, i32::MAX).get_delta_prime().unwrap().to_dec().unwrap());
+        assert_eq!("-2147483648", predicate(PredicateType::GE, i32::MIN).get_delta_prime().unwrap().to_dec().unwrap());
+    }
+
+    #[test]
+    fn sub_proof_request_builder_add_predicate_works_for_out_of_range_thresholds() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, sub_proof_request_builder.add_predicate("age", "GT", i32::MAX).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, sub_proof_request_builder.add_predicate("age", "LT", i32::MIN).unwrap_err().kind());
+
+        sub_proof_request_builder.add_predicate("age", "GE", i32::MAX).unwrap();
+        sub_proof_request_builder.add_predicate("age", "LE", i32::MIN).unwrap();
+        sub_proof_request_builder.add_predicate("age", "GT", i32::MAX - 1).unwrap();
+        sub_proof_request_builder.add_predicate("age", "LT", i32::MIN + 1).unwrap();
+        assert_eq!(4, sub_proof_request_builder.finalize().unwrap().predicates.len());
+
+        for value in &[(i32::MAX as i64 + 1).to_string(), (i32::MIN as i64 - 1).to_string(), i64::MAX.to_string(), i64::MIN.to_string()] {
+            let json = format!(r#"{{"attr_name":"age","p_type":"GE","value":{}}}"#, value);
+            assert!(serde_json::from_str::<Predicate>(&json).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn sub_proof_request_builder_add_range_predicate_works() {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_range_predicate("age", 18, 65).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        assert_eq!(btreeset![Predicate { attr_name: "age".to_string(), p_type: PredicateType::GE, value: 18 },
+                             Predicate { attr_name: "age".to_string(), p_type: PredicateType::LE, value: 65 }],
+                   sub_proof_request.predicates);
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        let err = sub_proof_request_builder.add_range_predicate("age", 65, 18).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+    }
+
+    #[test]
+    fn predicate_deserialization_works_for_ge_only_format() {
+        let predicate: Predicate = serde_json::from_str(r#"{"attr_name":"age","p_type":"GE","value":18}"#).unwrap();
+        assert_eq!(Predicate { attr_name: "age".to_string(), p_type: PredicateType::GE, value: 18 }, predicate);
+
+        let predicate: Predicate = serde_json::from_str(r#"{"attr_name":"age","p_type":"LT","value":65}"#).unwrap();
+        assert!(predicate.is_less());
+        assert_eq!(predicate, serde_json::from_str(&serde_json::to_string(&predicate).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn new_credential_def_with_progress_works_for_parallel_pool() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        // Explicit pool, so safe primes are searched by several workers on a single core machine too
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let mut primes_found = 0;
+        let (_, cred_priv_key, _) = pool.install(|| {
+            Issuer::new_credential_def_with_progress(&credential_schema, &non_credential_schema, false, &mut |event| {
+                if event == issuer::KeyGenProgress::PrimeFound {
+                    primes_found += 1;
+                }
+                true
+            })
+        }).unwrap();
+        assert_eq!(2, primes_found);
+        assert!(cred_priv_key.p_key.p.lshift1().unwrap().increment().unwrap().is_safe_prime(None).unwrap());
+        assert!(cred_priv_key.p_key.q.lshift1().unwrap().increment().unwrap().is_safe_prime(None).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "seeded_keys")]
+    fn seeded_credential_def_works() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let seed = b"seed of integration test issuer!";
+        let (cred_pub_key, cred_priv_key, _) =
+            Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, true, seed).unwrap();
+        let (same_cred_pub_key, same_cred_priv_key, _) =
+            Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, true, seed).unwrap();
+
+        assert_eq!(cred_priv_key.p_key.p, same_cred_priv_key.p_key.p);
+        assert_eq!(cred_priv_key.p_key.q, same_cred_priv_key.p_key.q);
+        assert_eq!(serde_json::to_string(&cred_pub_key.r_key).unwrap(), serde_json::to_string(&same_cred_pub_key.r_key).unwrap());
+        assert!(cred_priv_key.p_key.p.lshift1().unwrap().increment().unwrap().is_safe_prime(None).unwrap());
+        assert!(cred_priv_key.p_key.q.lshift1().unwrap().increment().unwrap().is_safe_prime(None).unwrap());
+
+        // the same on all platforms and in all versions
+        assert_eq!("BB8A311D81EA802878E490FBAB7D3992", &cred_pub_key.p_key.n.to_hex().unwrap()[..32]);
+
+        let (other_cred_pub_key, _, _) =
+            Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, false, b"other seed of integration tests!").unwrap();
+        assert_ne!(cred_pub_key.p_key.n, other_cred_pub_key.p_key.n);
+
+        let short_seed_err = Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, false, b"short seed").unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, short_seed_err.kind());
+    }
+
+    // Seeded keys don't depend on the parallel search of safe primes
+    #[test]
+    #[cfg(all(feature = "seeded_keys", feature = "parallel"))]
+    fn seeded_credential_def_works_for_parallel_pool() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let (cred_pub_key, _, _) = pool.install(|| {
+            Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, true, b"seed of integration test issuer!")
+        }).unwrap();
+
+        // the same as of seeded_credential_def_works
+        assert_eq!("BB8A311D81EA802878E490FBAB7D3992", &cred_pub_key.p_key.n.to_hex().unwrap()[..32]);
+    }
+
+    fn _issue_credential(cred_pub_key: &CredentialPublicKey,
+                         cred_priv_key: &CredentialPrivateKey,
+                         cred_key_correctness_proof: &CredentialKeyCorrectnessProof,
+                         cred_values: &CredentialValues) -> CredentialSignature {
+        let credential_nonce = new_nonce().unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(cred_pub_key, cred_key_correctness_proof, cred_values, &credential_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                    &blinded_credential_secrets,
+                                    &blinded_credential_secrets_correctness_proof,
+                                    &credential_nonce,
+                                    &cred_issuance_nonce,
+                                    cred_values,
+                                    cred_pub_key,
+                                    cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+        cred_signature
+    }
+
+    #[test]
+    fn verify_constant_outcome_works() {
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let mut name_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        name_schema_builder.add_attr("name").unwrap();
+        let name_schema = name_schema_builder.finalize().unwrap();
+        let (name_pub_key, name_priv_key, name_key_correctness_proof) =
+            Issuer::new_credential_def(&name_schema, &non_credential_schema, false).unwrap();
+
+        let mut age_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        age_schema_builder.add_attr("age").unwrap();
+        let age_schema = age_schema_builder.finalize().unwrap();
+        let (age_pub_key, age_priv_key, age_key_correctness_proof) =
+            Issuer::new_credential_def(&age_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        let name_values = credential_values_builder.finalize().unwrap();
+        let name_signature = _issue_credential(&name_pub_key, &name_priv_key, &name_key_correctness_proof, &name_values);
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let age_values = credential_values_builder.finalize().unwrap();
+        let age_signature = _issue_credential(&age_pub_key, &age_priv_key, &age_key_correctness_proof, &age_values);
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let name_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let age_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&name_sub_proof_request, &name_schema, &non_credential_schema,
+                                            &name_signature, &name_values, &name_pub_key, None, None).unwrap();
+        proof_builder.add_sub_proof_request(&age_sub_proof_request, &age_schema, &non_credential_schema,
+                                            &age_signature, &age_values, &age_pub_key, None, None).unwrap();
+        proof_builder.add_linked_sub_proofs("master_secret", &[0, 1]).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof_json = serde_json::to_string(&proof_builder.finalize(&nonce).unwrap()).unwrap();
+        let proof = || serde_json::from_str::<Proof>(&proof_json).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&name_sub_proof_request, &name_schema, &non_credential_schema,
+                                             &name_pub_key, None, None).unwrap();
+        proof_verifier.add_sub_proof_request(&age_sub_proof_request, &age_schema, &non_credential_schema,
+                                             &age_pub_key, None, None).unwrap();
+        proof_verifier.add_linked_sub_proofs("master_secret", &[0, 1]).unwrap();
+
+        // outcomes: sub proofs count, consistency and primary proof of both sub proofs, link, challenge
+        let failed_checks = |proof: &Proof| {
+            let outcomes = proof_verifier._evaluate_all_checks(proof, &nonce, None).unwrap();
+            assert_eq!(7, outcomes.len());
+            assert_eq!(outcomes.iter().all(|&passed| passed), proof_verifier.verify_constant_outcome(proof, &nonce, None).unwrap());
+            outcomes.iter().enumerate().filter(|&(_, &passed)| !passed).map(|(idx, _)| idx).collect::<Vec<usize>>()
+        };
+
+        let valid_proof = proof();
+        assert!(failed_checks(&valid_proof).is_empty());
+        assert!(proof_verifier.verify_constant_outcome(&valid_proof, &nonce, Some(&verifier::NonRevocVerificationCache::new().unwrap())).unwrap());
+        assert!(proof_verifier.verify(&valid_proof, &nonce).unwrap());
+
+        // corruption of the first sub proof, that plain verify rejects at the first check
+        let mut early_corrupted = proof();
+        early_corrupted.proofs[0].primary_proof.eq_proof.m.remove("master_secret");
+        assert_eq!(vec![2, 5, 6], failed_checks(&early_corrupted));
+        assert!(proof_verifier.verify(&early_corrupted, &nonce).is_err());
+
+        // corruption of the last sub proof, all checks before it are still evaluated
+        let mut late_corrupted = proof();
+        late_corrupted.proofs[1].primary_proof.ne_proofs[0].t.remove("DELTA");
+        assert_eq!(vec![4, 6], failed_checks(&late_corrupted));
+        assert!(proof_verifier.verify(&late_corrupted, &nonce).is_err());
+
+        // well formed but wrong response is found only by the challenge
+        let mut value_corrupted = proof();
+        let alpha = value_corrupted.proofs[1].primary_proof.ne_proofs[0].alpha.increment().unwrap();
+        value_corrupted.proofs[1].primary_proof.ne_proofs[0].alpha = alpha;
+        assert_eq!(vec![6], failed_checks(&value_corrupted));
+        assert!(!proof_verifier.verify(&value_corrupted, &nonce).unwrap());
+
+        // numbers that differ only in sign aren't equal
+        let mut negated_challenge = proof();
+        negated_challenge.aggregated_proof.c_hash = negated_challenge.aggregated_proof.c_hash.set_negative(true).unwrap();
+        assert!(failed_checks(&negated_challenge).contains(&6));
+        assert!(!proof_verifier.verify(&negated_challenge, &nonce).unwrap_or(false));
+
+        let mut negated_link = proof();
+        let response = negated_link.proofs[1].primary_proof.eq_proof.m["master_secret"].set_negative(true).unwrap();
+        negated_link.proofs[1].primary_proof.eq_proof.m.insert("master_secret".to_string(), response);
+        assert!(failed_checks(&negated_link).contains(&5));
+        assert!(!proof_verifier.verify(&negated_link, &nonce).unwrap_or(false));
+
+        // missing sub proof is rejected without error
+        let mut truncated = proof();
+        truncated.proofs.pop();
+        let outcomes = proof_verifier._evaluate_all_checks(&truncated, &nonce, None).unwrap();
+        assert_eq!(vec![false, true, true, false, false], outcomes);
+        assert!(!proof_verifier.verify_constant_outcome(&truncated, &nonce, None).unwrap());
+
+        // failed checks are outcomes, not errors returned to a caller
+        thread_local! {
+            static HOOK_CALLS: RefCell<usize> = const { RefCell::new(0) };
+        }
+        fn counting_hook(_err: &IndyCryptoError) {
+            HOOK_CALLS.with(|calls| *calls.borrow_mut() += 1);
+        }
+
+        let _lock = crate::errors::ERROR_HOOK_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        crate::errors::set_error_hook(counting_hook);
+        for proof in [&early_corrupted, &late_corrupted, &value_corrupted, &truncated] {
+            assert!(!proof_verifier.verify_constant_outcome(proof, &nonce, None).unwrap());
+        }
+        crate::errors::clear_error_hook();
+        assert_eq!(0, HOOK_CALLS.with(|calls| *calls.borrow()));
+    }
+
+    // Checks proofs of this crate against the layout documented in docs/anoncreds-design.md only,
+    // compatibility with Ursa isn't covered until Ursa-generated fixtures are checked in
+    #[test]
+    fn proof_encoding_follows_canonical_layout() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        for attr in &["name", "age", "height"] {
+            credential_schema_builder.add_attr(attr).unwrap();
+        }
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        credential_values_builder.add_dec_known("height", "175").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+        let cred_signature = _issue_credential(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &cred_values);
+
+        // predicates are added out of their order
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("height", "LE", 200).unwrap();
+        sub_proof_request_builder.add_predicate("age", "LE", 65).unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request, &credential_schema, &non_credential_schema,
+                                            &cred_signature, &cred_values, &cred_pub_key, None, None).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &non_credential_schema,
+                                             &cred_pub_key, None, None).unwrap();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // predicates are ordered by attribute name, type and value
+        let primary_proof = &proof.proofs[0].primary_proof;
+        let predicates = primary_proof.ne_proofs.iter()
+            .map(|ne_proof| (ne_proof.predicate.attr_name.as_str(), ne_proof.predicate.p_type.clone(), ne_proof.predicate.value))
+            .collect::<Vec<(&str, PredicateType, i32)>>();
+        assert_eq!(vec![("age", PredicateType::GE, 18), ("age", PredicateType::LE, 65), ("height", PredicateType::LE, 200)], predicates);
+
+        // c list: a_prime, then t of every predicate
+        let mut c_list = vec![primary_proof.eq_proof.a_prime.to_bytes().unwrap()];
+        for ne_proof in &primary_proof.ne_proofs {
+            for key in &["0", "1", "2", "3", "DELTA"] {
+                c_list.push(ne_proof.t[*key].to_bytes().unwrap());
+            }
+        }
+        assert_eq!(c_list, proof.aggregated_proof.c_list);
+
+        // numbers are unsigned big-endian of minimal length in hashes
+        assert_eq!(vec![1, 0], BigNumber::from_hex("0100").unwrap().to_bytes().unwrap());
+        assert_eq!(vec![1, 0], BigNumber::from_dec("-256").unwrap().to_bytes().unwrap());
+        assert!(BigNumber::new().unwrap().to_bytes().unwrap().is_empty());
+
+        // challenge hashes the concatenation of values without length prefixes
+        let values = vec![vec![1, 2], vec![3], nonce.to_bytes().unwrap()];
+        let concatenation = values.concat();
+        assert_eq!(BigNumber::from_bytes(&BigNumber::hash(&concatenation).unwrap()).unwrap(), get_hash_as_int(&values).unwrap());
+        assert_eq!(32, BigNumber::hash(&concatenation).unwrap().len());
+
+        // numbers are decimal strings in JSON
+        let proof_json: serde_json::Value = serde_json::to_value(&proof).unwrap();
+        assert_eq!(serde_json::Value::String(primary_proof.eq_proof.a_prime.to_dec().unwrap()),
+                   proof_json["proofs"][0]["primary_proof"]["eq_proof"]["a_prime"]);
+        assert_eq!(serde_json::Value::String(proof.aggregated_proof.c_hash.to_dec().unwrap()),
+                   proof_json["aggregated_proof"]["c_hash"]);
+        assert_eq!(serde_json::Value::String(nonce.to_dec().unwrap()), serde_json::to_value(&nonce).unwrap());
+    }
+
+    #[test]
+    fn add_linked_sub_proofs_works_for_invalid_links() {
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+        let credential_schema = issuer::mocks::credential_schema();
+        let cred_pub_key = issuer::mocks::credential_public_key();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        for _ in 0..2 {
+            proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &non_credential_schema,
+                                                 &cred_pub_key, None, None).unwrap();
+        }
+
+        for &(attr_name, indices) in &[("master_secret", &[0usize][..]), ("master_secret", &[0, 2]), ("master_secret", &[1, 1]),
+                                       ("name", &[0, 1]), ("unknown", &[0, 1])] {
+            assert_eq!(ErrorKind::InvalidStructure, proof_verifier.add_linked_sub_proofs(attr_name, indices).unwrap_err().kind());
+        }
+
+        proof_verifier.add_linked_sub_proofs("master_secret", &[1, 0]).unwrap();
+        proof_verifier.add_linked_sub_proofs("age", &[0, 1]).unwrap();
+    }
+
+    // Fixed vectors of the encoding, language wrappers must produce the same values
+    const ATTRIBUTE_ENCODING_VECTORS: &[(&str, &str)] = &[
+        ("0", "0"),
+        ("28", "28"),
+        ("-28", "-28"),
+        ("+28", "28"),
+        ("00028", "28"),
+        ("2147483647", "2147483647"),
+        ("-2147483648", "-2147483648"),
+        ("2147483648", "26221484005389514539852548961319751347124425277437769688639924217837557266135"),
+        ("-2147483649", "68956915425095939579909400566452872085353864667122112803508671228696852865689"),
+        ("1.5", "71991296136747855077697001202532249706619088658469249105695717234028982732581"),
+        ("", "102987336249554097029535212322581322789799900648198034993379397001115665086549"),
+        ("Alex", "99262857098057710338306967609588410025648622308394250666849665532448612202874"),
+        ("101 Wilson Lane", "68086943237164982734333428280784300550565381723532936263016368251445461241953"),
+        ("SLC", "101327353979588246869873249766058188995681113722618593621043638294296500696424"),
+        ("True", "27471875274925838976481193902417661171675582237244292940724984695988062543640"),
+        ("None", "99769404535520360775991420569103450442789945655240760487761322098828903685777"),
+        ("Алиса", "32369382686578246424455347160042326974550842313041529637127853019014268874007")
+    ];
+
+    #[test]
+    fn encode_attribute_works() {
+        for &(raw, encoded) in ATTRIBUTE_ENCODING_VECTORS {
+            assert_eq!(encoded, encode_attribute(raw, AttributeKind::Auto).unwrap(), "{:?}", raw);
+            assert!(decode_check(encoded).is_ok(), "{:?}", encoded);
+        }
+    }
+
+    #[test]
+    fn encode_attribute_works_for_integer_kind() {
+        assert_eq!("-2147483648", encode_attribute("-2147483648", AttributeKind::Integer).unwrap());
+
+        for raw in &["2147483648", "1.5", "Alex", "", " 28"] {
+            let err = encode_attribute(raw, AttributeKind::Integer).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, err.kind(), "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn encode_attribute_works_for_text_kind() {
+        assert_eq!("89285835870235024249843837431788976881639883045332783616982197198728217345810",
+                   encode_attribute("01234", AttributeKind::Text).unwrap());
+        assert_eq!("28868888936002759964297273519092175783217608547598560872893231678200101080007",
+                   encode_attribute("87121", AttributeKind::Text).unwrap());
+    }
+
+    #[test]
+    fn decode_check_works() {
+        assert_eq!(Some(28), decode_check("28").unwrap());
+        assert_eq!(Some(0), decode_check("0").unwrap());
+        assert_eq!(Some(-2147483648), decode_check("-2147483648").unwrap());
+        assert_eq!(None, decode_check("99262857098057710338306967609588410025648622308394250666849665532448612202874").unwrap());
+
+        // 2^256 - 1 and 2^256
+        assert_eq!(None, decode_check("115792089237316195423570985008687907853269984665640564039457584007913129639935").unwrap());
+
+        for encoded in &["", "-", "-0", "+28", "028", "1.5", " 28", "Alex", "-2147483649",
+                         "115792089237316195423570985008687907853269984665640564039457584007913129639936"] {
+            let err = decode_check(encoded).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, err.kind(), "{:?}", encoded);
+        }
+    }
+
+    #[test]
+    fn new_nonce_from_seed_works() {
+        let nonce = new_nonce_from_seed(b"indy").unwrap();
+        assert_eq!("400727458142521792328963", nonce.to_dec().unwrap());
+        assert_eq!(nonce, new_nonce_from_seed(b"indy").unwrap());
+        assert_eq!("520420380632270975311013", new_nonce_from_seed(&[0]).unwrap().to_dec().unwrap());
+
+        assert_ne!(nonce, new_nonce_from_seed(b"indz").unwrap());
+        assert_ne!(new_nonce_from_seed(&[0]).unwrap(), new_nonce_from_seed(&[0, 0]).unwrap());
+
+        for seed in 0u32..32 {
+            check_nonce(&new_nonce_from_seed(&seed.to_be_bytes()).unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn new_nonce_with_rng_works() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let nonce = new_nonce_with_rng(&mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(nonce, new_nonce_with_rng(&mut StdRng::seed_from_u64(1)).unwrap());
+        assert_ne!(nonce, new_nonce_with_rng(&mut StdRng::seed_from_u64(2)).unwrap());
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let nonces = (0..32).map(|_| new_nonce_with_rng(&mut rng).unwrap()).collect::<Vec<Nonce>>();
+        for nonce in &nonces {
+            check_nonce(nonce).unwrap();
+        }
+        assert_ne!(nonces[0], nonces[1]);
+    }
+
+    #[test]
+    fn check_nonce_works() {
+        check_nonce(&new_nonce().unwrap()).unwrap();
+        check_nonce(&BigNumber::from_u32(0).unwrap()).unwrap();
+        // 2^80 - 1
+        check_nonce(&BigNumber::from_dec("1208925819614629174706175").unwrap()).unwrap();
+
+        for nonce in &["1208925819614629174706176", "-1"] {
+            let err = check_nonce(&BigNumber::from_dec(nonce).unwrap()).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, err.kind(), "{:?}", nonce);
+        }
+    }
+
+    #[test]
+    fn credential_primary_public_key_conversion_works() {
+        let string1 = r#"{
+                 "n":"94752773003676215520340390286428145970577435379747248974837494389412082076547661891067434652276048522392442077335235388384984508621151996372559370276527598415204914831299768834758349425880859567795461321350412568232531440683627330032285846734752711268206613305069973750567165548816744023441650243801226580089078611213688037852063937259593837571943085718154394160122127891902723469618952030300431400181642597638732611518885616750614674142486169255034160093153314427704384760404032620300207070597238445621198019686315730573836193179483581719638565112589368474184957790046080767607443902003396643479910885086397579016949",
+                 "s":"69412039600361800795429063472749802282903100455399422661844374992112119187258494682747330126416608111152308407310993289705267392969490079422545377823004584691698371089275086755756916575365439635768831063415050875440259347714303092581127338698890829662982679857654396534761554232914231213603075653629534596880597317047082696083166437821687405393805812336036647064899914817619861844092002636340952247588092904075021313598848481976631171767602864723880294787434756140969093416957086578979859382777377267118038126527549503876861370823520292585383483415337137062969402135540724590433024573312636828352734474276871187481042",
+                 "r":{
+                    "age":"90213462228557102785520674066817329607065098280886260103565465379328385444439123494955469500769864345819799623656302322427095342533906338563811194606234218499052997878891037890681314502037670093285650999142741875494918117023196753133733183769000368858655309319559871473827485381905587653145346258174022279515774231018893119774525087260785417971477049379955435611260162822960318458092151247522911151421981946748062572207451174079699745404644326303405628719711440096340436702151418321760375229323874027809433387030362543124015034968644213166988773750220839778654632868402703075643503247560457217265822566406481434257658",
+                    "height":"5391629214047043372090966654120333203094518833743674393685635640778311836867622750170495792524304436281896432811455146477306501487333852472234525296058562723428516533641819658096275918819548576029252844651857904411902677509566190811985500618327955392620642519618001469964706236997279744030829811760566269297728600224591162795849338756438466021999870256717098048301453122263380103723520670896747657149140787953289875480355961166269553534983692005983375091110745903845958291035125718192228291126861666488320123420563113398593180368102996188897121307947248313167444374640621348136184583596487812048321382789134349482978",
+                    "name":"77620276231641170120118188540269028385259155493880444038204934044861538875241492581309232702380290690573764595644801264135299029620031922004969464948925209245961139274806949465303313280327009910224580146266877846633558282936147503639084871235301887617650455108586169172459479774206351621894071684884758716731250212971549835402948093455393537573942251389197338609379019568250835525301455105289583537704528678164781839386485243301381405947043141406604458853106372019953011725448481499511842635580639867624862131749700424467221215201558826025502015289693451254344465767556321748122037274143231500322140291667454975911415",
+                    "sex":"9589127953934298285127566793382980040568251918610023890115614786922171891298122457059996745443282235104668609426602496632245081143706804923757991602521162900045665258654877250328921570207935035808607238170708932487500434929591458680514420504595293934408583558084774019418964434729989362874165849497341625769388145344718883550286508846516335790153998186614300493752317413537864956171451048868305380731285315760405126912629495204641829764230906698870575251861738847175174907714361155400020318026100833368698707674675548636610079631382774152211885405135045997623813094890524761824654025566099289284433567918244183562578"
+                 },
+                 "rms": "51663676247842478814965591806476166314018329779100758392678204435864101706276421100107118776199283981546682625125866769910726045178868995629346547166162207336629797340989495021248125384357605197654315399409367101440127312902706857104045262430326903112478154165057770802221835566137181123204394005042244715693211063132775814710986488082414421678086296488865286754803461178476006057306298883090062534704773627985221339716152111236985859907502262026150818487846053415153813804554830872575193396851274528558072704096323791923604931528594861707067370303707070124331485728734993074005001622035563911923643592706985074084035",
+                 "rctxt":"60293229766149238310917923493206871325969738638348535857162249827595080348039120693847207728852550647187915587987334466582959087190830489258423645708276339586344792464665557038628519694583193692804909304334143467285824750999826903922956158114736424517794036832742439893595716442609416914557200249087236453529632524328334442017327755310827841619727229956823928475210644630763245343116656886668444813463622336899670813312626960927341115875144198394937398391514458462051400588820774593570752884252721428948286332429715774158007033348855655388287735570407811513582431434394169600082273657382209764160600063473877124656503",
+                 "z":"70486542646006986754234343446999146345523665952265004264483059055307042644604796098478326629348068818272043688144751523020343994424262034067120716287162029288580118176972850899641747743901392814182335879624697285262287085187745166728443417803755667806532945136078671895589773743252882095592683767377435647759252676700424432160196120135306640079450582642553870190550840243254909737360996391470076977433525925799327058405911708739601511578904084479784054523375804238021939950198346585735956776232824298799161587408330541161160988641895300133750453032202142977745163418534140360029475702333980267724847703258887949227842"
+              }"#;
+
+        let string2 = r#"{
+                 "n":"94752773003676215520340390286428145970577435379747248974837494389412082076547661891067434652276048522392442077335235388384984508621151996372559370276527598415204914831299768834758349425880859567795461321350412568232531440683627330032285846734752711268206613305069973750567165548816744023441650243801226580089078611213688037852063937259593837571943085718154394160122127891902723469618952030300431400181642597638732611518885616750614674142486169255034160093153314427704384760404032620300207070597238445621198019686315730573836193179483581719638565112589368474184957790046080767607443902003396643479910885086397579016949",
+                 "s":"69412039600361800795429063472749802282903100455399422661844374992112119187258494682747330126416608111152308407310993289705267392969490079422545377823004584691698371089275086755756916575365439635768831063415050875440259347714303092581127338698890829662982679857654396534761554232914231213603075653629534596880597317047082696083166437821687405393805812336036647064899914817619861844092002636340952247588092904075021313598848481976631171767602864723880294787434756140969093416957086578979859382777377267118038126527549503876861370823520292585383483415337137062969402135540724590433024573312636828352734474276871187481042",
+                 "r":{
+                    "age":"90213462228557102785520674066817329607065098280886260103565465379328385444439123494955469500769864345819799623656302322427095342533906338563811194606234218499052997878891037890681314502037670093285650999142741875494918117023196753133733183769000368858655309319559871473827485381905587653145346258174022279515774231018893119774525087260785417971477049379955435611260162822960318458092151247522911151421981946748062572207451174079699745404644326303405628719711440096340436702151418321760375229323874027809433387030362543124015034968644213166988773750220839778654632868402703075643503247560457217265822566406481434257658",
+                    "height":"5391629214047043372090966654120333203094518833743674393685635640778311836867622750170495792524304436281896432811455146477306501487333852472234525296058562723428516533641819658096275918819548576029252844651857904411902677509566190811985500618327955392620642519618001469964706236997279744030829811760566269297728600224591162795849338756438466021999870256717098048301453122263380103723520670896747657149140787953289875480355961166269553534983692005983375091110745903845958291035125718192228291126861666488320123420563113398593180368102996188897121307947248313167444374640621348136184583596487812048321382789134349482978",
+                    "name":"77620276231641170120118188540269028385259155493880444038204934044861538875241492581309232702380290690573764595644801264135299029620031922004969464948925209245961139274806949465303313280327009910224580146266877846633558282936147503639084871235301887617650455108586169172459479774206351621894071684884758716731250212971549835402948093455393537573942251389197338609379019568250835525301455105289583537704528678164781839386485243301381405947043141406604458853106372019953011725448481499511842635580639867624862131749700424467221215201558826025502015289693451254344465767556321748122037274143231500322140291667454975911415",
                     "sex":"9589127953934298285127566793382980040568251918610023890115614786922171891298122457059996745443282235104668609426602496632245081143706804923757991602521162900045665258654877250328921570207935035808607238170708932487500434929591458680514420504595293934408583558084774019418964434729989362874165849497341625769388145344718883550286508846516335790153998186614300493752317413537864956171451048868305380731285315760405126912629495204641829764230906698870575251861738847175174907714361155400020318026100833368698707674675548636610079631382774152211885405135045997623813094890524761824654025566099289284433567918244183562578",
                     "master_secret": "51663676247842478814965591806476166314018329779100758392678204435864101706276421100107118776199283981546682625125866769910726045178868995629346547166162207336629797340989495021248125384357605197654315399409367101440127312902706857104045262430326903112478154165057770802221835566137181123204394005042244715693211063132775814710986488082414421678086296488865286754803461178476006057306298883090062534704773627985221339716152111236985859907502262026150818487846053415153813804554830872575193396851274528558072704096323791923604931528594861707067370303707070124331485728734993074005001622035563911923643592706985074084035"
                  },
@@ -1374,96 +2992,101 @@ mod test {
     }
 
 
-    #[test]
-    fn demo() {
-        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
-        credential_schema_builder.add_attr("name").unwrap();
-        credential_schema_builder.add_attr("sex").unwrap();
-        credential_schema_builder.add_attr("age").unwrap();
-        credential_schema_builder.add_attr("height").unwrap();
-        let credential_schema = credential_schema_builder.finalize().unwrap();
-
-        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
-        non_credential_schema_builder.add_attr("master_secret").unwrap();
-        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
-
-        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
-
-        let master_secret = Prover::new_master_secret().unwrap();
-        let credential_nonce = new_nonce().unwrap();
-
-        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
-        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
-        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
-        credential_values_builder.add_dec_known("age", "28").unwrap();
-        credential_values_builder.add_dec_known("height", "175").unwrap();
-        let cred_values = credential_values_builder.finalize().unwrap();
-
-        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
-            Prover::blind_credential_secrets(&cred_pub_key,
-                                        &cred_key_correctness_proof,
-                                        &cred_values,
-                                        &credential_nonce).unwrap();
-
-
+    fn _write_tails_file(rev_tails_generator: &mut RevocationTailsGenerator, chunk_size: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for chunk in rev_tails_generator.chunks(chunk_size) {
+            for tail in chunk.unwrap() {
+                bytes.extend(tail.to_bytes().unwrap());
+            }
+        }
+        bytes
+    }
 
-        let cred_issuance_nonce = new_nonce().unwrap();
+    #[test]
+    fn revocation_tails_generator_chunks_works() {
+        let cred_pub_key = issuer::mocks::credential_public_key();
+        let (_, _, _, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&cred_pub_key, 5, false).unwrap();
+
+        let mut expected = Vec::new();
+        let mut generator = rev_tails_generator.clone();
+        while let Some(tail) = generator.next().unwrap() {
+            expected.extend(tail.to_bytes().unwrap());
+        }
+        assert_eq!(11 * Tail::BYTES_REPR_SIZE, expected.len());
 
-        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
-                                                                                        &blinded_credential_secrets,
-                                                                                        &blinded_credential_secrets_correctness_proof,
-                                                                                        &credential_nonce,
-                                                                                        &cred_issuance_nonce,
-                                                                                        &cred_values,
-                                                                                        &cred_pub_key,
-                                                                                        &cred_priv_key).unwrap();
+        let chunk_lens: Vec<usize> = rev_tails_generator.clone().chunks(4).map(|chunk| chunk.unwrap().len()).collect();
+        assert_eq!(vec![4, 4, 3], chunk_lens);
+        assert_eq!(expected, _write_tails_file(&mut rev_tails_generator.clone(), 4));
+        assert_eq!(expected, _write_tails_file(&mut rev_tails_generator.clone(), 20));
 
-        Prover::process_credential_signature(&mut cred_signature,
-                                             &cred_values,
-                                             &signature_correctness_proof,
-                                             &credential_secrets_blinding_factors,
-                                             &cred_pub_key,
-                                             &cred_issuance_nonce,
-                                             None,
-                                             None,
-                                             None).unwrap();
+        // Generation resumes from a checkpoint of a serialized generator
+        let mut bytes = Vec::new();
+        for tail in rev_tails_generator.next_chunk(3).unwrap() {
+            bytes.extend(tail.to_bytes().unwrap());
+        }
+        let index = rev_tails_generator.index();
+        assert_eq!(3, index);
 
-        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
-        sub_proof_request_builder.add_revealed_attr("name").unwrap();
-        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
-        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
-        let mut proof_builder = Prover::new_proof_builder().unwrap();
-        proof_builder.add_common_attribute("master_secret").unwrap();
-        proof_builder.add_sub_proof_request(&sub_proof_request,
-                                            &credential_schema,
-                                            &non_credential_schema,
-                                            &cred_signature,
-                                            &cred_values,
-                                            &cred_pub_key,
-                                            None,
-                                            None).unwrap();
+        let rev_tails_generator_json = serde_json::to_string(&rev_tails_generator).unwrap();
+        let mut rev_tails_generator: RevocationTailsGenerator = serde_json::from_str(&rev_tails_generator_json).unwrap();
+        rev_tails_generator.seek(0).unwrap();
+        assert_eq!(11, rev_tails_generator.count());
+        rev_tails_generator.seek(index).unwrap();
+        assert_eq!(8, rev_tails_generator.count());
 
-        let proof_request_nonce = new_nonce().unwrap();
-        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+        bytes.extend(_write_tails_file(&mut rev_tails_generator, 5));
+        assert_eq!(expected, bytes);
+        assert!(rev_tails_generator.next_chunk(5).unwrap().is_empty());
 
-        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
-                                             &non_credential_schema,
-                                             &cred_pub_key,
-                                             None,
-                                             None).unwrap();
-        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+        rev_tails_generator.seek(11).unwrap();
+        assert_eq!(0, rev_tails_generator.count());
+        assert!(rev_tails_generator.seek(12).is_err());
+        assert!(rev_tails_generator.next_chunk(0).is_err());
+
+        let mut chunks = rev_tails_generator.chunks(0);
+        assert!(chunks.next().unwrap().is_err());
+        assert!(chunks.next().is_none());
     }
 
     #[test]
-    fn demo_revocation() {
+    fn for_registry_works_for_key_without_issuance_type() {
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), 5, true).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &HashSet::new());
+
+        // Key serialized before type of issuance was stored
+        let mut rev_key_pub_json = serde_json::to_value(&rev_key_pub).unwrap();
+        rev_key_pub_json.as_object_mut().unwrap().remove("issuance_type").unwrap();
+        let rev_key_pub: RevocationKeyPublic = serde_json::from_value(rev_key_pub_json).unwrap();
+        assert_eq!(None, rev_key_pub.issuance_type());
+
+        let res = Witness::new_for_registry(1, 5, &rev_key_pub, &rev_reg_delta, &simple_tail_accessor);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+        let res = Issuer::sign_credential_with_revoc_for_registry("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                                  &prover::mocks::blinded_credential_secrets(),
+                                                                  &prover::mocks::blinded_credential_secrets_correctness_proof(),
+                                                                  &issuer::mocks::credential_nonce(),
+                                                                  &issuer::mocks::credential_issuance_nonce(),
+                                                                  &issuer::mocks::credential_values(),
+                                                                  &issuer::mocks::credential_public_key(),
+                                                                  &issuer::mocks::credential_private_key(),
+                                                                  1,
+                                                                  5,
+                                                                  &rev_key_pub,
+                                                                  &mut rev_reg,
+                                                                  &rev_key_priv,
+                                                                  &simple_tail_accessor);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    // Creates proof of a revocable credential with revealed "name" and predicate "age" >= 18,
+    // returns it with verifiers with and without revocation registry and credential public key
+    fn _revocation_proof() -> (Proof, Nonce, verifier::ProofVerifier, verifier::ProofVerifier, CredentialPublicKey) {
         let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
         credential_schema_builder.add_attr("name").unwrap();
-        credential_schema_builder.add_attr("sex").unwrap();
         credential_schema_builder.add_attr("age").unwrap();
-        credential_schema_builder.add_attr("height").unwrap();
         let credential_schema = credential_schema_builder.finalize().unwrap();
 
         let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
@@ -1476,31 +3099,20 @@ mod test {
         let issuance_by_default = false;
         let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
             Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
-
         let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
 
         let master_secret = Prover::new_master_secret().unwrap();
-
-        let credential_nonce = new_nonce().unwrap();
-
         let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
         credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
         credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
-        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
         credential_values_builder.add_dec_known("age", "28").unwrap();
-        credential_values_builder.add_dec_known("height", "175").unwrap();
         let cred_values = credential_values_builder.finalize().unwrap();
 
+        let credential_nonce = new_nonce().unwrap();
         let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
-            Prover::blind_credential_secrets(&cred_pub_key,
-                                        &cred_key_correctness_proof,
-                                        &cred_values,
-                                        &credential_nonce).unwrap();
-
-
+            Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
 
         let credential_issuance_nonce = new_nonce().unwrap();
-
         let rev_idx = 1;
         let (mut cred_signature, signature_correctness_proof, rev_reg_delta) =
             Issuer::sign_credential_with_revoc("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
@@ -1534,6 +3146,7 @@ mod test {
         sub_proof_request_builder.add_revealed_attr("name").unwrap();
         sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
         let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
         let mut proof_builder = Prover::new_proof_builder().unwrap();
         proof_builder.add_common_attribute("master_secret").unwrap();
         proof_builder.add_sub_proof_request(&sub_proof_request,
@@ -1548,12 +3161,877 @@ mod test {
         let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
 
         let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
-        proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                             &credential_schema,
-                                             &non_credential_schema,
-                                             &cred_pub_key,
-                                             Some(&rev_key_pub),
-                                             Some(&rev_reg)).unwrap();
-        assert_eq!(true, proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+        proof_verifier.add_sub_proof_request(&sub_proof_request, &credential_schema, &non_credential_schema,
+                                             &cred_pub_key, Some(&rev_key_pub), Some(&rev_reg)).unwrap();
+
+        let mut proof_verifier_without_rev_reg = Verifier::new_proof_verifier().unwrap();
+        proof_verifier_without_rev_reg.add_sub_proof_request(&sub_proof_request, &credential_schema, &non_credential_schema,
+                                                             &cred_pub_key, None, None).unwrap();
+
+        (proof, proof_request_nonce, proof_verifier, proof_verifier_without_rev_reg, cred_pub_key)
+    }
+
+    fn _corrupted_proof<F>(proof: &Proof, corrupt: F) -> Proof where F: FnOnce(&mut Proof) {
+        let mut proof: Proof = serde_json::from_str(&serde_json::to_string(proof).unwrap()).unwrap();
+        corrupt(&mut proof);
+        proof
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    fn proof_cbor_works() {
+        let (proof, nonce, proof_verifier, _, _) = _revocation_proof();
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let cbor = proof.to_cbor().unwrap();
+        assert!(cbor.len() * 2 < json.len());
+
+        // points are decoded in normalized form, so decoded proof is compared by its encoding
+        let decoded = Proof::from_cbor(&cbor).unwrap();
+        let decoded_again = Proof::from_cbor(&decoded.to_cbor().unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&decoded_again).unwrap());
+        assert!(proof_verifier.verify(&decoded, &nonce).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    fn proof_cbor_works_for_corrupted_input() {
+        let (proof, _, _, _, _) = _revocation_proof();
+        let cbor = proof.to_cbor().unwrap();
+
+        for len in &[0, 1, 2, cbor.len() / 2, cbor.len() - 1] {
+            let res = Proof::from_cbor(&cbor[..*len]);
+            assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+
+        let mut other_version = cbor.clone();
+        other_version[1] = cbor::FORMAT_VERSION as u8 + 1;
+        assert_eq!(ErrorKind::InvalidStructure, Proof::from_cbor(&other_version).unwrap_err().kind());
+
+        let mut trailing = cbor.clone();
+        trailing.push(0);
+        assert_eq!(ErrorKind::InvalidStructure, Proof::from_cbor(&trailing).unwrap_err().kind());
+
+        let res = Proof::from_cbor(serde_json::to_string(&proof).unwrap().as_bytes());
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn proof_verifier_verify_works_for_invalid_nonce() {
+        let (proof, nonce, proof_verifier, _, _) = _revocation_proof();
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+
+        // 2^80
+        let nonce = BigNumber::from_dec("1208925819614629174706176").unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, proof_verifier.verify(&proof, &nonce).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, proof_verifier.verify_constant_outcome(&proof, &nonce, None).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, proof_verifier.verify_with_diagnostics(&proof, &nonce).unwrap_err().kind());
+    }
+
+    #[test]
+    fn proof_verifier_verify_with_diagnostics_works() {
+        use self::verifier::{ProofCheckFailure, ProofVerificationReport, SubProofReport};
+
+        let (proof, nonce, proof_verifier, proof_verifier_without_rev_reg, _) = _revocation_proof();
+
+        let report = proof_verifier.verify_with_diagnostics(&proof, &nonce).unwrap();
+        assert_eq!(ProofVerificationReport { valid: true, sub_proofs: vec![SubProofReport { failures: vec![] }], challenge_mismatch: false }, report);
+
+        let failures = |proof: &Proof| {
+            let report = proof_verifier.verify_with_diagnostics(proof, &nonce).unwrap();
+            assert!(!report.valid);
+            assert!(!proof_verifier.verify(proof, &nonce).unwrap_or(false));
+            report.sub_proofs[0].failures.clone()
+        };
+
+        // revealed attribute removed
+        let proof_without_name = _corrupted_proof(&proof, |proof| { proof.proofs[0].primary_proof.eq_proof.revealed_attrs.remove("name"); });
+        assert_eq!(vec![ProofCheckFailure::RevealedAttrs { missing: btreeset!["name".to_string()], unexpected: BTreeSet::new() }],
+                   failures(&proof_without_name));
+
+        // eq proof commitment altered
+        let proof_with_a_prime = _corrupted_proof(&proof, |proof| {
+            let eq_proof = &mut proof.proofs[0].primary_proof.eq_proof;
+            eq_proof.a_prime = eq_proof.a_prime.increment().unwrap();
+        });
+        match failures(&proof_with_a_prime).as_slice() {
+            [ProofCheckFailure::EqProof { attr_name: None, .. }] => {}
+            failures => panic!("Unexpected failures: {:?}", failures)
+        }
+
+        // predicate commitment altered and removed
+        let proof_with_t = _corrupted_proof(&proof, |proof| {
+            let t = proof.proofs[0].primary_proof.ne_proofs[0].t.get_mut("1").unwrap();
+            *t = t.increment().unwrap();
+        });
+        match failures(&proof_with_t).as_slice() {
+            [ProofCheckFailure::Predicate { predicate_index: Some(0), ref attr_name, .. }] => assert_eq!("age", attr_name),
+            failures => panic!("Unexpected failures: {:?}", failures)
+        }
+
+        let proof_without_delta = _corrupted_proof(&proof, |proof| { proof.proofs[0].primary_proof.ne_proofs[0].t.remove("DELTA"); });
+        match failures(&proof_without_delta).as_slice() {
+            [ProofCheckFailure::Predicate { predicate_index: Some(0), ref attr_name, ref reason }] => {
+                assert_eq!("age", attr_name);
+                assert!(reason.contains("DELTA"));
+            }
+            failures => panic!("Unexpected failures: {:?}", failures)
+        }
+
+        let proof_without_predicate = _corrupted_proof(&proof, |proof| proof.proofs[0].primary_proof.ne_proofs.clear());
+        match failures(&proof_without_predicate).as_slice() {
+            [ProofCheckFailure::Predicate { predicate_index: None, ref attr_name, .. }] => assert_eq!("age", attr_name),
+            failures => panic!("Unexpected failures: {:?}", failures)
+        }
+
+        // non revocation commitment altered and missing registry
+        let proof_with_non_revoc_c_list = _corrupted_proof(&proof, |proof| {
+            proof.proofs[0].non_revoc_proof.as_mut().unwrap().c_list.e = PointG1::new().unwrap();
+        });
+        match failures(&proof_with_non_revoc_c_list).as_slice() {
+            [ProofCheckFailure::NonRevocation { .. }] => {}
+            failures => panic!("Unexpected failures: {:?}", failures)
+        }
+
+        let report = proof_verifier_without_rev_reg.verify_with_diagnostics(&proof, &nonce).unwrap();
+        assert!(!report.valid);
+        assert!(!report.challenge_mismatch);
+        match report.sub_proofs[0].failures.as_slice() {
+            [ProofCheckFailure::NonRevocation { .. }] => {}
+            failures => panic!("Unexpected failures: {:?}", failures)
+        }
+
+        // responses are bound only by the aggregated challenge
+        let proof_with_name = _corrupted_proof(&proof, |proof| {
+            let name = proof.proofs[0].primary_proof.eq_proof.revealed_attrs.get_mut("name").unwrap();
+            *name = name.increment().unwrap();
+        });
+        let proof_with_c_hash = _corrupted_proof(&proof, |proof| proof.aggregated_proof.c_hash = proof.aggregated_proof.c_hash.increment().unwrap());
+        for proof in &[proof_with_name, proof_with_c_hash] {
+            assert!(failures(proof).is_empty());
+            assert!(proof_verifier.verify_with_diagnostics(proof, &nonce).unwrap().challenge_mismatch);
+        }
+
+        let serialized = serde_json::to_value(&proof_verifier.verify_with_diagnostics(&proof_with_t, &nonce).unwrap()).unwrap();
+        assert_eq!("predicate", serialized["sub_proofs"][0]["failures"][0]["check"]);
+    }
+
+    #[test]
+    fn proof_verifier_verify_with_cache_works() {
+        use self::verifier::NonRevocVerificationCache;
+        use std::sync::Arc;
+        use std::thread;
+
+        let (proof, nonce, proof_verifier, _, cred_pub_key) = _revocation_proof();
+        let cache = Arc::new(NonRevocVerificationCache::new().unwrap());
+        assert!(cache.is_empty().unwrap());
+
+        // miss computes pairings, hit uses them
+        assert!(proof_verifier.verify(&proof, &nonce).unwrap());
+        assert!(proof_verifier.verify_with_cache(&proof, &nonce, &cache).unwrap());
+        assert_eq!(1, cache.len().unwrap());
+        assert!(proof_verifier.verify_with_cache(&proof, &nonce, &cache).unwrap());
+        cache.precompute(&cred_pub_key).unwrap();
+        assert_eq!(1, cache.len().unwrap());
+
+        let invalid_proof = _corrupted_proof(&proof, |proof| {
+            let x_list = &mut proof.proofs[0].non_revoc_proof.as_mut().unwrap().x_list;
+            x_list.rho = GroupOrderElement::new().unwrap();
+        });
+        assert!(!proof_verifier.verify(&invalid_proof, &nonce).unwrap());
+        assert!(!proof_verifier.verify_with_cache(&invalid_proof, &nonce, &cache).unwrap());
+
+        // proof for another key misses the cache
+        let (other_proof, other_nonce, other_proof_verifier, _, other_cred_pub_key) = _revocation_proof();
+        assert_ne!(cred_pub_key.r_key, other_cred_pub_key.r_key);
+
+        let other_cache = cache.clone();
+        let handle = thread::spawn(move || other_proof_verifier.verify_with_cache(&other_proof, &other_nonce, &other_cache).unwrap());
+        assert!(proof_verifier.verify_with_cache(&proof, &nonce, &cache).unwrap());
+        assert!(handle.join().unwrap());
+        assert_eq!(2, cache.len().unwrap());
+    }
+
+    #[test]
+    fn proof_verifier_verify_with_cache_works_for_max_entries() {
+        use self::verifier::NonRevocVerificationCache;
+
+        assert_eq!(ErrorKind::InvalidStructure, NonRevocVerificationCache::with_max_entries(0).unwrap_err().kind());
+
+        let (proof, nonce, proof_verifier, _, _) = _revocation_proof();
+        let (other_proof, other_nonce, other_proof_verifier, _, _) = _revocation_proof();
+        let cache = NonRevocVerificationCache::with_max_entries(1).unwrap();
+
+        // every new key evicts the only cached one, the evicted key is computed again
+        assert!(proof_verifier.verify_with_cache(&proof, &nonce, &cache).unwrap());
+        assert!(other_proof_verifier.verify_with_cache(&other_proof, &other_nonce, &cache).unwrap());
+        assert_eq!(1, cache.len().unwrap());
+        assert!(proof_verifier.verify_with_cache(&proof, &nonce, &cache).unwrap());
+        assert_eq!(1, cache.len().unwrap());
+    }
+
+    enum RevocationOp {
+        Revoke(u32),
+        Recover(u32)
+    }
+
+    // Applies operations to a new registry, returns the registry before and after them and a delta of every operation
+    fn _revocation_registry_deltas(max_cred_num: u32,
+                                   issuance_by_default: bool,
+                                   ops: &[RevocationOp]) -> (RevocationRegistry, RevocationRegistry, Vec<RevocationRegistryDelta>, SimpleTailsAccessor) {
+        let (_, _, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), max_cred_num, issuance_by_default).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let initial_rev_reg = rev_reg.clone();
+
+        let deltas = ops.iter()
+            .map(|op| match *op {
+                RevocationOp::Revoke(rev_idx) => Issuer::revoke_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap(),
+                RevocationOp::Recover(rev_idx) => Issuer::recovery_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap()
+            })
+            .collect();
+
+        (initial_rev_reg, rev_reg, deltas, simple_tail_accessor)
+    }
+
+    fn _merge_all(deltas: &[RevocationRegistryDelta]) -> RevocationRegistryDelta {
+        deltas[1..].iter().fold(deltas[0].clone(), |merged, delta| merged.merged(delta).unwrap())
+    }
+
+    fn _omega_bytes(witness: &Witness) -> Vec<u8> {
+        witness.omega.to_bytes().unwrap()
+    }
+
+    fn _assert_checkpoints_eq(expected: &AccumulatorCheckpoint, actual: &AccumulatorCheckpoint) {
+        assert_eq!(expected.seq_no, actual.seq_no);
+        assert_eq!(expected.accum.to_bytes().unwrap(), actual.accum.to_bytes().unwrap());
+        assert_eq!(expected.issued, actual.issued);
+        assert_eq!(expected.revoked, actual.revoked);
+    }
+
+    #[test]
+    fn revocation_index_allocator_works_for_mixed_strategies() {
+        let mut allocator = RevocationIndexAllocator::new(5);
+
+        assert_eq!(3, allocator.allocate(&IndexAllocation::Explicit(3)).unwrap());
+        assert_eq!(1, allocator.allocate(&IndexAllocation::Sequential).unwrap());
+        assert_eq!(2, allocator.allocate(&IndexAllocation::Sequential).unwrap());
+
+        // the state round-trips through serialization
+        let mut allocator: RevocationIndexAllocator = serde_json::from_str(&serde_json::to_string(&allocator).unwrap()).unwrap();
+        assert_eq!(hashset![1, 2, 3], allocator.used);
+
+        assert_eq!(4, allocator.allocate(&IndexAllocation::Sequential).unwrap());
+        assert_eq!(5, allocator.allocate(&IndexAllocation::Random).unwrap());
+
+        for allocation in &[IndexAllocation::Sequential, IndexAllocation::Random] {
+            assert_eq!(ErrorKind::AnoncredsRevocationAccumulatorIsFull, allocator.allocate(allocation).unwrap_err().kind());
+        }
+        assert_eq!(hashset![1, 2, 3, 4, 5], allocator.used);
+    }
+
+    #[test]
+    fn revocation_index_allocator_works_for_invalid_explicit_index() {
+        let mut allocator = RevocationIndexAllocator::new(5);
+        allocator.allocate(&IndexAllocation::Explicit(2)).unwrap();
+
+        for &rev_idx in &[0, 2, 6, u32::MAX] {
+            assert_eq!(ErrorKind::AnoncredsInvalidRevocationAccumulatorIndex,
+                       allocator.allocate(&IndexAllocation::Explicit(rev_idx)).unwrap_err().kind(), "{}", rev_idx);
+        }
+        assert_eq!(ErrorKind::AnoncredsInvalidRevocationAccumulatorIndex, allocator.reserve(2).unwrap_err().kind());
+        assert_eq!(hashset![2], allocator.used);
+
+        // sequential index skips explicitly used one
+        assert_eq!(1, allocator.allocate(&IndexAllocation::Sequential).unwrap());
+        assert_eq!(3, allocator.allocate(&IndexAllocation::Sequential).unwrap());
+    }
+
+    #[test]
+    fn revocation_index_allocator_works_for_random() {
+        let max_cred_num = 100;
+        let mut allocator = RevocationIndexAllocator::new(max_cred_num);
+        allocator.allocate(&IndexAllocation::Explicit(50)).unwrap();
+
+        // nearly full registry takes the remaining indices without collisions
+        let indices = (1..max_cred_num)
+            .map(|_| allocator.allocate(&IndexAllocation::Random).unwrap())
+            .collect::<HashSet<u32>>();
+        assert_eq!(max_cred_num as usize - 1, indices.len());
+        assert!(!indices.contains(&50));
+        assert_eq!((1..=max_cred_num).collect::<HashSet<u32>>(), allocator.used);
+
+        assert_eq!(r#"{"Explicit":7}"#, serde_json::to_string(&IndexAllocation::Explicit(7)).unwrap());
+        for allocation in &[IndexAllocation::Sequential, IndexAllocation::Explicit(7), IndexAllocation::Random] {
+            assert_eq!(*allocation, serde_json::from_str::<IndexAllocation>(&serde_json::to_string(allocation).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn sign_credential_with_revoc_index_allocation_works() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, false).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let mut allocator = RevocationIndexAllocator::new(max_cred_num);
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let mut sign = |allocation: IndexAllocation, rev_reg: &mut RevocationRegistry| {
+            let credential_nonce = new_nonce().unwrap();
+            let (blinded_credential_secrets, _, blinded_credential_secrets_correctness_proof) =
+                Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+            Issuer::sign_credential_with_revoc_index_allocation("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                                &blinded_credential_secrets,
+                                                                &blinded_credential_secrets_correctness_proof,
+                                                                &credential_nonce,
+                                                                &new_nonce().unwrap(),
+                                                                &cred_values,
+                                                                &cred_pub_key,
+                                                                &cred_priv_key,
+                                                                &allocation,
+                                                                &mut allocator,
+                                                                &rev_key_pub,
+                                                                rev_reg,
+                                                                &rev_key_priv,
+                                                                &simple_tail_accessor)
+                .map(|(cred_signature, _, rev_reg_delta, rev_idx)| (cred_signature, rev_reg_delta.unwrap(), rev_idx))
+        };
+
+        let mut issued = HashSet::new();
+        for &(allocation, expected) in &[(IndexAllocation::Explicit(4), Some(4)), (IndexAllocation::Sequential, Some(1)),
+                                         (IndexAllocation::Random, None), (IndexAllocation::Sequential, None)] {
+            let (cred_signature, rev_reg_delta, rev_idx) = sign(allocation, &mut rev_reg).unwrap();
+            if let Some(expected) = expected {
+                assert_eq!(expected, rev_idx);
+            }
+            assert!(issued.insert(rev_idx));
+            assert_eq!(hashset![rev_idx], rev_reg_delta.issued);
+
+            // the index round-trips through serialization of the signature
+            let cred_signature: CredentialSignature = serde_json::from_str(&serde_json::to_string(&cred_signature).unwrap()).unwrap();
+            assert_eq!(Some(rev_idx), cred_signature.extract_index());
+        }
+
+        // the registry isn't changed by rejected index
+        let accum = rev_reg.accum.to_bytes().unwrap();
+        for &allocation in &[IndexAllocation::Explicit(4), IndexAllocation::Explicit(0), IndexAllocation::Explicit(max_cred_num + 1)] {
+            assert_eq!(ErrorKind::AnoncredsInvalidRevocationAccumulatorIndex, sign(allocation, &mut rev_reg).unwrap_err().kind());
+        }
+        assert_eq!(accum, rev_reg.accum.to_bytes().unwrap());
+
+        let last = (1..=max_cred_num).find(|rev_idx| !issued.contains(rev_idx)).unwrap();
+        assert_eq!(last, sign(IndexAllocation::Random, &mut rev_reg).unwrap().2);
+        assert_eq!(ErrorKind::AnoncredsRevocationAccumulatorIsFull, sign(IndexAllocation::Sequential, &mut rev_reg).unwrap_err().kind());
+    }
+
+    #[test]
+    fn accumulator_checkpoint_works() {
+        let max_cred_num = 5;
+        let by_default_ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2), RevocationOp::Recover(1),
+                              RevocationOp::Revoke(3), RevocationOp::Recover(2), RevocationOp::Revoke(4)];
+        let on_demand_ops = [RevocationOp::Recover(1), RevocationOp::Recover(2), RevocationOp::Revoke(1),
+                             RevocationOp::Recover(3), RevocationOp::Revoke(2), RevocationOp::Recover(1)];
+
+        for &(issuance_by_default, ref ops) in &[(true, &by_default_ops), (false, &on_demand_ops)] {
+            let (initial_rev_reg, rev_reg, deltas, _) = _revocation_registry_deltas(max_cred_num, issuance_by_default, &ops[..]);
+            let genesis = AccumulatorCheckpoint::new(&initial_rev_reg);
+
+            let mut replayed = genesis.clone();
+            let mut snapshot = None;
+            for (i, delta) in deltas.iter().enumerate() {
+                replayed.apply_delta(delta).unwrap();
+                if i == 1 {
+                    snapshot = Some(serde_json::to_string(&replayed).unwrap());
+                }
+            }
+            assert_eq!(deltas.len() as u64, replayed.seq_no());
+            assert_eq!(rev_reg.accum.to_bytes().unwrap(), replayed.revocation_registry().accum.to_bytes().unwrap());
+
+            let snapshot: AccumulatorCheckpoint = serde_json::from_str(&snapshot.unwrap()).unwrap();
+            let mut restored = snapshot.clone();
+            for delta in deltas[2..].iter() {
+                restored.apply_delta(delta).unwrap();
+            }
+            _assert_checkpoints_eq(&replayed, &restored);
+
+            let merged = _merge_all(&deltas);
+            let from_genesis = genesis.diff(&replayed);
+            assert_eq!((0, deltas.len() as u64), (from_genesis.from_seq_no(), from_genesis.to_seq_no()));
+            assert_eq!(merged.issued, from_genesis.delta().issued);
+            assert_eq!(merged.revoked, from_genesis.delta().revoked);
+            assert_eq!(replayed.issued, from_genesis.delta().issued);
+            assert_eq!(replayed.revoked, from_genesis.delta().revoked);
+
+            // the diff moves a checkpoint forward and back by several deltas
+            let mut forward = snapshot.clone();
+            forward.apply_diff(&snapshot.diff(&replayed)).unwrap();
+            _assert_checkpoints_eq(&replayed, &forward);
+
+            let mut back = replayed.clone();
+            back.apply_diff(&replayed.diff(&snapshot)).unwrap();
+            assert_eq!(2, back.seq_no());
+            _assert_checkpoints_eq(&snapshot, &back);
+
+            // diff round-trips through serialization
+            let diff: AccumulatorCheckpointDiff = serde_json::from_str(&serde_json::to_string(&genesis.diff(&replayed)).unwrap()).unwrap();
+            let mut from_genesis = genesis.clone();
+            from_genesis.apply_diff(&diff).unwrap();
+            _assert_checkpoints_eq(&replayed, &from_genesis);
+        }
+    }
+
+    #[test]
+    fn accumulator_checkpoint_apply_diff_works_for_other_seq_no() {
+        let max_cred_num = 5;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2)];
+        let (initial_rev_reg, _, deltas, _) = _revocation_registry_deltas(max_cred_num, true, &ops);
+        let genesis = AccumulatorCheckpoint::new(&initial_rev_reg);
+        let mut checkpoint = genesis.clone();
+        checkpoint.apply_delta(&deltas[0]).unwrap();
+        let mut latest = checkpoint.clone();
+        latest.apply_delta(&deltas[1]).unwrap();
+
+        // checkpoint with the same accumulator but other sequence number
+        let mut other = genesis.clone();
+        other.seq_no = 1;
+        let err = other.apply_diff(&genesis.diff(&checkpoint)).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("continuity violation"));
+        assert_eq!(1, other.seq_no());
+
+        let mut same_seq_no = checkpoint.clone();
+        assert_eq!(ErrorKind::InvalidStructure, same_seq_no.apply_diff(&genesis.diff(&latest)).unwrap_err().kind());
+        _assert_checkpoints_eq(&checkpoint, &same_seq_no);
+    }
+
+    #[test]
+    fn accumulator_checkpoint_apply_delta_works_for_continuity_violation() {
+        let max_cred_num = 5;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2)];
+        let (initial_rev_reg, rev_reg, deltas, _) = _revocation_registry_deltas(max_cred_num, true, &ops);
+        let mut checkpoint = AccumulatorCheckpoint::new(&initial_rev_reg);
+
+        let err = checkpoint.apply_delta(&deltas[1]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("continuity violation"));
+
+        let without_prev_accum = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &hashset![1, 2]);
+        let err = checkpoint.apply_delta(&without_prev_accum).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("continuity violation"));
+
+        _assert_checkpoints_eq(&AccumulatorCheckpoint::new(&initial_rev_reg), &checkpoint);
+
+        checkpoint.apply_delta(&deltas[0]).unwrap();
+        checkpoint.apply_delta(&deltas[1]).unwrap();
+        assert_eq!(hashset![1, 2], checkpoint.revoked);
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    fn credential_and_revocation_entities_cbor_works() {
+        fn check<T, F>(entity: &T, from_cbor: F) where T: ::serde::Serialize, F: Fn(&[u8]) -> Result<T, IndyCryptoError> {
+            let json = serde_json::to_string(entity).unwrap();
+            let cbor = cbor::to_vec(entity).unwrap();
+            assert!(cbor.len() < json.len());
+            // points are decoded in normalized form, so decoded entity is compared by its encoding
+            assert_eq!(cbor, cbor::to_vec(&from_cbor(&cbor).unwrap()).unwrap());
+        }
+
+        let max_cred_num = 5;
+        let (rev_key_pub, _, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), max_cred_num, true).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let rev_reg_delta = Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &simple_tail_accessor).unwrap();
+        let witness = Witness::new(1, max_cred_num, true, &rev_reg_delta, &simple_tail_accessor).unwrap();
+
+        // mocked non revocation signature is built of points that are not on the curve
+        check(&CredentialSignature { p_credential: issuer::mocks::primary_credential(), r_credential: None }, CredentialSignature::from_cbor);
+        check(&issuer::mocks::signature_correctness_proof(), SignatureCorrectnessProof::from_cbor);
+        check(&rev_key_pub, RevocationKeyPublic::from_cbor);
+        check(&rev_reg, RevocationRegistry::from_cbor);
+        check(&rev_reg_delta, RevocationRegistryDelta::from_cbor);
+        check(&witness, Witness::from_cbor);
+    }
+
+    #[test]
+    fn revocation_registry_delta_compact_bytes_works() {
+        let (_, _, rev_reg, _) =
+            Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), 5, true).unwrap();
+
+        let dense = (1..100_001).collect::<HashSet<u32>>();
+        let isolated = (1..10_001).map(|i| i * 3).collect::<HashSet<u32>>();
+        let mixed = (1..5000).filter(|i| i % 10 < 4 || i % 997 == 0).collect::<HashSet<u32>>();
+
+        for &(prev_rev_reg, ref issued, ref revoked) in &[(None, &dense, &HashSet::new()),
+                                                          (Some(&rev_reg), &isolated, &mixed),
+                                                          (Some(&rev_reg), &HashSet::new(), &HashSet::new()),
+                                                          (None, &hashset![1, u32::MAX], &hashset![2])] {
+            let rev_reg_delta = RevocationRegistryDelta::from_parts(prev_rev_reg, &rev_reg, issued, revoked);
+            let bytes = rev_reg_delta.to_compact_bytes().unwrap();
+            assert!(bytes.len() < serde_json::to_string(&rev_reg_delta).unwrap().len());
+
+            let decoded = RevocationRegistryDelta::from_compact_bytes(&bytes).unwrap();
+            // decoded points are normalized, so they are compared by their bytes
+            assert_eq!(rev_reg_delta.prev_accum.map(|accum| accum.to_bytes().unwrap()),
+                       decoded.prev_accum.map(|accum| accum.to_bytes().unwrap()));
+            assert_eq!(rev_reg_delta.accum.to_bytes().unwrap(), decoded.accum.to_bytes().unwrap());
+            assert_eq!(rev_reg_delta.issued, decoded.issued);
+            assert_eq!(rev_reg_delta.revoked, decoded.revoked);
+        }
+
+        // 100000 issued indices take about 600 KB of json
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &dense, &HashSet::new());
+        assert!(rev_reg_delta.to_compact_bytes().unwrap().len() < 100);
+        assert!(serde_json::to_string(&rev_reg_delta).unwrap().len() > 500_000);
+    }
+
+    #[test]
+    fn revocation_registry_delta_compact_bytes_works_for_corrupted_input() {
+        let (_, _, rev_reg, _) =
+            Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), 5, true).unwrap();
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(Some(&rev_reg), &rev_reg, &hashset![1, 2, 3], &hashset![5]);
+        let bytes = rev_reg_delta.to_compact_bytes().unwrap();
+
+        for len in 0..bytes.len() {
+            assert!(RevocationRegistryDelta::from_compact_bytes(&bytes[..len]).is_err());
+        }
+
+        let mut unknown_flags = bytes.clone();
+        unknown_flags[1] |= 0x02;
+        assert_eq!(ErrorKind::InvalidStructure, RevocationRegistryDelta::from_compact_bytes(&unknown_flags).unwrap_err().kind());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(ErrorKind::InvalidStructure, RevocationRegistryDelta::from_compact_bytes(&trailing).unwrap_err().kind());
+
+        let mut invalid_accum = bytes.clone();
+        invalid_accum[2] = 0x04;
+        assert_eq!(ErrorKind::InvalidPoint, RevocationRegistryDelta::from_compact_bytes(&invalid_accum).unwrap_err().kind());
+
+        let json = serde_json::to_string(&rev_reg_delta).unwrap();
+        assert!(RevocationRegistryDelta::from_compact_bytes(json.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn witness_compact_bytes_works() {
+        let max_cred_num = 5;
+        let (_, _, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), max_cred_num, true).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let rev_reg_delta = Issuer::revoke_credential(&mut rev_reg, max_cred_num, 2, &simple_tail_accessor).unwrap();
+        let witness = Witness::new(1, max_cred_num, true, &rev_reg_delta, &simple_tail_accessor).unwrap();
+
+        let bytes = witness.to_compact_bytes().unwrap();
+        assert_eq!(1 + PointG2::COMPRESSED_BYTES_REPR_SIZE, bytes.len());
+        assert!(bytes.len() < serde_json::to_string(&witness).unwrap().len());
+        assert_eq!(_omega_bytes(&witness), _omega_bytes(&Witness::from_compact_bytes(&bytes).unwrap()));
+
+        assert_eq!(ErrorKind::InvalidStructure, Witness::from_compact_bytes(&bytes[..bytes.len() - 1]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, Witness::from_compact_bytes(&[bytes.as_slice(), &[0]].concat()).unwrap_err().kind());
+    }
+
+    #[test]
+    fn revocation_registry_delta_merge_works_for_chain() {
+        let max_cred_num = 5;
+        let rev_idx = 5;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2), RevocationOp::Recover(1),
+                   RevocationOp::Revoke(3), RevocationOp::Recover(2)];
+        let (initial_rev_reg, rev_reg, deltas, simple_tail_accessor) = _revocation_registry_deltas(max_cred_num, true, &ops);
+
+        let merged = _merge_all(&deltas);
+        let from_scratch = RevocationRegistryDelta::from_parts(Some(&initial_rev_reg), &rev_reg, &HashSet::new(), &hashset![3]);
+
+        assert_eq!(from_scratch.prev_accum, merged.prev_accum);
+        assert_eq!(from_scratch.accum, merged.accum);
+        assert_eq!(from_scratch.issued, merged.issued);
+        assert_eq!(from_scratch.revoked, merged.revoked);
+
+        let initial_delta = RevocationRegistryDelta::from_parts(None, &initial_rev_reg, &HashSet::new(), &HashSet::new());
+        let mut witness_by_deltas = Witness::new(rev_idx, max_cred_num, true, &initial_delta, &simple_tail_accessor).unwrap();
+        for delta in deltas.iter() {
+            witness_by_deltas.update(rev_idx, max_cred_num, delta, &simple_tail_accessor).unwrap();
+        }
+
+        let mut witness_by_merged = Witness::new(rev_idx, max_cred_num, true, &initial_delta, &simple_tail_accessor).unwrap();
+        witness_by_merged.update(rev_idx, max_cred_num, &merged, &simple_tail_accessor).unwrap();
+
+        let final_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &hashset![3]);
+        let witness_from_scratch = Witness::new(rev_idx, max_cred_num, true, &final_delta, &simple_tail_accessor).unwrap();
+
+        assert_eq!(_omega_bytes(&witness_from_scratch), _omega_bytes(&witness_by_deltas));
+        assert_eq!(_omega_bytes(&witness_from_scratch), _omega_bytes(&witness_by_merged));
+    }
+
+    #[test]
+    fn revocation_registry_delta_merge_works_for_reissued_index() {
+        let max_cred_num = 5;
+        let ops = [RevocationOp::Recover(1), RevocationOp::Recover(2), RevocationOp::Revoke(1),
+                   RevocationOp::Recover(1), RevocationOp::Revoke(2)];
+        let (initial_rev_reg, rev_reg, deltas, _) = _revocation_registry_deltas(max_cred_num, false, &ops);
+
+        let merged = _merge_all(&deltas);
+        assert_eq!(Some(initial_rev_reg.accum), merged.prev_accum);
+        assert_eq!(rev_reg.accum, merged.accum);
+        assert_eq!(hashset![1], merged.issued);
+        assert!(merged.revoked.is_empty());
+
+        let mut merged_in_place = deltas[0].clone();
+        for delta in deltas[1..].iter() {
+            merged_in_place.merge(delta).unwrap();
+        }
+        assert_eq!(merged.issued, merged_in_place.issued);
+        assert_eq!(merged.accum, merged_in_place.accum);
+    }
+
+    #[test]
+    fn revocation_registry_delta_merge_works_for_discontinuous_deltas() {
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2), RevocationOp::Revoke(3)];
+        let (initial_rev_reg, _, deltas, _) = _revocation_registry_deltas(5, true, &ops);
+
+        let mut delta = deltas[0].clone();
+        let err = delta.merge(&deltas[2]).unwrap_err();
+        assert!(err.to_string().contains("is not accumulator of this delta"));
+        assert_eq!(deltas[0].accum, delta.accum);
+        assert_eq!(deltas[0].revoked, delta.revoked);
+
+        assert!(deltas[1].merged(&deltas[0]).is_err());
+
+        let without_prev = RevocationRegistryDelta::from_parts(None, &initial_rev_reg, &HashSet::new(), &HashSet::new());
+        let err = deltas[0].merged(&without_prev).unwrap_err();
+        assert!(err.to_string().contains("has no previous accumulator"));
+    }
+
+    #[test]
+    fn revocation_registry_delta_merge_works_for_serialized_deltas() {
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2), RevocationOp::Recover(1)];
+        let (_, _, deltas, _) = _revocation_registry_deltas(5, true, &ops);
+
+        let deltas: Vec<RevocationRegistryDelta> = deltas.iter()
+            .map(|delta| serde_json::from_str(&serde_json::to_string(delta).unwrap()).unwrap())
+            .collect();
+        let merged = _merge_all(&deltas);
+
+        let merged_json = serde_json::to_string(&merged).unwrap();
+        let merged_back: RevocationRegistryDelta = serde_json::from_str(&merged_json).unwrap();
+        assert_eq!(merged.prev_accum, merged_back.prev_accum);
+        assert_eq!(merged.accum, merged_back.accum);
+        assert_eq!(hashset![2], merged_back.revoked);
+        assert!(merged_back.issued.is_empty());
+        assert!(!merged_json.contains("issued"));
+    }
+
+    struct CountingTailsAccessor {
+        tails_accessor: SimpleTailsAccessor,
+        reads: RefCell<HashMap<u32, usize>>
+    }
+
+    impl RevocationTailsAccessor for CountingTailsAccessor {
+        fn access_tail(&self, tail_id: u32, accessor: &mut dyn FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+            *self.reads.borrow_mut().entry(tail_id).or_insert(0) += 1;
+            self.tails_accessor.access_tail(tail_id, accessor)
+        }
+    }
+
+    // Merges runs of consecutive deltas of the given lens, so deltas have several indices
+    fn _chunk_deltas(deltas: &[RevocationRegistryDelta], lens: &[usize]) -> Vec<RevocationRegistryDelta> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        for len in lens.iter().cycle() {
+            if start == deltas.len() {
+                break;
+            }
+            let end = (start + len).min(deltas.len());
+            chunks.push(_merge_all(&deltas[start..end]));
+            start = end;
+        }
+        chunks
+    }
+
+    // Witness of rev_idx updated by every delta in turn and by all deltas at once
+    fn _witnesses(rev_idx: u32,
+                  max_cred_num: u32,
+                  initial_rev_reg: &RevocationRegistry,
+                  deltas: &[RevocationRegistryDelta],
+                  tails_accessor: &CountingTailsAccessor) -> (Witness, Witness) {
+        let initial_delta = RevocationRegistryDelta::from_parts(None, initial_rev_reg, &HashSet::new(), &HashSet::new());
+
+        let mut witness_by_deltas = Witness::new(rev_idx, max_cred_num, true, &initial_delta, tails_accessor).unwrap();
+        for delta in deltas.iter() {
+            witness_by_deltas.update(rev_idx, max_cred_num, delta, tails_accessor).unwrap();
+        }
+
+        let mut witness_at_once = Witness::new(rev_idx, max_cred_num, true, &initial_delta, tails_accessor).unwrap();
+        tails_accessor.reads.borrow_mut().clear();
+        witness_at_once.update_with_deltas(rev_idx, max_cred_num, deltas, tails_accessor).unwrap();
+
+        (witness_by_deltas, witness_at_once)
+    }
+
+    #[test]
+    fn witness_update_with_deltas_works_for_overlapping_indices() {
+        let max_cred_num = 6;
+        let rev_idx = 6;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2), RevocationOp::Recover(1), RevocationOp::Revoke(3),
+                   RevocationOp::Revoke(1), RevocationOp::Recover(2), RevocationOp::Recover(3)];
+        let (initial_rev_reg, _, deltas, tails_accessor) = _revocation_registry_deltas(max_cred_num, true, &ops);
+        let deltas = _chunk_deltas(&deltas, &[2, 2, 1, 2]);
+        assert_eq!(hashset![1, 2], deltas[0].revoked);
+        assert_eq!(hashset![1], deltas[1].issued);
+        assert_eq!(hashset![1], deltas[2].revoked);
+
+        let tails_accessor = CountingTailsAccessor { tails_accessor, reads: RefCell::new(HashMap::new()) };
+        let (witness_by_deltas, witness_at_once) = _witnesses(rev_idx, max_cred_num, &initial_rev_reg, &deltas, &tails_accessor);
+
+        assert_eq!(_omega_bytes(&witness_by_deltas), _omega_bytes(&witness_at_once));
+        assert_eq!(hashmap![max_cred_num + 1 - 1 + rev_idx => 1], *tails_accessor.reads.borrow());
+    }
+
+    #[test]
+    fn witness_update_with_deltas_works_for_random_deltas() {
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+
+        let max_cred_num = 8;
+        let rev_idx = 8;
+
+        for seed in 0..8 {
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            let mut revoked = HashSet::new();
+            let ops: Vec<RevocationOp> = (0..20)
+                .map(|_| {
+                    let index = rng.gen_range(1, rev_idx);
+                    if revoked.remove(&index) {
+                        RevocationOp::Recover(index)
+                    } else {
+                        revoked.insert(index);
+                        RevocationOp::Revoke(index)
+                    }
+                })
+                .collect();
+            let lens: Vec<usize> = (0..5).map(|_| rng.gen_range(1, 4)).collect();
+
+            let (initial_rev_reg, _, deltas, tails_accessor) = _revocation_registry_deltas(max_cred_num, true, &ops);
+            let deltas = _chunk_deltas(&deltas, &lens);
+
+            let tails_accessor = CountingTailsAccessor { tails_accessor, reads: RefCell::new(HashMap::new()) };
+            let (witness_by_deltas, witness_at_once) = _witnesses(rev_idx, max_cred_num, &initial_rev_reg, &deltas, &tails_accessor);
+
+            assert_eq!(_omega_bytes(&witness_by_deltas), _omega_bytes(&witness_at_once), "seed {}", seed);
+            assert_eq!(revoked.len(), tails_accessor.reads.borrow().len(), "seed {}", seed);
+            assert!(tails_accessor.reads.borrow().values().all(|reads| *reads == 1), "seed {}", seed);
+        }
+    }
+
+    // Supplies tails that updater requires in an order shuffled by seed
+    fn _supply_shuffled(mut witness_updater: WitnessUpdater, tails_accessor: &SimpleTailsAccessor, seed: u64) -> Witness {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+
+        let mut indices = witness_updater.required_indices();
+        indices.shuffle(&mut StdRng::seed_from_u64(seed));
+        for index in indices {
+            witness_updater.supply_entry(index, &tails_accessor.tails[index as usize].to_bytes().unwrap()).unwrap();
+        }
+        assert_eq!(None, witness_updater.next_required_index());
+        witness_updater.finish().unwrap()
+    }
+
+    #[test]
+    fn witness_updater_works() {
+        let max_cred_num = 8;
+        let rev_idx = 3;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(5), RevocationOp::Revoke(6), RevocationOp::Recover(5),
+                   RevocationOp::Revoke(2), RevocationOp::Revoke(8)];
+        let (initial_rev_reg, _, deltas, tails_accessor) = _revocation_registry_deltas(max_cred_num, true, &ops);
+        let initial_delta = RevocationRegistryDelta::from_parts(None, &initial_rev_reg, &HashSet::new(), &HashSet::new());
+
+        for seed in 0..4 {
+            let expected = Witness::new(rev_idx, max_cred_num, true, &_merge_all(&deltas), &tails_accessor).unwrap();
+            let witness_updater = WitnessUpdater::new(rev_idx, max_cred_num, true, &_merge_all(&deltas)).unwrap();
+            assert_eq!(3, witness_updater.required_indices().len());
+            assert_eq!(_omega_bytes(&expected), _omega_bytes(&_supply_shuffled(witness_updater, &tails_accessor, seed)));
+
+            let mut expected = Witness::new(rev_idx, max_cred_num, true, &initial_delta, &tails_accessor).unwrap();
+            let mut witness = expected.clone();
+            for delta in deltas.iter() {
+                expected.update(rev_idx, max_cred_num, delta, &tails_accessor).unwrap();
+                let witness_updater = WitnessUpdater::new_for_update(&witness, rev_idx, max_cred_num, delta).unwrap();
+                witness = _supply_shuffled(witness_updater, &tails_accessor, seed);
+                assert_eq!(_omega_bytes(&expected), _omega_bytes(&witness), "seed {}", seed);
+            }
+        }
+
+        // Tails of credentials both issued and revoked by a delta cancel out
+        let mut expected = Witness::new(rev_idx, max_cred_num, true, &initial_delta, &tails_accessor).unwrap();
+        let delta = RevocationRegistryDelta::from_parts(None, &initial_rev_reg, &hashset![4, 5], &hashset![5, 6]);
+        let witness_updater = WitnessUpdater::new_for_update(&expected, rev_idx, max_cred_num, &delta).unwrap();
+        assert_eq!(vec![max_cred_num + 1 - 6 + rev_idx, max_cred_num + 1 - 4 + rev_idx], witness_updater.required_indices());
+
+        let witness = _supply_shuffled(witness_updater, &tails_accessor, 0);
+        expected.update(rev_idx, max_cred_num, &delta, &tails_accessor).unwrap();
+        assert_eq!(_omega_bytes(&expected), _omega_bytes(&witness));
+    }
+
+    #[test]
+    fn witness_updater_works_for_missing_and_unexpected_tails() {
+        let max_cred_num = 5;
+        let rev_idx = 5;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2)];
+        let (initial_rev_reg, _, deltas, tails_accessor) = _revocation_registry_deltas(max_cred_num, true, &ops);
+        let tail_bytes = |index: u32| tails_accessor.tails[index as usize].to_bytes().unwrap();
+
+        let mut witness_updater = WitnessUpdater::new(rev_idx, max_cred_num, true, &_merge_all(&deltas)).unwrap();
+        assert_eq!(vec![7, 8], witness_updater.required_indices());
+
+        assert_eq!(ErrorKind::InvalidStructure, witness_updater.supply_entry(1, &tail_bytes(1)).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidPoint, witness_updater.supply_entry(7, &tail_bytes(7)[1..]).unwrap_err().kind());
+        assert_eq!(Some(7), witness_updater.next_required_index());
+
+        witness_updater.supply_entry(7, &tail_bytes(7)).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, witness_updater.supply_entry(7, &tail_bytes(7)).unwrap_err().kind());
+        assert_eq!(Some(8), witness_updater.next_required_index());
+        assert_eq!(ErrorKind::InvalidStructure, witness_updater.finish().unwrap_err().kind());
+
+        let delta = RevocationRegistryDelta::from_parts(None, &initial_rev_reg, &hashset![max_cred_num + 1], &HashSet::new());
+        assert_eq!(ErrorKind::InvalidStructure, WitnessUpdater::new(rev_idx, max_cred_num, false, &delta).unwrap_err().kind());
+    }
+
+    #[test]
+    fn witness_update_with_deltas_works_for_invalid_deltas() {
+        let max_cred_num = 5;
+        let rev_idx = 5;
+        let ops = [RevocationOp::Revoke(1), RevocationOp::Revoke(2), RevocationOp::Revoke(3)];
+        let (initial_rev_reg, _, deltas, tails_accessor) = _revocation_registry_deltas(max_cred_num, true, &ops);
+
+        let initial_delta = RevocationRegistryDelta::from_parts(None, &initial_rev_reg, &HashSet::new(), &HashSet::new());
+        let mut witness = Witness::new(rev_idx, max_cred_num, true, &initial_delta, &tails_accessor).unwrap();
+        let omega_bytes = _omega_bytes(&witness);
+
+        witness.update_with_deltas(rev_idx, max_cred_num, &[], &tails_accessor).unwrap();
+        assert_eq!(omega_bytes, _omega_bytes(&witness));
+
+        let discontinuous = [deltas[0].clone(), deltas[2].clone()];
+        assert!(witness.update_with_deltas(rev_idx, max_cred_num, &discontinuous, &tails_accessor).is_err());
+        assert_eq!(omega_bytes, _omega_bytes(&witness));
     }
 }
@@ -76,7 +76,7 @@ impl Prover {
                                                       credential_nonce: {:?}",
                credential_pub_key,
                credential_key_correctness_proof,
-               credential_values,
+               secret!(credential_values),
                credential_nonce
         );
         Prover::_check_credential_key_correctness_proof(&credential_pub_key.p_key, credential_key_correctness_proof)?;
@@ -201,7 +201,7 @@ impl Prover {
                                                           rev_reg: {:?}, \
                                                           witness: {:?}",
                credential_signature,
-               credential_values,
+               secret!(credential_values),
                signature_correctness_proof,
                credential_secrets_blinding_factors,
                credential_pub_key,
@@ -338,7 +338,7 @@ impl Prover {
                                                             credential_values: &CredentialValues) -> Result<PrimaryBlindedCredentialSecretsFactors, IndyCryptoError> {
         trace!("Prover::_generate_blinded_primary_credential_secrets_factors: >>> p_pub_key: {:?}, credential_values: {:?}",
                p_pub_key,
-               credential_values
+               secret!(credential_values)
         );
 
         let mut ctx = BigNumber::new_context()?;
@@ -358,7 +358,7 @@ impl Prover {
                 Some(&mut ctx),
             ),
             |acc, attr| {
-                let pk_r = p_pub_key.r.get(&attr.clone()).ok_or(
+                let pk_r = p_pub_key.r.get(&attr.clone()).ok_or_else(||
                     IndyCryptoError::InvalidStructure(
                         format!("Value by key '{}' not found in pk.r", attr),
                     ),
@@ -431,11 +431,11 @@ impl Prover {
                blinded_primary_credential_secrets,
                nonce,
                p_pub_key,
-               credential_values);
+               secret!(credential_values));
 
         let mut ctx = BigNumber::new_context()?;
 
-        let v_dash_tilde = bn_rand(LARGE_VPRIME_TILDE)?;
+        let mut v_dash_tilde = bn_rand(LARGE_VPRIME_TILDE)?;
 
         let mut m_tildes = BTreeMap::new();
         let mut r_tildes = BTreeMap::new();
@@ -451,7 +451,7 @@ impl Prover {
             .iter()
             .filter(|&(_, v)| v.is_hidden() || v.is_commitment()) {
             let m_tilde = bn_rand(LARGE_MTILDE)?;
-            let pk_r = p_pub_key.r.get(attr).ok_or(
+            let pk_r = p_pub_key.r.get(attr).ok_or_else(||
                 IndyCryptoError::InvalidStructure(
                     format!(
                         "Value by key '{}' not found in pk.r",
@@ -484,7 +484,7 @@ impl Prover {
                     values.extend_from_slice(&commitment_tilde.to_bytes()?);
                     let ca_value = blinded_primary_credential_secrets.committed_attributes
                         .get(attr)
-                        .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in primary_blinded_cred_secrets.committed_attributes", attr)))?;
+                        .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in primary_blinded_cred_secrets.committed_attributes", attr)))?;
                     values.extend_from_slice(&ca_value.to_bytes()?);
                     ()
                 }
@@ -506,7 +506,7 @@ impl Prover {
         let mut r_caps = BTreeMap::new();
 
         for (attr, m_tilde) in &m_tildes {
-            let ca = credential_values.attrs_values.get(attr).ok_or(
+            let ca = credential_values.attrs_values.get(attr).ok_or_else(||
                 IndyCryptoError::InvalidStructure(format!(
                     "Value by key '{}' not found in cred_values.committed_attributes",
                     attr
@@ -515,7 +515,10 @@ impl Prover {
 
             match ca {
                 &CredentialValue::Hidden { ref value } => {
-                    let m_cap = m_tilde.add(&c.mul(value, Some(&mut ctx))?)?;
+                    let mut c_value = c.mul(value, Some(&mut ctx))?;
+                    let m_cap = m_tilde.add(&c_value)?;
+                    c_value.zeroize();
+
                     m_caps.insert(attr.clone(), m_cap);
                     ()
                 }
@@ -523,8 +526,12 @@ impl Prover {
                     ref value,
                     ref blinding_factor,
                 } => {
-                    let m_cap = m_tilde.add(&c.mul(value, Some(&mut ctx))?)?;
-                    let r_cap = r_tildes[attr].add(&c.mul(blinding_factor, Some(&mut ctx))?)?;
+                    let mut c_value = c.mul(value, Some(&mut ctx))?;
+                    let mut c_blinding_factor = c.mul(blinding_factor, Some(&mut ctx))?;
+                    let m_cap = m_tilde.add(&c_value)?;
+                    let r_cap = r_tildes[attr].add(&c_blinding_factor)?;
+                    c_value.zeroize();
+                    c_blinding_factor.zeroize();
 
                     m_caps.insert(attr.clone(), m_cap);
                     r_caps.insert(attr.clone(), r_cap);
@@ -534,6 +541,11 @@ impl Prover {
             }
         }
 
+        // Knowing randomness of the proof reveals the hidden values from the caps
+        v_dash_tilde.zeroize();
+        m_tildes.values_mut().for_each(BigNumber::zeroize);
+        r_tildes.values_mut().for_each(BigNumber::zeroize);
+
         let blinded_credential_secrets_correctness_proof =
             BlindedCredentialSecretsCorrectnessProof {
                 c,
@@ -725,6 +737,43 @@ impl ProofBuilder {
         self.common_attributes.insert(attr_name.to_owned(), bn_rand(LARGE_MVECT)?);
         Ok(())
     }
+
+    /// Links already added sub proofs with `sub_proof_indices` (in order of adding) by the hidden value
+    /// of `attr_name`, usually the link secret, as `ProofVerifier::add_linked_sub_proofs` requires.
+    ///
+    /// The attribute must be added by `add_common_attribute`, so the sub proofs share its m_tilde and
+    /// their responses are equal only for equal values. Returns `InvalidStructure` if it isn't common,
+    /// isn't hidden in any of the sub proofs or the credentials have different values of it,
+    /// as the verifier would reject the proof.
+    pub fn add_linked_sub_proofs(&mut self, attr_name: &str, sub_proof_indices: &[usize]) -> Result<(), IndyCryptoError> {
+        trace!("ProofBuilder::add_linked_sub_proofs: >>> attr_name: {:?}, sub_proof_indices: {:?}", attr_name, sub_proof_indices);
+
+        check_linked_sub_proof_indices(self.init_proofs.len(), sub_proof_indices)?;
+
+        if !self.common_attributes.contains_key(attr_name) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Linked attribute '{}' isn't added as common attribute", attr_name)));
+        }
+
+        let mut values = Vec::with_capacity(sub_proof_indices.len());
+        for &idx in sub_proof_indices {
+            let init_proof = &self.init_proofs[idx];
+            let value = init_proof.credential_values.attrs_values.get(attr_name)
+                .filter(|_| !init_proof.sub_proof_request.revealed_attrs.contains(attr_name))
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                    format!("Linked attribute '{}' isn't hidden attribute of sub proof {}", attr_name, idx)))?;
+            values.push((idx, value.value()));
+        }
+
+        if let Some(&(idx, _)) = values.iter().find(|&&(_, value)| value != values[0].1) {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Sub proofs {} and {} don't share the value of '{}'", values[0].0, idx, attr_name)));
+        }
+
+        trace!("ProofBuilder::add_linked_sub_proofs: <<<");
+
+        Ok(())
+    }
     /// Adds sub proof request to proof builder which will be used fo building of proof.
     /// Part of proof request related to a particular schema-key.
     /// The order of sub-proofs is important: both Prover and Verifier should use the same order.
@@ -833,6 +882,8 @@ impl ProofBuilder {
             non_credential_schema,
         )?;
 
+        let shared_m_tildes = self._shared_m_tildes(sub_proof_request, credential_values)?;
+
         let mut non_revoc_init_proof = None;
         let mut m2_tilde: Option<BigNumber> = None;
 
@@ -851,7 +902,7 @@ impl ProofBuilder {
             non_revoc_init_proof = Some(proof);
         }
 
-        let primary_init_proof = ProofBuilder::_init_primary_proof(&self.common_attributes,
+        let primary_init_proof = ProofBuilder::_init_primary_proof(&shared_m_tildes,
                                                                    &credential_pub_key.p_key,
                                                                    &credential_signature.p_credential,
                                                                    credential_values,
@@ -980,7 +1031,8 @@ impl ProofBuilder {
                 &init_proof.sub_proof_request,
             )?;
 
-            let proof = SubProof { primary_proof, non_revoc_proof };
+            let equality_predicates = init_proof.sub_proof_request.equality_predicates.clone();
+            let proof = SubProof { primary_proof, non_revoc_proof, equality_predicates };
             proofs.push(proof);
         }
 
@@ -993,6 +1045,50 @@ impl ProofBuilder {
         Ok(proof)
     }
 
+    // m_tildes shared with other sub proofs: of common attributes and of attributes of equality predicates,
+    // that take m_tilde of the attribute of the earlier sub proof
+    fn _shared_m_tildes(&self,
+                        sub_proof_request: &SubProofRequest,
+                        credential_values: &CredentialValues) -> Result<HashMap<String, BigNumber>, IndyCryptoError> {
+        let idx = self.init_proofs.len();
+        let mut m_tildes = clone_bignum_map(&self.common_attributes)?;
+
+        for predicate in sub_proof_request.equality_predicates.iter() {
+            let other = self.init_proofs.get(predicate.other_sub_proof_idx)
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                    format!("Sub proof {} of equality predicate isn't added before sub proof {}", predicate.other_sub_proof_idx, idx)))?;
+
+            let value = credential_values.attrs_values.get(&predicate.attr_name)
+                .filter(|_| !sub_proof_request.revealed_attrs.contains(&predicate.attr_name))
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                    format!("Attribute '{}' of equality predicate isn't hidden attribute of sub proof {}", predicate.attr_name, idx)))?;
+            let other_value = other.credential_values.attrs_values.get(&predicate.other_attr_name)
+                .filter(|_| !other.sub_proof_request.revealed_attrs.contains(&predicate.other_attr_name))
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                    format!("Attribute '{}' of equality predicate isn't hidden attribute of sub proof {}", predicate.other_attr_name, predicate.other_sub_proof_idx)))?;
+
+            if value.value() != other_value.value() {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Attribute '{}' of sub proof {} isn't equal to attribute '{}' of sub proof {}",
+                            predicate.attr_name, idx, predicate.other_attr_name, predicate.other_sub_proof_idx)));
+            }
+
+            let other_m_tilde = other.primary_init_proof.eq_proof.m_tilde.get(&predicate.other_attr_name)
+                .ok_or_else(|| IndyCryptoError::InvalidState(format!("m_tilde of '{}' not found", predicate.other_attr_name)))?;
+
+            match m_tildes.get(&predicate.attr_name) {
+                Some(m_tilde) if m_tilde != other_m_tilde =>
+                    return Err(IndyCryptoError::InvalidStructure(
+                        format!("Attribute '{}' of sub proof {} is common attribute or is equal to attribute of other sub proof already",
+                                predicate.attr_name, idx))),
+                Some(_) => {}
+                None => { m_tildes.insert(predicate.attr_name.clone(), other_m_tilde.clone()?); }
+            }
+        }
+
+        Ok(m_tildes)
+    }
+
     fn _check_add_sub_proof_request_params_consistency(
         cred_values: &CredentialValues,
         sub_proof_request: &SubProofRequest,
@@ -1101,6 +1197,7 @@ impl ProofBuilder {
 
         let tau_list_params = ProofBuilder::_gen_tau_list_params()?;
         let tau_list = create_tau_list_values(&cred_rev_pub_key,
+                                              &RevocationPublicKeyPairings::new(cred_rev_pub_key)?,
                                               &rev_reg,
                                               &tau_list_params,
                                               &c_list)?;
@@ -1187,11 +1284,12 @@ impl ProofBuilder {
         let mut ctx = BigNumber::new_context()?;
 
         let attr_value = cred_values.attrs_values.get(&predicate.attr_name)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", predicate.attr_name)))?
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in cred_values", predicate.attr_name)))?
             .value()
             .to_dec()?
             .parse::<i32>()
-            .map_err(|_| IndyCryptoError::InvalidStructure(format!("Value by key '{}' has invalid format", predicate.attr_name)))?;
+            .map_err(|_| IndyCryptoError::InvalidStructure(
+                format!("Value by key '{}' isn't integer within i32 range of predicate values", predicate.attr_name)))?;
 
         let delta = predicate.get_delta(attr_value);
 
@@ -1207,7 +1305,7 @@ impl ProofBuilder {
 
         for i in 0..ITERATION {
             let cur_u = u.get(&i.to_string())
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
 
             let cur_r = bn_rand(LARGE_VPRIME)?;
             let cut_t = get_pedersen_commitment(&p_pub_key.z, &cur_u, &p_pub_key.s,
@@ -1239,7 +1337,7 @@ impl ProofBuilder {
         let alpha_tilde = bn_rand(LARGE_ALPHATILDE)?;
 
         let mj = m_tilde.get(&predicate.attr_name)
-            .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.mtilde", predicate.attr_name)))?;
+            .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in eq_proof.mtilde", predicate.attr_name)))?;
 
         let tau_list = calc_tne(&p_pub_key, &u_tilde, &r_tilde, &mj, &alpha_tilde, &t, predicate.is_less())?;
 
@@ -1299,10 +1397,10 @@ impl ProofBuilder {
 
         for k in unrevealed_attrs.iter() {
             let cur_mtilde = init_proof.m_tilde.get(k)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in init_proof.mtilde", k)))?;
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in init_proof.mtilde", k)))?;
 
             let cur_val = cred_values.attrs_values.get(k)
-                .ok_or(IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in attributes_values", k)))?;
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Value by key '{}' not found in attributes_values", k)))?;
 
             let val = challenge
                 .mul(&cur_val.value(), Some(&mut ctx))?
@@ -1322,7 +1420,7 @@ impl ProofBuilder {
                 attr.clone(),
                 cred_values.attrs_values
                     .get(attr)
-                    .ok_or(IndyCryptoError::InvalidStructure(format!("Encoded value not found")))?
+                    .ok_or_else(|| IndyCryptoError::InvalidStructure(format!("Encoded value not found")))?
                     .value()
                     .clone()?,
             );
@@ -1638,6 +1736,41 @@ mod tests {
         assert_eq!(ms.ms.to_dec().unwrap(), mocks::master_secret().ms.to_dec().unwrap());
     }
 
+    #[test]
+    fn master_secret_wipe_works() {
+        let mut ms = mocks::master_secret();
+        let ms_clone = ms.clone().unwrap();
+        let value = ms.value().unwrap();
+
+        ms.wipe();
+
+        assert_eq!(BigNumber::from_u32(0).unwrap(), ms.value().unwrap());
+        assert_eq!(json!({"ms": "0"}), serde_json::to_value(&ms).unwrap());
+
+        // Clones and copies of the value are independent of the wiped master secret
+        assert_eq!(mocks::master_secret().ms, ms_clone.value().unwrap());
+        assert_eq!(mocks::master_secret().ms, value);
+
+        ms.wipe();
+        assert_eq!(BigNumber::from_u32(0).unwrap(), ms.value().unwrap());
+    }
+
+    #[test]
+    fn master_secret_debug_hides_value() {
+        let ms = mocks::master_secret();
+        let dec = ms.ms.to_dec().unwrap();
+        let hex = ms.ms.to_hex().unwrap();
+
+        let debug = format!("{:?}", ms);
+        let debug_pretty = format!("{:#?}", ms);
+
+        assert_eq!("MasterSecret { ms: \"_\" }", debug);
+        for output in &[debug, debug_pretty] {
+            assert!(!output.contains(&dec));
+            assert!(!output.to_lowercase().contains(&hex.to_lowercase()));
+        }
+    }
+
     #[test]
     fn generate_blinded_primary_credential_secrets_works() {
         MockHelper::inject();
@@ -2114,7 +2247,8 @@ pub mod mocks {
     pub fn subproof() -> SubProof {
         SubProof {
             primary_proof: primary_proof(),
-            non_revoc_proof: Some(non_revoc_proof())
+            non_revoc_proof: Some(non_revoc_proof()),
+            equality_predicates: BTreeSet::new()
         }
     }
 
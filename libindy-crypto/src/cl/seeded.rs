@@ -0,0 +1,279 @@
+//! Deterministic randomness of issuer keys derived from a seed, for tests and development only.
+//!
+//! The stream of random bytes is SHA-256 in counter mode:
+//! `key = SHA-256("indy-crypto seeded issuer keys v1" || seed)`, then block `i` of the stream is
+//! `SHA-256(key || i)` with `i` as 8 bytes big-endian, blocks are concatenated and every value takes
+//! the following bytes of the stream:
+//! * number of `n` bits takes `ceil(n / 8)` bytes read big-endian, the extra high bits are cleared;
+//! * number in range `[0, m)` is a number of `bits(m) + 64` bits reduced modulo `m`;
+//! * safe prime `2q + 1` of `n` bits is searched from a number `q` of `n - 1` bits with the highest
+//!   and the lowest bits set, candidates `q, q + 2, q + 4, ...` not divisible by small primes
+//!   (as `2q + 1` too) are tested for primality; a new `q` is drawn if the candidates run out;
+//! * quadratic residue is a square of a number in range `[0, n)` modulo `n`;
+//! * group order element is `GroupOrderElement::new_from_seed` of 32 bytes, point of G1 is
+//!   `PointG1::map_to_curve` of 32 bytes, point of G2 is the generator multiplied by group order element.
+//!
+//! All arithmetic is of big numbers, so the same seed gives the same keys on all platforms.
+use crate::bn::{BigNumber, BigNumberContext};
+use crate::errors::IndyCryptoError;
+use crate::pair::{GroupOrderElement, PointG1, PointG2};
+use crate::utils::zeroize::zeroize;
+use super::helpers::KeyGenRandomness;
+
+use sha2::{Digest, Sha256};
+
+const DOMAIN: &[u8] = b"indy-crypto seeded issuer keys v1";
+
+/// Minimal length of seed, shorter seeds could be guessed.
+pub const MIN_SEED_LEN: usize = 32;
+
+// Odd primes below the bound filter the candidates of safe prime before primality tests
+const SMALL_PRIMES_BOUND: u32 = 1 << 14;
+
+// Number of candidates of safe prime searched from one random start
+const SAFE_PRIME_SEARCH_STEPS: u32 = 1 << 16;
+
+pub struct SeededRandomness {
+    key: Vec<u8>,
+    counter: u64,
+    buffer: Vec<u8>
+}
+
+impl SeededRandomness {
+    pub fn new(seed: &[u8]) -> Result<SeededRandomness, IndyCryptoError> {
+        if seed.len() < MIN_SEED_LEN {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Seed is too short: expected {} bytes at least, actual {}", MIN_SEED_LEN, seed.len())));
+        }
+
+        let mut hasher = Sha256::default();
+        hasher.input(DOMAIN);
+        hasher.input(seed);
+
+        Ok(SeededRandomness {
+            key: hasher.result().to_vec(),
+            counter: 0,
+            buffer: Vec::new()
+        })
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        while self.buffer.len() < len {
+            let mut hasher = Sha256::default();
+            hasher.input(&self.key);
+            hasher.input(&self.counter.to_be_bytes());
+            self.buffer.extend_from_slice(&hasher.result());
+            self.counter += 1;
+        }
+
+        let rest = self.buffer.split_off(len);
+        ::std::mem::replace(&mut self.buffer, rest)
+    }
+
+    fn bn(&mut self, bits: usize) -> Result<BigNumber, IndyCryptoError> {
+        let mut bytes = self.bytes(bits.div_ceil(8));
+        let extra_bits = bytes.len() * 8 - bits;
+        if let Some(first) = bytes.first_mut() {
+            *first &= 0xff >> extra_bits;
+        }
+        let bn = BigNumber::from_bytes(&bytes);
+        zeroize(&mut bytes);
+        bn
+    }
+
+    fn bn_range(&mut self, bound: &BigNumber, ctx: &mut BigNumberContext) -> Result<BigNumber, IndyCryptoError> {
+        let mut bn = self.bn(bound.num_bits()? as usize + 64)?;
+        let res = bn.modulus(bound, Some(ctx));
+        bn.zeroize();
+        res
+    }
+}
+
+impl Drop for SeededRandomness {
+    fn drop(&mut self) {
+        zeroize(&mut self.key);
+        zeroize(&mut self.buffer);
+    }
+}
+
+impl KeyGenRandomness for SeededRandomness {
+    fn safe_prime(&mut self, size: usize,
+                  on_candidate: &mut dyn FnMut() -> Result<(), IndyCryptoError>) -> Result<BigNumber, IndyCryptoError> {
+        trace!("SeededRandomness::safe_prime: >>> size: {:?}", size);
+
+        let mut ctx = BigNumber::new_context()?;
+        let small_primes = _small_primes(SMALL_PRIMES_BOUND);
+
+        loop {
+            let mut start_bytes = self.bytes((size - 1).div_ceil(8));
+            let top_bit = (size - 2) % 8;
+            start_bytes[0] = (start_bytes[0] & (0xff >> (7 - top_bit))) | (1 << top_bit);
+            *start_bytes.last_mut().unwrap() |= 1;
+
+            let residues = small_primes.iter()
+                .map(|prime| start_bytes.iter().fold(0u64, |r, byte| (r * 256 + *byte as u64) % *prime as u64))
+                .collect::<Vec<u64>>();
+            let start = BigNumber::from_bytes(&start_bytes)?;
+            zeroize(&mut start_bytes);
+
+            for step in 0..SAFE_PRIME_SEARCH_STEPS {
+                let delta = 2 * step as u64;
+                let divisible = small_primes.iter().zip(residues.iter())
+                    .any(|(prime, r)| (r + delta).is_multiple_of(*prime as u64) || (2 * (r + delta) + 1).is_multiple_of(*prime as u64));
+                if divisible {
+                    continue;
+                }
+
+                on_candidate()?;
+
+                let mut q = start.clone()?;
+                q.add_word(delta as u32)?;
+                if q.num_bits()? as usize != size - 1 {
+                    break;
+                }
+
+                if q.is_prime(Some(&mut ctx))? {
+                    let mut p = q.lshift1()?;
+                    p.add_word(1)?;
+                    if p.is_prime(Some(&mut ctx))? {
+                        trace!("SeededRandomness::safe_prime: <<< safe_prime: {:?}", secret!(&p));
+                        return Ok(p);
+                    }
+                }
+            }
+        }
+    }
+
+    fn random_qr(&mut self, n: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let mut x = self.bn_range(n, &mut ctx)?;
+        let qr = x.sqr(Some(&mut ctx))?.modulus(n, Some(&mut ctx));
+        x.zeroize();
+        qr
+    }
+
+    fn gen_x(&mut self, p: &BigNumber, q: &BigNumber) -> Result<BigNumber, IndyCryptoError> {
+        let mut ctx = BigNumber::new_context()?;
+        let mut bound = p.mul(q, Some(&mut ctx))?;
+        bound.sub_word(3)?;
+        let mut x = self.bn_range(&bound, &mut ctx)?;
+        x.add_word(2)?;
+        Ok(x)
+    }
+
+    fn group_order_element(&mut self) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut seed = self.bytes(GroupOrderElement::BYTES_REPR_SIZE);
+        let res = GroupOrderElement::new_from_seed(&seed);
+        zeroize(&mut seed);
+        res
+    }
+
+    fn point_g1(&mut self) -> Result<PointG1, IndyCryptoError> {
+        PointG1::map_to_curve(&self.bytes(32))
+    }
+
+    fn point_g2(&mut self) -> Result<PointG2, IndyCryptoError> {
+        PointG2::base().mul(&self.group_order_element()?)
+    }
+}
+
+// Odd primes below the bound by sieve of Eratosthenes
+fn _small_primes(bound: u32) -> Vec<u32> {
+    let mut composite = vec![false; bound as usize];
+    let mut primes = Vec::new();
+    for i in (3..bound as usize).step_by(2) {
+        if composite[i] {
+            continue;
+        }
+        primes.push(i as u32);
+        for j in (i * i..bound as usize).step_by(i) {
+            composite[j] = true;
+        }
+    }
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorKind;
+
+    const SEED: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn new_works_for_short_seed() {
+        assert_eq!(ErrorKind::InvalidStructure, SeededRandomness::new(&SEED[..MIN_SEED_LEN - 1]).err().unwrap().kind());
+    }
+
+    #[test]
+    fn bytes_works() {
+        let mut rng = SeededRandomness::new(&SEED).unwrap();
+        let stream = rng.bytes(100);
+
+        // the stream is the same whatever lengths it is read by
+        let mut other = SeededRandomness::new(&SEED).unwrap();
+        let mut parts = other.bytes(1);
+        parts.extend(other.bytes(32));
+        parts.extend(other.bytes(67));
+        assert_eq!(stream, parts);
+
+        let mut hasher = Sha256::default();
+        hasher.input(DOMAIN);
+        hasher.input(&SEED);
+        let key = hasher.result();
+        let mut hasher = Sha256::default();
+        hasher.input(&key);
+        hasher.input(&[0u8; 8]);
+        assert_eq!(hasher.result().as_slice(), &stream[..32]);
+
+        let mut seed = SEED;
+        seed[31] = 8;
+        assert_ne!(stream, SeededRandomness::new(&seed).unwrap().bytes(100));
+    }
+
+    #[test]
+    fn bn_works() {
+        let mut rng = SeededRandomness::new(&SEED).unwrap();
+        for bits in 1..100 {
+            assert!(rng.bn(bits).unwrap().num_bits().unwrap() as usize <= bits);
+        }
+    }
+
+    #[test]
+    fn safe_prime_works() {
+        let mut candidates = 0;
+        let prime = SeededRandomness::new(&SEED).unwrap()
+            .safe_prime(256, &mut || { candidates += 1; Ok(()) }).unwrap();
+
+        assert_eq!(256, prime.num_bits().unwrap());
+        assert!(prime.is_safe_prime(None).unwrap());
+        assert!(candidates > 0);
+
+        let same_prime = SeededRandomness::new(&SEED).unwrap().safe_prime(256, &mut || Ok(())).unwrap();
+        assert_eq!(prime, same_prime);
+    }
+
+    #[test]
+    fn safe_prime_works_for_cancelled_search() {
+        let err = SeededRandomness::new(&SEED).unwrap()
+            .safe_prime(256, &mut || Err(IndyCryptoError::Cancelled("cancelled".to_string()))).unwrap_err();
+        assert_eq!(ErrorKind::Cancelled, err.kind());
+    }
+
+    #[test]
+    fn gen_x_works() {
+        let p = BigNumber::from_u32(1009).unwrap();
+        let q = BigNumber::from_u32(1013).unwrap();
+        let bound = BigNumber::from_u32(1009 * 1013 - 1).unwrap();
+        let mut rng = SeededRandomness::new(&SEED).unwrap();
+        for _ in 0..100 {
+            let x = rng.gen_x(&p, &q).unwrap();
+            assert!(x >= BigNumber::from_u32(2).unwrap() && x < bound);
+        }
+    }
+
+    #[test]
+    fn small_primes_works() {
+        assert_eq!(vec![3, 5, 7, 11, 13, 17, 19, 23, 29], _small_primes(30));
+    }
+}
@@ -0,0 +1,392 @@
+//! Container of tails with integrity protection, so tails can be distributed over untrusted file servers.
+//!
+//! Layout of the container, numbers are big-endian:
+//! * header: magic `TAIL`, version (u16), SHA-256 of the revocation registry id (32 bytes),
+//!   entry count (u32), chunk size (u32);
+//! * entries: `entry count` tails in index order, `Tail::BYTES_REPR_SIZE` bytes each;
+//! * chunk hashes: hash of every `chunk size` entries (the last chunk may be shorter), 32 bytes each.
+//!
+//! Chunk hashes are leaves of a binary hash tree, the root of the container hashes the header and the root
+//! of the tree. `TailsWriter::finalize` returns the root to anchor it (e.g. on the ledger) together with
+//! the registry, `TailsReader` checks the container against the root and every accessed entry against its chunk hash.
+use crate::cl::{RevocationTailsAccessor, RevocationTailsGenerator, Tail};
+use crate::errors::IndyCryptoError;
+
+use sha2::{Digest, Sha256};
+
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+const MAGIC: &[u8] = b"TAIL";
+const VERSION: u16 = 1;
+const HASH_SIZE: usize = 32;
+const HEADER_SIZE: usize = 4 + 2 + HASH_SIZE + 4 + 4;
+
+// Domain separation of hashes of the tree
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+const ROOT_PREFIX: u8 = 2;
+
+/// Writer of tails container, that takes tails in index order and hashes them chunk by chunk.
+pub struct TailsWriter<W: Write> {
+    writer: W,
+    header: Vec<u8>,
+    entry_count: u32,
+    chunk_size: u32,
+    written: u32,
+    chunk: Sha256,
+    chunk_hashes: Vec<Vec<u8>>
+}
+
+impl<W: Write> TailsWriter<W> {
+    /// Writes header of container of `entry_count` tails of revocation registry `rev_reg_id`,
+    /// tails are hashed by chunks of `chunk_size`.
+    pub fn new(mut writer: W, rev_reg_id: &str, entry_count: u32, chunk_size: u32) -> Result<TailsWriter<W>, IndyCryptoError> {
+        if chunk_size == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Chunk size must be positive".to_string()));
+        }
+
+        let header = _header(rev_reg_id, entry_count, chunk_size);
+        writer.write_all(&header)?;
+
+        Ok(TailsWriter {
+            writer,
+            header,
+            entry_count,
+            chunk_size,
+            written: 0,
+            chunk: _leaf_hasher(),
+            chunk_hashes: Vec::new()
+        })
+    }
+
+    /// Writes the next tail.
+    pub fn add_tail(&mut self, tail: &Tail) -> Result<(), IndyCryptoError> {
+        if self.written == self.entry_count {
+            return Err(IndyCryptoError::InvalidStructure(format!("All {} tails are written", self.entry_count)));
+        }
+
+        let bytes = tail.to_bytes()?;
+        self.writer.write_all(&bytes)?;
+        self.chunk.input(&bytes);
+        self.written += 1;
+
+        if self.written.is_multiple_of(self.chunk_size) {
+            self._finish_chunk();
+        }
+        Ok(())
+    }
+
+    /// Writes all tails that `rev_tails_generator` has left, generated chunk by chunk.
+    pub fn add_tails(&mut self, rev_tails_generator: &mut RevocationTailsGenerator) -> Result<(), IndyCryptoError> {
+        for chunk in rev_tails_generator.chunks(self.chunk_size as usize) {
+            for tail in chunk? {
+                self.add_tail(&tail)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes chunk hashes after all tails, returns the writer and the root of the container.
+    pub fn finalize(mut self) -> Result<(W, Vec<u8>), IndyCryptoError> {
+        if self.written != self.entry_count {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("{} of {} tails are written", self.written, self.entry_count)));
+        }
+
+        if !self.written.is_multiple_of(self.chunk_size) {
+            self._finish_chunk();
+        }
+        for chunk_hash in &self.chunk_hashes {
+            self.writer.write_all(chunk_hash)?;
+        }
+        self.writer.flush()?;
+
+        let root = _root(&self.header, &self.chunk_hashes);
+        Ok((self.writer, root))
+    }
+
+    fn _finish_chunk(&mut self) {
+        let chunk = ::std::mem::replace(&mut self.chunk, _leaf_hasher());
+        self.chunk_hashes.push(chunk.result().to_vec());
+    }
+}
+
+/// Tails accessor over tails container, that checks every chunk of accessed tails against its hash
+/// before handing tails out. Mismatches are reported as `AnoncredsTailsIntegrityViolation`.
+pub struct TailsReader<R: Read + Seek> {
+    reader: RefCell<R>,
+    entry_count: u32,
+    chunk_size: u32,
+    chunk_hashes: Vec<Vec<u8>>,
+    // Last checked chunk, as witnesses take tails of neighbouring indices
+    chunk: RefCell<Option<(u32, Vec<Tail>)>>
+}
+
+impl<R: Read + Seek> TailsReader<R> {
+    /// Opens container of `entry_count` tails of revocation registry `rev_reg_id` and checks its header and
+    /// chunk hashes against `root` returned by `TailsWriter::finalize`.
+    ///
+    /// `entry_count` is the tails count of the registry (`RevocationTailsGenerator::count`), it is taken from
+    /// the caller instead of the untrusted header, so the header can't make the reader allocate more
+    /// than the registry needs.
+    pub fn open(mut reader: R, rev_reg_id: &str, entry_count: u32, root: &[u8]) -> Result<TailsReader<R>, IndyCryptoError> {
+        let mut header = vec![0u8; HEADER_SIZE];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC {
+            return Err(IndyCryptoError::InvalidStructure("Not a tails container".to_string()));
+        }
+        let version = u16::from_be_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(IndyCryptoError::InvalidStructure(format!("Unsupported version of tails container: {}", version)));
+        }
+
+        let chunk_size = _read_u32(&header[10 + HASH_SIZE..]);
+        if header != _header(rev_reg_id, entry_count, chunk_size) {
+            return Err(_integrity_violation(
+                format!("Tails don't belong to revocation registry '{}' of {} tails", rev_reg_id, entry_count)));
+        }
+        if chunk_size == 0 {
+            return Err(_integrity_violation("Chunk size is zero".to_string()));
+        }
+
+        let chunks_count = entry_count.div_ceil(chunk_size);
+        reader.seek(SeekFrom::Start(_entry_offset(entry_count)))?;
+        let chunk_hashes = (0..chunks_count)
+            .map(|_| {
+                let mut hash = vec![0u8; HASH_SIZE];
+                reader.read_exact(&mut hash)?;
+                Ok(hash)
+            })
+            .collect::<Result<Vec<Vec<u8>>, IndyCryptoError>>()?;
+
+        if _root(&header, &chunk_hashes) != root {
+            return Err(_integrity_violation("Tails container doesn't match the root".to_string()));
+        }
+
+        Ok(TailsReader {
+            reader: RefCell::new(reader),
+            entry_count,
+            chunk_size,
+            chunk_hashes,
+            chunk: RefCell::new(None)
+        })
+    }
+
+    pub fn entry_count(&self) -> u32 {
+        self.entry_count
+    }
+
+    fn _read_chunk(&self, chunk_idx: u32) -> Result<Vec<Tail>, IndyCryptoError> {
+        let first = chunk_idx * self.chunk_size;
+        let count = self.chunk_size.min(self.entry_count - first) as usize;
+
+        let mut bytes = vec![0u8; count * Tail::BYTES_REPR_SIZE];
+        let mut reader = self.reader.borrow_mut();
+        reader.seek(SeekFrom::Start(_entry_offset(first)))?;
+        reader.read_exact(&mut bytes)?;
+
+        let mut chunk = _leaf_hasher();
+        chunk.input(&bytes);
+        if chunk.result().as_slice() != self.chunk_hashes[chunk_idx as usize].as_slice() {
+            return Err(_integrity_violation(format!("Chunk {} of tails doesn't match its hash", chunk_idx)));
+        }
+
+        bytes.chunks(Tail::BYTES_REPR_SIZE).map(Tail::from_bytes).collect()
+    }
+}
+
+impl<R: Read + Seek> RevocationTailsAccessor for TailsReader<R> {
+    fn access_tail(&self, tail_id: u32, accessor: &mut dyn FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        if tail_id >= self.entry_count {
+            return Err(IndyCryptoError::InvalidStructure(
+                format!("Tail index is out of range: expected less than {}, actual {}", self.entry_count, tail_id)));
+        }
+
+        let chunk_idx = tail_id / self.chunk_size;
+        let tail = {
+            let mut chunk = self.chunk.borrow_mut();
+            let tails = match *chunk {
+                Some((idx, ref tails)) if idx == chunk_idx => tails,
+                _ => &chunk.insert((chunk_idx, self._read_chunk(chunk_idx)?)).1
+            };
+            tails[(tail_id % self.chunk_size) as usize]
+        };
+
+        accessor(&tail);
+        Ok(())
+    }
+}
+
+fn _header(rev_reg_id: &str, entry_count: u32, chunk_size: u32) -> Vec<u8> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&VERSION.to_be_bytes());
+    header.extend_from_slice(&Sha256::digest(rev_reg_id.as_bytes()));
+    header.extend_from_slice(&entry_count.to_be_bytes());
+    header.extend_from_slice(&chunk_size.to_be_bytes());
+    header
+}
+
+fn _read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn _entry_offset(tail_id: u32) -> u64 {
+    HEADER_SIZE as u64 + tail_id as u64 * Tail::BYTES_REPR_SIZE as u64
+}
+
+fn _leaf_hasher() -> Sha256 {
+    let mut hasher = Sha256::default();
+    hasher.input(&[LEAF_PREFIX]);
+    hasher
+}
+
+// Hash of the header and the root of the tree of chunk hashes, an odd node is lifted to the next level as is
+fn _root(header: &[u8], chunk_hashes: &[Vec<u8>]) -> Vec<u8> {
+    let mut level = chunk_hashes.to_vec();
+    while level.len() > 1 {
+        level = level.chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = Sha256::default();
+                    hasher.input(&[NODE_PREFIX]);
+                    hasher.input(left);
+                    hasher.input(right);
+                    hasher.result().to_vec()
+                }
+                _ => pair[0].clone()
+            })
+            .collect();
+    }
+
+    let mut hasher = Sha256::default();
+    hasher.input(&[ROOT_PREFIX]);
+    hasher.input(header);
+    hasher.input(level.first().map(Vec::as_slice).unwrap_or(&[]));
+    hasher.result().to_vec()
+}
+
+fn _integrity_violation(reason: String) -> IndyCryptoError {
+    IndyCryptoError::AnoncredsTailsIntegrityViolation(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cl::{issuer, SimpleTailsAccessor, Witness};
+    use crate::cl::issuer::Issuer;
+    use crate::errors::ErrorKind;
+
+    use std::io::Cursor;
+
+    const REV_REG_ID: &str = "rev_reg_id";
+    const CHUNK_SIZE: u32 = 4;
+
+    // Container of 11 tails of registry of 5 credentials, in 3 chunks
+    fn _container() -> (RevocationTailsGenerator, Vec<u8>, Vec<u8>) {
+        let (_, _, _, rev_tails_generator) = Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), 5, false).unwrap();
+
+        let mut writer = TailsWriter::new(Vec::new(), REV_REG_ID, rev_tails_generator.count(), CHUNK_SIZE).unwrap();
+        writer.add_tails(&mut rev_tails_generator.clone()).unwrap();
+        let (bytes, root) = writer.finalize().unwrap();
+        (rev_tails_generator, bytes, root)
+    }
+
+    fn _access(accessor: &dyn RevocationTailsAccessor, tail_id: u32) -> Result<Tail, IndyCryptoError> {
+        let mut accessed = None;
+        accessor.access_tail(tail_id, &mut |tail| accessed = Some(*tail))?;
+        Ok(accessed.unwrap())
+    }
+
+    fn _open(bytes: &[u8], root: &[u8]) -> Result<TailsReader<Cursor<Vec<u8>>>, IndyCryptoError> {
+        TailsReader::open(Cursor::new(bytes.to_vec()), REV_REG_ID, 11, root)
+    }
+
+    #[test]
+    fn tails_reader_works() {
+        let (mut rev_tails_generator, bytes, root) = _container();
+        assert_eq!(HEADER_SIZE + 11 * Tail::BYTES_REPR_SIZE + 3 * HASH_SIZE, bytes.len());
+        assert_eq!(HASH_SIZE, root.len());
+
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+        let tails_reader = _open(&bytes, &root).unwrap();
+        assert_eq!(11, tails_reader.entry_count());
+
+        for tail_id in [10, 0, 5, 4, 3, 7, 8, 1, 9, 2, 6] {
+            assert_eq!(_access(&simple_tail_accessor, tail_id).unwrap(), _access(&tails_reader, tail_id).unwrap());
+        }
+        assert_eq!(ErrorKind::InvalidStructure, _access(&tails_reader, 11).unwrap_err().kind());
+
+        let (_, _, mut rev_reg, _) = Issuer::new_revocation_registry_def(&issuer::mocks::credential_public_key(), 5, true).unwrap();
+        let rev_reg_delta = Issuer::revoke_credential(&mut rev_reg, 5, 2, &simple_tail_accessor).unwrap();
+        let expected = Witness::new(1, 5, true, &rev_reg_delta, &simple_tail_accessor).unwrap();
+        let witness = Witness::new(1, 5, true, &rev_reg_delta, &tails_reader).unwrap();
+        assert_eq!(expected.omega.to_bytes().unwrap(), witness.omega.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn tails_reader_works_for_tampered_entry() {
+        let (_, mut bytes, root) = _container();
+        // the last byte of tail 5, in the second chunk
+        bytes[HEADER_SIZE + 6 * Tail::BYTES_REPR_SIZE - 1] ^= 1;
+
+        // the header and chunk hashes are intact, so the container is opened
+        let tails_reader = _open(&bytes, &root).unwrap();
+        for tail_id in 4..8 {
+            assert_eq!(ErrorKind::AnoncredsTailsIntegrityViolation, _access(&tails_reader, tail_id).unwrap_err().kind());
+        }
+        for tail_id in (0..4).chain(8..11) {
+            _access(&tails_reader, tail_id).unwrap();
+        }
+    }
+
+    #[test]
+    fn tails_reader_works_for_tampered_container() {
+        let (_, bytes, root) = _container();
+
+        let tampered = |offset: usize| {
+            let mut bytes = bytes.clone();
+            bytes[offset] ^= 1;
+            _open(&bytes, &root).err().map(|err| err.kind())
+        };
+        assert_eq!(Some(ErrorKind::InvalidStructure), tampered(0));
+        assert_eq!(Some(ErrorKind::InvalidStructure), tampered(5));
+        assert_eq!(Some(ErrorKind::AnoncredsTailsIntegrityViolation), tampered(6));
+        assert_eq!(Some(ErrorKind::AnoncredsTailsIntegrityViolation), tampered(HEADER_SIZE - 1));
+        assert_eq!(Some(ErrorKind::AnoncredsTailsIntegrityViolation), tampered(bytes.len() - 1));
+
+        let mut other_root = root.clone();
+        other_root[0] ^= 1;
+        assert_eq!(ErrorKind::AnoncredsTailsIntegrityViolation, _open(&bytes, &other_root).err().unwrap().kind());
+        assert_eq!(ErrorKind::AnoncredsTailsIntegrityViolation,
+                   TailsReader::open(Cursor::new(bytes.clone()), "other_rev_reg_id", 11, &root).err().unwrap().kind());
+        assert_eq!(ErrorKind::AnoncredsTailsIntegrityViolation,
+                   TailsReader::open(Cursor::new(bytes.clone()), REV_REG_ID, 12, &root).err().unwrap().kind());
+        assert_eq!(ErrorKind::IOError, _open(&bytes[..bytes.len() - 1], &root).err().unwrap().kind());
+    }
+
+    #[test]
+    fn tails_reader_works_for_forged_entry_count() {
+        let (_, mut bytes, root) = _container();
+        // a header of u32::MAX tails in chunks of one is rejected before chunk hashes are read
+        bytes[6 + HASH_SIZE..10 + HASH_SIZE].copy_from_slice(&u32::MAX.to_be_bytes());
+        bytes[10 + HASH_SIZE..HEADER_SIZE].copy_from_slice(&1u32.to_be_bytes());
+        assert_eq!(ErrorKind::AnoncredsTailsIntegrityViolation, _open(&bytes, &root).err().unwrap().kind());
+    }
+
+    #[test]
+    fn tails_writer_works_for_invalid_count() {
+        let (mut rev_tails_generator, _, _) = _container();
+        assert_eq!(ErrorKind::InvalidStructure, TailsWriter::new(Vec::new(), REV_REG_ID, 11, 0).err().unwrap().kind());
+
+        let mut writer = TailsWriter::new(Vec::new(), REV_REG_ID, 10, CHUNK_SIZE).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, writer.add_tails(&mut rev_tails_generator.clone()).unwrap_err().kind());
+
+        let mut writer = TailsWriter::new(Vec::new(), REV_REG_ID, 12, CHUNK_SIZE).unwrap();
+        writer.add_tails(&mut rev_tails_generator).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, writer.finalize().err().unwrap().kind());
+    }
+}
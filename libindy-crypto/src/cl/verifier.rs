@@ -5,8 +5,9 @@ use crate::cl::helpers::*;
 use crate::cl::hash::get_hash_as_int;
 use crate::errors::IndyCryptoError;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::iter::FromIterator;
+use std::sync::{Arc, RwLock};
 
 /// Party that wants to check that prover has some credentials provided by issuer.
 pub struct Verifier {}
@@ -32,6 +33,34 @@ impl Verifier {
         Ok(res)
     }
 
+    /// Creates and returns sub proof request entity builder checked against credential schema.
+    ///
+    /// Every revealed and predicate attribute must be in the schema, see `SchemaSubProofRequestBuilder`.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Credential schema.
+    ///
+    /// # Example
+    /// ```
+    /// use indy_crypto::cl::issuer::Issuer;
+    /// use indy_crypto::cl::verifier::Verifier;
+    ///
+    /// let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+    /// credential_schema_builder.add_attr("name").unwrap();
+    /// credential_schema_builder.add_attr("sex").unwrap();
+    /// credential_schema_builder.add_attr("age").unwrap();
+    /// let credential_schema = credential_schema_builder.finalize().unwrap();
+    ///
+    /// let mut sub_proof_request_builder = Verifier::new_schema_sub_proof_request_builder(&credential_schema).unwrap();
+    /// sub_proof_request_builder.add_revealed_attr_group("identity", &["name", "sex"]).unwrap();
+    /// sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+    /// assert!(sub_proof_request_builder.add_revealed_attr("height").is_err());
+    /// let _sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+    /// ```
+    pub fn new_schema_sub_proof_request_builder(credential_schema: &CredentialSchema) -> Result<SchemaSubProofRequestBuilder, IndyCryptoError> {
+        SchemaSubProofRequestBuilder::new(credential_schema)
+    }
+
     /// Creates and returns proof verifier.
     ///
     /// The purpose of `proof verifier` is check proof provided by Prover.
@@ -45,14 +74,191 @@ impl Verifier {
     pub fn new_proof_verifier() -> Result<ProofVerifier, IndyCryptoError> {
         Ok(ProofVerifier {
             credentials: Vec::new(),
+            linked_sub_proofs: Vec::new(),
         })
     }
 }
 
 
+/// Cache of pairings for `ProofVerifier::verify_with_cache`.
+///
+/// Pairings of a credential revocation public key don't depend on proofs, so they are computed once
+/// on the first verification of a non revocation proof for the key (or by `precompute`). Entries are
+/// looked up by the bytes of the whole key, so a proof for another key is a cache miss and never uses
+/// its pairings. At most `max_entries` keys are kept, the oldest cached key is evicted to add a new one.
+/// The cache can be shared between verifiers and threads behind an `Arc`.
+#[derive(Debug)]
+pub struct NonRevocVerificationCache {
+    max_entries: usize,
+    entries: RwLock<NonRevocCacheEntries>
+}
+
+#[derive(Debug, Default)]
+struct NonRevocCacheEntries {
+    pairings: HashMap<Vec<u8>, Arc<NonRevocPairings>>,
+    // Keys in the order they were cached, the front one is evicted first
+    order: VecDeque<Vec<u8>>
+}
+
+impl NonRevocVerificationCache {
+    /// Number of keys `new` cache keeps.
+    pub const DEFAULT_MAX_ENTRIES: usize = 64;
+
+    pub fn new() -> Result<NonRevocVerificationCache, IndyCryptoError> {
+        NonRevocVerificationCache::with_max_entries(NonRevocVerificationCache::DEFAULT_MAX_ENTRIES)
+    }
+
+    /// Creates cache that keeps pairings of at most `max_entries` keys, it must be positive.
+    pub fn with_max_entries(max_entries: usize) -> Result<NonRevocVerificationCache, IndyCryptoError> {
+        if max_entries == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Verification cache must keep at least one entry".to_string()));
+        }
+
+        Ok(NonRevocVerificationCache {
+            max_entries,
+            entries: RwLock::new(NonRevocCacheEntries::default())
+        })
+    }
+
+    /// Computes pairings of revocation part of the credential public key, if they aren't cached yet.
+    pub fn precompute(&self, credential_pub_key: &CredentialPublicKey) -> Result<(), IndyCryptoError> {
+        let r_pub_key = credential_pub_key.r_key.as_ref()
+            .ok_or_else(|| IndyCryptoError::InvalidStructure("Credential public key doesn't support revocation".to_string()))?;
+
+        self.pairings(r_pub_key)?;
+        Ok(())
+    }
+
+    /// Number of cached credential revocation public keys.
+    pub fn len(&self) -> Result<usize, IndyCryptoError> {
+        Ok(self.entries.read()
+            .map_err(|_| IndyCryptoError::InvalidState("Verification cache lock is poisoned".to_string()))?
+            .pairings
+            .len())
+    }
+
+    pub fn is_empty(&self) -> Result<bool, IndyCryptoError> {
+        Ok(self.len()? == 0)
+    }
+
+    fn pairings(&self, r_pub_key: &CredentialRevocationPublicKey) -> Result<Arc<NonRevocPairings>, IndyCryptoError> {
+        let key = r_pub_key.cache_key()?;
+
+        let cached = self.entries.read()
+            .map_err(|_| IndyCryptoError::InvalidState("Verification cache lock is poisoned".to_string()))?
+            .pairings
+            .get(&key)
+            .cloned();
+
+        if let Some(pairings) = cached {
+            return Ok(pairings);
+        }
+
+        let pairings = Arc::new(NonRevocPairings::new(r_pub_key)?);
+
+        let mut entries = self.entries.write()
+            .map_err(|_| IndyCryptoError::InvalidState("Verification cache lock is poisoned".to_string()))?;
+
+        // Other thread could compute the same pairings meanwhile
+        if let Some(cached) = entries.pairings.get(&key) {
+            return Ok(cached.clone());
+        }
+
+        if entries.order.len() >= self.max_entries {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.pairings.remove(&oldest);
+            }
+        }
+
+        entries.order.push_back(key.clone());
+        entries.pairings.insert(key, pairings.clone());
+        Ok(pairings)
+    }
+}
+
+/// Pairings of a credential revocation public key, that are the same for all its non revocation proofs.
+#[derive(Debug)]
+struct NonRevocPairings {
+    r_pub_key_pairings: RevocationPublicKeyPairings,
+    g_g_dash: Pair
+}
+
+impl NonRevocPairings {
+    fn new(r_pub_key: &CredentialRevocationPublicKey) -> Result<NonRevocPairings, IndyCryptoError> {
+        Ok(NonRevocPairings {
+            r_pub_key_pairings: RevocationPublicKeyPairings::new(r_pub_key)?,
+            g_g_dash: Pair::pair(&r_pub_key.g, &r_pub_key.g_dash)?
+        })
+    }
+}
+
+impl CredentialRevocationPublicKey {
+    // Points are encoded in affine form, so equal keys always have equal bytes
+    fn cache_key(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut key = Vec::new();
+        for point in &[&self.g, &self.h, &self.h0, &self.h1, &self.h2, &self.htilde, &self.pk] {
+            key.extend_from_slice(&point.to_bytes()?);
+        }
+        for point in &[&self.g_dash, &self.h_cap, &self.u, &self.y] {
+            key.extend_from_slice(&point.to_bytes()?);
+        }
+        Ok(key)
+    }
+}
+
+/// Result of `ProofVerifier::verify_with_diagnostics`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProofVerificationReport {
+    /// Same as result of `ProofVerifier::verify`.
+    pub valid: bool,
+    /// Reports in the order of sub proof requests added to the verifier.
+    pub sub_proofs: Vec<SubProofReport>,
+    /// All sub proofs pass their checks, but the aggregated challenge doesn't match.
+    pub challenge_mismatch: bool
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubProofReport {
+    pub failures: Vec<ProofCheckFailure>
+}
+
+impl SubProofReport {
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Check of a sub proof that failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum ProofCheckFailure {
+    /// Revealed attributes differ from requested ones.
+    RevealedAttrs {
+        missing: BTreeSet<String>,
+        unexpected: BTreeSet<String>
+    },
+    /// Primary equality proof failed, `attr_name` is set if the failure concerns a particular attribute.
+    EqProof {
+        attr_name: Option<String>,
+        reason: String
+    },
+    /// Predicate proof failed, `predicate_index` is index in the sub proof or None for a requested
+    /// predicate the sub proof doesn't contain.
+    Predicate {
+        predicate_index: Option<usize>,
+        attr_name: String,
+        reason: String
+    },
+    /// Non revocation proof failed.
+    NonRevocation {
+        reason: String
+    }
+}
+
 #[derive(Debug)]
 pub struct ProofVerifier {
     credentials: Vec<VerifiableCredential>,
+    linked_sub_proofs: Vec<(String, Vec<usize>)>,
 }
 
 impl ProofVerifier {
@@ -105,6 +311,22 @@ impl ProofVerifier {
                                  rev_reg: Option<&RevocationRegistry>) -> Result<(), IndyCryptoError> {
         ProofVerifier::_check_add_sub_proof_request_params_consistency(sub_proof_request, credential_schema)?;
 
+        let idx = self.credentials.len();
+        for predicate in sub_proof_request.equality_predicates.iter() {
+            let other = self.credentials.get(predicate.other_sub_proof_idx)
+                .ok_or_else(|| IndyCryptoError::InvalidStructure(
+                    format!("Sub proof {} of equality predicate isn't added before sub proof {}", predicate.other_sub_proof_idx, idx)))?;
+
+            if !ProofVerifier::_is_hidden_attr(credential_schema, non_credential_schema, sub_proof_request, &predicate.attr_name) {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Attribute '{}' of equality predicate isn't hidden attribute of sub proof {}", predicate.attr_name, idx)));
+            }
+            if !ProofVerifier::_is_hidden_attr(&other.credential_schema, &other.non_credential_schema, &other.sub_proof_request, &predicate.other_attr_name) {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Attribute '{}' of equality predicate isn't hidden attribute of sub proof {}", predicate.other_attr_name, predicate.other_sub_proof_idx)));
+            }
+        }
+
         self.credentials.push(VerifiableCredential {
             pub_key: credential_pub_key.clone()?,
             sub_proof_request: sub_proof_request.clone(),
@@ -116,8 +338,40 @@ impl ProofVerifier {
         Ok(())
     }
 
+    /// Requires already added sub proofs with `sub_proof_indices` (in order of adding) to share the
+    /// hidden value of `attr_name`, usually the link secret, so the credentials are issued to the same holder.
+    ///
+    /// Every sub proof is verified with the aggregated challenge and the response for the attribute,
+    /// so equal responses prove equal values. Proof with different responses is rejected
+    /// with `AnoncredsProofRejected` error.
+    ///
+    /// # Arguments
+    /// * `attr_name` - Linking attribute, that isn't revealed in any of the sub proofs.
+    /// * `sub_proof_indices` - Indices of linked sub proofs, two at least.
+    pub fn add_linked_sub_proofs(&mut self, attr_name: &str, sub_proof_indices: &[usize]) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::add_linked_sub_proofs: >>> attr_name: {:?}, sub_proof_indices: {:?}", attr_name, sub_proof_indices);
+
+        check_linked_sub_proof_indices(self.credentials.len(), sub_proof_indices)?;
+
+        for &idx in sub_proof_indices {
+            let credential = &self.credentials[idx];
+            if !ProofVerifier::_is_hidden_attr(&credential.credential_schema, &credential.non_credential_schema, &credential.sub_proof_request, attr_name) {
+                return Err(IndyCryptoError::InvalidStructure(
+                    format!("Linked attribute '{}' isn't hidden attribute of sub proof {}", attr_name, idx)));
+            }
+        }
+
+        self.linked_sub_proofs.push((attr_name.to_owned(), sub_proof_indices.to_vec()));
+
+        trace!("ProofVerifier::add_linked_sub_proofs: <<<");
+
+        Ok(())
+    }
+
     /// Verifies proof.
     ///
+    /// Returns `InvalidStructure` if the nonce isn't valid by `check_nonce`.
+    ///
     /// # Arguments
     /// * `proof_verifier` - Proof verifier.
     /// * `proof` - Proof generated by Prover.
@@ -205,7 +459,41 @@ impl ProofVerifier {
                   nonce: &Nonce) -> Result<bool, IndyCryptoError> {
         trace!("ProofVerifier::verify: >>> proof: {:?}, nonce: {:?}", proof, nonce);
 
+        let valid = self._verify(proof, nonce, None)?;
+
+        trace!("ProofVerifier::verify: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Verifies proof like `verify`, but takes pairings of credential revocation public keys
+    /// from the cache instead of computing them for every non revocation proof.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof generated by Prover.
+    /// * `nonce` - Nonce.
+    /// * `cache` - Cache of pairings, that can be shared between verifiers.
+    pub fn verify_with_cache(&self,
+                             proof: &Proof,
+                             nonce: &Nonce,
+                             cache: &NonRevocVerificationCache) -> Result<bool, IndyCryptoError> {
+        trace!("ProofVerifier::verify_with_cache: >>> proof: {:?}, nonce: {:?}, cache: {:?}", proof, nonce, cache);
+
+        let valid = self._verify(proof, nonce, Some(cache))?;
+
+        trace!("ProofVerifier::verify_with_cache: <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    fn _verify(&self,
+               proof: &Proof,
+               nonce: &Nonce,
+               cache: Option<&NonRevocVerificationCache>) -> Result<bool, IndyCryptoError> {
+        check_nonce(nonce)?;
         ProofVerifier::_check_verify_params_consistency(&self.credentials, proof)?;
+        ProofVerifier::_check_linked_sub_proofs(&self.linked_sub_proofs, proof)?;
+        ProofVerifier::_check_equality_predicates(&self.credentials, proof)?;
 
         let mut tau_list: Vec<Vec<u8>> = Vec::new();
 
@@ -217,8 +505,14 @@ impl ProofVerifier {
                                                                                                              credential.pub_key.r_key.as_ref(),
                                                                                                              credential.rev_reg.as_ref(),
                                                                                                              credential.rev_key_pub.as_ref()) {
+                let r_pub_key_pairings = match cache {
+                    Some(cache) => cache.pairings(cred_rev_pub_key)?,
+                    None => Arc::new(NonRevocPairings::new(cred_rev_pub_key)?)
+                };
+
                 tau_list.extend_from_slice(
                     &ProofVerifier::_verify_non_revocation_proof(&cred_rev_pub_key,
+                                                                 &r_pub_key_pairings,
                                                                  &rev_reg,
                                                                  &rev_key_pub,
                                                                  &proof.aggregated_proof.c_hash,
@@ -245,13 +539,292 @@ impl ProofVerifier {
 
         info!(target: "anoncreds_service", "Verifier verify proof -> done");
 
-        let valid = c_hver == proof.aggregated_proof.c_hash;
+        Ok(c_hver == proof.aggregated_proof.c_hash)
+    }
 
-        trace!("ProofVerifier::verify: <<< valid: {:?}", valid);
+    /// Verifies proof like `verify` in constant outcome mode: every check of every sub proof is
+    /// evaluated even if an earlier check fails, failures are accumulated and only then the result is returned.
+    /// Challenges and responses of linked and equal attributes are compared in constant time, malformed components
+    /// are rejected with `false` like invalid ones instead of `AnoncredsProofRejected` error.
+    /// So neither the time nor the result of rejection tells which component of the proof failed.
+    /// Nonce isn't a part of the proof, invalid one is still `InvalidStructure` error as in `verify`.
+    ///
+    /// Big number and pairing arithmetic of the checks isn't constant time, only their control flow and comparisons are.
+    /// Use `verify_with_diagnostics` to enumerate the failed checks.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof generated by Prover.
+    /// * `nonce` - Nonce.
+    /// * `cache` - Optional cache of pairings, see `verify_with_cache`.
+    pub fn verify_constant_outcome(&self,
+                                   proof: &Proof,
+                                   nonce: &Nonce,
+                                   cache: Option<&NonRevocVerificationCache>) -> Result<bool, IndyCryptoError> {
+        trace!("ProofVerifier::verify_constant_outcome: >>> proof: {:?}, nonce: {:?}, cache: {:?}", proof, nonce, cache);
+
+        let outcomes = self._evaluate_all_checks(proof, nonce, cache)?;
+        let valid = outcomes.iter().fold(true, |valid, &passed| valid & passed);
+
+        trace!("ProofVerifier::verify_constant_outcome: <<< valid: {:?}", valid);
 
         Ok(valid)
     }
 
+    // Outcomes of all checks in the order: number of sub proofs, then for every sub proof its consistency
+    // with the request, non revocation proof (if any) and primary proof, then every link,
+    // then every equality predicate in order of sub proofs, then the challenge.
+    // Errors of failed proof checks are only outcomes here, they aren't returned and don't reach the error hook
+    pub(crate) fn _evaluate_all_checks(&self,
+                                       proof: &Proof,
+                                       nonce: &Nonce,
+                                       cache: Option<&NonRevocVerificationCache>) -> Result<Vec<bool>, IndyCryptoError> {
+        check_nonce(nonce)?;
+
+        let mut outcomes = vec![proof.proofs.len() == self.credentials.len()];
+        let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+        for (proof_item, credential) in proof.proofs.iter().zip(self.credentials.iter()) {
+            outcomes.push(ProofVerifier::_revealed_attrs_match(credential, proof_item) & ProofVerifier::_predicates_match(credential, proof_item));
+
+            if let (Some(non_revocation_proof), Some(cred_rev_pub_key), Some(rev_reg), Some(rev_key_pub)) = (proof_item.non_revoc_proof.as_ref(),
+                                                                                                             credential.pub_key.r_key.as_ref(),
+                                                                                                             credential.rev_reg.as_ref(),
+                                                                                                             credential.rev_key_pub.as_ref()) {
+                let r_pub_key_pairings = match cache {
+                    Some(cache) => cache.pairings(cred_rev_pub_key)?,
+                    None => Arc::new(NonRevocPairings::new(cred_rev_pub_key)?)
+                };
+
+                let taus = ProofVerifier::_verify_non_revocation_proof(cred_rev_pub_key,
+                                                                       &r_pub_key_pairings,
+                                                                       rev_reg,
+                                                                       rev_key_pub,
+                                                                       &proof.aggregated_proof.c_hash,
+                                                                       non_revocation_proof)
+                    .and_then(|taus| taus.as_slice());
+                outcomes.push(taus.is_ok());
+                if let Ok(taus) = taus {
+                    tau_list.extend_from_slice(&taus);
+                }
+            }
+
+            let taus = ProofVerifier::_verify_primary_proof(&credential.pub_key.p_key,
+                                                            &proof.aggregated_proof.c_hash,
+                                                            &proof_item.primary_proof,
+                                                            &credential.credential_schema,
+                                                            &credential.non_credential_schema,
+                                                            &credential.sub_proof_request);
+            outcomes.push(taus.is_ok());
+            if let Ok(taus) = taus {
+                tau_list.append_vec(&taus)?;
+            }
+        }
+
+        for (attr_name, sub_proof_indices) in self.linked_sub_proofs.iter() {
+            outcomes.push(ProofVerifier::_linked_responses_match(attr_name, sub_proof_indices, proof)?);
+        }
+
+        for (idx, credential) in self.credentials.iter().enumerate() {
+            for predicate in credential.sub_proof_request.equality_predicates.iter() {
+                let equal = match ProofVerifier::_equality_predicate_responses(idx, predicate, proof) {
+                    Some((response, other_response)) => constant_time_eq(&ProofVerifier::_signed_bytes(response)?,
+                                                                          &ProofVerifier::_signed_bytes(other_response)?),
+                    None => false
+                };
+                outcomes.push(equal);
+            }
+        }
+
+        let mut values: Vec<Vec<u8>> = Vec::new();
+        values.extend_from_slice(&tau_list);
+        values.extend_from_slice(&proof.aggregated_proof.c_list);
+        values.push(nonce.to_bytes()?);
+
+        let c_hver = get_hash_as_int(&values)?;
+        outcomes.push(constant_time_eq(&ProofVerifier::_signed_bytes(&c_hver)?,
+                                       &ProofVerifier::_signed_bytes(&proof.aggregated_proof.c_hash)?));
+
+        Ok(outcomes)
+    }
+
+    /// Verifies proof like `verify` and describes why an invalid proof is rejected.
+    ///
+    /// Every sub proof is checked against its sub proof request and against commitments of the
+    /// aggregated proof, failed checks are reported per sub proof. Responses of sub proofs (including
+    /// revealed attribute values) are bound only by the aggregated challenge, so if all sub proofs
+    /// pass their checks, a failure is reported as `challenge_mismatch`.
+    ///
+    /// `verify` stays the plain check, this function does the additional work only for invalid proofs.
+    ///
+    /// # Arguments
+    /// * `proof` - Proof generated by Prover.
+    /// * `nonce` - Nonce.
+    pub fn verify_with_diagnostics(&self,
+                                   proof: &Proof,
+                                   nonce: &Nonce) -> Result<ProofVerificationReport, IndyCryptoError> {
+        trace!("ProofVerifier::verify_with_diagnostics: >>> proof: {:?}, nonce: {:?}", proof, nonce);
+
+        check_nonce(nonce)?;
+
+        if proof.proofs.len() != self.credentials.len() {
+            return Err(IndyCryptoError::AnoncredsProofRejected(
+                format!("Proof contains {} sub proofs, {} are requested", proof.proofs.len(), self.credentials.len())));
+        }
+
+        let res = self.verify(proof, nonce);
+        let valid = match res {
+            Ok(valid) => valid,
+            Err(_) => false
+        };
+
+        let mut sub_proofs = Vec::with_capacity(proof.proofs.len());
+        let mut c_list_offset = 0;
+        for (sub_proof, credential) in proof.proofs.iter().zip(self.credentials.iter()) {
+            let failures = if valid {
+                Vec::new()
+            } else {
+                ProofVerifier::_diagnose_sub_proof(credential, sub_proof, &proof.aggregated_proof, &mut c_list_offset)?
+            };
+            sub_proofs.push(SubProofReport { failures });
+        }
+
+        let challenge_mismatch = !valid && sub_proofs.iter().all(SubProofReport::is_valid);
+
+        // verify failed with an error the sub proof checks don't explain
+        if challenge_mismatch {
+            res?;
+        }
+
+        let report = ProofVerificationReport { valid, sub_proofs, challenge_mismatch };
+
+        trace!("ProofVerifier::verify_with_diagnostics: <<< report: {:?}", report);
+
+        Ok(report)
+    }
+
+    // Checks sub proof against its request and its segment of commitments in the aggregated proof
+    fn _diagnose_sub_proof(credential: &VerifiableCredential,
+                           sub_proof: &SubProof,
+                           aggregated_proof: &AggregatedProof,
+                           c_list_offset: &mut usize) -> Result<Vec<ProofCheckFailure>, IndyCryptoError> {
+        trace!("ProofVerifier::_diagnose_sub_proof: >>> credential: {:?}, sub_proof: {:?}, aggregated_proof: {:?}, c_list_offset: {:?}",
+               credential, sub_proof, aggregated_proof, c_list_offset);
+
+        let mut failures = Vec::new();
+        let c_hash = &aggregated_proof.c_hash;
+        let c_list = &aggregated_proof.c_list;
+
+        let primary_proof = &sub_proof.primary_proof;
+        let sub_proof_request = &credential.sub_proof_request;
+
+        let revealed_attrs = BTreeSet::from_iter(primary_proof.eq_proof.revealed_attrs.keys().cloned());
+        if revealed_attrs != sub_proof_request.revealed_attrs {
+            failures.push(ProofCheckFailure::RevealedAttrs {
+                missing: sub_proof_request.revealed_attrs.difference(&revealed_attrs).cloned().collect(),
+                unexpected: revealed_attrs.difference(&sub_proof_request.revealed_attrs).cloned().collect()
+            });
+        }
+
+        let predicates = primary_proof.ne_proofs.iter()
+            .map(|ne_proof| ne_proof.predicate.clone())
+            .collect::<BTreeSet<Predicate>>();
+        for predicate in sub_proof_request.predicates.difference(&predicates) {
+            failures.push(ProofCheckFailure::Predicate {
+                predicate_index: None,
+                attr_name: predicate.attr_name.clone(),
+                reason: format!("Requested predicate {:?} {} is missing", predicate.p_type, predicate.value)
+            });
+        }
+
+        match (sub_proof.non_revoc_proof.as_ref(), credential.pub_key.r_key.as_ref(), credential.rev_reg.as_ref(), credential.rev_key_pub.as_ref()) {
+            (Some(non_revocation_proof), Some(cred_rev_pub_key), Some(rev_reg), Some(rev_key_pub)) => {
+                if !ProofVerifier::_commitments_match(c_list, c_list_offset, non_revocation_proof.c_list.as_list()?) {
+                    failures.push(ProofCheckFailure::NonRevocation {
+                        reason: "Commitments don't match the aggregated proof".to_string()
+                    });
+                } else if let Err(err) = ProofVerifier::_verify_non_revocation_proof(cred_rev_pub_key, &NonRevocPairings::new(cred_rev_pub_key)?,
+                                                                                                     rev_reg, rev_key_pub, c_hash, non_revocation_proof) {
+                    failures.push(ProofCheckFailure::NonRevocation { reason: err.to_string() });
+                }
+            }
+            (Some(non_revocation_proof), _, _, _) => {
+                ProofVerifier::_commitments_match(c_list, c_list_offset, non_revocation_proof.c_list.as_list()?);
+                failures.push(ProofCheckFailure::NonRevocation {
+                    reason: "Revocation registry and key aren't added for the sub proof".to_string()
+                });
+            }
+            (None, _, _, _) => {}
+        }
+
+        let eq_proof = &primary_proof.eq_proof;
+        let p_pub_key = &credential.pub_key.p_key;
+        let eq_proof_failures = failures.len();
+        for attr in eq_proof.revealed_attrs.keys().filter(|attr| !p_pub_key.r.contains_key(*attr)) {
+            failures.push(ProofCheckFailure::EqProof {
+                attr_name: Some(attr.clone()),
+                reason: "Attribute isn't in the credential public key".to_string()
+            });
+        }
+        for attr in credential.credential_schema.attrs.union(&credential.non_credential_schema.attrs)
+            .filter(|attr| !sub_proof_request.revealed_attrs.contains(*attr) && !eq_proof.m.contains_key(*attr)) {
+            failures.push(ProofCheckFailure::EqProof {
+                attr_name: Some(attr.clone()),
+                reason: "Response for the hidden attribute is missing".to_string()
+            });
+        }
+        if !ProofVerifier::_commitments_match(c_list, c_list_offset, vec![eq_proof.a_prime.to_bytes()?]) {
+            failures.push(ProofCheckFailure::EqProof {
+                attr_name: None,
+                reason: "Commitment a_prime doesn't match the aggregated proof".to_string()
+            });
+        } else if failures.len() == eq_proof_failures {
+            if let Err(err) = ProofVerifier::_verify_equality(p_pub_key, eq_proof, c_hash, &credential.credential_schema,
+                                                             &credential.non_credential_schema, sub_proof_request) {
+                failures.push(ProofCheckFailure::EqProof { attr_name: None, reason: err.to_string() });
+            }
+        }
+
+        for (predicate_index, ne_proof) in primary_proof.ne_proofs.iter().enumerate() {
+            let predicate_failure = |reason: String| ProofCheckFailure::Predicate {
+                predicate_index: Some(predicate_index),
+                attr_name: ne_proof.predicate.attr_name.clone(),
+                reason
+            };
+
+            if !sub_proof_request.predicates.contains(&ne_proof.predicate) {
+                failures.push(predicate_failure("Predicate isn't requested".to_string()));
+            }
+
+            let keys = (0..ITERATION).map(|i| i.to_string()).chain(Some("DELTA".to_string()));
+            let mut commitments = Vec::with_capacity(ITERATION + 1);
+            for key in keys {
+                match ne_proof.t.get(&key) {
+                    Some(t) => commitments.push(t.to_bytes()?),
+                    None => failures.push(predicate_failure(format!("Commitment {} is missing", key)))
+                }
+            }
+
+            if commitments.len() != ITERATION + 1 {
+                *c_list_offset += ITERATION + 1;
+            } else if !ProofVerifier::_commitments_match(c_list, c_list_offset, commitments) {
+                failures.push(predicate_failure("Commitments don't match the aggregated proof".to_string()));
+            } else if let Err(err) = ProofVerifier::_verify_ne_predicate(p_pub_key, ne_proof, c_hash) {
+                failures.push(predicate_failure(err.to_string()));
+            }
+        }
+
+        trace!("ProofVerifier::_diagnose_sub_proof: <<< failures: {:?}", failures);
+
+        Ok(failures)
+    }
+
+    // Compares commitments with the next segment of the aggregated proof c_list
+    fn _commitments_match(c_list: &[Vec<u8>], c_list_offset: &mut usize, commitments: Vec<Vec<u8>>) -> bool {
+        let segment = c_list.get(*c_list_offset..*c_list_offset + commitments.len());
+        *c_list_offset += commitments.len();
+        segment == Some(commitments.as_slice())
+    }
+
     fn _check_add_sub_proof_request_params_consistency(sub_proof_request: &SubProofRequest,
                                                        cred_schema: &CredentialSchema) -> Result<(), IndyCryptoError> {
         trace!("ProofVerifier::_check_add_sub_proof_request_params_consistency: >>> sub_proof_request: {:?}, cred_schema: {:?}", sub_proof_request, cred_schema);
@@ -280,30 +853,136 @@ impl ProofVerifier {
 
         assert_eq!(proof.proofs.len(), credentials.len()); //FIXME return error
         for idx in 0..proof.proofs.len() {
-            let proof_for_credential = &proof.proofs[idx];
-            let credential = &credentials[idx];
+            ProofVerifier::_check_sub_proof_consistency(&credentials[idx], &proof.proofs[idx])?;
+        }
+
+        trace!("ProofVerifier::_check_verify_params_consistency: <<<");
+
+        Ok(())
+    }
+
+    // Revealed attributes and predicates of sub proof must be the requested ones
+    fn _check_sub_proof_consistency(credential: &VerifiableCredential,
+                                    proof_for_credential: &SubProof) -> Result<(), IndyCryptoError> {
+        if !ProofVerifier::_revealed_attrs_match(credential, proof_for_credential) {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof revealed attributes not correspond to requested attributes")));
+        }
+
+        if !ProofVerifier::_predicates_match(credential, proof_for_credential) {
+            return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof predicates not correspond to requested predicates")));
+        }
+
+        Ok(())
+    }
 
-            let proof_revealed_attrs = BTreeSet::from_iter(proof_for_credential.primary_proof.eq_proof.revealed_attrs.keys().cloned());
+    fn _revealed_attrs_match(credential: &VerifiableCredential, proof_for_credential: &SubProof) -> bool {
+        let proof_revealed_attrs = BTreeSet::from_iter(proof_for_credential.primary_proof.eq_proof.revealed_attrs.keys().cloned());
+        proof_revealed_attrs == credential.sub_proof_request.revealed_attrs
+    }
 
-            if proof_revealed_attrs != credential.sub_proof_request.revealed_attrs {
-                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof revealed attributes not correspond to requested attributes")));
+    fn _predicates_match(credential: &VerifiableCredential, proof_for_credential: &SubProof) -> bool {
+        let proof_predicates =
+            proof_for_credential.primary_proof.ne_proofs.iter()
+                .map(|ne_proof| ne_proof.predicate.clone())
+                .collect::<BTreeSet<Predicate>>();
+        proof_predicates == credential.sub_proof_request.predicates
+    }
+
+    // Responses for the linking attribute are equal in linked sub proofs only for equal values
+    fn _check_linked_sub_proofs(linked_sub_proofs: &[(String, Vec<usize>)],
+                                proof: &Proof) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::_check_linked_sub_proofs: >>> linked_sub_proofs: {:?}, proof: {:?}", linked_sub_proofs, proof);
+
+        for (attr_name, sub_proof_indices) in linked_sub_proofs {
+            let mut responses = Vec::with_capacity(sub_proof_indices.len());
+            for &idx in sub_proof_indices {
+                let response = proof.proofs[idx].primary_proof.eq_proof.m.get(attr_name)
+                    .ok_or_else(|| IndyCryptoError::AnoncredsProofRejected(
+                        format!("Sub proof {} has no response for linked attribute '{}'", idx, attr_name)))?;
+                responses.push((idx, response));
+            }
+
+            if let Some(&(idx, _)) = responses.iter().find(|&&(_, response)| response != responses[0].1) {
+                return Err(IndyCryptoError::AnoncredsProofRejected(
+                    format!("Sub proofs {} and {} don't share the value of '{}'", responses[0].0, idx, attr_name)));
             }
+        }
 
-            let proof_predicates =
-                proof_for_credential.primary_proof.ne_proofs.iter()
-                    .map(|ne_proof| ne_proof.predicate.clone())
-                    .collect::<BTreeSet<Predicate>>();
+        trace!("ProofVerifier::_check_linked_sub_proofs: <<<");
+
+        Ok(())
+    }
 
-            if proof_predicates != credential.sub_proof_request.predicates {
-                return Err(IndyCryptoError::AnoncredsProofRejected(format!("Proof predicates not correspond to requested predicates")));
+    // Responses for attributes of equality predicate are equal only for equal values, as the prover
+    // blinds both attributes with the same m_tilde
+    fn _check_equality_predicates(credentials: &[VerifiableCredential],
+                                  proof: &Proof) -> Result<(), IndyCryptoError> {
+        trace!("ProofVerifier::_check_equality_predicates: >>> credentials: {:?}, proof: {:?}", credentials, proof);
+
+        for (idx, credential) in credentials.iter().enumerate() {
+            for predicate in credential.sub_proof_request.equality_predicates.iter() {
+                let (response, other_response) = ProofVerifier::_equality_predicate_responses(idx, predicate, proof)
+                    .ok_or_else(|| IndyCryptoError::AnoncredsProofRejected(
+                        format!("Sub proof {} doesn't prove equality of attribute '{}' to attribute '{}' of sub proof {}",
+                                idx, predicate.attr_name, predicate.other_attr_name, predicate.other_sub_proof_idx)))?;
+
+                if response != other_response {
+                    return Err(IndyCryptoError::AnoncredsProofRejected(
+                        format!("Attribute '{}' of sub proof {} isn't equal to attribute '{}' of sub proof {}",
+                                predicate.attr_name, idx, predicate.other_attr_name, predicate.other_sub_proof_idx)));
+                }
             }
         }
 
-        trace!("ProofVerifier::_check_verify_params_consistency: <<<");
+        trace!("ProofVerifier::_check_equality_predicates: <<<");
 
         Ok(())
     }
 
+    // Responses for both attributes of equality predicate of sub proof `idx`, if the sub proof contains the predicate
+    fn _equality_predicate_responses<'a>(idx: usize,
+                                         predicate: &EqualityPredicate,
+                                         proof: &'a Proof) -> Option<(&'a BigNumber, &'a BigNumber)> {
+        let sub_proof = proof.proofs.get(idx).filter(|sub_proof| sub_proof.equality_predicates.contains(predicate))?;
+        let response = sub_proof.primary_proof.eq_proof.m.get(&predicate.attr_name)?;
+        let other_response = proof.proofs.get(predicate.other_sub_proof_idx)?.primary_proof.eq_proof.m.get(&predicate.other_attr_name)?;
+        Some((response, other_response))
+    }
+
+    fn _is_hidden_attr(cred_schema: &CredentialSchema,
+                       non_cred_schema: &NonCredentialSchema,
+                       sub_proof_request: &SubProofRequest,
+                       attr_name: &str) -> bool {
+        (cred_schema.attrs.contains(attr_name) || non_cred_schema.attrs.contains(attr_name)) &&
+            !sub_proof_request.revealed_attrs.contains(attr_name)
+    }
+
+    // Like `_check_linked_sub_proofs` for a single link, but compares all responses in constant time
+    // and treats a missing sub proof or response as mismatch
+    fn _linked_responses_match(attr_name: &str,
+                               sub_proof_indices: &[usize],
+                               proof: &Proof) -> Result<bool, IndyCryptoError> {
+        let mut responses = Vec::with_capacity(sub_proof_indices.len());
+        let mut present = true;
+        for &idx in sub_proof_indices {
+            match proof.proofs.get(idx).and_then(|sub_proof| sub_proof.primary_proof.eq_proof.m.get(attr_name)) {
+                Some(response) => responses.push(ProofVerifier::_signed_bytes(response)?),
+                None => present = false
+            }
+        }
+
+        let equal = responses.iter().fold(true, |equal, response| equal & constant_time_eq(response, &responses[0]));
+
+        Ok(present & equal)
+    }
+
+    // Sign byte (1 for negative) followed by magnitude, `to_bytes` alone doesn't tell `x` from `-x`
+    fn _signed_bytes(number: &BigNumber) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut bytes = vec![number.is_negative() as u8];
+        bytes.extend_from_slice(&number.to_bytes()?);
+        Ok(bytes)
+    }
+
     fn _verify_primary_proof(p_pub_key: &CredentialPrimaryPublicKey,
                              c_hash: &BigNumber,
                              primary_proof: &PrimaryProof,
@@ -352,17 +1031,19 @@ impl ProofVerifier {
 
         let mut ctx = BigNumber::new_context()?;
 
-        let mut rar = proof.a_prime.mod_exp(&LARGE_E_START_VALUE, &p_pub_key.n, Some(&mut ctx))?;
+        let mut bases = vec![&proof.a_prime];
+        let mut exps: Vec<&BigNumber> = vec![&LARGE_E_START_VALUE];
 
         for (attr, encoded_value) in &proof.revealed_attrs {
             let cur_r = p_pub_key.r.get(attr)
-                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in pk.r", attr)))?;
+                .ok_or_else(|| IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in pk.r", attr)))?;
 
-            rar = cur_r
-                .mod_exp(encoded_value, &p_pub_key.n, Some(&mut ctx))?
-                .mod_mul(&rar, &p_pub_key.n, Some(&mut ctx))?;
+            bases.push(cur_r);
+            exps.push(encoded_value);
         }
 
+        let rar = BigNumber::multi_mod_exp(&bases, &exps, &p_pub_key.n, Some(&mut ctx))?;
+
         let t2: BigNumber = p_pub_key.z
             .mod_div(&rar, &p_pub_key.n, Some(&mut ctx))?
             .inverse(&p_pub_key.n, Some(&mut ctx))?
@@ -386,7 +1067,7 @@ impl ProofVerifier {
 
         for i in 0..ITERATION {
             let cur_t = proof.t.get(&i.to_string())
-                .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", i)))?;
+                .ok_or_else(|| IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", i)))?;
 
             tau_list[i] = cur_t
                 .mod_exp(&c_hash, &p_pub_key.n, Some(&mut ctx))?
@@ -395,7 +1076,7 @@ impl ProofVerifier {
         }
 
         let delta = proof.t.get("DELTA")
-            .ok_or(IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", "DELTA")))?;
+            .ok_or_else(|| IndyCryptoError::AnoncredsProofRejected(format!("Value by key '{}' not found in proof.t", "DELTA")))?;
 
         let delta_prime = if proof.predicate.is_less() {
             delta.inverse(&p_pub_key.n, Some(&mut ctx))?
@@ -422,6 +1103,7 @@ impl ProofVerifier {
     }
 
     fn _verify_non_revocation_proof(r_pub_key: &CredentialRevocationPublicKey,
+                                    pairings: &NonRevocPairings,
                                     rev_reg: &RevocationRegistry,
                                     rev_key_pub: &RevocationKeyPublic,
                                     c_hash: &BigNumber, proof: &NonRevocProof) -> Result<NonRevocProofTauList, IndyCryptoError> {
@@ -430,8 +1112,8 @@ impl ProofVerifier {
 
         let ch_num_z = bignum_to_group_element(&c_hash)?;
 
-        let t_hat_expected_values = create_tau_list_expected_values(r_pub_key, rev_reg, rev_key_pub, &proof.c_list)?;
-        let t_hat_calc_values = create_tau_list_values(&r_pub_key, rev_reg, &proof.x_list, &proof.c_list)?;
+        let t_hat_expected_values = create_tau_list_expected_values(r_pub_key, &pairings.g_g_dash, rev_reg, rev_key_pub, &proof.c_list)?;
+        let t_hat_calc_values = create_tau_list_values(r_pub_key, &pairings.r_pub_key_pairings, rev_reg, &proof.x_list, &proof.c_list)?;
 
 
         let non_revoc_proof_tau_list = Ok(NonRevocProofTauList {
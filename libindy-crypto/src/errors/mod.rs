@@ -2,73 +2,171 @@
 extern crate serde_json;
 extern crate log;
 
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::convert::TryFrom;
+use std::cell::{Cell, RefCell};
 use std::error::Error;
+use std::ffi::NulError;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::Utf8Error;
+use std::string::FromUtf8Error;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{fmt, io};
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
-#[repr(usize)]
-pub enum ErrorCode
-{
+#[cfg(feature = "bn_openssl")]
+use openssl::error::ErrorStack;
+
+macro_rules! error_codes {
+    ($($(#[$meta:meta])* $name:ident = $value:expr,)*) => {
+        #[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+        #[repr(usize)]
+        #[non_exhaustive]
+        pub enum ErrorCode {
+            $($(#[$meta])* $name = $value,)*
+        }
+
+        impl ErrorCode {
+            /// All known error codes in declaration order.
+            pub const VALUES: &'static [ErrorCode] = &[$(ErrorCode::$name,)*];
+
+            /// Name of the code as used in C headers and wrappers.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $(ErrorCode::$name => stringify!($name),)*
+                }
+            }
+        }
+
+        impl TryFrom<usize> for ErrorCode {
+            type Error = IndyCryptoError;
+
+            fn try_from(value: usize) -> Result<ErrorCode, IndyCryptoError> {
+                match value {
+                    $(value if value == $value => Ok(ErrorCode::$name),)*
+                    _ => Err(IndyCryptoError::InvalidStructure(format!("Unknown error code: {}", value)))
+                }
+            }
+        }
+    }
+}
+
+error_codes! {
     Success = 0,
 
     // Common errors
 
-    // Caller passed invalid value as param 1 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 1 (null, invalid json and etc..)
     CommonInvalidParam1 = 100,
 
-    // Caller passed invalid value as param 2 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 2 (null, invalid json and etc..)
     CommonInvalidParam2 = 101,
 
-    // Caller passed invalid value as param 3 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 3 (null, invalid json and etc..)
     CommonInvalidParam3 = 102,
 
-    // Caller passed invalid value as param 4 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 4 (null, invalid json and etc..)
     CommonInvalidParam4 = 103,
 
-    // Caller passed invalid value as param 5 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 5 (null, invalid json and etc..)
     CommonInvalidParam5 = 104,
 
-    // Caller passed invalid value as param 6 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 6 (null, invalid json and etc..)
     CommonInvalidParam6 = 105,
 
-    // Caller passed invalid value as param 7 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 7 (null, invalid json and etc..)
     CommonInvalidParam7 = 106,
 
-    // Caller passed invalid value as param 8 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 8 (null, invalid json and etc..)
     CommonInvalidParam8 = 107,
 
-    // Caller passed invalid value as param 9 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 9 (null, invalid json and etc..)
     CommonInvalidParam9 = 108,
 
-    // Caller passed invalid value as param 10 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 10 (null, invalid json and etc..)
     CommonInvalidParam10 = 109,
 
-    // Caller passed invalid value as param 11 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 11 (null, invalid json and etc..)
     CommonInvalidParam11 = 110,
 
-    // Caller passed invalid value as param 11 (null, invalid json and etc..)
+    /// Caller passed invalid value as param 12 (null, invalid json and etc..)
     CommonInvalidParam12 = 111,
 
-    // Invalid library state was detected in runtime. It signals library bug
+    /// Invalid library state was detected in runtime. It signals library bug
     CommonInvalidState = 112,
 
-    // Object (json, config, key, credential and etc...) passed by library caller has invalid structure
+    /// Object (json, config, key, credential and etc...) passed by library caller has invalid structure
     CommonInvalidStructure = 113,
 
-    // IO Error
+    /// IO Error
     CommonIOError = 114,
 
-    // Trying to issue non-revocation credential with full anoncreds revocation accumulator
+    /// Operation was cancelled by a callback of the library caller
+    CommonCancelled = 119,
+
+    /// Trying to issue non-revocation credential with full anoncreds revocation accumulator
     AnoncredsRevocationAccumulatorIsFull = 115,
 
-    // Invalid revocation accumulator index
+    /// Invalid revocation accumulator index
     AnoncredsInvalidRevocationAccumulatorIndex = 116,
 
-    // Credential revoked
+    /// Credential revoked
     AnoncredsCredentialRevoked = 117,
 
-    // Proof rejected
+    /// Proof rejected
     AnoncredsProofRejected = 118,
+
+    /// Tails don't match the root of tails container
+    AnoncredsTailsIntegrityViolation = 120,
+
+    // Crypto errors
+
+    /// Point is not on the curve or has invalid encoding
+    CryptoInvalidPoint = 200,
+
+    /// Signature (or proof of possession) is malformed
+    CryptoInvalidSignature = 201,
+
+    /// Sign or verification key is malformed
+    CryptoInvalidKey = 202,
+
+    /// Random number generator failed to produce entropy
+    CryptoRngFailure = 203,
+
+    /// Fewer signature shares than the threshold were given to combine
+    CryptoNotEnoughShares = 204,
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), *self as usize)
+    }
+}
+
+impl ErrorCode {
+    /// Returns CommonInvalidParamN code for 1-based param index, None if index is out of 1..=12.
+    pub fn invalid_param(index: usize) -> Option<ErrorCode> {
+        match index {
+            1 => Some(ErrorCode::CommonInvalidParam1),
+            2 => Some(ErrorCode::CommonInvalidParam2),
+            3 => Some(ErrorCode::CommonInvalidParam3),
+            4 => Some(ErrorCode::CommonInvalidParam4),
+            5 => Some(ErrorCode::CommonInvalidParam5),
+            6 => Some(ErrorCode::CommonInvalidParam6),
+            7 => Some(ErrorCode::CommonInvalidParam7),
+            8 => Some(ErrorCode::CommonInvalidParam8),
+            9 => Some(ErrorCode::CommonInvalidParam9),
+            10 => Some(ErrorCode::CommonInvalidParam10),
+            11 => Some(ErrorCode::CommonInvalidParam11),
+            12 => Some(ErrorCode::CommonInvalidParam12),
+            _ => None
+        }
+    }
+
+    /// Returns 1-based param index of CommonInvalidParamN code, None for other codes.
+    pub fn param_index(&self) -> Option<usize> {
+        (1..=12).find(|index| ErrorCode::invalid_param(*index) == Some(*self))
+    }
 }
 
 pub trait ToErrorCode {
@@ -76,88 +174,198 @@ pub trait ToErrorCode {
 }
 
 #[derive(Debug)]
-    pub enum IndyCryptoError {
-    InvalidParam1(String),
-    InvalidParam2(String),
-    InvalidParam3(String),
-    InvalidParam4(String),
-    InvalidParam5(String),
-    InvalidParam6(String),
-    InvalidParam7(String),
-    InvalidParam8(String),
-    InvalidParam9(String),
+#[non_exhaustive]
+pub enum IndyCryptoError {
+    /// Caller passed invalid value as param `index` (1..=12), other indices have `CommonInvalidState` code
+    InvalidParam { index: u8, reason: String },
     InvalidState(String),
     InvalidStructure(String),
     IOError(io::Error),
+    #[cfg(feature = "serialization")]
+    JsonError(serde_json::Error),
+    #[cfg(feature = "bn_openssl")]
+    OpenSSLError(ErrorStack),
+    LoggerError(log::SetLoggerError),
     AnoncredsRevocationAccumulatorIsFull(String),
     AnoncredsInvalidRevocationAccumulatorIndex(String),
     AnoncredsCredentialRevoked(String),
     AnoncredsProofRejected(String),
+    AnoncredsTailsIntegrityViolation(String),
+    InvalidPoint(String),
+    InvalidSignature(String),
+    InvalidKey(String),
+    RngFailure(String),
+    NotEnoughShares(String),
+    Cancelled(String),
+    /// Error refined with a message and possibly a more precise code, `source` is the original error
+    Context { code: ErrorCode, message: String, source: Box<IndyCryptoError> },
+}
+
+/// Variant of `IndyCryptoError` without its payload.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    InvalidParam,
+    InvalidState,
+    InvalidStructure,
+    IOError,
+    JsonError,
+    OpenSSLError,
+    LoggerError,
+    AnoncredsRevocationAccumulatorIsFull,
+    AnoncredsInvalidRevocationAccumulatorIndex,
+    AnoncredsCredentialRevoked,
+    AnoncredsProofRejected,
+    AnoncredsTailsIntegrityViolation,
+    InvalidPoint,
+    InvalidSignature,
+    InvalidKey,
+    RngFailure,
+    NotEnoughShares,
+    Cancelled,
+    Context,
+}
+
+#[allow(non_snake_case)]
+impl IndyCryptoError {
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            IndyCryptoError::InvalidParam { .. } => ErrorKind::InvalidParam,
+            IndyCryptoError::InvalidState(_) => ErrorKind::InvalidState,
+            IndyCryptoError::InvalidStructure(_) => ErrorKind::InvalidStructure,
+            IndyCryptoError::IOError(_) => ErrorKind::IOError,
+            #[cfg(feature = "serialization")]
+            IndyCryptoError::JsonError(_) => ErrorKind::JsonError,
+            #[cfg(feature = "bn_openssl")]
+            IndyCryptoError::OpenSSLError(_) => ErrorKind::OpenSSLError,
+            IndyCryptoError::LoggerError(_) => ErrorKind::LoggerError,
+            IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(_) => ErrorKind::AnoncredsRevocationAccumulatorIsFull,
+            IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(_) => ErrorKind::AnoncredsInvalidRevocationAccumulatorIndex,
+            IndyCryptoError::AnoncredsCredentialRevoked(_) => ErrorKind::AnoncredsCredentialRevoked,
+            IndyCryptoError::AnoncredsProofRejected(_) => ErrorKind::AnoncredsProofRejected,
+            IndyCryptoError::AnoncredsTailsIntegrityViolation(_) => ErrorKind::AnoncredsTailsIntegrityViolation,
+            IndyCryptoError::InvalidPoint(_) => ErrorKind::InvalidPoint,
+            IndyCryptoError::InvalidSignature(_) => ErrorKind::InvalidSignature,
+            IndyCryptoError::InvalidKey(_) => ErrorKind::InvalidKey,
+            IndyCryptoError::RngFailure(_) => ErrorKind::RngFailure,
+            IndyCryptoError::NotEnoughShares(_) => ErrorKind::NotEnoughShares,
+            IndyCryptoError::Cancelled(_) => ErrorKind::Cancelled,
+            IndyCryptoError::Context { .. } => ErrorKind::Context,
+        }
+    }
+
+    /// Reconstructs an error from a code and message, the inverse of `ToErrorCode`.
+    ///
+    /// The conversion is lossy:
+    /// * `message` becomes the description, so rehydrated `Display` adds the variant prefix again
+    /// * `CommonIOError` becomes an `IOError` of kind `Other` carrying only the message
+    /// * `CommonInvalidStructure` and `CommonInvalidState` never produce the source-carrying
+    ///   `JsonError`, `OpenSSLError` or `LoggerError` variants
+    /// * `Success` is not an error and becomes `InvalidState`
+    pub fn from_error_code(code: ErrorCode, message: String) -> IndyCryptoError {
+        if let Some(index) = code.param_index() {
+            return IndyCryptoError::InvalidParam { index: index as u8, reason: message };
+        }
+
+        match code {
+            ErrorCode::CommonInvalidStructure => IndyCryptoError::InvalidStructure(message),
+            ErrorCode::CommonIOError => IndyCryptoError::IOError(io::Error::other(message)),
+            ErrorCode::AnoncredsRevocationAccumulatorIsFull => IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(message),
+            ErrorCode::AnoncredsInvalidRevocationAccumulatorIndex => IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(message),
+            ErrorCode::AnoncredsCredentialRevoked => IndyCryptoError::AnoncredsCredentialRevoked(message),
+            ErrorCode::AnoncredsProofRejected => IndyCryptoError::AnoncredsProofRejected(message),
+            ErrorCode::AnoncredsTailsIntegrityViolation => IndyCryptoError::AnoncredsTailsIntegrityViolation(message),
+            ErrorCode::CryptoInvalidPoint => IndyCryptoError::InvalidPoint(message),
+            ErrorCode::CryptoInvalidSignature => IndyCryptoError::InvalidSignature(message),
+            ErrorCode::CryptoInvalidKey => IndyCryptoError::InvalidKey(message),
+            ErrorCode::CryptoRngFailure => IndyCryptoError::RngFailure(message),
+            ErrorCode::CryptoNotEnoughShares => IndyCryptoError::NotEnoughShares(message),
+            ErrorCode::CommonCancelled => IndyCryptoError::Cancelled(message),
+            _ => IndyCryptoError::InvalidState(message)
+        }
+    }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 1, reason }")]
+    pub fn InvalidParam1(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 1, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 2, reason }")]
+    pub fn InvalidParam2(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 2, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 3, reason }")]
+    pub fn InvalidParam3(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 3, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 4, reason }")]
+    pub fn InvalidParam4(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 4, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 5, reason }")]
+    pub fn InvalidParam5(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 5, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 6, reason }")]
+    pub fn InvalidParam6(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 6, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 7, reason }")]
+    pub fn InvalidParam7(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 7, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 8, reason }")]
+    pub fn InvalidParam8(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 8, reason } }
+
+    #[deprecated(note = "use IndyCryptoError::InvalidParam { index: 9, reason }")]
+    pub fn InvalidParam9(reason: String) -> IndyCryptoError { IndyCryptoError::InvalidParam { index: 9, reason } }
 }
 
 impl fmt::Display for IndyCryptoError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            IndyCryptoError::InvalidParam1(ref description) => write!(f, "Invalid param 1: {}", description),
-            IndyCryptoError::InvalidParam2(ref description) => write!(f, "Invalid param 2: {}", description),
-            IndyCryptoError::InvalidParam3(ref description) => write!(f, "Invalid param 3: {}", description),
-            IndyCryptoError::InvalidParam4(ref description) => write!(f, "Invalid param 4: {}", description),
-            IndyCryptoError::InvalidParam5(ref description) => write!(f, "Invalid param 4: {}", description),
-            IndyCryptoError::InvalidParam6(ref description) => write!(f, "Invalid param 4: {}", description),
-            IndyCryptoError::InvalidParam7(ref description) => write!(f, "Invalid param 4: {}", description),
-            IndyCryptoError::InvalidParam8(ref description) => write!(f, "Invalid param 4: {}", description),
-            IndyCryptoError::InvalidParam9(ref description) => write!(f, "Invalid param 4: {}", description),
+            IndyCryptoError::InvalidParam { index, ref reason } => write!(f, "Invalid param {}: {}", index, reason),
             IndyCryptoError::InvalidState(ref description) => write!(f, "Invalid library state: {}", description),
             IndyCryptoError::InvalidStructure(ref description) => write!(f, "Invalid structure: {}", description),
             IndyCryptoError::IOError(ref err) => err.fmt(f),
+            #[cfg(feature = "serialization")]
+            IndyCryptoError::JsonError(ref err) => write!(f, "Invalid structure: {}", err),
+            #[cfg(feature = "bn_openssl")]
+            IndyCryptoError::OpenSSLError(ref err) => write!(f, "Invalid structure: {}", err),
+            IndyCryptoError::LoggerError(ref err) => write!(f, "Invalid library state: {}", err),
             IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(ref description) => write!(f, "Revocation accumulator is full: {}", description),
             IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(ref description) => write!(f, "Invalid revocation accumulator index: {}", description),
             IndyCryptoError::AnoncredsCredentialRevoked(ref description) => write!(f, "Credential revoked: {}", description),
             IndyCryptoError::AnoncredsProofRejected(ref description) => write!(f, "Proof rejected: {}", description),
+            IndyCryptoError::AnoncredsTailsIntegrityViolation(ref description) => write!(f, "Tails integrity violation: {}", description),
+            IndyCryptoError::InvalidPoint(ref description) => write!(f, "Invalid point: {}", description),
+            IndyCryptoError::InvalidSignature(ref description) => write!(f, "Invalid signature: {}", description),
+            IndyCryptoError::InvalidKey(ref description) => write!(f, "Invalid key: {}", description),
+            IndyCryptoError::RngFailure(ref description) => write!(f, "Random number generator failure: {}", description),
+            IndyCryptoError::NotEnoughShares(ref description) => write!(f, "Not enough shares: {}", description),
+            IndyCryptoError::Cancelled(ref description) => write!(f, "Operation cancelled: {}", description),
+            IndyCryptoError::Context { ref message, ref source, .. } => write!(f, "{}: {}", message, source),
+        }
+    }
+}
+
+/// Errors are equal if they are the same variant with the same message.
+impl PartialEq for IndyCryptoError {
+    fn eq(&self, other: &IndyCryptoError) -> bool {
+        match (self, other) {
+            (IndyCryptoError::IOError(a), IndyCryptoError::IOError(b)) =>
+                a.kind() == b.kind() && a.to_string() == b.to_string(),
+            (IndyCryptoError::Context { code: code_a, message: message_a, source: source_a },
+                IndyCryptoError::Context { code: code_b, message: message_b, source: source_b }) =>
+                code_a == code_b && message_a == message_b && source_a == source_b,
+            _ => self.kind() == other.kind() && self.to_string() == other.to_string()
         }
     }
 }
 
 impl Error for IndyCryptoError {
-    fn description(&self) -> &str {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
         match *self {
-            IndyCryptoError::InvalidParam1(ref description) => description,
-            IndyCryptoError::InvalidParam2(ref description) => description,
-            IndyCryptoError::InvalidParam3(ref description) => description,
-            IndyCryptoError::InvalidParam4(ref description) => description,
-            IndyCryptoError::InvalidParam5(ref description) => description,
-            IndyCryptoError::InvalidParam6(ref description) => description,
-            IndyCryptoError::InvalidParam7(ref description) => description,
-            IndyCryptoError::InvalidParam8(ref description) => description,
-            IndyCryptoError::InvalidParam9(ref description) => description,
-            IndyCryptoError::InvalidState(ref description) => description,
-            IndyCryptoError::InvalidStructure(ref description) => description,
-            IndyCryptoError::IOError(ref err) => err.description(),
-            IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(ref description) => description,
-            IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(ref description) => description,
-            IndyCryptoError::AnoncredsCredentialRevoked(ref description) => description,
-            IndyCryptoError::AnoncredsProofRejected(ref description) => description,
-        }
-    }
-
-    fn cause(&self) -> Option<&dyn Error> {
-        match *self {
-            IndyCryptoError::InvalidParam1(_) |
-            IndyCryptoError::InvalidParam2(_) |
-            IndyCryptoError::InvalidParam3(_) |
-            IndyCryptoError::InvalidParam4(_) |
-            IndyCryptoError::InvalidParam5(_) |
-            IndyCryptoError::InvalidParam6(_) |
-            IndyCryptoError::InvalidParam7(_) |
-            IndyCryptoError::InvalidParam8(_) |
-            IndyCryptoError::InvalidParam9(_) |
-            IndyCryptoError::InvalidState(_) |
-            IndyCryptoError::InvalidStructure(_) => None,
             IndyCryptoError::IOError(ref err) => Some(err),
-            IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(_) => None,
-            IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(_) => None,
-            IndyCryptoError::AnoncredsCredentialRevoked(_) => None,
-            IndyCryptoError::AnoncredsProofRejected(_) => None,
+            #[cfg(feature = "serialization")]
+            IndyCryptoError::JsonError(ref err) => Some(err),
+            #[cfg(feature = "bn_openssl")]
+            IndyCryptoError::OpenSSLError(ref err) => Some(err),
+            IndyCryptoError::LoggerError(ref err) => Some(err),
+            IndyCryptoError::Context { ref source, .. } => Some(source.as_ref()),
+            _ => None
         }
     }
 }
@@ -165,34 +373,509 @@ impl Error for IndyCryptoError {
 impl ToErrorCode for IndyCryptoError {
     fn to_error_code(&self) -> ErrorCode {
         match *self {
-            IndyCryptoError::InvalidParam1(_) => ErrorCode::CommonInvalidParam1,
-            IndyCryptoError::InvalidParam2(_) => ErrorCode::CommonInvalidParam2,
-            IndyCryptoError::InvalidParam3(_) => ErrorCode::CommonInvalidParam3,
-            IndyCryptoError::InvalidParam4(_) => ErrorCode::CommonInvalidParam4,
-            IndyCryptoError::InvalidParam5(_) => ErrorCode::CommonInvalidParam5,
-            IndyCryptoError::InvalidParam6(_) => ErrorCode::CommonInvalidParam6,
-            IndyCryptoError::InvalidParam7(_) => ErrorCode::CommonInvalidParam7,
-            IndyCryptoError::InvalidParam8(_) => ErrorCode::CommonInvalidParam8,
-            IndyCryptoError::InvalidParam9(_) => ErrorCode::CommonInvalidParam9,
+            IndyCryptoError::InvalidParam { index, .. } =>
+                ErrorCode::invalid_param(index as usize).unwrap_or(ErrorCode::CommonInvalidState),
             IndyCryptoError::InvalidState(_) => ErrorCode::CommonInvalidState,
             IndyCryptoError::InvalidStructure(_) => ErrorCode::CommonInvalidStructure,
             IndyCryptoError::IOError(_) => ErrorCode::CommonIOError,
+            #[cfg(feature = "serialization")]
+            IndyCryptoError::JsonError(_) => ErrorCode::CommonInvalidStructure,
+            // TODO: FIXME: Analyze ErrorStack and split invalid structure errors from other errors
+            #[cfg(feature = "bn_openssl")]
+            IndyCryptoError::OpenSSLError(_) => ErrorCode::CommonInvalidStructure,
+            IndyCryptoError::LoggerError(_) => ErrorCode::CommonInvalidState,
             IndyCryptoError::AnoncredsRevocationAccumulatorIsFull(_) => ErrorCode::AnoncredsRevocationAccumulatorIsFull,
             IndyCryptoError::AnoncredsInvalidRevocationAccumulatorIndex(_) => ErrorCode::AnoncredsInvalidRevocationAccumulatorIndex,
             IndyCryptoError::AnoncredsCredentialRevoked(_) => ErrorCode::AnoncredsCredentialRevoked,
             IndyCryptoError::AnoncredsProofRejected(_) => ErrorCode::AnoncredsProofRejected,
+            IndyCryptoError::AnoncredsTailsIntegrityViolation(_) => ErrorCode::AnoncredsTailsIntegrityViolation,
+            IndyCryptoError::InvalidPoint(_) => ErrorCode::CryptoInvalidPoint,
+            IndyCryptoError::InvalidSignature(_) => ErrorCode::CryptoInvalidSignature,
+            IndyCryptoError::InvalidKey(_) => ErrorCode::CryptoInvalidKey,
+            IndyCryptoError::RngFailure(_) => ErrorCode::CryptoRngFailure,
+            IndyCryptoError::NotEnoughShares(_) => ErrorCode::CryptoNotEnoughShares,
+            IndyCryptoError::Cancelled(_) => ErrorCode::CommonCancelled,
+            IndyCryptoError::Context { code, .. } => code,
         }
     }
 }
 
+#[cfg(feature = "serialization")]
 impl From<serde_json::Error> for IndyCryptoError {
     fn from(err: serde_json::Error) -> IndyCryptoError {
-        IndyCryptoError::InvalidStructure(err.to_string())
+        IndyCryptoError::JsonError(err)
+    }
+}
+
+#[cfg(feature = "bn_openssl")]
+impl From<ErrorStack> for IndyCryptoError {
+    fn from(err: ErrorStack) -> IndyCryptoError {
+        IndyCryptoError::OpenSSLError(err)
+    }
+}
+
+impl From<io::Error> for IndyCryptoError {
+    fn from(err: io::Error) -> IndyCryptoError {
+        IndyCryptoError::IOError(err)
+    }
+}
+
+impl From<NulError> for IndyCryptoError {
+    fn from(err: NulError) -> IndyCryptoError {
+        IndyCryptoError::InvalidStructure(format!("String contains interior nul byte: {}", err))
+    }
+}
+
+impl From<Utf8Error> for IndyCryptoError {
+    fn from(err: Utf8Error) -> IndyCryptoError {
+        IndyCryptoError::InvalidStructure(format!("String is not valid UTF-8: {}", err))
+    }
+}
+
+impl From<FromUtf8Error> for IndyCryptoError {
+    fn from(err: FromUtf8Error) -> IndyCryptoError {
+        IndyCryptoError::InvalidStructure(format!("String is not valid UTF-8: {}", err))
     }
 }
 
 impl From<log::SetLoggerError> for IndyCryptoError {
-    fn from(err: log::SetLoggerError) -> IndyCryptoError{
-        IndyCryptoError::InvalidState(err.description().to_owned())
+    fn from(err: log::SetLoggerError) -> IndyCryptoError {
+        IndyCryptoError::LoggerError(err)
+    }
+}
+
+/// Adds context to errors that are already `IndyCryptoError`.
+///
+/// Errors of other crates (serde, poisoned locks) and errors that change their kind,
+/// e.g. point errors returned as `InvalidKey`, are still mapped with `map_err`.
+pub trait ResultExt<T> {
+    /// Prefixes the error with the message, the error code is kept.
+    fn context<M>(self, message: M) -> Result<T, IndyCryptoError> where M: Into<String>;
+
+    /// Marks the error as caused by the FFI parameter with 1-based `index` (1..=12).
+    fn with_param(self, index: usize) -> Result<T, IndyCryptoError>;
+}
+
+impl<T> ResultExt<T> for Result<T, IndyCryptoError> {
+    fn context<M>(self, message: M) -> Result<T, IndyCryptoError> where M: Into<String> {
+        self.map_err(|err| IndyCryptoError::Context {
+            code: err.to_error_code(),
+            message: message.into(),
+            source: Box::new(err)
+        })
+    }
+
+    fn with_param(self, index: usize) -> Result<T, IndyCryptoError> {
+        debug_assert!(ErrorCode::invalid_param(index).is_some(), "Invalid param index: {}", index);
+        self.map_err(|err| IndyCryptoError::Context {
+            code: ErrorCode::invalid_param(index).unwrap_or(ErrorCode::CommonInvalidState),
+            message: format!("Invalid param {}", index),
+            source: Box::new(err)
+        })
+    }
+}
+
+impl From<IndyCryptoError> for ErrorCode {
+    fn from(err: IndyCryptoError) -> ErrorCode {
+        set_current_error(&err);
+        err.to_error_code()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CurrentError {
+    code: usize,
+    message: String,
+    backtrace: Option<String>
+}
+
+thread_local! {
+    static CURRENT_ERROR: RefCell<Option<CurrentError>> = const { RefCell::new(None) };
+}
+
+/// Remembers the error as the last one produced on the current thread and passes it to the error hook.
+///
+/// Must be called wherever an error crosses the FFI or wasm boundary and is reduced to an `ErrorCode`.
+pub fn set_current_error(err: &IndyCryptoError) {
+    let backtrace = Backtrace::capture();
+    let backtrace = match backtrace.status() {
+        BacktraceStatus::Captured => Some(backtrace.to_string()),
+        _ => None
+    };
+
+    CURRENT_ERROR.with(|current_error| {
+        *current_error.borrow_mut() = Some(CurrentError {
+            code: err.to_error_code() as usize,
+            message: err.to_string(),
+            backtrace
+        });
+    });
+
+    if ERROR_HOOK_IS_SET.load(Ordering::Relaxed) {
+        call_error_hook(err);
+    }
+}
+
+/// Returns message of the last error produced on the current thread.
+pub fn get_current_error_message() -> Option<String> {
+    CURRENT_ERROR.with(|current_error| current_error.borrow().as_ref().map(|err| err.message.clone()))
+}
+
+/// Forgets the last error produced on the current thread.
+pub fn clear_current_error() {
+    CURRENT_ERROR.with(|current_error| *current_error.borrow_mut() = None);
+}
+
+/// Returns the last error produced on the current thread as `{ "code", "message", "backtrace" }` json.
+#[cfg(feature = "serialization")]
+pub fn get_current_error_json() -> Option<String> {
+    CURRENT_ERROR.with(|current_error| {
+        current_error.borrow().as_ref().and_then(|err| serde_json::to_string(err).ok())
+    })
+}
+
+pub type ErrorHook = fn(&IndyCryptoError);
+
+pub(crate) type ErrorHookFn = Arc<dyn Fn(&IndyCryptoError) + Send + Sync>;
+
+lazy_static! {
+    static ref ERROR_HOOK: RwLock<Option<ErrorHookFn>> = RwLock::new(None);
+}
+
+static ERROR_HOOK_IS_SET: AtomicBool = AtomicBool::new(false);
+
+// Serializes tests that install the process wide error hook
+#[cfg(test)]
+lazy_static! {
+    pub(crate) static ref ERROR_HOOK_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+}
+
+thread_local! {
+    static IN_ERROR_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs a callback invoked with every error returned to a FFI or wasm caller.
+///
+/// The callback gets the error as the caller sees it (after context is added), errors handled
+/// inside the library are not passed.
+/// Panics raised by the callback are caught and ignored.
+pub fn set_error_hook(callback: ErrorHook) {
+    set_error_hook_fn(Some(Arc::new(callback)));
+}
+
+/// Removes the callback installed with `set_error_hook`.
+pub fn clear_error_hook() {
+    set_error_hook_fn(None);
+}
+
+pub(crate) fn set_error_hook_fn(hook: Option<ErrorHookFn>) {
+    let is_set = hook.is_some();
+    let mut error_hook = ERROR_HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *error_hook = hook;
+    ERROR_HOOK_IS_SET.store(is_set, Ordering::SeqCst);
+}
+
+fn call_error_hook(err: &IndyCryptoError) {
+    // The lock is released before the call, so a panicking hook can't poison it
+    let hook = match ERROR_HOOK.read() {
+        Ok(hook) => hook.clone(),
+        Err(poisoned) => poisoned.into_inner().clone()
+    };
+
+    if let Some(hook) = hook {
+        // Errors produced by the hook itself are not reported again
+        if IN_ERROR_HOOK.with(|in_hook| in_hook.replace(true)) {
+            return;
+        }
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| hook(err)));
+        IN_ERROR_HOOK.with(|in_hook| in_hook.set(false));
+    }
+}
+
+/// Serializable description of an error handed to callers that can carry more than a code (wasm).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorDetails {
+    pub code: usize,
+    pub name: &'static str,
+    pub message: String
+}
+
+impl<'a> From<&'a IndyCryptoError> for ErrorDetails {
+    fn from(err: &'a IndyCryptoError) -> ErrorDetails {
+        let code = err.to_error_code();
+        ErrorDetails {
+            code: code as usize,
+            name: code.name(),
+            message: err.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn source_works_for_json_error() {
+        let err: IndyCryptoError = serde_json::from_str::<Vec<u8>>("[1, 2").unwrap_err().into();
+
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
+        let source = err.source().unwrap();
+        assert!(source.downcast_ref::<serde_json::Error>().is_some());
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn get_current_error_json_works() {
+        let err = IndyCryptoError::InvalidStructure("structure".to_string());
+        let message = err.to_string();
+
+        let error_code: ErrorCode = err.into();
+        assert_eq!(ErrorCode::CommonInvalidStructure, error_code);
+
+        let current_error: serde_json::Value = serde_json::from_str(&get_current_error_json().unwrap()).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure as u64, current_error["code"].as_u64().unwrap());
+        assert_eq!(message, current_error["message"].as_str().unwrap());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serialization", feature = "pair_amcl"))]
+    fn error_details_serialization_works_for_garbage_ver_key() {
+        let err = crate::bls::VerKey::from_bytes(&[1; 128]).unwrap_err();
+        let details = ErrorDetails::from(&err);
+
+        let json: serde_json::Value = serde_json::to_value(&details).unwrap();
+        assert_eq!(ErrorCode::CryptoInvalidKey as u64, json["code"].as_u64().unwrap());
+        assert_eq!("CryptoInvalidKey", json["name"].as_str().unwrap());
+        assert_eq!(err.to_string(), json["message"].as_str().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn error_details_serialization_works_for_invalid_structure() {
+        let err = IndyCryptoError::InvalidStructure("Invalid argument".to_string());
+
+        let json = serde_json::to_string(&ErrorDetails::from(&err)).unwrap();
+        assert_eq!(r#"{"code":113,"name":"CommonInvalidStructure","message":"Invalid structure: Invalid argument"}"#, json);
+    }
+
+    #[test]
+    fn partial_eq_works() {
+        assert_eq!(IndyCryptoError::InvalidState("state".to_string()), IndyCryptoError::InvalidState("state".to_string()));
+        assert_ne!(IndyCryptoError::InvalidState("state".to_string()), IndyCryptoError::InvalidState("other".to_string()));
+        assert_ne!(IndyCryptoError::InvalidState("state".to_string()), IndyCryptoError::InvalidStructure("state".to_string()));
+        assert_ne!(IndyCryptoError::InvalidParam { index: 1, reason: "reason".to_string() },
+                   IndyCryptoError::InvalidParam { index: 2, reason: "reason".to_string() });
+    }
+
+    #[test]
+    fn partial_eq_works_for_io_error() {
+        let err = || IndyCryptoError::IOError(io::Error::new(io::ErrorKind::NotFound, "file"));
+        assert_eq!(err(), err());
+        assert_ne!(err(), IndyCryptoError::IOError(io::Error::new(io::ErrorKind::Other, "file")));
+        assert_ne!(err(), IndyCryptoError::IOError(io::Error::new(io::ErrorKind::NotFound, "other")));
+    }
+
+    #[test]
+    fn partial_eq_works_for_context() {
+        let err = |reason: &str| Err::<(), _>(IndyCryptoError::InvalidStructure(reason.to_string())).context("context").unwrap_err();
+        assert_eq!(err("reason"), err("reason"));
+        assert_ne!(err("reason"), err("other"));
+    }
+
+    #[test]
+    fn kind_works() {
+        assert_eq!(ErrorKind::InvalidKey, IndyCryptoError::InvalidKey("key".to_string()).kind());
+        assert_eq!(ErrorKind::InvalidParam, IndyCryptoError::InvalidParam { index: 1, reason: "reason".to_string() }.kind());
+    }
+
+    thread_local! {
+        static HOOK_CALLS: RefCell<Vec<ErrorCode>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn counting_hook(err: &IndyCryptoError) {
+        HOOK_CALLS.with(|calls| calls.borrow_mut().push(err.to_error_code()));
+    }
+
+    fn panicking_hook(_err: &IndyCryptoError) {
+        panic!("error hook panic");
+    }
+
+    fn hook_calls() -> Vec<ErrorCode> {
+        HOOK_CALLS.with(|calls| calls.borrow_mut().drain(..).collect())
+    }
+
+    #[test]
+    #[cfg(feature = "pair_amcl")]
+    fn set_error_hook_works() {
+        let _lock = ERROR_HOOK_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        hook_calls();
+
+        set_error_hook(counting_hook);
+        let _ = ErrorCode::from(crate::bls::VerKey::from_bytes(&[1, 2, 3]).unwrap_err());
+        let _ = ErrorCode::from(crate::pair::GroupOrderElement::new_from_seed(&[1, 2, 3]).unwrap_err());
+        // Errors that don't reach a caller aren't passed
+        crate::pair::PointG1::from_bytes(&[1, 2, 3]).unwrap_err();
+        clear_error_hook();
+
+        let _ = ErrorCode::from(crate::pair::PointG1::from_bytes(&[1, 2, 3]).unwrap_err());
+
+        assert_eq!(vec![ErrorCode::CryptoInvalidKey, ErrorCode::CommonInvalidStructure], hook_calls());
+    }
+
+    #[test]
+    #[cfg(feature = "pair_amcl")]
+    fn set_error_hook_works_for_panicking_hook() {
+        let _lock = ERROR_HOOK_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        hook_calls();
+
+        set_error_hook(panicking_hook);
+        let err = crate::pair::PointG2::from_bytes(&[1, 2, 3]).unwrap_err();
+        assert_eq!(ErrorCode::CryptoInvalidPoint, ErrorCode::from(err));
+
+        set_error_hook(counting_hook);
+        let _ = ErrorCode::from(crate::pair::PointG2::from_bytes(&[1, 2, 3]).unwrap_err());
+        clear_error_hook();
+
+        assert_eq!(vec![ErrorCode::CryptoInvalidPoint], hook_calls());
+    }
+
+    #[test]
+    fn from_error_code_works_for_all_codes() {
+        for code in ErrorCode::VALUES.iter().filter(|code| **code != ErrorCode::Success) {
+            let err = IndyCryptoError::from_error_code(*code, "message".to_string());
+            assert_eq!(*code, err.to_error_code());
+
+            let rehydrated = IndyCryptoError::from_error_code(err.to_error_code(), "message".to_string());
+            assert_eq!(err, rehydrated);
+        }
+    }
+
+    #[test]
+    fn from_error_code_works_for_success() {
+        let err = IndyCryptoError::from_error_code(ErrorCode::Success, "message".to_string());
+        assert_eq!(IndyCryptoError::InvalidState("message".to_string()), err);
+    }
+
+    #[test]
+    fn from_error_code_works_for_io_error() {
+        let err = IndyCryptoError::from_error_code(ErrorCode::CommonIOError, "file not found".to_string());
+        assert_eq!(IndyCryptoError::IOError(io::Error::new(io::ErrorKind::Other, "file not found")), err);
+        assert_eq!("file not found", err.to_string());
+    }
+
+    #[test]
+    fn from_error_code_works_for_invalid_param() {
+        let err = IndyCryptoError::from_error_code(ErrorCode::CommonInvalidParam12, "reason".to_string());
+        assert_eq!(IndyCryptoError::InvalidParam { index: 12, reason: "reason".to_string() }, err);
+    }
+
+    #[test]
+    fn error_code_try_from_works_for_all_codes() {
+        for code in ErrorCode::VALUES {
+            assert_eq!(*code, ErrorCode::try_from(*code as usize).unwrap());
+        }
+    }
+
+    #[test]
+    fn error_code_try_from_works_for_unknown_code() {
+        assert!(ErrorCode::try_from(99).is_err());
+        assert!(ErrorCode::try_from(1000).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn error_code_serde_works_for_all_codes() {
+        for code in ErrorCode::VALUES {
+            let json = serde_json::to_string(code).unwrap();
+            assert_eq!(format!("\"{}\"", code.name()), json);
+            assert_eq!(*code, serde_json::from_str::<ErrorCode>(&json).unwrap());
+        }
+    }
+
+    #[test]
+    fn error_code_display_works() {
+        assert_eq!("Success (0)", ErrorCode::Success.to_string());
+        assert_eq!("CommonInvalidStructure (113)", ErrorCode::CommonInvalidStructure.to_string());
+        assert_eq!("CryptoInvalidPoint (200)", ErrorCode::CryptoInvalidPoint.to_string());
+    }
+
+    #[test]
+    fn invalid_param_works_for_all_indexes() {
+        let codes = [
+            ErrorCode::CommonInvalidParam1,
+            ErrorCode::CommonInvalidParam2,
+            ErrorCode::CommonInvalidParam3,
+            ErrorCode::CommonInvalidParam4,
+            ErrorCode::CommonInvalidParam5,
+            ErrorCode::CommonInvalidParam6,
+            ErrorCode::CommonInvalidParam7,
+            ErrorCode::CommonInvalidParam8,
+            ErrorCode::CommonInvalidParam9,
+            ErrorCode::CommonInvalidParam10,
+            ErrorCode::CommonInvalidParam11,
+            ErrorCode::CommonInvalidParam12,
+        ];
+
+        for (i, code) in codes.iter().enumerate() {
+            let index = i as u8 + 1;
+            let err = IndyCryptoError::InvalidParam { index, reason: "reason".to_string() };
+
+            assert_eq!(format!("Invalid param {}: reason", index), err.to_string());
+            assert_eq!(*code, err.to_error_code());
+            assert_eq!(Some(index as usize), code.param_index());
+        }
+
+        assert_eq!(None, ErrorCode::CommonInvalidStructure.param_index());
+    }
+
+    #[test]
+    fn invalid_param_works_for_out_of_range_index() {
+        for index in &[0, 13] {
+            let err = IndyCryptoError::InvalidParam { index: *index, reason: "reason".to_string() };
+            assert_eq!(ErrorCode::CommonInvalidState, err.to_error_code());
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn invalid_param_works_for_deprecated_constructors() {
+        let err = IndyCryptoError::InvalidParam5("reason".to_string());
+        assert_eq!("Invalid param 5: reason", err.to_string());
+        assert_eq!(ErrorCode::CommonInvalidParam5, err.to_error_code());
+    }
+
+    #[test]
+    fn context_works() {
+        let res: Result<(), IndyCryptoError> = Err(IndyCryptoError::InvalidPoint("Point is not on the curve".to_string()));
+        let err = res.context("Invalid generator").unwrap_err();
+
+        assert_eq!(ErrorCode::CryptoInvalidPoint, err.to_error_code());
+        assert_eq!("Invalid generator: Invalid point: Point is not on the curve", err.to_string());
+        assert!(err.source().unwrap().downcast_ref::<IndyCryptoError>().is_some());
+    }
+
+    #[test]
+    fn with_param_works() {
+        let res: Result<(), IndyCryptoError> = Err(IndyCryptoError::InvalidStructure("Invalid len of seed".to_string()));
+        let err = res.with_param(2).unwrap_err();
+
+        assert_eq!(ErrorCode::CommonInvalidParam2, err.to_error_code());
+        assert_eq!("Invalid param 2: Invalid structure: Invalid len of seed", err.to_string());
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.source().unwrap().downcast_ref::<IndyCryptoError>().unwrap().to_error_code());
+    }
+
+    #[test]
+    fn source_works_for_io_error() {
+        let err = IndyCryptoError::IOError(io::Error::new(io::ErrorKind::Other, "io"));
+        assert!(err.source().unwrap().downcast_ref::<io::Error>().is_some());
+    }
+
+    #[test]
+    fn source_works_for_string_error() {
+        let err = IndyCryptoError::InvalidStructure("structure".to_string());
+        assert!(err.source().is_none());
     }
 }
\ No newline at end of file
@@ -1,1885 +1,6144 @@
 use crate::bls::*;
-
-use crate::errors::ErrorCode;
-use crate::errors::ToErrorCode;
+use crate::bls::threshold::{SignKeyShare, SignatureShare, Threshold};
+
+use crate::errors::{get_current_error_message, ErrorCode, IndyCryptoError, ResultExt};
+use crate::ffi::ctypes::{self, CTypesUtils};
+use crate::ffi::handles;
+use crate::pair::{GroupOrderElement, Pair, PointG1, PointG2, CURVE_NAME};
+use crate::utils::hex::{from_hex, to_hex};
+use crate::utils::zeroize::SecretBytes;
+use libc::c_char;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json;
+use std::ffi::CString;
 use std::os::raw::c_void;
+use std::panic;
+use std::ptr;
 use std::slice;
-
-/// Creates and returns random generator point that satisfy BLS algorithm requirements.
-///
-/// BLS algorithm requires choosing of generator point that must be known to all parties.
-/// The most of BLS methods require generator to be provided.
-///
-/// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
-///
-/// # Arguments
-/// * `gen_p` - Reference that will contain generator instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_generator_new(gen_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_new: >>> gen_p: {:?}", gen_p);
-
-    check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Generator::new() {
-        Ok(gen) => {
-            trace!("indy_crypto_bls_generator_new: gen: {:?}", gen);
-            unsafe {
-                *gen_p = Box::into_raw(Box::new(gen)) as *const c_void;
-                trace!("indy_crypto_bls_generator_new: *gen_p: {:?}", *gen_p);
+use std::thread;
+
+ffi_fn! {
+    /// Creates and returns random generator point that satisfy BLS algorithm requirements.
+    ///
+    /// BLS algorithm requires choosing of generator point that must be known to all parties.
+    /// The most of BLS methods require generator to be provided.
+    ///
+    /// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
+    ///
+    /// # Arguments
+    /// * `gen_p` - Reference that will contain generator instance pointer
+    pub fn indy_crypto_bls_generator_new(gen_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_new: >>> gen_p: {:?}", gen_p);
+
+        check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Generator::new() {
+            Ok(gen) => {
+                trace!("indy_crypto_bls_generator_new: gen: {:?}", gen);
+                unsafe {
+                    *gen_p = register_c_reference!(gen);
+                    trace!("indy_crypto_bls_generator_new: *gen_p: {:?}", *gen_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_generator_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns generator point from bytes representation.
-///
-/// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
-///
-/// # Arguments
-/// * `bytes` - Bytes buffer pointer
-/// * `bytes_len` - Bytes buffer len
-/// * `gen_p` - Reference that will contain generator instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_generator_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                   gen_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, gen_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_generator_from_bytes: bytes: {:?}", bytes);
-
-    let res = match Generator::from_bytes(bytes) {
-        Ok(gen) => {
-            trace!("indy_crypto_bls_generator_from_bytes: gen: {:?}", gen);
-            unsafe {
-                *gen_p = Box::into_raw(Box::new(gen)) as *const c_void;
-                trace!("indy_crypto_bls_generator_from_bytes: *gen_p: {:?}", *gen_p);
+ffi_fn! {
+    /// Creates and returns generator point from bytes representation.
+    ///
+    /// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `gen_p` - Reference that will contain generator instance pointer
+    pub fn indy_crypto_bls_generator_from_bytes(bytes: *const u8, bytes_len: usize,
+                                                gen_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, gen_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_generator_from_bytes: bytes: {:?}", bytes);
+
+        let res = match Generator::from_bytes(bytes) {
+            Ok(gen) => {
+                trace!("indy_crypto_bls_generator_from_bytes: gen: {:?}", gen);
+                unsafe {
+                    *gen_p = register_c_reference!(gen);
+                    trace!("indy_crypto_bls_generator_from_bytes: *gen_p: {:?}", *gen_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_generator_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_from_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns bytes representation of generator point.
-///
-/// Note: Returned buffer lifetime is the same as generator instance.
-///
-/// # Arguments
-/// * `gen` - Generator instance pointer
-/// * `bytes_p` - Pointer that will contains bytes buffer
-/// * `bytes_len_p` - Pointer that will contains bytes buffer len
-#[no_mangle]
-pub extern fn indy_crypto_bls_generator_as_bytes(gen: *const c_void,
-                                                 bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", gen, bytes_p, bytes_len_p);
-
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+ffi_fn! {
+    /// Returns bytes representation of generator point.
+    ///
+    /// Note: Returned buffer lifetime is the same as generator instance.
+    ///
+    /// # Arguments
+    /// * `gen` - Generator instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_generator_as_bytes(gen: *const c_void,
+                                              bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", gen, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}", gen);
+
+        unsafe {
+            *bytes_p = gen.as_bytes().as_ptr();
+            *bytes_len_p = gen.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_generator_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
 
-    trace!("indy_crypto_bls_generator_as_bytes: >>> gen: {:?}", gen);
+ffi_fn! {
+    /// Writes bytes representation of generator into the caller allocated buffer.
+    ///
+    /// The caller owns the buffer, nothing has to be freed later. Size of representation is always
+    /// written to `written_p`: call with null buffer and zero len to query it. If the buffer is too small
+    /// CommonInvalidStructure is returned and nothing is written into the buffer.
+    ///
+    /// # Arguments
+    /// * `gen` - Generator instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_generator_as_bytes_into(gen: *const c_void,
+                                                   buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_as_bytes_into: >>> gen: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", gen, buf, buf_len, written_p);
+
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
 
-    unsafe {
-        *bytes_p = gen.as_bytes().as_ptr();
-        *bytes_len_p = gen.as_bytes().len();
-    };
+        trace!("indy_crypto_bls_generator_as_bytes_into: gen: {:?}", gen);
 
-    let res = ErrorCode::Success;
+        let res = _write_into_buffer(gen.as_bytes(), buf, buf_len, written_p);
 
-    trace!("indy_crypto_bls_generator_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates generator instance.
-///
-/// # Arguments
-/// * `gen` - Generator instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_generator_free(gen: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_generator_free: >>> gen: {:?}", gen);
+ffi_fn! {
+    /// Writes compressed bytes representation of generator point into the caller allocated buffer.
+    ///
+    /// Compressed representation is 65 bytes long, see indy_crypto_bls_generator_from_compressed_bytes.
+    /// Size of representation is always written to `written_p`: call with null buffer and zero len
+    /// to query it. If the buffer is too small CommonInvalidStructure is returned and nothing is
+    /// written into the buffer.
+    ///
+    /// # Arguments
+    /// * `gen` - Generator instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of compressed bytes representation
+    pub fn indy_crypto_bls_generator_as_compressed_bytes(gen: *const c_void,
+                                                         buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_as_compressed_bytes: >>> gen: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", gen, buf, buf_len, written_p);
+
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
 
-    check_useful_c_ptr!(gen, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_bls_generator_as_compressed_bytes: gen: {:?}", gen);
 
-    unsafe { let _ = Box::from_raw(gen as *mut Generator); }
-    let res = ErrorCode::Success;
+        let res = match gen.as_compressed_bytes() {
+            Ok(bytes) => _write_into_buffer(&bytes, buf, buf_len, written_p),
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_generator_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_as_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns random (or seeded from seed) BLS sign key algorithm requirements.
-///
-/// Note: Sign Key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free.
-///
-/// # Arguments
-/// * `seed` - Seed buffer pointer. For random generation null must be passed.
-/// * `seed` - Seed buffer len.
-/// * `gen_p` - Reference that will contain sign key instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_sign_key_new(seed: *const u8,
-                                           seed_len: usize,
-                                           sign_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_new: >>> seed: {:?}, seed_len: {:?}, sign_key_p: {:?}", seed, seed_len, sign_key_p);
-
-    check_useful_opt_c_byte_array!(seed, seed_len,
+ffi_fn! {
+    /// Creates and returns generator point from compressed bytes representation.
+    ///
+    /// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Compressed bytes buffer pointer
+    /// * `bytes_len` - Compressed bytes buffer len, must be 65
+    /// * `gen_p` - Reference that will contain generator point instance pointer
+    pub fn indy_crypto_bls_generator_from_compressed_bytes(bytes: *const u8, bytes_len: usize,
+                                                           gen_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_from_compressed_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, gen_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
                                    ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam3);
+        if bytes_len != Generator::COMPRESSED_BYTES_SIZE {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2,
+                                         format!("`bytes_len` must be {}, actual {}", Generator::COMPRESSED_BYTES_SIZE, bytes_len));
+        }
 
-    trace!("indy_crypto_bls_sign_key_new: seed: {:?}", secret!(&seed));
+        trace!("indy_crypto_bls_generator_from_compressed_bytes: bytes: {:?}", bytes);
 
-    let res = match SignKey::new(seed) {
-        Ok(sign_key) => {
-            trace!("indy_crypto_bls_generator_new: gen: {:?}", secret!(&sign_key));
-            unsafe {
-                *sign_key_p = Box::into_raw(Box::new(sign_key)) as *const c_void;
-                trace!("indy_crypto_bls_sign_key_new: *sign_key_p: {:?}", *sign_key_p);
+        let res = match Generator::from_compressed_bytes(bytes) {
+            Ok(gen) => {
+                trace!("indy_crypto_bls_generator_from_compressed_bytes: gen: {:?}", gen);
+                unsafe {
+                    *gen_p = register_c_reference!(gen);
+                    trace!("indy_crypto_bls_generator_from_compressed_bytes: *gen_p: {:?}", *gen_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_from_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns sign key from bytes representation.
-///
-/// Note: Sign key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free
-///
-/// # Arguments
-/// * `bytes` - Bytes buffer pointer
-/// * `bytes_len` - Bytes buffer len
-/// * `sign_key_p` - Reference that will contain sign key instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_sign_key_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                  sign_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, sign_key_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_sign_key_from_bytes: bytes: {:?}", secret!(&bytes));
-
-    let res = match SignKey::from_bytes(bytes) {
-        Ok(sign_key) => {
-            trace!("indy_crypto_bls_sign_key_from_bytes: sign_key: {:?}", secret!(&sign_key));
-            unsafe {
-                *sign_key_p = Box::into_raw(Box::new(sign_key)) as *const c_void;
-                trace!("indy_crypto_bls_sign_key_from_bytes: *sign_key_p: {:?}", *sign_key_p);
+ffi_fn! {
+    /// Creates and returns generator from hex string of its bytes representation.
+    ///
+    /// Hex string must have even length, lower or upper case hex digits only and encode exactly
+    /// Generator::BYTES_SIZE bytes, otherwise CommonInvalidParam1 is returned.
+    ///
+    /// Note: Generator instance deallocation must be performed by calling indy_crypto_bls_generator_free
+    ///
+    /// # Arguments
+    /// * `hex` - Hex string of bytes representation
+    /// * `gen_p` - Reference that will contain generator instance pointer
+    pub fn indy_crypto_bls_generator_from_hex(hex: *const c_char,
+                                              gen_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_from_hex: >>> hex: {:?}, gen_p: {:?}", hex, gen_p);
+
+        check_useful_c_str!(hex, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(gen_p, ErrorCode::CommonInvalidParam2);
+
+        let res = match _param_from_hex::<Generator>("hex", &hex, ErrorCode::CommonInvalidParam1) {
+            Ok(gen) => {
+                trace!("indy_crypto_bls_generator_from_hex: gen: {:?}", gen);
+                unsafe {
+                    *gen_p = register_c_reference!(gen);
+                    trace!("indy_crypto_bls_generator_from_hex: *gen_p: {:?}", *gen_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(res) => res
+        };
 
-    trace!("indy_crypto_bls_sign_key_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_from_hex: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns bytes representation of sign key.
-///
-/// Note: Returned buffer lifetime is the same as sign key instance.
-///
-/// # Arguments
-/// * `sign_key` - Sign key instance pointer
-/// * `bytes_p` - Pointer that will contains bytes buffer
-/// * `bytes_len_p` - Pointer that will contains bytes buffer len
-#[no_mangle]
-pub extern fn indy_crypto_bls_sign_key_as_bytes(sign_key: *const c_void,
-                                                bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_as_bytes: >>> sign_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", sign_key, bytes_p, bytes_len_p);
+ffi_fn! {
+    /// Returns lower case hex string of generator bytes representation.
+    ///
+    /// Note: Hex string deallocation must be performed by calling indy_crypto_string_free
+    ///
+    /// # Arguments
+    /// * `gen` - Generator instance pointer
+    /// * `hex_p` - Reference that will contain hex string
+    pub fn indy_crypto_bls_generator_to_hex(gen: *const c_void,
+                                            hex_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_to_hex: >>> gen: {:?}, hex_p: {:?}", gen, hex_p);
+
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(hex_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_generator_to_hex: gen: {:?}", gen);
+
+        let res = match CTypesUtils::string_to_cstring(to_hex(gen.as_bytes())) {
+            Ok(hex) => {
+                unsafe {
+                    *hex_p = hex.into_raw();
+                    trace!("indy_crypto_bls_generator_to_hex: *hex_p: {:?}", *hex_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        trace!("indy_crypto_bls_generator_to_hex: <<< res: {:?}", res);
+        res
+    }
+}
 
-    trace!("indy_crypto_bls_sign_key_as_bytes: sign_key: {:?}", secret!(sign_key));
+ffi_fn! {
+    /// Deallocates generator instance.
+    ///
+    /// # Arguments
+    /// * `gen` - Generator instance pointer
+    pub fn indy_crypto_bls_generator_free(gen: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_generator_free: >>> gen: {:?}", gen);
 
-    unsafe {
-        *bytes_p = sign_key.as_bytes().as_ptr();
-        *bytes_len_p = sign_key.as_bytes().len();
-    };
+        check_useful_c_ptr!(gen, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_sign_key_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_generator_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates sign key instance.
-///
-/// # Arguments
-/// * `sign_key` - Sign key instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_sign_key_free(sign_key: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(sign_key, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_sign_key_free: >>> sign_key: {:?}", secret!(sign_key));
-
-    unsafe { let _ = Box::from_raw(sign_key as *mut SignKey); }
-    let res = ErrorCode::Success;
+ffi_fn! {
+    /// Creates and returns random (or seeded from seed) BLS sign key algorithm requirements.
+    ///
+    /// Note: Sign Key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed buffer pointer. For random generation null must be passed.
+    /// * `seed` - Seed buffer len.
+    /// * `gen_p` - Reference that will contain sign key instance pointer
+    pub fn indy_crypto_bls_sign_key_new(seed: *const u8,
+                                        seed_len: usize,
+                                        sign_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_new: >>> seed: {:?}, seed_len: {:?}, sign_key_p: {:?}", seed, seed_len, sign_key_p);
+
+        check_useful_opt_c_byte_array!(seed, seed_len,
+                                       ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_sign_key_new: seed: {:?}", secret!(&seed));
+
+        // Only the seed can be wrong here, its length is checked by SignKey::new
+        let res = match SignKey::new(seed).with_param(2) {
+            Ok(sign_key) => {
+                trace!("indy_crypto_bls_generator_new: gen: {:?}", secret!(&sign_key));
+                unsafe {
+                    *sign_key_p = register_c_reference!(sign_key);
+                    trace!("indy_crypto_bls_sign_key_new: *sign_key_p: {:?}", *sign_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_sign_key_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns BLS ver key that corresponds to sign key.
-///
-/// Note: Verification key instance deallocation must be performed by calling indy_crypto_bls_ver_key_free.
-///
-/// # Arguments
-/// * `gen` - Generator point instance
-/// * `sign_key` - Sign key instance
-/// * `ver_key_p` - Reference that will contain verification key instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_new(gen: *const c_void,
-                                          sign_key: *const c_void,
-                                          ver_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_ver_key_new: >>> gen: {:?}, sign_key: {:?}, ver_key_p: {:?}", gen, sign_key, ver_key_p);
-
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_bls_ver_key_new: gen: {:?}, sign_key: {:?}", gen, secret!(sign_key));
-
-    let res = match VerKey::new(gen, sign_key) {
-        Ok(ver_key) => {
-            trace!("indy_crypto_bls_ver_key_new: ver_key: {:?}", ver_key);
-            unsafe {
-                *ver_key_p = Box::into_raw(Box::new(ver_key)) as *const c_void;
-                trace!("indy_crypto_bls_ver_key_new: *ver_key_p: {:?}", *ver_key_p);
+ffi_fn! {
+    /// Creates and returns sign key from bytes representation.
+    ///
+    /// Note: Sign key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `sign_key_p` - Reference that will contain sign key instance pointer
+    pub fn indy_crypto_bls_sign_key_from_bytes(bytes: *const u8, bytes_len: usize,
+                                               sign_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, sign_key_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_sign_key_from_bytes: bytes: {:?}", secret!(&bytes));
+
+        let res = match SignKey::from_bytes(bytes) {
+            Ok(sign_key) => {
+                trace!("indy_crypto_bls_sign_key_from_bytes: sign_key: {:?}", secret!(&sign_key));
+                unsafe {
+                    *sign_key_p = register_c_reference!(sign_key);
+                    trace!("indy_crypto_bls_sign_key_from_bytes: *sign_key_p: {:?}", *sign_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_from_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns verification key from bytes representation.
-///
-/// Note: Verification key instance deallocation must be performed by calling indy_crypto_bls_very_key_free
-///
-/// # Arguments
-/// * `bytes` - Bytes buffer pointer
-/// * `bytes_len` - Bytes buffer len
-/// * `ver_key_p` - Reference that will contain verification key instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                 ver_key_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_ver_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, ver_key_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(ver_key_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_ver_key_from_bytes: bytes: {:?}", bytes);
-
-    let res = match VerKey::from_bytes(bytes) {
-        Ok(ver_key) => {
-            trace!("indy_crypto_bls_ver_key_from_bytes: sign_key: {:?}", ver_key);
-            unsafe {
-                *ver_key_p = Box::into_raw(Box::new(ver_key)) as *const c_void;
-                trace!("indy_crypto_bls_ver_key_from_bytes: *ver_key_p: {:?}", *ver_key_p);
-            }
-            ErrorCode::Success
+ffi_fn! {
+    /// Returns bytes representation of sign key.
+    ///
+    /// Note: Returned buffer lifetime is the same as sign key instance.
+    ///
+    /// # Arguments
+    /// * `sign_key` - Sign key instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_sign_key_as_bytes(sign_key: *const c_void,
+                                             bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_as_bytes: >>> sign_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", sign_key, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_sign_key_as_bytes: sign_key: {:?}", secret!(sign_key));
+
+        unsafe {
+            *bytes_p = sign_key.as_bytes().as_ptr();
+            *bytes_len_p = sign_key.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_sign_key_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Writes bytes representation of sign key into the caller allocated buffer.
+    ///
+    /// Buffer handling is the same as of `indy_crypto_bls_generator_as_bytes_into`. The buffer receives
+    /// secret bytes, wiping it is the caller's responsibility.
+    ///
+    /// # Arguments
+    /// * `sign_key` - Sign key instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_sign_key_as_bytes_into(sign_key: *const c_void,
+                                                  buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_as_bytes_into: >>> sign_key: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", sign_key, buf, buf_len, written_p);
+
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
         }
-        Err(err) => err.to_error_code()
-    };
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_crypto_bls_ver_key_from_bytes: <<< res: {:?}", res);
-    res
-}
+        trace!("indy_crypto_bls_sign_key_as_bytes_into: sign_key: {:?}", sign_key);
 
-/// Returns bytes representation of verification key.
-///
-/// Note: Returned buffer lifetime is the same as verification key instance.
-///
-/// # Arguments
-/// * `ver_key` - Verification key instance pointer
-/// * `bytes_p` - Pointer that will contains bytes buffer
-/// * `bytes_len_p` - Pointer that will contains bytes buffer len
-#[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_as_bytes(ver_key: *const c_void,
-                                               bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_sign_key_as_bytes: >>> ver_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", ver_key, bytes_p, bytes_len_p);
+        let res = _write_into_buffer(sign_key.as_bytes(), buf, buf_len, written_p);
 
-    check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        trace!("indy_crypto_bls_sign_key_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
+}
 
-    trace!("indy_crypto_bls_ver_key_as_bytes: ver_key: {:?}", ver_key);
+ffi_fn! {
+    /// Creates and returns sign key from hex string of its bytes representation.
+    ///
+    /// Hex string must have even length, lower or upper case hex digits only and encode exactly
+    /// SignKey::BYTES_SIZE bytes, otherwise CommonInvalidParam1 is returned.
+    ///
+    /// Note: Sign key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free
+    ///
+    /// # Arguments
+    /// * `hex` - Hex string of bytes representation
+    /// * `sign_key_p` - Reference that will contain sign key instance pointer
+    pub fn indy_crypto_bls_sign_key_from_hex(hex: *const c_char,
+                                             sign_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_from_hex: >>> hex: {:?}, sign_key_p: {:?}", hex, sign_key_p);
+
+        check_useful_c_str!(hex, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam2);
+
+        let res = match _param_from_hex::<SignKey>("hex", &hex, ErrorCode::CommonInvalidParam1) {
+            Ok(sign_key) => {
+                trace!("indy_crypto_bls_sign_key_from_hex: sign_key: {:?}", secret!(&sign_key));
+                unsafe {
+                    *sign_key_p = register_c_reference!(sign_key);
+                    trace!("indy_crypto_bls_sign_key_from_hex: *sign_key_p: {:?}", *sign_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(res) => res
+        };
 
-    unsafe {
-        *bytes_p = ver_key.as_bytes().as_ptr();
-        *bytes_len_p = ver_key.as_bytes().len();
-    };
+        trace!("indy_crypto_bls_sign_key_from_hex: <<< res: {:?}", res);
+        res
+    }
+}
 
-    let res = ErrorCode::Success;
+ffi_fn! {
+    /// Returns lower case hex string of sign key bytes representation.
+    ///
+    /// Note: Hex string deallocation must be performed by calling indy_crypto_string_free.
+    /// The string holds the secret key and is not zeroized on deallocation.
+    ///
+    /// # Arguments
+    /// * `sign_key` - Sign key instance pointer
+    /// * `hex_p` - Reference that will contain hex string
+    pub fn indy_crypto_bls_sign_key_to_hex(sign_key: *const c_void,
+                                           hex_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_to_hex: >>> sign_key: {:?}, hex_p: {:?}", sign_key, hex_p);
+
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(hex_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_sign_key_to_hex: sign_key: {:?}", secret!(&sign_key));
+
+        let res = match CTypesUtils::string_to_cstring(to_hex(sign_key.as_bytes())) {
+            Ok(hex) => {
+                unsafe {
+                    *hex_p = hex.into_raw();
+                    trace!("indy_crypto_bls_sign_key_to_hex: *hex_p: {:?}", *hex_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_ver_key_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_to_hex: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates verification key instance.
-///
-/// # Arguments
-/// * `ver_key` - Verification key instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_ver_key_free(ver_key: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(ver_key, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Deallocates sign key instance.
+    ///
+    /// The secret scalar and its bytes representation are overwritten with zeros before the memory
+    /// is returned to the allocator. Freeing already freed handle is no-op, other handles are
+    /// reported as CommonInvalidParam1 and left untouched.
+    ///
+    /// # Arguments
+    /// * `sign_key` - Sign key instance pointer
+    pub fn indy_crypto_bls_sign_key_free(sign_key: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(sign_key, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_ver_key_free: >>> ver_key: {:?}", ver_key);
+        trace!("indy_crypto_bls_sign_key_free: >>> sign_key: {:?}", secret!(sign_key));
 
-    unsafe { let _ = Box::from_raw(ver_key as *mut VerKey); }
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_ver_key_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns BLS proof of possession that corresponds to ver key and sign key.
-///
-/// Note: Proof of possession instance deallocation must be performed by calling indy_crypto_bls_pop_free.
-///
-/// # Arguments
-/// * `ver_key` - Ver key instance
-/// * `sign_key` - Sign key instance
-/// * `pop_p` - Reference that will contain proof of possession instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_pop_new(ver_key: *const c_void,
-                                      sign_key: *const c_void,
-                                      pop_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_pop_new: >>> ver_key: {:?}, sign_key: {:?}, pop_p: {:?}", ver_key, sign_key, pop_p);
-
-    check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_bls_pop_new: ver_key: {:?}, sign_key: {:?}", ver_key, sign_key);
-
-    let res = match ProofOfPossession::new(ver_key, sign_key) {
-        Ok(pop) => {
-            trace!("indy_crypto_bls_pop_new: pop: {:?}", pop);
-            unsafe {
-                *pop_p = Box::into_raw(Box::new(pop)) as *const c_void;
-                trace!("indy_crypto_bls_pop_new: *pop_p: {:?}", *pop_p);
+ffi_fn! {
+    /// Creates and returns BLS ver key that corresponds to sign key.
+    ///
+    /// Note: Verification key instance deallocation must be performed by calling indy_crypto_bls_ver_key_free.
+    ///
+    /// # Arguments
+    /// * `gen` - Generator point instance
+    /// * `sign_key` - Sign key instance
+    /// * `ver_key_p` - Reference that will contain verification key instance pointer
+    pub fn indy_crypto_bls_ver_key_new(gen: *const c_void,
+                                       sign_key: *const c_void,
+                                       ver_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_new: >>> gen: {:?}, sign_key: {:?}, ver_key_p: {:?}", gen, sign_key, ver_key_p);
+
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_ver_key_new: gen: {:?}, sign_key: {:?}", gen, secret!(sign_key));
+
+        let res = match VerKey::new(gen, sign_key) {
+            Ok(ver_key) => {
+                trace!("indy_crypto_bls_ver_key_new: ver_key: {:?}", ver_key);
+                unsafe {
+                    *ver_key_p = register_c_reference!(ver_key);
+                    trace!("indy_crypto_bls_ver_key_new: *ver_key_p: {:?}", *ver_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_pop_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_sign_key_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns proof of possession from bytes representation.
-///
-/// Note: Proof of possession instance deallocation must be performed by calling indy_crypto_bls_pop_free
-///
-/// # Arguments
-/// * `bytes` - Bytes buffer pointer
-/// * `bytes_len` - Bytes buffer len
-/// * `pop_p` - Reference that will contain proof of possession instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_pop_from_bytes(bytes: *const u8, bytes_len: usize,
-                                             pop_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_pop_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, pop_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(pop_p, ErrorCode::CommonInvalidParam3);
-
-    trace!("indy_crypto_bls_pop_from_bytes: bytes: {:?}", bytes);
-
-    let res = match ProofOfPossession::from_bytes(bytes) {
-        Ok(pop) => {
-            trace!("indy_crypto_bls_pop_from_bytes: pop: {:?}", pop);
-            unsafe {
-                *pop_p = Box::into_raw(Box::new(pop)) as *const c_void;
-                trace!("indy_crypto_bls_pop_from_bytes: *pop_p: {:?}", *pop_p);
+ffi_fn! {
+    /// Creates and returns verification key from bytes representation.
+    ///
+    /// Note: Verification key instance deallocation must be performed by calling indy_crypto_bls_very_key_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `ver_key_p` - Reference that will contain verification key instance pointer
+    pub fn indy_crypto_bls_ver_key_from_bytes(bytes: *const u8, bytes_len: usize,
+                                              ver_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, ver_key_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(ver_key_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_ver_key_from_bytes: bytes: {:?}", bytes);
+
+        let res = match VerKey::from_bytes(bytes) {
+            Ok(ver_key) => {
+                trace!("indy_crypto_bls_ver_key_from_bytes: sign_key: {:?}", ver_key);
+                unsafe {
+                    *ver_key_p = register_c_reference!(ver_key);
+                    trace!("indy_crypto_bls_ver_key_from_bytes: *ver_key_p: {:?}", *ver_key_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_pop_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_from_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns bytes representation of proof of possession.
-///
-/// Note: Returned buffer lifetime is the same as proof of possession instance.
-///
-/// # Arguments
-/// * `pop` - Proof of possession instance pointer
-/// * `bytes_p` - Pointer that will contains bytes buffer
-/// * `bytes_len_p` - Pointer that will contains bytes buffer len
-#[no_mangle]
-pub extern fn indy_crypto_bls_pop_as_bytes(pop: *const c_void,
-                                           bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_pop_as_bytes: >>> pop: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", pop, bytes_p, bytes_len_p);
-
-    check_useful_c_reference!(pop, ProofOfPossession, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+ffi_fn! {
+    /// Returns bytes representation of verification key.
+    ///
+    /// Note: Returned buffer lifetime is the same as verification key instance.
+    ///
+    /// # Arguments
+    /// * `ver_key` - Verification key instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_ver_key_as_bytes(ver_key: *const c_void,
+                                            bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_as_bytes: >>> ver_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", ver_key, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_ver_key_as_bytes: ver_key: {:?}", ver_key);
+
+        unsafe {
+            *bytes_p = ver_key.as_bytes().as_ptr();
+            *bytes_len_p = ver_key.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_ver_key_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
 
-    trace!("indy_crypto_bls_pop_as_bytes: pop: {:?}", pop);
+ffi_fn! {
+    /// Writes bytes representation of verification key into the caller allocated buffer.
+    ///
+    /// Buffer handling is the same as of `indy_crypto_bls_generator_as_bytes_into`.
+    ///
+    /// # Arguments
+    /// * `ver_key` - Verification key instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_ver_key_as_bytes_into(ver_key: *const c_void,
+                                                 buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_as_bytes_into: >>> ver_key: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", ver_key, buf, buf_len, written_p);
+
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
 
-    unsafe {
-        *bytes_p = pop.as_bytes().as_ptr();
-        *bytes_len_p = pop.as_bytes().len();
-    };
+        trace!("indy_crypto_bls_ver_key_as_bytes_into: ver_key: {:?}", ver_key);
 
-    let res = ErrorCode::Success;
+        let res = _write_into_buffer(ver_key.as_bytes(), buf, buf_len, written_p);
 
-    trace!("indy_crypto_bls_pop_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates proof of possession instance.
-///
-/// # Arguments
-/// * `pop` - Proof of possession instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_pop_free(pop: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(pop, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Writes compressed bytes representation of verification key into the caller allocated buffer.
+    ///
+    /// Compressed representation is 65 bytes long, see indy_crypto_bls_ver_key_from_compressed_bytes.
+    /// Size of representation is always written to `written_p`: call with null buffer and zero len
+    /// to query it. If the buffer is too small CommonInvalidStructure is returned and nothing is
+    /// written into the buffer.
+    ///
+    /// # Arguments
+    /// * `ver_key` - Verification key instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of compressed bytes representation
+    pub fn indy_crypto_bls_ver_key_as_compressed_bytes(ver_key: *const c_void,
+                                                       buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_as_compressed_bytes: >>> ver_key: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", ver_key, buf, buf_len, written_p);
+
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_crypto_bls_pop_free: >>> pop: {:?}", pop);
+        trace!("indy_crypto_bls_ver_key_as_compressed_bytes: ver_key: {:?}", ver_key);
 
-    unsafe { let _ = Box::from_raw(pop as *mut ProofOfPossession); }
-    let res = ErrorCode::Success;
+        let res = match ver_key.as_compressed_bytes() {
+            Ok(bytes) => _write_into_buffer(&bytes, buf, buf_len, written_p),
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_pop_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_as_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns signature from bytes representation.
-///
-/// Note: Signature instance deallocation must be performed by calling indy_crypto_bls_signature_free
-///
-/// # Arguments
-/// * `bytes` - Bytes buffer pointer
-/// * `bytes_len` - Bytes buffer len
-/// * `signature_p` - Reference that will contain signature instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_signature_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                   signature_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, signature_p: {:?}", bytes, bytes_len, signature_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_signature_from_bytes: bytes: {:?}", bytes);
-
-    let res = match Signature::from_bytes(bytes) {
-        Ok(signature) => {
-            trace!("indy_crypto_bls_signature_from_bytes: signature: {:?}", signature);
-            unsafe {
-                *signature_p = Box::into_raw(Box::new(signature)) as *const c_void;
-                trace!("indy_crypto_bls_signature_from_bytes: *signature_p: {:?}", *signature_p);
-            }
-            ErrorCode::Success
+ffi_fn! {
+    /// Creates and returns verification key from compressed bytes representation.
+    ///
+    /// Note: Verification key instance deallocation must be performed by calling indy_crypto_bls_ver_key_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Compressed bytes buffer pointer
+    /// * `bytes_len` - Compressed bytes buffer len, must be 65
+    /// * `ver_key_p` - Reference that will contain verification key instance pointer
+    pub fn indy_crypto_bls_ver_key_from_compressed_bytes(bytes: *const u8, bytes_len: usize,
+                                                         ver_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_from_compressed_bytes: >>> bytes: {:?}, bytes_len: {:?}, ver_key_p: {:?}", bytes, bytes_len, ver_key_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(ver_key_p, ErrorCode::CommonInvalidParam3);
+        if bytes_len != VerKey::COMPRESSED_BYTES_SIZE {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2,
+                                         format!("`bytes_len` must be {}, actual {}", VerKey::COMPRESSED_BYTES_SIZE, bytes_len));
         }
-        Err(err) => err.to_error_code()
-    };
 
-    trace!("indy_crypto_bls_signature_from_bytes: <<< res: {:?}", res);
-    res
-}
+        trace!("indy_crypto_bls_ver_key_from_compressed_bytes: bytes: {:?}", bytes);
 
-/// Returns bytes representation of signature.
-///
-/// Note: Returned buffer lifetime is the same as signature instance.
-///
-/// # Arguments
-/// * `signature` - Signature instance pointer
-/// * `bytes_p` - Pointer that will contains bytes buffer
-/// * `bytes_len_p` - Pointer that will contains bytes buffer len
-#[no_mangle]
-pub extern fn indy_crypto_bls_signature_as_bytes(signature: *const c_void,
-                                                 bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_signature_as_bytes: >>> signature: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", signature, bytes_p, bytes_len_p);
+        let res = match VerKey::from_compressed_bytes(bytes) {
+            Ok(ver_key) => {
+                trace!("indy_crypto_bls_ver_key_from_compressed_bytes: ver_key: {:?}", ver_key);
+                unsafe {
+                    *ver_key_p = register_c_reference!(ver_key);
+                    trace!("indy_crypto_bls_ver_key_from_compressed_bytes: *ver_key_p: {:?}", *ver_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+        trace!("indy_crypto_bls_ver_key_from_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
+}
 
-    trace!("indy_crypto_bls_signature_as_bytes: signature: {:?}", signature);
+ffi_fn! {
+    /// Creates and returns verification key from hex string of its bytes representation.
+    ///
+    /// Hex string must have even length, lower or upper case hex digits only and encode exactly
+    /// VerKey::BYTES_SIZE bytes, otherwise CommonInvalidParam1 is returned.
+    ///
+    /// Note: Verification key instance deallocation must be performed by calling indy_crypto_bls_ver_key_free
+    ///
+    /// # Arguments
+    /// * `hex` - Hex string of bytes representation
+    /// * `ver_key_p` - Reference that will contain verification key instance pointer
+    pub fn indy_crypto_bls_ver_key_from_hex(hex: *const c_char,
+                                            ver_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_from_hex: >>> hex: {:?}, ver_key_p: {:?}", hex, ver_key_p);
+
+        check_useful_c_str!(hex, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(ver_key_p, ErrorCode::CommonInvalidParam2);
+
+        let res = match _param_from_hex::<VerKey>("hex", &hex, ErrorCode::CommonInvalidParam1) {
+            Ok(ver_key) => {
+                trace!("indy_crypto_bls_ver_key_from_hex: ver_key: {:?}", ver_key);
+                unsafe {
+                    *ver_key_p = register_c_reference!(ver_key);
+                    trace!("indy_crypto_bls_ver_key_from_hex: *ver_key_p: {:?}", *ver_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(res) => res
+        };
 
-    unsafe {
-        *bytes_p = signature.as_bytes().as_ptr();
-        *bytes_len_p = signature.as_bytes().len();
-    };
+        trace!("indy_crypto_bls_ver_key_from_hex: <<< res: {:?}", res);
+        res
+    }
+}
 
-    let res = ErrorCode::Success;
+ffi_fn! {
+    /// Returns lower case hex string of verification key bytes representation.
+    ///
+    /// Note: Hex string deallocation must be performed by calling indy_crypto_string_free
+    ///
+    /// # Arguments
+    /// * `ver_key` - Verification key instance pointer
+    /// * `hex_p` - Reference that will contain hex string
+    pub fn indy_crypto_bls_ver_key_to_hex(ver_key: *const c_void,
+                                          hex_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_ver_key_to_hex: >>> ver_key: {:?}, hex_p: {:?}", ver_key, hex_p);
+
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(hex_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_ver_key_to_hex: ver_key: {:?}", ver_key);
+
+        let res = match CTypesUtils::string_to_cstring(to_hex(ver_key.as_bytes())) {
+            Ok(hex) => {
+                unsafe {
+                    *hex_p = hex.into_raw();
+                    trace!("indy_crypto_bls_ver_key_to_hex: *hex_p: {:?}", *hex_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_signature_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_to_hex: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates signature instance.
-///
-/// # Arguments
-/// * `signature` - Signature instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_signature_free(signature: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(signature, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Deallocates verification key instance.
+    ///
+    /// # Arguments
+    /// * `ver_key` - Verification key instance pointer
+    pub fn indy_crypto_bls_ver_key_free(ver_key: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(ver_key, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_signature_free: >>> signature: {:?}", signature);
+        trace!("indy_crypto_bls_ver_key_free: >>> ver_key: {:?}", ver_key);
 
-    unsafe { let _ = Box::from_raw(signature as *mut Signature); }
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_signature_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_ver_key_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns multi signature for provided list of signatures.
-///
-/// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free.
-///
-/// # Arguments
-/// * `signatures` - Signature instance pointers array
-/// * `signatures_len` - Signature instance pointers array len
-/// * `multi_sig_p` - Reference that will contain multi signature instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_new(signatures: *const *const c_void,
-                                                  signatures_len: usize,
-                                                  multi_sig_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_multi_signature_new: >>> signatures: {:?}, signatures_len: {:?}, multi_sig_p: {:?}", signatures, signatures_len, multi_sig_p);
-
-    check_useful_c_reference_array!(signatures, signatures_len, Signature, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
-
-    trace!("indy_crypto_bls_multi_signature_new: signatures: {:?}", signatures);
-
-    let res = match MultiSignature::new(&signatures) {
-        Ok(multi_sig) => {
-            trace!("indy_crypto_bls_multi_signature_new: multi_sig: {:?}", multi_sig);
-            unsafe {
-                *multi_sig_p = Box::into_raw(Box::new(multi_sig)) as *const c_void;
-                trace!("indy_crypto_bls_multi_signature_new: *multi_sig_p: {:?}", *multi_sig_p);
+ffi_fn! {
+    /// Creates and returns BLS proof of possession that corresponds to ver key and sign key.
+    ///
+    /// Note: Proof of possession instance deallocation must be performed by calling indy_crypto_bls_pop_free.
+    ///
+    /// # Arguments
+    /// * `ver_key` - Ver key instance
+    /// * `sign_key` - Sign key instance
+    /// * `pop_p` - Reference that will contain proof of possession instance pointer
+    pub fn indy_crypto_bls_pop_new(ver_key: *const c_void,
+                                   sign_key: *const c_void,
+                                   pop_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_pop_new: >>> ver_key: {:?}, sign_key: {:?}, pop_p: {:?}", ver_key, sign_key, pop_p);
+
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_pop_new: ver_key: {:?}, sign_key: {:?}", ver_key, sign_key);
+
+        let res = match ProofOfPossession::new(ver_key, sign_key) {
+            Ok(pop) => {
+                trace!("indy_crypto_bls_pop_new: pop: {:?}", pop);
+                unsafe {
+                    *pop_p = register_c_reference!(pop);
+                    trace!("indy_crypto_bls_pop_new: *pop_p: {:?}", *pop_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_multi_signature_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_pop_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns multi signature from bytes representation.
-///
-/// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free
-///
-/// # Arguments
-/// * `bytes` - Bytes buffer pointer
-/// * `bytes_len` - Bytes buffer len
-/// * `multi_sig_p` - Reference that will contain multi signature instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_from_bytes(bytes: *const u8, bytes_len: usize,
-                                                         multi_sig_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_multi_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, multi_sig_p: {:?}", bytes, bytes_len, multi_sig_p);
-
-    check_useful_c_byte_array!(bytes, bytes_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_bls_multi_signature_from_bytes: bytes: {:?}", bytes);
-
-    let res = match MultiSignature::from_bytes(bytes) {
-        Ok(multi_sig) => {
-            trace!("indy_crypto_bls_multi_signature_from_bytes: multi_sig: {:?}", multi_sig);
-            unsafe {
-                *multi_sig_p = Box::into_raw(Box::new(multi_sig)) as *const c_void;
-                trace!("indy_crypto_bls_multi_signature_from_bytes: *multi_sig_p: {:?}", *multi_sig_p);
+ffi_fn! {
+    /// Creates and returns proof of possession from bytes representation.
+    ///
+    /// Note: Proof of possession instance deallocation must be performed by calling indy_crypto_bls_pop_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `pop_p` - Reference that will contain proof of possession instance pointer
+    pub fn indy_crypto_bls_pop_from_bytes(bytes: *const u8, bytes_len: usize,
+                                          pop_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_pop_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, gen_p: {:?}", bytes, bytes_len, pop_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(pop_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_pop_from_bytes: bytes: {:?}", bytes);
+
+        let res = match ProofOfPossession::from_bytes(bytes) {
+            Ok(pop) => {
+                trace!("indy_crypto_bls_pop_from_bytes: pop: {:?}", pop);
+                unsafe {
+                    *pop_p = register_c_reference!(pop);
+                    trace!("indy_crypto_bls_pop_from_bytes: *pop_p: {:?}", *pop_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_multi_signature_from_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_pop_from_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns bytes representation of multi signature.
-///
-/// Note: Returned buffer lifetime is the same as multi signature instance.
-///
-/// # Arguments
-/// * `multi_sig` - Multi signature instance pointer
-/// * `bytes_p` - Pointer that will contains bytes buffer
-/// * `bytes_len_p` - Pointer that will contains bytes buffer len
-#[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_as_bytes(multi_sig: *const c_void,
-                                                       bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
-    trace!("indy_crypto_bls_multi_signature_as_bytes: >>> multi_sig: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", multi_sig, bytes_p, bytes_len_p);
-
-    check_useful_c_ptr!(multi_sig, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+ffi_fn! {
+    /// Returns bytes representation of proof of possession.
+    ///
+    /// Note: Returned buffer lifetime is the same as proof of possession instance.
+    ///
+    /// # Arguments
+    /// * `pop` - Proof of possession instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_pop_as_bytes(pop: *const c_void,
+                                        bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_pop_as_bytes: >>> pop: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", pop, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(pop, ProofOfPossession, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_pop_as_bytes: pop: {:?}", pop);
+
+        unsafe {
+            *bytes_p = pop.as_bytes().as_ptr();
+            *bytes_len_p = pop.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_pop_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
 
-    let multi_sig = unsafe { &*(multi_sig as *const MultiSignature) };
-    trace!("indy_crypto_bls_multi_signature_as_bytes: multi_sig: {:?}", multi_sig);
+ffi_fn! {
+    /// Writes bytes representation of proof of possession into the caller allocated buffer.
+    ///
+    /// Buffer handling is the same as of `indy_crypto_bls_generator_as_bytes_into`.
+    ///
+    /// # Arguments
+    /// * `pop` - Proof of possession instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_pop_as_bytes_into(pop: *const c_void,
+                                             buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_pop_as_bytes_into: >>> pop: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", pop, buf, buf_len, written_p);
+
+        check_useful_c_reference!(pop, ProofOfPossession, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
 
-    unsafe {
-        *bytes_p = multi_sig.as_bytes().as_ptr();
-        *bytes_len_p = multi_sig.as_bytes().len();
-    };
+        trace!("indy_crypto_bls_pop_as_bytes_into: pop: {:?}", pop);
 
-    let res = ErrorCode::Success;
+        let res = _write_into_buffer(pop.as_bytes(), buf, buf_len, written_p);
 
-    trace!("indy_crypto_bls_multi_signature_as_bytes: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_pop_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates multi signature instance.
-///
-/// # Arguments
-/// * `multi_sig` - Multi signature instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_multi_signature_free(multi_sig: *const c_void) -> ErrorCode {
-    check_useful_c_ptr!(multi_sig, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Deallocates proof of possession instance.
+    ///
+    /// # Arguments
+    /// * `pop` - Proof of possession instance pointer
+    pub fn indy_crypto_bls_pop_free(pop: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(pop, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_multi_signature_free: >>> multi_sig: {:?}", multi_sig);
+        trace!("indy_crypto_bls_pop_free: >>> pop: {:?}", pop);
 
-    unsafe { let _ = Box::from_raw(multi_sig as *mut MultiSignature); }
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(pop, ProofOfPossession, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_bls_multi_signature_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_pop_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Signs the message and returns signature.
-///
-/// Note: allocated buffer referenced by (signature_p, signature_len_p) must be
-/// deallocated by calling indy_crypto_bls_free_array.
-///
-/// # Arguments
-///
-/// * `message` - Message to sign buffer pointer
-/// * `message_len` - Message to sign buffer len
-/// * `sign_key` - Pointer to Sign Key instance
-/// * `signature_p` - Reference that will contain Signture Instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_bls_sign(message: *const u8,
-                                   message_len: usize,
-                                   sign_key: *const c_void,
-                                   signature_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_bls_sign: >>> message: {:?}, message_len: {:?}, sign_key: {:?}, signature_p: {:?}", message, message_len, sign_key, signature_p);
-
-    check_useful_c_byte_array!(message, message_len,
-                               ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam3);
-    check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam5);
-
-    trace!("indy_crypto_bls_sign: message: {:?}, sign_key: {:?}", message, secret!(sign_key));
-
-    let res = match Bls::sign(message, sign_key) {
-        Ok(signature) => {
-            unsafe {
-                trace!("indy_crypto_bls_sign: signature: {:?}", signature);
-                *signature_p = Box::into_raw(Box::new(signature)) as *const c_void;
-                trace!("indy_crypto_bls_sign: *signature_p: {:?}", *signature_p);
+ffi_fn! {
+    /// Creates and returns signature from bytes representation.
+    ///
+    /// Note: Signature instance deallocation must be performed by calling indy_crypto_bls_signature_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `signature_p` - Reference that will contain signature instance pointer
+    pub fn indy_crypto_bls_signature_from_bytes(bytes: *const u8, bytes_len: usize,
+                                                signature_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, signature_p: {:?}", bytes, bytes_len, signature_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_signature_from_bytes: bytes: {:?}", bytes);
+
+        let res = match Signature::from_bytes(bytes) {
+            Ok(signature) => {
+                trace!("indy_crypto_bls_signature_from_bytes: signature: {:?}", signature);
+                unsafe {
+                    *signature_p = register_c_reference!(signature);
+                    trace!("indy_crypto_bls_signature_from_bytes: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_bls_sign: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_signature_from_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Verifies the message signature and returns true - if signature valid or false otherwise.
-///
-/// # Arguments
-///
-/// * `signature` - Signature instance pointer
-/// * `message` - Message to verify buffer pointer
-/// * `message_len` - Message to verify buffer len
-/// * `ver_key` - Verification key instance pinter
-/// * `gen` - Generator instance pointer
-/// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
-#[no_mangle]
-pub extern fn indy_crypto_bsl_verify(signature: *const c_void,
-                                     message: *const u8,
-                                     message_len: usize,
-                                     ver_key: *const c_void,
-                                     gen: *const c_void,
-                                     valid_p: *mut bool) -> ErrorCode {
-    trace!("indy_crypto_bsl_verify: >>> signature: {:?}, message: {:?}, message_len: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", signature, message, message_len, ver_key, gen, valid_p);
-
-    check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_byte_array!(message, message_len,
-                               ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam4);
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam5);
-    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
-
-    trace!("indy_crypto_bsl_verify: signature: {:?}, message: {:?}, ver_key: {:?}, gen: {:?}", signature, message, ver_key, gen);
-
-    let res = match Bls::verify(signature, message, ver_key, gen) {
-        Ok(valid) => {
-            trace!("indy_crypto_bsl_verify: valid: {:?}", valid);
-            unsafe { *valid_p = valid; }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_bsl_verify: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Returns bytes representation of signature.
+    ///
+    /// Note: Returned buffer lifetime is the same as signature instance.
+    ///
+    /// # Arguments
+    /// * `signature` - Signature instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_signature_as_bytes(signature: *const c_void,
+                                              bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_as_bytes: >>> signature: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", signature, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_signature_as_bytes: signature: {:?}", signature);
+
+        unsafe {
+            *bytes_p = signature.as_bytes().as_ptr();
+            *bytes_len_p = signature.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_signature_as_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Verifies the message multi signature and returns true - if signature valid or false otherwise.
-///
-/// # Arguments
-///
-/// * `multi_sig` - Multi signature instance pointer
-/// * `message` - Message to verify buffer pointer
-/// * `message_len` - Message to verify buffer len
-/// * `ver_keys` - Verification key instance pointers array
-/// * `ver_keys_len` - Verification keys instance pointers array len
-/// * `gen` - Generator point instance
-/// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
-#[no_mangle]
-pub extern fn indy_crypto_bls_verify_multi_sig(multi_sig: *const c_void,
-                                               message: *const u8,
-                                               message_len: usize,
-                                               ver_keys: *const *const c_void,
-                                               ver_keys_len: usize,
-                                               gen: *const c_void,
-                                               valid_p: *mut bool) -> ErrorCode {
-    trace!("indy_crypto_bls_verify_multi_sig: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}", multi_sig, message, message_len, ver_keys, ver_keys_len, gen, valid_p);
-
-    check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam6);
-    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam7);
-
-    trace!("indy_crypto_bls_verify_multi_sig: multi_sig: {:?}, message: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, ver_keys, gen);
-
-    let res = match Bls::verify_multi_sig(multi_sig, message, &ver_keys, gen) {
-        Ok(valid) => {
-            trace!("indy_crypto_bls_verify_multi_sig: valid: {:?}", valid);
-            unsafe { *valid_p = valid; }
-            ErrorCode::Success
+ffi_fn! {
+    /// Writes bytes representation of signature into the caller allocated buffer.
+    ///
+    /// Buffer handling is the same as of `indy_crypto_bls_generator_as_bytes_into`.
+    ///
+    /// # Arguments
+    /// * `signature` - Signature instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_signature_as_bytes_into(signature: *const c_void,
+                                                   buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_as_bytes_into: >>> signature: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", signature, buf, buf_len, written_p);
+
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
         }
-        Err(err) => err.to_error_code()
-    };
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_signature_as_bytes_into: signature: {:?}", signature);
+
+        let res = _write_into_buffer(signature.as_bytes(), buf, buf_len, written_p);
 
-    trace!("indy_crypto_bls_verify_multi_sig: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_bls_signature_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Verifies the proof of possession and returns true - if signature valid or false otherwise.
-///
-/// # Arguments
-///
-/// * `pop` - Proof of possession
-/// * `ver_key` - Verification key instance pinter
-/// * `gen` - Generator instance pointer
-/// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
-#[no_mangle]
-pub extern fn indy_crypto_bsl_verify_pop(pop: *const c_void,
-                                         ver_key: *const c_void,
-                                         gen: *const c_void,
-                                         valid_p: *mut bool) -> ErrorCode {
-    trace!("indy_crypto_bsl_verify_pop: >>> pop: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", pop, ver_key, gen, valid_p);
-
-    check_useful_c_reference!(pop, ProofOfPossession, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam3);
-    check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam4);
-
-    trace!("indy_crypto_bsl_verify_pop: pop: {:?}, ver_key: {:?}, gen: {:?}", pop, ver_key, gen);
-
-    let res = match Bls::verify_proof_of_posession(pop, ver_key, gen) {
-        Ok(valid) => {
-            trace!("indy_crypto_bsl_verify_pop: valid: {:?}", valid);
-            unsafe { *valid_p = valid; }
-            ErrorCode::Success
+ffi_fn! {
+    /// Writes compressed bytes representation of signature into the caller allocated buffer.
+    ///
+    /// Compressed representation is 33 bytes long, see indy_crypto_bls_signature_from_compressed_bytes.
+    /// Size of representation is always written to `written_p`: call with null buffer and zero len
+    /// to query it. If the buffer is too small CommonInvalidStructure is returned and nothing is
+    /// written into the buffer.
+    ///
+    /// # Arguments
+    /// * `signature` - Signature instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of compressed bytes representation
+    pub fn indy_crypto_bls_signature_as_compressed_bytes(signature: *const c_void,
+                                                         buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_as_compressed_bytes: >>> signature: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", signature, buf, buf_len, written_p);
+
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
         }
-        Err(err) => err.to_error_code()
-    };
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_signature_as_compressed_bytes: signature: {:?}", signature);
 
-    trace!("indy_crypto_bsl_verify_pop: <<< res: {:?}", res);
-    res
+        let res = match signature.as_compressed_bytes() {
+            Ok(bytes) => _write_into_buffer(&bytes, buf, buf_len, written_p),
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_signature_as_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::ptr;
+ffi_fn! {
+    /// Creates and returns signature from compressed bytes representation.
+    ///
+    /// Note: Signature instance deallocation must be performed by calling indy_crypto_bls_signature_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Compressed bytes buffer pointer
+    /// * `bytes_len` - Compressed bytes buffer len, must be 33
+    /// * `signature_p` - Reference that will contain signature instance pointer
+    pub fn indy_crypto_bls_signature_from_compressed_bytes(bytes: *const u8, bytes_len: usize,
+                                                           signature_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_from_compressed_bytes: >>> bytes: {:?}, bytes_len: {:?}, signature_p: {:?}", bytes, bytes_len, signature_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam3);
+        if bytes_len != Signature::COMPRESSED_BYTES_SIZE {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2,
+                                         format!("`bytes_len` must be {}, actual {}", Signature::COMPRESSED_BYTES_SIZE, bytes_len));
+        }
 
-    #[test]
-    fn indy_crypto_bls_generator_new_works() {
-        let mut gen: *const c_void = ptr::null();
+        trace!("indy_crypto_bls_signature_from_compressed_bytes: bytes: {:?}", bytes);
 
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!gen.is_null());
+        let res = match Signature::from_compressed_bytes(bytes) {
+            Ok(signature) => {
+                trace!("indy_crypto_bls_signature_from_compressed_bytes: signature: {:?}", signature);
+                unsafe {
+                    *signature_p = register_c_reference!(signature);
+                    trace!("indy_crypto_bls_signature_from_compressed_bytes: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        trace!("indy_crypto_bls_signature_from_compressed_bytes: <<< res: {:?}", res);
+        res
     }
+}
 
-    #[test]
-    fn indy_crypto_bls_generator_as_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
-        assert_eq!(err_code, ErrorCode::Success);
+ffi_fn! {
+    /// Creates and returns signature from hex string of its bytes representation.
+    ///
+    /// Hex string must have even length, lower or upper case hex digits only and encode exactly
+    /// Signature::BYTES_SIZE bytes, otherwise CommonInvalidParam1 is returned.
+    ///
+    /// Note: Signature instance deallocation must be performed by calling indy_crypto_bls_signature_free
+    ///
+    /// # Arguments
+    /// * `hex` - Hex string of bytes representation
+    /// * `signature_p` - Reference that will contain signature instance pointer
+    pub fn indy_crypto_bls_signature_from_hex(hex: *const c_char,
+                                              signature_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_from_hex: >>> hex: {:?}, signature_p: {:?}", hex, signature_p);
+
+        check_useful_c_str!(hex, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam2);
+
+        let res = match _param_from_hex::<Signature>("hex", &hex, ErrorCode::CommonInvalidParam1) {
+            Ok(signature) => {
+                trace!("indy_crypto_bls_signature_from_hex: signature: {:?}", signature);
+                unsafe {
+                    *signature_p = register_c_reference!(signature);
+                    trace!("indy_crypto_bls_signature_from_hex: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
+            }
+            Err(res) => res
+        };
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!gen.is_null());
-        assert!(bytes_len > 0);
+        trace!("indy_crypto_bls_signature_from_hex: <<< res: {:?}", res);
+        res
+    }
+}
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+ffi_fn! {
+    /// Returns lower case hex string of signature bytes representation.
+    ///
+    /// Note: Hex string deallocation must be performed by calling indy_crypto_string_free
+    ///
+    /// # Arguments
+    /// * `signature` - Signature instance pointer
+    /// * `hex_p` - Reference that will contain hex string
+    pub fn indy_crypto_bls_signature_to_hex(signature: *const c_void,
+                                            hex_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_to_hex: >>> signature: {:?}, hex_p: {:?}", signature, hex_p);
+
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(hex_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_signature_to_hex: signature: {:?}", signature);
+
+        let res = match CTypesUtils::string_to_cstring(to_hex(signature.as_bytes())) {
+            Ok(hex) => {
+                unsafe {
+                    *hex_p = hex.into_raw();
+                    trace!("indy_crypto_bls_signature_to_hex: *hex_p: {:?}", *hex_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_signature_to_hex: <<< res: {:?}", res);
+        res
     }
+}
 
-    #[test]
-    fn indy_crypto_bls_generator_from_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
-        assert_eq!(err_code, ErrorCode::Success);
+ffi_fn! {
+    /// Deallocates signature instance.
+    ///
+    /// # Arguments
+    /// * `signature` - Signature instance pointer
+    pub fn indy_crypto_bls_signature_free(signature: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(signature, ErrorCode::CommonInvalidParam1);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
-        assert_eq!(err_code, ErrorCode::Success);
+        trace!("indy_crypto_bls_signature_free: >>> signature: {:?}", signature);
 
-        let mut gen2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_from_bytes(bytes, bytes_len, &mut gen2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let res = free_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        trace!("indy_crypto_bls_signature_free: <<< res: {:?}", res);
+        res
+    }
+}
 
-        let err_code = indy_crypto_bls_generator_free(gen2);
-        assert_eq!(err_code, ErrorCode::Success);
+ffi_fn! {
+    /// Creates and returns multi signature for provided list of signatures.
+    ///
+    /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free.
+    ///
+    /// # Arguments
+    /// * `signatures` - Signature instance pointers array
+    /// * `signatures_len` - Signature instance pointers array len
+    /// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_new(signatures: *const *const c_void,
+                                               signatures_len: usize,
+                                               multi_sig_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_new: >>> signatures: {:?}, signatures_len: {:?}, multi_sig_p: {:?}", signatures, signatures_len, multi_sig_p);
+
+        check_useful_c_reference_array!(signatures, signatures_len, Signature, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_multi_signature_new: signatures: {:?}", signatures);
+
+        let res = match MultiSignature::new(&signatures) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_new: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = register_c_reference!(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_new: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_new: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns multi signature of no signatures, to add signatures to as they arrive
+    /// by calling indy_crypto_bls_multi_signature_add.
+    ///
+    /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free.
+    ///
+    /// # Arguments
+    /// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_new_empty(multi_sig_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_new_empty: >>> multi_sig_p: {:?}", multi_sig_p);
+
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match MultiSignature::empty() {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_new_empty: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = register_c_reference!(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_new_empty: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_new_empty: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Adds signature to the multi signature in place.
+    ///
+    /// The result is the same as of indy_crypto_bls_multi_signature_new for the list of all added
+    /// signatures, in any order.
+    ///
+    /// Note: Multi signature instance is mutated without synchronization. Caller must ensure that no other
+    /// call uses the same multi signature instance, in this or other thread, until this call returns.
+    /// Different instances may be used from different threads concurrently.
+    ///
+    /// # Arguments
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `signature` - Signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_add(multi_sig: *const c_void,
+                                               signature: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_add: >>> multi_sig: {:?}, signature: {:?}", multi_sig, signature);
+
+        check_useful_mut_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_multi_signature_add: multi_sig: {:?}, signature: {:?}", multi_sig, signature);
+
+        let res = match multi_sig.add(signature) {
+            Ok(()) => {
+                trace!("indy_crypto_bls_multi_signature_add: multi_sig: {:?}", multi_sig);
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_add: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns multi signature of all signatures of both multi signatures.
+    ///
+    /// Input instances are not changed and may be the same instance.
+    ///
+    /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free.
+    ///
+    /// # Arguments
+    /// * `a` - Multi signature instance pointer
+    /// * `b` - Multi signature instance pointer
+    /// * `multi_sig_p` - Reference that will contain merged multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_merge(a: *const c_void,
+                                                 b: *const c_void,
+                                                 multi_sig_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_merge: >>> a: {:?}, b: {:?}, multi_sig_p: {:?}", a, b, multi_sig_p);
+
+        check_useful_c_reference!(a, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(b, MultiSignature, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_multi_signature_merge: a: {:?}, b: {:?}", a, b);
+
+        let res = match _merge_multi_signatures(a, b) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_merge: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = register_c_reference!(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_merge: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_merge: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns multi signature from bytes representation.
+    ///
+    /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_from_bytes(bytes: *const u8, bytes_len: usize,
+                                                      multi_sig_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, multi_sig_p: {:?}", bytes, bytes_len, multi_sig_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_multi_signature_from_bytes: bytes: {:?}", bytes);
+
+        let res = match MultiSignature::from_bytes(bytes) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_from_bytes: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = register_c_reference!(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_from_bytes: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_from_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Returns bytes representation of multi signature.
+    ///
+    /// Note: Returned buffer lifetime is the same as multi signature instance.
+    ///
+    /// # Arguments
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_multi_signature_as_bytes(multi_sig: *const c_void,
+                                                    bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_as_bytes: >>> multi_sig: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", multi_sig, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_multi_signature_as_bytes: multi_sig: {:?}", multi_sig);
+
+        unsafe {
+            *bytes_p = multi_sig.as_bytes().as_ptr();
+            *bytes_len_p = multi_sig.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_multi_signature_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Writes bytes representation of multi signature into the caller allocated buffer.
+    ///
+    /// Buffer handling is the same as of `indy_crypto_bls_generator_as_bytes_into`.
+    ///
+    /// # Arguments
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_multi_signature_as_bytes_into(multi_sig: *const c_void,
+                                                         buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_as_bytes_into: >>> multi_sig: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", multi_sig, buf, buf_len, written_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_multi_signature_as_bytes_into: multi_sig: {:?}", multi_sig);
+
+        let res = _write_into_buffer(multi_sig.as_bytes(), buf, buf_len, written_p);
+
+        trace!("indy_crypto_bls_multi_signature_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Writes compressed bytes representation of multi signature into the caller allocated buffer.
+    ///
+    /// Compressed representation is 33 bytes long, see indy_crypto_bls_multi_signature_from_compressed_bytes.
+    /// Size of representation is always written to `written_p`: call with null buffer and zero len
+    /// to query it. If the buffer is too small CommonInvalidStructure is returned and nothing is
+    /// written into the buffer.
+    ///
+    /// # Arguments
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of compressed bytes representation
+    pub fn indy_crypto_bls_multi_signature_as_compressed_bytes(multi_sig: *const c_void,
+                                                               buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_as_compressed_bytes: >>> multi_sig: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", multi_sig, buf, buf_len, written_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_multi_signature_as_compressed_bytes: multi_sig: {:?}", multi_sig);
+
+        let res = match multi_sig.as_compressed_bytes() {
+            Ok(bytes) => _write_into_buffer(&bytes, buf, buf_len, written_p),
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_as_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns multi signature from compressed bytes representation.
+    ///
+    /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Compressed bytes buffer pointer
+    /// * `bytes_len` - Compressed bytes buffer len, must be 33
+    /// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_from_compressed_bytes(bytes: *const u8, bytes_len: usize,
+                                                                 multi_sig_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_from_compressed_bytes: >>> bytes: {:?}, bytes_len: {:?}, multi_sig_p: {:?}", bytes, bytes_len, multi_sig_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam3);
+        if bytes_len != MultiSignature::COMPRESSED_BYTES_SIZE {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2,
+                                         format!("`bytes_len` must be {}, actual {}", MultiSignature::COMPRESSED_BYTES_SIZE, bytes_len));
+        }
+
+        trace!("indy_crypto_bls_multi_signature_from_compressed_bytes: bytes: {:?}", bytes);
+
+        let res = match MultiSignature::from_compressed_bytes(bytes) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_from_compressed_bytes: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = register_c_reference!(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_from_compressed_bytes: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_from_compressed_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns multi signature from hex string of its bytes representation.
+    ///
+    /// Hex string must have even length, lower or upper case hex digits only and encode exactly
+    /// MultiSignature::BYTES_SIZE bytes, otherwise CommonInvalidParam1 is returned.
+    ///
+    /// Note: Multi signature instance deallocation must be performed by calling indy_crypto_bls_multi_signature_free
+    ///
+    /// # Arguments
+    /// * `hex` - Hex string of bytes representation
+    /// * `multi_sig_p` - Reference that will contain multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_from_hex(hex: *const c_char,
+                                                    multi_sig_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_from_hex: >>> hex: {:?}, multi_sig_p: {:?}", hex, multi_sig_p);
+
+        check_useful_c_str!(hex, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(multi_sig_p, ErrorCode::CommonInvalidParam2);
+
+        let res = match _param_from_hex::<MultiSignature>("hex", &hex, ErrorCode::CommonInvalidParam1) {
+            Ok(multi_sig) => {
+                trace!("indy_crypto_bls_multi_signature_from_hex: multi_sig: {:?}", multi_sig);
+                unsafe {
+                    *multi_sig_p = register_c_reference!(multi_sig);
+                    trace!("indy_crypto_bls_multi_signature_from_hex: *multi_sig_p: {:?}", *multi_sig_p);
+                }
+                ErrorCode::Success
+            }
+            Err(res) => res
+        };
+
+        trace!("indy_crypto_bls_multi_signature_from_hex: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Returns lower case hex string of multi signature bytes representation.
+    ///
+    /// Note: Hex string deallocation must be performed by calling indy_crypto_string_free
+    ///
+    /// # Arguments
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `hex_p` - Reference that will contain hex string
+    pub fn indy_crypto_bls_multi_signature_to_hex(multi_sig: *const c_void,
+                                                  hex_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_to_hex: >>> multi_sig: {:?}, hex_p: {:?}", multi_sig, hex_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(hex_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_multi_signature_to_hex: multi_sig: {:?}", multi_sig);
+
+        let res = match CTypesUtils::string_to_cstring(to_hex(multi_sig.as_bytes())) {
+            Ok(hex) => {
+                unsafe {
+                    *hex_p = hex.into_raw();
+                    trace!("indy_crypto_bls_multi_signature_to_hex: *hex_p: {:?}", *hex_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_to_hex: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Deallocates multi signature instance.
+    ///
+    /// # Arguments
+    /// * `multi_sig` - Multi signature instance pointer
+    pub fn indy_crypto_bls_multi_signature_free(multi_sig: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(multi_sig, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_multi_signature_free: >>> multi_sig: {:?}", multi_sig);
+
+        let res = free_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_multi_signature_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns aggregated verification key for provided list of verification keys.
+    ///
+    /// Aggregated ver key can be cached and used to verify multi signatures of the same signers
+    /// by calling indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key.
+    ///
+    /// Note: Aggregated ver key instance deallocation must be performed by calling indy_crypto_bls_aggregated_ver_key_free.
+    ///
+    /// # Arguments
+    /// * `ver_keys` - Verification key instance pointers array
+    /// * `ver_keys_len` - Verification key instance pointers array len
+    /// * `agg_ver_key_p` - Reference that will contain aggregated ver key instance pointer
+    pub fn indy_crypto_bls_aggregated_ver_key_new(ver_keys: *const *const c_void,
+                                                  ver_keys_len: usize,
+                                                  agg_ver_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_aggregated_ver_key_new: >>> ver_keys: {:?}, ver_keys_len: {:?}, agg_ver_key_p: {:?}", ver_keys, ver_keys_len, agg_ver_key_p);
+
+        check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(agg_ver_key_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_new: ver_keys: {:?}", ver_keys);
+
+        let res = match AggregatedVerKey::new(&ver_keys) {
+            Ok(agg_ver_key) => {
+                trace!("indy_crypto_bls_aggregated_ver_key_new: agg_ver_key: {:?}", agg_ver_key);
+                unsafe {
+                    *agg_ver_key_p = register_c_reference!(agg_ver_key);
+                    trace!("indy_crypto_bls_aggregated_ver_key_new: *agg_ver_key_p: {:?}", *agg_ver_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_aggregated_ver_key_new: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns aggregated verification key from bytes representation.
+    ///
+    /// Note: Aggregated ver key instance deallocation must be performed by calling indy_crypto_bls_aggregated_ver_key_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `agg_ver_key_p` - Reference that will contain aggregated ver key instance pointer
+    pub fn indy_crypto_bls_aggregated_ver_key_from_bytes(bytes: *const u8, bytes_len: usize,
+                                                         agg_ver_key_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_aggregated_ver_key_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, agg_ver_key_p: {:?}", bytes, bytes_len, agg_ver_key_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(agg_ver_key_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_from_bytes: bytes: {:?}", bytes);
+
+        let res = match AggregatedVerKey::from_bytes(bytes) {
+            Ok(agg_ver_key) => {
+                trace!("indy_crypto_bls_aggregated_ver_key_from_bytes: agg_ver_key: {:?}", agg_ver_key);
+                unsafe {
+                    *agg_ver_key_p = register_c_reference!(agg_ver_key);
+                    trace!("indy_crypto_bls_aggregated_ver_key_from_bytes: *agg_ver_key_p: {:?}", *agg_ver_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_aggregated_ver_key_from_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Returns bytes representation of aggregated verification key.
+    ///
+    /// Note: Returned buffer lifetime is the same as aggregated ver key instance.
+    ///
+    /// # Arguments
+    /// * `agg_ver_key` - Aggregated ver key instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_aggregated_ver_key_as_bytes(agg_ver_key: *const c_void,
+                                                       bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_aggregated_ver_key_as_bytes: >>> agg_ver_key: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", agg_ver_key, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(agg_ver_key, AggregatedVerKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_as_bytes: agg_ver_key: {:?}", agg_ver_key);
+
+        unsafe {
+            *bytes_p = agg_ver_key.as_bytes().as_ptr();
+            *bytes_len_p = agg_ver_key.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_aggregated_ver_key_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Writes bytes representation of aggregated verification key into the caller allocated buffer.
+    ///
+    /// Buffer handling is the same as of `indy_crypto_bls_generator_as_bytes_into`.
+    ///
+    /// # Arguments
+    /// * `agg_ver_key` - Aggregated verification key instance pointer
+    /// * `buf` - Buffer pointer, may be null if `buf_len` is 0
+    /// * `buf_len` - Buffer len
+    /// * `written_p` - Pointer that will contain size of bytes representation
+    pub fn indy_crypto_bls_aggregated_ver_key_as_bytes_into(agg_ver_key: *const c_void,
+                                                            buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_aggregated_ver_key_as_bytes_into: >>> agg_ver_key: {:?}, buf: {:?}, buf_len: {:?}, written_p: {:?}", agg_ver_key, buf, buf_len, written_p);
+
+        check_useful_c_reference!(agg_ver_key, AggregatedVerKey, ErrorCode::CommonInvalidParam1);
+        if buf.is_null() && buf_len > 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, "`buf` is null".to_string());
+        }
+        check_useful_c_ptr!(written_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_as_bytes_into: agg_ver_key: {:?}", agg_ver_key);
+
+        let res = _write_into_buffer(agg_ver_key.as_bytes(), buf, buf_len, written_p);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_as_bytes_into: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Deallocates aggregated verification key instance.
+    ///
+    /// # Arguments
+    /// * `agg_ver_key` - Aggregated ver key instance pointer
+    pub fn indy_crypto_bls_aggregated_ver_key_free(agg_ver_key: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(agg_ver_key, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_free: >>> agg_ver_key: {:?}", agg_ver_key);
+
+        let res = free_c_reference!(agg_ver_key, AggregatedVerKey, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_aggregated_ver_key_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Signs the message and returns signature.
+    ///
+    /// Note: allocated buffer referenced by (signature_p, signature_len_p) must be
+    /// deallocated by calling indy_crypto_bls_free_array.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to sign buffer len
+    /// * `sign_key` - Pointer to Sign Key instance
+    /// * `signature_p` - Reference that will contain Signture Instance pointer
+    pub fn indy_crypto_bls_sign(message: *const u8,
+                                message_len: usize,
+                                sign_key: *const c_void,
+                                signature_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign: >>> message: {:?}, message_len: {:?}, sign_key: {:?}, signature_p: {:?}", message, message_len, sign_key, signature_p);
+
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_sign: message: {:?}, sign_key: {:?}", message, secret!(sign_key));
+
+        let res = match Bls::sign(message, sign_key) {
+            Ok(signature) => {
+                unsafe {
+                    trace!("indy_crypto_bls_sign: signature: {:?}", signature);
+                    *signature_p = register_c_reference!(signature);
+                    trace!("indy_crypto_bls_sign: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_sign: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Signs the message with domain separation tag and returns signature.
+    ///
+    /// Signature can be verified only by indy_crypto_bls_verify_with_dst and
+    /// indy_crypto_bls_verify_multi_sig_with_dst for the same tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to sign buffer len
+    /// * `dst` - Domain separation tag buffer pointer, may be null if `dst_len` is 0
+    /// * `dst_len` - Domain separation tag buffer len, 0 to 255. If 0 message is signed without tag
+    ///   as indy_crypto_bls_sign does
+    /// * `sign_key` - Pointer to Sign Key instance
+    /// * `signature_p` - Reference that will contain Signture Instance pointer
+    pub fn indy_crypto_bls_sign_with_dst(message: *const u8,
+                                         message_len: usize,
+                                         dst: *const u8,
+                                         dst_len: usize,
+                                         sign_key: *const c_void,
+                                         signature_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_with_dst: >>> message: {:?}, message_len: {:?}, dst: {:?}, dst_len: {:?}, sign_key: {:?}, signature_p: {:?}", message, message_len, dst, dst_len, sign_key, signature_p);
+
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(dst, dst_len, ErrorCode::CommonInvalidParam3);
+        check_useful_c_reference!(sign_key, SignKey, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam6);
+
+        trace!("indy_crypto_bls_sign_with_dst: message: {:?}, dst: {:?}, sign_key: {:?}", message, dst, secret!(sign_key));
+
+        let signature = if dst.is_empty() {
+            Bls::sign(message, sign_key)
+        } else {
+            Bls::sign_with_dst(message, sign_key, dst)
+        };
+
+        let res = match signature {
+            Ok(signature) => {
+                unsafe {
+                    trace!("indy_crypto_bls_sign_with_dst: signature: {:?}", signature);
+                    *signature_p = register_c_reference!(signature);
+                    trace!("indy_crypto_bls_sign_with_dst: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_sign_with_dst: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message signature and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature instance pointer
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `ver_key` - Verification key instance pinter
+    /// * `gen` - Generator instance pointer
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bsl_verify(signature: *const c_void,
+                                  message: *const u8,
+                                  message_len: usize,
+                                  ver_key: *const c_void,
+                                  gen: *const c_void,
+                                  valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bsl_verify: >>> signature: {:?}, message: {:?}, message_len: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", signature, message, message_len, ver_key, gen, valid_p);
+
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+
+        trace!("indy_crypto_bsl_verify: signature: {:?}, message: {:?}, ver_key: {:?}, gen: {:?}", signature, message, ver_key, gen);
+
+        let res = match Bls::verify(signature, message, ver_key, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bsl_verify: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bsl_verify: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message signature made with domain separation tag and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature instance pointer
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `dst` - Domain separation tag buffer pointer, may be null if `dst_len` is 0
+    /// * `dst_len` - Domain separation tag buffer len, 0 to 255. If 0 signature is verified without tag
+    ///   as indy_crypto_bsl_verify does
+    /// * `ver_key` - Verification key instance pinter
+    /// * `gen` - Generator instance pointer
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bls_verify_with_dst(signature: *const c_void,
+                                           message: *const u8,
+                                           message_len: usize,
+                                           dst: *const u8,
+                                           dst_len: usize,
+                                           ver_key: *const c_void,
+                                           gen: *const c_void,
+                                           valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_with_dst: >>> signature: {:?}, message: {:?}, message_len: {:?}, dst: {:?}, dst_len: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", signature, message, message_len, dst, dst_len, ver_key, gen, valid_p);
+
+        check_useful_c_reference!(signature, Signature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_message!(dst, dst_len, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam6);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam7);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam8);
+
+        trace!("indy_crypto_bls_verify_with_dst: signature: {:?}, message: {:?}, dst: {:?}, ver_key: {:?}, gen: {:?}", signature, message, dst, ver_key, gen);
+
+        let valid = if dst.is_empty() {
+            Bls::verify(signature, message, ver_key, gen)
+        } else {
+            Bls::verify_with_dst(signature, message, ver_key, gen, dst)
+        };
+
+        let res = match valid {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_with_dst: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_with_dst: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bsl_verify that describes the failure in `error_detail_p`.
+    ///
+    /// On error `error_detail_p` is filled with json of error detail, on success it's set to null.
+    /// Returned json must be deallocated by calling indy_crypto_string_free.
+    ///
+    /// Error detail json:
+    /// ```text
+    /// {
+    ///     "code": int, // numeric ErrorCode, the same as returned
+    ///     "name": string, // name of ErrorCode
+    ///     "param": Optional<int>, // 1-based index of the invalid param, if code is CommonInvalidParamN
+    ///     "message": string // human readable error description
+    /// }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `signature`, `message`, `message_len`, `ver_key`, `gen`, `valid_p` - The same as of indy_crypto_bsl_verify
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_ext(signature: *const c_void,
+                                      message: *const u8,
+                                      message_len: usize,
+                                      ver_key: *const c_void,
+                                      gen: *const c_void,
+                                      valid_p: *mut bool,
+                                      error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam7);
+
+        let res = indy_crypto_bsl_verify(signature, message, message_len, ver_key, gen, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message multi signature and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `ver_keys` - Verification key instance pointers array
+    /// * `ver_keys_len` - Verification keys instance pointers array len
+    /// * `gen` - Generator point instance
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bls_verify_multi_sig(multi_sig: *const c_void,
+                                            message: *const u8,
+                                            message_len: usize,
+                                            ver_keys: *const *const c_void,
+                                            ver_keys_len: usize,
+                                            gen: *const c_void,
+                                            valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}", multi_sig, message, message_len, ver_keys, ver_keys_len, gen, valid_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam6);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam7);
+
+        trace!("indy_crypto_bls_verify_multi_sig: multi_sig: {:?}, message: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, ver_keys, gen);
+
+        let res = match Bls::verify_multi_sig(multi_sig, message, &ver_keys, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_multi_sig: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_multi_sig: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message multi signature made with domain separation tag and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `dst` - Domain separation tag buffer pointer, may be null if `dst_len` is 0
+    /// * `dst_len` - Domain separation tag buffer len, 0 to 255. If 0 signature is verified without tag
+    ///   as indy_crypto_bls_verify_multi_sig does
+    /// * `ver_keys` - Verification key instance pointers array
+    /// * `ver_keys_len` - Verification keys instance pointers array len
+    /// * `gen` - Generator point instance
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bls_verify_multi_sig_with_dst(multi_sig: *const c_void,
+                                                     message: *const u8,
+                                                     message_len: usize,
+                                                     dst: *const u8,
+                                                     dst_len: usize,
+                                                     ver_keys: *const *const c_void,
+                                                     ver_keys_len: usize,
+                                                     gen: *const c_void,
+                                                     valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig_with_dst: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, dst: {:?}, dst_len: {:?}, ver_keys: {:?}, ver_keys_len: {:?}, gen: {:?}, valid_p: {:?}", multi_sig, message, message_len, dst, dst_len, ver_keys, ver_keys_len, gen, valid_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_message!(dst, dst_len, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference_array!(ver_keys, ver_keys_len, VerKey, ErrorCode::CommonInvalidParam6, ErrorCode::CommonInvalidParam7);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam8);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam9);
+
+        trace!("indy_crypto_bls_verify_multi_sig_with_dst: multi_sig: {:?}, message: {:?}, dst: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, dst, ver_keys, gen);
+
+        let valid = if dst.is_empty() {
+            Bls::verify_multi_sig(multi_sig, message, &ver_keys, gen)
+        } else {
+            Bls::verify_multi_sig_with_dst(multi_sig, message, &ver_keys, gen, dst)
+        };
+
+        let res = match valid {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_multi_sig_with_dst: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_multi_sig_with_dst: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bls_verify_multi_sig that describes the failure in `error_detail_p`,
+    /// see indy_crypto_bls_verify_ext for error detail json.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig`, `message`, `message_len`, `ver_keys`, `ver_keys_len`, `gen`, `valid_p` - The same as of indy_crypto_bls_verify_multi_sig
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_multi_sig_ext(multi_sig: *const c_void,
+                                                message: *const u8,
+                                                message_len: usize,
+                                                ver_keys: *const *const c_void,
+                                                ver_keys_len: usize,
+                                                gen: *const c_void,
+                                                valid_p: *mut bool,
+                                                error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam8);
+
+        let res = indy_crypto_bls_verify_multi_sig(multi_sig, message, message_len, ver_keys, ver_keys_len, gen, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_multi_sig_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message multi signature against aggregated verification key of the signers
+    /// and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig` - Multi signature instance pointer
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `agg_ver_key` - Aggregated verification key instance pointer
+    /// * `gen` - Generator point instance
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig: *const c_void,
+                                                                    message: *const u8,
+                                                                    message_len: usize,
+                                                                    agg_ver_key: *const c_void,
+                                                                    gen: *const c_void,
+                                                                    valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key: >>> multi_sig: {:?}, message: {:?}, message_len: {:?}, agg_ver_key: {:?}, gen: {:?}, valid_p: {:?}",
+               multi_sig, message, message_len, agg_ver_key, gen, valid_p);
+
+        check_useful_c_reference!(multi_sig, MultiSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(agg_ver_key, AggregatedVerKey, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+
+        trace!("indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key: multi_sig: {:?}, message: {:?}, agg_ver_key: {:?}, gen: {:?}", multi_sig, message, agg_ver_key, gen);
+
+        let res = match Bls::verify_multi_sig_with_aggregated_ver_key(multi_sig, message, agg_ver_key, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key that describes the failure in `error_detail_p`,
+    /// see indy_crypto_bls_verify_ext for error detail json.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig`, `message`, `message_len`, `agg_ver_key`, `gen`, `valid_p` - The same as of indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key_ext(multi_sig: *const c_void,
+                                                                        message: *const u8,
+                                                                        message_len: usize,
+                                                                        agg_ver_key: *const c_void,
+                                                                        gen: *const c_void,
+                                                                        valid_p: *mut bool,
+                                                                        error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam7);
+
+        let res = indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, message, message_len, agg_ver_key, gen, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+/// Item of indy_crypto_bls_verify_batch: message with its signature and verification key.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BatchItem {
+    /// Message buffer pointer, may be null if `message_len` is 0
+    pub message: *const u8,
+    /// Message buffer len
+    pub message_len: usize,
+    /// Signature instance pointer
+    pub signature: *const c_void,
+    /// Verification key instance pointer
+    pub ver_key: *const c_void,
+}
+
+ffi_fn! {
+    /// Verifies a batch of independent message signatures at once and returns true - if all signatures valid or false otherwise.
+    ///
+    /// Result doesn't tell which signature is invalid, callers that need it can fall back to indy_crypto_bls_verify per item.
+    ///
+    /// Items are validated before any verification: for the first item with null message, zero message len
+    /// or invalid handle CommonInvalidParam1 is returned, indy_crypto_get_current_error tells the item index and field.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - Batch items array pointer
+    /// * `items_len` - Batch items array len
+    /// * `gen` - Generator point instance
+    /// * `valid_p` - Reference that will be filled with true - if all signatures valid or false otherwise.
+    pub fn indy_crypto_bls_verify_batch(items: *const BatchItem,
+                                        items_len: usize,
+                                        gen: *const c_void,
+                                        valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_batch: >>> items: {:?}, items_len: {:?}, gen: {:?}, valid_p: {:?}", items, items_len, gen, valid_p);
+
+        check_useful_c_byte_array!(items, items_len, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam4);
+
+        let batch = match _batch_items(items) {
+            Ok(batch) => batch,
+            Err(reason) => {
+                let res = ctypes::invalid_param(ErrorCode::CommonInvalidParam1, reason);
+                trace!("indy_crypto_bls_verify_batch: <<< res: {:?}", res);
+                return res;
+            }
+        };
+
+        trace!("indy_crypto_bls_verify_batch: batch: {:?}, gen: {:?}", batch, gen);
+
+        let batch: Vec<(&Signature, &[u8], &VerKey)> = batch.iter()
+            .map(|(signature, message, ver_key)| (&**signature, *message, &**ver_key))
+            .collect();
+        let res = match Bls::verify_batch(&batch, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_batch: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_batch: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bls_verify_batch that describes the failure in `error_detail_p`,
+    /// see indy_crypto_bls_verify_ext for error detail json.
+    ///
+    /// # Arguments
+    ///
+    /// * `items`, `items_len`, `gen`, `valid_p` - The same as of indy_crypto_bls_verify_batch
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_batch_ext(items: *const BatchItem,
+                                            items_len: usize,
+                                            gen: *const c_void,
+                                            valid_p: *mut bool,
+                                            error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_batch_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam5);
+
+        let res = indy_crypto_bls_verify_batch(items, items_len, gen, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_batch_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+/// Callback of indy_crypto_bls_verify_batch_async.
+///
+/// # Arguments
+/// * `command_handle` - Command handle passed to indy_crypto_bls_verify_batch_async
+/// * `err` - Error code of the verification
+/// * `valid` - True if all signatures valid or false otherwise, false on error
+pub type VerifyBatchCB = extern fn(command_handle: i32, err: ErrorCode, valid: bool);
+
+ffi_fn! {
+    /// Verifies a batch of independent message signatures on a worker thread and passes the result to `cb`.
+    ///
+    /// Items are validated and copied before the function returns, so the caller may free the messages
+    /// and handles right away. Invalid params are returned as by indy_crypto_bls_verify_batch and `cb`
+    /// is not called then. Otherwise Success is returned and `cb` is called exactly once from the worker
+    /// thread. Details of the error passed to `cb` are available by calling indy_crypto_get_current_error
+    /// inside `cb` only. A panic of the verification is passed to `cb` as CommonInvalidState.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - Batch items array pointer
+    /// * `items_len` - Batch items array len
+    /// * `gen` - Generator point instance
+    /// * `command_handle` - Handle passed to `cb` to match the call
+    /// * `cb` - Callback that will be called with the result
+    pub fn indy_crypto_bls_verify_batch_async(items: *const BatchItem,
+                                              items_len: usize,
+                                              gen: *const c_void,
+                                              command_handle: i32,
+                                              cb: Option<VerifyBatchCB>) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_batch_async: >>> items: {:?}, items_len: {:?}, gen: {:?}, command_handle: {:?}, cb: {:?}",
+               items, items_len, gen, command_handle, cb);
+
+        check_useful_c_byte_array!(items, items_len, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam3);
+        check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+        let batch: Vec<(Signature, Vec<u8>, VerKey)> = match _batch_items(items) {
+            Ok(batch) => batch.into_iter()
+                .map(|(signature, message, ver_key)| (signature.clone(), message.to_vec(), ver_key.clone()))
+                .collect(),
+            Err(reason) => return ctypes::invalid_param(ErrorCode::CommonInvalidParam1, reason)
+        };
+        let gen = gen.clone();
+
+        trace!("indy_crypto_bls_verify_batch_async: batch: {:?}, gen: {:?}", batch, gen);
+
+        let worker = thread::Builder::new()
+            .name("indy-crypto-verify-batch".to_string())
+            .spawn(move || {
+                let mut valid = false;
+                let err = ctypes::catch_panic(|| {
+                    let batch: Vec<(&Signature, &[u8], &VerKey)> = batch.iter()
+                        .map(|(signature, message, ver_key)| (signature, message.as_slice(), ver_key))
+                        .collect();
+                    match Bls::verify_batch(&batch, &gen) {
+                        Ok(res) => {
+                            valid = res;
+                            ErrorCode::Success
+                        }
+                        Err(err) => err.into()
+                    }
+                });
+
+                trace!("indy_crypto_bls_verify_batch_async: command_handle: {:?}, err: {:?}, valid: {:?}", command_handle, err, valid);
+
+                if panic::catch_unwind(|| cb(command_handle, err, valid)).is_err() {
+                    warn!("indy_crypto_bls_verify_batch_async: callback of command_handle {:?} panicked", command_handle);
+                }
+            });
+
+        let res = match worker {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => IndyCryptoError::InvalidState(format!("Unable to spawn verification thread: {}", err)).into()
+        };
+
+        trace!("indy_crypto_bls_verify_batch_async: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the proof of possession and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `pop` - Proof of possession
+    /// * `ver_key` - Verification key instance pinter
+    /// * `gen` - Generator instance pointer
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bsl_verify_pop(pop: *const c_void,
+                                      ver_key: *const c_void,
+                                      gen: *const c_void,
+                                      valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bsl_verify_pop: >>> pop: {:?}, ver_key: {:?}, gen: {:?}, valid_p: {:?}", pop, ver_key, gen, valid_p);
+
+        check_useful_c_reference!(pop, ProofOfPossession, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(ver_key, VerKey, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(gen, Generator, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bsl_verify_pop: pop: {:?}, ver_key: {:?}, gen: {:?}", pop, ver_key, gen);
+
+        let res = match Bls::verify_proof_of_posession(pop, ver_key, gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bsl_verify_pop: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bsl_verify_pop: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bsl_verify_pop that describes the failure in `error_detail_p`,
+    /// see indy_crypto_bls_verify_ext for error detail json.
+    ///
+    /// # Arguments
+    ///
+    /// * `pop`, `ver_key`, `gen`, `valid_p` - The same as of indy_crypto_bsl_verify_pop
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_pop_ext(pop: *const c_void,
+                                          ver_key: *const c_void,
+                                          gen: *const c_void,
+                                          valid_p: *mut bool,
+                                          error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_pop_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam5);
+
+        let res = indy_crypto_bsl_verify_pop(pop, ver_key, gen, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_pop_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Splits random (or seeded from seed) sign key to `n` shares so that any `threshold` of them can sign.
+    ///
+    /// Sign key is returned to the dealer to derive the verification key of the group, it isn't needed to sign.
+    /// Shares are written to the caller allocated array of `n` pointers, share at position i has index i + 1.
+    ///
+    /// Note: Sign key instance deallocation must be performed by calling indy_crypto_bls_sign_key_free
+    /// and sign key share instances deallocation by calling indy_crypto_bls_sign_key_share_free.
+    ///
+    /// # Arguments
+    /// * `n` - Number of shares
+    /// * `threshold` - Number of shares required to combine the signature, from 1 to `n`
+    /// * `seed` - Seed buffer pointer. For random generation null must be passed.
+    /// * `seed_len` - Seed buffer len.
+    /// * `sign_key_p` - Reference that will contain sign key instance pointer
+    /// * `shares_p` - Array of `n` pointers that will contain sign key share instance pointers
+    pub fn indy_crypto_bls_generate_shares(n: u32,
+                                           threshold: u32,
+                                           seed: *const u8,
+                                           seed_len: usize,
+                                           sign_key_p: *mut *const c_void,
+                                           shares_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_generate_shares: >>> n: {:?}, threshold: {:?}, seed: {:?}, seed_len: {:?}, sign_key_p: {:?}, shares_p: {:?}",
+               n, threshold, seed, seed_len, sign_key_p, shares_p);
+
+        if n == 0 {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam1, "`n` is 0".to_string());
+        }
+        if threshold == 0 || threshold > n {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, format!("`threshold` must be from 1 to {}, actual {}", n, threshold));
+        }
+        check_useful_opt_c_byte_array!(seed, seed_len,
+                                       ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+        check_useful_c_ptr!(sign_key_p, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(shares_p, ErrorCode::CommonInvalidParam6);
+
+        trace!("indy_crypto_bls_generate_shares: seed: {:?}", secret!(&seed));
+
+        // Only the seed can be wrong here, its length is checked by SignKey::from_seed
+        let res = match Threshold::generate_shares(n, threshold, seed).with_param(4) {
+            Ok((sign_key, shares)) => {
+                trace!("indy_crypto_bls_generate_shares: sign_key: {:?}, shares: {:?}", secret!(&sign_key), secret!(&shares));
+                unsafe {
+                    *sign_key_p = register_c_reference!(sign_key);
+                    for (i, share) in shares.into_iter().enumerate() {
+                        *shares_p.add(i) = register_c_reference!(share);
+                    }
+                    trace!("indy_crypto_bls_generate_shares: *sign_key_p: {:?}", *sign_key_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_generate_shares: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns sign key share from bytes representation.
+    ///
+    /// Note: Sign key share instance deallocation must be performed by calling indy_crypto_bls_sign_key_share_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `share_p` - Reference that will contain sign key share instance pointer
+    pub fn indy_crypto_bls_sign_key_share_from_bytes(bytes: *const u8, bytes_len: usize,
+                                                     share_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_share_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, share_p: {:?}", bytes, bytes_len, share_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(share_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_sign_key_share_from_bytes: bytes: {:?}", secret!(&bytes));
+
+        let res = match SignKeyShare::from_bytes(bytes) {
+            Ok(share) => {
+                trace!("indy_crypto_bls_sign_key_share_from_bytes: share: {:?}", secret!(&share));
+                unsafe {
+                    *share_p = register_c_reference!(share);
+                    trace!("indy_crypto_bls_sign_key_share_from_bytes: *share_p: {:?}", *share_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_sign_key_share_from_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Returns bytes representation of sign key share.
+    ///
+    /// Note: Returned buffer lifetime is the same as sign key share instance.
+    ///
+    /// # Arguments
+    /// * `share` - Sign key share instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_sign_key_share_as_bytes(share: *const c_void,
+                                                   bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_key_share_as_bytes: >>> share: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", share, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(share, SignKeyShare, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_sign_key_share_as_bytes: share: {:?}", secret!(share));
+
+        unsafe {
+            *bytes_p = share.as_bytes().as_ptr();
+            *bytes_len_p = share.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_sign_key_share_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Deallocates sign key share instance, the share is zeroized.
+    ///
+    /// # Arguments
+    /// * `share` - Sign key share instance pointer
+    pub fn indy_crypto_bls_sign_key_share_free(share: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(share, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_sign_key_share_free: >>> share: {:?}", secret!(share));
+
+        let res = free_c_reference!(share, SignKeyShare, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_sign_key_share_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Signs the message with sign key share and returns signature share.
+    ///
+    /// Note: Signature share instance deallocation must be performed by calling indy_crypto_bls_signature_share_free
+    ///
+    /// # Arguments
+    /// * `message` - Message to sign buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to sign buffer len
+    /// * `share` - Sign key share instance pointer
+    /// * `signature_share_p` - Reference that will contain signature share instance pointer
+    pub fn indy_crypto_bls_sign_share(message: *const u8,
+                                      message_len: usize,
+                                      share: *const c_void,
+                                      signature_share_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_share: >>> message: {:?}, message_len: {:?}, share: {:?}, signature_share_p: {:?}", message, message_len, share, signature_share_p);
+
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(share, SignKeyShare, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(signature_share_p, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_bls_sign_share: message: {:?}, share: {:?}", message, secret!(share));
+
+        let res = match Threshold::sign_share(message, share) {
+            Ok(signature_share) => {
+                trace!("indy_crypto_bls_sign_share: signature_share: {:?}", signature_share);
+                unsafe {
+                    *signature_share_p = register_c_reference!(signature_share);
+                    trace!("indy_crypto_bls_sign_share: *signature_share_p: {:?}", *signature_share_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_sign_share: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns signature share from bytes representation.
+    ///
+    /// Note: Signature share instance deallocation must be performed by calling indy_crypto_bls_signature_share_free
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes buffer pointer
+    /// * `bytes_len` - Bytes buffer len
+    /// * `signature_share_p` - Reference that will contain signature share instance pointer
+    pub fn indy_crypto_bls_signature_share_from_bytes(bytes: *const u8, bytes_len: usize,
+                                                      signature_share_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_share_from_bytes: >>> bytes: {:?}, bytes_len: {:?}, signature_share_p: {:?}", bytes, bytes_len, signature_share_p);
+
+        check_useful_c_byte_array!(bytes, bytes_len,
+                                   ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(signature_share_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_signature_share_from_bytes: bytes: {:?}", bytes);
+
+        let res = match SignatureShare::from_bytes(bytes) {
+            Ok(signature_share) => {
+                trace!("indy_crypto_bls_signature_share_from_bytes: signature_share: {:?}", signature_share);
+                unsafe {
+                    *signature_share_p = register_c_reference!(signature_share);
+                    trace!("indy_crypto_bls_signature_share_from_bytes: *signature_share_p: {:?}", *signature_share_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_signature_share_from_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Returns bytes representation of signature share.
+    ///
+    /// Note: Returned buffer lifetime is the same as signature share instance.
+    ///
+    /// # Arguments
+    /// * `signature_share` - Signature share instance pointer
+    /// * `bytes_p` - Pointer that will contains bytes buffer
+    /// * `bytes_len_p` - Pointer that will contains bytes buffer len
+    pub fn indy_crypto_bls_signature_share_as_bytes(signature_share: *const c_void,
+                                                    bytes_p: *mut *const u8, bytes_len_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_signature_share_as_bytes: >>> signature_share: {:?}, bytes_p: {:?}, bytes_len_p: {:?}", signature_share, bytes_p, bytes_len_p);
+
+        check_useful_c_reference!(signature_share, SignatureShare, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(bytes_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(bytes_len_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_signature_share_as_bytes: signature_share: {:?}", signature_share);
+
+        unsafe {
+            *bytes_p = signature_share.as_bytes().as_ptr();
+            *bytes_len_p = signature_share.as_bytes().len();
+        };
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_signature_share_as_bytes: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Deallocates signature share instance.
+    ///
+    /// # Arguments
+    /// * `signature_share` - Signature share instance pointer
+    pub fn indy_crypto_bls_signature_share_free(signature_share: *const c_void) -> ErrorCode {
+        check_useful_c_ptr!(signature_share, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_signature_share_free: >>> signature_share: {:?}", signature_share);
+
+        let res = free_c_reference!(signature_share, SignatureShare, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_bls_signature_share_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Combines signature shares of the same message to the signature verifiable with the verification key of the group.
+    /// Only the first `threshold` shares are used.
+    ///
+    /// CryptoNotEnoughShares is returned if less than `threshold` shares are given, CommonInvalidStructure
+    /// if shares have different thresholds or duplicate indexes.
+    ///
+    /// Note: Signature instance deallocation must be performed by calling indy_crypto_bls_signature_free.
+    ///
+    /// # Arguments
+    /// * `signature_shares` - Signature share instance pointers array
+    /// * `signature_shares_len` - Signature share instance pointers array len
+    /// * `signature_p` - Reference that will contain signature instance pointer
+    pub fn indy_crypto_bls_combine_signature_shares(signature_shares: *const *const c_void,
+                                                    signature_shares_len: usize,
+                                                    signature_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_bls_combine_signature_shares: >>> signature_shares: {:?}, signature_shares_len: {:?}, signature_p: {:?}",
+               signature_shares, signature_shares_len, signature_p);
+
+        check_useful_c_reference_array!(signature_shares, signature_shares_len, SignatureShare,
+                                        ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(signature_p, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_bls_combine_signature_shares: signature_shares: {:?}", signature_shares);
+
+        let res = match Threshold::combine_signature_shares(&signature_shares) {
+            Ok(signature) => {
+                trace!("indy_crypto_bls_combine_signature_shares: signature: {:?}", signature);
+                unsafe {
+                    *signature_p = register_c_reference!(signature);
+                    trace!("indy_crypto_bls_combine_signature_shares: *signature_p: {:?}", *signature_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_combine_signature_shares: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Signs the message and returns signature json.
+    ///
+    /// Objects are taken and returned as json of their serde representation, so short-lived
+    /// operations don't need handles. Json is validated strictly, invalid one is reported
+    /// as invalid param.
+    ///
+    /// Note: Signature json deallocation must be performed by calling indy_crypto_string_free
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message to sign buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to sign buffer len
+    /// * `sign_key_json` - Sign key json
+    /// * `signature_json_p` - Reference that will contain signature json
+    pub fn indy_crypto_bls_sign_json(message: *const u8,
+                                     message_len: usize,
+                                     sign_key_json: *const c_char,
+                                     signature_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_sign_json: >>> message: {:?}, message_len: {:?}, sign_key_json: {:?}, signature_json_p: {:?}", message, message_len, sign_key_json, signature_json_p);
+
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(sign_key_json, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(signature_json_p, ErrorCode::CommonInvalidParam4);
+
+        let sign_key: SignKey = match _param_from_json("sign_key_json", &sign_key_json, ErrorCode::CommonInvalidParam3) {
+            Ok(sign_key) => sign_key,
+            Err(res) => return res
+        };
+
+        trace!("indy_crypto_bls_sign_json: message: {:?}, sign_key: {:?}", message, secret!(&sign_key));
+
+        let res = match Bls::sign(message, &sign_key).and_then(|signature| _to_json(&signature)) {
+            Ok(signature_json) => {
+                trace!("indy_crypto_bls_sign_json: signature_json: {:?}", signature_json);
+                unsafe {
+                    *signature_json_p = signature_json.into_raw();
+                    trace!("indy_crypto_bls_sign_json: *signature_json_p: {:?}", *signature_json_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_sign_json: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message signature given as json and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature_json` - Signature json
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `ver_key_json` - Verification key json
+    /// * `gen_json` - Generator json
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bls_verify_json(signature_json: *const c_char,
+                                       message: *const u8,
+                                       message_len: usize,
+                                       ver_key_json: *const c_char,
+                                       gen_json: *const c_char,
+                                       valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_json: >>> signature_json: {:?}, message: {:?}, message_len: {:?}, ver_key_json: {:?}, gen_json: {:?}, valid_p: {:?}",
+               signature_json, message, message_len, ver_key_json, gen_json, valid_p);
+
+        check_useful_c_str!(signature_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(ver_key_json, ErrorCode::CommonInvalidParam4);
+        check_useful_c_str!(gen_json, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+
+        let params = _param_from_json("signature_json", &signature_json, ErrorCode::CommonInvalidParam1)
+            .and_then(|signature| _param_from_json("ver_key_json", &ver_key_json, ErrorCode::CommonInvalidParam4)
+                .map(|ver_key| (signature, ver_key)))
+            .and_then(|(signature, ver_key)| _param_from_json("gen_json", &gen_json, ErrorCode::CommonInvalidParam5)
+                .map(|gen| (signature, ver_key, gen)));
+
+        let (signature, ver_key, gen): (Signature, VerKey, Generator) = match params {
+            Ok(params) => params,
+            Err(res) => return res
+        };
+
+        trace!("indy_crypto_bls_verify_json: signature: {:?}, message: {:?}, ver_key: {:?}, gen: {:?}", signature, message, ver_key, gen);
+
+        let res = match Bls::verify(&signature, message, &ver_key, &gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_json: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_json: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bls_verify_json that describes the failure in `error_detail_p`,
+    /// see indy_crypto_bls_verify_ext for error detail json.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature_json`, `message`, `message_len`, `ver_key_json`, `gen_json`, `valid_p` - The same as of indy_crypto_bls_verify_json
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_json_ext(signature_json: *const c_char,
+                                           message: *const u8,
+                                           message_len: usize,
+                                           ver_key_json: *const c_char,
+                                           gen_json: *const c_char,
+                                           valid_p: *mut bool,
+                                           error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_json_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam7);
+
+        let res = indy_crypto_bls_verify_json(signature_json, message, message_len, ver_key_json, gen_json, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_json_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates and returns multi signature json for json array of signatures.
+    ///
+    /// Note: Multi signature json deallocation must be performed by calling indy_crypto_string_free
+    ///
+    /// # Arguments
+    /// * `signatures_json` - Json array of signatures
+    /// * `multi_sig_json_p` - Reference that will contain multi signature json
+    pub fn indy_crypto_bls_multi_signature_json(signatures_json: *const c_char,
+                                                multi_sig_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_multi_signature_json: >>> signatures_json: {:?}, multi_sig_json_p: {:?}", signatures_json, multi_sig_json_p);
+
+        check_useful_c_str!(signatures_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(multi_sig_json_p, ErrorCode::CommonInvalidParam2);
+
+        let signatures: Vec<Signature> = match _params_from_json("signatures_json", &signatures_json, ErrorCode::CommonInvalidParam1) {
+            Ok(signatures) => signatures,
+            Err(res) => return res
+        };
+        let signatures: Vec<&Signature> = signatures.iter().collect();
+
+        trace!("indy_crypto_bls_multi_signature_json: signatures: {:?}", signatures);
+
+        let res = match MultiSignature::new(&signatures).and_then(|multi_sig| _to_json(&multi_sig)) {
+            Ok(multi_sig_json) => {
+                trace!("indy_crypto_bls_multi_signature_json: multi_sig_json: {:?}", multi_sig_json);
+                unsafe {
+                    *multi_sig_json_p = multi_sig_json.into_raw();
+                    trace!("indy_crypto_bls_multi_signature_json: *multi_sig_json_p: {:?}", *multi_sig_json_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_multi_signature_json: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Verifies the message multi signature given as json and returns true - if signature valid or false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig_json` - Multi signature json
+    /// * `message` - Message to verify buffer pointer, may be null if `message_len` is 0
+    /// * `message_len` - Message to verify buffer len
+    /// * `ver_keys_json` - Json array of verification keys
+    /// * `gen_json` - Generator json
+    /// * `valid_p` - Reference that will be filled with true - if signature valid or false otherwise.
+    pub fn indy_crypto_bls_verify_multi_sig_json(multi_sig_json: *const c_char,
+                                                 message: *const u8,
+                                                 message_len: usize,
+                                                 ver_keys_json: *const c_char,
+                                                 gen_json: *const c_char,
+                                                 valid_p: *mut bool) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig_json: >>> multi_sig_json: {:?}, message: {:?}, message_len: {:?}, ver_keys_json: {:?}, gen_json: {:?}, valid_p: {:?}",
+               multi_sig_json, message, message_len, ver_keys_json, gen_json, valid_p);
+
+        check_useful_c_str!(multi_sig_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_message!(message, message_len, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(ver_keys_json, ErrorCode::CommonInvalidParam4);
+        check_useful_c_str!(gen_json, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(valid_p, ErrorCode::CommonInvalidParam6);
+
+        let params = _param_from_json("multi_sig_json", &multi_sig_json, ErrorCode::CommonInvalidParam1)
+            .and_then(|multi_sig| _params_from_json("ver_keys_json", &ver_keys_json, ErrorCode::CommonInvalidParam4)
+                .map(|ver_keys| (multi_sig, ver_keys)))
+            .and_then(|(multi_sig, ver_keys)| _param_from_json("gen_json", &gen_json, ErrorCode::CommonInvalidParam5)
+                .map(|gen| (multi_sig, ver_keys, gen)));
+
+        let (multi_sig, ver_keys, gen): (MultiSignature, Vec<VerKey>, Generator) = match params {
+            Ok(params) => params,
+            Err(res) => return res
+        };
+        let ver_keys: Vec<&VerKey> = ver_keys.iter().collect();
+
+        trace!("indy_crypto_bls_verify_multi_sig_json: multi_sig: {:?}, message: {:?}, ver_keys: {:?}, gen: {:?}", multi_sig, message, ver_keys, gen);
+
+        let res = match Bls::verify_multi_sig(&multi_sig, message, &ver_keys, &gen) {
+            Ok(valid) => {
+                trace!("indy_crypto_bls_verify_multi_sig_json: valid: {:?}", valid);
+                unsafe { *valid_p = valid; }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_bls_verify_multi_sig_json: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Variant of indy_crypto_bls_verify_multi_sig_json that describes the failure in `error_detail_p`,
+    /// see indy_crypto_bls_verify_ext for error detail json.
+    ///
+    /// # Arguments
+    ///
+    /// * `multi_sig_json`, `message`, `message_len`, `ver_keys_json`, `gen_json`, `valid_p` - The same as of indy_crypto_bls_verify_multi_sig_json
+    /// * `error_detail_p` - Reference that will contain error detail json or null
+    pub fn indy_crypto_bls_verify_multi_sig_json_ext(multi_sig_json: *const c_char,
+                                                     message: *const u8,
+                                                     message_len: usize,
+                                                     ver_keys_json: *const c_char,
+                                                     gen_json: *const c_char,
+                                                     valid_p: *mut bool,
+                                                     error_detail_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_verify_multi_sig_json_ext: >>> error_detail_p: {:?}", error_detail_p);
+
+        check_useful_c_ptr!(error_detail_p, ErrorCode::CommonInvalidParam7);
+
+        let res = indy_crypto_bls_verify_multi_sig_json(multi_sig_json, message, message_len, ver_keys_json, gen_json, valid_p);
+        _set_error_detail(res, error_detail_p);
+
+        trace!("indy_crypto_bls_verify_multi_sig_json_ext: <<< res: {:?}", res);
+        res
+    }
+}
+
+// Json has both the point and its bytes, the value is rebuilt from the bytes,
+// so they are validated by from_bytes and can't disagree with the point
+trait FromJson: Sized + DeserializeOwned {
+    const BYTES_SIZE: usize;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, IndyCryptoError>;
+    fn as_bytes(&self) -> &[u8];
+
+    fn from_json(json: &str) -> Result<Self, IndyCryptoError> {
+        let value: Self = serde_json::from_str(json)?;
+        Self::from_bytes(value.as_bytes())
+    }
+}
+
+macro_rules! impl_from_json {
+    ($($type:ty),*) => {$(
+        impl FromJson for $type {
+            const BYTES_SIZE: usize = <$type>::BYTES_SIZE;
+
+            fn from_bytes(bytes: &[u8]) -> Result<Self, IndyCryptoError> { <$type>::from_bytes(bytes) }
+            fn as_bytes(&self) -> &[u8] { <$type>::as_bytes(self) }
+        }
+    )*};
+}
+
+impl_from_json!(Generator, SignKey, VerKey, Signature, MultiSignature);
+
+fn _param_from_json<T: FromJson>(name: &str, json: &str, err: ErrorCode) -> Result<T, ErrorCode> {
+    T::from_json(json)
+        .map_err(|e| ctypes::invalid_param(err, format!("`{}` is invalid: {}", name, e)))
+}
+
+// Hex is decoded to bytes of the exact size, then validated by from_bytes as json is
+fn _param_from_hex<T: FromJson>(name: &str, hex: &str, err: ErrorCode) -> Result<T, ErrorCode> {
+    // Bytes of sign keys are secret, so all decoded bytes are zeroized
+    let bytes = from_hex(hex)
+        .map(SecretBytes::new)
+        .map_err(|e| ctypes::invalid_param(err, format!("`{}` is invalid: {}", name, e)))?;
+
+    if bytes.len() != T::BYTES_SIZE {
+        return Err(ctypes::invalid_param(err, format!("`{}` must encode {} bytes, actual {}", name, T::BYTES_SIZE, bytes.len())));
+    }
+
+    T::from_bytes(&bytes)
+        .map_err(|e| ctypes::invalid_param(err, format!("`{}` is invalid: {}", name, e)))
+}
+
+fn _params_from_json<T: FromJson>(name: &str, json: &str, err: ErrorCode) -> Result<Vec<T>, ErrorCode> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| ctypes::invalid_param(err, format!("`{}` is invalid: {}", name, e)))?;
+
+    if values.is_empty() {
+        return Err(ctypes::invalid_param(err, format!("`{}` is empty array", name)));
+    }
+
+    values.iter()
+        .enumerate()
+        .map(|(index, value)| T::from_json(&value.to_string())
+            .map_err(|e| ctypes::invalid_param(err, format!("`{}` item {} is invalid: {}", name, index, e))))
+        .collect()
+}
+
+fn _to_json<T: Serialize>(value: &T) -> Result<CString, IndyCryptoError> {
+    serde_json::to_string(value)
+        .map_err(|err| IndyCryptoError::InvalidState(format!("Unable to convert to json: {}", err)))
+        .and_then(CTypesUtils::string_to_cstring)
+}
+
+type Batch<'a> = Vec<(handles::Ref<Signature>, &'a [u8], handles::Ref<VerKey>)>;
+
+// Resolves handles of batch items, the error tells the first invalid item
+fn _batch_items(items: &[BatchItem]) -> Result<Batch<'_>, String> {
+    items.iter()
+        .enumerate()
+        .map(|(index, item)| {
+            if item.message.is_null() && item.message_len > 0 {
+                return Err(format!("Item {} has invalid `message`", index));
+            }
+
+            let signature = handles::get::<Signature>(item.signature)
+                .map_err(|reason| format!("Item {} has invalid `signature`: {}", index, reason))?;
+            let ver_key = handles::get::<VerKey>(item.ver_key)
+                .map_err(|reason| format!("Item {} has invalid `ver_key`: {}", index, reason))?;
+            let message: &[u8] = if item.message_len == 0 {
+                &[]
+            } else {
+                unsafe { slice::from_raw_parts(item.message, item.message_len) }
+            };
+            Ok((signature, message, ver_key))
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct ErrorDetail {
+    code: usize,
+    name: &'static str,
+    param: Option<usize>,
+    message: String,
+}
+
+// Fills `error_detail_p` of `_ext` functions with detail of the error `res` was returned for
+fn _set_error_detail(res: ErrorCode, error_detail_p: *mut *const c_char) {
+    let error_detail = if res == ErrorCode::Success {
+        None
+    } else {
+        let error_detail = ErrorDetail {
+            code: res as usize,
+            name: res.name(),
+            param: res.param_index(),
+            message: get_current_error_message().unwrap_or_default(),
+        };
+        _to_json(&error_detail).ok()
+    };
+
+    unsafe {
+        *error_detail_p = error_detail.map_or(ptr::null(), |error_detail| error_detail.into_raw() as *const c_char);
+    }
+}
+
+fn _merge_multi_signatures(a: &MultiSignature, b: &MultiSignature) -> Result<MultiSignature, IndyCryptoError> {
+    let mut multi_sig = MultiSignature::empty()?;
+    multi_sig.merge(a)?;
+    multi_sig.merge(b)?;
+    Ok(multi_sig)
+}
+
+fn _write_into_buffer(bytes: &[u8], buf: *mut u8, buf_len: usize, written_p: *mut usize) -> ErrorCode {
+    unsafe { *written_p = bytes.len(); }
+
+    if buf.is_null() {
+        return ErrorCode::Success;
+    }
+
+    if buf_len < bytes.len() {
+        return IndyCryptoError::InvalidStructure(
+            format!("Buffer is too small: {} bytes required, {} given", bytes.len(), buf_len)).into();
+    }
+
+    unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), buf, bytes.len()); }
+    ErrorCode::Success
+}
+
+/// Byte sizes of bls objects and of pair module primitives by name, see indy_crypto_bls_constant.
+const CONSTANTS: &[(&str, usize)] = &[
+    ("generator_size", Generator::BYTES_SIZE),
+    ("generator_compressed_size", Generator::COMPRESSED_BYTES_SIZE),
+    ("sign_key_size", SignKey::BYTES_SIZE),
+    ("seed_size", GroupOrderElement::BYTES_REPR_SIZE),
+    ("ver_key_size", VerKey::BYTES_SIZE),
+    ("ver_key_compressed_size", VerKey::COMPRESSED_BYTES_SIZE),
+    ("signature_size", Signature::BYTES_SIZE),
+    ("signature_compressed_size", Signature::COMPRESSED_BYTES_SIZE),
+    ("multi_signature_size", MultiSignature::BYTES_SIZE),
+    ("multi_signature_compressed_size", MultiSignature::COMPRESSED_BYTES_SIZE),
+    ("point_g1_size", PointG1::BYTES_REPR_SIZE),
+    ("point_g1_compressed_size", PointG1::COMPRESSED_BYTES_REPR_SIZE),
+    ("point_g2_size", PointG2::BYTES_REPR_SIZE),
+    ("point_g2_compressed_size", PointG2::COMPRESSED_BYTES_REPR_SIZE),
+    ("group_order_element_size", GroupOrderElement::BYTES_REPR_SIZE),
+    ("pair_size", Pair::BYTES_REPR_SIZE),
+];
+
+lazy_static! {
+    static ref C_CURVE_NAME: CString = CString::new(CURVE_NAME).unwrap();
+}
+
+ffi_fn! {
+    /// Returns value of the library constant, so bindings don't have to hardcode them.
+    ///
+    /// Known names are byte sizes of bls objects: `generator_size`, `sign_key_size`, `seed_size`
+    /// (the seed of indy_crypto_bls_sign_key_new), `ver_key_size`, `signature_size`, `multi_signature_size`,
+    /// `*_compressed_size` of generator, ver key, signature and multi signature, and byte sizes of
+    /// pairing primitives: `point_g1_size`, `point_g2_size`, `point_g1_compressed_size`,
+    /// `point_g2_compressed_size`, `group_order_element_size` and `pair_size`.
+    ///
+    /// # Arguments
+    /// * `name` - Constant name
+    /// * `value_p` - Reference that will contain the constant value
+    pub fn indy_crypto_bls_constant(name: *const c_char,
+                                    value_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_bls_constant: >>> name: {:?}, value_p: {:?}", name, value_p);
+
+        check_useful_c_str!(name, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(value_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_bls_constant: name: {:?}", name);
+
+        let value = match CONSTANTS.iter().find(|(known, _)| *known == name) {
+            Some((_, value)) => *value,
+            None => return ctypes::invalid_param(ErrorCode::CommonInvalidParam1, format!("Unknown constant: {}", name))
+        };
+
+        trace!("indy_crypto_bls_constant: value: {:?}", value);
+        unsafe { *value_p = value; }
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_constant: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Returns name of the pairing friendly curve the library is built with, e.g. "BN254".
+    ///
+    /// Note: Returned string is owned by the library and must not be deallocated.
+    ///
+    /// # Arguments
+    /// * `curve_name_p` - Reference that will contain curve name string pointer
+    pub fn indy_crypto_bls_curve_name(curve_name_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_bls_curve_name: >>> curve_name_p: {:?}", curve_name_p);
+
+        check_useful_c_ptr!(curve_name_p, ErrorCode::CommonInvalidParam1);
+
+        unsafe {
+            *curve_name_p = C_CURVE_NAME.as_ptr();
+            trace!("indy_crypto_bls_curve_name: *curve_name_p: {:?}", *curve_name_p);
+        }
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_bls_curve_name: <<< res: {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::error::indy_crypto_get_current_error;
+    use crate::ffi::memory::indy_crypto_string_free;
+    use libc::c_char;
+    use serde_json;
+    use std::collections::HashMap;
+    use std::ffi::CStr;
+    use std::ptr;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn indy_crypto_bls_generator_new_works() {
+        let mut gen: *const c_void = ptr::null();
+
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!gen.is_null());
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_generator_as_bytes_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!gen.is_null());
+        assert!(bytes_len > 0);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_generator_from_bytes_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut gen2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_from_bytes(bytes, bytes_len, &mut gen2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_generator_free(gen2);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_generator_free_works() {
+        let mut gen: *const c_void = ptr::null();
+
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!gen.is_null());
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_new_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!sign_key.is_null());
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_new_works_for_seed() {
+        let mut sign_key: *const c_void = ptr::null();
+
+        let seed_v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+                          11, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+                          21, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31, 32];
+        let seed = seed_v.as_ptr();
+        let seed_len = seed_v.len();
+
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!sign_key.is_null());
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_new_works_for_invalid_seed_len() {
+        let mut sign_key: *const c_void = ptr::null();
+
+        let seed_v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let seed = seed_v.as_ptr();
+        let seed_len = seed_v.len();
+
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        assert!(sign_key.is_null());
+
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        let error_json = unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() };
+        let error: serde_json::Value = serde_json::from_str(error_json).unwrap();
+
+        let inner_err = SignKey::new(Some(&seed_v)).unwrap_err();
+        assert!(error["message"].as_str().unwrap().ends_with(&inner_err.to_string()));
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_as_bytes_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_as_bytes(sign_key, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_from_bytes_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_as_bytes(sign_key, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_from_bytes(bytes, bytes_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_free_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_sign_key_free_works_for_reuse() {
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(gen);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+        assert!(_current_error_message().ends_with("is already freed"));
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_as_bytes(sign_key, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        // Generator is not affected by the failed free
+        let err_code = indy_crypto_bls_generator_as_bytes(gen, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    #[test]
+    fn indy_crypto_bls_ver_key_new_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!ver_key.is_null());
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_ver_key_as_bytes_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_ver_key_as_bytes(ver_key, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_ver_key_from_bytes_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_ver_key_as_bytes(ver_key, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let mut ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_from_bytes(bytes, bytes_len, &mut ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_ver_key_free_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_signature_as_bytes_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_signature_as_bytes(signature, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_signature_from_bytes_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_signature_as_bytes(signature, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_signature_from_bytes(bytes, bytes_len, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_signature_free_works() {
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_multi_signature_new_works() {
+        let mut sign_key1: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!multi_sig.is_null());
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_multi_signature_as_bytes_works() {
+        let mut sign_key1: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!multi_sig.is_null());
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_multi_signature_as_bytes(multi_sig, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_multi_signature_from_bytes_works() {
+        let mut sign_key1: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_multi_signature_as_bytes(multi_sig, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let mut multi_sig2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_from_bytes(bytes, bytes_len, &mut multi_sig2);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!multi_sig2.is_null());
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig2);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_multi_signature_free_works() {
+        let mut sign_key1: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+
+    #[test]
+    fn indy_crypto_bsl_verify_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut valid = false;
+
+        let err_code = indy_crypto_bsl_verify(signature,
+                                              message, message_len,
+                                              ver_key,
+                                              gen, &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature);
+        assert_eq!(err_code, ErrorCode::Success);
     }
 
     #[test]
-    fn indy_crypto_bls_generator_free_works() {
+    fn indy_crypto_bsl_verify_works_for_invalid() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut valid = false;
+
+        let err_code = indy_crypto_bsl_verify(signature,
+                                              message, message_len,
+                                              ver_key,
+                                              gen, &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_pop_new_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!ver_key.is_null());
+
+        let mut pop: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!pop.is_null());
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_pop_free(pop);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_pop_as_bytes_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut pop: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_pop_as_bytes(pop, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_pop_free(pop);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_pop_from_bytes_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut pop: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_pop_as_bytes(pop, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!bytes.is_null());
+        assert!(bytes_len > 0);
+
+        let mut pop2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_from_bytes(bytes, bytes_len, &mut pop2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_pop_free(pop);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_pop_free(pop2);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bls_pop_free_works() {
         let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut pop: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_pop_free(pop);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_bsl_verify_pop_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut pop: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut valid = false;
+
+        let err_code = indy_crypto_bsl_verify_pop(pop,
+                                                  ver_key,
+                                                  gen,
+                                                  &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!gen.is_null());
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let err_code = indy_crypto_bls_pop_free(pop);
         assert_eq!(err_code, ErrorCode::Success);
     }
 
     #[test]
-    fn indy_crypto_bls_sign_key_new_works() {
-        let mut sign_key: *const c_void = ptr::null();
+    fn indy_crypto_bls_verify_multi_sig_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut sign_key1: *const c_void = ptr::null();
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!sign_key.is_null());
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
-    }
 
-    #[test]
-    fn indy_crypto_bls_sign_key_new_works_for_seed() {
-        let mut sign_key: *const c_void = ptr::null();
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
 
-        let seed_v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
-                          11, 2, 3, 4, 5, 6, 7, 8, 9, 10,
-                          21, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31, 32];
-        let seed = seed_v.as_ptr();
-        let seed_len = seed_v.len();
+        let signatures = [signature1, signature2];
 
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!sign_key.is_null());
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let mut ver_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
         assert_eq!(err_code, ErrorCode::Success);
-    }
 
-    #[test]
-    fn indy_crypto_bls_sign_key_as_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let mut ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_as_bytes(sign_key, &mut bytes, &mut bytes_len);
+        let ver_keys = [ver_key1, ver_key2];
+        let mut valid = false;
+
+        let err_code = indy_crypto_bls_verify_multi_sig(multi_sig,
+                                                        message, message_len,
+                                                        ver_keys.as_ptr(), ver_keys.len(),
+                                                        gen,
+                                                        &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
+        assert!(valid);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
-    }
 
-    #[test]
-    fn indy_crypto_bls_sign_key_from_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_as_bytes(sign_key, &mut bytes, &mut bytes_len);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign_key_from_bytes(bytes, bytes_len, &mut sign_key2);
+        let err_code = indy_crypto_bls_ver_key_free(ver_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        let err_code = indy_crypto_bls_signature_free(signature1);
         assert_eq!(err_code, ErrorCode::Success);
-    }
 
-    #[test]
-    fn indy_crypto_bls_sign_key_free_works() {
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_signature_free(signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
     }
 
     #[test]
-    fn indy_crypto_bls_ver_key_new_works() {
+    fn indy_crypto_bls_verify_multi_sig_works_for_invalid() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
+        let mut sign_key1: *const c_void = ptr::null();
         let seed: *const u8 = ptr::null();
         let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        let mut sign_key2: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!ver_key.is_null());
+
+        let mut sign_key3: *const c_void = ptr::null();
+        let seed: *const u8 = ptr::null();
+        let seed_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key3);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key3, &mut ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let ver_keys = [ver_key1, ver_key2];
+        let mut valid = false;
+
+        let err_code = indy_crypto_bls_verify_multi_sig(multi_sig,
+                                                        message, message_len,
+                                                        ver_keys.as_ptr(), ver_keys.len(),
+                                                        gen,
+                                                        &mut valid);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
 
         let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_sign_key_free(sign_key3);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_signature_free(signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
     }
 
     #[test]
-    fn indy_crypto_bls_ver_key_as_bytes_works() {
+    fn indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key_works() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let mut sign_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        let mut sign_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message_v = vec![1, 2, 3, 4, 5];
+        let message = message_v.as_ptr();
+        let message_len = message_v.len();
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature1, signature2];
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let ver_keys = [ver_key1, ver_key2];
+        let mut agg_ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), ver_keys.len(), &mut agg_ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut bytes: *const u8 = ptr::null();
         let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_ver_key_as_bytes(ver_key, &mut bytes, &mut bytes_len);
+        let err_code = indy_crypto_bls_aggregated_ver_key_as_bytes(agg_ver_key, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
         assert!(bytes_len > 0);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let mut agg_ver_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_aggregated_ver_key_from_bytes(bytes, bytes_len, &mut agg_ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let mut valid = false;
+        let err_code = indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, message, message_len, agg_ver_key2, gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let message_invalid = [5, 4, 3, 2, 1];
+        let err_code = indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, message_invalid.as_ptr(), message_invalid.len(), agg_ver_key2, gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
+
+        for handle in [ver_key1, ver_key2] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(handle));
+        }
+        for handle in [signature1, signature2] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(handle));
+        }
+        for handle in [sign_key1, sign_key2] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(handle));
+        }
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_free(agg_ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_free(agg_ver_key2));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
     }
 
     #[test]
-    fn indy_crypto_bls_ver_key_from_bytes_works() {
+    fn indy_crypto_bls_aggregated_ver_key_works_for_invalid_params() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let ver_keys = [ver_key];
+        let mut agg_ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_aggregated_ver_key_new(ptr::null(), 1, &mut agg_ver_key));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), 0, &mut agg_ver_key));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), usize::MAX, &mut agg_ver_key));
+        assert!(_current_error_message().contains("`ver_keys_len` is too large"));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_aggregated_ver_key_new([ver_key, ptr::null()].as_ptr(), 2, &mut agg_ver_key));
+        assert!(_current_error_message().contains("Item 1: Handle is null"));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_aggregated_ver_key_new([ver_key, gen].as_ptr(), 2, &mut agg_ver_key));
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), 1, ptr::null_mut()));
+
+        let bytes = [1u8; 3];
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_aggregated_ver_key_from_bytes(ptr::null(), 3, &mut agg_ver_key));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_aggregated_ver_key_from_bytes(bytes.as_ptr(), 0, &mut agg_ver_key));
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_aggregated_ver_key_from_bytes(bytes.as_ptr(), 3, ptr::null_mut()));
+        assert_eq!(ErrorCode::CryptoInvalidKey, indy_crypto_bls_aggregated_ver_key_from_bytes(bytes.as_ptr(), 3, &mut agg_ver_key));
+        assert!(agg_ver_key.is_null());
+
+        let mut bytes_p: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_aggregated_ver_key_as_bytes(ptr::null(), &mut bytes_p, &mut bytes_len));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_aggregated_ver_key_free(ptr::null()));
+
+        let err_code = indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), 1, &mut agg_ver_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let signatures = [signature];
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut valid = false;
+        assert_eq!(ErrorCode::CommonInvalidParam1,
+                   indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(ptr::null(), message.as_ptr(), message.len(), agg_ver_key, gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam2,
+                   indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, ptr::null(), message.len(), agg_ver_key, gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam4,
+                   indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, message.as_ptr(), message.len(), ptr::null(), gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam5,
+                   indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, message.as_ptr(), message.len(), agg_ver_key, ptr::null(), &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam6,
+                   indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(multi_sig, message.as_ptr(), message.len(), agg_ver_key, gen, ptr::null_mut()));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_free(agg_ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    #[test]
+    fn indy_crypto_bls_verify_batch_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        let mut sign_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_ver_key_as_bytes(ver_key, &mut bytes, &mut bytes_len);
+        let mut sign_key2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key2);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
 
         let mut ver_key2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_from_bytes(bytes, bytes_len, &mut ver_key2);
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let message1 = [1u8, 2, 3, 4, 5];
+        let message2 = [5u8, 4, 3, 2, 1];
+
+        let mut signature1: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message1.as_ptr(), message1.len(), sign_key1, &mut signature1);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let mut signature2: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message2.as_ptr(), message2.len(), sign_key2, &mut signature2);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let items = [
+            BatchItem { message: message1.as_ptr(), message_len: message1.len(), signature: signature1, ver_key: ver_key1 },
+            BatchItem { message: message2.as_ptr(), message_len: message2.len(), signature: signature2, ver_key: ver_key2 },
+        ];
+
+        let mut valid = false;
+        let err_code = indy_crypto_bls_verify_batch(items.as_ptr(), items.len(), gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
+        let items = [
+            BatchItem { message: message1.as_ptr(), message_len: message1.len(), signature: signature1, ver_key: ver_key1 },
+            BatchItem { message: message2.as_ptr(), message_len: message2.len(), signature: signature1, ver_key: ver_key2 },
+        ];
+
+        let err_code = indy_crypto_bls_verify_batch(items.as_ptr(), items.len(), gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
+
+        for handle in [ver_key1, ver_key2] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(handle));
+        }
+        for handle in [signature1, signature2] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(handle));
+        }
+        for handle in [sign_key1, sign_key2] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(handle));
+        }
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
     }
 
     #[test]
-    fn indy_crypto_bls_ver_key_free_works() {
+    fn indy_crypto_bls_verify_batch_works_for_invalid_params() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut ver_key: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
-
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let item = BatchItem { message: message.as_ptr(), message_len: message.len(), signature, ver_key };
+        let items = [item];
+
+        let mut valid = true;
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_verify_batch(ptr::null(), 1, gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_verify_batch(items.as_ptr(), 0, gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_verify_batch(items.as_ptr(), 1, ptr::null(), &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam4, indy_crypto_bls_verify_batch(items.as_ptr(), 1, gen, ptr::null_mut()));
+
+        let bad_items = [
+            BatchItem { message: ptr::null(), ..item },
+            BatchItem { signature: ptr::null(), ..item },
+            BatchItem { ver_key: ptr::null(), ..item },
+        ];
+
+        for (bad_item, field) in bad_items.iter().zip(["message", "signature", "ver_key"]) {
+            let items = [item, *bad_item, item];
+            let err_code = indy_crypto_bls_verify_batch(items.as_ptr(), items.len(), gen, &mut valid);
+            assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+            assert!(valid);
+
+            let mut error_json_p: *const c_char = ptr::null();
+            indy_crypto_get_current_error(&mut error_json_p);
+            let error_json = unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() };
+            let error: serde_json::Value = serde_json::from_str(error_json).unwrap();
+            assert!(error["message"].as_str().unwrap().contains(&format!("Item 1 has invalid `{}`", field)));
+        }
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
     }
 
     #[test]
-    fn indy_crypto_bls_signature_as_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
-
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+    fn indy_crypto_bls_handles_work_for_concurrent_threads() {
+        const THREADS: usize = 8;
+        const ITERATIONS: usize = 10_000;
+        const VERIFY_EVERY: usize = 1_000;
 
-        let mut signature: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_signature_as_bytes(signature, &mut bytes, &mut bytes_len);
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_signature_free(signature);
-        assert_eq!(err_code, ErrorCode::Success);
-    }
+        let message = [1u8, 2, 3, 4, 5];
+        let signature = _sign_handle(message.as_ptr(), message.len(), sign_key);
 
-    #[test]
-    fn indy_crypto_bls_signature_from_bytes_works() {
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let gen_bytes = _handle_bytes(gen, indy_crypto_bls_generator_as_bytes);
+        let ver_key_bytes = _handle_bytes(ver_key, indy_crypto_bls_ver_key_as_bytes);
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+        // Handles are tokens, they are passed to threads as numbers
+        let (gen, ver_key, signature) = (gen as usize, ver_key as usize, signature as usize);
 
-        let mut signature: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
-        assert_eq!(err_code, ErrorCode::Success);
+        thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    let (gen, ver_key, signature) = (gen as *const c_void, ver_key as *const c_void, signature as *const c_void);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_signature_as_bytes(signature, &mut bytes, &mut bytes_len);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
+                    for iteration in 0..ITERATIONS {
+                        assert_eq!(gen_bytes, _handle_bytes(gen, indy_crypto_bls_generator_as_bytes));
+                        assert_eq!(ver_key_bytes, _handle_bytes(ver_key, indy_crypto_bls_ver_key_as_bytes));
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_signature_from_bytes(bytes, bytes_len, &mut signature2);
-        assert_eq!(err_code, ErrorCode::Success);
+                        // Other handles are created and freed meanwhile, so the registry grows and reuses slots
+                        let mut multi_sig: *const c_void = ptr::null();
+                        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_new_empty(&mut multi_sig));
+                        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+                        if iteration % VERIFY_EVERY == 0 {
+                            let mut valid = false;
+                            let err_code = indy_crypto_bsl_verify(signature, message.as_ptr(), message.len(), ver_key, gen, &mut valid);
+                            assert_eq!(err_code, ErrorCode::Success);
+                            assert!(valid);
+                        }
+                    }
+                });
+            }
+        });
 
-        let err_code = indy_crypto_bls_signature_free(signature);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature as *const c_void));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key as *const c_void));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen as *const c_void));
+    }
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
-        assert_eq!(err_code, ErrorCode::Success);
+    type VerifyBatchResult = (ErrorCode, bool);
+
+    lazy_static! {
+        static ref VERIFY_BATCH_SENDERS: Mutex<HashMap<i32, mpsc::Sender<VerifyBatchResult>>> = Default::default();
+    }
+
+    static COMMAND_HANDLE_COUNTER: AtomicI32 = AtomicI32::new(1);
+
+    extern fn _verify_batch_cb(command_handle: i32, err: ErrorCode, valid: bool) {
+        let sender = VERIFY_BATCH_SENDERS.lock().unwrap().remove(&command_handle).unwrap();
+        sender.send((err, valid)).unwrap();
+    }
+
+    fn _verify_batch_command() -> (i32, mpsc::Receiver<VerifyBatchResult>) {
+        let command_handle = COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = mpsc::channel();
+        VERIFY_BATCH_SENDERS.lock().unwrap().insert(command_handle, sender);
+        (command_handle, receiver)
     }
 
     #[test]
-    fn indy_crypto_bls_signature_free_works() {
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+    fn indy_crypto_bls_verify_batch_async_works() {
+        for corrupt in [false, true] {
+            let mut gen: *const c_void = ptr::null();
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+            let mut sign_key: *const c_void = ptr::null();
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let mut signature: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
-        assert_eq!(err_code, ErrorCode::Success);
+            let mut ver_key: *const c_void = ptr::null();
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+            let mut message = vec![1u8, 2, 3, 4, 5];
+            let mut signature: *const c_void = ptr::null();
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature));
 
-        let err_code = indy_crypto_bls_signature_free(signature);
-        assert_eq!(err_code, ErrorCode::Success);
+            if corrupt {
+                message[0] = 0;
+            }
+
+            let items = [BatchItem { message: message.as_ptr(), message_len: message.len(), signature, ver_key }];
+            let (command_handle, receiver) = _verify_batch_command();
+            let err_code = indy_crypto_bls_verify_batch_async(items.as_ptr(), items.len(), gen, command_handle, Some(_verify_batch_cb));
+            assert_eq!(err_code, ErrorCode::Success);
+
+            // Items are copied, so everything may be released before the verification ends
+            drop(message);
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+
+            let (err_code, valid) = receiver.recv_timeout(Duration::from_secs(60)).unwrap();
+            assert_eq!(err_code, ErrorCode::Success);
+            assert_eq!(valid, !corrupt);
+        }
     }
 
     #[test]
-    fn indy_crypto_bls_multi_signature_new_works() {
-        let mut sign_key1: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+    fn indy_crypto_bls_verify_batch_async_works_for_invalid_params() {
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
 
-        let mut signature1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature));
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let item = BatchItem { message: message.as_ptr(), message_len: message.len(), signature, ver_key };
+        let bad_items = [item, BatchItem { ver_key: signature, ..item }];
 
-        let signatures = [signature1, signature2];
+        let (command_handle, receiver) = _verify_batch_command();
+        let cb = Some(_verify_batch_cb as VerifyBatchCB);
 
-        let mut multi_sig: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!multi_sig.is_null());
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_verify_batch_async(ptr::null(), 1, gen, command_handle, cb));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_verify_batch_async(&item, 0, gen, command_handle, cb));
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_verify_batch_async(&item, 1, ptr::null(), command_handle, cb));
+        assert_eq!(ErrorCode::CommonInvalidParam5, indy_crypto_bls_verify_batch_async(&item, 1, gen, command_handle, None));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let err_code = indy_crypto_bls_verify_batch_async(bad_items.as_ptr(), bad_items.len(), gen, command_handle, cb);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_current_error_message().contains("Item 1 has invalid `ver_key`"));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        // Callback is not called when the call fails
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        VERIFY_BATCH_SENDERS.lock().unwrap().remove(&command_handle);
 
-        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    fn _current_error_message() -> String {
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        let error_json = unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() };
+        let error: serde_json::Value = serde_json::from_str(error_json).unwrap();
+        error["message"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn indy_crypto_bls_free_works_for_double_free() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
+        let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
     }
 
     #[test]
-    fn indy_crypto_bls_multi_signature_as_bytes_works() {
-        let mut sign_key1: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+    fn indy_crypto_bls_free_works_for_wrong_type() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let err_code = indy_crypto_bls_sign_key_free(gen);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_current_error_message().ends_with("expected indy_crypto::bls::SignKey"));
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+        let err_code = indy_crypto_bls_generator_free(gen);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
 
-        let mut signature1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+    #[test]
+    fn indy_crypto_bls_ver_key_new_works_for_wrong_type() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let signatures = [signature1, signature2];
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(sign_key, gen, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(ver_key.is_null());
+        assert!(_current_error_message().ends_with("is indy_crypto::bls::SignKey, expected indy_crypto::bls::Generator"));
 
+        let signatures = [gen];
         let mut multi_sig: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!multi_sig.is_null());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_current_error_message().contains("Item 0: Handle"));
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_multi_signature_as_bytes(multi_sig, &mut bytes, &mut bytes_len);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
-
-        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
+    }
 
-        let err_code = indy_crypto_bls_signature_free(signature1);
+    #[test]
+    fn indy_crypto_bls_ver_key_as_bytes_works_for_freed_handle() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
-    }
 
-    #[test]
-    fn indy_crypto_bls_multi_signature_from_bytes_works() {
-        let mut sign_key1: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_ver_key_as_bytes(ver_key, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(bytes.is_null());
+        assert!(_current_error_message().ends_with("is already freed"));
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+        let err_code = indy_crypto_bls_ver_key_new(gen, usize::MAX as *const c_void, &mut ver_key);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        assert!(_current_error_message().ends_with("is not a valid handle"));
 
-        let mut signature1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
+    }
 
-        let signatures = [signature1, signature2];
+    #[test]
+    fn indy_crypto_bls_ver_key_as_bytes_into_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        assert_eq!(err_code, ErrorCode::Success);
 
-        let mut multi_sig: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut bytes: *const u8 = ptr::null();
         let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_multi_signature_as_bytes(multi_sig, &mut bytes, &mut bytes_len);
+        let err_code = indy_crypto_bls_ver_key_as_bytes(ver_key, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
+        let bytes = unsafe { slice::from_raw_parts(bytes, bytes_len) }.to_vec();
 
-        let mut multi_sig2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_multi_signature_from_bytes(bytes, bytes_len, &mut multi_sig2);
+        let mut written: usize = 0;
+        let err_code = indy_crypto_bls_ver_key_as_bytes_into(ver_key, ptr::null_mut(), 0, &mut written);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!multi_sig2.is_null());
+        assert_eq!(bytes_len, written);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        let mut buf = vec![0u8; written];
+        let mut written: usize = 0;
+        let err_code = indy_crypto_bls_ver_key_as_bytes_into(ver_key, buf.as_mut_ptr(), buf.len(), &mut written);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(bytes_len, written);
+        assert_eq!(bytes, buf);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+        let mut small_buf = vec![0u8; bytes_len - 1];
+        let mut written: usize = 0;
+        let err_code = indy_crypto_bls_ver_key_as_bytes_into(ver_key, small_buf.as_mut_ptr(), small_buf.len(), &mut written);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+        assert_eq!(bytes_len, written);
+        assert!(small_buf.iter().all(|b| *b == 0));
+
+        let err_code = indy_crypto_bls_ver_key_as_bytes_into(ver_key, ptr::null_mut(), 1, &mut written);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+
+        let err_code = indy_crypto_bls_ver_key_as_bytes_into(ver_key, buf.as_mut_ptr(), buf.len(), ptr::null_mut());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam4);
+
+        let err_code = indy_crypto_bls_ver_key_as_bytes_into(ptr::null(), buf.as_mut_ptr(), buf.len(), &mut written);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let err_code = indy_crypto_bls_ver_key_free(ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_signature_free(signature1);
+        let err_code = indy_crypto_bls_sign_key_free(sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
+        let err_code = indy_crypto_bls_generator_free(gen);
         assert_eq!(err_code, ErrorCode::Success);
+    }
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+    fn _as_bytes_into(handle: *const c_void,
+                      as_bytes: extern fn(*const c_void, *mut *const u8, *mut usize) -> ErrorCode,
+                      as_bytes_into: extern fn(*const c_void, *mut u8, usize, *mut usize) -> ErrorCode) {
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = as_bytes(handle, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
+        let bytes = unsafe { slice::from_raw_parts(bytes, bytes_len) };
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig2);
+        let mut written: usize = 0;
+        let err_code = as_bytes_into(handle, ptr::null_mut(), 0, &mut written);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(bytes_len, written);
+
+        let mut buf = vec![0u8; written + 1];
+        let err_code = as_bytes_into(handle, buf.as_mut_ptr(), buf.len(), &mut written);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(bytes_len, written);
+        assert_eq!(bytes, &buf[..written]);
+
+        let err_code = as_bytes_into(handle, buf.as_mut_ptr(), written - 1, &mut written);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+        assert_eq!(bytes_len, written);
     }
 
     #[test]
-    fn indy_crypto_bls_multi_signature_free_works() {
-        let mut sign_key1: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+    fn indy_crypto_bls_as_bytes_into_works() {
+        let mut gen: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
-
-        let mut signature1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        let mut pop: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let signatures = [signature1, signature2];
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
 
+        let signatures = [signature];
         let mut multi_sig: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
+        let ver_keys = [ver_key];
+        let mut agg_ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), ver_keys.len(), &mut agg_ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        _as_bytes_into(gen, indy_crypto_bls_generator_as_bytes, indy_crypto_bls_generator_as_bytes_into);
+        _as_bytes_into(sign_key, indy_crypto_bls_sign_key_as_bytes, indy_crypto_bls_sign_key_as_bytes_into);
+        _as_bytes_into(ver_key, indy_crypto_bls_ver_key_as_bytes, indy_crypto_bls_ver_key_as_bytes_into);
+        _as_bytes_into(pop, indy_crypto_bls_pop_as_bytes, indy_crypto_bls_pop_as_bytes_into);
+        _as_bytes_into(signature, indy_crypto_bls_signature_as_bytes, indy_crypto_bls_signature_as_bytes_into);
+        _as_bytes_into(multi_sig, indy_crypto_bls_multi_signature_as_bytes, indy_crypto_bls_multi_signature_as_bytes_into);
+        _as_bytes_into(agg_ver_key, indy_crypto_bls_aggregated_ver_key_as_bytes, indy_crypto_bls_aggregated_ver_key_as_bytes_into);
 
-        let err_code = indy_crypto_bls_signature_free(signature1);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_free(agg_ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_pop_free(pop));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    fn _compressed_round_trip(handle: *const c_void,
+                              compressed_len: usize,
+                              as_bytes: extern fn(*const c_void, *mut *const u8, *mut usize) -> ErrorCode,
+                              as_compressed_bytes: extern fn(*const c_void, *mut u8, usize, *mut usize) -> ErrorCode,
+                              from_compressed_bytes: extern fn(*const u8, usize, *mut *const c_void) -> ErrorCode,
+                              free: extern fn(*const c_void) -> ErrorCode) {
+        let mut written: usize = 0;
+        let err_code = as_compressed_bytes(handle, ptr::null_mut(), 0, &mut written);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(compressed_len, written);
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
+        let mut buf = vec![0u8; written];
+        let err_code = as_compressed_bytes(handle, buf.as_mut_ptr(), buf.len(), &mut written);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
+        let mut decompressed: *const c_void = ptr::null();
+        let err_code = from_compressed_bytes(buf.as_ptr(), buf.len(), &mut decompressed);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(_handle_bytes(handle, as_bytes), _handle_bytes(decompressed, as_bytes));
+        assert_eq!(ErrorCode::Success, free(decompressed));
+
+        let err_code = from_compressed_bytes(buf.as_ptr(), buf.len() - 1, &mut decompressed);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        assert!(_current_error_message().contains(&format!("`bytes_len` must be {}", compressed_len)));
+
+        let err_code = as_compressed_bytes(handle, buf.as_mut_ptr(), buf.len() - 1, &mut written);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
     }
 
+    fn _handle_bytes(handle: *const c_void,
+                     as_bytes: extern fn(*const c_void, *mut *const u8, *mut usize) -> ErrorCode) -> Vec<u8> {
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = as_bytes(handle, &mut bytes, &mut bytes_len);
+        assert_eq!(err_code, ErrorCode::Success);
+        unsafe { slice::from_raw_parts(bytes, bytes_len) }.to_vec()
+    }
 
     #[test]
-    fn indy_crypto_bsl_verify_works() {
+    fn indy_crypto_bls_compressed_bytes_works() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut ver_key: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
-
+        let message = [1u8, 2, 3];
         let mut signature: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut valid = false;
+        let signatures = [signature];
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bsl_verify(signature,
-                                              message, message_len,
-                                              ver_key,
-                                              gen, &mut valid);
+        _compressed_round_trip(gen, 65, indy_crypto_bls_generator_as_bytes, indy_crypto_bls_generator_as_compressed_bytes,
+                               indy_crypto_bls_generator_from_compressed_bytes, indy_crypto_bls_generator_free);
+        _compressed_round_trip(ver_key, 65, indy_crypto_bls_ver_key_as_bytes, indy_crypto_bls_ver_key_as_compressed_bytes,
+                               indy_crypto_bls_ver_key_from_compressed_bytes, indy_crypto_bls_ver_key_free);
+        _compressed_round_trip(signature, 33, indy_crypto_bls_signature_as_bytes, indy_crypto_bls_signature_as_compressed_bytes,
+                               indy_crypto_bls_signature_from_compressed_bytes, indy_crypto_bls_signature_free);
+        _compressed_round_trip(multi_sig, 33, indy_crypto_bls_multi_signature_as_bytes, indy_crypto_bls_multi_signature_as_compressed_bytes,
+                               indy_crypto_bls_multi_signature_from_compressed_bytes, indy_crypto_bls_multi_signature_free);
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    #[test]
+    fn indy_crypto_bls_from_compressed_bytes_works_for_native_bytes() {
+        let gen = Generator::new().unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let ver_key = VerKey::new(&gen, &sign_key).unwrap();
+        let message = [1u8, 2, 3];
+        let signature = Bls::sign(&message, &sign_key).unwrap();
+
+        let mut compressed_handles = [ptr::null(); 3];
+        let compressed = [gen.as_compressed_bytes().unwrap(), ver_key.as_compressed_bytes().unwrap(), signature.as_compressed_bytes().unwrap()];
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_from_compressed_bytes(compressed[0].as_ptr(), compressed[0].len(), &mut compressed_handles[0]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_from_compressed_bytes(compressed[1].as_ptr(), compressed[1].len(), &mut compressed_handles[1]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_from_compressed_bytes(compressed[2].as_ptr(), compressed[2].len(), &mut compressed_handles[2]));
+
+        // Decompressed bytes load through the uncompressed path
+        let bytes = [_handle_bytes(compressed_handles[0], indy_crypto_bls_generator_as_bytes),
+                     _handle_bytes(compressed_handles[1], indy_crypto_bls_ver_key_as_bytes),
+                     _handle_bytes(compressed_handles[2], indy_crypto_bls_signature_as_bytes)];
+        assert_eq!(gen.as_bytes(), bytes[0].as_slice());
+        assert_eq!(ver_key.as_bytes(), bytes[1].as_slice());
+        assert_eq!(signature.as_bytes(), bytes[2].as_slice());
+
+        let mut handles = [ptr::null(); 3];
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_from_bytes(bytes[0].as_ptr(), bytes[0].len(), &mut handles[0]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_from_bytes(bytes[1].as_ptr(), bytes[1].len(), &mut handles[1]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_from_bytes(bytes[2].as_ptr(), bytes[2].len(), &mut handles[2]));
+
+        let mut valid = false;
+        let err_code = indy_crypto_bsl_verify(handles[2], message.as_ptr(), message.len(), handles[1], handles[0], &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
         assert!(valid);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(handles[0]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(handles[1]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(handles[2]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(compressed_handles[0]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(compressed_handles[1]));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(compressed_handles[2]));
+    }
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+    fn _hex_round_trip(handle: *const c_void,
+                       bytes_size: usize,
+                       as_bytes: extern fn(*const c_void, *mut *const u8, *mut usize) -> ErrorCode,
+                       handle_to_hex: extern fn(*const c_void, *mut *const c_char) -> ErrorCode,
+                       handle_from_hex: extern fn(*const c_char, *mut *const c_void) -> ErrorCode,
+                       free: extern fn(*const c_void) -> ErrorCode) {
+        let mut hex_p: *const c_char = ptr::null();
+        let err_code = handle_to_hex(handle, &mut hex_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        let hex = unsafe { CStr::from_ptr(hex_p).to_str().unwrap().to_string() };
+        assert_eq!(ErrorCode::Success, indy_crypto_string_free(hex_p));
+        assert_eq!(to_hex(&_handle_bytes(handle, as_bytes)), hex);
+        assert_eq!(2 * bytes_size, hex.len());
+
+        for hex in [hex.clone(), hex.to_uppercase()] {
+            let hex = CString::new(hex).unwrap();
+            let mut decoded: *const c_void = ptr::null();
+            let err_code = handle_from_hex(hex.as_ptr(), &mut decoded);
+            assert_eq!(err_code, ErrorCode::Success);
+            assert_eq!(_handle_bytes(handle, as_bytes), _handle_bytes(decoded, as_bytes));
+            assert_eq!(ErrorCode::Success, free(decoded));
+        }
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let invalid = [(format!("{}0", hex), "odd length"),
+                       (format!("{}0g", &hex[..hex.len() - 2]), "bad digit"),
+                       (hex[..hex.len() - 2].to_string(), "must encode"),
+                       (format!("{}00", hex), "must encode"),
+                       ("".to_string(), "is empty")];
+        for (hex, reason) in invalid.iter() {
+            let hex = CString::new(hex.as_str()).unwrap();
+            let mut decoded: *const c_void = ptr::null();
+            let err_code = handle_from_hex(hex.as_ptr(), &mut decoded);
+            assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+            assert!(_current_error_message().contains(reason), "{}", _current_error_message());
+            assert!(decoded.is_null());
+        }
 
-        let err_code = indy_crypto_bls_signature_free(signature);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut decoded: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam1, handle_from_hex(ptr::null(), &mut decoded));
+        let hex = CString::new(hex).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidParam2, handle_from_hex(hex.as_ptr(), ptr::null_mut()));
+        assert_eq!(ErrorCode::CommonInvalidParam1, handle_to_hex(ptr::null(), &mut hex_p));
+        assert_eq!(ErrorCode::CommonInvalidParam2, handle_to_hex(handle, ptr::null_mut()));
     }
 
     #[test]
-    fn indy_crypto_bsl_verify_works_for_invalid() {
+    fn indy_crypto_bls_hex_works() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
-
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut ver_key: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key);
+        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
-
+        let message = [1u8, 2, 3];
         let mut signature: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut valid = false;
-
-        let err_code = indy_crypto_bsl_verify(signature,
-                                              message, message_len,
-                                              ver_key,
-                                              gen, &mut valid);
+        let signatures = [signature];
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!valid);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        _hex_round_trip(gen, Generator::BYTES_SIZE, indy_crypto_bls_generator_as_bytes, indy_crypto_bls_generator_to_hex,
+                        indy_crypto_bls_generator_from_hex, indy_crypto_bls_generator_free);
+        _hex_round_trip(sign_key, SignKey::BYTES_SIZE, indy_crypto_bls_sign_key_as_bytes, indy_crypto_bls_sign_key_to_hex,
+                        indy_crypto_bls_sign_key_from_hex, indy_crypto_bls_sign_key_free);
+        _hex_round_trip(ver_key, VerKey::BYTES_SIZE, indy_crypto_bls_ver_key_as_bytes, indy_crypto_bls_ver_key_to_hex,
+                        indy_crypto_bls_ver_key_from_hex, indy_crypto_bls_ver_key_free);
+        _hex_round_trip(signature, Signature::BYTES_SIZE, indy_crypto_bls_signature_as_bytes, indy_crypto_bls_signature_to_hex,
+                        indy_crypto_bls_signature_from_hex, indy_crypto_bls_signature_free);
+        _hex_round_trip(multi_sig, MultiSignature::BYTES_SIZE, indy_crypto_bls_multi_signature_as_bytes, indy_crypto_bls_multi_signature_to_hex,
+                        indy_crypto_bls_multi_signature_from_hex, indy_crypto_bls_multi_signature_free);
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+    #[test]
+    fn indy_crypto_bls_ver_key_from_hex_works_for_point_not_on_curve() {
+        let hex = CString::new("01".repeat(VerKey::BYTES_SIZE)).unwrap();
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_from_hex(hex.as_ptr(), &mut ver_key);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(ver_key.is_null());
+    }
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
+    fn _json_from_handle<T: FromJson + Serialize>(handle: *const c_void,
+                                                  as_bytes: extern fn(*const c_void, *mut *const u8, *mut usize) -> ErrorCode) -> CString {
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = as_bytes(handle, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let value = T::from_bytes(unsafe { slice::from_raw_parts(bytes, bytes_len) }).unwrap();
+        CString::new(serde_json::to_string(&value).unwrap()).unwrap()
+    }
 
-        let err_code = indy_crypto_bls_signature_free(signature);
-        assert_eq!(err_code, ErrorCode::Success);
+    fn _bytes_from_json<T: FromJson>(json: *const c_char) -> Vec<u8> {
+        let json = unsafe { CStr::from_ptr(json).to_str().unwrap() };
+        T::from_json(json).unwrap().as_bytes().to_vec()
     }
 
     #[test]
-    fn indy_crypto_bls_pop_new_works() {
+    fn indy_crypto_bls_json_works_for_handles_round_trip() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut ver_key: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!ver_key.is_null());
 
-        let mut pop: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!pop.is_null());
+        let gen_json = _json_from_handle::<Generator>(gen, indy_crypto_bls_generator_as_bytes);
+        let sign_key_json = _json_from_handle::<SignKey>(sign_key, indy_crypto_bls_sign_key_as_bytes);
+        let ver_key_json = _json_from_handle::<VerKey>(ver_key, indy_crypto_bls_ver_key_as_bytes);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let message = [1u8, 2, 3, 4, 5];
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let mut signature_json: *const c_char = ptr::null();
+        let err_code = indy_crypto_bls_sign_json(message.as_ptr(), message.len(), sign_key_json.as_ptr(), &mut signature_json);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_signature_as_bytes(signature, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(unsafe { slice::from_raw_parts(bytes, bytes_len) }, &_bytes_from_json::<Signature>(signature_json)[..]);
 
-        let err_code = indy_crypto_bls_pop_free(pop);
+        let mut valid = false;
+        let err_code = indy_crypto_bls_verify_json(signature_json, message.as_ptr(), message.len(), ver_key_json.as_ptr(), gen_json.as_ptr(), &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
-    }
+        assert!(valid);
 
-    #[test]
-    fn indy_crypto_bls_pop_as_bytes_works() {
-        let mut gen: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
+        let message_invalid = [5u8, 4, 3, 2, 1];
+        let err_code = indy_crypto_bls_verify_json(signature_json, message_invalid.as_ptr(), message_invalid.len(), ver_key_json.as_ptr(), gen_json.as_ptr(), &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
 
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let signatures = [signature];
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut ver_key: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
+        let signature_json_str = unsafe { CStr::from_ptr(signature_json).to_str().unwrap() };
+        let signatures_json = CString::new(format!("[{}]", signature_json_str)).unwrap();
+        let mut multi_sig_json: *const c_char = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_json(signatures_json.as_ptr(), &mut multi_sig_json);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut pop: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        let err_code = indy_crypto_bls_multi_signature_as_bytes(multi_sig, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(unsafe { slice::from_raw_parts(bytes, bytes_len) }, &_bytes_from_json::<MultiSignature>(multi_sig_json)[..]);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_pop_as_bytes(pop, &mut bytes, &mut bytes_len);
+        let ver_keys_json = CString::new(format!("[{}]", ver_key_json.to_str().unwrap())).unwrap();
+        let err_code = indy_crypto_bls_verify_multi_sig_json(multi_sig_json, message.as_ptr(), message.len(), ver_keys_json.as_ptr(), gen_json.as_ptr(), &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
+        assert!(valid);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let ver_keys = [ver_key];
+        let mut valid_handles = false;
+        let err_code = indy_crypto_bls_verify_multi_sig(multi_sig, message.as_ptr(), message.len(), ver_keys.as_ptr(), ver_keys.len(), gen, &mut valid_handles);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(valid_handles, valid);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        assert_eq!(ErrorCode::Success, indy_crypto_string_free(multi_sig_json));
+        assert_eq!(ErrorCode::Success, indy_crypto_string_free(signature_json));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    #[test]
+    fn indy_crypto_bls_json_works_for_invalid_params() {
+        let gen_json = CString::new(serde_json::to_string(&Generator::new().unwrap()).unwrap()).unwrap();
+        let sign_key = SignKey::new(None).unwrap();
+        let sign_key_json = CString::new(serde_json::to_string(&sign_key).unwrap()).unwrap();
+        let ver_key_json = CString::new(serde_json::to_string(&VerKey::new(&Generator::new().unwrap(), &sign_key).unwrap()).unwrap()).unwrap();
+        let message = [1u8, 2, 3];
+
+        let mut signature_json: *const c_char = ptr::null();
+        let err_code = indy_crypto_bls_sign_json(message.as_ptr(), message.len(), ptr::null(), &mut signature_json);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+
+        let invalid_utf8 = [0xC3u8, 0x28, 0x00];
+        let err_code = indy_crypto_bls_sign_json(message.as_ptr(), message.len(), invalid_utf8.as_ptr() as *const c_char, &mut signature_json);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+
+        let invalid_json = CString::new("{").unwrap();
+        let err_code = indy_crypto_bls_sign_json(message.as_ptr(), message.len(), invalid_json.as_ptr(), &mut signature_json);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+        assert!(_current_error_message().contains("`sign_key_json` is invalid"));
+
+        let err_code = indy_crypto_bls_sign_json(message.as_ptr(), message.len(), sign_key_json.as_ptr(), ptr::null_mut());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam4);
+
+        let err_code = indy_crypto_bls_sign_json(message.as_ptr(), message.len(), sign_key_json.as_ptr(), &mut signature_json);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let mut valid = false;
+        let err_code = indy_crypto_bls_verify_json(signature_json, message.as_ptr(), message.len(), sign_key_json.as_ptr(), gen_json.as_ptr(), &mut valid);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam4);
+
+        let err_code = indy_crypto_bls_verify_json(signature_json, message.as_ptr(), message.len(), ver_key_json.as_ptr(), invalid_json.as_ptr(), &mut valid);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam5);
+
+        let err_code = indy_crypto_bls_verify_json(ver_key_json.as_ptr(), message.as_ptr(), message.len(), ver_key_json.as_ptr(), gen_json.as_ptr(), &mut valid);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let empty_array = CString::new("[]").unwrap();
+        let mut multi_sig_json: *const c_char = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_json(empty_array.as_ptr(), &mut multi_sig_json);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let signatures_json = CString::new(format!("[{}, {{}}]", unsafe { CStr::from_ptr(signature_json).to_str().unwrap() })).unwrap();
+        let err_code = indy_crypto_bls_multi_signature_json(signatures_json.as_ptr(), &mut multi_sig_json);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_current_error_message().contains("`signatures_json` item 1 is invalid"));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_string_free(signature_json));
+    }
+
+    // Both pointers of an empty message: null and valid one with zero len
+    fn _empty_messages(message: &[u8]) -> [(*const u8, usize); 2] {
+        [(ptr::null(), 0), (message.as_ptr(), 0)]
+    }
+
+    fn _sign_handle(message: *const u8, message_len: usize, sign_key: *const c_void) -> *const c_void {
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message, message_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
+        signature
+    }
 
-        let err_code = indy_crypto_bls_pop_free(pop);
+    #[test]
+    fn indy_crypto_bls_sign_works_for_empty_message() {
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
+
+        let message = [1u8, 2, 3];
+        let signature = _sign_handle(message.as_ptr(), message.len(), sign_key);
+        let expected = Bls::sign(&[], &handles::get::<SignKey>(sign_key).unwrap()).unwrap();
+        let sign_key_json = _json_from_handle::<SignKey>(sign_key, indy_crypto_bls_sign_key_as_bytes);
+
+        for (message, message_len) in _empty_messages(&message).iter() {
+            let empty_signature = _sign_handle(*message, *message_len, sign_key);
+            assert_eq!(expected.as_bytes(), _handle_bytes(empty_signature, indy_crypto_bls_signature_as_bytes).as_slice());
+            assert_ne!(_handle_bytes(signature, indy_crypto_bls_signature_as_bytes), _handle_bytes(empty_signature, indy_crypto_bls_signature_as_bytes));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(empty_signature));
+
+            let mut signature_json: *const c_char = ptr::null();
+            let err_code = indy_crypto_bls_sign_json(*message, *message_len, sign_key_json.as_ptr(), &mut signature_json);
+            assert_eq!(err_code, ErrorCode::Success);
+            assert_eq!(expected.as_bytes(), _bytes_from_json::<Signature>(signature_json).as_slice());
+            assert_eq!(ErrorCode::Success, indy_crypto_string_free(signature_json));
+        }
+
+        let mut empty_signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(ptr::null(), message.len(), sign_key, &mut empty_signature);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_current_error_message().contains("`message` is null"));
+
+        let mut signature_json: *const c_char = ptr::null();
+        let err_code = indy_crypto_bls_sign_json(ptr::null(), message.len(), sign_key_json.as_ptr(), &mut signature_json);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let (share_sign_key, shares) = _generate_shares(3, 2);
+        for (message, message_len) in _empty_messages(&message).iter() {
+            let mut signature_share: *const c_void = ptr::null();
+            let err_code = indy_crypto_bls_sign_share(*message, *message_len, shares[0], &mut signature_share);
+            assert_eq!(err_code, ErrorCode::Success);
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_share_free(signature_share));
+        }
+        let mut signature_share: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_share(ptr::null(), message.len(), shares[0], &mut signature_share);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        for share in shares {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_share_free(share));
+        }
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(share_sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
     }
 
     #[test]
-    fn indy_crypto_bls_pop_from_bytes_works() {
+    fn indy_crypto_bls_verify_works_for_empty_message() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
         let mut ver_key: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut pop: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        let ver_keys = [ver_key];
+        let mut agg_ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_aggregated_ver_key_new(ver_keys.as_ptr(), ver_keys.len(), &mut agg_ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut bytes: *const u8 = ptr::null();
-        let mut bytes_len: usize = 0;
-        let err_code = indy_crypto_bls_pop_as_bytes(pop, &mut bytes, &mut bytes_len);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!bytes.is_null());
-        assert!(bytes_len > 0);
+        let message = [1u8, 2, 3];
+        let signature = _sign_handle(message.as_ptr(), message.len(), sign_key);
+        let empty_signature = _sign_handle(ptr::null(), 0, sign_key);
 
-        let mut pop2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_pop_from_bytes(bytes, bytes_len, &mut pop2);
+        let mut empty_multi_sig: *const c_void = ptr::null();
+        let signatures = [empty_signature];
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut empty_multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        let empty_signature_json = _json_from_handle::<Signature>(empty_signature, indy_crypto_bls_signature_as_bytes);
+        let empty_multi_sig_json = _json_from_handle::<MultiSignature>(empty_multi_sig, indy_crypto_bls_multi_signature_as_bytes);
+        let ver_key_json = _json_from_handle::<VerKey>(ver_key, indy_crypto_bls_ver_key_as_bytes);
+        let ver_keys_json = CString::new(format!("[{}]", ver_key_json.to_str().unwrap())).unwrap();
+        let gen_json = _json_from_handle::<Generator>(gen, indy_crypto_bls_generator_as_bytes);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let verify_all = |message: *const u8, message_len: usize| -> Vec<(ErrorCode, bool)> {
+            let mut results = Vec::new();
+            let mut valid = false;
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+            results.push((indy_crypto_bsl_verify(empty_signature, message, message_len, ver_key, gen, &mut valid), valid));
+            results.push((indy_crypto_bls_verify_multi_sig(empty_multi_sig, message, message_len, ver_keys.as_ptr(), ver_keys.len(), gen, &mut valid), valid));
+            results.push((indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key(empty_multi_sig, message, message_len, agg_ver_key, gen, &mut valid), valid));
+            results.push((indy_crypto_bls_verify_json(empty_signature_json.as_ptr(), message, message_len, ver_key_json.as_ptr(), gen_json.as_ptr(), &mut valid), valid));
+            results.push((indy_crypto_bls_verify_multi_sig_json(empty_multi_sig_json.as_ptr(), message, message_len, ver_keys_json.as_ptr(), gen_json.as_ptr(), &mut valid), valid));
+            results
+        };
+
+        let verify_batch = |message: *const u8, message_len: usize| -> (ErrorCode, bool) {
+            let items = [BatchItem { message, message_len, signature: empty_signature, ver_key }];
+            let mut valid = false;
+            (indy_crypto_bls_verify_batch(items.as_ptr(), items.len(), gen, &mut valid), valid)
+        };
+
+        for (message, message_len) in _empty_messages(&message).iter() {
+            for (err_code, valid) in verify_all(*message, *message_len) {
+                assert_eq!(err_code, ErrorCode::Success);
+                assert!(valid);
+            }
+            assert_eq!((ErrorCode::Success, true), verify_batch(*message, *message_len));
+        }
+
+        for (err_code, valid) in verify_all(message.as_ptr(), message.len()) {
+            assert_eq!(err_code, ErrorCode::Success);
+            assert!(!valid);
+        }
+        assert_eq!((ErrorCode::Success, false), verify_batch(message.as_ptr(), message.len()));
+
+        for (err_code, _) in verify_all(ptr::null(), message.len()) {
+            assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        }
+        assert!(_current_error_message().contains("`message` is null"));
+        assert_eq!(ErrorCode::CommonInvalidParam1, verify_batch(ptr::null(), message.len()).0);
+        assert!(_current_error_message().contains("Item 0 has invalid `message`"));
+
+        let mut valid = true;
+        let err_code = indy_crypto_bsl_verify(signature, ptr::null(), 0, ver_key, gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
 
-        let err_code = indy_crypto_bls_pop_free(pop);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(empty_multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(empty_signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_free(agg_ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    fn _generate_shares(n: u32, threshold: u32) -> (*const c_void, Vec<*const c_void>) {
+        let mut sign_key: *const c_void = ptr::null();
+        let mut shares: Vec<*const c_void> = vec![ptr::null(); n as usize];
+        let err_code = indy_crypto_bls_generate_shares(n, threshold, ptr::null(), 0, &mut sign_key, shares.as_mut_ptr());
         assert_eq!(err_code, ErrorCode::Success);
+        (sign_key, shares)
+    }
 
-        let err_code = indy_crypto_bls_pop_free(pop2);
+    fn _sign_share(message: &[u8], share: *const c_void) -> *const c_void {
+        let mut signature_share: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_share(message.as_ptr(), message.len(), share, &mut signature_share);
         assert_eq!(err_code, ErrorCode::Success);
+        signature_share
     }
 
     #[test]
-    fn indy_crypto_bls_pop_free_works() {
+    fn indy_crypto_bls_threshold_works() {
         let mut gen: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_generator_new(&mut gen);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let (sign_key, shares) = _generate_shares(3, 2);
 
         let mut ver_key: *const c_void = ptr::null();
         let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut pop: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
+        // Share is sent to the device as bytes
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_sign_key_share_as_bytes(shares[2], &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let mut device_share: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_share_from_bytes(bytes, bytes_len, &mut device_share);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+        let message = vec![1, 2, 3, 4, 5];
+        let signature_share1 = _sign_share(&message, shares[0]);
+        let signature_share3 = _sign_share(&message, device_share);
+
+        // Signature share is sent back as bytes
+        let err_code = indy_crypto_bls_signature_share_as_bytes(signature_share3, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let mut received_share3: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_signature_share_from_bytes(bytes, bytes_len, &mut received_share3);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_pop_free(pop);
+        let signature_shares = [received_share3, signature_share1];
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_combine_signature_shares(signature_shares.as_ptr(), signature_shares.len(), &mut signature);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let mut valid = false;
+        let err_code = indy_crypto_bsl_verify(signature, message.as_ptr(), message.len(), ver_key, gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(valid);
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        for signature_share in [signature_share1, signature_share3, received_share3] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_share_free(signature_share));
+        }
+        for share in shares.iter().chain([device_share].iter()) {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_share_free(*share));
+        }
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
     }
 
     #[test]
-    fn indy_crypto_bsl_verify_pop_works() {
-        let mut gen: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
+    fn indy_crypto_bls_generate_shares_works_for_seed() {
+        let seed = [1u8, 2, 3];
+        let mut sign_key: *const c_void = ptr::null();
+        let mut shares: Vec<*const c_void> = vec![ptr::null(); 2];
+        let err_code = indy_crypto_bls_generate_shares(2, 2, seed.as_ptr(), seed.len(), &mut sign_key, shares.as_mut_ptr());
         assert_eq!(err_code, ErrorCode::Success);
 
+        let (seeded_sign_key, _) = Threshold::generate_shares(2, 2, Some(&seed)).unwrap();
+        assert_eq!(seeded_sign_key.as_bytes(), handles::get::<SignKey>(sign_key).unwrap().as_bytes());
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        for share in shares {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_share_free(share));
+        }
+    }
+
+    #[test]
+    fn indy_crypto_bls_combine_signature_shares_works_for_not_enough_shares() {
+        let (sign_key, shares) = _generate_shares(3, 2);
+        let message = [1u8, 2, 3];
+        let signature_share = _sign_share(&message, shares[0]);
+
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_combine_signature_shares([signature_share].as_ptr(), 1, &mut signature);
+        assert_eq!(err_code, ErrorCode::CryptoNotEnoughShares);
+        assert!(_current_error_message().contains("Expected at least 2 signature shares, actual 1"));
+
+        let duplicate_shares = [signature_share, signature_share];
+        let err_code = indy_crypto_bls_combine_signature_shares(duplicate_shares.as_ptr(), duplicate_shares.len(), &mut signature);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let (other_sign_key, other_shares) = _generate_shares(3, 3);
+        let other_signature_share = _sign_share(&message, other_shares[1]);
+        let mixed_shares = [signature_share, other_signature_share];
+        let err_code = indy_crypto_bls_combine_signature_shares(mixed_shares.as_ptr(), mixed_shares.len(), &mut signature);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        for handle in [signature_share, other_signature_share] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_share_free(handle));
+        }
+        for share in shares.into_iter().chain(other_shares) {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_share_free(share));
+        }
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(other_sign_key));
+    }
+
+    #[test]
+    fn indy_crypto_bls_threshold_works_for_invalid_params() {
         let mut sign_key: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut shares: Vec<*const c_void> = vec![ptr::null(); 3];
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_generate_shares(0, 0, ptr::null(), 0, &mut sign_key, shares.as_mut_ptr()));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_generate_shares(3, 0, ptr::null(), 0, &mut sign_key, shares.as_mut_ptr()));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_generate_shares(3, 4, ptr::null(), 0, &mut sign_key, shares.as_mut_ptr()));
+        assert_eq!(ErrorCode::CommonInvalidParam4, indy_crypto_bls_generate_shares(3, 2, [1u8].as_ptr(), 0, &mut sign_key, shares.as_mut_ptr()));
+        assert_eq!(ErrorCode::CommonInvalidParam5, indy_crypto_bls_generate_shares(3, 2, ptr::null(), 0, ptr::null_mut(), shares.as_mut_ptr()));
+        assert_eq!(ErrorCode::CommonInvalidParam6, indy_crypto_bls_generate_shares(3, 2, ptr::null(), 0, &mut sign_key, ptr::null_mut()));
+
+        let (sign_key, shares) = _generate_shares(3, 2);
+        let message = [1u8, 2, 3];
+        let mut signature_share: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_sign_share(message.as_ptr(), message.len(), sign_key, &mut signature_share));
+        assert!(_current_error_message().contains("expected indy_crypto::bls::threshold::SignKeyShare"));
+        assert_eq!(ErrorCode::CommonInvalidParam4, indy_crypto_bls_sign_share(message.as_ptr(), message.len(), shares[0], ptr::null_mut()));
+
+        let mut share: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_sign_key_share_from_bytes(message.as_ptr(), message.len(), ptr::null_mut()));
+        assert_eq!(ErrorCode::CommonInvalidStructure, indy_crypto_bls_sign_key_share_from_bytes(message.as_ptr(), message.len(), &mut share));
+        assert_eq!(ErrorCode::CommonInvalidStructure, indy_crypto_bls_signature_share_from_bytes(message.as_ptr(), message.len(), &mut signature_share));
 
-        let mut ver_key: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut signature: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_combine_signature_shares(ptr::null(), 1, &mut signature));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_combine_signature_shares(shares.as_ptr(), 0, &mut signature));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_combine_signature_shares(shares.as_ptr(), shares.len(), &mut signature));
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_sign_key_share_free(ptr::null()));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_signature_share_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        for share in shares {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_share_free(share));
+        }
+    }
 
-        let mut pop: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_pop_new(ver_key, sign_key, &mut pop);
-        assert_eq!(err_code, ErrorCode::Success);
+    fn _error_detail(error_detail: *const c_char) -> serde_json::Value {
+        assert!(!error_detail.is_null());
+        let value = serde_json::from_str(unsafe { CStr::from_ptr(error_detail).to_str().unwrap() }).unwrap();
+        assert_eq!(ErrorCode::Success, indy_crypto_string_free(error_detail));
+        value
+    }
+
+    #[test]
+    fn indy_crypto_bls_verify_ext_works() {
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature));
 
         let mut valid = false;
+        let mut error_detail: *const c_char = ptr::null();
 
-        let err_code = indy_crypto_bsl_verify_pop(pop,
-                                                  ver_key,
-                                                  gen,
-                                                  &mut valid);
+        let err_code = indy_crypto_bls_verify_ext(signature, message.as_ptr(), message.len(), ver_key, gen, &mut valid, &mut error_detail);
         assert_eq!(err_code, ErrorCode::Success);
         assert!(valid);
+        assert!(error_detail.is_null());
 
-        let err_code = indy_crypto_bls_generator_free(gen);
+        let other_message = [3u8, 2, 1];
+        let err_code = indy_crypto_bls_verify_ext(signature, other_message.as_ptr(), other_message.len(), ver_key, gen, &mut valid, &mut error_detail);
         assert_eq!(err_code, ErrorCode::Success);
+        assert!(!valid);
+        assert!(error_detail.is_null());
+
+        let err_code = indy_crypto_bls_verify_ext(ptr::null(), message.as_ptr(), message.len(), ver_key, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        let detail = _error_detail(error_detail);
+        assert_eq!(ErrorCode::CommonInvalidParam1 as u64, detail["code"].as_u64().unwrap());
+        assert_eq!("CommonInvalidParam1", detail["name"]);
+        assert_eq!(1, detail["param"]);
+        assert_eq!("Invalid param 1: `signature` is null", detail["message"]);
+
+        let err_code = indy_crypto_bls_verify_ext(signature, ptr::null(), message.len(), ver_key, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        assert_eq!(2, _error_detail(error_detail)["param"]);
+
+        let err_code = indy_crypto_bls_verify_ext(signature, message.as_ptr(), message.len(), gen, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam4);
+        let detail = _error_detail(error_detail);
+        assert_eq!(4, detail["param"]);
+        assert!(detail["message"].as_str().unwrap().contains("Generator, expected"));
+
+        let err_code = indy_crypto_bls_verify_ext(signature, message.as_ptr(), message.len(), ver_key, gen, &mut valid, ptr::null_mut());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam7);
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        let err_code = indy_crypto_bls_verify_ext(signature, message.as_ptr(), message.len(), ver_key, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_error_detail(error_detail)["message"].as_str().unwrap().ends_with("is already freed"));
+
+        let ver_keys: [*const c_void; 0] = [];
+        let mut multi_sig: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_new_empty(&mut multi_sig));
+        let err_code = indy_crypto_bls_verify_multi_sig_ext(multi_sig, message.as_ptr(), message.len(), ver_keys.as_ptr(), 0, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam5);
+        assert_eq!("Invalid param 5: `ver_keys_len` is 0", _error_detail(error_detail)["message"]);
+
+        let items: [BatchItem; 0] = [];
+        let err_code = indy_crypto_bls_verify_batch_ext(items.as_ptr(), 0, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        assert_eq!(2, _error_detail(error_detail)["param"]);
+
+        let err_code = indy_crypto_bls_verify_pop_ext(ver_key, ver_key, gen, &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert_eq!(1, _error_detail(error_detail)["param"]);
+
+        let signature_json = CString::new("{}").unwrap();
+        let json = CString::new("\"\"").unwrap();
+        let err_code = indy_crypto_bls_verify_json_ext(signature_json.as_ptr(), message.as_ptr(), message.len(), json.as_ptr(), json.as_ptr(), &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        let detail = _error_detail(error_detail);
+        assert_eq!(1, detail["param"]);
+        assert!(detail["message"].as_str().unwrap().contains("`signature_json` is invalid"));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key);
+    #[test]
+    fn indy_crypto_bls_verify_ext_works_for_multi_signatures() {
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
+        let mut agg_ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_new([ver_key].as_ptr(), 1, &mut agg_ver_key));
+
+        let message = [1u8, 2, 3];
+        let mut valid = false;
+        let mut error_detail: *const c_char = ptr::null();
+        let err_code = indy_crypto_bls_verify_multi_sig_with_aggregated_ver_key_ext(agg_ver_key, message.as_ptr(), message.len(), agg_ver_key, gen,
+                                                                                    &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert_eq!(1, _error_detail(error_detail)["param"]);
+
+        let multi_sig_json = CString::new("[]").unwrap();
+        let json = CString::new("\"\"").unwrap();
+        let err_code = indy_crypto_bls_verify_multi_sig_json_ext(multi_sig_json.as_ptr(), message.as_ptr(), message.len(), json.as_ptr(), json.as_ptr(),
+                                                                 &mut valid, &mut error_detail);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        assert!(_error_detail(error_detail)["message"].as_str().unwrap().contains("`multi_sig_json` is invalid"));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_aggregated_ver_key_free(agg_ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
+
+    fn _sign(message: &[u8]) -> *const c_void {
+        let mut sign_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key);
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let err_code = indy_crypto_bls_pop_free(pop);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        signature
+    }
+
+    fn _multi_signature_bytes(multi_sig: *const c_void) -> Vec<u8> {
+        let mut bytes: *const u8 = ptr::null();
+        let mut bytes_len: usize = 0;
+        let err_code = indy_crypto_bls_multi_signature_as_bytes(multi_sig, &mut bytes, &mut bytes_len);
         assert_eq!(err_code, ErrorCode::Success);
+        unsafe { slice::from_raw_parts(bytes, bytes_len) }.to_vec()
     }
 
     #[test]
-    fn indy_crypto_bls_verify_multi_sig_works() {
-        let mut gen: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
-        assert_eq!(err_code, ErrorCode::Success);
+    fn indy_crypto_bls_multi_signature_add_works() {
+        let message = [1u8, 2, 3];
+        let signatures: Vec<*const c_void> = (0..3).map(|_| _sign(&message)).collect();
 
-        let mut sign_key1: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
+        let mut batch: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut batch);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
+        let mut incremental: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new_empty(&mut incremental);
         assert_eq!(err_code, ErrorCode::Success);
+        for signature in signatures.iter().rev() {
+            let err_code = indy_crypto_bls_multi_signature_add(incremental, *signature);
+            assert_eq!(err_code, ErrorCode::Success);
+        }
+        assert_eq!(_multi_signature_bytes(batch), _multi_signature_bytes(incremental));
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+        let mut a: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), 1, &mut a);
+        assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        let mut b: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures[1..].as_ptr(), 2, &mut b);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
+        let mut merged: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_merge(a, b, &mut merged);
         assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(_multi_signature_bytes(batch), _multi_signature_bytes(merged));
+        assert_ne!(_multi_signature_bytes(a), _multi_signature_bytes(merged));
 
-        let signatures = [signature1, signature2];
+        for multi_sig in [batch, incremental, a, b, merged] {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        }
+        for signature in signatures {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        }
+    }
+
+    #[test]
+    fn indy_crypto_bls_multi_signature_add_works_for_invalid_params() {
+        let signature = _sign(&[1, 2, 3]);
 
         let mut multi_sig: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_new_empty(ptr::null_mut()));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_new_empty(&mut multi_sig));
+        let empty_bytes = _multi_signature_bytes(multi_sig);
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_add(ptr::null(), signature));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_add(signature, signature));
+        assert!(_current_error_message().contains("expected indy_crypto::bls::MultiSignature"));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_multi_signature_add(multi_sig, ptr::null()));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_multi_signature_add(multi_sig, multi_sig));
+        assert_eq!(empty_bytes, _multi_signature_bytes(multi_sig));
+
+        let mut merged: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_merge(signature, multi_sig, &mut merged));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_multi_signature_merge(multi_sig, ptr::null(), &mut merged));
+        assert_eq!(ErrorCode::CommonInvalidParam3, indy_crypto_bls_multi_signature_merge(multi_sig, multi_sig, ptr::null_mut()));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_add(multi_sig, signature));
+        assert!(_current_error_message().ends_with("is already freed"));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+    }
 
-        let mut ver_key1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
+    fn _sign_with_dst(message: &[u8], dst: *const u8, dst_len: usize, sign_key: *const c_void) -> *const c_void {
+        let mut signature: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_sign_with_dst(message.as_ptr(), message.len(), dst, dst_len, sign_key, &mut signature);
         assert_eq!(err_code, ErrorCode::Success);
+        signature
+    }
 
-        let mut ver_key2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key2, &mut ver_key2);
+    fn _verify_with_dst(signature: *const c_void, message: &[u8], dst: &[u8], ver_key: *const c_void, gen: *const c_void) -> bool {
+        let mut valid = false;
+        let err_code = indy_crypto_bls_verify_with_dst(signature, message.as_ptr(), message.len(), dst.as_ptr(), dst.len(), ver_key, gen, &mut valid);
         assert_eq!(err_code, ErrorCode::Success);
+        valid
+    }
 
-        let ver_keys = [ver_key1, ver_key2];
-        let mut valid = false;
+    #[test]
+    fn indy_crypto_bls_verify_with_dst_works() {
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let err_code = indy_crypto_bls_verify_multi_sig(multi_sig,
-                                                        message, message_len,
-                                                        ver_keys.as_ptr(), ver_keys.len(),
-                                                        gen,
-                                                        &mut valid);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(valid);
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let message = [1u8, 2, 3, 4, 5];
+        let dst = b"INDY-BLS-SIG";
+        let signature = _sign_with_dst(&message, dst.as_ptr(), dst.len(), sign_key);
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert!(_verify_with_dst(signature, &message, dst, ver_key, gen));
+        assert!(!_verify_with_dst(signature, &message, b"INDY-BLS-POP", ver_key, gen));
+        assert!(!_verify_with_dst(signature, &message, &[], ver_key, gen));
+        assert!(!_verify_with_dst(signature, &[1, 2, 3], dst, ver_key, gen));
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+    #[test]
+    fn indy_crypto_bls_sign_with_dst_works_for_empty_dst() {
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let err_code = indy_crypto_bls_signature_free(signature1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
-        assert_eq!(err_code, ErrorCode::Success);
+        let message = [1u8, 2, 3, 4, 5];
+        let legacy = _sign_handle(message.as_ptr(), message.len(), sign_key);
+        let legacy_bytes = _handle_bytes(legacy, indy_crypto_bls_signature_as_bytes);
+
+        for (dst, dst_len) in _empty_messages(b"INDY-BLS-SIG") {
+            let signature = _sign_with_dst(&message, dst, dst_len, sign_key);
+            assert_eq!(legacy_bytes, _handle_bytes(signature, indy_crypto_bls_signature_as_bytes));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+
+            let mut valid = false;
+            let err_code = indy_crypto_bls_verify_with_dst(legacy, message.as_ptr(), message.len(), dst, dst_len, ver_key, gen, &mut valid);
+            assert_eq!(err_code, ErrorCode::Success);
+            assert!(valid);
+        }
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(legacy));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
     }
 
     #[test]
-    fn indy_crypto_bls_verify_multi_sig_works_for_invalid() {
+    fn indy_crypto_bls_verify_multi_sig_with_dst_works() {
         let mut gen: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_generator_new(&mut gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let mut sign_key1: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let message = [1u8, 2, 3, 4, 5];
+        let dst = b"INDY-BLS-SIG";
 
-        let mut sign_key2: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut signatures = Vec::new();
+        let mut ver_keys = Vec::new();
+        for _ in 0..2 {
+            let mut sign_key: *const c_void = ptr::null();
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let mut sign_key3: *const c_void = ptr::null();
-        let seed: *const u8 = ptr::null();
-        let seed_len: usize = 0;
-        let err_code = indy_crypto_bls_sign_key_new(seed, seed_len, &mut sign_key3);
-        assert_eq!(err_code, ErrorCode::Success);
+            let mut ver_key: *const c_void = ptr::null();
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
 
-        let message_v = vec![1, 2, 3, 4, 5];
-        let message = message_v.as_ptr();
-        let message_len = message_v.len();
+            signatures.push(_sign_with_dst(&message, dst.as_ptr(), dst.len(), sign_key));
+            ver_keys.push(ver_key);
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        }
 
-        let mut signature1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key1, &mut signature1);
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
         assert_eq!(err_code, ErrorCode::Success);
 
-        let mut signature2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_sign(message, message_len, sign_key2, &mut signature2);
-        assert_eq!(err_code, ErrorCode::Success);
+        for (tag, expected) in [(&dst[..], true), (&b"INDY-BLS-POP"[..], false), (&[][..], false)] {
+            let mut valid = !expected;
+            let err_code = indy_crypto_bls_verify_multi_sig_with_dst(multi_sig,
+                                                                     message.as_ptr(), message.len(),
+                                                                     tag.as_ptr(), tag.len(),
+                                                                     ver_keys.as_ptr(), ver_keys.len(),
+                                                                     gen,
+                                                                     &mut valid);
+            assert_eq!(err_code, ErrorCode::Success);
+            assert_eq!(expected, valid);
+        }
 
-        let signatures = [signature1, signature2];
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        for (signature, ver_key) in signatures.into_iter().zip(ver_keys) {
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        }
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
 
-        let mut multi_sig: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
-        assert_eq!(err_code, ErrorCode::Success);
+    #[test]
+    fn indy_crypto_bls_with_dst_works_for_invalid_params() {
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let mut ver_key1: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key1, &mut ver_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let mut ver_key2: *const c_void = ptr::null();
-        let err_code = indy_crypto_bls_ver_key_new(gen, sign_key3, &mut ver_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
+
+        let message = [1u8, 2, 3];
+        let long_dst = [0u8; 256];
+        let signature = _sign_handle(message.as_ptr(), message.len(), sign_key);
+        let mut multi_sig: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_new(&signature, 1, &mut multi_sig));
+        let ver_keys = [ver_key];
+
+        let mut out: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::CommonInvalidParam3,
+                   indy_crypto_bls_sign_with_dst(message.as_ptr(), message.len(), ptr::null(), 1, sign_key, &mut out));
+        assert_eq!(ErrorCode::CommonInvalidStructure,
+                   indy_crypto_bls_sign_with_dst(message.as_ptr(), message.len(), long_dst.as_ptr(), long_dst.len(), sign_key, &mut out));
+        assert_eq!(ErrorCode::CommonInvalidParam5,
+                   indy_crypto_bls_sign_with_dst(message.as_ptr(), message.len(), ptr::null(), 0, ptr::null(), &mut out));
 
-        let ver_keys = [ver_key1, ver_key2];
         let mut valid = false;
+        assert_eq!(ErrorCode::CommonInvalidParam4,
+                   indy_crypto_bls_verify_with_dst(signature, message.as_ptr(), message.len(), ptr::null(), 1, ver_key, gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidStructure,
+                   indy_crypto_bls_verify_with_dst(signature, message.as_ptr(), message.len(), long_dst.as_ptr(), long_dst.len(), ver_key, gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam8,
+                   indy_crypto_bls_verify_with_dst(signature, message.as_ptr(), message.len(), ptr::null(), 0, ver_key, gen, ptr::null_mut()));
+
+        assert_eq!(ErrorCode::CommonInvalidParam4,
+                   indy_crypto_bls_verify_multi_sig_with_dst(multi_sig, message.as_ptr(), message.len(), ptr::null(), 1,
+                                                             ver_keys.as_ptr(), ver_keys.len(), gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidStructure,
+                   indy_crypto_bls_verify_multi_sig_with_dst(multi_sig, message.as_ptr(), message.len(), long_dst.as_ptr(), long_dst.len(),
+                                                             ver_keys.as_ptr(), ver_keys.len(), gen, &mut valid));
+        assert_eq!(ErrorCode::CommonInvalidParam6,
+                   indy_crypto_bls_verify_multi_sig_with_dst(multi_sig, message.as_ptr(), message.len(), ptr::null(), 0,
+                                                             ptr::null(), 1, gen, &mut valid));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
 
-        let err_code = indy_crypto_bls_verify_multi_sig(multi_sig,
-                                                        message, message_len,
-                                                        ver_keys.as_ptr(), ver_keys.len(),
-                                                        gen,
-                                                        &mut valid);
-        assert_eq!(err_code, ErrorCode::Success);
-        assert!(!valid);
+    #[test]
+    fn indy_crypto_bls_constant_works() {
+        let expected = [
+            ("generator_size", 128),
+            ("generator_compressed_size", 65),
+            ("sign_key_size", 32),
+            ("seed_size", 32),
+            ("ver_key_size", 128),
+            ("ver_key_compressed_size", 65),
+            ("signature_size", 128),
+            ("signature_compressed_size", 33),
+            ("multi_signature_size", 128),
+            ("multi_signature_compressed_size", 33),
+            ("point_g1_size", 128),
+            ("point_g1_compressed_size", 33),
+            ("point_g2_size", 128),
+            ("point_g2_compressed_size", 65),
+            ("group_order_element_size", 32),
+            ("pair_size", 512),
+        ];
+        assert_eq!(expected.len(), CONSTANTS.len());
+
+        for (name, size) in expected.iter() {
+            let c_name = CString::new(*name).unwrap();
+            let mut value: usize = 0;
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_constant(c_name.as_ptr(), &mut value), "{}", name);
+            assert_eq!(*size, value, "{}", name);
+        }
 
-        let err_code = indy_crypto_bls_generator_free(gen);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut gen: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_new(&mut gen));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        let mut ver_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_new(gen, sign_key, &mut ver_key));
+
+        let signature = _sign_handle([1u8, 2, 3].as_ptr(), 3, sign_key);
+        for (name, handle, as_bytes) in [
+            ("generator_size", gen, indy_crypto_bls_generator_as_bytes as extern fn(*const c_void, *mut *const u8, *mut usize) -> ErrorCode),
+            ("sign_key_size", sign_key, indy_crypto_bls_sign_key_as_bytes),
+            ("ver_key_size", ver_key, indy_crypto_bls_ver_key_as_bytes),
+            ("signature_size", signature, indy_crypto_bls_signature_as_bytes),
+        ] {
+            let c_name = CString::new(name).unwrap();
+            let mut value: usize = 0;
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_constant(c_name.as_ptr(), &mut value));
+            assert_eq!(_handle_bytes(handle, as_bytes).len(), value, "{}", name);
+        }
 
-        let err_code = indy_crypto_bls_sign_key_free(sign_key3);
-        assert_eq!(err_code, ErrorCode::Success);
+        let seed_name = CString::new("seed_size").unwrap();
+        let mut seed_size: usize = 0;
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_constant(seed_name.as_ptr(), &mut seed_size));
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key1);
-        assert_eq!(err_code, ErrorCode::Success);
+        let seed = vec![7u8; seed_size + 1];
+        let mut seeded: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(seed.as_ptr(), seed_size, &mut seeded));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(seeded));
+        assert_ne!(ErrorCode::Success, indy_crypto_bls_sign_key_new(seed.as_ptr(), seed_size + 1, &mut seeded));
 
-        let err_code = indy_crypto_bls_ver_key_free(ver_key2);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_ver_key_free(ver_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_generator_free(gen));
+    }
 
-        let err_code = indy_crypto_bls_signature_free(signature1);
-        assert_eq!(err_code, ErrorCode::Success);
+    #[test]
+    fn indy_crypto_bls_constant_works_for_invalid_params() {
+        let mut value: usize = 0;
+        let name = CString::new("signature_size").unwrap();
+        let unknown = CString::new("signature_len").unwrap();
+        let empty = CString::new("").unwrap();
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_constant(ptr::null(), &mut value));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_constant(empty.as_ptr(), &mut value));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_constant(unknown.as_ptr(), &mut value));
+        assert!(_current_error_message().contains("Unknown constant: signature_len"));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_constant(name.as_ptr(), ptr::null_mut()));
+        assert_eq!(0, value);
+    }
 
-        let err_code = indy_crypto_bls_signature_free(signature2);
-        assert_eq!(err_code, ErrorCode::Success);
+    #[test]
+    fn indy_crypto_bls_curve_name_works() {
+        let mut curve_name: *const c_char = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_curve_name(&mut curve_name));
+        assert_eq!(CURVE_NAME, unsafe { CStr::from_ptr(curve_name) }.to_str().unwrap());
+        assert_eq!("BN254", unsafe { CStr::from_ptr(curve_name) }.to_str().unwrap());
 
-        let err_code = indy_crypto_bls_multi_signature_free(multi_sig);
-        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_curve_name(ptr::null_mut()));
+    }
+
+    #[test]
+    fn indy_crypto_bls_multi_signature_new_works_for_invalid_handles() {
+        let signature = _sign(&[1, 2, 3]);
+        let mut multi_sig: *const c_void = ptr::null();
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_new(ptr::null(), 1, &mut multi_sig));
+        assert!(_current_error_message().contains("`signatures` is null"));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_bls_multi_signature_new([signature, ptr::null()].as_ptr(), 2, &mut multi_sig));
+        assert!(_current_error_message().contains("Item 1: Handle is null"));
+        assert_eq!(ErrorCode::CommonInvalidParam2, indy_crypto_bls_multi_signature_new([signature].as_ptr(), usize::MAX, &mut multi_sig));
+        assert!(_current_error_message().contains("`signatures_len` is too large"));
+        assert!(multi_sig.is_null());
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
     }
-}
\ No newline at end of file
+}
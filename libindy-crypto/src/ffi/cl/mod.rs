@@ -1,12 +1,14 @@
 use crate::cl::*;
 use crate::cl::issuer::Issuer;
 use crate::cl::verifier::Verifier;
-use crate::errors::{IndyCryptoError, ToErrorCode};
+use crate::errors::{IndyCryptoError, ResultExt};
 use crate::errors::ErrorCode;
-use crate::ffi::ctypes::CTypesUtils;
+use crate::ffi::ctypes::{self, CTypesUtils};
+use crate::ffi::handles;
 
 use serde_json;
 use std::ptr;
+use std::slice;
 use std::os::raw::c_void;
 use libc::c_char;
 
@@ -17,791 +19,1135 @@ pub mod verifier;
 
 type FFITailTake = extern fn(ctx: *const c_void, idx: u32, tail_p: *mut *const c_void) -> ErrorCode;
 type FFITailPut = extern fn(ctx: *const c_void, tail: *const c_void) -> ErrorCode;
-
-#[no_mangle]
-pub extern fn indy_crypto_cl_tails_generator_next(rev_tails_generator: *const c_void,
-                                                  tail_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_tails_generator_next: >>> rev_tails_generator: {:?}, tail_p {:?}",
-           rev_tails_generator, tail_p);
-
-    check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(tail_p, ErrorCode::CommonInvalidParam2);
-
-    let res = match rev_tails_generator.next() {
-        Ok(tail) => {
-            unsafe {
-                if let Some(tail) = tail {
-                    *tail_p = Box::into_raw(Box::new(tail)) as *const c_void;
-                } else {
-                    *tail_p = ptr::null();
+type FFITailRead = extern fn(ctx: *const c_void, idx: u32, out: *mut u8, out_len: usize) -> ErrorCode;
+
+ffi_fn! {
+    pub fn indy_crypto_cl_tails_generator_next(rev_tails_generator: *const c_void,
+                                               tail_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_generator_next: >>> rev_tails_generator: {:?}, tail_p {:?}",
+               rev_tails_generator, tail_p);
+
+        check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(tail_p, ErrorCode::CommonInvalidParam2);
+
+        let res = match rev_tails_generator.next() {
+            Ok(tail) => {
+                unsafe {
+                    if let Some(tail) = tail {
+                        *tail_p = register_c_reference!(tail);
+                    } else {
+                        *tail_p = ptr::null();
+                    }
+                    trace!("indy_crypto_cl_tails_generator_next: *tail_p: {:?}", *tail_p);
                 }
-                trace!("indy_crypto_cl_tails_generator_next: *tail_p: {:?}", *tail_p);
+                ErrorCode::Success
             }
-            ErrorCode::Success
+            Err(err) => err.into(),
+        };
+
+        trace!("indy_crypto_cl_tails_generator_next: <<< {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    pub fn indy_crypto_cl_tails_generator_count(rev_tails_generator: *const c_void,
+                                                count_p: *mut u32) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_generator_count: >>> rev_tails_generator: {:?}, count_p {:?}",
+               rev_tails_generator, count_p);
+
+        check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(count_p, ErrorCode::CommonInvalidParam2);
+
+        let cnt = rev_tails_generator.count();
+        unsafe {
+            *count_p = cnt;
+            trace!("indy_crypto_cl_tails_generator_count: *count_p: {:?}", *count_p);
         }
-        Err(err) => err.to_error_code(),
-    };
+        let res = ErrorCode::Success;
 
-    trace!("indy_crypto_cl_tails_generator_next: <<< {:?}", res);
-    res
+
+        trace!("indy_crypto_cl_tails_generator_count: <<< {:?}", res);
+        res
+    }
 }
 
-#[no_mangle]
-pub extern fn indy_crypto_cl_tails_generator_count(rev_tails_generator: *const c_void,
-                                                   count_p: *mut u32) -> ErrorCode {
-    trace!("indy_crypto_cl_tails_generator_count: >>> rev_tails_generator: {:?}, count_p {:?}",
-           rev_tails_generator, count_p);
+ffi_fn! {
+    /// Returns index of the tail the generator generates next, a checkpoint to resume generation from
+    /// by indy_crypto_cl_tails_generator_seek.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator instance pointer
+    /// * `index_p` - Reference that will contain the index
+    pub fn indy_crypto_cl_tails_generator_index(rev_tails_generator: *const c_void,
+                                                index_p: *mut u32) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_generator_index: >>> rev_tails_generator: {:?}, index_p {:?}",
+               rev_tails_generator, index_p);
+
+        check_useful_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(index_p, ErrorCode::CommonInvalidParam2);
+
+        unsafe {
+            *index_p = rev_tails_generator.index();
+            trace!("indy_crypto_cl_tails_generator_index: *index_p: {:?}", *index_p);
+        }
+        let res = ErrorCode::Success;
 
-    check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(count_p, ErrorCode::CommonInvalidParam2);
+        trace!("indy_crypto_cl_tails_generator_index: <<< {:?}", res);
+        res
+    }
+}
 
-    let cnt = rev_tails_generator.count();
-    unsafe {
-        *count_p = cnt;
-        trace!("indy_crypto_cl_tails_generator_count: *count_p: {:?}", *count_p);
+ffi_fn! {
+    /// Moves generation to the tail with index, tails before it are not generated again.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator instance pointer
+    /// * `index` - Index of the tail to generate next, at most the index after the last tail
+    pub fn indy_crypto_cl_tails_generator_seek(rev_tails_generator: *const c_void,
+                                               index: u32) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_generator_seek: >>> rev_tails_generator: {:?}, index {:?}",
+               rev_tails_generator, index);
+
+        check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+
+        let res = match rev_tails_generator.seek(index) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_tails_generator_seek: <<< {:?}", res);
+        res
     }
-    let res = ErrorCode::Success;
+}
 
+ffi_fn! {
+    /// Generates next tails into the caller allocated buffer, so tails can be streamed to a tails file
+    /// without a handle per tail. Tails are written in index order as bytes representation of 128 bytes,
+    /// as many whole tails as fit into the buffer. Zero tails are written if all tails are generated.
+    ///
+    /// # Arguments
+    /// * `rev_tails_generator` - Revocation tails generator instance pointer
+    /// * `buf` - Buffer pointer
+    /// * `buf_len` - Buffer len, at least 128
+    /// * `tails_count_p` - Reference that will contain number of tails written
+    pub fn indy_crypto_cl_tails_generator_next_chunk(rev_tails_generator: *const c_void,
+                                                     buf: *mut u8,
+                                                     buf_len: usize,
+                                                     tails_count_p: *mut usize) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_generator_next_chunk: >>> rev_tails_generator: {:?}, buf: {:?}, buf_len: {:?}, tails_count_p: {:?}",
+               rev_tails_generator, buf, buf_len, tails_count_p);
+
+        check_useful_mut_c_reference!(rev_tails_generator, RevocationTailsGenerator, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(buf, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(tails_count_p, ErrorCode::CommonInvalidParam4);
+
+        if buf_len < Tail::BYTES_REPR_SIZE {
+            return ctypes::invalid_param(ErrorCode::CommonInvalidParam3,
+                                         format!("`buf_len` must be at least {}, actual {}", Tail::BYTES_REPR_SIZE, buf_len));
+        }
 
-    trace!("indy_crypto_cl_tails_generator_count: <<< {:?}", res);
-    res
+        let res = match _write_tails_chunk(rev_tails_generator, buf, buf_len / Tail::BYTES_REPR_SIZE) {
+            Ok(tails_count) => {
+                unsafe {
+                    *tails_count_p = tails_count;
+                    trace!("indy_crypto_cl_tails_generator_next_chunk: *tails_count_p: {:?}", *tails_count_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_tails_generator_next_chunk: <<< {:?}", res);
+        res
+    }
 }
 
-#[no_mangle]
-pub extern fn indy_crypto_cl_tail_free(tail: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_tail_free: >>> tail: {:?}", tail);
+fn _write_tails_chunk(rev_tails_generator: &mut RevocationTailsGenerator, buf: *mut u8, chunk_size: usize) -> Result<usize, IndyCryptoError> {
+    let chunk = rev_tails_generator.next_chunk(chunk_size)?;
+    let buf = unsafe { slice::from_raw_parts_mut(buf, chunk.len() * Tail::BYTES_REPR_SIZE) };
+
+    for (tail, out) in chunk.iter().zip(buf.chunks_mut(Tail::BYTES_REPR_SIZE)) {
+        out.copy_from_slice(&tail.to_bytes()?);
+    }
+    Ok(chunk.len())
+}
 
-    check_useful_c_ptr!(tail, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    pub fn indy_crypto_cl_tail_free(tail: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_tail_free: >>> tail: {:?}", tail);
 
-    let tail = unsafe { let _ = Box::from_raw(tail as *mut Tail); };
-    trace!("indy_crypto_cl_tail_free: entity: tail: {:?}", tail);
+        check_useful_c_ptr!(tail, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(tail, Tail, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_cl_tail_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_tail_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-#[no_mangle]
-pub extern fn indy_crypto_cl_witness_new(rev_idx: u32,
+ffi_fn! {
+    pub fn indy_crypto_cl_witness_new(rev_idx: u32,
+                                      max_cred_num: u32,
+                                      issuance_by_default: bool,
+                                      rev_reg_delta: *const c_void,
+                                      ctx_tails: *const c_void,
+                                      take_tail: FFITailTake,
+                                      put_tail: FFITailPut,
+                                      witness_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_witness_new: >>> rev_idx: {:?}, max_cred_num {}, issuance_by_default {}, rev_reg_delta {:?}, ctx_tails {:?}, take_tail {:?}, \
+        put_tail {:?}, witness_p {:?}", rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, ctx_tails, take_tail, put_tail, witness_p);
+
+        check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam4);
+
+        let rta = FFITailsAccessor::new(ctx_tails, take_tail, put_tail);
+        let res = match Witness::new(rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, &rta) {
+            Ok(witness) => {
+                unsafe {
+                    *witness_p = register_c_reference!(witness);
+                    trace!("indy_crypto_cl_witness_new: *witness_p: {:?}", *witness_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_witness_new: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    pub fn indy_crypto_cl_witness_update(rev_idx: u32,
                                          max_cred_num: u32,
-                                         issuance_by_default: bool,
                                          rev_reg_delta: *const c_void,
+                                         witness: *mut c_void,
                                          ctx_tails: *const c_void,
                                          take_tail: FFITailTake,
-                                         put_tail: FFITailPut,
-                                         witness_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_witness_new: >>> rev_idx: {:?}, max_cred_num {}, issuance_by_default {}, rev_reg_delta {:?}, ctx_tails {:?}, take_tail {:?}, \
-    put_tail {:?}, witness_p {:?}", rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, ctx_tails, take_tail, put_tail, witness_p);
-
-    check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam3);
-
-    let rta = FFITailsAccessor::new(ctx_tails, take_tail, put_tail);
-    let res = match Witness::new(rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, &rta) {
-        Ok(witness) => {
-            unsafe {
-                *witness_p = Box::into_raw(Box::new(witness)) as *const c_void;
-                trace!("indy_crypto_cl_witness_new: *witness_p: {:?}", *witness_p);
-            }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+                                         put_tail: FFITailPut) -> ErrorCode {
+        trace!("indy_crypto_cl_witness_update: >>> rev_idx: {:?}, max_cred_num {}, rev_reg_delta {:?}, ctx_tails {:?}, take_tail {:?}, put_tail {:?}, witness {:?}",
+               rev_idx, max_cred_num, rev_reg_delta, ctx_tails, take_tail, put_tail, witness);
+
+        check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam3);
+        check_useful_mut_c_reference!(witness, Witness, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_crypto_cl_witness_new: <<< res: {:?}", res);
-    res
+        let rta = FFITailsAccessor::new(ctx_tails, take_tail, put_tail);
+        let res = match witness.update(rev_idx, max_cred_num, rev_reg_delta, &rta) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_witness_update: <<< res: {:?}", res);
+        res
+    }
 }
 
-#[no_mangle]
-pub extern fn indy_crypto_cl_witness_update(rev_idx: u32,
-                                            max_cred_num: u32,
-                                            rev_reg_delta: *const c_void,
-                                            witness: *mut c_void,
-                                            ctx_tails: *const c_void,
-                                            take_tail: FFITailTake,
-                                            put_tail: FFITailPut) -> ErrorCode {
-    trace!("indy_crypto_cl_witness_update: >>> rev_idx: {:?}, max_cred_num {}, rev_reg_delta {:?}, ctx_tails {:?}, take_tail {:?}, put_tail {:?}, witness {:?}",
-           rev_idx, max_cred_num, rev_reg_delta, ctx_tails, take_tail, put_tail, witness);
-
-    check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam3);
-    check_useful_mut_c_reference!(witness, Witness, ErrorCode::CommonInvalidParam4);
-
-    let rta = FFITailsAccessor::new(ctx_tails, take_tail, put_tail);
-    let res = match witness.update(rev_idx, max_cred_num, rev_reg_delta, &rta) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_witness_update: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    pub fn indy_crypto_cl_witness_free(witness: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_witness_free: >>> witness: {:?}", witness);
+
+        check_useful_c_ptr!(witness, ErrorCode::CommonInvalidParam1);
+
+        let res = free_c_reference!(witness, Witness, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_witness_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-#[no_mangle]
-pub extern fn indy_crypto_cl_witness_free(witness: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_witness_free: >>> witness: {:?}", witness);
+ffi_fn! {
+    /// Creates tails accessor that reads tails by the callback, so tails file never has to be loaded whole.
+    ///
+    /// `read_cb` is called with `context`, index of the tail and buffer of `out_len` bytes, it must write
+    /// bytes representation of the tail into the buffer (as stored in tails files, 128 bytes) and return
+    /// Success. Other error code fails the call that accesses the tail with CommonInvalidState.
+    /// The callback is called on the thread of that call, only during the call.
+    ///
+    /// Note: Accessor instance deallocation must be performed by calling indy_crypto_cl_tails_accessor_free.
+    /// Context must stay valid until then.
+    ///
+    /// # Arguments
+    /// * `context` - Context passed to `read_cb`, may be null
+    /// * `read_cb` - Callback that reads the tail
+    /// * `accessor_p` - Reference that will contain tails accessor instance pointer
+    pub fn indy_crypto_cl_tails_accessor_create(context: *const c_void,
+                                                read_cb: Option<FFITailRead>,
+                                                accessor_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_accessor_create: >>> context: {:?}, read_cb: {:?}, accessor_p: {:?}", context, read_cb, accessor_p);
+
+        check_useful_c_callback!(read_cb, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(accessor_p, ErrorCode::CommonInvalidParam3);
+
+        let accessor = FFITailsReader { ctx: context, read: read_cb };
+        unsafe {
+            *accessor_p = register_c_reference!(accessor);
+            trace!("indy_crypto_cl_tails_accessor_create: *accessor_p: {:?}", *accessor_p);
+        }
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_cl_tails_accessor_create: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Deallocates tails accessor instance, its context is not touched.
+    ///
+    /// # Arguments
+    /// * `accessor` - Tails accessor instance pointer
+    pub fn indy_crypto_cl_tails_accessor_free(accessor: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_tails_accessor_free: >>> accessor: {:?}", accessor);
+
+        check_useful_c_ptr!(accessor, ErrorCode::CommonInvalidParam1);
 
-    check_useful_c_ptr!(witness, ErrorCode::CommonInvalidParam1);
+        let res = free_c_reference!(accessor, FFITailsReader, ErrorCode::CommonInvalidParam1);
 
-    let witness = unsafe { let _ = Box::from_raw(witness as *mut Witness); };
-    trace!("indy_crypto_cl_witness_free: entity: witness: {:?}", witness);
+        trace!("indy_crypto_cl_tails_accessor_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates witness as indy_crypto_cl_witness_new, tails are read by the tails accessor.
+    ///
+    /// # Arguments
+    /// * `rev_idx` - Index of the credential in revocation registry
+    /// * `max_cred_num` - Max credential number in revocation registry
+    /// * `issuance_by_default` - Type of issuance
+    /// * `rev_reg_delta` - Revocation registry delta instance pointer
+    /// * `tails_accessor` - Tails accessor instance pointer, see indy_crypto_cl_tails_accessor_create
+    /// * `witness_p` - Reference that will contain witness instance pointer
+    pub fn indy_crypto_cl_witness_new_with_tails_accessor(rev_idx: u32,
+                                                          max_cred_num: u32,
+                                                          issuance_by_default: bool,
+                                                          rev_reg_delta: *const c_void,
+                                                          tails_accessor: *const c_void,
+                                                          witness_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_witness_new_with_tails_accessor: >>> rev_idx: {:?}, max_cred_num {}, issuance_by_default {}, rev_reg_delta {:?}, \
+        tails_accessor {:?}, witness_p {:?}", rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, tails_accessor, witness_p);
+
+        check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(tails_accessor, FFITailsReader, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(witness_p, ErrorCode::CommonInvalidParam6);
+
+        let res = match Witness::new(rev_idx, max_cred_num, issuance_by_default, rev_reg_delta, tails_accessor) {
+            Ok(witness) => {
+                unsafe {
+                    *witness_p = register_c_reference!(witness);
+                    trace!("indy_crypto_cl_witness_new_with_tails_accessor: *witness_p: {:?}", *witness_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    let res = ErrorCode::Success;
+        trace!("indy_crypto_cl_witness_new_with_tails_accessor: <<< res: {:?}", res);
+        res
+    }
+}
 
-    trace!("indy_crypto_cl_witness_free: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Updates witness as indy_crypto_cl_witness_update, tails are read by the tails accessor.
+    ///
+    /// # Arguments
+    /// * `rev_idx` - Index of the credential in revocation registry
+    /// * `max_cred_num` - Max credential number in revocation registry
+    /// * `rev_reg_delta` - Revocation registry delta instance pointer
+    /// * `witness` - Witness instance pointer
+    /// * `tails_accessor` - Tails accessor instance pointer, see indy_crypto_cl_tails_accessor_create
+    pub fn indy_crypto_cl_witness_update_with_tails_accessor(rev_idx: u32,
+                                                             max_cred_num: u32,
+                                                             rev_reg_delta: *const c_void,
+                                                             witness: *mut c_void,
+                                                             tails_accessor: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_witness_update_with_tails_accessor: >>> rev_idx: {:?}, max_cred_num {}, rev_reg_delta {:?}, witness {:?}, tails_accessor {:?}",
+               rev_idx, max_cred_num, rev_reg_delta, witness, tails_accessor);
+
+        check_useful_c_reference!(rev_reg_delta, RevocationRegistryDelta, ErrorCode::CommonInvalidParam3);
+        check_useful_mut_c_reference!(witness, Witness, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(tails_accessor, FFITailsReader, ErrorCode::CommonInvalidParam5);
+
+        let res = match witness.update(rev_idx, max_cred_num, rev_reg_delta, tails_accessor) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_witness_update_with_tails_accessor: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns credential schema entity builder.
-///
-/// The purpose of credential schema builder is building of credential schema entity that
-/// represents credential schema attributes set.
-///
-/// Note: Credential schema builder instance deallocation must be performed by
-/// calling indy_crypto_cl_credential_schema_builder_finalize.
-///
-/// # Arguments
-/// * `credential_schema_builder_p` - Reference that will contain credentials attributes builder instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_schema_builder_new(credential_schema_builder_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_schema_builder_new: >>> credential_schema_builder_p: {:?}", credential_schema_builder_p);
-
-    check_useful_c_ptr!(credential_schema_builder_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Issuer::new_credential_schema_builder() {
-        Ok(credential_schema_builder) => {
-            trace!("indy_crypto_cl_credential_schema_builder_new: credential_schema_builder: {:?}", credential_schema_builder);
-            unsafe {
-                *credential_schema_builder_p = Box::into_raw(Box::new(credential_schema_builder)) as *const c_void;
-                trace!("indy_crypto_cl_credential_schema_builder_new: *credential_schema_builder_p: {:?}", *credential_schema_builder_p);
+ffi_fn! {
+    /// Creates and returns credential schema entity builder.
+    ///
+    /// The purpose of credential schema builder is building of credential schema entity that
+    /// represents credential schema attributes set.
+    ///
+    /// Note: Credential schema builder instance deallocation must be performed by
+    /// calling indy_crypto_cl_credential_schema_builder_finalize.
+    ///
+    /// # Arguments
+    /// * `credential_schema_builder_p` - Reference that will contain credentials attributes builder instance pointer.
+    pub fn indy_crypto_cl_credential_schema_builder_new(credential_schema_builder_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_schema_builder_new: >>> credential_schema_builder_p: {:?}", credential_schema_builder_p);
+
+        check_useful_c_ptr!(credential_schema_builder_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Issuer::new_credential_schema_builder() {
+            Ok(credential_schema_builder) => {
+                trace!("indy_crypto_cl_credential_schema_builder_new: credential_schema_builder: {:?}", credential_schema_builder);
+                unsafe {
+                    *credential_schema_builder_p = register_c_reference!(credential_schema_builder);
+                    trace!("indy_crypto_cl_credential_schema_builder_new: *credential_schema_builder_p: {:?}", *credential_schema_builder_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_credential_schema_builder_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_schema_builder_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds new attribute to credential schema.
-///
-/// # Arguments
-/// * `credential_schema_builder` - Reference that contains credential schema builder instance pointer.
-/// * `attr` - Attribute to add as null terminated string.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_schema_builder_add_attr(credential_schema_builder: *const c_void,
-                                                                attr: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_schema_builder_add_attr: >>> credential_schema_builder: {:?}, attr: {:?}", credential_schema_builder, attr);
+ffi_fn! {
+    /// Adds new attribute to credential schema.
+    ///
+    /// # Arguments
+    /// * `credential_schema_builder` - Reference that contains credential schema builder instance pointer.
+    /// * `attr` - Attribute to add as null terminated string.
+    pub fn indy_crypto_cl_credential_schema_builder_add_attr(credential_schema_builder: *const c_void,
+                                                             attr: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_schema_builder_add_attr: >>> credential_schema_builder: {:?}, attr: {:?}", credential_schema_builder, attr);
 
-    check_useful_mut_c_reference!(credential_schema_builder, CredentialSchemaBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+        check_useful_mut_c_reference!(credential_schema_builder, CredentialSchemaBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
 
-    trace!("indy_crypto_cl_credential_schema_builder_add_attr: entities: credential_schema_builder: {:?}, attr: {:?}", credential_schema_builder, attr);
+        trace!("indy_crypto_cl_credential_schema_builder_add_attr: entities: credential_schema_builder: {:?}, attr: {:?}", credential_schema_builder, attr);
 
-    let res = match credential_schema_builder.add_attr(&attr) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
+        let res = match credential_schema_builder.add_attr(&attr) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_credential_schema_builder_add_attr: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_schema_builder_add_attr: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates credential schema builder and returns credential schema entity instead.
-///
-/// Note: Credentials schema instance deallocation must be performed by
-/// calling indy_crypto_cl_credential_schema_free.
-///
-/// # Arguments
-/// * `credential_schema_builder` - Reference that contains credential schema builder instance pointer
-/// * `credential_schema_p` - Reference that will contain credentials schema instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_schema_builder_finalize(credential_schema_builder: *const c_void,
-                                                                credential_schema_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_schema_builder_finalize: >>> credential_schema_builder: {:?}, credential_schema_p: {:?}", credential_schema_builder, credential_schema_p);
-
-    check_useful_c_ptr!(credential_schema_builder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(credential_schema_p, ErrorCode::CommonInvalidParam2);
-
-    let credential_schema_builder = unsafe { Box::from_raw(credential_schema_builder as *mut CredentialSchemaBuilder) };
-
-    trace!("indy_crypto_cl_credential_schema_builder_finalize: entities: credential_schema_builder: {:?}", credential_schema_builder);
-
-    let res = match credential_schema_builder.finalize() {
-        Ok(credential_schema) => {
-            trace!("indy_crypto_cl_credential_schema_builder_finalize: credential_schema: {:?}", credential_schema);
-            unsafe {
-                *credential_schema_p = Box::into_raw(Box::new(credential_schema)) as *const c_void;
-                trace!("indy_crypto_cl_credential_schema_builder_finalize: *credential_schema_p: {:?}", *credential_schema_p);
+ffi_fn! {
+    /// Deallocates credential schema builder and returns credential schema entity instead.
+    ///
+    /// Note: Credentials schema instance deallocation must be performed by
+    /// calling indy_crypto_cl_credential_schema_free.
+    ///
+    /// # Arguments
+    /// * `credential_schema_builder` - Reference that contains credential schema builder instance pointer
+    /// * `credential_schema_p` - Reference that will contain credentials schema instance pointer.
+    pub fn indy_crypto_cl_credential_schema_builder_finalize(credential_schema_builder: *const c_void,
+                                                             credential_schema_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_schema_builder_finalize: >>> credential_schema_builder: {:?}, credential_schema_p: {:?}", credential_schema_builder, credential_schema_p);
+
+        check_useful_c_ptr!(credential_schema_builder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_schema_p, ErrorCode::CommonInvalidParam2);
+
+        take_useful_c_reference!(credential_schema_builder, CredentialSchemaBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_credential_schema_builder_finalize: entities: credential_schema_builder: {:?}", credential_schema_builder);
+
+        let res = match credential_schema_builder.finalize() {
+            Ok(credential_schema) => {
+                trace!("indy_crypto_cl_credential_schema_builder_finalize: credential_schema: {:?}", credential_schema);
+                unsafe {
+                    *credential_schema_p = register_c_reference!(credential_schema);
+                    trace!("indy_crypto_cl_credential_schema_builder_finalize: *credential_schema_p: {:?}", *credential_schema_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_credential_schema_builder_finalize: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_schema_builder_finalize: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates credential schema instance.
-///
-/// # Arguments
-/// * `credential_schema` - Reference that contains credential schema instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_schema_free(credential_schema: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_schema_free: >>> credential_schema: {:?}", credential_schema);
+ffi_fn! {
+    /// Deallocates credential schema instance.
+    ///
+    /// # Arguments
+    /// * `credential_schema` - Reference that contains credential schema instance pointer.
+    pub fn indy_crypto_cl_credential_schema_free(credential_schema: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_schema_free: >>> credential_schema: {:?}", credential_schema);
 
-    check_useful_c_ptr!(credential_schema, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_schema, ErrorCode::CommonInvalidParam1);
 
-    let credential_schema = unsafe { let _ = Box::from_raw(credential_schema as *mut CredentialSchema); };
-    trace!("indy_crypto_cl_credential_schema_free: entity: credential_schema: {:?}", credential_schema);
+        let res = free_c_reference!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
-
-    trace!("indy_crypto_cl_credential_schema_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_schema_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns non credential schema builder.
-///
-/// The purpose of non credential schema builder is building of non credential schema that
-/// represents non credential schema attributes set. These are attributes added to schemas that are not on the ledger
-///
-/// Note: Non credential schema builder instance deallocation must be performed by
-/// calling indy_crypto_cl_non_credential_schema_builder_finalize.
-///
-/// # Arguments
-/// * `credential_schema_builder_p` - Reference that will contain credentials attributes builder instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_non_credential_schema_builder_new(non_credential_schema_builder_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_non_credential_schema_builder_new: >>> non_credential_schema_builder_p: {:?}", non_credential_schema_builder_p);
-
-    check_useful_c_ptr!(non_credential_schema_builder_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Issuer::new_non_credential_schema_builder() {
-        Ok(non_credential_schema_builder) => {
-            trace!("indy_crypto_cl_credential_schema_builder_new: non_credential_schema_builder: {:?}", non_credential_schema_builder);
-            unsafe {
-                *non_credential_schema_builder_p = Box::into_raw(Box::new(non_credential_schema_builder)) as *const c_void;
-                trace!("indy_crypto_cl_credential_schema_builder_new: *credential_schema_builder_p: {:?}", *non_credential_schema_builder_p);
+ffi_fn! {
+    /// Creates and returns non credential schema builder.
+    ///
+    /// The purpose of non credential schema builder is building of non credential schema that
+    /// represents non credential schema attributes set. These are attributes added to schemas that are not on the ledger
+    ///
+    /// Note: Non credential schema builder instance deallocation must be performed by
+    /// calling indy_crypto_cl_non_credential_schema_builder_finalize.
+    ///
+    /// # Arguments
+    /// * `credential_schema_builder_p` - Reference that will contain credentials attributes builder instance pointer.
+    pub fn indy_crypto_cl_non_credential_schema_builder_new(non_credential_schema_builder_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_non_credential_schema_builder_new: >>> non_credential_schema_builder_p: {:?}", non_credential_schema_builder_p);
+
+        check_useful_c_ptr!(non_credential_schema_builder_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Issuer::new_non_credential_schema_builder() {
+            Ok(non_credential_schema_builder) => {
+                trace!("indy_crypto_cl_credential_schema_builder_new: non_credential_schema_builder: {:?}", non_credential_schema_builder);
+                unsafe {
+                    *non_credential_schema_builder_p = register_c_reference!(non_credential_schema_builder);
+                    trace!("indy_crypto_cl_credential_schema_builder_new: *credential_schema_builder_p: {:?}", *non_credential_schema_builder_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_non_credential_schema_builder_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_non_credential_schema_builder_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds new attribute to non credential schema.
-///
-/// # Arguments
-/// * `non_credential_schema_builder` - Reference that contains non credential schema builder instance pointer.
-/// * `attr` - Attribute to add as null terminated string.
-#[no_mangle]
-pub extern fn indy_crypto_cl_non_credential_schema_builder_add_attr(non_credential_schema_builder: *const c_void,
-                                                                    attr: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_schema_builder_add_attr: >>> non_credential_schema_builder: {:?}, attr: {:?}", non_credential_schema_builder, attr);
+ffi_fn! {
+    /// Adds new attribute to non credential schema.
+    ///
+    /// # Arguments
+    /// * `non_credential_schema_builder` - Reference that contains non credential schema builder instance pointer.
+    /// * `attr` - Attribute to add as null terminated string.
+    pub fn indy_crypto_cl_non_credential_schema_builder_add_attr(non_credential_schema_builder: *const c_void,
+                                                                 attr: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_schema_builder_add_attr: >>> non_credential_schema_builder: {:?}, attr: {:?}", non_credential_schema_builder, attr);
 
-    check_useful_mut_c_reference!(non_credential_schema_builder, NonCredentialSchemaBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+        check_useful_mut_c_reference!(non_credential_schema_builder, NonCredentialSchemaBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
 
-    trace!("indy_crypto_cl_credential_schema_builder_add_attr: entities: credential_schema_builder: {:?}, attr: {:?}", non_credential_schema_builder, attr);
+        trace!("indy_crypto_cl_credential_schema_builder_add_attr: entities: credential_schema_builder: {:?}, attr: {:?}", non_credential_schema_builder, attr);
 
-    let res = match non_credential_schema_builder.add_attr(&attr) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
+        let res = match non_credential_schema_builder.add_attr(&attr) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_non_credential_schema_builder_add_attr: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_non_credential_schema_builder_add_attr: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates non_credential schema builder and returns non credential schema entity instead.
-///
-/// Note: Non credential schema instance deallocation must be performed by
-/// calling indy_crypto_cl_non_credential_schema_free.
-///
-/// # Arguments
-/// * `non_credential_schema_builder` - Reference that contains non credential schema builder instance pointer
-/// * `non_credential_schema_p` - Reference that will contain non credentials schema instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_non_credential_schema_builder_finalize(non_credential_schema_builder: *const c_void,
-                                                                    non_credential_schema_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_non_credential_schema_builder_finalize: >>> non_credential_schema_builder: {:?}, non_credential_schema_p: {:?}", non_credential_schema_builder, non_credential_schema_p);
-
-    check_useful_c_ptr!(non_credential_schema_builder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(non_credential_schema_p, ErrorCode::CommonInvalidParam2);
-
-    let non_credential_schema_builder = unsafe { Box::from_raw(non_credential_schema_builder as *mut NonCredentialSchemaBuilder) };
-
-    trace!("indy_crypto_cl_non_credential_schema_builder_finalize: entities: credential_schema_builder: {:?}", non_credential_schema_builder);
-
-    let res = match non_credential_schema_builder.finalize() {
-        Ok(non_credential_schema) => {
-            trace!("indy_crypto_cl_non_credential_schema_builder_finalize: credential_schema: {:?}", non_credential_schema);
-            unsafe {
-                *non_credential_schema_p = Box::into_raw(Box::new(non_credential_schema)) as *const c_void;
-                trace!("indy_crypto_cl_non_credential_schema_builder_finalize: *credential_schema_p: {:?}", *non_credential_schema_p);
+ffi_fn! {
+    /// Deallocates non_credential schema builder and returns non credential schema entity instead.
+    ///
+    /// Note: Non credential schema instance deallocation must be performed by
+    /// calling indy_crypto_cl_non_credential_schema_free.
+    ///
+    /// # Arguments
+    /// * `non_credential_schema_builder` - Reference that contains non credential schema builder instance pointer
+    /// * `non_credential_schema_p` - Reference that will contain non credentials schema instance pointer.
+    pub fn indy_crypto_cl_non_credential_schema_builder_finalize(non_credential_schema_builder: *const c_void,
+                                                                 non_credential_schema_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_non_credential_schema_builder_finalize: >>> non_credential_schema_builder: {:?}, non_credential_schema_p: {:?}", non_credential_schema_builder, non_credential_schema_p);
+
+        check_useful_c_ptr!(non_credential_schema_builder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(non_credential_schema_p, ErrorCode::CommonInvalidParam2);
+
+        take_useful_c_reference!(non_credential_schema_builder, NonCredentialSchemaBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_non_credential_schema_builder_finalize: entities: credential_schema_builder: {:?}", non_credential_schema_builder);
+
+        let res = match non_credential_schema_builder.finalize() {
+            Ok(non_credential_schema) => {
+                trace!("indy_crypto_cl_non_credential_schema_builder_finalize: credential_schema: {:?}", non_credential_schema);
+                unsafe {
+                    *non_credential_schema_p = register_c_reference!(non_credential_schema);
+                    trace!("indy_crypto_cl_non_credential_schema_builder_finalize: *credential_schema_p: {:?}", *non_credential_schema_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_non_credential_schema_builder_finalize: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_non_credential_schema_builder_finalize: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates credential schema instance.
-///
-/// # Arguments
-/// * `non_credential_schema` - Reference that contains non credential schema instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_non_credential_schema_free(non_credential_schema: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_non_credential_schema_free: >>> non_credential_schema: {:?}", non_credential_schema);
-
-    check_useful_c_ptr!(non_credential_schema, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Deallocates credential schema instance.
+    ///
+    /// # Arguments
+    /// * `non_credential_schema` - Reference that contains non credential schema instance pointer.
+    pub fn indy_crypto_cl_non_credential_schema_free(non_credential_schema: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_non_credential_schema_free: >>> non_credential_schema: {:?}", non_credential_schema);
 
-    let non_credential_schema = unsafe { let _ = Box::from_raw(non_credential_schema as *mut NonCredentialSchema); };
-    trace!("indy_crypto_cl_non_credential_schema_free: entity: credential_schema: {:?}", non_credential_schema);
+        check_useful_c_ptr!(non_credential_schema, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(non_credential_schema, NonCredentialSchema, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_cl_non_credential_schema_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_non_credential_schema_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns credentials values entity builder.
-///
-/// The purpose of credential values builder is building of credential values entity that
-/// represents credential attributes values map.
-///
-/// Note: Credentials values builder instance deallocation must be performed by
-/// calling indy_crypto_cl_credential_values_builder_finalize.
-///
-/// # Arguments
-/// * `credential_values_builder_p` - Reference that will contain credentials values builder instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_values_builder_new(credential_values_builder_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_values_builder_new: >>> credential_values_builder_p: {:?}", credential_values_builder_p);
-
-    check_useful_c_ptr!(credential_values_builder_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Issuer::new_credential_values_builder() {
-        Ok(credential_values_builder) => {
-            trace!("indy_crypto_cl_credential_values_builder_new: credential_values_builder: {:?}", credential_values_builder);
-            unsafe {
-                *credential_values_builder_p = Box::into_raw(Box::new(credential_values_builder)) as *const c_void;
-                trace!("indy_crypto_cl_credential_values_builder_new: *credential_values_builder_p: {:?}", *credential_values_builder_p);
+ffi_fn! {
+    /// Creates and returns credentials values entity builder.
+    ///
+    /// The purpose of credential values builder is building of credential values entity that
+    /// represents credential attributes values map.
+    ///
+    /// Note: Credentials values builder instance deallocation must be performed by
+    /// calling indy_crypto_cl_credential_values_builder_finalize.
+    ///
+    /// # Arguments
+    /// * `credential_values_builder_p` - Reference that will contain credentials values builder instance pointer.
+    pub fn indy_crypto_cl_credential_values_builder_new(credential_values_builder_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_values_builder_new: >>> credential_values_builder_p: {:?}", credential_values_builder_p);
+
+        check_useful_c_ptr!(credential_values_builder_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Issuer::new_credential_values_builder() {
+            Ok(credential_values_builder) => {
+                trace!("indy_crypto_cl_credential_values_builder_new: credential_values_builder: {:?}", credential_values_builder);
+                unsafe {
+                    *credential_values_builder_p = register_c_reference!(credential_values_builder);
+                    trace!("indy_crypto_cl_credential_values_builder_new: *credential_values_builder_p: {:?}", *credential_values_builder_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_credential_values_builder_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_values_builder_new: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds new known attribute dec_value to credential values map.
-///
-/// # Arguments
-/// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
-/// * `attr` - Credential attr to add as null terminated string.
-/// * `dec_value` - Credential attr dec_value. Decimal BigNum representation as null terminated string.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_values_builder_add_dec_known(credential_values_builder: *const c_void,
-                                                                 attr: *const c_char,
-                                                                 dec_value: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_known: >>> credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}",
-           credential_values_builder, attr, dec_value);
-
-    check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
-    check_useful_c_str!(dec_value, ErrorCode::CommonInvalidParam3);
-
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_known: entities: credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}", credential_values_builder, attr, dec_value);
-
-    let res = match credential_values_builder.add_dec_known(&attr, &dec_value) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_known: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Adds new known attribute dec_value to credential values map.
+    ///
+    /// # Arguments
+    /// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
+    /// * `attr` - Credential attr to add as null terminated string.
+    /// * `dec_value` - Credential attr dec_value. Decimal BigNum representation as null terminated string.
+    pub fn indy_crypto_cl_credential_values_builder_add_dec_known(credential_values_builder: *const c_void,
+                                                              attr: *const c_char,
+                                                              dec_value: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_known: >>> credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}",
+               credential_values_builder, attr, dec_value);
+
+        check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(dec_value, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_known: entities: credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}", credential_values_builder, attr, dec_value);
+
+        let res = match credential_values_builder.add_dec_known(&attr, &dec_value) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_known: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds new hidden attribute dec_value to credential values map.
-///
-/// # Arguments
-/// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
-/// * `attr` - Credential attr to add as null terminated string.
-/// * `dec_value` - Credential attr dec_value. Decimal BigNum representation as null terminated string.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_values_builder_add_dec_hidden(credential_values_builder: *const c_void,
-                                                                      attr: *const c_char,
-                                                                      dec_value: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_hidden: >>> credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}",
-           credential_values_builder, attr, dec_value);
-
-    check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
-    check_useful_c_str!(dec_value, ErrorCode::CommonInvalidParam3);
-
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_hidden: entities: credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}", credential_values_builder, attr, dec_value);
-
-    let res = match credential_values_builder.add_dec_hidden(&attr, &dec_value) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_hidden: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Adds new hidden attribute dec_value to credential values map.
+    ///
+    /// # Arguments
+    /// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
+    /// * `attr` - Credential attr to add as null terminated string.
+    /// * `dec_value` - Credential attr dec_value. Decimal BigNum representation as null terminated string.
+    pub fn indy_crypto_cl_credential_values_builder_add_dec_hidden(credential_values_builder: *const c_void,
+                                                                   attr: *const c_char,
+                                                                   dec_value: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_hidden: >>> credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}",
+               credential_values_builder, attr, dec_value);
+
+        check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(dec_value, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_hidden: entities: credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}", credential_values_builder, attr, dec_value);
+
+        let res = match credential_values_builder.add_dec_hidden(&attr, &dec_value) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_hidden: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds new hidden attribute dec_value to credential values map.
-///
-/// # Arguments
-/// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
-/// * `attr` - Credential attr to add as null terminated string.
-/// * `dec_value` - Credential attr dec_value. Decimal BigNum representation as null terminated string.
-/// * `dec_blinding_factor` - Credential blinding factor. Decimal BigNum representation as null terminated string
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_values_builder_add_dec_commitment(credential_values_builder: *const c_void,
-                                                                          attr: *const c_char,
-                                                                          dec_value: *const c_char,
-                                                                          dec_blinding_factor: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_commitment: >>> credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}, dec_blinding_factor: {:?}",
-           credential_values_builder, attr, dec_value, dec_blinding_factor);
-
-    check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
-    check_useful_c_str!(dec_value, ErrorCode::CommonInvalidParam3);
-    check_useful_c_str!(dec_blinding_factor, ErrorCode::CommonInvalidParam4);
-
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_commitment: entities: credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}, dec_blinding_factor: {:?}", credential_values_builder, attr, dec_value, dec_blinding_factor);
-
-    let res = match credential_values_builder.add_dec_commitment(&attr, &dec_value, &dec_blinding_factor) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_credential_values_builder_add_dec_commitment: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Adds new hidden attribute dec_value to credential values map.
+    ///
+    /// # Arguments
+    /// * `credential_values_builder` - Reference that contains credential values builder instance pointer.
+    /// * `attr` - Credential attr to add as null terminated string.
+    /// * `dec_value` - Credential attr dec_value. Decimal BigNum representation as null terminated string.
+    /// * `dec_blinding_factor` - Credential blinding factor. Decimal BigNum representation as null terminated string
+    pub fn indy_crypto_cl_credential_values_builder_add_dec_commitment(credential_values_builder: *const c_void,
+                                                                       attr: *const c_char,
+                                                                       dec_value: *const c_char,
+                                                                       dec_blinding_factor: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_commitment: >>> credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}, dec_blinding_factor: {:?}",
+               credential_values_builder, attr, dec_value, dec_blinding_factor);
+
+        check_useful_mut_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(dec_value, ErrorCode::CommonInvalidParam3);
+        check_useful_c_str!(dec_blinding_factor, ErrorCode::CommonInvalidParam4);
+
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_commitment: entities: credential_values_builder: {:?}, attr: {:?}, dec_value: {:?}, dec_blinding_factor: {:?}", credential_values_builder, attr, dec_value, dec_blinding_factor);
+
+        let res = match credential_values_builder.add_dec_commitment(&attr, &dec_value, &dec_blinding_factor) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_credential_values_builder_add_dec_commitment: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates credential values builder and returns credential values entity instead.
-///
-/// Note: Credentials values instance deallocation must be performed by
-/// calling indy_crypto_cl_credential_values_free.
-///
-/// # Arguments
-/// * `credential_values_builder` - Reference that contains credential attribute builder instance pointer.
-/// * `credential_values_p` - Reference that will contain credentials values instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_values_builder_finalize(credential_values_builder: *const c_void,
-                                                                credential_values_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_values_builder_finalize: >>> credential_values_builder: {:?}, credential_values_p: {:?}", credential_values_builder, credential_values_p);
-
-    check_useful_c_ptr!(credential_values_builder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(credential_values_p, ErrorCode::CommonInvalidParam2);
-
-    let credential_values_builder = unsafe { Box::from_raw(credential_values_builder as *mut CredentialValuesBuilder) };
-
-    trace!("indy_crypto_cl_credential_values_builder_finalize: entities: credential_values_builder: {:?}", credential_values_builder);
-
-    let res = match credential_values_builder.finalize() {
-        Ok(credential_values) => {
-            trace!("indy_crypto_cl_credential_values_builder_finalize: credential_values: {:?}", credential_values);
-            unsafe {
-                *credential_values_p = Box::into_raw(Box::new(credential_values)) as *const c_void;
-                trace!("indy_crypto_cl_credential_values_builder_finalize: *credential_values_p: {:?}", *credential_values_p);
+ffi_fn! {
+    /// Deallocates credential values builder and returns credential values entity instead.
+    ///
+    /// Note: Credentials values instance deallocation must be performed by
+    /// calling indy_crypto_cl_credential_values_free.
+    ///
+    /// # Arguments
+    /// * `credential_values_builder` - Reference that contains credential attribute builder instance pointer.
+    /// * `credential_values_p` - Reference that will contain credentials values instance pointer.
+    pub fn indy_crypto_cl_credential_values_builder_finalize(credential_values_builder: *const c_void,
+                                                             credential_values_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_values_builder_finalize: >>> credential_values_builder: {:?}, credential_values_p: {:?}", credential_values_builder, credential_values_p);
+
+        check_useful_c_ptr!(credential_values_builder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_values_p, ErrorCode::CommonInvalidParam2);
+
+        take_useful_c_reference!(credential_values_builder, CredentialValuesBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_credential_values_builder_finalize: entities: credential_values_builder: {:?}", credential_values_builder);
+
+        let res = match credential_values_builder.finalize() {
+            Ok(credential_values) => {
+                trace!("indy_crypto_cl_credential_values_builder_finalize: credential_values: {:?}", credential_values);
+                unsafe {
+                    *credential_values_p = register_c_reference!(credential_values);
+                    trace!("indy_crypto_cl_credential_values_builder_finalize: *credential_values_p: {:?}", *credential_values_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_credential_values_builder_finalize: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_values_builder_finalize: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates credential values instance.
-///
-/// # Arguments
-/// * `credential_values` - Credential values instance pointer
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_values_free(credential_values: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_values_free: >>> credential_values: {:?}", credential_values);
+ffi_fn! {
+    /// Deallocates credential values instance.
+    ///
+    /// # Arguments
+    /// * `credential_values` - Credential values instance pointer
+    pub fn indy_crypto_cl_credential_values_free(credential_values: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_values_free: >>> credential_values: {:?}", credential_values);
 
-    check_useful_c_ptr!(credential_values, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_values, ErrorCode::CommonInvalidParam1);
 
-    let credential_values = unsafe { let _ = Box::from_raw(credential_values as *mut CredentialValues); };
-    trace!("indy_crypto_cl_credential_values_free: entity: credential_values: {:?}", credential_values);
+        let res = free_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
-
-    trace!("indy_crypto_cl_credential_values_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_values_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns sub proof request entity builder.
-///
-/// The purpose of sub proof request builder is building of sub proof request entity that
-/// represents requested attributes and predicates.
-///
-/// Note: sub proof request builder instance deallocation must be performed by
-/// calling indy_crypto_cl_sub_proof_request_builder_finalize.
-///
-/// # Arguments
-/// * `sub_proof_request_builder_p` - Reference that will contain sub proof request builder instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_sub_proof_request_builder_new(sub_proof_request_builder_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_sub_proof_request_builder_new: >>> sub_proof_request_builder_p: {:?}", sub_proof_request_builder_p);
-
-    check_useful_c_ptr!(sub_proof_request_builder_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Verifier::new_sub_proof_request_builder() {
-        Ok(sub_proof_request_builder) => {
-            trace!("indy_crypto_cl_sub_proof_request_builder_new: sub_proof_request_builder: {:?}", sub_proof_request_builder);
-            unsafe {
-                *sub_proof_request_builder_p = Box::into_raw(Box::new(sub_proof_request_builder)) as *const c_void;
-                trace!("indy_crypto_cl_sub_proof_request_builder_new: *sub_proof_request_builder_p: {:?}", *sub_proof_request_builder_p);
+ffi_fn! {
+    /// Creates and returns sub proof request entity builder.
+    ///
+    /// The purpose of sub proof request builder is building of sub proof request entity that
+    /// represents requested attributes and predicates.
+    ///
+    /// Note: sub proof request builder instance deallocation must be performed by
+    /// calling indy_crypto_cl_sub_proof_request_builder_finalize.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request_builder_p` - Reference that will contain sub proof request builder instance pointer.
+    pub fn indy_crypto_cl_sub_proof_request_builder_new(sub_proof_request_builder_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_sub_proof_request_builder_new: >>> sub_proof_request_builder_p: {:?}", sub_proof_request_builder_p);
+
+        check_useful_c_ptr!(sub_proof_request_builder_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Verifier::new_sub_proof_request_builder() {
+            Ok(sub_proof_request_builder) => {
+                trace!("indy_crypto_cl_sub_proof_request_builder_new: sub_proof_request_builder: {:?}", sub_proof_request_builder);
+                unsafe {
+                    *sub_proof_request_builder_p = register_c_reference!(sub_proof_request_builder);
+                    trace!("indy_crypto_cl_sub_proof_request_builder_new: *sub_proof_request_builder_p: {:?}", *sub_proof_request_builder_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_sub_proof_request_builder_new: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_sub_proof_request_builder_new: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Adds new revealed attribute to sub proof request.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+    /// * `attr` - Credential attr to add as null terminated string.
+    pub fn indy_crypto_cl_sub_proof_request_builder_add_revealed_attr(sub_proof_request_builder: *const c_void,
+                                                                      attr: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_revealed_attr: >>> sub_proof_request_builder: {:?}, attr: {:?}",
+               sub_proof_request_builder, attr);
+
+        check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_revealed_attr: entities: sub_proof_request_builder: {:?}, attr: {:?}",
+               sub_proof_request_builder, attr);
+
+        let res = match sub_proof_request_builder.add_revealed_attr(&attr) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_revealed_attr: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds new revealed attribute to sub proof request.
-///
-/// # Arguments
-/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
-/// * `attr` - Credential attr to add as null terminated string.
-#[no_mangle]
-pub extern fn indy_crypto_cl_sub_proof_request_builder_add_revealed_attr(sub_proof_request_builder: *const c_void,
-                                                                         attr: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_sub_proof_request_builder_add_revealed_attr: >>> sub_proof_request_builder: {:?}, attr: {:?}",
-           sub_proof_request_builder, attr);
-
-    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_sub_proof_request_builder_add_revealed_attr: entities: sub_proof_request_builder: {:?}, attr: {:?}",
-           sub_proof_request_builder, attr);
-
-    let res = match sub_proof_request_builder.add_revealed_attr(&attr) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_sub_proof_request_builder_add_revealed_attr: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Adds predicate to sub proof request.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+    /// * `attr_name` - Related attribute
+    /// * `p_type` - Predicate type (`GE`, `GT`, `LE` or `LT`).
+    /// * `value` - Requested value.
+    pub fn indy_crypto_cl_sub_proof_request_builder_add_predicate(sub_proof_request_builder: *const c_void,
+                                                                  attr_name: *const c_char,
+                                                                  p_type: *const c_char,
+                                                                  value: i32) -> ErrorCode {
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, p_type: {:?}, value: {:?}",
+               sub_proof_request_builder, attr_name, p_type, value);
+
+        check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+        check_useful_c_str!(p_type, ErrorCode::CommonInvalidParam3);
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, p_type: {:?}, value: {:?}",
+               sub_proof_request_builder, attr_name, p_type, value);
+
+        let res = match sub_proof_request_builder.add_predicate(&attr_name, &p_type, value) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_predicate: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Adds predicate to sub proof request.
-///
-/// # Arguments
-/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
-/// * `attr_name` - Related attribute
-/// * `p_type` - Predicate type (Currently `GE` only).
-/// * `value` - Requested value.
-#[no_mangle]
-pub extern fn indy_crypto_cl_sub_proof_request_builder_add_predicate(sub_proof_request_builder: *const c_void,
-                                                                     attr_name: *const c_char,
-                                                                     p_type: *const c_char,
-                                                                     value: i32) -> ErrorCode {
-    trace!("indy_crypto_cl_sub_proof_request_builder_add_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, p_type: {:?}, value: {:?}",
-           sub_proof_request_builder, attr_name, p_type, value);
-
-    check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
-    check_useful_c_str!(p_type, ErrorCode::CommonInvalidParam3);
-
-    trace!("indy_crypto_cl_sub_proof_request_builder_add_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, p_type: {:?}, value: {:?}",
-           sub_proof_request_builder, attr_name, p_type, value);
-
-    let res = match sub_proof_request_builder.add_predicate(&attr_name, &p_type, value) {
-        Ok(_) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_sub_proof_request_builder_add_predicate: <<< res: {:?}", res);
-    res
+ffi_fn! {
+    /// Adds two sided range predicate min <= attr_value <= max to sub proof request.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+    /// * `attr_name` - Related attribute
+    /// * `min` - Lower bound of the range (inclusive).
+    /// * `max` - Upper bound of the range (inclusive).
+    pub fn indy_crypto_cl_sub_proof_request_builder_add_range_predicate(sub_proof_request_builder: *const c_void,
+                                                                        attr_name: *const c_char,
+                                                                        min: i32,
+                                                                        max: i32) -> ErrorCode {
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_range_predicate: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, min: {:?}, max: {:?}",
+               sub_proof_request_builder, attr_name, min, max);
+
+        check_useful_mut_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(attr_name, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_range_predicate: entities: >>> sub_proof_request_builder: {:?}, attr_name: {:?}, min: {:?}, max: {:?}",
+               sub_proof_request_builder, attr_name, min, max);
+
+        let res = match sub_proof_request_builder.add_range_predicate(&attr_name, min, max) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_add_range_predicate: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates sub proof request builder and returns sub proof request entity instead.
-///
-/// Note: Sub proof request instance deallocation must be performed by
-/// calling indy_crypto_cl_sub_proof_request_free.
-///
-/// # Arguments
-/// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
-/// * `sub_proof_request_p` - Reference that will contain sub proof request instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_sub_proof_request_builder_finalize(sub_proof_request_builder: *const c_void,
-                                                                sub_proof_request_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_sub_proof_request_builder_finalize: >>> sub_proof_request_builder: {:?}, sub_proof_request_p: {:?}",
-           sub_proof_request_builder, sub_proof_request_p);
-
-    check_useful_c_ptr!(sub_proof_request_builder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(sub_proof_request_p, ErrorCode::CommonInvalidParam2);
-
-    let sub_proof_request_builder = unsafe { Box::from_raw(sub_proof_request_builder as *mut SubProofRequestBuilder) };
-
-    trace!("indy_crypto_cl_sub_proof_request_builder_finalize: entities: sub_proof_request_builder: {:?}", sub_proof_request_builder);
-
-    let res = match sub_proof_request_builder.finalize() {
-        Ok(sub_proof_request) => {
-            trace!("indy_crypto_cl_sub_proof_request_builder_finalize: sub_proof_request: {:?}", sub_proof_request);
-            unsafe {
-                *sub_proof_request_p = Box::into_raw(Box::new(sub_proof_request)) as *const c_void;
-                trace!("indy_crypto_cl_sub_proof_request_builder_finalize: *sub_proof_request_p: {:?}", *sub_proof_request_p);
+ffi_fn! {
+    /// Deallocates sub proof request builder and returns sub proof request entity instead.
+    ///
+    /// Note: Sub proof request instance deallocation must be performed by
+    /// calling indy_crypto_cl_sub_proof_request_free.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request_builder` - Reference that contains sub proof request builder instance pointer.
+    /// * `sub_proof_request_p` - Reference that will contain sub proof request instance pointer.
+    pub fn indy_crypto_cl_sub_proof_request_builder_finalize(sub_proof_request_builder: *const c_void,
+                                                             sub_proof_request_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_sub_proof_request_builder_finalize: >>> sub_proof_request_builder: {:?}, sub_proof_request_p: {:?}",
+               sub_proof_request_builder, sub_proof_request_p);
+
+        check_useful_c_ptr!(sub_proof_request_builder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(sub_proof_request_p, ErrorCode::CommonInvalidParam2);
+
+        take_useful_c_reference!(sub_proof_request_builder, SubProofRequestBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_sub_proof_request_builder_finalize: entities: sub_proof_request_builder: {:?}", sub_proof_request_builder);
+
+        let res = match sub_proof_request_builder.finalize() {
+            Ok(sub_proof_request) => {
+                trace!("indy_crypto_cl_sub_proof_request_builder_finalize: sub_proof_request: {:?}", sub_proof_request);
+                unsafe {
+                    *sub_proof_request_p = register_c_reference!(sub_proof_request);
+                    trace!("indy_crypto_cl_sub_proof_request_builder_finalize: *sub_proof_request_p: {:?}", *sub_proof_request_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_sub_proof_request_builder_finalize: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_sub_proof_request_builder_finalize: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates sub proof request instance.
-///
-/// # Arguments
-/// * `sub_proof_request` - Reference that contains sub proof request instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_sub_proof_request_free(sub_proof_request: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_sub_proof_request_free: >>> sub_proof_request: {:?}", sub_proof_request);
+ffi_fn! {
+    /// Deallocates sub proof request instance.
+    ///
+    /// # Arguments
+    /// * `sub_proof_request` - Reference that contains sub proof request instance pointer.
+    pub fn indy_crypto_cl_sub_proof_request_free(sub_proof_request: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_sub_proof_request_free: >>> sub_proof_request: {:?}", sub_proof_request);
 
-    check_useful_c_ptr!(sub_proof_request, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(sub_proof_request, ErrorCode::CommonInvalidParam1);
 
-    let sub_proof_request = unsafe { let _ = Box::from_raw(sub_proof_request as *mut SubProofRequest); };
-    trace!("indy_crypto_cl_sub_proof_request_free: entity: sub_proof_request: {:?}", sub_proof_request);
+        let res = free_c_reference!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
+        trace!("indy_crypto_cl_sub_proof_request_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Creates random nonce.
+    ///
+    /// Note that nonce deallocation must be performed by calling indy_crypto_cl_nonce_free.
+    ///
+    /// # Arguments
+    /// * `nonce_p` - Reference that will contain nonce instance pointer.
+    pub fn indy_crypto_cl_new_nonce(nonce_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_new_nonce: >>> {:?}", nonce_p);
+
+        check_useful_c_ptr!(nonce_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match new_nonce() {
+            Ok(nonce) => {
+                trace!("indy_crypto_cl_new_nonce: nonce: {:?}", nonce);
+                unsafe {
+                    *nonce_p = register_c_reference!(nonce);
+                    trace!("indy_crypto_cl_new_nonce: *nonce_p: {:?}", *nonce_p);
+                }
+                ErrorCode::Success
+            }
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_sub_proof_request_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_new_nonce: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates random nonce.
-///
-/// Note that nonce deallocation must be performed by calling indy_crypto_cl_nonce_free.
-///
-/// # Arguments
-/// * `nonce_p` - Reference that will contain nonce instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_new_nonce(nonce_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_new_nonce: >>> {:?}", nonce_p);
-
-    check_useful_c_ptr!(nonce_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match new_nonce() {
-        Ok(nonce) => {
-            trace!("indy_crypto_cl_new_nonce: nonce: {:?}", nonce);
-            unsafe {
-                *nonce_p = Box::into_raw(Box::new(nonce)) as *const c_void;
-                trace!("indy_crypto_cl_new_nonce: *nonce_p: {:?}", *nonce_p);
+ffi_fn! {
+    /// Creates nonce deterministically from seed: the first 80 bits of SHA-256 hash of the seed.
+    ///
+    /// Note that nonce deallocation must be performed by calling indy_crypto_cl_nonce_free.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed bytes, must be unpredictable for production use.
+    /// * `seed_len` - Seed len.
+    /// * `nonce_p` - Reference that will contain nonce instance pointer.
+    pub fn indy_crypto_cl_new_nonce_from_seed(seed: *const u8,
+                                              seed_len: usize,
+                                              nonce_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_new_nonce_from_seed: >>> seed: {:?}, seed_len: {:?}, nonce_p: {:?}", seed, seed_len, nonce_p);
+
+        check_useful_c_byte_array!(seed, seed_len, ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(nonce_p, ErrorCode::CommonInvalidParam3);
+
+        let res = match new_nonce_from_seed(seed) {
+            Ok(nonce) => {
+                trace!("indy_crypto_cl_new_nonce_from_seed: nonce: {:?}", nonce);
+                unsafe {
+                    *nonce_p = register_c_reference!(nonce);
+                    trace!("indy_crypto_cl_new_nonce_from_seed: *nonce_p: {:?}", *nonce_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_new_nonce: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_new_nonce_from_seed: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns json representation of nonce.
-///
-/// # Arguments
-/// * `nonce` - Reference that contains nonce instance pointer.
-/// * `nonce_json_p` - Reference that will contain nonce json.
-#[no_mangle]
-pub extern fn indy_crypto_cl_nonce_to_json(nonce: *const c_void,
-                                           nonce_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_nonce_to_json: >>> nonce: {:?}, nonce_json_p: {:?}", nonce, nonce_json_p);
-
-    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(nonce_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_nonce_to_json: entity >>> nonce: {:?}", nonce);
-
-    let res = match serde_json::to_string(nonce) {
-        Ok(nonce_json) => {
-            trace!("indy_crypto_cl_nonce_to_json: nonce_json: {:?}", nonce_json);
-            unsafe {
-                let nonce_json = CTypesUtils::string_to_cstring(nonce_json);
-                *nonce_json_p = nonce_json.into_raw();
-                trace!("indy_crypto_cl_nonce_to_json: nonce_json_p: {:?}", *nonce_json_p);
+ffi_fn! {
+    /// Encodes raw attribute value to decimal string of a credential value.
+    /// Decimal integer within i32 range is passed through, any other value is encoded as SHA-256 hash.
+    ///
+    /// # Arguments
+    /// * `raw` - Raw attribute value (may be empty).
+    /// * `kind` - `Auto` (de-facto Indy encoding), `Integer` (value must be integer) or `Text` (value is always hashed).
+    /// * `encoded_p` - Reference that will contain encoded value.
+    pub fn indy_crypto_cl_encode_attribute(raw: *const c_char,
+                                           kind: *const c_char,
+                                           encoded_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_encode_attribute: >>> raw: {:?}, kind: {:?}, encoded_p: {:?}", raw, kind, encoded_p);
+
+        check_useful_c_ptr!(raw, ErrorCode::CommonInvalidParam1);
+        check_useful_opt_c_str!(raw, ErrorCode::CommonInvalidParam1);
+        check_useful_c_str!(kind, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(encoded_p, ErrorCode::CommonInvalidParam3);
+
+        let kind = match kind.as_str() {
+            "Auto" => AttributeKind::Auto,
+            "Integer" => AttributeKind::Integer,
+            "Text" => AttributeKind::Text,
+            kind => return ctypes::invalid_param(ErrorCode::CommonInvalidParam2, format!("Invalid attribute kind: {:?}", kind))
+        };
+
+        trace!("indy_crypto_cl_encode_attribute: entities: >>> raw: {:?}, kind: {:?}", raw, kind);
+
+        let res = match encode_attribute(&raw.unwrap_or_default(), kind)
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(encoded) => {
+                trace!("indy_crypto_cl_encode_attribute: encoded: {:?}", encoded);
+                unsafe {
+                    *encoded_p = encoded.into_raw();
+                    trace!("indy_crypto_cl_encode_attribute: *encoded_p: {:?}", *encoded_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidState
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_nonce_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_encode_attribute: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns nonce json.
-///
-/// Note: Nonce instance deallocation must be performed by calling indy_crypto_cl_nonce_free.
-///
-/// # Arguments
-/// * `nonce_json` - Reference that contains nonce json.
-/// * `nonce_p` - Reference that will contain nonce instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_nonce_from_json(nonce_json: *const c_char,
-                                             nonce_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_nonce_from_json: >>> nonce_json: {:?}, nonce_p: {:?}", nonce_json, nonce_p);
-
-    check_useful_c_str!(nonce_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(nonce_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_nonce_from_json: entity: nonce_json: {:?}", nonce_json);
-
-    let res = match serde_json::from_str::<Nonce>(&nonce_json) {
-        Ok(nonce) => {
-            trace!("indy_crypto_cl_nonce_from_json: nonce: {:?}", nonce);
-            unsafe {
-                *nonce_p = Box::into_raw(Box::new(nonce)) as *const c_void;
-                trace!("indy_crypto_cl_nonce_from_json: *nonce_p: {:?}", *nonce_p);
+ffi_fn! {
+    /// Returns json representation of nonce.
+    ///
+    /// # Arguments
+    /// * `nonce` - Reference that contains nonce instance pointer.
+    /// * `nonce_json_p` - Reference that will contain nonce json.
+    pub fn indy_crypto_cl_nonce_to_json(nonce: *const c_void,
+                                        nonce_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_nonce_to_json: >>> nonce: {:?}, nonce_json_p: {:?}", nonce, nonce_json_p);
+
+        check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(nonce_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_nonce_to_json: entity >>> nonce: {:?}", nonce);
+
+        let res = match serde_json::to_string(nonce)
+            .map_err(|err| IndyCryptoError::InvalidState(err.to_string()))
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(nonce_json) => {
+                trace!("indy_crypto_cl_nonce_to_json: nonce_json: {:?}", nonce_json);
+                unsafe {
+                    *nonce_json_p = nonce_json.into_raw();
+                    trace!("indy_crypto_cl_nonce_to_json: nonce_json_p: {:?}", *nonce_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidStructure
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_nonce_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_nonce_to_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates nonce instance.
-///
-/// # Arguments
-/// * `nonce` - Reference that contains nonce instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_nonce_free(nonce: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_nonce_free: >>> nonce: {:?}", nonce);
+ffi_fn! {
+    /// Creates and returns nonce json.
+    ///
+    /// Note: Nonce instance deallocation must be performed by calling indy_crypto_cl_nonce_free.
+    ///
+    /// # Arguments
+    /// * `nonce_json` - Reference that contains nonce json.
+    /// * `nonce_p` - Reference that will contain nonce instance pointer.
+    pub fn indy_crypto_cl_nonce_from_json(nonce_json: *const c_char,
+                                          nonce_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_nonce_from_json: >>> nonce_json: {:?}, nonce_p: {:?}", nonce_json, nonce_p);
+
+        check_useful_c_str!(nonce_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(nonce_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_nonce_from_json: entity: nonce_json: {:?}", nonce_json);
+
+        let res = match serde_json::from_str::<Nonce>(&nonce_json) {
+            Ok(nonce) => {
+                trace!("indy_crypto_cl_nonce_from_json: nonce: {:?}", nonce);
+                unsafe {
+                    *nonce_p = register_c_reference!(nonce);
+                    trace!("indy_crypto_cl_nonce_from_json: *nonce_p: {:?}", *nonce_p);
+                }
+                ErrorCode::Success
+            }
+            Err(_) => ErrorCode::CommonInvalidStructure
+        };
 
-    check_useful_c_ptr!(nonce, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_nonce_from_json: <<< res: {:?}", res);
+        res
+    }
+}
 
-    let nonce = unsafe { let _ = Box::from_raw(nonce as *mut Nonce); };
-    trace!("indy_crypto_cl_nonce_free: entity: nonce: {:?}", nonce);
+ffi_fn! {
+    /// Deallocates nonce instance.
+    ///
+    /// # Arguments
+    /// * `nonce` - Reference that contains nonce instance pointer.
+    pub fn indy_crypto_cl_nonce_free(nonce: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_nonce_free: >>> nonce: {:?}", nonce);
 
-    let res = ErrorCode::Success;
+        check_useful_c_ptr!(nonce, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_cl_nonce_free: <<< res: {:?}", res);
-    res
+        let res = free_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_nonce_free: <<< res: {:?}", res);
+        res
+    }
 }
 
 
@@ -827,9 +1173,11 @@ impl RevocationTailsAccessor for FFITailsAccessor {
                 format!("FFI call take_tail {:?} (ctx {:?}, id {}) failed: tail_p {:?}, returned error code {:?}",
                         self.take, self.ctx, tail_id, tail_p, res)));
         }
-        let tail: *const Tail = tail_p as *const Tail;
+        let tail = handles::get::<Tail>(tail_p)
+            .map_err(|reason| IndyCryptoError::InvalidState(
+                format!("FFI call take_tail {:?} (ctx {:?}, id {}) returned invalid tail: {}", self.take, self.ctx, tail_id, reason)))?;
 
-        accessor(&unsafe { *tail });
+        accessor(&tail);
 
         let res = (self.put)(self.ctx, tail_p);
         if res != ErrorCode::Success {
@@ -842,13 +1190,43 @@ impl RevocationTailsAccessor for FFITailsAccessor {
     }
 }
 
+struct FFITailsReader {
+    ctx: *const c_void,
+    read: FFITailRead,
+}
+
+// Context is owned by the caller, the reader only passes it to the callback on the thread of the call
+unsafe impl Send for FFITailsReader {}
+unsafe impl Sync for FFITailsReader {}
+
+impl RevocationTailsAccessor for FFITailsReader {
+    fn access_tail(&self, tail_id: u32, accessor: &mut dyn FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+        let mut bytes = vec![0u8; Tail::BYTES_REPR_SIZE];
+
+        let res = (self.read)(self.ctx, tail_id, bytes.as_mut_ptr(), bytes.len());
+        if res != ErrorCode::Success {
+            return Err(IndyCryptoError::InvalidState(
+                format!("FFI call read_tail {:?} (ctx {:?}, id {}) failed: returned error code {:?}",
+                        self.read, self.ctx, tail_id, res)));
+        }
+
+        let tail = Tail::from_bytes(&bytes)
+            .context(format!("FFI call read_tail {:?} (ctx {:?}, id {}) returned invalid tail", self.read, self.ctx, tail_id))?;
+
+        accessor(&tail);
+
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
     use std::ffi::CString;
     use std::ptr;
+    use crate::cl::issuer::mocks as issuer_mocks;
     use crate::ffi::cl::mocks::*;
 
     #[test]
@@ -893,6 +1271,20 @@ mod tests {
         _free_credential_schema_builder(credential_schema_builder);
     }
 
+    #[test]
+    fn indy_crypto_cl_credential_schema_builder_add_attr_works_for_invalid_utf8() {
+        let credential_schema_builder = _credential_schema_builder();
+
+        let attr = [0xC3u8, 0x28, 0x00];
+        let err_code = indy_crypto_cl_credential_schema_builder_add_attr(credential_schema_builder, attr.as_ptr() as *const c_char);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+
+        let error_json = crate::errors::get_current_error_json().unwrap();
+        assert!(error_json.contains("String is not valid UTF-8"), "{}", error_json);
+
+        _free_credential_schema_builder(credential_schema_builder);
+    }
+
     #[test]
     fn indy_crypto_cl_non_credential_schema_builder_add_attr_works() {
         let non_credential_schema_builder = _non_credential_schema_builder();
@@ -1078,6 +1470,21 @@ mod tests {
         _free_sub_proof_request_builder(sub_proof_request_builder);
     }
 
+    #[test]
+    fn indy_crypto_cl_sub_proof_request_builder_add_range_predicate_works() {
+        let sub_proof_request_builder = _sub_proof_request_builder();
+
+        let attr_name = CString::new("age").unwrap();
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_range_predicate(sub_proof_request_builder, attr_name.as_ptr(), 18, 65);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        let err_code = indy_crypto_cl_sub_proof_request_builder_add_range_predicate(sub_proof_request_builder, attr_name.as_ptr(), 65, 18);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        _free_sub_proof_request_builder(sub_proof_request_builder);
+    }
+
     #[test]
     fn indy_crypto_cl_sub_proof_request_builder_finalize_works() {
         let sub_proof_request_builder = _sub_proof_request_builder();
@@ -1113,6 +1520,52 @@ mod tests {
         _free_nonce(nonce_p)
     }
 
+    #[test]
+    fn indy_crypto_cl_new_nonce_from_seed_works() {
+        let seed = b"indy";
+        let mut nonce_p: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_new_nonce_from_seed(seed.as_ptr(), seed.len(), &mut nonce_p);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!nonce_p.is_null());
+
+        assert_eq!("400727458142521792328963", handles::get::<Nonce>(nonce_p).unwrap().to_dec().unwrap());
+
+        _free_nonce(nonce_p)
+    }
+
+    #[test]
+    fn indy_crypto_cl_new_nonce_from_seed_works_for_empty_seed() {
+        let mut nonce_p: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_new_nonce_from_seed(b"".as_ptr(), 0, &mut nonce_p);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        assert!(nonce_p.is_null());
+    }
+
+    #[test]
+    fn indy_crypto_cl_encode_attribute_works() {
+        let kind = CString::new("Auto").unwrap();
+        for &(raw, expected) in &[("28", "28"),
+                                  ("Alex", "99262857098057710338306967609588410025648622308394250666849665532448612202874"),
+                                  ("", "102987336249554097029535212322581322789799900648198034993379397001115665086549")] {
+            let raw = CString::new(raw).unwrap();
+            let mut encoded_p: *const c_char = ptr::null();
+            let err_code = indy_crypto_cl_encode_attribute(raw.as_ptr(), kind.as_ptr(), &mut encoded_p);
+            assert_eq!(err_code, ErrorCode::Success);
+            assert_eq!(expected, CTypesUtils::c_str_to_string(encoded_p).unwrap().unwrap());
+        }
+
+        let raw = CString::new("Alex").unwrap();
+        let mut encoded_p: *const c_char = ptr::null();
+
+        let kind = CString::new("Integer").unwrap();
+        let err_code = indy_crypto_cl_encode_attribute(raw.as_ptr(), kind.as_ptr(), &mut encoded_p);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+
+        let kind = CString::new("String").unwrap();
+        let err_code = indy_crypto_cl_encode_attribute(raw.as_ptr(), kind.as_ptr(), &mut encoded_p);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+    }
+
     #[test]
     fn indy_crypto_cl_nonce_to_json_works() {
         let nonce = _nonce();
@@ -1146,11 +1599,144 @@ mod tests {
         let err_code = indy_crypto_cl_nonce_free(nonce);
         assert_eq!(err_code, ErrorCode::Success);
     }
+
+    // Sums of the same tails in other order differ in projective coordinates, so witnesses are compared by bytes
+    fn _omega_bytes(witness: &Witness) -> Vec<u8> {
+        let witness = serde_json::to_value(witness).unwrap();
+        Tail::from_string(witness["omega"].as_str().unwrap()).unwrap().to_bytes().unwrap()
+    }
+
+    #[test]
+    fn indy_crypto_cl_witness_works_for_tails_accessor() {
+        let max_cred_num = 5;
+        let rev_idx = 2;
+        let credential_pub_key = issuer_mocks::credential_public_key();
+        let (_, _, rev_reg, mut rev_tails_generator) = Issuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, true).unwrap();
+        let simple_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator.clone()).unwrap();
+
+        let mut tails = Vec::new();
+        while let Some(tail) = rev_tails_generator.next().unwrap() {
+            tails.push(tail.to_bytes().unwrap());
+        }
+        let tails_file = FFITailsFile::from_tails(tails);
+        let tails_accessor = _tails_accessor(&tails_file);
+
+        let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &HashSet::new());
+        let rev_reg_delta_p = handles::register(rev_reg_delta.clone()).unwrap();
+
+        let mut witness: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_witness_new_with_tails_accessor(rev_idx, max_cred_num, true, rev_reg_delta_p, tails_accessor, &mut witness);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(max_cred_num as usize - 1, tails_file.reads.get());
+
+        let mut expected = Witness::new(rev_idx, max_cred_num, true, &rev_reg_delta, &simple_tails_accessor).unwrap();
+        assert_eq!(_omega_bytes(&expected), _omega_bytes(&handles::get::<Witness>(witness).unwrap()));
+
+        let revoked: HashSet<u32> = [4].iter().cloned().collect();
+        let update_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &revoked);
+        let update_delta_p = handles::register(update_delta.clone()).unwrap();
+
+        let err_code = indy_crypto_cl_witness_update_with_tails_accessor(rev_idx, max_cred_num, update_delta_p, witness as *mut c_void, tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+
+        expected.update(rev_idx, max_cred_num, &update_delta, &simple_tails_accessor).unwrap();
+        assert_eq!(_omega_bytes(&expected), _omega_bytes(&handles::get::<Witness>(witness).unwrap()));
+
+        tails_file.fail.set(true);
+        let mut failed_witness: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_witness_new_with_tails_accessor(rev_idx, max_cred_num, true, rev_reg_delta_p, tails_accessor, &mut failed_witness);
+        assert_eq!(err_code, ErrorCode::CommonInvalidState);
+        assert!(failed_witness.is_null());
+
+        let err_code = indy_crypto_cl_witness_new_with_tails_accessor(rev_idx, max_cred_num, true, rev_reg_delta_p, witness, &mut failed_witness);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam5);
+
+        _free_witness(witness);
+        _free_tails_accessor(tails_accessor);
+        handles::free::<RevocationRegistryDelta>(rev_reg_delta_p).unwrap();
+        handles::free::<RevocationRegistryDelta>(update_delta_p).unwrap();
+    }
+
+    #[test]
+    fn indy_crypto_cl_tails_accessor_create_works_for_invalid_params() {
+        let mut tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_tails_accessor_create(ptr::null(), None, &mut tails_accessor);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+
+        let err_code = indy_crypto_cl_tails_accessor_create(ptr::null(), Some(FFITailsFile::tail_read), ptr::null_mut());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+
+        // Context may be null, it is only passed to the callback
+        let err_code = indy_crypto_cl_tails_accessor_create(ptr::null(), Some(FFITailsFile::tail_read), &mut tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(ErrorCode::Success, indy_crypto_cl_tails_accessor_free(tails_accessor));
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_cl_tails_accessor_free(ptr::null()));
+    }
+
+    #[test]
+    fn indy_crypto_cl_tails_generator_next_chunk_works() {
+        let credential_pub_key = issuer_mocks::credential_public_key();
+        let (_, _, _, mut expected_generator) = Issuer::new_revocation_registry_def(&credential_pub_key, 5, true).unwrap();
+        let rev_tails_generator = handles::register(expected_generator.clone()).unwrap();
+
+        let mut expected = Vec::new();
+        while let Some(tail) = expected_generator.next().unwrap() {
+            expected.extend(tail.to_bytes().unwrap());
+        }
+
+        // Buffer of 4.5 tails takes 4 tails at a time
+        let mut buf = vec![0u8; 4 * Tail::BYTES_REPR_SIZE + Tail::BYTES_REPR_SIZE / 2];
+        let mut tails = Vec::new();
+        let mut index = 0;
+        let mut tails_count = 0;
+        loop {
+            let err_code = indy_crypto_cl_tails_generator_next_chunk(rev_tails_generator, buf.as_mut_ptr(), buf.len(), &mut tails_count);
+            assert_eq!(err_code, ErrorCode::Success);
+            if tails_count == 0 {
+                break;
+            }
+            tails.extend_from_slice(&buf[..tails_count * Tail::BYTES_REPR_SIZE]);
+
+            if index == 0 {
+                let err_code = indy_crypto_cl_tails_generator_index(rev_tails_generator, &mut index);
+                assert_eq!(err_code, ErrorCode::Success);
+                assert_eq!(4, index);
+            }
+        }
+        assert_eq!(expected, tails);
+
+        let err_code = indy_crypto_cl_tails_generator_seek(rev_tails_generator, index);
+        assert_eq!(err_code, ErrorCode::Success);
+        let err_code = indy_crypto_cl_tails_generator_next_chunk(rev_tails_generator, buf.as_mut_ptr(), Tail::BYTES_REPR_SIZE, &mut tails_count);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert_eq!(1, tails_count);
+        assert_eq!(&expected[4 * Tail::BYTES_REPR_SIZE..5 * Tail::BYTES_REPR_SIZE], &buf[..Tail::BYTES_REPR_SIZE]);
+
+        let err_code = indy_crypto_cl_tails_generator_seek(rev_tails_generator, 12);
+        assert_eq!(err_code, ErrorCode::CommonInvalidStructure);
+        let err_code = indy_crypto_cl_tails_generator_seek(ptr::null(), 0);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+
+        let err_code = indy_crypto_cl_tails_generator_index(rev_tails_generator, ptr::null_mut());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+
+        let err_code = indy_crypto_cl_tails_generator_next_chunk(ptr::null(), buf.as_mut_ptr(), buf.len(), &mut tails_count);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+        let err_code = indy_crypto_cl_tails_generator_next_chunk(rev_tails_generator, ptr::null_mut(), buf.len(), &mut tails_count);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam2);
+        let err_code = indy_crypto_cl_tails_generator_next_chunk(rev_tails_generator, buf.as_mut_ptr(), Tail::BYTES_REPR_SIZE - 1, &mut tails_count);
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam3);
+        let err_code = indy_crypto_cl_tails_generator_next_chunk(rev_tails_generator, buf.as_mut_ptr(), buf.len(), ptr::null_mut());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam4);
+
+        handles::free::<RevocationTailsGenerator>(rev_tails_generator).unwrap();
+    }
 }
 
 pub mod mocks {
     use super::*;
 
+    use std::cell::Cell;
     use std::ffi::CString;
     use std::ptr;
 
@@ -1183,7 +1769,7 @@ pub mod mocks {
 
     pub fn _free_non_credential_schema_builder(non_credential_schema_builder: *const c_void) {
         let mut non_credential_schema: *const c_void = ptr::null();
-        let err_code = indy_crypto_cl_credential_schema_builder_finalize(non_credential_schema_builder, &mut non_credential_schema);
+        let err_code = indy_crypto_cl_non_credential_schema_builder_finalize(non_credential_schema_builder, &mut non_credential_schema);
         assert_eq!(err_code, ErrorCode::Success);
         assert!(!non_credential_schema.is_null());
 
@@ -1407,6 +1993,58 @@ pub mod mocks {
     }
 
 
+    pub fn _tails_accessor(tails_file: &FFITailsFile) -> *const c_void {
+        let mut tails_accessor: *const c_void = ptr::null();
+        let err_code = indy_crypto_cl_tails_accessor_create(tails_file.get_ctx(), Some(FFITailsFile::tail_read), &mut tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+        assert!(!tails_accessor.is_null());
+
+        tails_accessor
+    }
+
+    pub fn _free_tails_accessor(tails_accessor: *const c_void) {
+        let err_code = indy_crypto_cl_tails_accessor_free(tails_accessor);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    /// Tails as bytes read by `tail_read` callback of tails accessor, like tails file.
+    pub struct FFITailsFile {
+        pub tails: Vec<Vec<u8>>,
+        pub reads: Cell<usize>,
+        pub fail: Cell<bool>,
+    }
+
+    impl FFITailsFile {
+        pub fn new(tail_storage: &FFISimpleTailStorage) -> Self {
+            let tails = tail_storage.tails.iter()
+                .map(|tail| handles::get::<Tail>(*tail).unwrap().to_bytes().unwrap())
+                .collect();
+            Self::from_tails(tails)
+        }
+
+        pub fn from_tails(tails: Vec<Vec<u8>>) -> Self {
+            Self { tails, reads: Cell::new(0), fail: Cell::new(false) }
+        }
+
+        pub fn get_ctx(&self) -> *const c_void {
+            self as *const FFITailsFile as *const c_void
+        }
+
+        pub extern "C" fn tail_read(ctx: *const c_void, idx: u32, out: *mut u8, out_len: usize) -> ErrorCode {
+            let tails_file: &FFITailsFile = unsafe { &*(ctx as *const FFITailsFile) };
+
+            let tail = match tails_file.tails.get(idx as usize) {
+                Some(tail) if !tails_file.fail.get() && tail.len() == out_len => tail,
+                _ => return ErrorCode::CommonIOError
+            };
+
+            unsafe { ptr::copy_nonoverlapping(tail.as_ptr(), out, out_len) };
+            tails_file.reads.set(tails_file.reads.get() + 1);
+
+            ErrorCode::Success
+        }
+    }
+
     pub struct FFISimpleTailStorage {
         tails: Box<Vec<*const c_void>>
     }
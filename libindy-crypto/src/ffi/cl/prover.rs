@@ -1,831 +1,842 @@
 use crate::cl::prover::*;
 use crate::cl::*;
-use crate::errors::ToErrorCode;
-use crate::errors::ErrorCode;
+use crate::errors::{ErrorCode, IndyCryptoError};
 use crate::ffi::ctypes::CTypesUtils;
 
 use serde_json;
 use std::os::raw::c_void;
 use libc::c_char;
 
-/// Creates a master secret.
-///
-/// Note that master secret deallocation must be performed by
-/// calling indy_crypto_cl_master_secret_free.
-///
-/// # Arguments
-/// * `master_secret_p` - Reference that will contain master secret instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_prover_new_master_secret(master_secret_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_new_master_secret: >>> {:?}", master_secret_p);
-
-    check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Prover::new_master_secret() {
-        Ok(master_secret) => {
-            trace!("indy_crypto_cl_prover_new_master_secret: master_secret: {:?}", master_secret);
-            unsafe {
-                *master_secret_p = Box::into_raw(Box::new(master_secret)) as *const c_void;
-                trace!("indy_crypto_cl_prover_new_master_secret: *master_secret_p: {:?}", *master_secret_p);
+ffi_fn! {
+    /// Creates a master secret.
+    ///
+    /// Note that master secret deallocation must be performed by
+    /// calling indy_crypto_cl_master_secret_free.
+    ///
+    /// # Arguments
+    /// * `master_secret_p` - Reference that will contain master secret instance pointer.
+    pub fn indy_crypto_cl_prover_new_master_secret(master_secret_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_prover_new_master_secret: >>> {:?}", master_secret_p);
+
+        check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Prover::new_master_secret() {
+            Ok(master_secret) => {
+                trace!("indy_crypto_cl_prover_new_master_secret: master_secret: {:?}", master_secret);
+                unsafe {
+                    *master_secret_p = register_c_reference!(master_secret);
+                    trace!("indy_crypto_cl_prover_new_master_secret: *master_secret_p: {:?}", *master_secret_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_prover_new_master_secret: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_prover_new_master_secret: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns json representation of master secret.
-///
-/// # Arguments
-/// * `master_secret` - Reference that contains master secret instance pointer.
-/// * `master_secret_json_p` - Reference that will contain master secret json.
-#[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_to_json(master_secret: *const c_void,
-                                                   master_secret_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_to_json: >>> master_secret: {:?}, master_secret_json_p: {:?}", master_secret, master_secret_json_p);
-
-    check_useful_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(master_secret_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_master_secret_to_json: entity >>> master_secret: {:?}", master_secret);
-
-    let res = match serde_json::to_string(master_secret) {
-        Ok(master_secret_json) => {
-            trace!("indy_crypto_cl_master_secret_to_json: master_secret_json: {:?}", master_secret_json);
-            unsafe {
-                let master_secret_json = CTypesUtils::string_to_cstring(master_secret_json);
-                *master_secret_json_p = master_secret_json.into_raw();
-                trace!("indy_crypto_cl_master_secret_to_json: master_secret_json_p: {:?}", *master_secret_json_p);
+ffi_fn! {
+    /// Returns json representation of master secret.
+    ///
+    /// # Arguments
+    /// * `master_secret` - Reference that contains master secret instance pointer.
+    /// * `master_secret_json_p` - Reference that will contain master secret json.
+    pub fn indy_crypto_cl_master_secret_to_json(master_secret: *const c_void,
+                                                master_secret_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_master_secret_to_json: >>> master_secret: {:?}, master_secret_json_p: {:?}", master_secret, master_secret_json_p);
+
+        check_useful_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_master_secret_to_json: entity >>> master_secret: {:?}", master_secret);
+
+        let res = match serde_json::to_string(master_secret)
+            .map_err(|err| IndyCryptoError::InvalidState(err.to_string()))
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(master_secret_json) => {
+                trace!("indy_crypto_cl_master_secret_to_json: master_secret_json: {:?}", master_secret_json);
+                unsafe {
+                    *master_secret_json_p = master_secret_json.into_raw();
+                    trace!("indy_crypto_cl_master_secret_to_json: master_secret_json_p: {:?}", *master_secret_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidState
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_master_secret_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_master_secret_to_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns master secret from json.
-///
-/// Note: Master secret instance deallocation must be performed
-/// by calling indy_crypto_cl_master_secret_free.
-///
-/// # Arguments
-/// * `master_secret_json` - Reference that contains master secret json.
-/// * `master_secret_p` - Reference that will contain master secret instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_from_json(master_secret_json: *const c_char,
-                                                     master_secret_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_from_json: >>> master_secret_json: {:?}, master_secret_p: {:?}", master_secret_json, master_secret_p);
-
-    check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_master_secret_from_json: entity: master_secret_json: {:?}", master_secret_json);
-
-    let res = match serde_json::from_str::<MasterSecret>(&master_secret_json) {
-        Ok(master_secret) => {
-            trace!("indy_crypto_cl_master_secret_from_json: master_secret: {:?}", master_secret);
-            unsafe {
-                *master_secret_p = Box::into_raw(Box::new(master_secret)) as *const c_void;
-                trace!("indy_crypto_cl_master_secret_from_json: *master_secret_p: {:?}", *master_secret_p);
+ffi_fn! {
+    /// Creates and returns master secret from json.
+    ///
+    /// Note: Master secret instance deallocation must be performed
+    /// by calling indy_crypto_cl_master_secret_free.
+    ///
+    /// # Arguments
+    /// * `master_secret_json` - Reference that contains master secret json.
+    /// * `master_secret_p` - Reference that will contain master secret instance pointer.
+    pub fn indy_crypto_cl_master_secret_from_json(master_secret_json: *const c_char,
+                                                  master_secret_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_master_secret_from_json: >>> master_secret_json: {:?}, master_secret_p: {:?}", master_secret_json, master_secret_p);
+
+        check_useful_c_str!(master_secret_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_master_secret_from_json: entity: master_secret_json: {:?}", master_secret_json);
+
+        let res = match serde_json::from_str::<MasterSecret>(&master_secret_json) {
+            Ok(master_secret) => {
+                trace!("indy_crypto_cl_master_secret_from_json: master_secret: {:?}", master_secret);
+                unsafe {
+                    *master_secret_p = register_c_reference!(master_secret);
+                    trace!("indy_crypto_cl_master_secret_from_json: *master_secret_p: {:?}", *master_secret_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidStructure
-    };
+            Err(_) => ErrorCode::CommonInvalidStructure
+        };
 
-    trace!("indy_crypto_cl_master_secret_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_master_secret_from_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates master secret instance.
-///
-/// # Arguments
-/// * `master_secret` - Reference that contains master secret instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_master_secret_free(master_secret: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_master_secret_free: >>> master_secret: {:?}", master_secret);
+ffi_fn! {
+    /// Deallocates master secret instance.
+    ///
+    /// # Arguments
+    /// * `master_secret` - Reference that contains master secret instance pointer.
+    pub fn indy_crypto_cl_master_secret_free(master_secret: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_master_secret_free: >>> master_secret: {:?}", master_secret);
 
-    check_useful_c_ptr!(master_secret, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(master_secret, ErrorCode::CommonInvalidParam1);
 
-    let master_secret = unsafe { let _ = Box::from_raw(master_secret as *mut MasterSecret); };
-    trace!("indy_crypto_cl_master_secret_free: entity: master_secret: {:?}", master_secret);
+        let res = free_c_reference!(master_secret, MasterSecret, ErrorCode::CommonInvalidParam1);
+        trace!("indy_crypto_cl_master_secret_free: <<< res: {:?}", res);
 
-    let res = ErrorCode::Success;
-    trace!("indy_crypto_cl_master_secret_free: <<< res: {:?}", res);
-
-    res
+        res
+    }
 }
 
-/// Creates blinded credential secrets for given issuer key and master secret.
-///
-/// Note that blinded credential secrets deallocation must be performed by
-/// calling indy_crypto_cl_blinded_credential_secrets_free.
-///
-/// Note that credential secrets blinding factors deallocation must be performed by
-/// calling indy_crypto_cl_credential_secrets_blinding_factors_free.
-///
-/// Note that blinded credential secrets correctness proof deallocation must be performed by
-/// calling indy_crypto_cl_blinded_credential_secrets_correctness_proof_free.
-///
-/// # Arguments
-/// * `credential_pub_key` - Reference that contains credential public key instance pointer.
-/// * `credential_key_correctness_proof` - Reference that contains credential key correctness proof instance pointer.
-/// * `credential_values` - Reference that contains credential values pointer.
-/// * `credential_nonce` - Reference that contains nonce instance pointer.
-/// * `blinded_credential_secrets_p` - Reference that will contain blinded credential secrets instance pointer.
-/// * `credential_secrets_blinding_factors_p` - Reference that will contain credential secrets blinding factors instance pointer.
-/// * `blinded_credential_secrets_correctness_proof_p` - Reference that will contain blinded credential secrets correctness proof instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_prover_blind_credential_secrets(credential_pub_key: *const c_void,
-                                                             credential_key_correctness_proof: *const c_void,
-                                                             credential_values: *const c_void,
-                                                             credential_nonce: *const c_void,
-                                                             blinded_credential_secrets_p: *mut *const c_void,
-                                                             credential_secrets_blinding_factors_p: *mut *const c_void,
-                                                             blinded_credential_secrets_correctness_proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_blind_credential_secrets: >>> credential_pub_key: {:?}, \
-                                                                credential_key_correctness_proof: {:?}, \
-                                                                credential_values: {:?}, \
-                                                                credential_nonce: {:?}, \
-                                                                blinded_credential_secrets_p: {:?}, \
-                                                                credential_secrets_blinding_factors_p: {:?}, \
-                                                                blinded_credential_secrets_correctness_proof_p: {:?}",
-                                                                credential_pub_key,
-                                                                credential_key_correctness_proof,
-                                                                credential_values,
-                                                                credential_nonce,
-                                                                blinded_credential_secrets_p,
-                                                                credential_secrets_blinding_factors_p,
-                                                                blinded_credential_secrets_correctness_proof_p);
-
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(credential_key_correctness_proof, CredentialKeyCorrectnessProof, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(credential_nonce, Nonce, ErrorCode::CommonInvalidParam4);
-    check_useful_c_ptr!(blinded_credential_secrets_p, ErrorCode::CommonInvalidParam5);
-    check_useful_c_ptr!(credential_secrets_blinding_factors_p, ErrorCode::CommonInvalidParam6);
-    check_useful_c_ptr!(blinded_credential_secrets_correctness_proof_p, ErrorCode::CommonInvalidParam7);
-
-    trace!("indy_crypto_cl_prover_blind_credential_secrets: inputs: credential_pub_key: {:?}, \
+ffi_fn! {
+    /// Creates blinded credential secrets for given issuer key and master secret.
+    ///
+    /// Note that blinded credential secrets deallocation must be performed by
+    /// calling indy_crypto_cl_blinded_credential_secrets_free.
+    ///
+    /// Note that credential secrets blinding factors deallocation must be performed by
+    /// calling indy_crypto_cl_credential_secrets_blinding_factors_free.
+    ///
+    /// Note that blinded credential secrets correctness proof deallocation must be performed by
+    /// calling indy_crypto_cl_blinded_credential_secrets_correctness_proof_free.
+    ///
+    /// # Arguments
+    /// * `credential_pub_key` - Reference that contains credential public key instance pointer.
+    /// * `credential_key_correctness_proof` - Reference that contains credential key correctness proof instance pointer.
+    /// * `credential_values` - Reference that contains credential values pointer.
+    /// * `credential_nonce` - Reference that contains nonce instance pointer.
+    /// * `blinded_credential_secrets_p` - Reference that will contain blinded credential secrets instance pointer.
+    /// * `credential_secrets_blinding_factors_p` - Reference that will contain credential secrets blinding factors instance pointer.
+    /// * `blinded_credential_secrets_correctness_proof_p` - Reference that will contain blinded credential secrets correctness proof instance pointer.
+    pub fn indy_crypto_cl_prover_blind_credential_secrets(credential_pub_key: *const c_void,
+                                                          credential_key_correctness_proof: *const c_void,
+                                                          credential_values: *const c_void,
+                                                          credential_nonce: *const c_void,
+                                                          blinded_credential_secrets_p: *mut *const c_void,
+                                                          credential_secrets_blinding_factors_p: *mut *const c_void,
+                                                          blinded_credential_secrets_correctness_proof_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_prover_blind_credential_secrets: >>> credential_pub_key: {:?}, \
                                                                     credential_key_correctness_proof: {:?}, \
                                                                     credential_values: {:?}, \
-                                                                    credential_nonce: {:?}",
+                                                                    credential_nonce: {:?}, \
+                                                                    blinded_credential_secrets_p: {:?}, \
+                                                                    credential_secrets_blinding_factors_p: {:?}, \
+                                                                    blinded_credential_secrets_correctness_proof_p: {:?}",
                                                                     credential_pub_key,
                                                                     credential_key_correctness_proof,
                                                                     credential_values,
-                                                                    credential_nonce);
-
-    let res = match Prover::blind_credential_secrets(credential_pub_key,
-                                                     credential_key_correctness_proof,
-                                                     credential_values,
-                                                     credential_nonce) {
-        Ok((blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof)) => {
-            trace!("indy_crypto_cl_prover_blind_credential_secrets: blinded_credential_secrets: {:?}, \
-                                                                    credential_secrets_blinding_factors: {:?}, \
-                                                                    blinded_credential_secrets_correctness_proof: {:?}",
-                                                                    blinded_credential_secrets,
-                                                                    credential_secrets_blinding_factors,
-                                                                    blinded_credential_secrets_correctness_proof);
-            unsafe {
-                *blinded_credential_secrets_p = Box::into_raw(Box::new(blinded_credential_secrets)) as *const c_void;
-                *credential_secrets_blinding_factors_p = Box::into_raw(Box::new(credential_secrets_blinding_factors)) as *const c_void;
-                *blinded_credential_secrets_correctness_proof_p = Box::into_raw(Box::new(blinded_credential_secrets_correctness_proof)) as *const c_void;
-                trace!("indy_crypto_cl_prover_blind_credential_secrets: *blinded_credential_secrets_p: {:?}, \
-                                                                        *credential_secrets_blinding_factors_p: {:?}, \
-                                                                        *blinded_credential_secrets_correctness_proof_p: {:?}",
-                                                                        *blinded_credential_secrets_p,
-                                                                        *credential_secrets_blinding_factors_p,
-                                                                        *blinded_credential_secrets_correctness_proof_p);
+                                                                    credential_nonce,
+                                                                    blinded_credential_secrets_p,
+                                                                    credential_secrets_blinding_factors_p,
+                                                                    blinded_credential_secrets_correctness_proof_p);
+
+        check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(credential_key_correctness_proof, CredentialKeyCorrectnessProof, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam3);
+        check_useful_c_reference!(credential_nonce, Nonce, ErrorCode::CommonInvalidParam4);
+        check_useful_c_ptr!(blinded_credential_secrets_p, ErrorCode::CommonInvalidParam5);
+        check_useful_c_ptr!(credential_secrets_blinding_factors_p, ErrorCode::CommonInvalidParam6);
+        check_useful_c_ptr!(blinded_credential_secrets_correctness_proof_p, ErrorCode::CommonInvalidParam7);
+
+        trace!("indy_crypto_cl_prover_blind_credential_secrets: inputs: credential_pub_key: {:?}, \
+                                                                        credential_key_correctness_proof: {:?}, \
+                                                                        credential_values: {:?}, \
+                                                                        credential_nonce: {:?}",
+                                                                        credential_pub_key,
+                                                                        credential_key_correctness_proof,
+                                                                        credential_values,
+                                                                        credential_nonce);
+
+        let res = match Prover::blind_credential_secrets(credential_pub_key,
+                                                         credential_key_correctness_proof,
+                                                         credential_values,
+                                                         credential_nonce) {
+            Ok((blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof)) => {
+                trace!("indy_crypto_cl_prover_blind_credential_secrets: blinded_credential_secrets: {:?}, \
+                                                                        credential_secrets_blinding_factors: {:?}, \
+                                                                        blinded_credential_secrets_correctness_proof: {:?}",
+                                                                        blinded_credential_secrets,
+                                                                        credential_secrets_blinding_factors,
+                                                                        blinded_credential_secrets_correctness_proof);
+                unsafe {
+                    *blinded_credential_secrets_p = register_c_reference!(blinded_credential_secrets);
+                    *credential_secrets_blinding_factors_p = register_c_reference!(credential_secrets_blinding_factors);
+                    *blinded_credential_secrets_correctness_proof_p = register_c_reference!(blinded_credential_secrets_correctness_proof);
+                    trace!("indy_crypto_cl_prover_blind_credential_secrets: *blinded_credential_secrets_p: {:?}, \
+                                                                            *credential_secrets_blinding_factors_p: {:?}, \
+                                                                            *blinded_credential_secrets_correctness_proof_p: {:?}",
+                                                                            *blinded_credential_secrets_p,
+                                                                            *credential_secrets_blinding_factors_p,
+                                                                            *blinded_credential_secrets_correctness_proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_prover_blind_credential_secrets: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_prover_blind_credential_secrets: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns json representation of blinded credential secrets.
-///
-/// # Arguments
-/// * `blinded_credential_secrets` - Reference that contains Blinded credential secrets pointer.
-/// * `blinded_credential_secrets_json_p` - Reference that will contain blinded credential secrets json.
-#[no_mangle]
-pub extern fn indy_crypto_cl_blinded_credential_secrets_to_json(blinded_credential_secrets: *const c_void,
-                                                                blinded_credential_secrets_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_credential_secrets_to_json: >>> blinded_credential_secrets: {:?}\n\
-                                                                   blinded_credential_secrets_json_p: {:?}", blinded_credential_secrets, blinded_credential_secrets_json_p);
-
-    check_useful_c_reference!(blinded_credential_secrets, BlindedCredentialSecrets, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_credential_secrets_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_credential_secrets_to_json: entity >>> blinded_credential_secrets: {:?}", blinded_credential_secrets);
-
-    let res = match serde_json::to_string(blinded_credential_secrets) {
-        Ok(blinded_credential_secrets_json) => {
-            trace!("indy_crypto_cl_blinded_credential_secrets_to_json: blinded_credential_secrets_json: {:?}", blinded_credential_secrets_json);
-            unsafe {
-                let blinded_credential_secrets_json = CTypesUtils::string_to_cstring(blinded_credential_secrets_json);
-                *blinded_credential_secrets_json_p = blinded_credential_secrets_json.into_raw();
-
-                trace!("indy_crypto_cl_blinded_credential_secrets_to_json: blinded_credential_secrets_json_p: {:?}", *blinded_credential_secrets_json_p);
+ffi_fn! {
+    /// Returns json representation of blinded credential secrets.
+    ///
+    /// # Arguments
+    /// * `blinded_credential_secrets` - Reference that contains Blinded credential secrets pointer.
+    /// * `blinded_credential_secrets_json_p` - Reference that will contain blinded credential secrets json.
+    pub fn indy_crypto_cl_blinded_credential_secrets_to_json(blinded_credential_secrets: *const c_void,
+                                                             blinded_credential_secrets_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_blinded_credential_secrets_to_json: >>> blinded_credential_secrets: {:?}\n\
+                                                                       blinded_credential_secrets_json_p: {:?}", blinded_credential_secrets, blinded_credential_secrets_json_p);
+
+        check_useful_c_reference!(blinded_credential_secrets, BlindedCredentialSecrets, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_credential_secrets_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_credential_secrets_to_json: entity >>> blinded_credential_secrets: {:?}", blinded_credential_secrets);
+
+        let res = match serde_json::to_string(blinded_credential_secrets)
+            .map_err(|err| IndyCryptoError::InvalidState(err.to_string()))
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(blinded_credential_secrets_json) => {
+                trace!("indy_crypto_cl_blinded_credential_secrets_to_json: blinded_credential_secrets_json: {:?}", blinded_credential_secrets_json);
+                unsafe {
+                    *blinded_credential_secrets_json_p = blinded_credential_secrets_json.into_raw();
+
+                    trace!("indy_crypto_cl_blinded_credential_secrets_to_json: blinded_credential_secrets_json_p: {:?}", *blinded_credential_secrets_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidState
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_blinded_credential_secrets_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_credential_secrets_to_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns blinded credential secrets from json.
-///
-/// Note: Blinded credential secrets instance deallocation must be performed
-/// by calling indy_crypto_cl_blinded_credential_secrets_free
-///
-/// # Arguments
-/// * `blinded_credential_secrets_json` - Reference that contains blinded credential secret json.
-/// * `blinded_credential_secrets_p` - Reference that will contain blinded credential secret instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_blinded_credential_secrets_from_json(blinded_credential_secrets_json: *const c_char,
-                                                                  blinded_credential_secrets_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_credential_secrets_from_json: >>> blinded_credential_secrets_json: {:?}, blinded_credential_secrets_p: {:?}", blinded_credential_secrets_json, blinded_credential_secrets_p);
-
-    check_useful_c_str!(blinded_credential_secrets_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_credential_secrets_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_credential_secrets_from_json: entity: blinded_credential_secrets_json: {:?}", blinded_credential_secrets_json);
-
-    let res = match serde_json::from_str::<BlindedCredentialSecrets>(&blinded_credential_secrets_json) {
-        Ok(blinded_credential_secrets) => {
-            trace!("indy_crypto_cl_blinded_credential_secrets_from_json: blinded_credential_secrets: {:?}", blinded_credential_secrets);
-            unsafe {
-                *blinded_credential_secrets_p = Box::into_raw(Box::new(blinded_credential_secrets)) as *const c_void;
-                trace!("indy_crypto_cl_blinded_credential_secrets_from_json: *blinded_credential_secrets_p: {:?}", *blinded_credential_secrets_p);
+ffi_fn! {
+    /// Creates and returns blinded credential secrets from json.
+    ///
+    /// Note: Blinded credential secrets instance deallocation must be performed
+    /// by calling indy_crypto_cl_blinded_credential_secrets_free
+    ///
+    /// # Arguments
+    /// * `blinded_credential_secrets_json` - Reference that contains blinded credential secret json.
+    /// * `blinded_credential_secrets_p` - Reference that will contain blinded credential secret instance pointer.
+    pub fn indy_crypto_cl_blinded_credential_secrets_from_json(blinded_credential_secrets_json: *const c_char,
+                                                               blinded_credential_secrets_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_blinded_credential_secrets_from_json: >>> blinded_credential_secrets_json: {:?}, blinded_credential_secrets_p: {:?}", blinded_credential_secrets_json, blinded_credential_secrets_p);
+
+        check_useful_c_str!(blinded_credential_secrets_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_credential_secrets_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_credential_secrets_from_json: entity: blinded_credential_secrets_json: {:?}", blinded_credential_secrets_json);
+
+        let res = match serde_json::from_str::<BlindedCredentialSecrets>(&blinded_credential_secrets_json) {
+            Ok(blinded_credential_secrets) => {
+                trace!("indy_crypto_cl_blinded_credential_secrets_from_json: blinded_credential_secrets: {:?}", blinded_credential_secrets);
+                unsafe {
+                    *blinded_credential_secrets_p = register_c_reference!(blinded_credential_secrets);
+                    trace!("indy_crypto_cl_blinded_credential_secrets_from_json: *blinded_credential_secrets_p: {:?}", *blinded_credential_secrets_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidStructure
-    };
+            Err(_) => ErrorCode::CommonInvalidStructure
+        };
 
-    trace!("indy_crypto_cl_blinded_credential_secrets_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_credential_secrets_from_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates  blinded credential secrets instance.
-///
-/// # Arguments
-/// * `blinded_credential_secrets` - Reference that contains blinded credential secrets instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_blinded_credential_secrets_free(blinded_credential_secrets: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_credential_secrets_free: >>> blinded_credential_secrets: {:?}", blinded_credential_secrets);
-
-    check_useful_c_ptr!(blinded_credential_secrets, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Deallocates  blinded credential secrets instance.
+    ///
+    /// # Arguments
+    /// * `blinded_credential_secrets` - Reference that contains blinded credential secrets instance pointer.
+    pub fn indy_crypto_cl_blinded_credential_secrets_free(blinded_credential_secrets: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_blinded_credential_secrets_free: >>> blinded_credential_secrets: {:?}", blinded_credential_secrets);
 
-    let blinded_credential_secrets = unsafe { let _ = Box::from_raw(blinded_credential_secrets as *mut BlindedCredentialSecrets); };
-    trace!("indy_crypto_cl_blinded_credential_secrets_free: entity: blinded_credential_secrets: {:?}", blinded_credential_secrets);
+        check_useful_c_ptr!(blinded_credential_secrets, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(blinded_credential_secrets, BlindedCredentialSecrets, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_cl_blinded_credential_secrets_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_credential_secrets_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns json representation of credential secrets blinding factors.
-///
-/// # Arguments
-/// * `credential_secrets_blinding_factors` - Reference that contains credential secrets blinding factors pointer.
-/// * `credential_secrets_blinding_factors_json_p` - Reference that will contain credential secrets blinding factors json.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_secrets_blinding_factors_to_json(credential_secrets_blinding_factors: *const c_void,
-                                                                         credential_secrets_blinding_factors_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: >>> credential_secrets_blinding_factors: {:?}\n\
-                                                                           credential_secrets_blinding_factors_json_p: {:?}", credential_secrets_blinding_factors, credential_secrets_blinding_factors_json_p);
-
-    check_useful_c_reference!(credential_secrets_blinding_factors, CredentialSecretsBlindingFactors, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(credential_secrets_blinding_factors_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: entity >>> credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
-
-    let res = match serde_json::to_string(credential_secrets_blinding_factors) {
-        Ok(credential_secrets_blinding_factors_json) => {
-            trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: credential_secrets_blinding_factors_json: {:?}", credential_secrets_blinding_factors_json);
-            unsafe {
-                let credential_secrets_blinding_factors_json = CTypesUtils::string_to_cstring(credential_secrets_blinding_factors_json);
-                *credential_secrets_blinding_factors_json_p = credential_secrets_blinding_factors_json.into_raw();
-                trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: credential_secrets_blinding_factors_json_p: {:?}", *credential_secrets_blinding_factors_json_p);
+ffi_fn! {
+    /// Returns json representation of credential secrets blinding factors.
+    ///
+    /// # Arguments
+    /// * `credential_secrets_blinding_factors` - Reference that contains credential secrets blinding factors pointer.
+    /// * `credential_secrets_blinding_factors_json_p` - Reference that will contain credential secrets blinding factors json.
+    pub fn indy_crypto_cl_credential_secrets_blinding_factors_to_json(credential_secrets_blinding_factors: *const c_void,
+                                                                      credential_secrets_blinding_factors_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: >>> credential_secrets_blinding_factors: {:?}\n\
+                                                                               credential_secrets_blinding_factors_json_p: {:?}", credential_secrets_blinding_factors, credential_secrets_blinding_factors_json_p);
+
+        check_useful_c_reference!(credential_secrets_blinding_factors, CredentialSecretsBlindingFactors, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_secrets_blinding_factors_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: entity >>> credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
+
+        let res = match serde_json::to_string(credential_secrets_blinding_factors)
+            .map_err(|err| IndyCryptoError::InvalidState(err.to_string()))
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(credential_secrets_blinding_factors_json) => {
+                trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: credential_secrets_blinding_factors_json: {:?}", credential_secrets_blinding_factors_json);
+                unsafe {
+                    *credential_secrets_blinding_factors_json_p = credential_secrets_blinding_factors_json.into_raw();
+                    trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: credential_secrets_blinding_factors_json_p: {:?}", *credential_secrets_blinding_factors_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidState
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_secret_blinding_factors_to_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns credential secrets blinding factors json.
-///
-/// Note: Credential secrets blinding factors instance deallocation must be performed
-/// by calling indy_crypto_cl_credential_secrets_blinding_factors_free.
-///
-/// # Arguments
-/// * `credential_secrets_blinding_factors_json` - Reference that contains credential secrets blinding factors json.
-/// * `credential_secrets_blinding_factors_p` - Reference that will contain credential secrets blinding factors instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_secrets_blinding_factors_from_json(credential_secrets_blinding_factors_json: *const c_char,
-                                                                           credential_secrets_blinding_factors_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: >>> credential_secrets_blinding_factors_json: {:?}\n\
-                                                                              credential_secrets_blinding_factors_p: {:?}", credential_secrets_blinding_factors_json, credential_secrets_blinding_factors_p);
-
-    check_useful_c_str!(credential_secrets_blinding_factors_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(credential_secrets_blinding_factors_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: entity: credential_secrets_blinding_factors_json: {:?}", credential_secrets_blinding_factors_json);
-
-    let res = match serde_json::from_str::<CredentialSecretsBlindingFactors>(&credential_secrets_blinding_factors_json) {
-        Ok(credential_secrets_blinding_factors) => {
-            trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
-            unsafe {
-                *credential_secrets_blinding_factors_p = Box::into_raw(Box::new(credential_secrets_blinding_factors)) as *const c_void;
-                trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: *credential_secrets_blinding_factors_p: {:?}", *credential_secrets_blinding_factors_p);
+ffi_fn! {
+    /// Creates and returns credential secrets blinding factors json.
+    ///
+    /// Note: Credential secrets blinding factors instance deallocation must be performed
+    /// by calling indy_crypto_cl_credential_secrets_blinding_factors_free.
+    ///
+    /// # Arguments
+    /// * `credential_secrets_blinding_factors_json` - Reference that contains credential secrets blinding factors json.
+    /// * `credential_secrets_blinding_factors_p` - Reference that will contain credential secrets blinding factors instance pointer.
+    pub fn indy_crypto_cl_credential_secrets_blinding_factors_from_json(credential_secrets_blinding_factors_json: *const c_char,
+                                                                        credential_secrets_blinding_factors_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: >>> credential_secrets_blinding_factors_json: {:?}\n\
+                                                                                  credential_secrets_blinding_factors_p: {:?}", credential_secrets_blinding_factors_json, credential_secrets_blinding_factors_p);
+
+        check_useful_c_str!(credential_secrets_blinding_factors_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_secrets_blinding_factors_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: entity: credential_secrets_blinding_factors_json: {:?}", credential_secrets_blinding_factors_json);
+
+        let res = match serde_json::from_str::<CredentialSecretsBlindingFactors>(&credential_secrets_blinding_factors_json) {
+            Ok(credential_secrets_blinding_factors) => {
+                trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
+                unsafe {
+                    *credential_secrets_blinding_factors_p = register_c_reference!(credential_secrets_blinding_factors);
+                    trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: *credential_secrets_blinding_factors_p: {:?}", *credential_secrets_blinding_factors_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidStructure
-    };
+            Err(_) => ErrorCode::CommonInvalidStructure
+        };
 
-    trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_secrets_blinding_factors_from_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates credential secrets blinding factors instance.
-///
-/// # Arguments
-/// * `credential_secrets_blinding_factors` - Reference that contains credential secrets blinding factors instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_credential_secrets_blinding_factors_free(credential_secrets_blinding_factors: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_credential_secrets_blinding_factors_free: >>> credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
+ffi_fn! {
+    /// Deallocates credential secrets blinding factors instance.
+    ///
+    /// # Arguments
+    /// * `credential_secrets_blinding_factors` - Reference that contains credential secrets blinding factors instance pointer.
+    pub fn indy_crypto_cl_credential_secrets_blinding_factors_free(credential_secrets_blinding_factors: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_credential_secrets_blinding_factors_free: >>> credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
 
-    check_useful_c_ptr!(credential_secrets_blinding_factors, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(credential_secrets_blinding_factors, ErrorCode::CommonInvalidParam1);
 
-    let credential_secrets_blinding_factors = unsafe { let _ = Box::from_raw(credential_secrets_blinding_factors as *mut CredentialSecretsBlindingFactors); };
-    trace!("indy_crypto_cl_credential_secrets_blinding_factors_free: entity: credential_secrets_blinding_factors: {:?}", credential_secrets_blinding_factors);
+        let res = free_c_reference!(credential_secrets_blinding_factors, CredentialSecretsBlindingFactors, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
-
-    trace!("indy_crypto_cl_credential_secrets_blinding_factors_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_credential_secrets_blinding_factors_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns json representation of blinded credential secrets correctness proof.
-///
-/// # Arguments
-/// * `blinded_credential_secrets_correctness_proof` - Reference that contains blinded credential secrets correctness proof pointer.
-/// * `blinded_credential_secrets_correctness_proof_json_p` - Reference that will contain blinded credential secrets correctness proof json.
-#[no_mangle]
-pub extern fn indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json(blinded_credential_secrets_correctness_proof: *const c_void,
-                                                                                  blinded_credential_secrets_correctness_proof_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: >>> blinded_credential_secrets_correctness_proof: {:?}\n\
-                                                                                     blinded_credential_secrets_correctness_proof_json_p: {:?}", blinded_credential_secrets_correctness_proof, blinded_credential_secrets_correctness_proof_json_p);
-
-    check_useful_c_reference!(blinded_credential_secrets_correctness_proof, BlindedCredentialSecretsCorrectnessProof, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_credential_secrets_correctness_proof_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: entity >>> blinded_credential_secrets_correctness_proof: {:?}",
-           blinded_credential_secrets_correctness_proof);
-
-    let res = match serde_json::to_string(blinded_credential_secrets_correctness_proof) {
-        Ok(blinded_credential_secrets_correctness_proof_json) => {
-            trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: blinded_credential_secrets_correctness_proof: {:?}",
-                   blinded_credential_secrets_correctness_proof_json);
-            unsafe {
-                let blinded_credential_secrets_correctness_proof_json = CTypesUtils::string_to_cstring(blinded_credential_secrets_correctness_proof_json);
-                *blinded_credential_secrets_correctness_proof_json_p = blinded_credential_secrets_correctness_proof_json.into_raw();
-                trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: blinded_credential_secrets_correctness_proof_json_p: {:?}",
-                       *blinded_credential_secrets_correctness_proof_json_p);
+ffi_fn! {
+    /// Returns json representation of blinded credential secrets correctness proof.
+    ///
+    /// # Arguments
+    /// * `blinded_credential_secrets_correctness_proof` - Reference that contains blinded credential secrets correctness proof pointer.
+    /// * `blinded_credential_secrets_correctness_proof_json_p` - Reference that will contain blinded credential secrets correctness proof json.
+    pub fn indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json(blinded_credential_secrets_correctness_proof: *const c_void,
+                                                                               blinded_credential_secrets_correctness_proof_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: >>> blinded_credential_secrets_correctness_proof: {:?}\n\
+                                                                                         blinded_credential_secrets_correctness_proof_json_p: {:?}", blinded_credential_secrets_correctness_proof, blinded_credential_secrets_correctness_proof_json_p);
+
+        check_useful_c_reference!(blinded_credential_secrets_correctness_proof, BlindedCredentialSecretsCorrectnessProof, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_credential_secrets_correctness_proof_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: entity >>> blinded_credential_secrets_correctness_proof: {:?}",
+               blinded_credential_secrets_correctness_proof);
+
+        let res = match serde_json::to_string(blinded_credential_secrets_correctness_proof)
+            .map_err(|err| IndyCryptoError::InvalidState(err.to_string()))
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(blinded_credential_secrets_correctness_proof_json) => {
+                trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: blinded_credential_secrets_correctness_proof: {:?}",
+                       blinded_credential_secrets_correctness_proof_json);
+                unsafe {
+                    *blinded_credential_secrets_correctness_proof_json_p = blinded_credential_secrets_correctness_proof_json.into_raw();
+                    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: blinded_credential_secrets_correctness_proof_json_p: {:?}",
+                           *blinded_credential_secrets_correctness_proof_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidState
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_to_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns blinded credential secrets correctness proof json.
-///
-/// Note: Blinded credential secrets correctness proof instance deallocation must be performed
-/// by calling indy_crypto_cl_blinded_credential_secrets_correctness_proof_free.
-///
-/// # Arguments
-/// * `blinded_credential_secrets_correctness_proof_json` - Reference that contains blinded credential secrets correctness proof json.
-/// * `blinded_credential_secrets_correctness_proof_p` - Reference that will contain blinded credential secret correctness proof instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json(blinded_credential_secrets_correctness_proof_json: *const c_char,
-                                                                                    blinded_credential_secrets_correctness_proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: >>> blinded_credential_secrets_correctness_proof_json: {:?},\
-     blinded_credential_secrets_correctness_proof_p: {:?}", blinded_credential_secrets_correctness_proof_json, blinded_credential_secrets_correctness_proof_p);
-
-    check_useful_c_str!(blinded_credential_secrets_correctness_proof_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(blinded_credential_secrets_correctness_proof_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: entity: blinded_credential_secrets_correctness_proof_json: {:?}",
-           blinded_credential_secrets_correctness_proof_json);
-
-    let res = match serde_json::from_str::<BlindedCredentialSecretsCorrectnessProof>(&blinded_credential_secrets_correctness_proof_json) {
-        Ok(blinded_credential_secrets_correctness_proof) => {
-            trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: blinded_credential_secrets_correctness_proof: {:?}",
-                   blinded_credential_secrets_correctness_proof);
-            unsafe {
-                *blinded_credential_secrets_correctness_proof_p = Box::into_raw(Box::new(blinded_credential_secrets_correctness_proof)) as *const c_void;
-                trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: *blinded_credential_secrets_correctness_proof_p: {:?}",
-                       *blinded_credential_secrets_correctness_proof_p);
+ffi_fn! {
+    /// Creates and returns blinded credential secrets correctness proof json.
+    ///
+    /// Note: Blinded credential secrets correctness proof instance deallocation must be performed
+    /// by calling indy_crypto_cl_blinded_credential_secrets_correctness_proof_free.
+    ///
+    /// # Arguments
+    /// * `blinded_credential_secrets_correctness_proof_json` - Reference that contains blinded credential secrets correctness proof json.
+    /// * `blinded_credential_secrets_correctness_proof_p` - Reference that will contain blinded credential secret correctness proof instance pointer.
+    pub fn indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json(blinded_credential_secrets_correctness_proof_json: *const c_char,
+                                                                                 blinded_credential_secrets_correctness_proof_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: >>> blinded_credential_secrets_correctness_proof_json: {:?},\
+         blinded_credential_secrets_correctness_proof_p: {:?}", blinded_credential_secrets_correctness_proof_json, blinded_credential_secrets_correctness_proof_p);
+
+        check_useful_c_str!(blinded_credential_secrets_correctness_proof_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_credential_secrets_correctness_proof_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: entity: blinded_credential_secrets_correctness_proof_json: {:?}",
+               blinded_credential_secrets_correctness_proof_json);
+
+        let res = match serde_json::from_str::<BlindedCredentialSecretsCorrectnessProof>(&blinded_credential_secrets_correctness_proof_json) {
+            Ok(blinded_credential_secrets_correctness_proof) => {
+                trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: blinded_credential_secrets_correctness_proof: {:?}",
+                       blinded_credential_secrets_correctness_proof);
+                unsafe {
+                    *blinded_credential_secrets_correctness_proof_p = register_c_reference!(blinded_credential_secrets_correctness_proof);
+                    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: *blinded_credential_secrets_correctness_proof_p: {:?}",
+                           *blinded_credential_secrets_correctness_proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidStructure
-    };
+            Err(_) => ErrorCode::CommonInvalidStructure
+        };
 
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_from_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates blinded credential secrets correctness proof instance.
-///
-/// # Arguments
-/// * `blinded_credential_secrets_correctness_proof` - Reference that contains blinded credential secrets correctness proof instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_blinded_credential_secrets_correctness_proof_free(blinded_credential_secrets_correctness_proof: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_free: >>> blinded_credential_secrets_correctness_proof: {:?}",
-           blinded_credential_secrets_correctness_proof);
+ffi_fn! {
+    /// Deallocates blinded credential secrets correctness proof instance.
+    ///
+    /// # Arguments
+    /// * `blinded_credential_secrets_correctness_proof` - Reference that contains blinded credential secrets correctness proof instance pointer.
+    pub fn indy_crypto_cl_blinded_credential_secrets_correctness_proof_free(blinded_credential_secrets_correctness_proof: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_free: >>> blinded_credential_secrets_correctness_proof: {:?}",
+               blinded_credential_secrets_correctness_proof);
 
-    check_useful_c_ptr!(blinded_credential_secrets_correctness_proof, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(blinded_credential_secrets_correctness_proof, ErrorCode::CommonInvalidParam1);
 
-    let blinded_credential_secrets_correctness_proof = unsafe { let _ = Box::from_raw(blinded_credential_secrets_correctness_proof as *mut BlindedCredentialSecretsCorrectnessProof); };
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_free: entity: blinded_credential_secrets_correctness_proof: {:?}", blinded_credential_secrets_correctness_proof);
+        let res = free_c_reference!(blinded_credential_secrets_correctness_proof, BlindedCredentialSecretsCorrectnessProof, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
-
-    trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_blinded_credential_secrets_correctness_proof_free: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Updates the credential signature by a credential secrets blinding factors.
-///
-/// # Arguments
-/// * `credential_signature` - Credential signature instance pointer generated by Issuer.
-/// * `credential_values` - Credential values instance pointer.
-/// * `signature_correctness_proof` - Credential signature correctness proof instance pointer.
-/// * `credential_secrets_blinding_factors` - Credential secrets blinding factors instance pointer.
-/// * `credential_pub_key` - Credential public key instance pointer.
-/// * `nonce` -  Nonce instance pointer was used by Issuer for the creation of signature_correctness_proof.
-/// * `rev_key_pub` - (Optional) Revocation registry public key  instance pointer.
-/// * `rev_reg` - (Optional) Revocation registry  instance pointer.
-/// * `witness` - (Optional) Witness instance pointer.
-#[no_mangle]
-#[allow(unused_variables)]
-pub extern fn indy_crypto_cl_prover_process_credential_signature(credential_signature: *const c_void,
-                                                                 credential_values: *const c_void,
-                                                                 signature_correctness_proof: *const c_void,
-                                                                 credential_secrets_blinding_factors: *const c_void,
-                                                                 credential_pub_key: *const c_void,
-                                                                 credential_issuance_nonce: *const c_void,
-                                                                 rev_key_pub: *const c_void,
-                                                                 rev_reg: *const c_void,
-                                                                 witness: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}\n\
-                                                                    signature_correctness_proof: {:?}\n\
-                                                                    credential_secrets_blinding_factors: {:?}\n\
-                                                                    credential_pub_key: {:?}\n\
-                                                                    credential_issuance_nonce: {:?}\n\
-                                                                    rev_key_pub: {:?}\n\
-                                                                    rev_reg {:?}\n\
-                                                                    witness {:?}",
-           credential_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_pub_key, credential_issuance_nonce, rev_key_pub, rev_reg, witness);
-
-    check_useful_mut_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(signature_correctness_proof, SignatureCorrectnessProof, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(credential_secrets_blinding_factors, CredentialSecretsBlindingFactors, ErrorCode::CommonInvalidParam4);
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam5);
-    check_useful_c_reference!(credential_issuance_nonce, Nonce, ErrorCode::CommonInvalidParam6);
-    check_useful_opt_c_reference!(rev_key_pub, RevocationKeyPublic);
-    check_useful_opt_c_reference!(rev_reg, RevocationRegistry);
-    check_useful_opt_c_reference!(witness, Witness);
-
-    trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}\n\
-                                                                    credential_values: {:?}\n\
-                                                                    signature_correctness_proof: {:?}\n\
-                                                                    credential_secrets_blinding_factors: {:?}\n\
-                                                                    credential_pub_key: {:?}\n\
-                                                                    credential_issuance_nonce: {:?}\n\
-                                                                    rev_key_pub: {:?}\n\
-                                                                    rev_reg {:?}, witness {:?}",
-                                                                    credential_signature,
-                                                                    credential_values,
-                                                                    signature_correctness_proof,
-                                                                    credential_secrets_blinding_factors,
-                                                                    credential_pub_key,
-                                                                    credential_issuance_nonce,
-                                                                    rev_key_pub,
-                                                                    rev_reg,
-                                                                    witness);
-
-    let res = match Prover::process_credential_signature(credential_signature,
-                                                         credential_values,
-                                                         signature_correctness_proof,
-                                                         credential_secrets_blinding_factors,
-                                                         credential_pub_key,
-                                                         credential_issuance_nonce,
-                                                         rev_key_pub,
-                                                         rev_reg,
-                                                         witness) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_prover_process_credential_signature: <<< res: {:?}", res);
-    ErrorCode::Success
+ffi_fn! {
+    /// Updates the credential signature by a credential secrets blinding factors.
+    ///
+    /// # Arguments
+    /// * `credential_signature` - Credential signature instance pointer generated by Issuer.
+    /// * `credential_values` - Credential values instance pointer.
+    /// * `signature_correctness_proof` - Credential signature correctness proof instance pointer.
+    /// * `credential_secrets_blinding_factors` - Credential secrets blinding factors instance pointer.
+    /// * `credential_pub_key` - Credential public key instance pointer.
+    /// * `nonce` -  Nonce instance pointer was used by Issuer for the creation of signature_correctness_proof.
+    /// * `rev_key_pub` - (Optional) Revocation registry public key  instance pointer.
+    /// * `rev_reg` - (Optional) Revocation registry  instance pointer.
+    /// * `witness` - (Optional) Witness instance pointer.
+    #[allow(unused_variables)]
+    pub fn indy_crypto_cl_prover_process_credential_signature(credential_signature: *const c_void,
+                                                              credential_values: *const c_void,
+                                                              signature_correctness_proof: *const c_void,
+                                                              credential_secrets_blinding_factors: *const c_void,
+                                                              credential_pub_key: *const c_void,
+                                                              credential_issuance_nonce: *const c_void,
+                                                              rev_key_pub: *const c_void,
+                                                              rev_reg: *const c_void,
+                                                              witness: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}\n\
+                                                                        signature_correctness_proof: {:?}\n\
+                                                                        credential_secrets_blinding_factors: {:?}\n\
+                                                                        credential_pub_key: {:?}\n\
+                                                                        credential_issuance_nonce: {:?}\n\
+                                                                        rev_key_pub: {:?}\n\
+                                                                        rev_reg {:?}\n\
+                                                                        witness {:?}",
+               credential_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_pub_key, credential_issuance_nonce, rev_key_pub, rev_reg, witness);
+
+        check_useful_mut_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(signature_correctness_proof, SignatureCorrectnessProof, ErrorCode::CommonInvalidParam3);
+        check_useful_c_reference!(credential_secrets_blinding_factors, CredentialSecretsBlindingFactors, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam5);
+        check_useful_c_reference!(credential_issuance_nonce, Nonce, ErrorCode::CommonInvalidParam6);
+        check_useful_opt_c_reference!(rev_key_pub, RevocationKeyPublic, ErrorCode::CommonInvalidParam7);
+        check_useful_opt_c_reference!(rev_reg, RevocationRegistry, ErrorCode::CommonInvalidParam8);
+        check_useful_opt_c_reference!(witness, Witness, ErrorCode::CommonInvalidParam9);
+
+        trace!("indy_crypto_cl_prover_process_credential_signature: >>> credential_signature: {:?}\n\
+                                                                        credential_values: {:?}\n\
+                                                                        signature_correctness_proof: {:?}\n\
+                                                                        credential_secrets_blinding_factors: {:?}\n\
+                                                                        credential_pub_key: {:?}\n\
+                                                                        credential_issuance_nonce: {:?}\n\
+                                                                        rev_key_pub: {:?}\n\
+                                                                        rev_reg {:?}, witness {:?}",
+                                                                        credential_signature,
+                                                                        credential_values,
+                                                                        signature_correctness_proof,
+                                                                        credential_secrets_blinding_factors,
+                                                                        credential_pub_key,
+                                                                        credential_issuance_nonce,
+                                                                        rev_key_pub,
+                                                                        rev_reg,
+                                                                        witness);
+
+        let res = match Prover::process_credential_signature(credential_signature,
+                                                             credential_values,
+                                                             signature_correctness_proof,
+                                                             credential_secrets_blinding_factors,
+                                                             credential_pub_key,
+                                                             credential_issuance_nonce,
+                                                             rev_key_pub,
+                                                             rev_reg,
+                                                             witness) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_prover_process_credential_signature: <<< res: {:?}", res);
+        ErrorCode::Success
+    }
 }
 
-#[no_mangle]
-#[allow(unused_variables)]
-pub extern fn indy_crypto_cl_prover_get_credential_revocation_index(credential_signature: *const c_void,
-                                                                    cred_rev_indx: *mut u32) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_get_credential_revocation_index: >>> credential_signature: {:?}, cred_rev_indx: {:?}",
-           credential_signature, cred_rev_indx);
-
-    check_useful_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_cl_prover_get_credential_revocation_index: >>> credential_signature: {:?}", credential_signature);
-
-    let res = match credential_signature.extract_index() {
-        Some(index) => {
-            trace!("indy_crypto_cl_prover_get_credential_revocation_index: index: {:?}", index);
-            unsafe {
-                *cred_rev_indx = index;
+ffi_fn! {
+    #[allow(unused_variables)]
+    pub fn indy_crypto_cl_prover_get_credential_revocation_index(credential_signature: *const c_void,
+                                                                 cred_rev_indx: *mut u32) -> ErrorCode {
+        trace!("indy_crypto_cl_prover_get_credential_revocation_index: >>> credential_signature: {:?}, cred_rev_indx: {:?}",
+               credential_signature, cred_rev_indx);
+
+        check_useful_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_prover_get_credential_revocation_index: >>> credential_signature: {:?}", credential_signature);
+
+        let res = match credential_signature.extract_index() {
+            Some(index) => {
+                trace!("indy_crypto_cl_prover_get_credential_revocation_index: index: {:?}", index);
+                unsafe {
+                    *cred_rev_indx = index;
+                }
+                trace!("indy_crypto_cl_prover_get_credential_revocation_index: *cred_rev_indx: {:?}", cred_rev_indx);
+                ErrorCode::Success
             }
-            trace!("indy_crypto_cl_prover_get_credential_revocation_index: *cred_rev_indx: {:?}", cred_rev_indx);
-            ErrorCode::Success
-        }
-        None => ErrorCode::CommonInvalidState
-    };
-
-    trace!("indy_crypto_cl_prover_get_credential_revocation_index: <<< res: {:?}", res);
-    ErrorCode::Success
+            None => ErrorCode::CommonInvalidState
+        };
+
+        trace!("indy_crypto_cl_prover_get_credential_revocation_index: <<< res: {:?}", res);
+        ErrorCode::Success
+    }
 }
 
-/// Creates and returns proof builder.
-///
-/// The purpose of proof builder is building of proof entity according to the given request .
-///
-/// Note that proof builder deallocation must be performed by
-/// calling indy_crypto_cl_proof_builder_finalize.
-///
-/// # Arguments
-/// * `proof_builder_p` - Reference that will contain proof builder instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_prover_new_proof_builder(proof_builder_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_prover_new_proof_builder: >>> {:?}", proof_builder_p);
-
-    check_useful_c_ptr!(proof_builder_p, ErrorCode::CommonInvalidParam1);
-
-    let res = match Prover::new_proof_builder() {
-        Ok(proof_builder) => {
-            trace!("indy_crypto_cl_prover_new_proof_builder: proof_builder: {:?}", proof_builder);
-            unsafe {
-                *proof_builder_p = Box::into_raw(Box::new(proof_builder)) as *const c_void;
-                trace!("indy_crypto_cl_prover_new_proof_builder: *proof_builder_p: {:?}", *proof_builder_p);
+ffi_fn! {
+    /// Creates and returns proof builder.
+    ///
+    /// The purpose of proof builder is building of proof entity according to the given request .
+    ///
+    /// Note that proof builder deallocation must be performed by
+    /// calling indy_crypto_cl_proof_builder_finalize.
+    ///
+    /// # Arguments
+    /// * `proof_builder_p` - Reference that will contain proof builder instance pointer.
+    pub fn indy_crypto_cl_prover_new_proof_builder(proof_builder_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_prover_new_proof_builder: >>> {:?}", proof_builder_p);
+
+        check_useful_c_ptr!(proof_builder_p, ErrorCode::CommonInvalidParam1);
+
+        let res = match Prover::new_proof_builder() {
+            Ok(proof_builder) => {
+                trace!("indy_crypto_cl_prover_new_proof_builder: proof_builder: {:?}", proof_builder);
+                unsafe {
+                    *proof_builder_p = register_c_reference!(proof_builder);
+                    trace!("indy_crypto_cl_prover_new_proof_builder: *proof_builder_p: {:?}", *proof_builder_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_prover_new_proof_builder: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_prover_new_proof_builder: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Add a sub proof request to the proof builder
-///
-/// # Arguments
-/// * `proof_builder` - Reference that contain proof builder instance pointer.
-/// * `sub_proof_request` - Reference that contain sub proof request instance pointer.
-/// * `credential_schema` - Reference that contains credential schema instance pointer.
-/// * `non_credential_schema` - Reference that contains non credential schema instance pointer.
-/// * `credential_signature` - Reference that contains the credential signature pointer.
-/// * `credential_values` - Reference that contains credential values instance pointer.
-/// * `credential_pub_key` - Reference that contains credential public key instance pointer.
-/// * `rev_reg` - (Optional) Reference that will contain revocation registry public instance pointer.
-/// * `witness` - (Optional) Reference that will contain witness instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder: *const c_void,
-                                                                 sub_proof_request: *const c_void,
-                                                                 credential_schema: *const c_void,
-                                                                 non_credential_schema: *const c_void,
-                                                                 credential_signature: *const c_void,
-                                                                 credential_values: *const c_void,
-                                                                 credential_pub_key: *const c_void,
-                                                                 rev_reg: *const c_void,
-                                                                 witness: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: >>> proof_builder: {:?}, \
-                                                                    sub_proof_request: {:?}, \
-                                                                    credential_schema: {:?}, \
-                                                                    non_credential_schema: {:?}, \
-                                                                    credential_signature: {:?}, \
-                                                                    credential_values: {:?}, \
-                                                                    credential_pub_key: {:?}, \
-                                                                    rev_reg: {:?}, \
-                                                                    witness: {:?}",
-                    proof_builder,
-                    sub_proof_request,
-                    credential_schema,
-                    non_credential_schema,
-                    credential_signature,
-                    credential_values,
-                    credential_pub_key,
-                    rev_reg,
-                    witness);
-
-    check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
-    check_useful_c_reference!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
-    check_useful_c_reference!(non_credential_schema, NonCredentialSchema, ErrorCode::CommonInvalidParam4);
-    check_useful_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam5);
-    check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam6);
-    check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam7);
-    check_useful_opt_c_reference!(rev_reg, RevocationRegistry);
-    check_useful_opt_c_reference!(witness, Witness);
-
-    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: entities: proof_builder: {:?}, \
-                                                                          sub_proof_request: {:?}, \
-                                                                          credential_schema: {:?}, \
-                                                                          non_credential_schema: {:?}, \
-                                                                          credential_signature: {:?}, \
-                                                                          credential_values: {:?}, \
-                                                                          credential_pub_key: {:?}, \
-                                                                          rev_reg: {:?}, \
-                                                                          witness: {:?}",
-           proof_builder,
-           sub_proof_request,
-           credential_schema,
-           non_credential_schema,
-           credential_signature,
-           credential_values,
-           credential_pub_key,
-           rev_reg,
-           witness);
-
-    let res = match proof_builder.add_sub_proof_request(sub_proof_request,
-                                                        credential_schema,
-                                                        non_credential_schema,
-                                                        credential_signature,
-                                                        credential_values,
-                                                        credential_pub_key,
-                                                        rev_reg,
-                                                        witness) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: <<< res: {:?}", res);
-    ErrorCode::Success
+ffi_fn! {
+    /// Add a sub proof request to the proof builder
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Reference that contain proof builder instance pointer.
+    /// * `sub_proof_request` - Reference that contain sub proof request instance pointer.
+    /// * `credential_schema` - Reference that contains credential schema instance pointer.
+    /// * `non_credential_schema` - Reference that contains non credential schema instance pointer.
+    /// * `credential_signature` - Reference that contains the credential signature pointer.
+    /// * `credential_values` - Reference that contains credential values instance pointer.
+    /// * `credential_pub_key` - Reference that contains credential public key instance pointer.
+    /// * `rev_reg` - (Optional) Reference that will contain revocation registry public instance pointer.
+    /// * `witness` - (Optional) Reference that will contain witness instance pointer.
+    pub fn indy_crypto_cl_proof_builder_add_sub_proof_request(proof_builder: *const c_void,
+                                                              sub_proof_request: *const c_void,
+                                                              credential_schema: *const c_void,
+                                                              non_credential_schema: *const c_void,
+                                                              credential_signature: *const c_void,
+                                                              credential_values: *const c_void,
+                                                              credential_pub_key: *const c_void,
+                                                              rev_reg: *const c_void,
+                                                              witness: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: >>> proof_builder: {:?}, \
+                                                                        sub_proof_request: {:?}, \
+                                                                        credential_schema: {:?}, \
+                                                                        non_credential_schema: {:?}, \
+                                                                        credential_signature: {:?}, \
+                                                                        credential_values: {:?}, \
+                                                                        credential_pub_key: {:?}, \
+                                                                        rev_reg: {:?}, \
+                                                                        witness: {:?}",
+                        proof_builder,
+                        sub_proof_request,
+                        credential_schema,
+                        non_credential_schema,
+                        credential_signature,
+                        credential_values,
+                        credential_pub_key,
+                        rev_reg,
+                        witness);
+
+        check_useful_mut_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(sub_proof_request, SubProofRequest, ErrorCode::CommonInvalidParam2);
+        check_useful_c_reference!(credential_schema, CredentialSchema, ErrorCode::CommonInvalidParam3);
+        check_useful_c_reference!(non_credential_schema, NonCredentialSchema, ErrorCode::CommonInvalidParam4);
+        check_useful_c_reference!(credential_signature, CredentialSignature, ErrorCode::CommonInvalidParam5);
+        check_useful_c_reference!(credential_values, CredentialValues, ErrorCode::CommonInvalidParam6);
+        check_useful_c_reference!(credential_pub_key, CredentialPublicKey, ErrorCode::CommonInvalidParam7);
+        check_useful_opt_c_reference!(rev_reg, RevocationRegistry, ErrorCode::CommonInvalidParam8);
+        check_useful_opt_c_reference!(witness, Witness, ErrorCode::CommonInvalidParam9);
+
+        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: entities: proof_builder: {:?}, \
+                                                                              sub_proof_request: {:?}, \
+                                                                              credential_schema: {:?}, \
+                                                                              non_credential_schema: {:?}, \
+                                                                              credential_signature: {:?}, \
+                                                                              credential_values: {:?}, \
+                                                                              credential_pub_key: {:?}, \
+                                                                              rev_reg: {:?}, \
+                                                                              witness: {:?}",
+               proof_builder,
+               sub_proof_request,
+               credential_schema,
+               non_credential_schema,
+               credential_signature,
+               credential_values,
+               credential_pub_key,
+               rev_reg,
+               witness);
+
+        let res = match proof_builder.add_sub_proof_request(sub_proof_request,
+                                                            credential_schema,
+                                                            non_credential_schema,
+                                                            credential_signature,
+                                                            credential_values,
+                                                            credential_pub_key,
+                                                            rev_reg,
+                                                            witness) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_cl_proof_builder_add_sub_proof_request: <<< res: {:?}", res);
+        ErrorCode::Success
+    }
 }
 
 
-/// Finalize proof.
-///
-/// Note that proof deallocation must be performed by
-/// calling indy_crypto_cl_proof_free.
-///
-/// # Arguments
-/// * `proof_builder` - Reference that contain proof builder instance pointer.
-/// * `nonce` - Reference that contain nonce instance pointer.
-/// * `proof_p` - Reference that will contain proof instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_proof_builder_finalize(proof_builder: *const c_void,
-                                                    nonce: *const c_void,
-                                                    proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_builder_finalize: >>> proof_builder: {:?}, nonce: {:?}, proof_p: {:?}",
-           proof_builder, nonce, proof_p);
-
-    check_useful_c_ptr!(proof_builder, ErrorCode::CommonInvalidParam1);
-    check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam2);
-    check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam3);
-
-    let proof_builder = unsafe { Box::from_raw(proof_builder as *mut ProofBuilder) };
-
-    trace!("indy_crypto_cl_proof_builder_finalize: entities: proof_builder: {:?}, nonce: {:?}",
-           proof_builder, nonce);
-
-    let res = match proof_builder.finalize(nonce) {
-        Ok(proof) => {
-            trace!("indy_crypto_cl_proof_builder_finalize: proof: {:?}", proof);
-            unsafe {
-                *proof_p = Box::into_raw(Box::new(proof)) as *const c_void;
-                trace!("indy_crypto_cl_proof_builder_finalize: *proof_p: {:?}", *proof_p);
+ffi_fn! {
+    /// Finalize proof.
+    ///
+    /// Note that proof deallocation must be performed by
+    /// calling indy_crypto_cl_proof_free.
+    ///
+    /// # Arguments
+    /// * `proof_builder` - Reference that contain proof builder instance pointer.
+    /// * `nonce` - Reference that contain nonce instance pointer.
+    /// * `proof_p` - Reference that will contain proof instance pointer.
+    pub fn indy_crypto_cl_proof_builder_finalize(proof_builder: *const c_void,
+                                                 nonce: *const c_void,
+                                                 proof_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_proof_builder_finalize: >>> proof_builder: {:?}, nonce: {:?}, proof_p: {:?}",
+               proof_builder, nonce, proof_p);
+
+        check_useful_c_ptr!(proof_builder, ErrorCode::CommonInvalidParam1);
+        check_useful_c_reference!(nonce, Nonce, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam3);
+
+        take_useful_c_reference!(proof_builder, ProofBuilder, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_cl_proof_builder_finalize: entities: proof_builder: {:?}, nonce: {:?}",
+               proof_builder, nonce);
+
+        let res = match proof_builder.finalize(nonce) {
+            Ok(proof) => {
+                trace!("indy_crypto_cl_proof_builder_finalize: proof: {:?}", proof);
+                unsafe {
+                    *proof_p = register_c_reference!(proof);
+                    trace!("indy_crypto_cl_proof_builder_finalize: *proof_p: {:?}", *proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(err) => err.to_error_code()
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_proof_builder_finalize: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_builder_finalize: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Returns json representation of proof.
-///
-/// # Arguments
-/// * `proof` - Reference that contains proof instance pointer.
-/// * `proof_json_p` - Reference that will contain proof json.
-#[no_mangle]
-pub extern fn indy_crypto_cl_proof_to_json(proof: *const c_void,
-                                           proof_json_p: *mut *const c_char) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_to_json: >>> proof: {:?}, proof_json_p: {:?}", proof, proof_json_p);
-
-    check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(proof_json_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_proof_to_json: entity >>> proof: {:?}", proof);
-
-    let res = match serde_json::to_string(proof) {
-        Ok(proof_json) => {
-            trace!("indy_crypto_cl_proof_to_json: proof_json: {:?}", proof_json);
-            unsafe {
-                let proof_json = CTypesUtils::string_to_cstring(proof_json);
-                *proof_json_p = proof_json.into_raw();
-                trace!("indy_crypto_cl_proof_to_json: proof_json_p: {:?}", *proof_json_p);
+ffi_fn! {
+    /// Returns json representation of proof.
+    ///
+    /// # Arguments
+    /// * `proof` - Reference that contains proof instance pointer.
+    /// * `proof_json_p` - Reference that will contain proof json.
+    pub fn indy_crypto_cl_proof_to_json(proof: *const c_void,
+                                        proof_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_cl_proof_to_json: >>> proof: {:?}, proof_json_p: {:?}", proof, proof_json_p);
+
+        check_useful_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(proof_json_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_proof_to_json: entity >>> proof: {:?}", proof);
+
+        let res = match serde_json::to_string(proof)
+            .map_err(|err| IndyCryptoError::InvalidState(err.to_string()))
+            .and_then(CTypesUtils::string_to_cstring) {
+            Ok(proof_json) => {
+                trace!("indy_crypto_cl_proof_to_json: proof_json: {:?}", proof_json);
+                unsafe {
+                    *proof_json_p = proof_json.into_raw();
+                    trace!("indy_crypto_cl_proof_to_json: proof_json_p: {:?}", *proof_json_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidState
-    };
+            Err(err) => err.into()
+        };
 
-    trace!("indy_crypto_cl_proof_to_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_to_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Creates and returns proof json.
-///
-/// Note: Proof instance deallocation must be performed by calling indy_crypto_cl_proof_free.
-///
-/// # Arguments
-/// * `proof_json` - Reference that contains proof json.
-/// * `proof_p` - Reference that will contain proof instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_proof_from_json(proof_json: *const c_char,
-                                             proof_p: *mut *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_from_json: >>> proof_json: {:?}, proof_p: {:?}", proof_json, proof_p);
-
-    check_useful_c_str!(proof_json, ErrorCode::CommonInvalidParam1);
-    check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam2);
-
-    trace!("indy_crypto_cl_proof_from_json: entity: proof_json: {:?}", proof_json);
-
-    let res = match serde_json::from_str::<Proof>(&proof_json) {
-        Ok(proof) => {
-            trace!("indy_crypto_cl_proof_from_json: proof: {:?}", proof);
-            unsafe {
-                *proof_p = Box::into_raw(Box::new(proof)) as *const c_void;
-                trace!("indy_crypto_cl_proof_from_json: *proof_p: {:?}", *proof_p);
+ffi_fn! {
+    /// Creates and returns proof json.
+    ///
+    /// Note: Proof instance deallocation must be performed by calling indy_crypto_cl_proof_free.
+    ///
+    /// # Arguments
+    /// * `proof_json` - Reference that contains proof json.
+    /// * `proof_p` - Reference that will contain proof instance pointer.
+    pub fn indy_crypto_cl_proof_from_json(proof_json: *const c_char,
+                                          proof_p: *mut *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_proof_from_json: >>> proof_json: {:?}, proof_p: {:?}", proof_json, proof_p);
+
+        check_useful_c_str!(proof_json, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(proof_p, ErrorCode::CommonInvalidParam2);
+
+        trace!("indy_crypto_cl_proof_from_json: entity: proof_json: {:?}", proof_json);
+
+        let res = match serde_json::from_str::<Proof>(&proof_json) {
+            Ok(proof) => {
+                trace!("indy_crypto_cl_proof_from_json: proof: {:?}", proof);
+                unsafe {
+                    *proof_p = register_c_reference!(proof);
+                    trace!("indy_crypto_cl_proof_from_json: *proof_p: {:?}", *proof_p);
+                }
+                ErrorCode::Success
             }
-            ErrorCode::Success
-        }
-        Err(_) => ErrorCode::CommonInvalidStructure
-    };
+            Err(_) => ErrorCode::CommonInvalidStructure
+        };
 
-    trace!("indy_crypto_cl_proof_from_json: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_from_json: <<< res: {:?}", res);
+        res
+    }
 }
 
-/// Deallocates proof instance.
-///
-/// # Arguments
-/// * `proof` - Reference that contains proof instance pointer.
-#[no_mangle]
-pub extern fn indy_crypto_cl_proof_free(proof: *const c_void) -> ErrorCode {
-    trace!("indy_crypto_cl_proof_free: >>> proof: {:?}", proof);
-
-    check_useful_c_ptr!(proof, ErrorCode::CommonInvalidParam1);
+ffi_fn! {
+    /// Deallocates proof instance.
+    ///
+    /// # Arguments
+    /// * `proof` - Reference that contains proof instance pointer.
+    pub fn indy_crypto_cl_proof_free(proof: *const c_void) -> ErrorCode {
+        trace!("indy_crypto_cl_proof_free: >>> proof: {:?}", proof);
 
-    let proof = unsafe { let _ = Box::from_raw(proof as *mut Proof); };
-    trace!("indy_crypto_cl_proof_free: entity: proof: {:?}", proof);
+        check_useful_c_ptr!(proof, ErrorCode::CommonInvalidParam1);
 
-    let res = ErrorCode::Success;
+        let res = free_c_reference!(proof, Proof, ErrorCode::CommonInvalidParam1);
 
-    trace!("indy_crypto_cl_proof_free: <<< res: {:?}", res);
-    res
+        trace!("indy_crypto_cl_proof_free: <<< res: {:?}", res);
+        res
+    }
 }
 
 
@@ -977,7 +988,7 @@ mod tests {
                                                                                         credential_issuance_nonce,
                                                                                         credential_values,
                                                                                         credential_pub_key,
-                                                                                        credential_pub_key);
+                                                                                        credential_priv_key);
         _process_credential_signature(credential_signature,
                                       signature_correctness_proof,
                                       credential_secrets_blinding_factors,
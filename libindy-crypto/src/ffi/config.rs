@@ -0,0 +1,116 @@
+use crate::errors::ErrorCode;
+use crate::ffi::ctypes::CTypesUtils;
+use crate::utils::config::{set_runtime_config, RuntimeConfig};
+
+use libc::c_char;
+
+ffi_fn! {
+    /// Replaces runtime config of the library, calls in progress finish with the previous one.
+    ///
+    /// Missing keys take default values, so every call sets the whole config. Unknown keys are
+    /// logged as warnings and ignored. Invalid values are reported as CommonInvalidStructure,
+    /// details are available by calling indy_crypto_get_current_error.
+    ///
+    /// # Arguments
+    /// * `config_json` - Runtime config json
+    ///
+    /// Runtime config json:
+    /// ```text
+    /// {
+    ///     "verification_threads": Optional<int>, // threads of batch verification, default 1
+    ///     "strict_validation": Optional<bool>, // reject identity ver keys and signatures, default false
+    ///     "max_signers": Optional<int>, // max signatures, ver keys or batch items of one call, default 0 - unlimited
+    ///     "max_message_size": Optional<int> // max size of message to sign or verify, default 0 - unlimited
+    /// }
+    /// ```
+    pub fn indy_crypto_set_runtime_config(config_json: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_set_runtime_config: >>> config_json: {:?}", config_json);
+
+        check_useful_c_str!(config_json, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_set_runtime_config: config_json: {:?}", config_json);
+
+        let res = match RuntimeConfig::from_json(&config_json).and_then(set_runtime_config) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_set_runtime_config: <<< res: {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ffi::bls::{indy_crypto_bls_multi_signature_new, indy_crypto_bls_multi_signature_free,
+                          indy_crypto_bls_sign, indy_crypto_bls_sign_key_new, indy_crypto_bls_sign_key_free,
+                          indy_crypto_bls_signature_free};
+    use crate::ffi::error::indy_crypto_get_current_error;
+    use crate::utils::config::{runtime_config, RUNTIME_CONFIG_TEST_LOCK};
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_void;
+    use std::ptr;
+
+    // Limit is above the signers of other tests running concurrently
+    const MAX_SIGNERS: usize = 1000;
+
+    fn _current_error_message() -> String {
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        let error_json = unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() };
+        let error: serde_json::Value = serde_json::from_str(error_json).unwrap();
+        error["message"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn indy_crypto_set_runtime_config_works() {
+        let _lock = RUNTIME_CONFIG_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature));
+        let signatures = vec![signature; MAX_SIGNERS + 1];
+
+        let config_json = CString::new(format!(r#"{{"max_signers": {}, "unknown": true}}"#, MAX_SIGNERS)).unwrap();
+        assert_eq!(ErrorCode::Success, indy_crypto_set_runtime_config(config_json.as_ptr()));
+        assert_eq!(MAX_SIGNERS, runtime_config().max_signers);
+
+        let mut multi_sig: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), signatures.len(), &mut multi_sig);
+        assert_eq!(ErrorCode::CommonInvalidStructure, err_code);
+        assert!(_current_error_message().contains("Too many signers: expected at most 1000, actual 1001"));
+
+        let err_code = indy_crypto_bls_multi_signature_new(signatures.as_ptr(), MAX_SIGNERS, &mut multi_sig);
+        assert_eq!(ErrorCode::Success, err_code);
+
+        let default_json = CString::new("{}").unwrap();
+        assert_eq!(ErrorCode::Success, indy_crypto_set_runtime_config(default_json.as_ptr()));
+        assert_eq!(RuntimeConfig::default(), runtime_config());
+
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_multi_signature_free(multi_sig));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_signature_free(signature));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+    }
+
+    #[test]
+    fn indy_crypto_set_runtime_config_works_for_invalid_config() {
+        let _lock = RUNTIME_CONFIG_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let config = runtime_config();
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_set_runtime_config(ptr::null()));
+
+        let config_json = CString::new(r#"{"verification_threads": 0}"#).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure, indy_crypto_set_runtime_config(config_json.as_ptr()));
+        assert!(_current_error_message().contains("`verification_threads` must be positive"));
+
+        let config_json = CString::new(r#"{"max_message_size": "large"}"#).unwrap();
+        assert_eq!(ErrorCode::CommonInvalidStructure, indy_crypto_set_runtime_config(config_json.as_ptr()));
+        assert!(_current_error_message().contains("max_message_size"));
+
+        assert_eq!(config, runtime_config());
+    }
+}
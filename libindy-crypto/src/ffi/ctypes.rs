@@ -1,38 +1,204 @@
 use libc::c_char;
 
+use crate::errors::{set_current_error, ErrorCode, IndyCryptoError};
+use crate::ffi::handles::{self, Ref};
+
+use std::any::Any;
 use std::ffi::CStr;
-use std::str::Utf8Error;
 use std::ffi::CString;
+use std::mem;
+use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
 
 pub struct CTypesUtils {}
 
+/// Upper bound of handle array len, longer arrays can't be addressed as a slice.
+pub(crate) const MAX_HANDLE_ARRAY_LEN: usize = isize::MAX as usize / mem::size_of::<*const c_void>();
+
+/// Reports invalid param error with the reason, remembers it as the current error and returns its code.
+pub(crate) fn invalid_param(err: ErrorCode, reason: String) -> ErrorCode {
+    IndyCryptoError::from_error_code(err, reason).into()
+}
+
+/// Result of FFI function that body panicked, see `catch_panic`.
+pub(crate) trait PanicResult {
+    fn from_panic(err: IndyCryptoError) -> Self;
+}
+
+impl PanicResult for ErrorCode {
+    fn from_panic(err: IndyCryptoError) -> ErrorCode {
+        err.into()
+    }
+}
+
+impl PanicResult for () {
+    fn from_panic(err: IndyCryptoError) {
+        set_current_error(&err);
+    }
+}
+
+/// Runs body of FFI function. Unwinding across `extern` function is undefined behavior, so a panic
+/// is remembered as the current error with the panic message and CommonInvalidState is returned.
+///
+/// Every `#[no_mangle]` function is defined by `ffi_fn!` macro that runs its whole body here.
+pub(crate) fn catch_panic<R: PanicResult, F: FnOnce() -> R>(body: F) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(res) => res,
+        Err(payload) => R::from_panic(IndyCryptoError::InvalidState(format!("Panic: {}", _panic_message(&payload))))
+    }
+}
+
+fn _panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
 impl CTypesUtils {
-    pub fn c_str_to_string(cstr: *const c_char) -> Result<Option<String>, Utf8Error> {
+    pub fn c_str_to_string(cstr: *const c_char) -> Result<Option<String>, IndyCryptoError> {
         if cstr.is_null() {
             return Ok(None);
         }
 
-        unsafe {
-            match CStr::from_ptr(cstr).to_str() {
-                Ok(str) => Ok(Some(str.to_string())),
-                Err(err) => Err(err)
-            }
+        let str = unsafe { CStr::from_ptr(cstr).to_str()? };
+        Ok(Some(str.to_string()))
+    }
+
+    pub fn string_to_cstring(s: String) -> Result<CString, IndyCryptoError> {
+        Ok(CString::new(s)?)
+    }
+
+    /// Converts array of handles to references to their values.
+    ///
+    /// Null `ptrs` and an item that is null, freed or of other type than `T` are reported as `err_ptrs`,
+    /// zero `len` or `len` above `max_len` as `err_len`. The error is remembered as the current error
+    /// and its code is returned, `name` is the array param name for the error message.
+    pub(crate) fn c_handle_array_to_vec<T: Any + Sync>(name: &str,
+                                                       ptrs: *const *const c_void,
+                                                       len: usize,
+                                                       max_len: usize,
+                                                       err_ptrs: ErrorCode,
+                                                       err_len: ErrorCode) -> Result<Vec<Ref<T>>, ErrorCode> {
+        if ptrs.is_null() {
+            return Err(invalid_param(err_ptrs, format!("`{}` is null", name)));
+        }
+
+        if len == 0 {
+            return Err(invalid_param(err_len, format!("`{}_len` is 0", name)));
+        }
+
+        let max_len = max_len.min(MAX_HANDLE_ARRAY_LEN);
+        if len > max_len {
+            return Err(invalid_param(err_len, format!("`{}_len` is too large: expected at most {}, actual {}", name, max_len, len)));
         }
+
+        unsafe { slice::from_raw_parts(ptrs, len) }
+            .iter()
+            .enumerate()
+            .map(|(index, ptr)| {
+                if ptr.is_null() {
+                    return Err(format!("Item {}: Handle is null", index));
+                }
+                handles::get::<T>(*ptr).map_err(|reason| format!("Item {}: {}", index, reason))
+            })
+            .collect::<Result<Vec<Ref<T>>, String>>()
+            .map_err(|reason| invalid_param(err_ptrs, reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{ErrorCode, ToErrorCode};
+    use std::ptr;
+
+    #[test]
+    fn string_to_cstring_works() {
+        let cstring = CTypesUtils::string_to_cstring("json".to_string()).unwrap();
+        assert_eq!("json", cstring.to_str().unwrap());
+    }
+
+    #[test]
+    fn string_to_cstring_works_for_interior_nul() {
+        let err = CTypesUtils::string_to_cstring("js\0on".to_string()).unwrap_err();
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
+    }
+
+    #[test]
+    fn c_str_to_string_works_for_invalid_utf8() {
+        let bytes = [0xC3u8, 0x28, 0x00];
+        let err = CTypesUtils::c_str_to_string(bytes.as_ptr() as *const c_char).unwrap_err();
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.to_error_code());
     }
 
-    pub fn string_to_cstring(s: String) -> CString {
-        CString::new(s).unwrap()
+    fn _handle_array(ptrs: *const *const c_void, len: usize, max_len: usize) -> Result<Vec<u32>, ErrorCode> {
+        CTypesUtils::c_handle_array_to_vec::<u32>("items", ptrs, len, max_len,
+                                                  ErrorCode::CommonInvalidParam1, ErrorCode::CommonInvalidParam2)
+            .map(|values| values.iter().map(|value| **value).collect())
+    }
+
+    #[test]
+    fn c_handle_array_to_vec_works() {
+        let handles = [handles::register(1u32).unwrap(), handles::register(2u32).unwrap()];
+
+        let values = _handle_array(handles.as_ptr(), handles.len(), MAX_HANDLE_ARRAY_LEN).unwrap();
+        assert_eq!(vec![1, 2], values);
+
+        let values = _handle_array(handles.as_ptr(), 1, 1).unwrap();
+        assert_eq!(vec![1], values);
+
+        for handle in handles.iter() {
+            handles::free::<u32>(*handle).unwrap();
+        }
+    }
+
+    #[test]
+    fn c_handle_array_to_vec_works_for_invalid_array() {
+        let handles = [handles::register(1u32).unwrap(), ptr::null(), handles::register("item").unwrap()];
+
+        assert_eq!(Err(ErrorCode::CommonInvalidParam1), _handle_array(ptr::null(), 1, MAX_HANDLE_ARRAY_LEN));
+        assert_eq!(Err(ErrorCode::CommonInvalidParam2), _handle_array(handles.as_ptr(), 0, MAX_HANDLE_ARRAY_LEN));
+        assert_eq!(Err(ErrorCode::CommonInvalidParam2), _handle_array(handles.as_ptr(), 2, 1));
+        assert_eq!(Err(ErrorCode::CommonInvalidParam2), _handle_array(handles.as_ptr(), usize::MAX, usize::MAX));
+        assert_eq!(Err(ErrorCode::CommonInvalidParam1), _handle_array(handles.as_ptr(), 2, MAX_HANDLE_ARRAY_LEN));
+        assert_eq!(Err(ErrorCode::CommonInvalidParam1), _handle_array(handles[2..].as_ptr(), 1, MAX_HANDLE_ARRAY_LEN));
+
+        handles::free::<u32>(handles[0]).unwrap();
+        assert_eq!(Err(ErrorCode::CommonInvalidParam1), _handle_array(handles.as_ptr(), 1, MAX_HANDLE_ARRAY_LEN));
+        handles::free::<&str>(handles[2]).unwrap();
+    }
+}
+
+macro_rules! catch_panic {
+    ($body:block) => {
+        crate::ffi::ctypes::catch_panic(|| $body)
+    }
+}
+
+/// Defines `#[no_mangle] pub extern "C"` function whose whole body is run by `catch_panic!`.
+macro_rules! ffi_fn {
+    ($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $res:ty)? $body:block) => {
+        $(#[$attr])*
+        #[no_mangle]
+        pub extern "C" fn $name($($arg: $ty),*) $(-> $res)? {
+            catch_panic!($body)
+        }
     }
 }
 
 macro_rules! check_useful_c_byte_array {
     ($ptr:ident, $len:expr, $err1:expr, $err2:expr) => {
         if $ptr.is_null() {
-            return $err1
+            return crate::ffi::ctypes::invalid_param($err1, format!("`{}` is null", stringify!($ptr)))
         }
 
         if $len <= 0 {
-            return $err2
+            return crate::ffi::ctypes::invalid_param($err2, format!("`{}` is 0", stringify!($len)))
         }
 
         let $ptr = unsafe { slice::from_raw_parts($ptr, $len) };
@@ -42,7 +208,7 @@ macro_rules! check_useful_c_byte_array {
 macro_rules! check_useful_opt_c_byte_array {
     ($ptr:ident, $len:expr, $err1:expr, $err2:expr) => {
         if !$ptr.is_null() && $len <= 0 {
-            return $err2
+            return crate::ffi::ctypes::invalid_param($err2, format!("`{}` is 0", stringify!($len)))
         }
 
         let $ptr = if $ptr.is_null() {
@@ -53,58 +219,112 @@ macro_rules! check_useful_opt_c_byte_array {
     }
 }
 
+// Message may be empty: null or any pointer with zero len is an empty message,
+// only null pointer with non zero len is an error
+macro_rules! check_useful_c_message {
+    ($ptr:ident, $len:expr, $err:expr) => {
+        if $ptr.is_null() && $len > 0 {
+            return crate::ffi::ctypes::invalid_param($err, format!("`{}` is null", stringify!($ptr)))
+        }
+
+        let $ptr: &[u8] = if $len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts($ptr, $len) }
+        };
+    }
+}
+
 macro_rules! check_useful_c_reference {
     ($ptr:ident, $type:ty, $err:expr) => {
         if $ptr.is_null() {
-            return $err
+            return crate::ffi::ctypes::invalid_param($err, format!("`{}` is null", stringify!($ptr)))
         }
 
-        let $ptr: &$type = unsafe { &*($ptr as *const $type) };
+        let $ptr = match crate::ffi::handles::get::<$type>($ptr) {
+            Ok(val) => val,
+            Err(reason) => return crate::ffi::ctypes::invalid_param($err, reason)
+        };
+        let $ptr: &$type = &$ptr;
     }
 }
 
 macro_rules! check_useful_mut_c_reference {
     ($ptr:ident, $type:ty, $err:expr) => {
         if $ptr.is_null() {
-            return $err
+            return crate::ffi::ctypes::invalid_param($err, format!("`{}` is null", stringify!($ptr)))
         }
 
-        let $ptr: &mut $type = unsafe { &mut *($ptr as *mut $type) };
+        let mut $ptr = match crate::ffi::handles::get_mut::<$type>($ptr) {
+            Ok(val) => val,
+            Err(reason) => return crate::ffi::ctypes::invalid_param($err, reason)
+        };
+        let $ptr: &mut $type = &mut $ptr;
     }
 }
 
 macro_rules! check_useful_opt_c_reference {
-    ($ptr:ident, $type:ty) => {
-        let $ptr: Option<&$type> = if $ptr.is_null() {
+    ($ptr:ident, $type:ty, $err:expr) => {
+        let $ptr: Option<crate::ffi::handles::Ref<$type>> = if $ptr.is_null() {
             None
         } else {
-            Some(unsafe { & *($ptr as *const $type) })
+            match crate::ffi::handles::get::<$type>($ptr) {
+                Ok(val) => Some(val),
+                Err(reason) => return crate::ffi::ctypes::invalid_param($err, reason)
+            }
         };
+        let $ptr: Option<&$type> = $ptr.as_deref();
     }
 }
 
-macro_rules! check_useful_c_reference_array {
-    ($ptrs:ident, $ptrs_len:ident, $type:ty, $err1:expr, $err2:expr) => {
-        if $ptrs.is_null() {
-            return $err1
+macro_rules! take_useful_c_reference {
+    ($ptr:ident, $type:ty, $err:expr) => {
+        if $ptr.is_null() {
+            return crate::ffi::ctypes::invalid_param($err, format!("`{}` is null", stringify!($ptr)))
+        }
+
+        let $ptr: $type = match crate::ffi::handles::take::<$type>($ptr) {
+            Ok(val) => val,
+            Err(reason) => return crate::ffi::ctypes::invalid_param($err, reason)
+        };
+    }
+}
+
+macro_rules! register_c_reference {
+    ($value:expr) => {
+        match crate::ffi::handles::register($value) {
+            Ok(handle) => handle,
+            Err(err) => return err.into()
         }
+    }
+}
 
-        if $ptrs_len <= 0 {
-            return $err2
+macro_rules! free_c_reference {
+    ($ptr:ident, $type:ty, $err:expr) => {
+        match crate::ffi::handles::free::<$type>($ptr) {
+            Ok(()) => ErrorCode::Success,
+            Err(reason) => crate::ffi::ctypes::invalid_param($err, reason)
         }
+    }
+}
 
-        let $ptrs: Vec<&$type> =
-            unsafe { slice::from_raw_parts($ptrs, $ptrs_len) }
-                .iter()
-                .map(|ptr| unsafe { &*(*ptr as *const $type) })
-                .collect();
+macro_rules! check_useful_c_reference_array {
+    ($ptrs:ident, $ptrs_len:ident, $type:ty, $err1:expr, $err2:expr) => {
+        let $ptrs: Vec<crate::ffi::handles::Ref<$type>> =
+            match crate::ffi::ctypes::CTypesUtils::c_handle_array_to_vec::<$type>(stringify!($ptrs), $ptrs, $ptrs_len,
+                                                                                  crate::ffi::ctypes::MAX_HANDLE_ARRAY_LEN,
+                                                                                  $err1, $err2) {
+                Ok(val) => val,
+                Err(err) => return err
+            };
+        let $ptrs: Vec<&$type> = $ptrs.iter().map(|val| &**val).collect();
     }
 }
 
 macro_rules! check_useful_hashset {
     ($ptr:ident, $len:expr, $err1:expr, $err2:expr) => {
         if $ptr.is_null() {
-            return $err1
+            return crate::ffi::ctypes::invalid_param($err1, format!("`{}` is null", stringify!($ptr)))
         }
 
         let $ptr = HashSet::from_iter( unsafe {  slice::from_raw_parts($ptr, $len) }.iter().cloned());
@@ -114,7 +334,7 @@ macro_rules! check_useful_hashset {
 macro_rules! check_useful_c_ptr {
     ($ptr:ident, $err1:expr) => {
         if $ptr.is_null() {
-            return $err1
+            return crate::ffi::ctypes::invalid_param($err1, format!("`{}` is null", stringify!($ptr)))
         }
     }
 }
@@ -123,11 +343,12 @@ macro_rules! check_useful_c_str {
     ($x:ident, $e:expr) => {
         let $x = match CTypesUtils::c_str_to_string($x) {
             Ok(Some(val)) => val,
-            _ => return $e,
+            Ok(None) => return crate::ffi::ctypes::invalid_param($e, format!("`{}` is null", stringify!($x))),
+            Err(err) => return crate::ffi::ctypes::invalid_param($e, format!("`{}` is invalid: {}", stringify!($x), err))
         };
 
         if $x.is_empty() {
-            return $e
+            return crate::ffi::ctypes::invalid_param($e, format!("`{}` is empty", stringify!($x)))
         }
     }
 }
@@ -136,7 +357,7 @@ macro_rules! check_useful_opt_c_str {
     ($x:ident, $e:expr) => {
         let $x = match CTypesUtils::c_str_to_string($x) {
             Ok(opt_val) => opt_val,
-            Err(_) => return $e
+            Err(err) => return crate::ffi::ctypes::invalid_param($e, format!("`{}` is invalid: {}", stringify!($x), err))
         };
     }
 }
@@ -145,7 +366,7 @@ macro_rules! check_useful_c_callback {
     ($x:ident, $e:expr) => {
         let $x = match $x {
             Some($x) => $x,
-            None => return $e
+            None => return crate::ffi::ctypes::invalid_param($e, format!("`{}` is null", stringify!($x)))
         };
     }
 }
\ No newline at end of file
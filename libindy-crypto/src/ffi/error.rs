@@ -0,0 +1,240 @@
+use crate::errors::{get_current_error_json, set_error_hook_fn, ErrorCode, IndyCryptoError, ToErrorCode};
+use crate::ffi::ctypes::CTypesUtils;
+
+use libc::{c_char, c_void};
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::ptr;
+use std::sync::Arc;
+
+pub type ErrorHookCB = extern "C" fn(context: *const c_void,
+                                     code: ErrorCode,
+                                     message: *const c_char);
+
+struct FFIErrorHook {
+    context: *const c_void,
+    hook: ErrorHookCB,
+}
+
+unsafe impl Sync for FFIErrorHook {}
+
+unsafe impl Send for FFIErrorHook {}
+
+impl FFIErrorHook {
+    fn call(&self, err: &IndyCryptoError) {
+        let message = CTypesUtils::string_to_cstring(err.to_string()).unwrap_or_default();
+        (self.hook)(self.context, err.to_error_code(), message.as_ptr());
+    }
+}
+
+thread_local! {
+    static CURRENT_ERROR_C_JSON: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+ffi_fn! {
+    /// Returns details of the last error produced on the calling thread.
+    ///
+    /// Every function returning non Success code remembers the error first, including invalid params:
+    /// the message tells which param is invalid and why.
+    ///
+    /// Note: Returned string is owned by the library and must not be freed by the caller. It stays
+    /// valid until the next indy_crypto_get_current_error call on the same thread, copy it to keep longer.
+    ///
+    /// # Arguments
+    /// * `error_json_p` - Reference that will contain error details json or null if no error happened
+    ///
+    /// Error details json:
+    /// ```text
+    /// {
+    ///     "code": int, // numeric ErrorCode
+    ///     "message": string, // human readable error description
+    ///     "backtrace": Optional<string> // captured only if RUST_BACKTRACE is set
+    /// }
+    /// ```
+    pub fn indy_crypto_get_current_error(error_json_p: *mut *const c_char) {
+        trace!("indy_crypto_get_current_error: >>> error_json_p: {:?}", error_json_p);
+
+        if error_json_p.is_null() {
+            return;
+        }
+
+        let error = get_current_error_json().and_then(|json| CTypesUtils::string_to_cstring(json).ok());
+
+        CURRENT_ERROR_C_JSON.with(|current_error| {
+            let mut current_error = current_error.borrow_mut();
+            *current_error = error;
+            unsafe {
+                *error_json_p = current_error.as_ref().map(|err| err.as_ptr()).unwrap_or(ptr::null());
+            }
+        });
+
+        trace!("indy_crypto_get_current_error: <<<");
+    }
+}
+
+ffi_fn! {
+    /// Set handler called for every error returned by the library.
+    ///
+    /// Handler is called with the code and message the caller gets, on the calling thread before
+    /// the error is returned. Errors handled inside the library are not passed.
+    ///
+    /// #Params
+    /// context: pointer to some context that will be available in the handler.
+    /// hook: (optional) handler called with code and message of the error. Removes current handler if not specified.
+    ///
+    /// #Returns
+    /// Error code
+    pub fn indy_crypto_set_error_hook(context: *const c_void,
+                                      hook: Option<ErrorHookCB>) -> ErrorCode {
+        trace!("indy_crypto_set_error_hook: >>> context: {:?}, hook: {:?}", context, hook);
+
+        match hook {
+            Some(hook) => {
+                let hook = FFIErrorHook { context, hook };
+                set_error_hook_fn(Some(Arc::new(move |err: &IndyCryptoError| hook.call(err))));
+            }
+            None => set_error_hook_fn(None)
+        }
+
+        trace!("indy_crypto_set_error_hook: <<<");
+
+        ErrorCode::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::errors::ERROR_HOOK_TEST_LOCK;
+    use crate::ffi::bls::indy_crypto_bls_ver_key_from_bytes;
+    use crate::bls::VerKey;
+    use std::ffi::CStr;
+    use std::os::raw::c_void;
+    use std::sync::Mutex;
+    use std::thread::{self, ThreadId};
+
+    use serde_json;
+
+    #[test]
+    fn indy_crypto_get_current_error_works() {
+        let bytes = vec![1u8; 128];
+        let mut ver_key: *const c_void = ptr::null();
+
+        let err_code = indy_crypto_bls_ver_key_from_bytes(bytes.as_ptr(), bytes.len(), &mut ver_key);
+        assert_eq!(ErrorCode::CryptoInvalidKey, err_code);
+
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        assert!(!error_json_p.is_null());
+
+        let error_json = unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() };
+        let error: serde_json::Value = serde_json::from_str(error_json).unwrap();
+
+        assert_eq!(ErrorCode::CryptoInvalidKey as u64, error["code"].as_u64().unwrap());
+        assert_eq!(VerKey::from_bytes(&bytes).unwrap_err().to_string(), error["message"].as_str().unwrap());
+    }
+
+    #[test]
+    fn indy_crypto_get_current_error_works_for_invalid_param() {
+        let bytes = vec![1u8; 128];
+
+        let err_code = indy_crypto_bls_ver_key_from_bytes(bytes.as_ptr(), bytes.len(), ptr::null_mut());
+        assert_eq!(ErrorCode::CommonInvalidParam3, err_code);
+
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        assert!(!error_json_p.is_null());
+
+        let error_json = unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() };
+        let error: serde_json::Value = serde_json::from_str(error_json).unwrap();
+
+        assert_eq!(ErrorCode::CommonInvalidParam3 as u64, error["code"].as_u64().unwrap());
+        assert_eq!("Invalid param 3: `ver_key_p` is null", error["message"].as_str().unwrap());
+    }
+
+    type HookCalls = Mutex<Vec<(ThreadId, ErrorCode, String)>>;
+
+    // Other test threads report errors while the hook is set, so calls are recorded with their thread
+    extern "C" fn counting_hook(context: *const c_void, code: ErrorCode, message: *const c_char) {
+        let calls = unsafe { &*(context as *const HookCalls) };
+        let message = unsafe { CStr::from_ptr(message).to_str().unwrap().to_string() };
+        calls.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push((thread::current().id(), code, message));
+    }
+
+    // Removes the hook also if the test fails
+    struct HookReset;
+
+    impl Drop for HookReset {
+        fn drop(&mut self) {
+            indy_crypto_set_error_hook(ptr::null(), None);
+        }
+    }
+
+    #[test]
+    fn indy_crypto_set_error_hook_works() {
+        let _lock = ERROR_HOOK_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Leaked, as a call that has taken the hook on other thread may still run after the hook is removed
+        let calls: &'static HookCalls = Box::leak(Box::new(Mutex::new(Vec::new())));
+        let context = calls as *const HookCalls as *const c_void;
+
+        let _reset = HookReset;
+        let err_code = indy_crypto_set_error_hook(context, Some(counting_hook));
+        assert_eq!(ErrorCode::Success, err_code);
+
+        let bytes = vec![1u8; 3];
+        let mut ver_key: *const c_void = ptr::null();
+        let err_code = indy_crypto_bls_ver_key_from_bytes(bytes.as_ptr(), bytes.len(), &mut ver_key);
+        assert_eq!(ErrorCode::CryptoInvalidKey, err_code);
+
+        let err_code = indy_crypto_set_error_hook(ptr::null(), None);
+        assert_eq!(ErrorCode::Success, err_code);
+
+        indy_crypto_bls_ver_key_from_bytes(bytes.as_ptr(), bytes.len(), &mut ver_key);
+
+        let calls = calls.lock().unwrap().iter()
+            .filter(|(thread_id, _, _)| *thread_id == thread::current().id())
+            .map(|(_, code, message)| (*code, message.clone()))
+            .collect::<Vec<(ErrorCode, String)>>();
+        assert_eq!(vec![(ErrorCode::CryptoInvalidKey, VerKey::from_bytes(&bytes).unwrap_err().to_string())], calls);
+    }
+
+    ffi_fn! {
+        pub fn indy_crypto_test_panic(message: *const c_char) -> ErrorCode {
+            check_useful_c_str!(message, ErrorCode::CommonInvalidParam1);
+            panic!("{}", message)
+        }
+    }
+
+    ffi_fn! {
+        pub fn indy_crypto_test_panic_without_code() {
+            std::panic::panic_any(1)
+        }
+    }
+
+    fn _current_error() -> serde_json::Value {
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        serde_json::from_str(unsafe { CStr::from_ptr(error_json_p).to_str().unwrap() }).unwrap()
+    }
+
+    #[test]
+    fn catch_panic_works() {
+        let message = CString::new("deliberate panic").unwrap();
+        let err_code = indy_crypto_test_panic(message.as_ptr());
+        assert_eq!(ErrorCode::CommonInvalidState, err_code);
+
+        let error = _current_error();
+        assert_eq!(ErrorCode::CommonInvalidState as u64, error["code"].as_u64().unwrap());
+        assert_eq!("Invalid library state: Panic: deliberate panic", error["message"].as_str().unwrap());
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_test_panic(ptr::null()));
+    }
+
+    #[test]
+    fn catch_panic_works_for_unit_result() {
+        indy_crypto_test_panic_without_code();
+        assert_eq!("Invalid library state: Panic: unknown panic payload", _current_error()["message"].as_str().unwrap());
+    }
+}
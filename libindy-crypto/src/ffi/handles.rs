@@ -0,0 +1,372 @@
+use crate::errors::IndyCryptoError;
+
+use std::any::{type_name, Any};
+use std::fmt;
+use std::cell::UnsafeCell;
+use std::ops::{Deref, DerefMut};
+use std::os::raw::c_void;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// Objects given out through FFI live in this registry and handles are opaque tokens of slot index
+// and slot generation, not pointers. Every call looks the handle up, so a handle of another type,
+// a freed handle or a garbage value is reported as invalid param instead of being dereferenced.
+//
+// Generation of the slot is bumped on free, so a stale handle never resolves to the next object
+// stored in the same slot. Generations start from 1, so no token is null. A slot whose generation
+// is exhausted is retired instead of wrapping around, and registering fails once all indices
+// of the token are taken.
+//
+// Host runtimes call the library from thread pools, so values must be Send + Sync: a handle
+// created on one thread may be freed on another and `get` gives out shared references that are
+// used from several threads at once. Values are plain data without lazily computed state, so the
+// bounds hold without locks. Functions that take a handle through `get_mut` change the value and
+// need exclusive use of that handle, it is the caller's duty.
+
+const GENERATION_BITS: u32 = usize::BITS / 2;
+const GENERATION_MASK: usize = (1 << GENERATION_BITS) - 1;
+const MAX_INDEX: usize = usize::MAX >> GENERATION_BITS;
+
+// Value of the handle, shared by the registry and references of calls that use it, so freeing
+// the handle during a call doesn't free the value under the call
+struct Value(UnsafeCell<Box<dyn Any + Send + Sync>>);
+
+// Shared access goes through `Ref`, exclusive one through `RefMut`, which is the caller's duty
+unsafe impl Sync for Value {}
+
+struct Entry {
+    value: Arc<Value>,
+    type_name: &'static str,
+}
+
+struct Slot {
+    generation: usize,
+    entry: Option<Entry>,
+}
+
+#[derive(Default)]
+struct Registry {
+    slots: Vec<Slot>,
+    free_slots: Vec<usize>,
+}
+
+enum LookupError {
+    Invalid,
+    Freed,
+    WrongType(&'static str),
+}
+
+/// Shared reference to the value of a handle, that keeps the value alive until dropped.
+pub(crate) struct Ref<T> {
+    _value: Arc<Value>,
+    ptr: *const T,
+}
+
+impl<T> Deref for Ref<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Ref<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Mutable reference to the value of a handle, see `Ref`.
+pub(crate) struct RefMut<T> {
+    _value: Arc<Value>,
+    ptr: *mut T,
+}
+
+impl<T> Deref for RefMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T> DerefMut for RefMut<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.ptr }
+    }
+}
+
+lazy_static! {
+    static ref REGISTRY: Mutex<Registry> = Mutex::new(Registry::default());
+}
+
+fn _registry() -> MutexGuard<'static, Registry> {
+    // Registry is consistent after every statement, a panic in other thread can't break it
+    REGISTRY.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn _reason<T>(handle: *const c_void, err: LookupError) -> String {
+    match err {
+        LookupError::Invalid => format!("Handle {:?} is not a valid handle", handle),
+        LookupError::Freed => format!("Handle {:?} is already freed", handle),
+        LookupError::WrongType(actual) => format!("Handle {:?} is {}, expected {}", handle, actual, type_name::<T>()),
+    }
+}
+
+impl Registry {
+    fn _lookup<T: Any>(&mut self, handle: *const c_void) -> Result<(usize, Arc<Value>, *mut T), LookupError> {
+        let token = handle as usize;
+        let (index, generation) = (token >> GENERATION_BITS, token & GENERATION_MASK);
+
+        let slot = match self.slots.get_mut(index) {
+            Some(slot) if generation != 0 && generation <= slot.generation => slot,
+            _ => return Err(LookupError::Invalid)
+        };
+
+        let entry = match slot.entry {
+            Some(ref mut entry) if slot.generation == generation => entry,
+            _ => return Err(LookupError::Freed)
+        };
+
+        let value = unsafe { &mut **entry.value.0.get() };
+        match value.downcast_mut::<T>() {
+            Some(value) => Ok((index, entry.value.clone(), value as *mut T)),
+            None => Err(LookupError::WrongType(entry.type_name))
+        }
+    }
+
+    fn _remove(&mut self, index: usize) -> Entry {
+        let slot = &mut self.slots[index];
+        // Slot of the last generation is retired, wrapping around would make its stale handles valid again
+        if slot.generation < GENERATION_MASK {
+            slot.generation += 1;
+            self.free_slots.push(index);
+        }
+        slot.entry.take().unwrap()
+    }
+}
+
+/// Stores the value in the registry and returns its handle.
+///
+/// Fails with `InvalidState` if every index a handle can encode is taken.
+pub(crate) fn register<T: Any + Send + Sync>(value: T) -> Result<*const c_void, IndyCryptoError> {
+    let mut registry = _registry();
+    let entry = Entry { value: Arc::new(Value(UnsafeCell::new(Box::new(value)))), type_name: type_name::<T>() };
+
+    let (index, generation) = match registry.free_slots.pop() {
+        Some(index) => {
+            let slot = &mut registry.slots[index];
+            slot.entry = Some(entry);
+            (index, slot.generation)
+        }
+        None if registry.slots.len() > MAX_INDEX => {
+            return Err(IndyCryptoError::InvalidState(format!("All {} handles are taken", registry.slots.len())));
+        }
+        None => {
+            registry.slots.push(Slot { generation: 1, entry: Some(entry) });
+            (registry.slots.len() - 1, 1)
+        }
+    };
+
+    Ok((index << GENERATION_BITS | generation) as *const c_void)
+}
+
+/// Returns reference to the value of the handle.
+///
+/// The reference keeps the value alive, so it stays valid if the handle is freed concurrently,
+/// e.g. by indy_crypto_terminate. Using a freed handle is still caller's error.
+pub(crate) fn get<T: Any + Sync>(handle: *const c_void) -> Result<Ref<T>, String> {
+    let (_, value, ptr) = _registry()._lookup::<T>(handle).map_err(|err| _reason::<T>(handle, err))?;
+    Ok(Ref { _value: value, ptr })
+}
+
+/// Returns mutable reference to the value of the handle, see `get`.
+pub(crate) fn get_mut<T: Any>(handle: *const c_void) -> Result<RefMut<T>, String> {
+    let (_, value, ptr) = _registry()._lookup::<T>(handle).map_err(|err| _reason::<T>(handle, err))?;
+    Ok(RefMut { _value: value, ptr })
+}
+
+/// Removes the value of the handle from the registry and returns it, the handle becomes freed.
+///
+/// Fails if a concurrent call uses the value. The value is moved out of its allocation without wiping it,
+/// secrets must be freed with `free`.
+pub(crate) fn take<T: Any>(handle: *const c_void) -> Result<T, String> {
+    let mut registry = _registry();
+    let (index, value, _) = registry._lookup::<T>(handle).map_err(|err| _reason::<T>(handle, err))?;
+    // References are cloned under the lock only, so no new one appears before the entry is removed
+    if Arc::strong_count(&value) > 2 {
+        return Err(format!("Handle {:?} is in use", handle));
+    }
+    drop(value);
+
+    let entry = registry._remove(index);
+    let value = Arc::try_unwrap(entry.value).ok().unwrap();
+    Ok(*value.0.into_inner().downcast::<T>().unwrap())
+}
+
+/// Drops the value of the handle. Freeing already freed handle is no-op.
+///
+/// The value is dropped in place when the last reference to it is dropped, so `Drop` of secrets
+/// zeroizes the memory that is deallocated.
+pub(crate) fn free<T: Any>(handle: *const c_void) -> Result<(), String> {
+    let mut registry = _registry();
+    let index = match registry._lookup::<T>(handle) {
+        Ok((index, _, _)) => index,
+        Err(LookupError::Freed) => return Ok(()),
+        Err(err) => return Err(_reason::<T>(handle, err))
+    };
+    let entry = registry._remove(index);
+    // Value is dropped after the registry is unlocked
+    drop(registry);
+    drop(entry);
+    Ok(())
+}
+
+/// Initializes the registry, it is initialized on first use otherwise.
+pub(crate) fn init() {
+    lazy_static::initialize(&REGISTRY);
+}
+
+/// Drops values of all handles and returns their number, the handles become freed.
+///
+/// Values that are referenced by calls in progress are dropped when the calls drop their references.
+pub(crate) fn clear() -> usize {
+    let mut registry = _registry();
+    let indexes: Vec<usize> = registry.slots.iter().enumerate()
+        .filter(|(_, slot)| slot.entry.is_some())
+        .map(|(index, _)| index)
+        .collect();
+    let entries: Vec<Entry> = indexes.into_iter().map(|index| registry._remove(index)).collect();
+    // Values are dropped after the registry is unlocked
+    drop(registry);
+    entries.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct First(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Second(u32);
+
+    #[test]
+    fn register_works() {
+        let handle = register(First(1)).unwrap();
+        assert!(!handle.is_null());
+        assert_eq!(First(1), *get::<First>(handle).unwrap());
+
+        get_mut::<First>(handle).unwrap().0 = 2;
+        assert_eq!(First(2), *get::<First>(handle).unwrap());
+
+        free::<First>(handle).unwrap();
+    }
+
+    #[test]
+    fn get_works_for_wrong_type() {
+        let handle = register(First(1)).unwrap();
+        let reason = get::<Second>(handle).unwrap_err();
+        assert!(reason.contains("First, expected"));
+        assert!(reason.ends_with("Second"));
+
+        free::<Second>(handle).unwrap_err();
+        free::<First>(handle).unwrap();
+    }
+
+    #[test]
+    fn get_works_for_freed_handle() {
+        let handle = register(First(1)).unwrap();
+        free::<First>(handle).unwrap();
+        assert!(get::<First>(handle).unwrap_err().ends_with("is already freed"));
+    }
+
+    #[test]
+    fn get_works_for_invalid_handle() {
+        assert!(get::<First>((1usize << GENERATION_BITS) as *const c_void).unwrap_err().ends_with("is not a valid handle"));
+        assert!(get::<First>(usize::MAX as *const c_void).unwrap_err().ends_with("is not a valid handle"));
+    }
+
+    #[test]
+    fn get_works_for_handle_freed_while_referenced() {
+        let handle = register(First(1)).unwrap();
+        let value = get::<First>(handle).unwrap();
+
+        free::<First>(handle).unwrap();
+        assert_eq!(First(1), *value);
+        get::<First>(handle).unwrap_err();
+    }
+
+    #[test]
+    fn free_works_for_drop_in_place() {
+        struct Tracked(Arc<Mutex<usize>>);
+
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                *self.0.lock().unwrap() = self as *const Tracked as usize;
+            }
+        }
+
+        let dropped_at = Arc::new(Mutex::new(0));
+        let handle = register(Tracked(dropped_at.clone())).unwrap();
+        let address = {
+            let value = get::<Tracked>(handle).unwrap();
+            &*value as *const Tracked as usize
+        };
+
+        free::<Tracked>(handle).unwrap();
+        assert_eq!(address, *dropped_at.lock().unwrap());
+    }
+
+    #[test]
+    fn free_works_for_double_free() {
+        let handle = register(First(1)).unwrap();
+        free::<First>(handle).unwrap();
+        free::<First>(handle).unwrap();
+    }
+
+    #[test]
+    fn free_works_for_exhausted_generation() {
+        let handle = register(First(1)).unwrap();
+        let index = handle as usize >> GENERATION_BITS;
+        _registry().slots[index].generation = GENERATION_MASK;
+        let last = (index << GENERATION_BITS | GENERATION_MASK) as *const c_void;
+
+        free::<First>(last).unwrap();
+        assert!(!_registry().free_slots.contains(&index));
+        assert!(get::<First>(last).unwrap_err().ends_with("is already freed"));
+        assert!(get::<First>(handle).unwrap_err().ends_with("is already freed"));
+
+        // The retired slot is never taken again
+        let other = register(First(2)).unwrap();
+        assert_ne!(index, other as usize >> GENERATION_BITS);
+        free::<First>(other).unwrap();
+    }
+
+    #[test]
+    fn take_works_for_referenced_handle() {
+        let handle = register(First(1)).unwrap();
+        let value = get::<First>(handle).unwrap();
+        assert!(take::<First>(handle).unwrap_err().ends_with("is in use"));
+
+        drop(value);
+        assert_eq!(First(1), take::<First>(handle).unwrap());
+    }
+
+    #[test]
+    fn register_works_for_reused_slot() {
+        let stale = register(First(1)).unwrap();
+        free::<First>(stale).unwrap();
+
+        // Slot may be taken by other test thread, stale handle is invalid either way
+        let handle = register(First(2)).unwrap();
+        assert_ne!(stale, handle);
+        get::<First>(stale).unwrap_err();
+        assert_eq!(First(2), *get::<First>(handle).unwrap());
+
+        free::<First>(stale).unwrap();
+        assert_eq!(First(2), *get::<First>(handle).unwrap());
+        assert_eq!(First(2), take::<First>(handle).unwrap());
+        take::<First>(handle).unwrap_err();
+    }
+}
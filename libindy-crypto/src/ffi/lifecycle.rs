@@ -0,0 +1,182 @@
+use crate::errors::{clear_current_error, ErrorCode};
+use crate::ffi::handles;
+use crate::pair::reseed;
+use crate::utils::version::library_version_json;
+
+use libc::c_char;
+use std::ffi::CString;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    // Json of the version has no interior nul
+    static ref C_VERSION_JSON: CString = CString::new(library_version_json()).unwrap();
+}
+
+ffi_fn! {
+    /// Returns version and build metadata of the library as json:
+    /// { "version": string, "features": [string], "curve": Optional<string> }.
+    ///
+    /// Note: Returned string is owned by the library and must not be deallocated.
+    ///
+    /// # Arguments
+    /// * `version_json_p` - Reference that will contain version json string pointer
+    pub fn indy_crypto_version(version_json_p: *mut *const c_char) -> ErrorCode {
+        trace!("indy_crypto_version: >>> version_json_p: {:?}", version_json_p);
+
+        check_useful_c_ptr!(version_json_p, ErrorCode::CommonInvalidParam1);
+
+        unsafe {
+            *version_json_p = C_VERSION_JSON.as_ptr();
+            trace!("indy_crypto_version: *version_json_p: {:?}", *version_json_p);
+        }
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_version: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Initializes global state of the library.
+    ///
+    /// Calling it is optional, every function initializes the state it uses on first call.
+    /// Calling it again, also after indy_crypto_terminate, is no-op if the library is initialized.
+    pub fn indy_crypto_init() -> ErrorCode {
+        trace!("indy_crypto_init: >>>");
+
+        if !INITIALIZED.swap(true, Ordering::SeqCst) {
+            handles::init();
+        }
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_init: <<< res: {:?}", res);
+        res
+    }
+}
+
+ffi_fn! {
+    /// Releases global state of the library.
+    ///
+    /// Flushes the logger, frees all objects given out by the library, so outstanding handles are
+    /// reported as freed instead of being used, reseeds random generators before their next use
+    /// and clears the last error of the calling thread.
+    ///
+    /// Note: No other call may be in progress on any thread. The library stays usable after the call:
+    /// it is initialized again by indy_crypto_init or by the next call of any function.
+    pub fn indy_crypto_terminate() -> ErrorCode {
+        trace!("indy_crypto_terminate: >>>");
+
+        log::logger().flush();
+
+        let freed = handles::clear();
+        trace!("indy_crypto_terminate: freed handles: {:?}", freed);
+
+        INITIALIZED.store(false, Ordering::SeqCst);
+        clear_current_error();
+
+        let res = match reseed() {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_terminate: <<< res: {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ffi::bls::{indy_crypto_bls_sign_key_new, indy_crypto_bls_sign_key_free, indy_crypto_bls_sign};
+    use crate::ffi::error::indy_crypto_get_current_error;
+    use std::ffi::CStr;
+    use std::os::raw::c_void;
+    use std::process::Command;
+    use std::ptr;
+
+    // Terminate frees handles of all threads, so it is tested in a separate process
+    const TERMINATE_TEST_ENV: &str = "INDY_CRYPTO_TERMINATE_TEST";
+
+    fn _sign_key() -> *const c_void {
+        let mut sign_key: *const c_void = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_new(ptr::null(), 0, &mut sign_key));
+        sign_key
+    }
+
+    fn _sign(sign_key: *const c_void) -> ErrorCode {
+        let message = [1u8, 2, 3];
+        let mut signature: *const c_void = ptr::null();
+        indy_crypto_bls_sign(message.as_ptr(), message.len(), sign_key, &mut signature)
+    }
+
+    fn _current_error() -> *const c_char {
+        let mut error_json_p: *const c_char = ptr::null();
+        indy_crypto_get_current_error(&mut error_json_p);
+        error_json_p
+    }
+
+    #[test]
+    fn indy_crypto_version_works() {
+        let mut version_json: *const c_char = ptr::null();
+        assert_eq!(ErrorCode::Success, indy_crypto_version(&mut version_json));
+
+        let version_json = unsafe { CStr::from_ptr(version_json) }.to_str().unwrap();
+        let version: serde_json::Value = serde_json::from_str(version_json).unwrap();
+        assert_eq!(env!("CARGO_PKG_VERSION"), version["version"].as_str().unwrap());
+        assert!(version["features"].as_array().unwrap().iter().any(|feature| feature == "ffi"));
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_version(ptr::null_mut()));
+    }
+
+    #[test]
+    fn indy_crypto_init_works() {
+        assert_eq!(ErrorCode::Success, indy_crypto_init());
+        assert_eq!(ErrorCode::Success, indy_crypto_init());
+
+        let sign_key = _sign_key();
+        assert_eq!(ErrorCode::Success, _sign(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+    }
+
+    #[test]
+    fn indy_crypto_terminate_works() {
+        let output = Command::new(std::env::current_exe().unwrap())
+            .args(&["--exact", "ffi::lifecycle::tests::indy_crypto_terminate_works_in_process", "--test-threads=1"])
+            .env(TERMINATE_TEST_ENV, "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+        assert!(stdout.contains("1 passed"), "{}", stdout);
+    }
+
+    #[test]
+    fn indy_crypto_terminate_works_in_process() {
+        if std::env::var(TERMINATE_TEST_ENV).is_err() {
+            return;
+        }
+
+        for _ in 0..2 {
+            assert_eq!(ErrorCode::Success, indy_crypto_init());
+            let sign_key = _sign_key();
+            assert_eq!(ErrorCode::CommonInvalidParam3, _sign(ptr::null()));
+            assert!(!_current_error().is_null());
+
+            assert_eq!(ErrorCode::Success, indy_crypto_terminate());
+            assert!(_current_error().is_null());
+
+            assert_eq!(ErrorCode::CommonInvalidParam3, _sign(sign_key));
+            assert_eq!(ErrorCode::Success, indy_crypto_bls_sign_key_free(sign_key));
+        }
+
+        // Functions initialize the library without indy_crypto_init
+        let sign_key = _sign_key();
+        assert_eq!(ErrorCode::Success, _sign(sign_key));
+        assert_eq!(ErrorCode::Success, indy_crypto_terminate());
+        assert_eq!(ErrorCode::CommonInvalidParam3, _sign(sign_key));
+    }
+}
@@ -7,68 +7,213 @@ use crate::errors::ErrorCode;
 extern crate time;
 extern crate log;
 
-use crate::errors::ToErrorCode;
 
 use crate::cl::logger::{EnabledCB, LogCB, FlushCB, IndyCryptoLogger, IndyCryptoDefaultLogger};
 use crate::ffi::ctypes::CTypesUtils;
 
-/// Set custom logger implementation.
-///
-/// Allows library user to provide custom logger implementation as set of handlers.
-///
-/// #Params
-/// context: pointer to some logger context that will be available in logger handlers.
-/// enabled: (optional) "enabled" operation handler - calls to determines if a log record would be logged. (false positive if not specified)
-/// log: "log" operation handler - calls to logs a record.
-/// flush: (optional) "flush" operation handler - calls to flushes buffered records (in case of crash or signal).
-///
-/// #Returns
-/// Error code
-#[no_mangle]
-pub extern fn indy_crypto_set_logger(context: *const c_void,
-                                     enabled: Option<EnabledCB>,
-                                     log: Option<LogCB>,
-                                     flush: Option<FlushCB>) -> ErrorCode {
-    trace!("indy_crypto_set_logger >>> context: {:?}, enabled: {:?}, log: {:?}, flush: {:?}", context, log, enabled, flush);
-
-    check_useful_c_callback!(log, ErrorCode::CommonInvalidParam3);
-
-    let res = match IndyCryptoLogger::init(context, enabled, log, flush) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_set_logger: <<< res: {:?}", res);
-
-    res
+use std::ptr;
+
+ffi_fn! {
+    /// Set custom logger implementation.
+    ///
+    /// Allows library user to provide custom logger implementation as set of handlers.
+    ///
+    /// #Params
+    /// context: pointer to some logger context that will be available in logger handlers.
+    /// enabled: (optional) "enabled" operation handler - calls to determines if a log record would be logged. (false positive if not specified)
+    /// log: "log" operation handler - calls to logs a record.
+    /// flush: (optional) "flush" operation handler - calls to flushes buffered records (in case of crash or signal).
+    ///
+    /// #Returns
+    /// Error code
+    pub fn indy_crypto_set_logger(context: *const c_void,
+                                  enabled: Option<EnabledCB>,
+                                  log: Option<LogCB>,
+                                  flush: Option<FlushCB>) -> ErrorCode {
+        trace!("indy_crypto_set_logger >>> context: {:?}, enabled: {:?}, log: {:?}, flush: {:?}", context, log, enabled, flush);
+
+        check_useful_c_callback!(log, ErrorCode::CommonInvalidParam3);
+
+        let res = match IndyCryptoLogger::init(context, enabled, log, flush) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_set_logger: <<< res: {:?}", res);
+
+        res
+    }
 }
 
-/// Set default logger implementation.
-///
-/// Allows library user use `env_logger` logger as default implementation.
-/// More details about `env_logger` and its customization can be found here: https://crates.io/crates/env_logger
-///
-/// #Params
-/// pattern: (optional) pattern that corresponds with the log messages to show.
-///
-/// NOTE: You should specify either `pattern` parameter or `RUST_LOG` environment variable to init logger.
-///
-/// #Returns
-/// Error code
-#[no_mangle]
-pub extern fn indy_crypto_set_default_logger(pattern: *const c_char) -> ErrorCode {
-    trace!("indy_crypto_set_default_logger >>> pattern: {:?}", pattern);
-
-    check_useful_opt_c_str!(pattern, ErrorCode::CommonInvalidParam1);
-
-    trace!("indy_crypto_set_default_logger: entities >>> pattern: {:?}", pattern);
-
-    let res = match IndyCryptoDefaultLogger::init(pattern) {
-        Ok(()) => ErrorCode::Success,
-        Err(err) => err.to_error_code()
-    };
-
-    trace!("indy_crypto_set_default_logger: <<< res: {:?}", res);
-
-    res
-}
\ No newline at end of file
+ffi_fn! {
+    /// Get logger implementation installed by `indy_crypto_set_logger`.
+    ///
+    /// Allows library user to chain installed logger, for example to wrap its handlers with own ones.
+    ///
+    /// #Params
+    /// context_p: pointer to store logger context.
+    /// enabled_cb_p: pointer to store "enabled" operation handler, null if not specified.
+    /// log_cb_p: pointer to store "log" operation handler, null if logger was not installed by `indy_crypto_set_logger`.
+    /// flush_cb_p: pointer to store "flush" operation handler, null if not specified.
+    ///
+    /// #Returns
+    /// Error code
+    pub fn indy_crypto_get_logger(context_p: *mut *const c_void,
+                                  enabled_cb_p: *mut Option<EnabledCB>,
+                                  log_cb_p: *mut Option<LogCB>,
+                                  flush_cb_p: *mut Option<FlushCB>) -> ErrorCode {
+        trace!("indy_crypto_get_logger >>> context_p: {:?}, enabled_cb_p: {:?}, log_cb_p: {:?}, flush_cb_p: {:?}", context_p, enabled_cb_p, log_cb_p, flush_cb_p);
+
+        check_useful_c_ptr!(context_p, ErrorCode::CommonInvalidParam1);
+        check_useful_c_ptr!(enabled_cb_p, ErrorCode::CommonInvalidParam2);
+        check_useful_c_ptr!(log_cb_p, ErrorCode::CommonInvalidParam3);
+        check_useful_c_ptr!(flush_cb_p, ErrorCode::CommonInvalidParam4);
+
+        let (context, enabled, log, flush) = match IndyCryptoLogger::get() {
+            Some((context, enabled, log, flush)) => (context, enabled, Some(log), flush),
+            None => (ptr::null(), None, None, None)
+        };
+
+        unsafe {
+            *context_p = context;
+            *enabled_cb_p = enabled;
+            *log_cb_p = log;
+            *flush_cb_p = flush;
+        }
+
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_get_logger: <<< res: {:?}", res);
+
+        res
+    }
+}
+
+ffi_fn! {
+    /// Set default logger implementation.
+    ///
+    /// Allows library user use `env_logger` logger as default implementation.
+    /// More details about `env_logger` and its customization can be found here: https://crates.io/crates/env_logger
+    ///
+    /// #Params
+    /// pattern: (optional) pattern that corresponds with the log messages to show.
+    ///
+    /// NOTE: You should specify either `pattern` parameter or `RUST_LOG` environment variable to init logger.
+    ///
+    /// #Returns
+    /// Error code
+    pub fn indy_crypto_set_default_logger(pattern: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_set_default_logger >>> pattern: {:?}", pattern);
+
+        check_useful_opt_c_str!(pattern, ErrorCode::CommonInvalidParam1);
+
+        trace!("indy_crypto_set_default_logger: entities >>> pattern: {:?}", pattern);
+
+        let res = match IndyCryptoDefaultLogger::init(pattern) {
+            Ok(()) => ErrorCode::Success,
+            Err(err) => err.into()
+        };
+
+        trace!("indy_crypto_set_default_logger: <<< res: {:?}", res);
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const TARGET: &str = "indy_crypto_get_logger_test";
+
+    static CONTEXT: AtomicUsize = AtomicUsize::new(0);
+    static LOGGED: AtomicUsize = AtomicUsize::new(0);
+    static FLUSHED: AtomicUsize = AtomicUsize::new(0);
+
+    extern fn _enabled(_context: *const c_void, _level: u32, _target: *const c_char) -> bool {
+        true
+    }
+
+    extern fn _log(context: *const c_void, _level: u32, target: *const c_char, message: *const c_char,
+                   _module_path: *const c_char, _file: *const c_char, _line: u32) {
+        let target = unsafe { CStr::from_ptr(target).to_str().unwrap() };
+        if target == TARGET && unsafe { CStr::from_ptr(message).to_str().unwrap() } == "chained" {
+            CONTEXT.store(context as usize, Ordering::SeqCst);
+            LOGGED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    extern fn _flush(_context: *const c_void) {
+        FLUSHED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // Logger and max level are process wide and `log` accepts one logger per process,
+    // so installing is tested in a separate process
+    const LOGGER_TEST_ENV: &str = "INDY_CRYPTO_LOGGER_TEST";
+
+    // Restores the max level also if the test fails
+    struct MaxLevelReset(log::LevelFilter);
+
+    impl Drop for MaxLevelReset {
+        fn drop(&mut self) {
+            log::set_max_level(self.0);
+        }
+    }
+
+    #[test]
+    fn indy_crypto_get_logger_works() {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(&["--exact", "ffi::logger::tests::indy_crypto_get_logger_works_in_process", "--test-threads=1"])
+            .env(LOGGER_TEST_ENV, "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+        assert!(stdout.contains("1 passed"), "{}", stdout);
+    }
+
+    #[test]
+    fn indy_crypto_get_logger_works_in_process() {
+        if std::env::var(LOGGER_TEST_ENV).is_err() {
+            return;
+        }
+
+        let mut context: *const c_void = ptr::null();
+        let mut enabled: Option<EnabledCB> = None;
+        let mut log: Option<LogCB> = None;
+        let mut flush: Option<FlushCB> = None;
+
+        assert_eq!(ErrorCode::Success, indy_crypto_get_logger(&mut context, &mut enabled, &mut log, &mut flush));
+        assert!(context.is_null() && enabled.is_none() && log.is_none() && flush.is_none());
+
+        let _reset = MaxLevelReset(log::max_level());
+        let installed_context = &CONTEXT as *const AtomicUsize as *const c_void;
+        assert_eq!(ErrorCode::Success, indy_crypto_set_logger(installed_context, Some(_enabled), Some(_log), Some(_flush)));
+
+        assert_eq!(ErrorCode::Success, indy_crypto_get_logger(&mut context, &mut enabled, &mut log, &mut flush));
+        assert_eq!(installed_context, context);
+
+        let target = std::ffi::CString::new(TARGET).unwrap();
+        let message = std::ffi::CString::new("chained").unwrap();
+        assert!(enabled.unwrap()(context, 5, target.as_ptr()));
+        log.unwrap()(context, 5, target.as_ptr(), message.as_ptr(), ptr::null(), ptr::null(), 0);
+        flush.unwrap()(context);
+
+        assert_eq!(1, LOGGED.load(Ordering::SeqCst));
+        assert_eq!(installed_context as usize, CONTEXT.load(Ordering::SeqCst));
+        assert_eq!(1, FLUSHED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn indy_crypto_get_logger_works_for_null_pointers() {
+        let mut context: *const c_void = ptr::null();
+        let mut enabled: Option<EnabledCB> = None;
+        let mut log: Option<LogCB> = None;
+
+        assert_eq!(ErrorCode::CommonInvalidParam1, indy_crypto_get_logger(ptr::null_mut(), &mut enabled, &mut log, ptr::null_mut()));
+        assert_eq!(ErrorCode::CommonInvalidParam4, indy_crypto_get_logger(&mut context, &mut enabled, &mut log, ptr::null_mut()));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::errors::ErrorCode;
+
+use libc::c_char;
+use std::ffi::CString;
+
+ffi_fn! {
+    /// Deallocates string returned by the library, e.g. json of indy_crypto_bls_sign_json
+    /// or indy_crypto_cl_*_to_json functions.
+    ///
+    /// Note: Strings owned by the library, like the one of indy_crypto_get_current_error
+    /// or indy_crypto_version, must not be passed here.
+    ///
+    /// # Arguments
+    /// * `s` - String pointer returned by the library
+    pub fn indy_crypto_string_free(s: *const c_char) -> ErrorCode {
+        trace!("indy_crypto_string_free: >>> s: {:?}", s);
+
+        check_useful_c_ptr!(s, ErrorCode::CommonInvalidParam1);
+
+        unsafe { let _ = CString::from_raw(s as *mut c_char); }
+        let res = ErrorCode::Success;
+
+        trace!("indy_crypto_string_free: <<< res: {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ffi::ctypes::CTypesUtils;
+    use std::ptr;
+
+    #[test]
+    fn indy_crypto_string_free_works() {
+        let s = CTypesUtils::string_to_cstring("json".to_string()).unwrap().into_raw();
+
+        let err_code = indy_crypto_string_free(s);
+        assert_eq!(err_code, ErrorCode::Success);
+    }
+
+    #[test]
+    fn indy_crypto_string_free_works_for_null() {
+        let err_code = indy_crypto_string_free(ptr::null());
+        assert_eq!(err_code, ErrorCode::CommonInvalidParam1);
+    }
+}
@@ -1,5 +1,11 @@
 #[macro_use]
 mod ctypes;
+mod handles;
 pub mod cl;
 pub mod bls;
+pub mod config;
 pub mod logger;
+pub mod error;
+pub mod lifecycle;
+pub mod memory;
+
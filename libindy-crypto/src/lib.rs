@@ -2,9 +2,15 @@
 extern crate wasm_bindgen;
 #[cfg(feature = "wasm")]
 extern crate console_error_panic_hook;
+#[cfg(feature = "wasm")]
+extern crate js_sys;
+#[cfg(feature = "wasm")]
+extern crate serde_wasm_bindgen;
 
 #[cfg(feature = "pair_amcl")]
 extern crate amcl;
+#[cfg(target_arch = "wasm32")]
+extern crate getrandom;
 extern crate env_logger;
 #[macro_use]
 extern crate log;
@@ -32,12 +38,18 @@ extern crate serde_json;
 #[cfg(feature = "bn_openssl")]
 extern crate openssl;
 
+#[cfg(feature = "bn_openssl")]
+extern crate openssl_sys;
+
 #[cfg(feature = "bn_openssl")]
 extern crate int_traits;
 
 #[cfg(feature = "ffi")]
 extern crate libc;
 
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
 extern crate time;
 
 #[cfg(feature = "cl")]
@@ -57,6 +69,8 @@ pub mod ffi;
 #[path = "pair/amcl.rs"]
 pub mod pair;
 
+pub mod utils;
+
 #[macro_use]
 extern crate lazy_static;
 
@@ -1,6 +1,8 @@
 use crate::errors::IndyCryptoError;
+use crate::utils::zeroize::zeroize;
 
 use amcl::big::BIG;
+use amcl::dbig::DBIG;
 
 use amcl::rom::{
     CURVE_GX,
@@ -10,7 +12,9 @@ use amcl::rom::{
     CURVE_PYA,
     CURVE_PXB,
     CURVE_PYB,
-    MODBYTES
+    CURVE_COF,
+    MODBYTES,
+    MODULUS
 };
 
 use amcl::ecp::ECP;
@@ -20,9 +24,21 @@ use amcl::fp2::FP2;
 use amcl::pair::{ate, g1mul, g2mul, gtpow, fexp};
 use amcl::rand::RAND;
 
+#[cfg(not(target_arch = "wasm32"))]
 use rand::rngs::OsRng;
+#[cfg(not(target_arch = "wasm32"))]
 use rand::RngCore;
+use sha2::{Sha512, Digest};
 use std::fmt::{Debug, Formatter, Error};
+use std::iter::Sum;
+use std::cell::RefCell;
+use std::sync::RwLock;
+use std::ptr;
+use std::sync::atomic::{compiler_fence, AtomicUsize, Ordering};
+#[cfg(all(unix, feature = "fork_safety"))]
+use std::sync::Once;
+#[cfg(any(test, feature = "testing"))]
+use std::collections::VecDeque;
 
 #[cfg(feature = "serialization")]
 use serde::ser::{Serialize, Serializer, Error as SError};
@@ -31,57 +47,352 @@ use serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
 #[cfg(feature = "serialization")]
 use std::fmt;
 
-#[cfg(test)]
-use std::cell::RefCell;
+/// Name of the curve AMCL is built for.
+pub const CURVE_NAME: &str = "BN254";
+
+#[cfg(any(test, feature = "testing"))]
+const DEFAULT_MOCK_SCALAR: &str = "22EB5716FB01F2122DE924466542B923D8C96F16C9B5FE2C00B7D7DC1499EA50";
+
+/// Value returned by an injected pair mock instead of a random one.
+#[cfg(any(test, feature = "testing"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockValue {
+    /// Hex scalar, used as is by `GroupOrderElement::new` and as generator ^ scalar by `PointG1::new`/`PointG2::new`
+    Scalar(String),
+    /// Hex point in the `PointG1::to_string` format, returned by `PointG1::new`
+    PointG1(String),
+    /// Hex point in the `PointG2::to_string` format, returned by `PointG2::new`
+    PointG2(String),
+}
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
+enum PairMocks {
+    Constant,
+    Scripted(VecDeque<MockValue>),
+}
+
+#[cfg(any(test, feature = "testing"))]
 thread_local! {
-  pub static PAIR_USE_MOCKS: RefCell<bool> = RefCell::new(false);
+    static PAIR_MOCKS: RefCell<Option<PairMocks>> = const { RefCell::new(None) };
 }
 
-#[cfg(test)]
+/// Makes random constructors of the current thread deterministic.
+#[cfg(any(test, feature = "testing"))]
 pub struct PairMocksHelper {}
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 impl PairMocksHelper {
+    /// Every random draw returns the same hard-coded scalar.
     pub fn inject() {
-        PAIR_USE_MOCKS.with(|use_mocks| {
-            *use_mocks.borrow_mut() = true;
+        PAIR_MOCKS.with(|mocks| {
+            *mocks.borrow_mut() = Some(PairMocks::Constant);
+        });
+    }
+
+    /// Random draws return the given values in order and fail once they are exhausted.
+    pub fn inject_values(values: Vec<MockValue>) {
+        PAIR_MOCKS.with(|mocks| {
+            *mocks.borrow_mut() = Some(PairMocks::Scripted(values.into_iter().collect()));
+        });
+    }
+
+    /// Random draws return the given hex scalars in order and fail once they are exhausted.
+    pub fn inject_scalars(scalars: &[&str]) {
+        PairMocksHelper::inject_values(scalars.iter().map(|scalar| MockValue::Scalar(scalar.to_string())).collect())
+    }
+
+    /// Restores real randomness.
+    pub fn clear() {
+        PAIR_MOCKS.with(|mocks| {
+            *mocks.borrow_mut() = None;
         });
     }
 
     pub fn is_injected() -> bool {
-        PAIR_USE_MOCKS.with(|use_mocks| {
-            return *use_mocks.borrow();
+        PAIR_MOCKS.with(|mocks| {
+            mocks.borrow().is_some()
+        })
+    }
+
+    fn next_scalar() -> Result<Option<BIG>, IndyCryptoError> {
+        match PairMocksHelper::next(|_| true)? {
+            Some(MockValue::Scalar(hex)) => Ok(Some(BIG::from_hex(hex))),
+            Some(value) => Err(IndyCryptoError::InvalidState(
+                format!("Pair mock expected a scalar, got {:?}", value))),
+            None => Ok(None)
+        }
+    }
+
+    fn next_point_g1() -> Result<Option<ECP>, IndyCryptoError> {
+        match PairMocksHelper::next(|value| matches!(value, MockValue::PointG1(_)))? {
+            Some(MockValue::PointG1(hex)) => Ok(Some(ECP::from_hex(hex))),
+            _ => Ok(None)
+        }
+    }
+
+    fn next_point_g2() -> Result<Option<ECP2>, IndyCryptoError> {
+        match PairMocksHelper::next(|value| matches!(value, MockValue::PointG2(_)))? {
+            Some(MockValue::PointG2(hex)) => Ok(Some(ECP2::from_hex(hex))),
+            _ => Ok(None)
+        }
+    }
+
+    // Pops the head of the queue if `accept` agrees, scalars are left for `random_mod_order`
+    fn next<F>(accept: F) -> Result<Option<MockValue>, IndyCryptoError> where F: Fn(&MockValue) -> bool {
+        PAIR_MOCKS.with(|mocks| {
+            match *mocks.borrow_mut() {
+                None => Ok(None),
+                Some(PairMocks::Constant) => {
+                    let value = MockValue::Scalar(DEFAULT_MOCK_SCALAR.to_string());
+                    Ok(if accept(&value) { Some(value) } else { None })
+                }
+                Some(PairMocks::Scripted(ref mut values)) => {
+                    match values.front() {
+                        Some(value) if accept(value) => Ok(values.pop_front()),
+                        Some(_) => Ok(None),
+                        None => Err(IndyCryptoError::InvalidState("Pair mocks queue is exhausted".to_string()))
+                    }
+                }
+            }
         })
     }
 }
 
-#[cfg(not(test))]
+#[cfg(not(any(test, feature = "testing")))]
 fn random_mod_order() -> Result<BIG, IndyCryptoError> {
     _random_mod_order()
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 fn random_mod_order() -> Result<BIG, IndyCryptoError> {
-    if PairMocksHelper::is_injected() {
-        Ok(BIG::from_hex("22EB5716FB01F2122DE924466542B923D8C96F16C9B5FE2C00B7D7DC1499EA50".to_string()))
+    match PairMocksHelper::next_scalar()? {
+        Some(scalar) => Ok(scalar),
+        None => _random_mod_order()
     }
-    else {
-        _random_mod_order()
+}
+
+#[cfg(not(any(test, feature = "testing")))]
+fn mocked_point_g1() -> Result<Option<ECP>, IndyCryptoError> {
+    Ok(None)
+}
+
+#[cfg(any(test, feature = "testing"))]
+fn mocked_point_g1() -> Result<Option<ECP>, IndyCryptoError> {
+    PairMocksHelper::next_point_g1()
+}
+
+#[cfg(not(any(test, feature = "testing")))]
+fn mocked_point_g2() -> Result<Option<ECP2>, IndyCryptoError> {
+    Ok(None)
+}
+
+#[cfg(any(test, feature = "testing"))]
+fn mocked_point_g2() -> Result<Option<ECP2>, IndyCryptoError> {
+    PairMocksHelper::next_point_g2()
+}
+
+/// Source of entropy used to seed the RAND instances. Must fill the whole buffer.
+pub type EntropySource = fn(&mut [u8]) -> Result<(), IndyCryptoError>;
+
+/// Number of draws after which a thread's RAND is reseeded from fresh entropy.
+pub const DEFAULT_RESEED_INTERVAL: usize = 1 << 16;
+
+// AMCL recommends to initialise from at least 128 bytes, check doc for `RAND.seed`
+const ENTROPY_BYTES: usize = 128;
+
+lazy_static! {
+    static ref ENTROPY_SOURCE: RwLock<Option<EntropySource>> = RwLock::new(None);
+}
+
+static RESEED_INTERVAL: AtomicUsize = AtomicUsize::new(DEFAULT_RESEED_INTERVAL);
+// Bumped by `reseed` and by the fork handler, every thread reseeds once it sees a new epoch
+static RESEED_EPOCH: AtomicUsize = AtomicUsize::new(0);
+
+struct RngState {
+    rng: RAND,
+    draws: usize,
+    epoch: usize,
+}
+
+thread_local! {
+    static RNG_STATE: RefCell<Option<RngState>> = const { RefCell::new(None) };
+}
+
+/// Forces every RAND instance to be reseeded from the entropy source before its next draw.
+pub fn reseed() -> Result<(), IndyCryptoError> {
+    RESEED_EPOCH.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Sets the number of draws after which RAND is reseeded automatically.
+pub fn set_reseed_interval(draws: usize) -> Result<(), IndyCryptoError> {
+    if draws == 0 {
+        return Err(IndyCryptoError::InvalidStructure("Reseed interval must be positive".to_string()));
     }
+    RESEED_INTERVAL.store(draws, Ordering::SeqCst);
+    Ok(())
 }
 
-fn _random_mod_order() -> Result<BIG, IndyCryptoError> {
-    let entropy_bytes = 128;
-    let mut seed = vec![0; entropy_bytes];
-    let mut os_rng = OsRng::new().unwrap();
-    os_rng.fill_bytes(&mut seed.as_mut_slice());
+/// Replaces the entropy source used for seeding, `None` restores the OS one.
+/// Takes effect on the next reseed.
+pub fn set_entropy_source(source: Option<EntropySource>) -> Result<(), IndyCryptoError> {
+    let mut entropy_source = ENTROPY_SOURCE.write()
+        .map_err(|_| IndyCryptoError::InvalidState("Entropy source lock is poisoned".to_string()))?;
+    *entropy_source = source;
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn os_entropy(buf: &mut [u8]) -> Result<(), IndyCryptoError> {
+    let mut os_rng = OsRng::new()
+        .map_err(|err| IndyCryptoError::RngFailure(format!("Unable to access OS entropy: {}", err)))?;
+    os_rng.fill_bytes(buf);
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn os_entropy(buf: &mut [u8]) -> Result<(), IndyCryptoError> {
+    getrandom::getrandom(buf)
+        .map_err(|err| IndyCryptoError::RngFailure(format!("Unable to access platform entropy: {}", err)))
+}
+
+#[cfg(all(unix, feature = "fork_safety"))]
+extern "C" fn reseed_in_child() {
+    RESEED_EPOCH.fetch_add(1, Ordering::SeqCst);
+}
+
+#[cfg(all(unix, feature = "fork_safety"))]
+fn register_fork_handler() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| unsafe {
+        libc::pthread_atfork(None, None, Some(reseed_in_child));
+    });
+}
+
+#[cfg(not(all(unix, feature = "fork_safety")))]
+fn register_fork_handler() {}
+
+fn new_rng_state(epoch: usize) -> Result<RngState, IndyCryptoError> {
+    let source = ENTROPY_SOURCE.read()
+        .map_err(|_| IndyCryptoError::InvalidState("Entropy source lock is poisoned".to_string()))?
+        .unwrap_or(os_entropy);
+
+    let mut seed = vec![0; ENTROPY_BYTES];
+    source(&mut seed)?;
+
     let mut rng = RAND::new();
     rng.clean();
-    // AMCL recommends to initialise from at least 128 bytes, check doc for `RAND.seed`
-    rng.seed(entropy_bytes, &seed);
-    Ok(BIG::randomnum(&BIG::new_ints(&CURVE_ORDER), &mut rng))
+    rng.seed(ENTROPY_BYTES, &seed);
+
+    Ok(RngState { rng, draws: 0, epoch })
+}
+
+fn _random_mod_order() -> Result<BIG, IndyCryptoError> {
+    register_fork_handler();
+
+    RNG_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let epoch = RESEED_EPOCH.load(Ordering::SeqCst);
+
+        let expired = match *state {
+            Some(ref state) => state.epoch != epoch || state.draws >= RESEED_INTERVAL.load(Ordering::SeqCst),
+            None => true
+        };
+        if expired {
+            *state = Some(new_rng_state(epoch)?);
+        }
+
+        let state = state.as_mut().unwrap();
+        state.draws += 1;
+        Ok(BIG::randomnum(&BIG::new_ints(&CURVE_ORDER), &mut state.rng))
+    })
+}
+
+// AMCL from_hex panics on empty or non hex tokens, so they are checked before parsing
+fn check_hex(str: &str, with_inf: bool, tokens: usize) -> bool {
+    let mut iter = str.split_whitespace();
+    if with_inf && !matches!(iter.next(), Some("true") | Some("false")) {
+        return false;
+    }
+    let hex = iter.collect::<Vec<&str>>();
+    hex.len() == tokens && hex.iter().all(|token| token.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+const WNAF_WINDOW: usize = 4;
+const WNAF_TABLE_SIZE: usize = 1 << (WNAF_WINDOW - 2);
+
+/// Width-w non-adjacent form of a non-negative scalar, least significant digit first.
+/// Every non-zero digit is odd and lies in (-2^(w-1), 2^(w-1)).
+fn wnaf_digits(mut k: BIG) -> Vec<i8> {
+    let window = 1isize << WNAF_WINDOW;
+    let mut digits = Vec::with_capacity(MODBYTES * 8 + 1);
+
+    k.norm();
+    while !k.iszilch() {
+        let mut digit = 0;
+        if k.parity() == 1 {
+            digit = k.lastbits(WNAF_WINDOW);
+            if digit >= window / 2 {
+                digit -= window;
+            }
+            k.dec(digit);
+            k.norm();
+        }
+        digits.push(digit as i8);
+        k.fshr(1);
+    }
+
+    digits
+}
+
+/// Scalar for the Montgomery ladder: k mod order plus order or twice order, so that
+/// the top bit is always at the same position and the ladder length is scalar independent.
+fn ladder_scalar(e: &GroupOrderElement) -> (BIG, usize) {
+    let mut order = BIG::new_ints(&CURVE_ORDER);
+    let bits = order.nbits();
+
+    let mut k = e.bn;
+    k.rmod(&order);
+    k.add(&order);
+    k.norm();
+
+    let mut k_plus_order = k;
+    k_plus_order.add(&order);
+    k_plus_order.norm();
+
+    k.cmove(&k_plus_order, 1 - k.bit(bits));
+    (k, bits)
+}
+
+// Checks len and prefix of compressed point, returns sign of y or None for the identity
+fn _check_compressed_bytes(b: &[u8], len: usize) -> Result<Option<u8>, IndyCryptoError> {
+    if b.len() != len {
+        return Err(IndyCryptoError::InvalidPoint(
+            "Invalid len of compressed bytes representation".to_string()));
+    }
+
+    match b[0] {
+        0x02 | 0x03 => Ok(Some(b[0] & 0x01)),
+        0x00 if b.iter().all(|byte| *byte == 0) => Ok(None),
+        _ => Err(IndyCryptoError::InvalidPoint(
+            "Invalid prefix of compressed bytes representation".to_string()))
+    }
+}
+
+// Coordinates must be reduced, so every point has the only compressed representation
+fn _field_element_from_bytes(b: &[u8]) -> Result<BIG, IndyCryptoError> {
+    let element = BIG::frombytes(b);
+    if BIG::comp(&element, &BIG::new_ints(&MODULUS)) >= 0 {
+        return Err(IndyCryptoError::InvalidPoint(
+            "Coordinate is not reduced modulo field prime".to_string()));
+    }
+    Ok(element)
+}
+
+fn _fp2_sign(element: &mut FP2) -> u8 {
+    let a = element.geta();
+    let sign = if a.iszilch() { element.getb().parity() } else { a.parity() };
+    sign as u8
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -91,6 +402,7 @@ pub struct PointG1 {
 
 impl PointG1 {
     pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+    pub const COMPRESSED_BYTES_REPR_SIZE: usize = MODBYTES + 1;
 
     /// Creates new random PointG1
     pub fn new() -> Result<PointG1, IndyCryptoError> {
@@ -99,26 +411,39 @@ impl PointG1 {
         let point_y = BIG::new_ints(&CURVE_GY);
         let mut gen_g1 = ECP::new_bigs(&point_x, &point_y);
 
-        let point = g1mul(&mut gen_g1, &mut random_mod_order()?);
+        let point = match mocked_point_g1()? {
+            Some(point) => point,
+            None => g1mul(&mut gen_g1, &mut random_mod_order()?)
+        };
 
         Ok(PointG1 {
             point: point
         })
     }
 
-    /// Creates new infinity PointG1
-    pub fn new_inf() -> Result<PointG1, IndyCryptoError> {
+    /// Creates identity (infinity) PointG1
+    pub fn identity() -> PointG1 {
         let mut r = ECP::new();
         r.inf();
-        Ok(PointG1 {
+        PointG1 {
             point: r
-        })
+        }
+    }
+
+    /// Checks that PointG1 is identity (infinity)
+    pub fn is_identity(&self) -> bool {
+        let mut r = self.point;
+        r.is_infinity()
+    }
+
+    /// Creates new infinity PointG1
+    pub fn new_inf() -> Result<PointG1, IndyCryptoError> {
+        Ok(PointG1::identity())
     }
 
     /// Checks infinity
     pub fn is_inf(&self) -> Result<bool, IndyCryptoError> {
-        let mut r = self.point;
-        Ok(r.is_infinity())
+        Ok(self.is_identity())
     }
 
     /// PointG1 ^ GroupOrderElement
@@ -130,6 +455,73 @@ impl PointG1 {
         })
     }
 
+    /// PointG1 ^ GroupOrderElement computed with a Montgomery ladder.
+    ///
+    /// Performs the same sequence of group operations for every scalar, so it must be
+    /// used whenever the scalar is secret (signing, verification key generation).
+    pub fn mul_ct(&self, e: &GroupOrderElement) -> Result<PointG1, IndyCryptoError> {
+        let (k, bits) = ladder_scalar(e);
+
+        let mut r0 = self.point;
+        let mut r1 = self.point;
+        r1.dbl();
+
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            r0.cswap(&mut r1, bit);
+            r1.add(&mut r0);
+            r0.dbl();
+            r0.cswap(&mut r1, bit);
+        }
+
+        r0.affine();
+        Ok(PointG1 {
+            point: r0
+        })
+    }
+
+    /// PointG1 ^ GroupOrderElement computed with a width-4 windowed NAF.
+    ///
+    /// Faster than `mul` for variable base points, but the running time depends on the scalar.
+    /// Must never be used with secret scalars (sign keys, blinding factors): it is intended
+    /// for public points and public scalars such as batch verification coefficients.
+    pub fn mul_vartime(&self, e: &GroupOrderElement) -> Result<PointG1, IndyCryptoError> {
+        let mut k = e.bn;
+        k.rmod(&BIG::new_ints(&CURVE_ORDER));
+
+        let mut p = self.point;
+        let mut r = ECP::new();
+
+        if k.iszilch() || p.is_infinity() {
+            return Ok(PointG1 {
+                point: r
+            });
+        }
+
+        // Odd multiples P, 3P, 5P, 7P for digits in (-2^(w-1), 2^(w-1))
+        let mut double_p = p;
+        double_p.dbl();
+        let mut table = [p; WNAF_TABLE_SIZE];
+        for i in 1..WNAF_TABLE_SIZE {
+            table[i] = table[i - 1];
+            table[i].add(&mut double_p);
+        }
+
+        for digit in wnaf_digits(k).iter().rev() {
+            r.dbl();
+            if *digit > 0 {
+                r.add(&mut table[(*digit / 2) as usize]);
+            } else if *digit < 0 {
+                r.sub(&mut table[(-*digit / 2) as usize]);
+            }
+        }
+
+        r.affine();
+        Ok(PointG1 {
+            point: r
+        })
+    }
+
     /// PointG1 * PointG1
     pub fn add(&self, q: &PointG1) -> Result<PointG1, IndyCryptoError> {
         let mut r = self.point;
@@ -164,6 +556,10 @@ impl PointG1 {
     }
 
     pub fn from_string(str: &str) -> Result<PointG1, IndyCryptoError> {
+        if !check_hex(str, true, 3) {
+            return Err(IndyCryptoError::InvalidPoint(
+                "Invalid string representation".to_string()));
+        }
         Ok(PointG1 {
             point: ECP::from_hex(str.to_string())
         })
@@ -178,14 +574,53 @@ impl PointG1 {
 
     pub fn from_bytes(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
         if b.len() != Self::BYTES_REPR_SIZE {
-            return Err(IndyCryptoError::InvalidStructure(
+            return Err(IndyCryptoError::InvalidPoint(
                 "Invalid len of bytes representation".to_string()));
         }
-        Ok(
-            PointG1 {
-                point: ECP::frombytes(b)
-            }
-        )
+
+        // AMCL silently returns the identity for coordinates that are not on the curve
+        let point = PointG1 {
+            point: ECP::frombytes(b)
+        };
+        if point.is_identity() && b != PointG1::identity().to_bytes()?.as_slice() {
+            return Err(IndyCryptoError::InvalidPoint(
+                "Point is not on the curve".to_string()));
+        }
+
+        Ok(point)
+    }
+
+    /// Returns compressed bytes representation: sign of y, 0x02 if even and 0x03 if odd,
+    /// followed by x. The identity is all zeros.
+    pub fn to_compressed_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut vec = vec![0u8; Self::COMPRESSED_BYTES_REPR_SIZE];
+        if self.is_identity() {
+            return Ok(vec);
+        }
+
+        let mut point = self.point;
+        vec[0] = 0x02 | point.gets() as u8;
+        point.getx().tobytes(&mut vec[1..]);
+        Ok(vec)
+    }
+
+    pub fn from_compressed_bytes(b: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        let sign = _check_compressed_bytes(b, Self::COMPRESSED_BYTES_REPR_SIZE)?;
+        let sign = match sign {
+            Some(sign) => sign,
+            None => return Ok(PointG1::identity())
+        };
+
+        let x = _field_element_from_bytes(&b[1..])?;
+        let mut point = ECP::new_bigint(&x, sign as isize);
+        if point.is_infinity() {
+            return Err(IndyCryptoError::InvalidPoint(
+                "Point is not on the curve".to_string()));
+        }
+
+        Ok(PointG1 {
+            point
+        })
     }
 
     pub fn from_hash(hash: &[u8]) -> Result<PointG1, IndyCryptoError> {
@@ -201,6 +636,74 @@ impl PointG1 {
             point: point
         })
     }
+
+    /// Maps arbitrary bytes to a uniformly distributed PointG1.
+    ///
+    /// Try-and-increment on the x coordinate: for counter = 0, 1, ..., 255 computes
+    /// h = SHA-512(bytes || counter), takes x = h mod p reading h as a 512-bit big-endian integer,
+    /// and accepts the first x on the curve with the y whose parity equals the top bit of h.
+    /// The point is then multiplied by the cofactor (1 for BN254).
+    ///
+    /// Not compatible with `from_hash`, which is kept for already stored signatures.
+    pub fn map_to_curve(bytes: &[u8]) -> Result<PointG1, IndyCryptoError> {
+        let modulus = BIG::new_ints(&MODULUS);
+
+        for counter in 0..=u8::MAX {
+            let mut hasher = Sha512::default();
+            hasher.input(bytes);
+            hasher.input(&[counter]);
+            let hash = hasher.result();
+
+            let x = PointG1::_field_element_from_hash(hash.as_slice(), &modulus);
+            let mut point = ECP::new_bigint(&x, (hash[0] >> 7) as isize);
+
+            if !point.is_infinity() {
+                let mut point = point.mul(&mut BIG::new_ints(&CURVE_COF));
+                point.affine();
+                return Ok(PointG1 {
+                    point
+                });
+            }
+        }
+
+        Err(IndyCryptoError::InvalidState("Unable to map bytes to curve".to_string()))
+    }
+
+    // (hi * 2^256 + lo) mod p for a 64 bytes hash = hi || lo
+    fn _field_element_from_hash(hash: &[u8], modulus: &BIG) -> BIG {
+        let mut hi = BIG::frombytes(&hash[..MODBYTES]);
+        let mut lo = BIG::frombytes(&hash[MODBYTES..]);
+
+        let mut two_pow_256 = DBIG::new_scopy(&BIG::new_int(1));
+        two_pow_256.shl(MODBYTES * 8);
+        let mut two_pow_256 = two_pow_256.dmod(modulus);
+
+        let mut x = BIG::modmul(&mut hi, &mut two_pow_256, modulus);
+        lo.rmod(modulus);
+        x.add(&lo);
+        x.norm();
+        x.rmod(modulus);
+        x
+    }
+}
+
+impl Default for PointG1 {
+    fn default() -> PointG1 {
+        PointG1::identity()
+    }
+}
+
+impl Sum for PointG1 {
+    fn sum<I: Iterator<Item=PointG1>>(iter: I) -> PointG1 {
+        iter.fold(PointG1::identity(), |sum, q| {
+            let mut r = sum.point;
+            let mut point = q.point;
+            r.add(&mut point);
+            PointG1 {
+                point: r
+            }
+        })
+    }
 }
 
 impl Debug for PointG1 {
@@ -212,6 +715,9 @@ impl Debug for PointG1 {
 #[cfg(feature = "serialization")]
 impl Serialize for PointG1 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.to_bytes().map_err(SError::custom)?);
+        }
         serializer.serialize_newtype_struct("PointG1", &self.to_string().map_err(SError::custom)?)
     }
 }
@@ -233,9 +739,19 @@ impl<'a> Deserialize<'a> for PointG1 {
             {
                 Ok(PointG1::from_string(value).map_err(DError::custom)?)
             }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<PointG1, E>
+                where E: DError
+            {
+                PointG1::from_bytes(value).map_err(DError::custom)
+            }
         }
 
-        deserializer.deserialize_str(PointG1Visitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PointG1Visitor)
+        } else {
+            deserializer.deserialize_bytes(PointG1Visitor)
+        }
     }
 }
 
@@ -246,9 +762,30 @@ pub struct PointG2 {
 
 impl PointG2 {
     pub const BYTES_REPR_SIZE: usize = MODBYTES * 4;
+    pub const COMPRESSED_BYTES_REPR_SIZE: usize = MODBYTES * 2 + 1;
 
     /// Creates new random PointG2
     pub fn new() -> Result<PointG2, IndyCryptoError> {
+        let mut gen_g2 = PointG2::_base();
+
+        let point = match mocked_point_g2()? {
+            Some(point) => point,
+            None => g2mul(&mut gen_g2, &mut random_mod_order()?)
+        };
+
+        Ok(PointG2 {
+            point: point
+        })
+    }
+
+    /// Returns the fixed generator of G2 defined by the curve parameters
+    pub fn base() -> PointG2 {
+        PointG2 {
+            point: PointG2::_base()
+        }
+    }
+
+    fn _base() -> ECP2 {
         let point_xa = BIG::new_ints(&CURVE_PXA);
         let point_xb = BIG::new_ints(&CURVE_PXB);
         let point_ya = BIG::new_ints(&CURVE_PYA);
@@ -257,23 +794,33 @@ impl PointG2 {
         let point_x = FP2::new_bigs(&point_xa, &point_xb);
         let point_y = FP2::new_bigs(&point_ya, &point_yb);
 
-        let mut gen_g2 = ECP2::new_fp2s(&point_x, &point_y);
+        ECP2::new_fp2s(&point_x, &point_y)
+    }
+
+    /// Creates identity (infinity) PointG2
+    pub fn identity() -> PointG2 {
+        let mut point = ECP2::new();
+        point.inf();
 
-        let point = g2mul(&mut gen_g2, &mut random_mod_order()?);
+        PointG2 {
+            point
+        }
+    }
 
-        Ok(PointG2 {
-            point: point
-        })
+    /// Checks that PointG2 is identity (infinity)
+    pub fn is_identity(&self) -> bool {
+        let mut r = self.point;
+        r.is_infinity()
     }
 
     /// Creates new infinity PointG2
     pub fn new_inf() -> Result<PointG2, IndyCryptoError> {
-        let mut point = ECP2::new();
-        point.inf();
+        Ok(PointG2::identity())
+    }
 
-        Ok(PointG2 {
-            point: point
-        })
+    /// Checks infinity
+    pub fn is_inf(&self) -> Result<bool, IndyCryptoError> {
+        Ok(self.is_identity())
     }
 
     /// PointG2 * PointG2
@@ -307,11 +854,60 @@ impl PointG2 {
         })
     }
 
+    /// PointG2 ^ GroupOrderElement computed with a Montgomery ladder.
+    ///
+    /// Performs the same sequence of group operations for every scalar, so it must be
+    /// used whenever the scalar is secret (verification key generation).
+    pub fn mul_ct(&self, e: &GroupOrderElement) -> Result<PointG2, IndyCryptoError> {
+        let (k, bits) = ladder_scalar(e);
+
+        let mut r0 = self.point;
+        let mut r1 = self.point;
+        r1.dbl();
+
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            PointG2::cswap(&mut r0, &mut r1, bit);
+            r1.add(&mut r0);
+            r0.dbl();
+            PointG2::cswap(&mut r0, &mut r1, bit);
+        }
+
+        r0.affine();
+        Ok(PointG2 {
+            point: r0
+        })
+    }
+
+    // ECP2 has no cswap, build it from two conditional moves.
+    // ECP2::cmove drops the infinity flag when d == 0, so the flags are restored afterwards;
+    // that branch is only taken for the identity, which never appears for a valid sign key.
+    fn cswap(a: &mut ECP2, b: &mut ECP2, d: isize) {
+        let a_inf = a.is_infinity() as isize;
+        let b_inf = b.is_infinity() as isize;
+        let mask = (a_inf ^ b_inf) & d;
+
+        let t = *a;
+        a.cmove(b, d);
+        b.cmove(&t, d);
+
+        if a_inf ^ mask == 1 {
+            a.inf();
+        }
+        if b_inf ^ mask == 1 {
+            b.inf();
+        }
+    }
+
     pub fn to_string(&self) -> Result<String, IndyCryptoError> {
         Ok(self.point.to_hex())
     }
 
     pub fn from_string(str: &str) -> Result<PointG2, IndyCryptoError> {
+        if !check_hex(str, true, 6) {
+            return Err(IndyCryptoError::InvalidPoint(
+                "Invalid string representation".to_string()));
+        }
         Ok(PointG2 {
             point: ECP2::from_hex(str.to_string())
         })
@@ -326,14 +922,80 @@ impl PointG2 {
 
     pub fn from_bytes(b: &[u8]) -> Result<PointG2, IndyCryptoError> {
         if b.len() != Self::BYTES_REPR_SIZE {
-            return Err(IndyCryptoError::InvalidStructure(
+            return Err(IndyCryptoError::InvalidPoint(
                 "Invalid len of bytes representation".to_string()));
         }
-        Ok(
+
+        // AMCL silently returns the identity for coordinates that are not on the curve
+        let point = PointG2 {
+            point: ECP2::frombytes(b)
+        };
+        if point.is_identity() && b != PointG2::identity().to_bytes()?.as_slice() {
+            return Err(IndyCryptoError::InvalidPoint(
+                "Point is not on the curve".to_string()));
+        }
+
+        Ok(point)
+    }
+
+    /// Returns compressed bytes representation: sign of y, 0x02 if even and 0x03 if odd,
+    /// followed by x. The identity is all zeros.
+    ///
+    /// Sign of y is the parity of its first component, or of the second one if the first is zero.
+    pub fn to_compressed_bytes(&self) -> Result<Vec<u8>, IndyCryptoError> {
+        let mut vec = vec![0u8; Self::COMPRESSED_BYTES_REPR_SIZE];
+        if self.is_identity() {
+            return Ok(vec);
+        }
+
+        let mut point = self.point;
+        let mut x = point.getx();
+        vec[0] = 0x02 | _fp2_sign(&mut point.gety());
+        x.geta().tobytes(&mut vec[1..MODBYTES + 1]);
+        x.getb().tobytes(&mut vec[MODBYTES + 1..]);
+        Ok(vec)
+    }
+
+    pub fn from_compressed_bytes(b: &[u8]) -> Result<PointG2, IndyCryptoError> {
+        let sign = _check_compressed_bytes(b, Self::COMPRESSED_BYTES_REPR_SIZE)?;
+        let sign = match sign {
+            Some(sign) => sign,
+            None => return Ok(PointG2::identity())
+        };
+
+        let x = FP2::new_bigs(&_field_element_from_bytes(&b[1..MODBYTES + 1])?,
+                              &_field_element_from_bytes(&b[MODBYTES + 1..])?);
+        let mut point = ECP2::new_fp2(&x);
+        if point.is_infinity() {
+            return Err(IndyCryptoError::InvalidPoint(
+                "Point is not on the curve".to_string()));
+        }
+        if _fp2_sign(&mut point.gety()) != sign {
+            point.neg();
+        }
+
+        Ok(PointG2 {
+            point
+        })
+    }
+}
+
+impl Default for PointG2 {
+    fn default() -> PointG2 {
+        PointG2::identity()
+    }
+}
+
+impl Sum for PointG2 {
+    fn sum<I: Iterator<Item=PointG2>>(iter: I) -> PointG2 {
+        iter.fold(PointG2::identity(), |sum, q| {
+            let mut r = sum.point;
+            let mut point = q.point;
+            r.add(&mut point);
             PointG2 {
-                point: ECP2::frombytes(b)
+                point: r
             }
-        )
+        })
     }
 }
 
@@ -346,6 +1008,9 @@ impl Debug for PointG2 {
 #[cfg(feature = "serialization")]
 impl Serialize for PointG2 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.to_bytes().map_err(SError::custom)?);
+        }
         serializer.serialize_newtype_struct("PointG2", &self.to_string().map_err(SError::custom)?)
     }
 }
@@ -367,9 +1032,19 @@ impl<'a> Deserialize<'a> for PointG2 {
             {
                 Ok(PointG2::from_string(value).map_err(DError::custom)?)
             }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<PointG2, E>
+                where E: DError
+            {
+                PointG2::from_bytes(value).map_err(DError::custom)
+            }
         }
 
-        deserializer.deserialize_str(PointG2Visitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PointG2Visitor)
+        } else {
+            deserializer.deserialize_bytes(PointG2Visitor)
+        }
     }
 }
 
@@ -424,21 +1099,8 @@ impl GroupOrderElement {
 
     /// (GroupOrderElement - GroupOrderElement) mod GroupOrder
     pub fn sub_mod(&self, r: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
-        //need to use modneg if sub is negative
-        let mut diff = self.bn;
-        diff.sub(&r.bn);
-        let mut zero = BIG::new();
-        zero.zero();
-
-        if diff < zero {
-            return Ok(GroupOrderElement {
-                bn: BIG::modneg(&mut diff, &BIG::new_ints(&CURVE_ORDER))
-            });
-        }
-
-        Ok(GroupOrderElement {
-            bn: diff
-        })
+        // a - b = a + (GroupOrder - b), so the difference never goes negative
+        self.add_mod(&r.mod_neg()?)
     }
 
     /// (GroupOrderElement * GroupOrderElement) mod GroupOrder
@@ -460,6 +1122,42 @@ impl GroupOrderElement {
         })
     }
 
+    /// (GroupOrderElement / GroupOrderElement) mod GroupOrder
+    pub fn div_mod(&self, rhs: &GroupOrderElement) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut divisor = rhs.bn;
+        divisor.rmod(&BIG::new_ints(&CURVE_ORDER));
+        if divisor.iszilch() {
+            return Err(IndyCryptoError::InvalidStructure("Division by zero".to_string()));
+        }
+        self.mul_mod(&rhs.inverse()?)
+    }
+
+    /// (GroupOrderElement ^ u64) mod GroupOrder
+    pub fn pow_u64(&self, e: u64) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut base = self.bn;
+        let mut result = BIG::new_int(1);
+        let order = BIG::new_ints(&CURVE_ORDER);
+        let mut e = e;
+
+        while e > 0 {
+            if e & 1 == 1 {
+                result = BIG::modmul(&mut result, &mut base, &order);
+            }
+            base = BIG::modsqr(&mut base, &order);
+            e >>= 1;
+        }
+
+        Ok(GroupOrderElement {
+            bn: result
+        })
+    }
+
+    /// Overwrites the element with zero, the write is not optimized away.
+    pub fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(&mut self.bn, BIG::new()) };
+        compiler_fence(Ordering::SeqCst);
+    }
+
     /// - GroupOrderElement mod GroupOrder
     pub fn mod_neg(&self) -> Result<GroupOrderElement, IndyCryptoError> {
         let mut r = self.bn;
@@ -475,6 +1173,10 @@ impl GroupOrderElement {
     }
 
     pub fn from_string(str: &str) -> Result<GroupOrderElement, IndyCryptoError> {
+        if !check_hex(str, false, 1) {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid string representation".to_string()));
+        }
         Ok(GroupOrderElement {
             bn: BIG::from_hex(str.to_string())
         })
@@ -510,6 +1212,45 @@ impl GroupOrderElement {
             }
         )
     }
+
+    /// Canonical big-endian encoding: the element is reduced mod GroupOrder and written as
+    /// exactly MODBYTES (32) bytes, most significant byte first, left padded with zeros.
+    pub fn to_be_bytes(&self) -> [u8; MODBYTES] {
+        let mut bn = self.bn;
+        bn.rmod(&BIG::new_ints(&CURVE_ORDER));
+        let mut bytes = [0u8; MODBYTES];
+        bn.tobytes(&mut bytes);
+        bytes
+    }
+
+    /// Canonical little-endian encoding: the element is reduced mod GroupOrder and written as
+    /// exactly MODBYTES (32) bytes, least significant byte first, right padded with zeros.
+    pub fn to_le_bytes(&self) -> [u8; MODBYTES] {
+        let mut bytes = self.to_be_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Decodes exactly MODBYTES (32) bytes, most significant byte first.
+    /// Fails if the encoded value is not less than GroupOrder.
+    pub fn from_be_bytes(bytes: &[u8; MODBYTES]) -> Result<GroupOrderElement, IndyCryptoError> {
+        let bn = BIG::frombytes(bytes);
+        if BIG::comp(&bn, &BIG::new_ints(&CURVE_ORDER)) >= 0 {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Value is not less than the group order".to_string()));
+        }
+        Ok(GroupOrderElement {
+            bn
+        })
+    }
+
+    /// Decodes exactly MODBYTES (32) bytes, least significant byte first.
+    /// Fails if the encoded value is not less than GroupOrder.
+    pub fn from_le_bytes(bytes: &[u8; MODBYTES]) -> Result<GroupOrderElement, IndyCryptoError> {
+        let mut be_bytes = *bytes;
+        be_bytes.reverse();
+        GroupOrderElement::from_be_bytes(&be_bytes)
+    }
 }
 
 impl Debug for GroupOrderElement {
@@ -522,7 +1263,18 @@ impl Debug for GroupOrderElement {
 #[cfg(feature = "serialization")]
 impl Serialize for GroupOrderElement {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        serializer.serialize_newtype_struct("GroupOrderElement", &self.to_string().map_err(SError::custom)?)
+        if !serializer.is_human_readable() {
+            let mut bytes = self.to_bytes().map_err(SError::custom)?;
+            let res = serializer.serialize_bytes(&bytes);
+            zeroize(&mut bytes);
+            return res;
+        }
+
+        let mut hex = self.to_string().map_err(SError::custom)?;
+        let res = serializer.serialize_newtype_struct("GroupOrderElement", &hex);
+        // Elements may be secret, so the temporary representation is wiped
+        zeroize(unsafe { hex.as_bytes_mut() });
+        res
     }
 }
 
@@ -543,9 +1295,19 @@ impl<'a> Deserialize<'a> for GroupOrderElement {
             {
                 Ok(GroupOrderElement::from_string(value).map_err(DError::custom)?)
             }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<GroupOrderElement, E>
+                where E: DError
+            {
+                GroupOrderElement::from_bytes(value).map_err(DError::custom)
+            }
         }
 
-        deserializer.deserialize_str(GroupOrderElementVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(GroupOrderElementVisitor)
+        } else {
+            deserializer.deserialize_bytes(GroupOrderElementVisitor)
+        }
     }
 }
 
@@ -603,6 +1365,10 @@ impl Pair {
     }
 
     pub fn from_string(str: &str) -> Result<Pair, IndyCryptoError> {
+        if !check_hex(str, false, 12) {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid string representation".to_string()));
+        }
         Ok(Pair {
             pair: FP12::from_hex(str.to_string())
         })
@@ -614,6 +1380,16 @@ impl Pair {
         r.tobytes(&mut vec);
         Ok(vec)
     }
+
+    pub fn from_bytes(b: &[u8]) -> Result<Pair, IndyCryptoError> {
+        if b.len() != Self::BYTES_REPR_SIZE {
+            return Err(IndyCryptoError::InvalidStructure(
+                "Invalid len of bytes representation".to_string()));
+        }
+        Ok(Pair {
+            pair: FP12::frombytes(b)
+        })
+    }
 }
 
 impl Debug for Pair {
@@ -625,6 +1401,9 @@ impl Debug for Pair {
 #[cfg(feature = "serialization")]
 impl Serialize for Pair {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.to_bytes().map_err(SError::custom)?);
+        }
         serializer.serialize_newtype_struct("Pair", &self.to_string().map_err(SError::custom)?)
     }
 }
@@ -646,9 +1425,19 @@ impl<'a> Deserialize<'a> for Pair {
             {
                 Ok(Pair::from_string(value).map_err(DError::custom)?)
             }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Pair, E>
+                where E: DError
+            {
+                Pair::from_bytes(value).map_err(DError::custom)
+            }
         }
 
-        deserializer.deserialize_str(PairVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PairVisitor)
+        } else {
+            deserializer.deserialize_bytes(PairVisitor)
+        }
     }
 }
 
@@ -658,10 +1447,384 @@ mod tests {
     use crate::errors::ToErrorCode;
     use crate::errors::ErrorCode;
 
+    fn fixed_entropy(buf: &mut [u8]) -> Result<(), IndyCryptoError> {
+        for (i, b) in buf.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn random_mod_order_works_for_sequential_draws() {
+        let first = _random_mod_order().unwrap();
+        let second = _random_mod_order().unwrap();
+        assert_ne!(0, BIG::comp(&first, &second));
+    }
+
+    // Entropy source is process wide, tests drawing randomness concurrently would be seeded
+    // by the fixed one, so it is tested in a separate process
+    const ENTROPY_SOURCE_TEST_ENV: &str = "INDY_CRYPTO_ENTROPY_SOURCE_TEST";
+
+    // Restores the OS entropy source also if the test fails
+    struct EntropySourceReset;
+
+    impl Drop for EntropySourceReset {
+        fn drop(&mut self) {
+            set_entropy_source(None).unwrap();
+            reseed().unwrap();
+        }
+    }
+
+    #[test]
+    fn reseed_works_for_fixed_entropy_source() {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(&["--exact", "pair::tests::reseed_works_for_fixed_entropy_source_in_process", "--test-threads=1"])
+            .env(ENTROPY_SOURCE_TEST_ENV, "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+        assert!(stdout.contains("1 passed"), "{}", stdout);
+    }
+
+    #[test]
+    fn reseed_works_for_fixed_entropy_source_in_process() {
+        if std::env::var(ENTROPY_SOURCE_TEST_ENV).is_err() {
+            return;
+        }
+
+        let reset = EntropySourceReset;
+        set_entropy_source(Some(fixed_entropy)).unwrap();
+
+        reseed().unwrap();
+        let first = _random_mod_order().unwrap();
+        let second = _random_mod_order().unwrap();
+
+        reseed().unwrap();
+        let after_reseed = _random_mod_order().unwrap();
+
+        drop(reset);
+
+        assert_ne!(0, BIG::comp(&first, &second));
+        assert_ne!(0, BIG::comp(&second, &after_reseed));
+        assert_eq!(0, BIG::comp(&first, &after_reseed));
+    }
+
+    #[test]
+    fn group_order_element_sub_mod_works() {
+        let order = BIG::new_ints(&CURVE_ORDER);
+        for _ in 0..16 {
+            let a = GroupOrderElement::new().unwrap();
+            let b = GroupOrderElement::new().unwrap();
+
+            let diff = a.sub_mod(&b).unwrap();
+            assert!(BIG::comp(&diff.bn, &order) < 0);
+            assert_eq!(a.to_bytes().unwrap(), diff.add_mod(&b).unwrap().to_bytes().unwrap());
+            assert_eq!(diff.to_bytes().unwrap(), b.sub_mod(&a).unwrap().mod_neg().unwrap().to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn group_order_element_sub_mod_works_for_negative_difference() {
+        let two = GroupOrderElement { bn: BIG::new_int(2) };
+        let three = GroupOrderElement { bn: BIG::new_int(3) };
+
+        let diff = two.sub_mod(&three).unwrap();
+        assert_eq!(two, diff.add_mod(&three).unwrap());
+        assert_eq!(GroupOrderElement { bn: BIG::new_int(1) }.mod_neg().unwrap().to_be_bytes(), diff.to_be_bytes());
+        assert_eq!(GroupOrderElement { bn: BIG::new_int(1) }, three.sub_mod(&two).unwrap());
+        assert_eq!(GroupOrderElement { bn: BIG::new_int(0) }.to_be_bytes(), two.sub_mod(&two).unwrap().to_be_bytes());
+    }
+
+    #[test]
+    fn group_order_element_be_le_bytes_work_for_one() {
+        let one = GroupOrderElement { bn: BIG::new_int(1) };
+
+        let be = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+        let le = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+        assert_eq!(be, one.to_be_bytes());
+        assert_eq!(le, one.to_le_bytes());
+        assert_eq!(one, GroupOrderElement::from_be_bytes(&be).unwrap());
+        assert_eq!(one, GroupOrderElement::from_le_bytes(&le).unwrap());
+    }
+
+    #[test]
+    fn group_order_element_be_le_bytes_work_for_two_pow_64() {
+        let two_pow_64 = GroupOrderElement::from_string("010000000000000000").unwrap();
+
+        let be = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let le = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+        assert_eq!(be, two_pow_64.to_be_bytes());
+        assert_eq!(le, two_pow_64.to_le_bytes());
+        assert_eq!(two_pow_64, GroupOrderElement::from_be_bytes(&be).unwrap());
+        assert_eq!(two_pow_64, GroupOrderElement::from_le_bytes(&le).unwrap());
+    }
+
+    #[test]
+    fn group_order_element_from_be_le_bytes_work_for_out_of_range() {
+        let mut order = [0u8; MODBYTES];
+        BIG::new_ints(&CURVE_ORDER).tobytes(&mut order);
+        let max = [0xFFu8; MODBYTES];
+
+        assert!(GroupOrderElement::from_be_bytes(&order).is_err());
+        assert!(GroupOrderElement::from_be_bytes(&max).is_err());
+        order.reverse();
+        assert!(GroupOrderElement::from_le_bytes(&order).is_err());
+    }
+
+    #[test]
+    fn from_string_works_for_malformed_hex() {
+        for str in &["", "garbage", "false", "false 12 34", "maybe 1 2 3", "false 1 2 Z", "false 1 2 \u{e9}"] {
+            assert_eq!(ErrorCode::CryptoInvalidPoint, PointG1::from_string(str).unwrap_err().to_error_code());
+        }
+        assert_eq!(ErrorCode::CryptoInvalidPoint, PointG2::from_string("false 1 2 3").unwrap_err().to_error_code());
+        assert_eq!(ErrorCode::CommonInvalidStructure, GroupOrderElement::from_string("").unwrap_err().to_error_code());
+        assert_eq!(ErrorCode::CommonInvalidStructure, GroupOrderElement::from_string("1 2").unwrap_err().to_error_code());
+        assert_eq!(ErrorCode::CommonInvalidStructure, Pair::from_string("1 2 3").unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn from_string_works_for_to_string_output() {
+        let point = PointG1::new().unwrap();
+        assert_eq!(point.to_bytes().unwrap(), PointG1::from_string(&point.to_string().unwrap()).unwrap().to_bytes().unwrap());
+        let point = PointG2::new().unwrap();
+        assert_eq!(point.to_bytes().unwrap(), PointG2::from_string(&point.to_string().unwrap()).unwrap().to_bytes().unwrap());
+        let pair = Pair::pair(&PointG1::new().unwrap(), &PointG2::new().unwrap()).unwrap();
+        assert_eq!(pair.to_bytes().unwrap(), Pair::from_string(&pair.to_string().unwrap()).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn point_g1_from_bytes_works_for_off_curve_point() {
+        let mut bytes = PointG1::new().unwrap().to_bytes().unwrap();
+        bytes[10] ^= 0x01;
+
+        let res = PointG1::from_bytes(&bytes);
+        assert_eq!(IndyCryptoError::InvalidPoint("Point is not on the curve".to_string()), res.unwrap_err());
+    }
+
+    #[test]
+    fn point_g2_from_bytes_works_for_off_curve_point() {
+        let mut bytes = PointG2::new().unwrap().to_bytes().unwrap();
+        bytes[10] ^= 0x01;
+
+        let res = PointG2::from_bytes(&bytes);
+        assert_eq!(IndyCryptoError::InvalidPoint("Point is not on the curve".to_string()), res.unwrap_err());
+    }
+
+    #[test]
+    fn point_g1_from_compressed_bytes_works() {
+        for point in [PointG1::new().unwrap(), PointG1::new().unwrap().neg().unwrap(), PointG1::identity()] {
+            let bytes = point.to_compressed_bytes().unwrap();
+            assert_eq!(PointG1::COMPRESSED_BYTES_REPR_SIZE, bytes.len());
+            assert_eq!(point.to_bytes().unwrap(), PointG1::from_compressed_bytes(&bytes).unwrap().to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn point_g2_from_compressed_bytes_works() {
+        let point = PointG2::new().unwrap();
+        let mut neg = point.point;
+        neg.neg();
+        for point in [point, PointG2 { point: neg }, PointG2::base(), PointG2::identity()] {
+            let bytes = point.to_compressed_bytes().unwrap();
+            assert_eq!(PointG2::COMPRESSED_BYTES_REPR_SIZE, bytes.len());
+            assert_eq!(point.to_bytes().unwrap(), PointG2::from_compressed_bytes(&bytes).unwrap().to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn point_from_compressed_bytes_works_for_invalid_bytes() {
+        let bytes = PointG1::new().unwrap().to_compressed_bytes().unwrap();
+        assert_eq!(IndyCryptoError::InvalidPoint("Invalid len of compressed bytes representation".to_string()),
+                   PointG1::from_compressed_bytes(&bytes[1..]).unwrap_err());
+
+        let mut invalid_prefix = bytes.clone();
+        invalid_prefix[0] = 0x04;
+        assert_eq!(IndyCryptoError::InvalidPoint("Invalid prefix of compressed bytes representation".to_string()),
+                   PointG1::from_compressed_bytes(&invalid_prefix).unwrap_err());
+
+        let mut unreduced = bytes.clone();
+        unreduced[1..].copy_from_slice(&[0xff; MODBYTES]);
+        assert_eq!(IndyCryptoError::InvalidPoint("Coordinate is not reduced modulo field prime".to_string()),
+                   PointG1::from_compressed_bytes(&unreduced).unwrap_err());
+
+        let mut bytes = PointG2::new().unwrap().to_compressed_bytes().unwrap();
+        bytes[0] = 0x00;
+        assert_eq!(IndyCryptoError::InvalidPoint("Invalid prefix of compressed bytes representation".to_string()),
+                   PointG2::from_compressed_bytes(&bytes).unwrap_err());
+    }
+
+    #[test]
+    fn point_from_compressed_bytes_works_for_off_curve_point() {
+        // About half of x coordinates have no point on the curve
+        let mut bytes = vec![0u8; PointG1::COMPRESSED_BYTES_REPR_SIZE];
+        bytes[0] = 0x02;
+        let res = (1..=u8::MAX)
+            .map(|x| {
+                bytes[PointG1::COMPRESSED_BYTES_REPR_SIZE - 1] = x;
+                PointG1::from_compressed_bytes(&bytes)
+            })
+            .find(Result::is_err)
+            .unwrap();
+        assert_eq!(IndyCryptoError::InvalidPoint("Point is not on the curve".to_string()), res.unwrap_err());
+    }
+
+    #[test]
+    fn from_bytes_to_bytes_works_for_pair() {
+        let pair = Pair::pair(&PointG1::new().unwrap(), &PointG2::new().unwrap()).unwrap();
+        let bytes = pair.to_bytes().unwrap();
+        let result = Pair::from_bytes(&bytes).unwrap();
+        assert_eq!(pair, result);
+        assert_eq!(bytes, result.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn from_bytes_works_for_invalid_len_of_pair() {
+        let res = Pair::from_bytes(&[0; 32]);
+        assert_eq!(IndyCryptoError::InvalidStructure("Invalid len of bytes representation".to_string()), res.unwrap_err());
+    }
+
+    #[test]
+    fn point_from_bytes_works_for_identity() {
+        let bytes = PointG1::identity().to_bytes().unwrap();
+        assert!(PointG1::from_bytes(&bytes).unwrap().is_identity());
+
+        let bytes = PointG2::identity().to_bytes().unwrap();
+        assert!(PointG2::from_bytes(&bytes).unwrap().is_identity());
+    }
+
+    #[test]
+    fn map_to_curve_works_for_empty_bytes() {
+        let expected = PointG1::from_string("false 00BA44CE7A6FBA965EE72CA683A57A8C374D3E7819CF6E776FCFD76E3348FCE8 04D21EA07ADBCA5770DB8380D5B0753D3284D5D42156927DDC34A57A85DBBFBE 095E45DDF417D05FB10933FFC63D474548B7FFFF7888802F07FFFFFF7D07A8A8").unwrap();
+        assert_eq!(expected, PointG1::map_to_curve(b"").unwrap());
+    }
+
+    #[test]
+    fn map_to_curve_works_for_abc() {
+        let expected = PointG1::from_string("false 0374BF50B7161F8B4C7DED9C3F9225763CED55CDED0C074173D82C4ADAC175E1 220F2DF7BC9D70C1F837A8AA9C8ED1C06CD5585A318BB9C66A5B046AB9BF72EF 095E45DDF417D05FB10933FFC63D474548B7FFFF7888802F07FFFFFF7D07A8A8").unwrap();
+        assert_eq!(expected, PointG1::map_to_curve(b"abc").unwrap());
+    }
+
+    #[test]
+    fn map_to_curve_works_for_different_bytes() {
+        let point = PointG1::map_to_curve(&[1, 2, 3]).unwrap();
+        assert!(!point.is_identity());
+        assert_ne!(point, PointG1::map_to_curve(&[1, 2, 4]).unwrap());
+        assert_ne!(point, PointG1::from_hash(&[1, 2, 3]).unwrap());
+    }
+
+    #[test]
+    fn pair_mocks_work_for_constant() {
+        PairMocksHelper::inject();
+        let first = GroupOrderElement::new().unwrap();
+        let second = GroupOrderElement::new().unwrap();
+        PairMocksHelper::clear();
+
+        assert_eq!(first, second);
+        assert!(!PairMocksHelper::is_injected());
+    }
+
+    #[test]
+    fn pair_mocks_work_for_scripted_values() {
+        let point_g1 = PointG1::new().unwrap();
+        let point_g2 = PointG2::new().unwrap();
+
+        PairMocksHelper::inject_values(vec![
+            MockValue::Scalar("01".to_string()),
+            MockValue::PointG1(point_g1.to_string().unwrap()),
+            MockValue::PointG2(point_g2.to_string().unwrap()),
+            MockValue::Scalar("02".to_string()),
+        ]);
+        let element = GroupOrderElement::new().unwrap();
+        let mocked_g1 = PointG1::new().unwrap();
+        let mocked_g2 = PointG2::new().unwrap();
+        let from_scalar = PointG1::new().unwrap();
+        PairMocksHelper::clear();
+
+        // Scalar queued for a point is the multiplier of the curve generator, so "02" gives g + g
+        let generator = PointG1 { point: ECP::new_bigs(&BIG::new_ints(&CURVE_GX), &BIG::new_ints(&CURVE_GY)) };
+        assert_eq!(GroupOrderElement { bn: BIG::new_int(1) }, element);
+        assert_eq!(point_g1, mocked_g1);
+        assert_eq!(point_g2, mocked_g2);
+        assert_eq!(generator.add(&generator).unwrap().to_bytes().unwrap(), from_scalar.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn pair_mocks_work_for_exhausted_queue() {
+        PairMocksHelper::inject_scalars(&["01"]);
+        GroupOrderElement::new().unwrap();
+        let res = GroupOrderElement::new();
+        PairMocksHelper::clear();
+
+        assert_eq!(ErrorCode::CommonInvalidState, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn pair_mocks_work_for_unexpected_value() {
+        PairMocksHelper::inject_values(vec![MockValue::PointG2(PointG2::new().unwrap().to_string().unwrap())]);
+        let res = PointG1::new();
+        PairMocksHelper::clear();
+
+        assert_eq!(ErrorCode::CommonInvalidState, res.unwrap_err().to_error_code());
+    }
+
+    #[test]
+    fn set_reseed_interval_works_for_zero() {
+        assert!(set_reseed_interval(0).is_err());
+    }
+
     #[test]
     fn group_order_element_new_from_seed_works_for_invalid_seed_len() {
         let err = GroupOrderElement::new_from_seed(&[0, 1, 2]).unwrap_err();
-        assert_eq!(err.to_error_code(), ErrorCode::CommonInvalidStructure);
+        assert_eq!(IndyCryptoError::InvalidStructure(format!("Invalid len of seed: expected {}, actual 3", MODBYTES)), err);
+    }
+
+    #[test]
+    fn group_order_element_div_mod_works() {
+        for _ in 0..16 {
+            let a = GroupOrderElement::new().unwrap();
+            let b = GroupOrderElement::new().unwrap();
+            assert_eq!(a.to_bytes().unwrap(), a.div_mod(&b).unwrap().mul_mod(&b).unwrap().to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn group_order_element_div_mod_works_for_one() {
+        let a = GroupOrderElement::new().unwrap();
+        let one = GroupOrderElement { bn: BIG::new_int(1) };
+        assert_eq!(a.to_bytes().unwrap(), a.div_mod(&one).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn group_order_element_div_mod_works_for_zero() {
+        let a = GroupOrderElement::new().unwrap();
+        let zero = GroupOrderElement { bn: BIG::new() };
+        let err = a.div_mod(&zero).unwrap_err();
+        assert_eq!(IndyCryptoError::InvalidStructure("Division by zero".to_string()), err);
+    }
+
+    #[test]
+    fn group_order_element_pow_u64_works() {
+        let a = GroupOrderElement::new().unwrap();
+        let expected = a.mul_mod(&a).unwrap().mul_mod(&a).unwrap().mul_mod(&a).unwrap().mul_mod(&a).unwrap();
+        assert_eq!(expected.to_bytes().unwrap(), a.pow_u64(5).unwrap().to_bytes().unwrap());
+        assert_eq!(GroupOrderElement { bn: BIG::new_int(1) }.to_bytes().unwrap(), a.pow_u64(0).unwrap().to_bytes().unwrap());
+        assert_eq!(a.pow_mod(&GroupOrderElement { bn: BIG::new_int(65537) }).unwrap().to_bytes().unwrap(),
+                   a.pow_u64(65537).unwrap().to_bytes().unwrap());
     }
 
     #[test]
@@ -675,6 +1838,137 @@ mod tests {
         assert_eq!(left, right);
     }
 
+    #[test]
+    fn point_g1_mul_vartime_works() {
+        let p = PointG1::new().unwrap();
+        for _ in 0..16 {
+            let e = GroupOrderElement::new().unwrap();
+            assert_eq!(p.mul(&e).unwrap(), p.mul_vartime(&e).unwrap());
+        }
+    }
+
+    #[test]
+    fn point_g1_mul_vartime_works_for_edge_scalars() {
+        let p = PointG1::new().unwrap();
+
+        let mut order_minus_one = BIG::new_ints(&CURVE_ORDER);
+        order_minus_one.dec(1);
+        order_minus_one.norm();
+
+        let zero = GroupOrderElement { bn: BIG::new() };
+        let one = GroupOrderElement { bn: BIG::new_int(1) };
+        let order_minus_one = GroupOrderElement { bn: order_minus_one };
+
+        assert!(p.mul_vartime(&zero).unwrap().is_inf().unwrap());
+        assert_eq!(p, p.mul_vartime(&one).unwrap());
+        assert_eq!(p.mul(&order_minus_one).unwrap(), p.mul_vartime(&order_minus_one).unwrap());
+        assert_eq!(p.neg().unwrap().to_bytes().unwrap(), p.mul_vartime(&order_minus_one).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn point_g1_mul_ct_works() {
+        let p = PointG1::new().unwrap();
+        for _ in 0..16 {
+            let e = GroupOrderElement::new().unwrap();
+            assert_eq!(p.mul(&e).unwrap().to_bytes().unwrap(), p.mul_ct(&e).unwrap().to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn point_g1_mul_ct_works_for_edge_scalars() {
+        let p = PointG1::new().unwrap();
+
+        let mut order_minus_one = BIG::new_ints(&CURVE_ORDER);
+        order_minus_one.dec(1);
+        order_minus_one.norm();
+
+        let zero = GroupOrderElement { bn: BIG::new() };
+        let one = GroupOrderElement { bn: BIG::new_int(1) };
+        let order_minus_one = GroupOrderElement { bn: order_minus_one };
+
+        assert!(p.mul_ct(&zero).unwrap().is_identity());
+        assert_eq!(p.to_bytes().unwrap(), p.mul_ct(&one).unwrap().to_bytes().unwrap());
+        assert_eq!(p.neg().unwrap().to_bytes().unwrap(), p.mul_ct(&order_minus_one).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn point_g2_mul_ct_works() {
+        let p = PointG2::new().unwrap();
+        for _ in 0..16 {
+            let e = GroupOrderElement::new().unwrap();
+            assert_eq!(p.mul(&e).unwrap().to_bytes().unwrap(), p.mul_ct(&e).unwrap().to_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn point_g2_mul_ct_works_for_edge_scalars() {
+        let p = PointG2::new().unwrap();
+
+        let mut order_minus_one = BIG::new_ints(&CURVE_ORDER);
+        order_minus_one.dec(1);
+        order_minus_one.norm();
+
+        let zero = GroupOrderElement { bn: BIG::new() };
+        let one = GroupOrderElement { bn: BIG::new_int(1) };
+        let order_minus_one = GroupOrderElement { bn: order_minus_one };
+
+        assert!(p.mul_ct(&zero).unwrap().is_identity());
+        assert_eq!(p.to_bytes().unwrap(), p.mul_ct(&one).unwrap().to_bytes().unwrap());
+        assert_eq!(p.mul(&order_minus_one).unwrap().to_bytes().unwrap(), p.mul_ct(&order_minus_one).unwrap().to_bytes().unwrap());
+    }
+
+    #[test]
+    fn point_g1_identity_works() {
+        assert!(PointG1::identity().is_identity());
+        assert!(PointG1::default().is_identity());
+        assert!(!PointG1::new().unwrap().is_identity());
+    }
+
+    #[test]
+    fn point_g1_sum_works_for_empty_iterator() {
+        let sum: PointG1 = Vec::<PointG1>::new().into_iter().sum();
+        assert!(sum.is_identity());
+    }
+
+    #[test]
+    fn point_g1_sum_works() {
+        let points = vec![PointG1::new().unwrap(), PointG1::new().unwrap(), PointG1::new().unwrap()];
+
+        let mut expected = PointG1::new_inf().unwrap();
+        for point in points.iter() {
+            expected = expected.add(point).unwrap();
+        }
+
+        let sum: PointG1 = points.iter().cloned().sum();
+        assert_eq!(expected, sum);
+    }
+
+    #[test]
+    fn point_g2_identity_works() {
+        assert!(PointG2::identity().is_identity());
+        assert!(PointG2::default().is_identity());
+        assert!(!PointG2::new().unwrap().is_identity());
+    }
+
+    #[test]
+    fn point_g2_sum_works_for_empty_iterator() {
+        let sum: PointG2 = Vec::<PointG2>::new().into_iter().sum();
+        assert!(sum.is_identity());
+    }
+
+    #[test]
+    fn point_g2_sum_works() {
+        let points = vec![PointG2::new().unwrap(), PointG2::new().unwrap(), PointG2::new().unwrap()];
+
+        let mut expected = PointG2::new_inf().unwrap();
+        for point in points.iter() {
+            expected = expected.add(point).unwrap();
+        }
+
+        let sum: PointG2 = points.iter().cloned().sum();
+        assert_eq!(expected, sum);
+    }
+
     #[test]
     fn point_g1_infinity_test() {
         let p = PointG1::new_inf().unwrap();
@@ -0,0 +1,128 @@
+use crate::errors::IndyCryptoError;
+
+const BASE64_DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as standard base64 string with padding.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::utils::base64;
+/// assert_eq!("AP8a", base64::to_base64(&[0, 255, 26]));
+/// ```
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut base64 = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let block = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                base64.push(BASE64_DIGITS[(block >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                base64.push('=');
+            }
+        }
+    }
+    base64
+}
+
+/// Decodes standard base64 string to bytes. Padding is optional.
+///
+/// Returns `InvalidStructure` error for string of invalid length or with non base64 digit.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::utils::base64;
+/// assert_eq!(vec![0, 255, 26, 1], base64::from_base64("AP8aAQ==").unwrap());
+/// ```
+pub fn from_base64(base64: &str) -> Result<Vec<u8>, IndyCryptoError> {
+    let digits = base64.as_bytes();
+    let unpadded = if digits.len().is_multiple_of(4) {
+        base64.trim_end_matches('=').as_bytes()
+    } else {
+        digits
+    };
+
+    if digits.len() - unpadded.len() > 2 || unpadded.len() % 4 == 1 {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid base64 string: invalid length {}", base64.len())));
+    }
+
+    if let Some(position) = unpadded.iter().position(|&b| _digit(b).is_none()) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid base64 string: bad digit at position {}", position)));
+    }
+
+    let mut bytes = Vec::with_capacity(unpadded.len() * 3 / 4);
+    for chunk in unpadded.chunks(4) {
+        let block = chunk.iter()
+            .enumerate()
+            .fold(0u32, |block, (i, &b)| block | (_digit(b).unwrap() as u32) << (18 - 6 * i));
+        bytes.extend(block.to_be_bytes()[1..chunk.len()].iter());
+    }
+    Ok(bytes)
+}
+
+fn _digit(b: u8) -> Option<u8> {
+    match b {
+        b'A'..=b'Z' => Some(b - b'A'),
+        b'a'..=b'z' => Some(b - b'a' + 26),
+        b'0'..=b'9' => Some(b - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorKind;
+
+    #[test]
+    fn to_base64_works() {
+        assert_eq!("", to_base64(&[]));
+        assert_eq!("Zg==", to_base64(b"f"));
+        assert_eq!("Zm8=", to_base64(b"fo"));
+        assert_eq!("Zm9v", to_base64(b"foo"));
+        assert_eq!("Zm9vYmFy", to_base64(b"foobar"));
+        assert_eq!("+/8=", to_base64(&[251, 255]));
+    }
+
+    #[test]
+    fn from_base64_works() {
+        assert_eq!(Vec::<u8>::new(), from_base64("").unwrap());
+        assert_eq!(b"f".to_vec(), from_base64("Zg==").unwrap());
+        assert_eq!(b"fo".to_vec(), from_base64("Zm8=").unwrap());
+        assert_eq!(b"foobar".to_vec(), from_base64("Zm9vYmFy").unwrap());
+        assert_eq!(vec![251, 255], from_base64("+/8=").unwrap());
+    }
+
+    #[test]
+    fn from_base64_works_without_padding() {
+        assert_eq!(b"f".to_vec(), from_base64("Zg").unwrap());
+        assert_eq!(b"fo".to_vec(), from_base64("Zm8").unwrap());
+    }
+
+    #[test]
+    fn from_base64_works_for_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..6 {
+            assert_eq!(bytes[len..], from_base64(&to_base64(&bytes[len..])).unwrap()[..]);
+        }
+    }
+
+    #[test]
+    fn from_base64_works_for_invalid_length() {
+        assert_eq!(ErrorKind::InvalidStructure, from_base64("Z").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, from_base64("Zm9vY").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, from_base64("Z===").unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_base64_works_for_bad_digit() {
+        assert_eq!(ErrorKind::InvalidStructure, from_base64("Zm9-").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, from_base64("Zm=v").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, from_base64("Zm9v\n").unwrap_err().kind());
+    }
+}
@@ -0,0 +1,226 @@
+//! Compact binary serialization of library entities in CBOR (RFC 8949) by `ciborium`.
+//!
+//! Entities are serialized by serde as in json, but big numbers, points and group elements become
+//! byte strings instead of decimal or hex strings, as they do in every not human readable format.
+//! Encoded entity is `[FORMAT_VERSION, entity]` array, version is checked before the entity is decoded.
+use crate::errors::IndyCryptoError;
+
+use serde::de::{self, Deserialize, DeserializeOwned, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serialize;
+
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Version of the encoding, tagged to every encoded entity.
+pub const FORMAT_VERSION: u64 = 1;
+
+// Nesting of arrays and maps that decoder accepts, entities are nested a few levels only
+const MAX_DEPTH: usize = 64;
+
+/// Serializes value to CBOR tagged by `FORMAT_VERSION`.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, IndyCryptoError> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&(FORMAT_VERSION, value), &mut bytes)
+        .map_err(|err| IndyCryptoError::InvalidStructure(format!("Invalid CBOR: {}", err)))?;
+    Ok(bytes)
+}
+
+/// Deserializes value from CBOR produced by `to_vec`.
+///
+/// Unknown version, malformed or truncated input and trailing bytes are InvalidStructure errors.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, IndyCryptoError> {
+    let mut input = bytes;
+
+    let Envelope(value) = ciborium::de::from_reader_with_recursion_limit(&mut input, MAX_DEPTH)
+        .map_err(|err| IndyCryptoError::InvalidStructure(format!("Invalid CBOR: {}", err)))?;
+
+    if !input.is_empty() {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid CBOR: {} trailing bytes", input.len())));
+    }
+
+    Ok(value)
+}
+
+// `[FORMAT_VERSION, entity]`, the entity is decoded only if the version is supported
+struct Envelope<T>(T);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Envelope<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Envelope<T>, D::Error> where D: Deserializer<'de> {
+        struct EnvelopeVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for EnvelopeVisitor<T> {
+            type Value = Envelope<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("[version, entity] array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Envelope<T>, A::Error> where A: SeqAccess<'de> {
+                let version: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                if version != FORMAT_VERSION {
+                    return Err(de::Error::custom(format!("Unsupported format version {}", version)));
+                }
+
+                let value = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(3, &self));
+                }
+
+                Ok(Envelope(value))
+            }
+        }
+
+        deserializer.deserialize_seq(EnvelopeVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bn::BigNumber;
+    use crate::errors::ErrorKind;
+
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Unit,
+        Newtype(u32),
+        Struct { name: String }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entity {
+        flag: bool,
+        small: u8,
+        large: u64,
+        negative: i64,
+        float: f64,
+        text: String,
+        bytes: Vec<u8>,
+        missing: Option<u32>,
+        present: Option<u32>,
+        kinds: Vec<Kind>,
+        map: BTreeMap<String, i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        skipped: Option<String>
+    }
+
+    fn _entity() -> Entity {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), -1);
+        map.insert("b".to_string(), 1000);
+
+        Entity {
+            flag: true,
+            small: 23,
+            large: u64::max_value(),
+            negative: i64::min_value(),
+            float: -1.5,
+            text: "text".to_string(),
+            bytes: vec![0, 1, 255],
+            missing: None,
+            present: Some(24),
+            kinds: vec![Kind::Unit, Kind::Newtype(7), Kind::Struct { name: "name".to_string() }],
+            map,
+            skipped: None
+        }
+    }
+
+    #[test]
+    fn to_vec_works() {
+        assert_eq!(vec![0x82, 0x01, 0x00], to_vec(&0u8).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0x17], to_vec(&23u32).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0x18, 0x18], to_vec(&24u32).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0x19, 0x01, 0xf4], to_vec(&500u32).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0x20], to_vec(&-1i32).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0x61, 0x61], to_vec("a").unwrap());
+        assert_eq!(vec![0x82, 0x01, 0xf5], to_vec(&true).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0xf6], to_vec(&None::<u8>).unwrap());
+    }
+
+    #[test]
+    fn to_vec_works_for_big_number() {
+        assert_eq!(vec![0x82, 0x01, 0x42, 0x00, 0xff], to_vec(&BigNumber::from_u32(255).unwrap()).unwrap());
+        assert_eq!(vec![0x82, 0x01, 0x42, 0x01, 0xff], to_vec(&BigNumber::from_dec("-255").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn from_slice_works_for_round_trip() {
+        let entity = _entity();
+        let bytes = to_vec(&entity).unwrap();
+        assert_eq!(entity, from_slice::<Entity>(&bytes).unwrap());
+    }
+
+    #[test]
+    fn from_slice_works_for_big_number() {
+        for number in &["0", "1", "-1", "123456789012345678901234567890", "-98765432109876543210"] {
+            let number = BigNumber::from_dec(number).unwrap();
+            let bytes = to_vec(&number).unwrap();
+            assert_eq!(number, from_slice::<BigNumber>(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn from_slice_works_for_truncated_input() {
+        let bytes = to_vec(&_entity()).unwrap();
+        for len in 0..bytes.len() {
+            let res = from_slice::<Entity>(&bytes[..len]);
+            assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+    }
+
+    #[test]
+    fn from_slice_works_for_unsupported_version() {
+        let res = from_slice::<u8>(&[0x82, 0x02, 0x00]);
+        let err = res.unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("Unsupported format version 2"));
+    }
+
+    #[test]
+    fn from_slice_works_for_missed_envelope() {
+        let res = from_slice::<u8>(&[0x00]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+        let res = from_slice::<u8>(&[0x83, 0x01, 0x00, 0x00]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_slice_works_for_trailing_bytes() {
+        let res = from_slice::<u8>(&[0x82, 0x01, 0x00, 0x00]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_slice_works_for_too_deep_nesting() {
+        let mut bytes = vec![0x82, 0x01];
+        bytes.extend(vec![0x81; MAX_DEPTH + 1]);
+        bytes.push(0x80);
+
+        let res = from_slice::<serde_json::Value>(&bytes);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_slice_works_for_length_exceeding_input() {
+        let res = from_slice::<Vec<u8>>(&[0x82, 0x01, 0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+        let res = from_slice::<String>(&[0x82, 0x01, 0x7a, 0xff, 0xff, 0xff, 0xff, 0x61]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_slice_works_for_invalid_utf8() {
+        let res = from_slice::<String>(&[0x82, 0x01, 0x61, 0xff]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_slice_works_for_invalid_big_number_sign() {
+        let res = from_slice::<BigNumber>(&[0x82, 0x01, 0x42, 0x02, 0xff]);
+        assert_eq!(ErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+}
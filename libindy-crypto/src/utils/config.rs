@@ -0,0 +1,185 @@
+use crate::errors::IndyCryptoError;
+
+use std::sync::RwLock;
+
+/// Library behaviour tunable at runtime without recompiling, see `set_runtime_config`.
+///
+/// Limits of 0 mean unlimited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// Number of threads `Bls::verify_batch` splits the batch between, 1 verifies on the calling thread.
+    /// Ignored on wasm32, which verifies on the calling thread.
+    pub verification_threads: usize,
+    /// Rejects the identity point as verification key or signature on deserialization.
+    /// The identity signature verifies any message with the identity verification key.
+    pub strict_validation: bool,
+    /// Maximum number of signatures, verification keys or batch items in one call.
+    pub max_signers: usize,
+    /// Maximum size of message to sign or verify in bytes.
+    pub max_message_size: usize,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> RuntimeConfig {
+        RuntimeConfig {
+            verification_threads: 1,
+            strict_validation: false,
+            max_signers: 0,
+            max_message_size: 0
+        }
+    }
+}
+
+impl RuntimeConfig {
+    /// Parses config from json object. Missing keys take default values, unknown keys are
+    /// logged as warnings and ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indy_crypto::utils::config::RuntimeConfig;
+    /// let config = RuntimeConfig::from_json(r#"{"max_signers": 100}"#).unwrap();
+    /// assert_eq!(100, config.max_signers);
+    /// assert_eq!(1, config.verification_threads);
+    /// ```
+    #[cfg(feature = "serialization")]
+    pub fn from_json(json: &str) -> Result<RuntimeConfig, IndyCryptoError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+
+        let object = value.as_object()
+            .ok_or_else(|| IndyCryptoError::InvalidStructure("Runtime config must be json object".to_string()))?;
+
+        let mut config = RuntimeConfig::default();
+        for (key, value) in object {
+            match key.as_str() {
+                "verification_threads" => config.verification_threads = _value(key, value)?,
+                "strict_validation" => config.strict_validation = _value(key, value)?,
+                "max_signers" => config.max_signers = _value(key, value)?,
+                "max_message_size" => config.max_message_size = _value(key, value)?,
+                _ => warn!("Unknown runtime config key is ignored: {}", key)
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), IndyCryptoError> {
+        if self.verification_threads == 0 {
+            return Err(IndyCryptoError::InvalidStructure("Runtime config `verification_threads` must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serialization")]
+fn _value<T: serde::de::DeserializeOwned>(key: &str, value: &serde_json::Value) -> Result<T, IndyCryptoError> {
+    T::deserialize(value)
+        .map_err(|err| IndyCryptoError::InvalidStructure(format!("Invalid runtime config `{}`: {}", key, err)))
+}
+
+lazy_static! {
+    static ref RUNTIME_CONFIG: RwLock<RuntimeConfig> = RwLock::new(RuntimeConfig::default());
+}
+
+/// Replaces runtime config of the library, calls in progress finish with the previous one.
+pub fn set_runtime_config(config: RuntimeConfig) -> Result<(), IndyCryptoError> {
+    config.validate()?;
+    // Config is replaced by single assignment, a poisoned lock still holds a whole config
+    let mut runtime_config = RUNTIME_CONFIG.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *runtime_config = config;
+    Ok(())
+}
+
+/// Returns current runtime config of the library.
+pub fn runtime_config() -> RuntimeConfig {
+    RUNTIME_CONFIG.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Checks the number of signers against `max_signers`.
+pub(crate) fn check_signers(count: usize) -> Result<(), IndyCryptoError> {
+    let max_signers = runtime_config().max_signers;
+    if max_signers != 0 && count > max_signers {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Too many signers: expected at most {}, actual {}", max_signers, count)));
+    }
+    Ok(())
+}
+
+/// Checks the message size against `max_message_size`.
+pub(crate) fn check_message_size(message: &[u8]) -> Result<(), IndyCryptoError> {
+    let max_message_size = runtime_config().max_message_size;
+    if max_message_size != 0 && message.len() > max_message_size {
+        return Err(IndyCryptoError::InvalidStructure(
+            format!("Message is too large: expected at most {} bytes, actual {}", max_message_size, message.len())));
+    }
+    Ok(())
+}
+
+// Serializes tests that change the process wide runtime config. Other tests read the config concurrently,
+// so these tests set only limits above what other tests use, other settings are passed to the internals explicitly.
+#[cfg(test)]
+lazy_static! {
+    pub(crate) static ref RUNTIME_CONFIG_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorKind;
+
+    const MAX_MESSAGE_SIZE: usize = 1 << 20;
+
+    #[test]
+    fn from_json_works() {
+        let config = RuntimeConfig::from_json(
+            r#"{"verification_threads": 4, "strict_validation": true, "max_signers": 10, "max_message_size": 1024}"#).unwrap();
+        assert_eq!(RuntimeConfig { verification_threads: 4, strict_validation: true, max_signers: 10, max_message_size: 1024 }, config);
+    }
+
+    #[test]
+    fn from_json_works_for_defaults() {
+        assert_eq!(RuntimeConfig::default(), RuntimeConfig::from_json("{}").unwrap());
+
+        let config = RuntimeConfig::from_json(r#"{"strict_validation": true}"#).unwrap();
+        assert!(config.strict_validation);
+        assert_eq!(1, config.verification_threads);
+        assert_eq!(0, config.max_signers);
+    }
+
+    #[test]
+    fn from_json_works_for_unknown_keys() {
+        let config = RuntimeConfig::from_json(r#"{"max_signers": 3, "thread_pool": 8}"#).unwrap();
+        assert_eq!(3, config.max_signers);
+    }
+
+    #[test]
+    fn from_json_works_for_invalid_values() {
+        for json in [r#"{"max_signers": -1}"#, r#"{"strict_validation": "yes"}"#, r#"{"verification_threads": 0}"#, "[]", "{"] {
+            let err = RuntimeConfig::from_json(json).unwrap_err();
+            assert!(err.kind() == ErrorKind::JsonError || err.kind() == ErrorKind::InvalidStructure, "{}", json);
+        }
+
+        let err = RuntimeConfig::from_json(r#"{"max_signers": 1.5}"#).unwrap_err();
+        assert!(err.to_string().contains("Invalid runtime config `max_signers`"));
+    }
+
+    #[test]
+    fn set_runtime_config_works() {
+        let _lock = RUNTIME_CONFIG_TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Limit is above the messages of other tests running concurrently
+        let message = vec![0; MAX_MESSAGE_SIZE + 1];
+        set_runtime_config(RuntimeConfig { max_message_size: MAX_MESSAGE_SIZE, ..RuntimeConfig::default() }).unwrap();
+        check_message_size(&message[..MAX_MESSAGE_SIZE]).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, check_message_size(&message).unwrap_err().kind());
+
+        set_runtime_config(RuntimeConfig::default()).unwrap();
+        check_message_size(&message).unwrap();
+
+        assert_eq!(ErrorKind::InvalidStructure,
+                   set_runtime_config(RuntimeConfig { verification_threads: 0, ..RuntimeConfig::default() }).unwrap_err().kind());
+        assert_eq!(RuntimeConfig::default(), runtime_config());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::errors::IndyCryptoError;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Encodes bytes as lower case hex string.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::utils::hex;
+/// assert_eq!("00ff1a", hex::to_hex(&[0, 255, 26]));
+/// ```
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        hex.push(HEX_DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    hex
+}
+
+/// Decodes hex string of lower or upper case digits to bytes.
+///
+/// Returns `InvalidStructure` error for string of odd length or with non hex digit.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::utils::hex;
+/// assert_eq!(vec![0, 255, 26], hex::from_hex("00FF1a").unwrap());
+/// ```
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, IndyCryptoError> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid hex string: odd length {}", hex.len())));
+    }
+
+    if let Some(position) = hex.bytes().position(|b| !b.is_ascii_hexdigit()) {
+        return Err(IndyCryptoError::InvalidStructure(format!("Invalid hex string: bad digit at position {}", position)));
+    }
+
+    Ok(hex.as_bytes()
+        .chunks(2)
+        .map(|pair| (_digit(pair[0]) << 4) | _digit(pair[1]))
+        .collect())
+}
+
+// Digits are checked by from_hex beforehand, so anything else is an upper case letter
+fn _digit(b: u8) -> u8 {
+    match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'f' => b - b'a' + 10,
+        _ => b - b'A' + 10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorKind;
+
+    #[test]
+    fn to_hex_works() {
+        assert_eq!("", to_hex(&[]));
+        assert_eq!("000f10ff", to_hex(&[0, 15, 16, 255]));
+    }
+
+    #[test]
+    fn from_hex_works() {
+        assert_eq!(Vec::<u8>::new(), from_hex("").unwrap());
+        assert_eq!(vec![0, 15, 16, 255], from_hex("000f10ff").unwrap());
+        assert_eq!(vec![171, 205], from_hex("ABcd").unwrap());
+    }
+
+    #[test]
+    fn from_hex_works_for_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(bytes, from_hex(&to_hex(&bytes)).unwrap());
+    }
+
+    #[test]
+    fn from_hex_works_for_odd_length() {
+        assert_eq!(ErrorKind::InvalidStructure, from_hex("abc").unwrap_err().kind());
+    }
+
+    #[test]
+    fn from_hex_works_for_bad_digit() {
+        assert_eq!(ErrorKind::InvalidStructure, from_hex("0g").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, from_hex("+f").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, from_hex("é0").unwrap_err().kind());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod base64;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod config;
+pub mod hex;
+pub mod version;
+pub mod zeroize;
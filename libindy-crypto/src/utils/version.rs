@@ -0,0 +1,79 @@
+/// Version and build metadata of the library, the answer to "which build produced this".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LibraryVersion {
+    /// Crate version from the manifest
+    pub version: &'static str,
+    /// Cargo features the library is built with
+    pub features: Vec<&'static str>,
+    /// Pairing friendly curve of BLS signatures, `None` if the library is built without pairing
+    pub curve: Option<&'static str>
+}
+
+/// Returns version and build metadata of the library.
+///
+/// # Example
+///
+/// ```
+/// use indy_crypto::utils::version;
+/// assert_eq!(env!("CARGO_PKG_VERSION"), version::library_version().version);
+/// ```
+pub fn library_version() -> LibraryVersion {
+    let features = [
+        ("bn_openssl", cfg!(feature = "bn_openssl")),
+        ("pair_amcl", cfg!(feature = "pair_amcl")),
+        ("serialization", cfg!(feature = "serialization")),
+        ("cbor", cfg!(feature = "cbor")),
+        ("wasm", cfg!(feature = "wasm")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("cl", cfg!(feature = "cl")),
+        ("fork_safety", cfg!(feature = "fork_safety")),
+        ("testing", cfg!(feature = "testing")),
+        ("parallel", cfg!(feature = "parallel")),
+        ("seeded_keys", cfg!(feature = "seeded_keys")),
+    ];
+
+    LibraryVersion {
+        version: env!("CARGO_PKG_VERSION"),
+        features: features.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect(),
+        curve: _curve()
+    }
+}
+
+/// Returns `library_version` as `{ "version", "features", "curve" }` json.
+#[cfg(feature = "serialization")]
+pub fn library_version_json() -> String {
+    // Static strings and a vector of them, serialization can't fail
+    serde_json::to_string(&library_version()).unwrap_or_default()
+}
+
+#[cfg(feature = "pair_amcl")]
+fn _curve() -> Option<&'static str> {
+    Some(crate::pair::CURVE_NAME)
+}
+
+#[cfg(not(feature = "pair_amcl"))]
+fn _curve() -> Option<&'static str> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library_version_works() {
+        let version = library_version();
+        assert_eq!(env!("CARGO_PKG_VERSION"), version.version);
+        assert_eq!(cfg!(feature = "serialization"), version.features.contains(&"serialization"));
+        assert!(!version.features.contains(&"unknown"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "serialization", feature = "pair_amcl"))]
+    fn library_version_json_works() {
+        let json: serde_json::Value = serde_json::from_str(&library_version_json()).unwrap();
+        assert_eq!(env!("CARGO_PKG_VERSION"), json["version"].as_str().unwrap());
+        assert!(json["features"].as_array().unwrap().iter().any(|feature| feature == "pair_amcl"));
+        assert_eq!("BN254", json["curve"].as_str().unwrap());
+    }
+}
@@ -0,0 +1,54 @@
+use std::ops::Deref;
+use std::ptr;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// Overwrites the bytes with zeros.
+///
+/// Writes are volatile, so they are not optimized away even if the bytes are never read again.
+pub fn zeroize(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Temporary copy of secret bytes that is zeroized on drop.
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> SecretBytes {
+        SecretBytes(bytes)
+    }
+}
+
+impl Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        zeroize(&mut self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zeroize_works() {
+        let mut bytes = vec![1, 2, 3, 255];
+        zeroize(&mut bytes);
+        assert_eq!(vec![0, 0, 0, 0], bytes);
+    }
+
+    #[test]
+    fn secret_bytes_works() {
+        let secret = SecretBytes::new(vec![1, 2, 3]);
+        assert_eq!(&[1, 2, 3], &*secret);
+    }
+}
@@ -1,209 +1,1061 @@
 use crate::wasm_bindgen::prelude::*;
+use crate::wasm_bindgen::JsCast;
 
 use crate::bls;
-use crate::errors::IndyCryptoError;
-use crate::errors::ToErrorCode;
-use serde;
+use crate::bls::threshold::{SignKeyShare, SignatureShare, Threshold};
+use crate::errors::{set_current_error, ErrorDetails, IndyCryptoError};
+use crate::pair::{reseed, set_entropy_source, EntropySource, PointG1, PointG2};
+use crate::utils::hex::{from_hex, to_hex};
+use crate::utils::version::library_version_json;
+use crate::utils::zeroize::SecretBytes;
+use js_sys;
+use serde::{self, Deserialize, Serialize};
+use serde_wasm_bindgen;
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
 
+/// Converts to a js `Error` carrying `code`, `name` and `message` of `ErrorDetails`.
 impl From<IndyCryptoError> for JsValue {
     fn from(err: IndyCryptoError) -> JsValue {
-        let error_code = err.to_error_code();
-        JsValue::from_serde(&error_code).unwrap()
+        set_current_error(&err);
+
+        let details = ErrorDetails::from(&err);
+        let error = js_sys::Error::new(&details.message);
+        if let Ok(details) = details.serialize(&serde_wasm_bindgen::Serializer::json_compatible()) {
+            js_sys::Object::assign(&error, &details.into());
+        }
+        error.into()
     }
 }
 
-fn convert_from_js<T>(val: &JsValue) -> Result<T, IndyCryptoError>
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** Points are hex strings of AMCL coordinates, bytes are the compressed point. */
+export interface Generator { point: string; bytes: number[]; }
+export interface SignKey { group_order_element: string; bytes: number[]; }
+export interface VerKey { point: string; bytes: number[]; }
+export interface ProofOfPossession { point: string; bytes: number[]; }
+export interface Signature { point: string; bytes: number[]; }
+export interface MultiSignature { point: string; bytes: number[]; }
+export interface AggregatedVerKey { point: string; bytes: number[]; }
+export interface KeyPair { signKey: SignKey; verKey: VerKey; proofOfPossession: ProofOfPossession; }
+export interface BatchItem { signature: Signature; message: Uint8Array | number[]; verKey: VerKey; }
+export interface PopBatchItem { proofOfPossession: ProofOfPossession; verKey: VerKey; }
+export interface BatchResult { valid: boolean; failedIndex?: number; }
+export interface AggregateItem { message: Uint8Array | number[]; verKey: VerKey; }
+export interface SignKeyShare { index: number; threshold: number; group_order_element: string; bytes: number[]; }
+export interface SignatureShare { index: number; threshold: number; point: string; bytes: number[]; }
+export interface SignKeyShares { signKey: SignKey; shares: SignKeyShare[]; }
+
+/** Thrown by every function on failure. `code` is the numeric ErrorCode and `name` its name. */
+export interface IndyCryptoError extends Error { code: number; name: string; message: string; }
+"#;
+
+// Byte arguments are taken as `&[u8]`, which wasm-bindgen fills from a `Uint8Array` with a single copy
+// into wasm memory, and byte results are returned as `Vec<u8>`, which becomes a freshly allocated
+// `Uint8Array`. Key and signature objects go through serde, use the `*Raw` functions to avoid that.
+//
+// Copies per call, audited for every function of the module:
+// - `&[u8]` argument: one copy into wasm memory, freed after the call;
+// - `Vec<u8>` argument (secret bytes only): one copy, owned so it is zeroized on drop;
+// - `Uint8Array` array item: one `to_vec` copy per item;
+// - `Vec<u8>` result: one copy out of wasm memory into a new `Uint8Array`;
+// - object argument or result: serde walk of the object plus its hex strings and byte arrays.
+// Results are not returned as views of wasm memory: a view is detached as soon as the memory grows,
+// so any later call could invalidate it under the caller. `allocations_of_raw_api_are_fewer` in
+// tests/wasm.rs measures the difference between the raw and object functions.
+//
+// Objects are converted with the json compatible serializer, so JS sees the same shape as `JSON.parse`
+// of the serde_json representation: plain objects, hex strings for points and arrays of numbers for bytes.
+pub(super) fn convert_from_js<T>(val: &JsValue) -> Result<T, IndyCryptoError>
 where
     for<'a> T: serde::Deserialize<'a>,
 {
-    match val.into_serde() {
+    match serde_wasm_bindgen::from_value(val.clone()) {
         Ok(unwrapped) => Ok(unwrapped),
-        Err(_) => Err(IndyCryptoError::InvalidStructure(
-            "Invalid argument".to_string(),
-        )),
+        Err(err) => Err(IndyCryptoError::InvalidStructure(format!(
+            "Invalid argument: {}",
+            err
+        ))),
+    }
+}
+
+pub(super) fn convert_to_js<T>(val: &T) -> Result<JsValue, IndyCryptoError>
+where
+    T: serde::Serialize,
+{
+    val.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).map_err(|err| {
+        IndyCryptoError::InvalidState(format!(
+            "Unable to convert to js value: {}",
+            err
+        ))
+    })
+}
+
+fn convert_all_from_js<T>(vals: &[JsValue]) -> Result<Vec<T>, IndyCryptoError>
+where
+    for<'a> T: serde::Deserialize<'a>,
+{
+    vals.iter()
+        .enumerate()
+        .map(|(index, val)| {
+            serde_wasm_bindgen::from_value(val.clone()).map_err(|err| {
+                IndyCryptoError::InvalidStructure(format!(
+                    "Invalid argument at index {}: {}",
+                    index, err
+                ))
+            })
+        })
+        .collect()
+}
+
+// Arguments of the verify functions are validated before any curve math: objects are deserialized,
+// their bytes decoded with the point checks of `from_bytes` and compared to their `point`, arrays must
+// not be empty and the message must be present, though it may be empty. Errors name the offending
+// parameter. Missing generator stands for the standard one.
+pub(super) trait Validate {
+    fn validate(&self) -> Result<(), String>;
+}
+
+macro_rules! validate_points {
+    ($($type:ty => $len:expr),*) => {$(
+        impl Validate for $type {
+            fn validate(&self) -> Result<(), String> {
+                if self.as_bytes().len() != $len {
+                    return Err(format!("invalid len of bytes: expected {}, actual {}", $len, self.as_bytes().len()));
+                }
+                let decoded = <$type>::from_bytes(self.as_bytes()).map_err(|err| format!("invalid bytes: {}", err))?;
+                if decoded.point_bytes().map_err(|err| err.to_string())? != self.point_bytes().map_err(|err| err.to_string())? {
+                    return Err("point doesn't match bytes".to_string());
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+validate_points!(
+    bls::Generator => PointG2::BYTES_REPR_SIZE,
+    bls::VerKey => PointG2::BYTES_REPR_SIZE,
+    bls::AggregatedVerKey => PointG2::BYTES_REPR_SIZE,
+    bls::Signature => PointG1::BYTES_REPR_SIZE,
+    bls::MultiSignature => PointG1::BYTES_REPR_SIZE,
+    bls::ProofOfPossession => PointG1::BYTES_REPR_SIZE
+);
+
+fn validate_field<T: Validate>(field: &str, value: &T) -> Result<(), String> {
+    value.validate().map_err(|err| format!("{}: {}", field, err))
+}
+
+pub(super) fn invalid_argument(name: &str, reason: &dyn std::fmt::Display) -> IndyCryptoError {
+    IndyCryptoError::InvalidStructure(format!("Invalid argument `{}`: {}", name, reason))
+}
+
+fn parse<T>(val: &JsValue) -> Result<T, String>
+where
+    for<'a> T: serde::Deserialize<'a> + Validate,
+{
+    if val.is_undefined() || val.is_null() {
+        return Err("value is missing".to_string());
+    }
+    let value: T = serde_wasm_bindgen::from_value(val.clone()).map_err(|err| err.to_string())?;
+    value.validate()?;
+    Ok(value)
+}
+
+fn param<T>(name: &str, val: &JsValue) -> Result<T, IndyCryptoError>
+where
+    for<'a> T: serde::Deserialize<'a> + Validate,
+{
+    parse(val).map_err(|err| invalid_argument(name, &err))
+}
+
+fn params<T>(name: &str, vals: &[JsValue]) -> Result<Vec<T>, IndyCryptoError>
+where
+    for<'a> T: serde::Deserialize<'a> + Validate,
+{
+    if vals.is_empty() {
+        return Err(invalid_argument(name, &"array is empty"));
     }
+    vals.iter()
+        .enumerate()
+        .map(|(index, val)| {
+            parse(val).map_err(|err| {
+                IndyCryptoError::InvalidStructure(format!(
+                    "Invalid argument `{}` at index {}: {}",
+                    name, index, err
+                ))
+            })
+        })
+        .collect()
 }
 
+// `null` (or omitted) generator of the verify functions means the standard one
+fn generator_param(name: &str, val: &JsValue) -> Result<bls::Generator, IndyCryptoError> {
+    if val.is_undefined() || val.is_null() {
+        return bls::Generator::standard();
+    }
+    param(name, val)
+}
+
+fn message_param(name: &str, message: Option<Vec<u8>>) -> Result<Vec<u8>, IndyCryptoError> {
+    message.ok_or_else(|| invalid_argument(name, &"value is missing"))
+}
+
+// Entropy is taken from `crypto.getRandomValues` by default, `blsSetEntropy` is for environments without it
+const MIN_INJECTED_ENTROPY_BYTES: usize = 32;
+
+struct InjectedEntropy {
+    seed: SecretBytes,
+    counter: u64,
+}
+
+lazy_static! {
+    static ref INJECTED_ENTROPY: Mutex<Option<InjectedEntropy>> = Mutex::new(None);
+}
+
+// Expands the injected seed with SHA-256 in counter mode, so every reseed gets different bytes
+fn injected_entropy(buf: &mut [u8]) -> Result<(), IndyCryptoError> {
+    let mut injected = INJECTED_ENTROPY
+        .lock()
+        .map_err(|_| IndyCryptoError::InvalidState("Injected entropy lock is poisoned".to_string()))?;
+    let injected = injected
+        .as_mut()
+        .ok_or_else(|| IndyCryptoError::RngFailure("Injected entropy is not set".to_string()))?;
+
+    for chunk in buf.chunks_mut(32) {
+        let mut hasher = Sha256::default();
+        hasher.input(&injected.counter.to_be_bytes());
+        hasher.input(&injected.seed);
+        chunk.copy_from_slice(&hasher.result()[..chunk.len()]);
+        injected.counter += 1;
+    }
+    Ok(())
+}
+
+/// Returns `{ version, features, curve }` json with crate version, enabled cargo features and the curve.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsLibraryVersion() -> String {
+    library_version_json()
+}
+
+/// Seeds the random generator from given bytes (at least 32) instead of `crypto.getRandomValues`.
+/// Passing nothing restores the platform entropy. Random generator is reseeded in both cases.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
+pub fn blsSetEntropy(bytes: Option<Vec<u8>>) -> Result<(), JsValue> {
+    let source = match bytes {
+        Some(ref bytes) if bytes.len() < MIN_INJECTED_ENTROPY_BYTES => {
+            return Err(IndyCryptoError::InvalidStructure(format!(
+                "Not enough entropy: expected at least {} bytes, actual {}",
+                MIN_INJECTED_ENTROPY_BYTES,
+                bytes.len()
+            ))
+            .into());
+        }
+        Some(_) => Some(injected_entropy as EntropySource),
+        None => None,
+    };
+
+    *INJECTED_ENTROPY
+        .lock()
+        .map_err(|_| IndyCryptoError::InvalidState("Injected entropy lock is poisoned".to_string()))? =
+        bytes.map(|seed| InjectedEntropy { seed: SecretBytes::new(seed), counter: 0 });
+    set_entropy_source(source)?;
+    reseed()?;
+    Ok(())
+}
+
+#[wasm_bindgen(unchecked_return_type = "Generator")]
+#[allow(non_snake_case)]
 pub fn blsGenerator() -> Result<JsValue, JsValue> {
     let gen = bls::Generator::new()?;
-    Ok(JsValue::from_serde(&gen).unwrap())
+    Ok(convert_to_js(&gen)?)
+}
+
+/// Returns the standard generator, the same on every call and in every build.
+/// Verify functions use it when `null` is passed for the generator.
+#[wasm_bindgen(unchecked_return_type = "Generator")]
+#[allow(non_snake_case)]
+pub fn blsDefaultGenerator() -> Result<JsValue, JsValue> {
+    let gen = bls::Generator::standard()?;
+    Ok(convert_to_js(&gen)?)
 }
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsGeneratorAsBytes(generator: &JsValue) -> Result<Vec<u8>, JsValue> {
+pub fn blsGeneratorAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "Generator")] generator: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
     let gen: bls::Generator = convert_from_js(generator)?;
     Ok(gen.as_bytes().to_vec())
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Generator")]
 #[allow(non_snake_case)]
 pub fn blsGeneratorFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
     let gen = bls::Generator::from_bytes(bytes)?;
-    Ok(JsValue::from_serde(&gen).unwrap())
+    Ok(convert_to_js(&gen)?)
 }
 
+/// Encodes generator as lower case hex string of its bytes.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
+pub fn blsGeneratorToHex(
+    #[wasm_bindgen(unchecked_param_type = "Generator")] generator: &JsValue,
+) -> Result<String, JsValue> {
+    let gen: bls::Generator = convert_from_js(generator)?;
+    Ok(to_hex(gen.as_bytes()))
+}
+
+#[wasm_bindgen(unchecked_return_type = "Generator")]
+#[allow(non_snake_case)]
+pub fn blsGeneratorFromHex(hex: &str) -> Result<JsValue, JsValue> {
+    let gen = bls::Generator::from_bytes(&from_hex(hex)?)?;
+    Ok(convert_to_js(&gen)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "SignKey")]
+#[allow(non_snake_case)]
 pub fn blsSignKey(seed: Option<Vec<u8>>) -> Result<JsValue, JsValue> {
-    let seedOption = seed.as_ref().map(|v| v.as_slice());
-    let sk = bls::SignKey::new(seedOption)?;
-    Ok(JsValue::from_serde(&sk).unwrap())
+    let sk = match seed.map(SecretBytes::new) {
+        Some(seed) => bls::SignKey::from_seed(&seed)?,
+        None => bls::SignKey::new(None)?,
+    };
+    Ok(convert_to_js(&sk)?)
+}
+
+/// Creates sign key seeded from hex encoded seed of any non zero length.
+#[wasm_bindgen(unchecked_return_type = "SignKey")]
+#[allow(non_snake_case)]
+pub fn blsSignKeyFromHexSeed(seed: &str) -> Result<JsValue, JsValue> {
+    let seed = SecretBytes::new(from_hex(seed)?);
+    let sk = bls::SignKey::from_seed(&seed)?;
+    Ok(convert_to_js(&sk)?)
 }
 
+/// Returns copy of the secret bytes, wiping it is the caller's responsibility.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsSignKeyAsBytes(signKey: &JsValue) -> Result<Vec<u8>, JsValue> {
+pub fn blsSignKeyAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+) -> Result<js_sys::Uint8Array, JsValue> {
+    // Copied to JS directly, the Rust side copy is zeroized with the sign key
     let sk: bls::SignKey = convert_from_js(signKey)?;
-    Ok(sk.as_bytes().to_vec())
+    Ok(js_sys::Uint8Array::from(sk.as_bytes()))
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "SignKey")]
 #[allow(non_snake_case)]
-pub fn blsSignKeyFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
-    let sk = bls::SignKey::from_bytes(bytes)?;
-    Ok(JsValue::from_serde(&sk).unwrap())
+pub fn blsSignKeyFromBytes(bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+    let bytes = SecretBytes::new(bytes);
+    let sk = bls::SignKey::from_bytes(&bytes)?;
+    Ok(convert_to_js(&sk)?)
 }
 
+/// Wipes the sign key object: zeroes its `bytes` and removes its fields, so further use of it fails
+/// with `CommonInvalidStructure` error. Rust side copies of sign keys are zeroized on drop anyway,
+/// strings and other copies made on the JS side are the caller's responsibility.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsSign(message: &[u8], signKey: &JsValue) -> Result<JsValue, JsValue> {
+pub fn blsSignKeyZeroize(
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+) -> Result<(), JsValue> {
+    if !signKey.is_object() {
+        return Err(IndyCryptoError::InvalidStructure("Sign key is not an object".to_string()).into());
+    }
+
+    let sign_key = js_sys::Object::from(signKey.clone());
+    let bytes = js_sys::Reflect::get(&sign_key, &JsValue::from_str("bytes"))?;
+    if let Some(bytes) = bytes.dyn_ref::<js_sys::Array>() {
+        bytes.fill(&JsValue::from(0), 0, bytes.length());
+    } else if let Some(bytes) = bytes.dyn_ref::<js_sys::Uint8Array>() {
+        bytes.fill(0, 0, bytes.length());
+    }
+
+    for field in ["group_order_element", "bytes"] {
+        js_sys::Reflect::delete_property(&sign_key, &JsValue::from_str(field))?;
+    }
+    Ok(())
+}
+
+#[wasm_bindgen(unchecked_return_type = "Signature")]
+#[allow(non_snake_case)]
+pub fn blsSign(
+    message: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+) -> Result<JsValue, JsValue> {
     let sk: bls::SignKey = convert_from_js(signKey)?;
     let signature: bls::Signature = bls::Bls::sign(message, &sk)?;
-    Ok(JsValue::from_serde(&signature).unwrap())
+    Ok(convert_to_js(&signature)?)
 }
 
+/// Signs the message under the domain separation tag (1 to 255 bytes) for DST aware verifiers.
+/// Without `dst` the signature is the same as of `blsSign`.
+#[wasm_bindgen(unchecked_return_type = "Signature")]
+#[allow(non_snake_case)]
+pub fn blsSignWithDst(
+    message: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+    dst: Option<Vec<u8>>,
+) -> Result<JsValue, JsValue> {
+    let sk: bls::SignKey = convert_from_js(signKey)?;
+    let signature = match dst {
+        Some(dst) => bls::Bls::sign_with_dst(message, &sk, &dst)?,
+        None => bls::Bls::sign(message, &sk)?,
+    };
+    Ok(convert_to_js(&signature)?)
+}
+
+/// Signs the message with sign key given as bytes and returns signature bytes.
+///
+/// Skips the json object layer: `Uint8Array` arguments are copied into wasm memory once
+/// and the result is copied out once as a new `Uint8Array`, whose buffer can be transferred
+/// to another worker.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsVerKey(generator: &JsValue, signKey: &JsValue) -> Result<JsValue, JsValue> {
+pub fn blsSignRaw(message: &[u8], signKeyBytes: Vec<u8>) -> Result<Vec<u8>, JsValue> {
+    let signKeyBytes = SecretBytes::new(signKeyBytes);
+    let sk = bls::SignKey::from_bytes(&signKeyBytes)?;
+    let signature = bls::Bls::sign(message, &sk)?;
+    Ok(signature.as_bytes().to_vec())
+}
+
+/// Verifies the message signature with signature, ver key and generator given as bytes.
+///
+/// Points are decoded with the checks of `from_bytes`, so malformed bytes fail before pairing.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyRaw(
+    signatureBytes: &[u8],
+    message: &[u8],
+    verKeyBytes: &[u8],
+    generatorBytes: &[u8],
+) -> Result<bool, JsValue> {
+    let sig = bls::Signature::from_bytes(signatureBytes)?;
+    let vk = bls::VerKey::from_bytes(verKeyBytes)?;
+    let gen = bls::Generator::from_bytes(generatorBytes)?;
+    Ok(bls::Bls::verify(&sig, message, &vk, &gen)?)
+}
+
+/// Combines signatures given as bytes to multi signature bytes.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureRaw(signatureBytesArray: Vec<js_sys::Uint8Array>) -> Result<Vec<u8>, JsValue> {
+    if signatureBytesArray.is_empty() {
+        return Err(invalid_argument("signatureBytesArray", &"array is empty").into());
+    }
+    let sigs = signatureBytesArray
+        .iter()
+        .enumerate()
+        .map(|(index, bytes)| {
+            bls::Signature::from_bytes(&bytes.to_vec()).map_err(|err| {
+                IndyCryptoError::InvalidStructure(format!(
+                    "Invalid argument `signatureBytesArray` at index {}: {}",
+                    index, err
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let ms = bls::MultiSignature::new(sigs.iter().collect::<Vec<_>>().as_slice())?;
+    Ok(ms.as_bytes().to_vec())
+}
+
+/// Same as `blsSignRaw`, kept for compatibility.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignBytes(message: &[u8], signKey: Vec<u8>) -> Result<Vec<u8>, JsValue> {
+    blsSignRaw(message, signKey)
+}
+
+/// Same as `blsVerifyRaw`, kept for compatibility.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyBytes(
+    signature: &[u8],
+    message: &[u8],
+    verKey: &[u8],
+    generator: &[u8],
+) -> Result<bool, JsValue> {
+    blsVerifyRaw(signature, message, verKey, generator)
+}
+
+#[wasm_bindgen(unchecked_return_type = "VerKey")]
+#[allow(non_snake_case)]
+pub fn blsVerKey(
+    #[wasm_bindgen(unchecked_param_type = "Generator")] generator: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+) -> Result<JsValue, JsValue> {
     let gen: bls::Generator = convert_from_js(generator)?;
     let sk: bls::SignKey = convert_from_js(signKey)?;
     let vk = bls::VerKey::new(&gen, &sk)?;
-    Ok(JsValue::from_serde(&vk).unwrap())
+    Ok(convert_to_js(&vk)?)
 }
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsVerKeyAsBytes(verKey: &JsValue) -> Result<Vec<u8>, JsValue> {
+pub fn blsVerKeyAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
     let vk: bls::VerKey = convert_from_js(verKey)?;
     Ok(vk.as_bytes().to_vec())
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "VerKey")]
 #[allow(non_snake_case)]
 pub fn blsVerKeyFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
     let vk = bls::VerKey::from_bytes(bytes)?;
-    Ok(JsValue::from_serde(&vk).unwrap())
+    Ok(convert_to_js(&vk)?)
 }
 
+/// Encodes verification key as lower case hex string of its bytes.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsProofOfPossession(verKey: &JsValue, signKey: &JsValue) -> Result<JsValue, JsValue> {
+pub fn blsVerKeyToHex(
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+) -> Result<String, JsValue> {
+    let vk: bls::VerKey = convert_from_js(verKey)?;
+    Ok(to_hex(vk.as_bytes()))
+}
+
+#[wasm_bindgen(unchecked_return_type = "VerKey")]
+#[allow(non_snake_case)]
+pub fn blsVerKeyFromHex(hex: &str) -> Result<JsValue, JsValue> {
+    let vk = bls::VerKey::from_bytes(&from_hex(hex)?)?;
+    Ok(convert_to_js(&vk)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "ProofOfPossession")]
+#[allow(non_snake_case)]
+pub fn blsProofOfPossession(
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+) -> Result<JsValue, JsValue> {
     let vk: bls::VerKey = convert_from_js(verKey)?;
     let sk: bls::SignKey = convert_from_js(signKey)?;
     let pop = bls::ProofOfPossession::new(&vk, &sk)?;
-    Ok(JsValue::from_serde(&pop).unwrap())
+    Ok(convert_to_js(&pop)?)
+}
+
+/// Creates proof of possession of the ver key over non empty challenge issued by the verifier.
+#[wasm_bindgen(unchecked_return_type = "ProofOfPossession")]
+#[allow(non_snake_case)]
+pub fn blsProofOfPossessionForChallenge(
+    challenge: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "SignKey")] signKey: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let vk: bls::VerKey = convert_from_js(verKey)?;
+    let sk: bls::SignKey = convert_from_js(signKey)?;
+    let pop = bls::ProofOfPossession::new_for_challenge(challenge, &vk, &sk)?;
+    Ok(convert_to_js(&pop)?)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyPairJs<'a> {
+    sign_key: &'a bls::SignKey,
+    ver_key: &'a bls::VerKey,
+    proof_of_possession: &'a bls::ProofOfPossession,
+}
+
+/// Creates sign key (seeded if seed is given), verification key and proof of possession in one call.
+#[wasm_bindgen(unchecked_return_type = "KeyPair")]
+#[allow(non_snake_case)]
+pub fn blsKeyPair(
+    #[wasm_bindgen(unchecked_param_type = "Generator")] generator: &JsValue,
+    seed: Option<Vec<u8>>,
+) -> Result<JsValue, JsValue> {
+    let gen: bls::Generator = convert_from_js(generator)?;
+    let seed = seed.map(SecretBytes::new);
+    let key_pair = bls::KeyPair::new(&gen, seed.as_deref())?;
+    Ok(convert_to_js(&KeyPairJs {
+        sign_key: key_pair.sign_key(),
+        ver_key: key_pair.ver_key(),
+        proof_of_possession: key_pair.proof_of_possession(),
+    })?)
 }
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsProofOfPossessionAsBytes(proofOfPossession: &JsValue) -> Result<Vec<u8>, JsValue> {
+pub fn blsProofOfPossessionAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "ProofOfPossession")] proofOfPossession: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
     let pop: bls::ProofOfPossession = convert_from_js(proofOfPossession)?;
     Ok(pop.as_bytes().to_vec())
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "ProofOfPossession")]
 #[allow(non_snake_case)]
 pub fn blsProofOfPossessionFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
     let pop = bls::ProofOfPossession::from_bytes(bytes)?;
-    Ok(JsValue::from_serde(&pop).unwrap())
+    Ok(convert_to_js(&pop)?)
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "MultiSignature")]
 #[allow(non_snake_case)]
-pub fn blsMultiSignature(signatures: Vec<JsValue>) -> Result<JsValue, JsValue> {
-    let sigs: Vec<bls::Signature> = signatures.iter().map(|x| x.into_serde().unwrap()).collect();
+pub fn blsMultiSignature(
+    #[wasm_bindgen(unchecked_param_type = "Signature[]")] signatures: Vec<JsValue>,
+) -> Result<JsValue, JsValue> {
+    let sigs: Vec<bls::Signature> = convert_all_from_js(&signatures)?;
     let ms = bls::MultiSignature::new(sigs.iter().collect::<Vec<_>>().as_slice())?;
-    Ok(JsValue::from_serde(&ms).unwrap())
+    Ok(convert_to_js(&ms)?)
+}
+
+/// Returns multi signature of no signatures to add signatures to as they arrive.
+#[wasm_bindgen(unchecked_return_type = "MultiSignature")]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureEmpty() -> Result<JsValue, JsValue> {
+    let ms = bls::MultiSignature::empty()?;
+    Ok(convert_to_js(&ms)?)
+}
+
+/// Returns new multi signature with the signature added, the given one is left as is.
+#[wasm_bindgen(unchecked_return_type = "MultiSignature")]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureAdd(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSig: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Signature")] signature: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let mut ms: bls::MultiSignature = param("multiSig", multiSig)?;
+    let sig: bls::Signature = param("signature", signature)?;
+    ms.add(&sig)?;
+    Ok(convert_to_js(&ms)?)
+}
+
+/// Returns new multi signature of the signatures of both multi signatures.
+#[wasm_bindgen(unchecked_return_type = "MultiSignature")]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureMerge(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] b: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let mut ms: bls::MultiSignature = param("a", a)?;
+    let other: bls::MultiSignature = param("b", b)?;
+    ms.merge(&other)?;
+    Ok(convert_to_js(&ms)?)
 }
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsMultiSignatureAsBytes(multiSignature: &JsValue) -> Result<Vec<u8>, JsValue> {
+pub fn blsMultiSignatureAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSignature: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
     let ms: bls::MultiSignature = convert_from_js(multiSignature)?;
     Ok(ms.as_bytes().to_vec())
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "MultiSignature")]
 #[allow(non_snake_case)]
 pub fn blsMultiSignatureFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
     let ms = bls::MultiSignature::from_bytes(bytes)?;
-    Ok(JsValue::from_serde(&ms).unwrap())
+    Ok(convert_to_js(&ms)?)
+}
+
+/// Encodes multi signature as lower case hex string of its bytes.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureToHex(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSignature: &JsValue,
+) -> Result<String, JsValue> {
+    let ms: bls::MultiSignature = convert_from_js(multiSignature)?;
+    Ok(to_hex(ms.as_bytes()))
+}
+
+#[wasm_bindgen(unchecked_return_type = "MultiSignature")]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureFromHex(hex: &str) -> Result<JsValue, JsValue> {
+    let ms = bls::MultiSignature::from_bytes(&from_hex(hex)?)?;
+    Ok(convert_to_js(&ms)?)
 }
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn blsVerify(
-    signature: &JsValue,
-    message: &[u8],
-    verKey: &JsValue,
-    generator: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Signature")] signature: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Uint8Array")] message: Option<Vec<u8>>,
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
 ) -> Result<bool, JsValue> {
-    let sig: bls::Signature = convert_from_js(signature)?;
-    let vk: bls::VerKey = convert_from_js(verKey)?;
-    let gen: bls::Generator = convert_from_js(generator)?;
-    Ok(bls::Bls::verify(&sig, message, &vk, &gen)?)
+    let sig: bls::Signature = param("signature", signature)?;
+    let message = message_param("message", message)?;
+    let vk: bls::VerKey = param("verKey", verKey)?;
+    let gen = generator_param("generator", generator)?;
+    Ok(bls::Bls::verify(&sig, &message, &vk, &gen)?)
+}
+
+/// Verifies the message signature created by `blsSignWithDst` under the same domain separation tag.
+/// Without `dst` it's the same as `blsVerify`.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyWithDst(
+    #[wasm_bindgen(unchecked_param_type = "Signature")] signature: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Uint8Array")] message: Option<Vec<u8>>,
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+    dst: Option<Vec<u8>>,
+) -> Result<bool, JsValue> {
+    let sig: bls::Signature = param("signature", signature)?;
+    let message = message_param("message", message)?;
+    let vk: bls::VerKey = param("verKey", verKey)?;
+    let gen = generator_param("generator", generator)?;
+    match dst {
+        Some(dst) => Ok(bls::Bls::verify_with_dst(&sig, &message, &vk, &gen, &dst)?),
+        None => Ok(bls::Bls::verify(&sig, &message, &vk, &gen)?),
+    }
 }
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn blsVerifyProofOfPossession(
-    proofOfPossession: &JsValue,
-    verKey: &JsValue,
-    generator: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "ProofOfPossession")] proofOfPossession: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
 ) -> Result<bool, JsValue> {
-    let pop: bls::ProofOfPossession = convert_from_js(proofOfPossession)?;
-    let vk: bls::VerKey = convert_from_js(verKey)?;
-    let gen: bls::Generator = convert_from_js(generator)?;
+    let pop: bls::ProofOfPossession = param("proofOfPossession", proofOfPossession)?;
+    let vk: bls::VerKey = param("verKey", verKey)?;
+    let gen = generator_param("generator", generator)?;
     Ok(bls::Bls::verify_proof_of_posession(&pop, &vk, &gen)?)
 }
 
+/// Verifies proof of possession created by `blsProofOfPossessionForChallenge` for the challenge.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyProofOfPossessionForChallenge(
+    #[wasm_bindgen(unchecked_param_type = "ProofOfPossession")] proofOfPossession: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Uint8Array")] challenge: Option<Vec<u8>>,
+    #[wasm_bindgen(unchecked_param_type = "VerKey")] verKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+) -> Result<bool, JsValue> {
+    let pop: bls::ProofOfPossession = param("proofOfPossession", proofOfPossession)?;
+    let challenge = message_param("challenge", challenge)?;
+    let vk: bls::VerKey = param("verKey", verKey)?;
+    let gen = generator_param("generator", generator)?;
+    Ok(bls::Bls::verify_proof_of_possession_for_challenge(&pop, &challenge, &vk, &gen)?)
+}
+
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn blsVerifyMultiSig(
-    multiSig: &JsValue,
-    message: &[u8],
-    verKeys: Vec<JsValue>,
-    generator: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSig: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Uint8Array")] message: Option<Vec<u8>>,
+    #[wasm_bindgen(unchecked_param_type = "VerKey[]")] verKeys: Vec<JsValue>,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
 ) -> Result<bool, JsValue> {
-    let ms: bls::MultiSignature = convert_from_js(multiSig)?;
-    let vks: Vec<bls::VerKey> = verKeys
-        .iter()
-        .map(|x| {
-            // TODO: Handle error case
-            convert_from_js(x).unwrap()
-        }).collect();
-    let gen: bls::Generator = convert_from_js(generator)?;
+    let ms: bls::MultiSignature = param("multiSig", multiSig)?;
+    let message = message_param("message", message)?;
+    let vks: Vec<bls::VerKey> = params("verKeys", &verKeys)?;
+    let gen = generator_param("generator", generator)?;
     Ok(bls::Bls::verify_multi_sig(
         &ms,
-        message,
+        &message,
         vks.iter().collect::<Vec<_>>().as_slice(),
         &gen,
     )?)
 }
 
+/// Verifies the message multi signature of signatures created by `blsSignWithDst` under the same
+/// domain separation tag. Without `dst` it's the same as `blsVerifyMultiSig`.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyMultiSigWithDst(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSig: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Uint8Array")] message: Option<Vec<u8>>,
+    #[wasm_bindgen(unchecked_param_type = "VerKey[]")] verKeys: Vec<JsValue>,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+    dst: Option<Vec<u8>>,
+) -> Result<bool, JsValue> {
+    let ms: bls::MultiSignature = param("multiSig", multiSig)?;
+    let message = message_param("message", message)?;
+    let vks: Vec<bls::VerKey> = params("verKeys", &verKeys)?;
+    let gen = generator_param("generator", generator)?;
+    let vks = vks.iter().collect::<Vec<_>>();
+    match dst {
+        Some(dst) => Ok(bls::Bls::verify_multi_sig_with_dst(&ms, &message, &vks, &gen, &dst)?),
+        None => Ok(bls::Bls::verify_multi_sig(&ms, &message, &vks, &gen)?),
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "AggregatedVerKey")]
+#[allow(non_snake_case)]
+pub fn blsCreateAggregatedVerKey(
+    #[wasm_bindgen(unchecked_param_type = "VerKey[]")] verKeys: Vec<JsValue>,
+) -> Result<JsValue, JsValue> {
+    let vks: Vec<bls::VerKey> = convert_all_from_js(&verKeys)?;
+    let avk = bls::AggregatedVerKey::new(vks.iter().collect::<Vec<_>>().as_slice())?;
+    Ok(convert_to_js(&avk)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsAggregatedVerKeyAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "AggregatedVerKey")] aggVerKey: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
+    let avk: bls::AggregatedVerKey = convert_from_js(aggVerKey)?;
+    Ok(avk.as_bytes().to_vec())
+}
+
+#[wasm_bindgen(unchecked_return_type = "AggregatedVerKey")]
+#[allow(non_snake_case)]
+pub fn blsAggregatedVerKeyFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    let avk = bls::AggregatedVerKey::from_bytes(bytes)?;
+    Ok(convert_to_js(&avk)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyMultiSigWithAggregatedVerKey(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSig: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Uint8Array")] message: Option<Vec<u8>>,
+    #[wasm_bindgen(unchecked_param_type = "AggregatedVerKey")] aggVerKey: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+) -> Result<bool, JsValue> {
+    let ms: bls::MultiSignature = param("multiSig", multiSig)?;
+    let message = message_param("message", message)?;
+    let avk: bls::AggregatedVerKey = param("aggVerKey", aggVerKey)?;
+    let gen = generator_param("generator", generator)?;
+    Ok(bls::Bls::verify_multi_sig_with_aggregated_ver_key(
+        &ms, &message, &avk, &gen,
+    )?)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchItemJs {
+    signature: bls::Signature,
+    message: Vec<u8>,
+    ver_key: bls::VerKey,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PopBatchItemJs {
+    proof_of_possession: bls::ProofOfPossession,
+    ver_key: bls::VerKey,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AggregateItemJs {
+    message: Vec<u8>,
+    ver_key: bls::VerKey,
+}
+
+impl Validate for BatchItemJs {
+    fn validate(&self) -> Result<(), String> {
+        validate_field("signature", &self.signature)?;
+        validate_field("verKey", &self.ver_key)
+    }
+}
+
+impl Validate for PopBatchItemJs {
+    fn validate(&self) -> Result<(), String> {
+        validate_field("proofOfPossession", &self.proof_of_possession)?;
+        validate_field("verKey", &self.ver_key)
+    }
+}
+
+impl Validate for AggregateItemJs {
+    fn validate(&self) -> Result<(), String> {
+        validate_field("verKey", &self.ver_key)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchResultJs {
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed_index: Option<usize>,
+}
+
+// Batch check doesn't tell which item is invalid, so on failure the items are checked one by one
+// to find the first invalid. Valid batches pay only for the batch check.
+fn batch_result<T, F>(items: &[T], valid: bool, verify: F) -> Result<BatchResultJs, IndyCryptoError>
+where
+    F: Fn(&T) -> Result<bool, IndyCryptoError>,
+{
+    if valid {
+        return Ok(BatchResultJs { valid, failed_index: None });
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        if !verify(item)? {
+            return Ok(BatchResultJs { valid, failed_index: Some(index) });
+        }
+    }
+
+    Ok(BatchResultJs { valid, failed_index: None })
+}
+
+/// Verifies `{ signature, message, verKey }` items at once.
+/// Returns `{ valid }` plus `failedIndex` of the first invalid item if the batch is invalid.
+#[wasm_bindgen(unchecked_return_type = "BatchResult")]
+#[allow(non_snake_case)]
+pub fn blsVerifyBatch(
+    #[wasm_bindgen(unchecked_param_type = "BatchItem[]")] items: Vec<JsValue>,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let items: Vec<BatchItemJs> = params("items", &items)?;
+    let gen = generator_param("generator", generator)?;
+
+    let batch: Vec<(&bls::Signature, &[u8], &bls::VerKey)> = items
+        .iter()
+        .map(|item| (&item.signature, item.message.as_slice(), &item.ver_key))
+        .collect();
+    let valid = bls::Bls::verify_batch(&batch, &gen)?;
+
+    let result = batch_result(&items, valid, |item| {
+        bls::Bls::verify(&item.signature, &item.message, &item.ver_key, &gen)
+    })?;
+    Ok(convert_to_js(&result)?)
+}
+
+/// Verifies `{ proofOfPossession, verKey }` items at once.
+/// Returns `{ valid }` plus `failedIndex` of the first invalid item if the batch is invalid.
+#[wasm_bindgen(unchecked_return_type = "BatchResult")]
+#[allow(non_snake_case)]
+pub fn blsVerifyPopBatch(
+    #[wasm_bindgen(unchecked_param_type = "PopBatchItem[]")] items: Vec<JsValue>,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let items: Vec<PopBatchItemJs> = params("items", &items)?;
+    let gen = generator_param("generator", generator)?;
+
+    let batch: Vec<(&bls::ProofOfPossession, &bls::VerKey)> = items
+        .iter()
+        .map(|item| (&item.proof_of_possession, &item.ver_key))
+        .collect();
+    let valid = bls::Bls::verify_proof_of_possession_batch(&batch, &gen)?;
+
+    let result = batch_result(&items, valid, |item| {
+        bls::Bls::verify_proof_of_posession(&item.proof_of_possession, &item.ver_key, &gen)
+    })?;
+    Ok(convert_to_js(&result)?)
+}
+
+/// Verifies multi signature over distinct messages given as `{ message, verKey }` items, one per signer.
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn blsSignatureAsBytes(signature: &JsValue) -> Result<Vec<u8>, JsValue> {
+pub fn blsVerifyAggregate(
+    #[wasm_bindgen(unchecked_param_type = "MultiSignature")] multiSig: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "AggregateItem[]")] items: Vec<JsValue>,
+    #[wasm_bindgen(unchecked_param_type = "Generator | null")] generator: &JsValue,
+) -> Result<bool, JsValue> {
+    let ms: bls::MultiSignature = param("multiSig", multiSig)?;
+    let items: Vec<AggregateItemJs> = params("items", &items)?;
+    let gen = generator_param("generator", generator)?;
+
+    let aggregate: Vec<(&[u8], &bls::VerKey)> = items
+        .iter()
+        .map(|item| (item.message.as_slice(), &item.ver_key))
+        .collect();
+    Ok(bls::Bls::verify_aggregate(&ms, &aggregate, &gen)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignatureAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "Signature")] signature: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
     let sig: bls::Signature = convert_from_js(signature)?;
     Ok(sig.as_bytes().to_vec())
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Signature")]
 #[allow(non_snake_case)]
 pub fn blsSignatureFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
     let sig = bls::Signature::from_bytes(bytes)?;
-    Ok(JsValue::from_serde(&sig).unwrap())
+    Ok(convert_to_js(&sig)?)
+}
+
+/// Encodes signature as lower case hex string of its bytes.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignatureToHex(
+    #[wasm_bindgen(unchecked_param_type = "Signature")] signature: &JsValue,
+) -> Result<String, JsValue> {
+    let sig: bls::Signature = convert_from_js(signature)?;
+    Ok(to_hex(sig.as_bytes()))
+}
+
+#[wasm_bindgen(unchecked_return_type = "Signature")]
+#[allow(non_snake_case)]
+pub fn blsSignatureFromHex(hex: &str) -> Result<JsValue, JsValue> {
+    let sig = bls::Signature::from_bytes(&from_hex(hex)?)?;
+    Ok(convert_to_js(&sig)?)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignKeySharesJs {
+    sign_key: bls::SignKey,
+    shares: Vec<SignKeyShare>,
+}
+
+/// Splits random (or seeded if seed is given) sign key to `n` shares so that any `threshold` of them can sign.
+/// Returns `{ signKey, shares }`, the dealer derives verification key of the group from `signKey`.
+#[wasm_bindgen(unchecked_return_type = "SignKeyShares")]
+#[allow(non_snake_case)]
+pub fn blsGenerateShares(n: u32, threshold: u32, seed: Option<Vec<u8>>) -> Result<JsValue, JsValue> {
+    let seed = seed.map(SecretBytes::new);
+    let (sign_key, shares) = Threshold::generate_shares(n, threshold, seed.as_deref())?;
+    Ok(convert_to_js(&SignKeySharesJs { sign_key, shares })?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "SignatureShare")]
+#[allow(non_snake_case)]
+pub fn blsSignShare(
+    message: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "SignKeyShare")] share: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let share: SignKeyShare = convert_from_js(share)?;
+    let signature_share = Threshold::sign_share(message, &share)?;
+    Ok(convert_to_js(&signature_share)?)
+}
+
+/// Combines at least `threshold` signature shares with distinct indexes to the signature of the group.
+#[wasm_bindgen(unchecked_return_type = "Signature")]
+#[allow(non_snake_case)]
+pub fn blsCombineSignatureShares(
+    #[wasm_bindgen(unchecked_param_type = "SignatureShare[]")] shares: Vec<JsValue>,
+) -> Result<JsValue, JsValue> {
+    let shares: Vec<SignatureShare> = convert_all_from_js(&shares)?;
+    let shares: Vec<&SignatureShare> = shares.iter().collect();
+    let signature = Threshold::combine_signature_shares(&shares)?;
+    Ok(convert_to_js(&signature)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignKeyShareAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "SignKeyShare")] share: &JsValue,
+) -> Result<js_sys::Uint8Array, JsValue> {
+    let share: SignKeyShare = convert_from_js(share)?;
+    Ok(js_sys::Uint8Array::from(share.as_bytes()))
+}
+
+#[wasm_bindgen(unchecked_return_type = "SignKeyShare")]
+#[allow(non_snake_case)]
+pub fn blsSignKeyShareFromBytes(bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+    let bytes = SecretBytes::new(bytes);
+    let share = SignKeyShare::from_bytes(&bytes)?;
+    Ok(convert_to_js(&share)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignatureShareAsBytes(
+    #[wasm_bindgen(unchecked_param_type = "SignatureShare")] share: &JsValue,
+) -> Result<Vec<u8>, JsValue> {
+    let share: SignatureShare = convert_from_js(share)?;
+    Ok(share.as_bytes().to_vec())
+}
+
+#[wasm_bindgen(unchecked_return_type = "SignatureShare")]
+#[allow(non_snake_case)]
+pub fn blsSignatureShareFromBytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    let share = SignatureShare::from_bytes(bytes)?;
+    Ok(convert_to_js(&share)?)
 }
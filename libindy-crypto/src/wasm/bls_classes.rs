@@ -0,0 +1,299 @@
+use crate::wasm_bindgen::prelude::*;
+
+use crate::bls;
+use crate::errors::IndyCryptoError;
+use crate::utils::zeroize::SecretBytes;
+use super::bls::{convert_from_js, convert_to_js};
+
+// Class API keeping keys and signatures on the Rust side between calls.
+// `toJSON` and `fromJSON` convert to and from the objects used by the function API.
+
+#[wasm_bindgen]
+pub struct BlsGenerator {
+    inner: bls::Generator,
+}
+
+#[wasm_bindgen]
+impl BlsGenerator {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<BlsGenerator, JsValue> {
+        Ok(BlsGenerator { inner: bls::Generator::new()? })
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlsGenerator, JsValue> {
+        Ok(BlsGenerator { inner: bls::Generator::from_bytes(bytes)? })
+    }
+
+    #[wasm_bindgen(js_name = asBytes)]
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.inner.as_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(#[wasm_bindgen(unchecked_param_type = "Generator")] generator: &JsValue) -> Result<BlsGenerator, JsValue> {
+        Ok(BlsGenerator { inner: convert_from_js(generator)? })
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "Generator")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        Ok(convert_to_js(&self.inner)?)
+    }
+}
+
+/// Sign key kept on the Rust side. It is zeroized on `free` and by `zeroize`, which leaves the handle unusable.
+#[wasm_bindgen]
+pub struct BlsSignKey {
+    inner: Option<bls::SignKey>,
+}
+
+#[wasm_bindgen]
+impl BlsSignKey {
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: Option<Vec<u8>>) -> Result<BlsSignKey, JsValue> {
+        let inner = match seed.map(SecretBytes::new) {
+            Some(seed) => bls::SignKey::from_seed(&seed)?,
+            None => bls::SignKey::new(None)?,
+        };
+        Ok(BlsSignKey { inner: Some(inner) })
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<BlsSignKey, JsValue> {
+        let bytes = SecretBytes::new(bytes);
+        Ok(BlsSignKey { inner: Some(bls::SignKey::from_bytes(&bytes)?) })
+    }
+
+    /// Returns copy of the secret bytes, wiping it is the caller's responsibility.
+    #[wasm_bindgen(js_name = asBytes)]
+    pub fn as_bytes(&self) -> Result<js_sys::Uint8Array, JsValue> {
+        Ok(js_sys::Uint8Array::from(self.sign_key()?.as_bytes()))
+    }
+
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(#[wasm_bindgen(unchecked_param_type = "SignKey")] sign_key: &JsValue) -> Result<BlsSignKey, JsValue> {
+        Ok(BlsSignKey { inner: Some(convert_from_js(sign_key)?) })
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "SignKey")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        Ok(convert_to_js(self.sign_key()?)?)
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<BlsSignature, JsValue> {
+        Ok(BlsSignature { inner: bls::Bls::sign(message, self.sign_key()?)? })
+    }
+
+    /// Wipes the sign key, all further calls fail with `CommonInvalidState` error.
+    pub fn zeroize(&mut self) {
+        // Sign key is zeroized on drop
+        self.inner = None;
+    }
+
+    fn sign_key(&self) -> Result<&bls::SignKey, IndyCryptoError> {
+        self.inner.as_ref().ok_or_else(|| IndyCryptoError::InvalidState("Sign key is zeroized".to_string()))
+    }
+}
+
+#[wasm_bindgen]
+pub struct BlsVerKey {
+    inner: bls::VerKey,
+}
+
+#[wasm_bindgen]
+impl BlsVerKey {
+    #[wasm_bindgen(constructor)]
+    pub fn new(generator: &BlsGenerator, sign_key: &BlsSignKey) -> Result<BlsVerKey, JsValue> {
+        Ok(BlsVerKey { inner: bls::VerKey::new(&generator.inner, sign_key.sign_key()?)? })
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlsVerKey, JsValue> {
+        Ok(BlsVerKey { inner: bls::VerKey::from_bytes(bytes)? })
+    }
+
+    #[wasm_bindgen(js_name = asBytes)]
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.inner.as_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(#[wasm_bindgen(unchecked_param_type = "VerKey")] ver_key: &JsValue) -> Result<BlsVerKey, JsValue> {
+        Ok(BlsVerKey { inner: convert_from_js(ver_key)? })
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "VerKey")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        Ok(convert_to_js(&self.inner)?)
+    }
+
+    pub fn verify(&self, signature: &BlsSignature, message: &[u8], generator: &BlsGenerator) -> Result<bool, JsValue> {
+        Ok(bls::Bls::verify(&signature.inner, message, &self.inner, &generator.inner)?)
+    }
+}
+
+#[wasm_bindgen]
+pub struct BlsSignature {
+    inner: bls::Signature,
+}
+
+#[wasm_bindgen]
+impl BlsSignature {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlsSignature, JsValue> {
+        Ok(BlsSignature { inner: bls::Signature::from_bytes(bytes)? })
+    }
+
+    #[wasm_bindgen(js_name = asBytes)]
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.inner.as_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(#[wasm_bindgen(unchecked_param_type = "Signature")] signature: &JsValue) -> Result<BlsSignature, JsValue> {
+        Ok(BlsSignature { inner: convert_from_js(signature)? })
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "Signature")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        Ok(convert_to_js(&self.inner)?)
+    }
+
+    pub fn verify(&self, message: &[u8], ver_key: &BlsVerKey, generator: &BlsGenerator) -> Result<bool, JsValue> {
+        ver_key.verify(self, message, generator)
+    }
+}
+
+/// Multi signature accumulated from signatures of the same message with `add`.
+#[wasm_bindgen]
+pub struct BlsMultiSignature {
+    inner: bls::MultiSignature,
+}
+
+#[wasm_bindgen]
+impl BlsMultiSignature {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<BlsMultiSignature, JsValue> {
+        Ok(BlsMultiSignature { inner: bls::MultiSignature::new(&[])? })
+    }
+
+    /// Adds signature of one more signer.
+    pub fn add(&mut self, signature: &BlsSignature) -> Result<(), JsValue> {
+        self.inner.add(&signature.inner)?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlsMultiSignature, JsValue> {
+        Ok(BlsMultiSignature { inner: bls::MultiSignature::from_bytes(bytes)? })
+    }
+
+    #[wasm_bindgen(js_name = asBytes)]
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.inner.as_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(#[wasm_bindgen(unchecked_param_type = "MultiSignature")] multi_signature: &JsValue) -> Result<BlsMultiSignature, JsValue> {
+        Ok(BlsMultiSignature { inner: convert_from_js(multi_signature)? })
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "MultiSignature")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        Ok(convert_to_js(&self.inner)?)
+    }
+
+    /// Verifies the multi signature against the aggregated verification key of the signers.
+    pub fn verify(&self, message: &[u8], agg_ver_key: &BlsAggregatedVerKey, generator: &BlsGenerator) -> Result<bool, JsValue> {
+        Ok(bls::Bls::verify_multi_sig_with_aggregated_ver_key(&self.inner, message, &agg_ver_key.inner, &generator.inner)?)
+    }
+}
+
+/// Aggregated verification key accumulated from verification keys of the signers with `add`.
+#[wasm_bindgen]
+pub struct BlsAggregatedVerKey {
+    inner: bls::AggregatedVerKey,
+}
+
+#[wasm_bindgen]
+impl BlsAggregatedVerKey {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<BlsAggregatedVerKey, JsValue> {
+        Ok(BlsAggregatedVerKey { inner: bls::AggregatedVerKey::new(&[])? })
+    }
+
+    /// Adds verification key of one more signer.
+    pub fn add(&mut self, ver_key: &BlsVerKey) -> Result<(), JsValue> {
+        self.inner.add(&ver_key.inner)?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlsAggregatedVerKey, JsValue> {
+        Ok(BlsAggregatedVerKey { inner: bls::AggregatedVerKey::from_bytes(bytes)? })
+    }
+
+    #[wasm_bindgen(js_name = asBytes)]
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.inner.as_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(#[wasm_bindgen(unchecked_param_type = "AggregatedVerKey")] agg_ver_key: &JsValue) -> Result<BlsAggregatedVerKey, JsValue> {
+        Ok(BlsAggregatedVerKey { inner: convert_from_js(agg_ver_key)? })
+    }
+
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "AggregatedVerKey")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        Ok(convert_to_js(&self.inner)?)
+    }
+}
+
+/// Hasher of message given in chunks with `update`, so large messages don't have to be copied to wasm at once.
+/// Signature and verification results are the same as for the whole message.
+#[wasm_bindgen]
+pub struct BlsHasher {
+    inner: Option<bls::MessageHasher>,
+}
+
+#[wasm_bindgen]
+impl BlsHasher {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> BlsHasher {
+        BlsHasher { inner: Some(bls::MessageHasher::new()) }
+    }
+
+    /// Appends the chunk to the message.
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), JsValue> {
+        self.hasher()?.update(chunk);
+        Ok(())
+    }
+
+    /// Signs the message, the hasher can't be used after that.
+    #[wasm_bindgen(js_name = finalizeSign)]
+    pub fn finalize_sign(&mut self, sign_key: &BlsSignKey) -> Result<BlsSignature, JsValue> {
+        let hasher = self.take_hasher()?;
+        Ok(BlsSignature { inner: hasher.sign(sign_key.sign_key()?)? })
+    }
+
+    /// Verifies the message signature, the hasher can't be used after that.
+    #[wasm_bindgen(js_name = finalizeVerify)]
+    pub fn finalize_verify(&mut self, signature: &BlsSignature, ver_key: &BlsVerKey, generator: &BlsGenerator) -> Result<bool, JsValue> {
+        let hasher = self.take_hasher()?;
+        Ok(hasher.verify(&signature.inner, &ver_key.inner, &generator.inner)?)
+    }
+
+    fn hasher(&mut self) -> Result<&mut bls::MessageHasher, IndyCryptoError> {
+        self.inner.as_mut().ok_or_else(|| IndyCryptoError::InvalidState("Hasher is already finalized".to_string()))
+    }
+
+    fn take_hasher(&mut self) -> Result<bls::MessageHasher, IndyCryptoError> {
+        self.inner.take().ok_or_else(|| IndyCryptoError::InvalidState("Hasher is already finalized".to_string()))
+    }
+}
+
+impl Default for BlsHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
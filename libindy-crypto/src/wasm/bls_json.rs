@@ -0,0 +1,219 @@
+use crate::wasm_bindgen::prelude::*;
+
+use super::bls::{invalid_argument, Validate};
+use crate::bls;
+use crate::errors::{set_current_error, ErrorDetails, IndyCryptoError};
+use crate::utils::base64::from_base64;
+use crate::utils::zeroize::SecretBytes;
+use serde::{self, Serialize};
+use serde_json;
+
+// Variants of the main BLS functions for embedders that pass strings better than objects, e.g. JSI
+// bridges: objects are taken and returned as JSON strings of the same shape as the JsValue API uses
+// and messages and seeds as standard base64 strings. No JsValue is constructed on the way.
+//
+// Functions don't throw, an error is returned as `{"error":{"code":113,"name":"...","message":"..."}}`.
+// None of the returned objects has an `error` field, so its presence tells the failure.
+
+#[derive(Serialize)]
+struct ErrorJson {
+    error: ErrorDetails,
+}
+
+fn to_json_result<T: Serialize>(res: Result<T, IndyCryptoError>) -> String {
+    let res = res.and_then(|val| {
+        serde_json::to_string(&val).map_err(|err| {
+            IndyCryptoError::InvalidState(format!("Unable to convert to json: {}", err))
+        })
+    });
+
+    match res {
+        Ok(json) => json,
+        Err(err) => {
+            set_current_error(&err);
+            let error = ErrorJson { error: ErrorDetails::from(&err) };
+            // ErrorDetails has only a number and strings, its serialization can't fail
+            serde_json::to_string(&error).unwrap_or_default()
+        }
+    }
+}
+
+fn parse<T>(val: serde_json::Value) -> Result<T, String>
+where
+    for<'a> T: serde::Deserialize<'a> + Validate,
+{
+    let value: T = serde_json::from_value(val).map_err(|err| err.to_string())?;
+    value.validate()?;
+    Ok(value)
+}
+
+fn json_param<T>(name: &str, json: &str) -> Result<T, IndyCryptoError>
+where
+    for<'a> T: serde::Deserialize<'a> + Validate,
+{
+    let val: serde_json::Value = serde_json::from_str(json).map_err(|err| invalid_argument(name, &err))?;
+    parse(val).map_err(|err| invalid_argument(name, &err))
+}
+
+fn json_params<T>(name: &str, json: &str) -> Result<Vec<T>, IndyCryptoError>
+where
+    for<'a> T: serde::Deserialize<'a> + Validate,
+{
+    let vals: Vec<serde_json::Value> = serde_json::from_str(json).map_err(|err| invalid_argument(name, &err))?;
+    if vals.is_empty() {
+        return Err(invalid_argument(name, &"array is empty"));
+    }
+    vals.into_iter()
+        .enumerate()
+        .map(|(index, val)| {
+            parse(val).map_err(|err| {
+                IndyCryptoError::InvalidStructure(format!(
+                    "Invalid argument `{}` at index {}: {}",
+                    name, index, err
+                ))
+            })
+        })
+        .collect()
+}
+
+fn base64_param(name: &str, base64: &str) -> Result<Vec<u8>, IndyCryptoError> {
+    from_base64(base64).map_err(|err| invalid_argument(name, &err))
+}
+
+// Sign keys are only deserialized and signed with, `Validate` is the length check of the verify functions
+fn sign_key_param(name: &str, json: &str) -> Result<bls::SignKey, IndyCryptoError> {
+    serde_json::from_str(json).map_err(|err| invalid_argument(name, &err))
+}
+
+/// Returns JSON of a new random `Generator`.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsGeneratorJson() -> String {
+    to_json_result(bls::Generator::new())
+}
+
+/// Returns JSON of a new `SignKey`, seeded from base64 encoded seed of any non zero length if it's given.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignKeyJson(seedBase64: Option<String>) -> String {
+    to_json_result((|| {
+        match seedBase64 {
+            Some(seed) => bls::SignKey::from_seed(&SecretBytes::new(base64_param("seedBase64", &seed)?)),
+            None => bls::SignKey::new(None)
+        }
+    })())
+}
+
+/// Returns JSON of `VerKey` of the sign key.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerKeyJson(generatorJson: &str, signKeyJson: &str) -> String {
+    to_json_result((|| {
+        let gen: bls::Generator = json_param("generatorJson", generatorJson)?;
+        let sk = sign_key_param("signKeyJson", signKeyJson)?;
+        bls::VerKey::new(&gen, &sk)
+    })())
+}
+
+/// Returns JSON of `ProofOfPossession` of the ver key.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsProofOfPossessionJson(verKeyJson: &str, signKeyJson: &str) -> String {
+    to_json_result((|| {
+        let vk: bls::VerKey = json_param("verKeyJson", verKeyJson)?;
+        let sk = sign_key_param("signKeyJson", signKeyJson)?;
+        bls::ProofOfPossession::new(&vk, &sk)
+    })())
+}
+
+/// Returns JSON of `Signature` of the base64 encoded message.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsSignJson(messageBase64: &str, signKeyJson: &str) -> String {
+    to_json_result((|| {
+        let message = base64_param("messageBase64", messageBase64)?;
+        let sk = sign_key_param("signKeyJson", signKeyJson)?;
+        bls::Bls::sign(&message, &sk)
+    })())
+}
+
+/// Returns `true` or `false` if the signature of the base64 encoded message is valid or not.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyJson(signatureJson: &str, messageBase64: &str, verKeyJson: &str, generatorJson: &str) -> String {
+    to_json_result((|| {
+        let sig: bls::Signature = json_param("signatureJson", signatureJson)?;
+        let message = base64_param("messageBase64", messageBase64)?;
+        let vk: bls::VerKey = json_param("verKeyJson", verKeyJson)?;
+        let gen: bls::Generator = json_param("generatorJson", generatorJson)?;
+        bls::Bls::verify(&sig, &message, &vk, &gen)
+    })())
+}
+
+/// Returns `true` or `false` if the proof of possession is valid or not.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyProofOfPossessionJson(proofOfPossessionJson: &str, verKeyJson: &str, generatorJson: &str) -> String {
+    to_json_result((|| {
+        let pop: bls::ProofOfPossession = json_param("proofOfPossessionJson", proofOfPossessionJson)?;
+        let vk: bls::VerKey = json_param("verKeyJson", verKeyJson)?;
+        let gen: bls::Generator = json_param("generatorJson", generatorJson)?;
+        bls::Bls::verify_proof_of_posession(&pop, &vk, &gen)
+    })())
+}
+
+/// Returns JSON of `MultiSignature` of JSON array of signatures.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsMultiSignatureJson(signaturesJson: &str) -> String {
+    to_json_result((|| {
+        let signatures: Vec<bls::Signature> = json_params("signaturesJson", signaturesJson)?;
+        let signatures: Vec<&bls::Signature> = signatures.iter().collect();
+        bls::MultiSignature::new(&signatures)
+    })())
+}
+
+/// Returns `true` or `false` if the multi signature of the base64 encoded message is valid or not
+/// for JSON array of ver keys.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyMultiSigJson(multiSigJson: &str, messageBase64: &str, verKeysJson: &str, generatorJson: &str) -> String {
+    to_json_result((|| {
+        let multi_sig: bls::MultiSignature = json_param("multiSigJson", multiSigJson)?;
+        let message = base64_param("messageBase64", messageBase64)?;
+        let ver_keys: Vec<bls::VerKey> = json_params("verKeysJson", verKeysJson)?;
+        let gen: bls::Generator = json_param("generatorJson", generatorJson)?;
+        let ver_keys: Vec<&bls::VerKey> = ver_keys.iter().collect();
+        bls::Bls::verify_multi_sig(&multi_sig, &message, &ver_keys, &gen)
+    })())
+}
+
+/// Returns JSON of `AggregatedVerKey` of JSON array of ver keys.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsCreateAggregatedVerKeyJson(verKeysJson: &str) -> String {
+    to_json_result((|| {
+        let ver_keys: Vec<bls::VerKey> = json_params("verKeysJson", verKeysJson)?;
+        let ver_keys: Vec<&bls::VerKey> = ver_keys.iter().collect();
+        bls::AggregatedVerKey::new(&ver_keys)
+    })())
+}
+
+/// Returns `true` or `false` if the multi signature of the base64 encoded message is valid or not
+/// for the aggregated ver key.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn blsVerifyMultiSigWithAggregatedVerKeyJson(
+    multiSigJson: &str,
+    messageBase64: &str,
+    aggregatedVerKeyJson: &str,
+    generatorJson: &str,
+) -> String {
+    to_json_result((|| {
+        let multi_sig: bls::MultiSignature = json_param("multiSigJson", multiSigJson)?;
+        let message = base64_param("messageBase64", messageBase64)?;
+        let agg_ver_key: bls::AggregatedVerKey = json_param("aggregatedVerKeyJson", aggregatedVerKeyJson)?;
+        let gen: bls::Generator = json_param("generatorJson", generatorJson)?;
+        bls::Bls::verify_multi_sig_with_aggregated_ver_key(&multi_sig, &message, &agg_ver_key, &gen)
+    })())
+}
@@ -1 +1,4 @@
 pub mod bls;
+pub mod bls_classes;
+pub mod bls_json;
+pub mod pair;
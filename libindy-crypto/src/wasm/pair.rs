@@ -0,0 +1,280 @@
+use crate::wasm_bindgen::prelude::*;
+
+use crate::pair::{GroupOrderElement, Pair, PointG1, PointG2};
+use super::bls::{convert_from_js, convert_to_js};
+
+// Low level pairing primitives. Values are passed to JS in their serde form, which is the AMCL hex string,
+// so `*ToHex` and `*FromHex` are validating conversions between the JS value and a plain string.
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/**
+ * AMCL hex string representations of the pairing primitives. The types are branded, so one
+ * can't be passed for another, use the `*FromHex` functions to get them from plain strings.
+ */
+export type PointG1 = string & { readonly __brand: "PointG1" };
+export type PointG2 = string & { readonly __brand: "PointG2" };
+export type GroupOrderElement = string & { readonly __brand: "GroupOrderElement" };
+export type Pair = string & { readonly __brand: "Pair" };
+"#;
+
+macro_rules! conversions {
+    ($type:ident, $ts:literal, $from_bytes:ident, $to_bytes:ident, $from_hex:ident, $to_hex:ident) => {
+        #[wasm_bindgen(unchecked_return_type = $ts)]
+        #[allow(non_snake_case)]
+        pub fn $from_bytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
+            Ok(convert_to_js(&$type::from_bytes(bytes)?)?)
+        }
+
+        #[wasm_bindgen]
+        #[allow(non_snake_case)]
+        pub fn $to_bytes(#[wasm_bindgen(unchecked_param_type = $ts)] value: &JsValue) -> Result<Vec<u8>, JsValue> {
+            let value: $type = convert_from_js(value)?;
+            Ok(value.to_bytes()?)
+        }
+
+        #[wasm_bindgen(unchecked_return_type = $ts)]
+        #[allow(non_snake_case)]
+        pub fn $from_hex(hex: &str) -> Result<JsValue, JsValue> {
+            Ok(convert_to_js(&$type::from_string(hex)?)?)
+        }
+
+        #[wasm_bindgen]
+        #[allow(non_snake_case)]
+        pub fn $to_hex(#[wasm_bindgen(unchecked_param_type = $ts)] value: &JsValue) -> Result<String, JsValue> {
+            let value: $type = convert_from_js(value)?;
+            Ok(value.to_string()?)
+        }
+    };
+}
+
+conversions!(PointG1, "PointG1", pointG1FromBytes, pointG1ToBytes, pointG1FromHex, pointG1ToHex);
+conversions!(PointG2, "PointG2", pointG2FromBytes, pointG2ToBytes, pointG2FromHex, pointG2ToHex);
+conversions!(GroupOrderElement, "GroupOrderElement", groupOrderElementFromBytes, groupOrderElementToBytes, groupOrderElementFromHex, groupOrderElementToHex);
+conversions!(Pair, "Pair", pairFromBytes, pairToBytes, pairFromHex, pairToHex);
+
+/// Random point of G1.
+#[wasm_bindgen(unchecked_return_type = "PointG1")]
+#[allow(non_snake_case)]
+pub fn pointG1New() -> Result<JsValue, JsValue> {
+    Ok(convert_to_js(&PointG1::new()?)?)
+}
+
+/// Point at infinity of G1.
+#[wasm_bindgen(unchecked_return_type = "PointG1")]
+#[allow(non_snake_case)]
+pub fn pointG1NewInf() -> Result<JsValue, JsValue> {
+    Ok(convert_to_js(&PointG1::new_inf()?)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn pointG1IsInf(#[wasm_bindgen(unchecked_param_type = "PointG1")] p: &JsValue) -> Result<bool, JsValue> {
+    let p: PointG1 = convert_from_js(p)?;
+    Ok(p.is_inf()?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG1")]
+#[allow(non_snake_case)]
+pub fn pointG1Add(
+    #[wasm_bindgen(unchecked_param_type = "PointG1")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "PointG1")] q: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG1 = convert_from_js(p)?;
+    let q: PointG1 = convert_from_js(q)?;
+    Ok(convert_to_js(&p.add(&q)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG1")]
+#[allow(non_snake_case)]
+pub fn pointG1Sub(
+    #[wasm_bindgen(unchecked_param_type = "PointG1")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "PointG1")] q: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG1 = convert_from_js(p)?;
+    let q: PointG1 = convert_from_js(q)?;
+    Ok(convert_to_js(&p.sub(&q)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG1")]
+#[allow(non_snake_case)]
+pub fn pointG1Neg(#[wasm_bindgen(unchecked_param_type = "PointG1")] p: &JsValue) -> Result<JsValue, JsValue> {
+    let p: PointG1 = convert_from_js(p)?;
+    Ok(convert_to_js(&p.neg()?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG1")]
+#[allow(non_snake_case)]
+pub fn pointG1Mul(
+    #[wasm_bindgen(unchecked_param_type = "PointG1")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] e: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG1 = convert_from_js(p)?;
+    let e: GroupOrderElement = convert_from_js(e)?;
+    Ok(convert_to_js(&p.mul(&e)?)?)
+}
+
+/// Random point of G2.
+#[wasm_bindgen(unchecked_return_type = "PointG2")]
+#[allow(non_snake_case)]
+pub fn pointG2New() -> Result<JsValue, JsValue> {
+    Ok(convert_to_js(&PointG2::new()?)?)
+}
+
+/// Point at infinity of G2.
+#[wasm_bindgen(unchecked_return_type = "PointG2")]
+#[allow(non_snake_case)]
+pub fn pointG2NewInf() -> Result<JsValue, JsValue> {
+    Ok(convert_to_js(&PointG2::new_inf()?)?)
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn pointG2IsInf(#[wasm_bindgen(unchecked_param_type = "PointG2")] p: &JsValue) -> Result<bool, JsValue> {
+    let p: PointG2 = convert_from_js(p)?;
+    Ok(p.is_inf()?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG2")]
+#[allow(non_snake_case)]
+pub fn pointG2Add(
+    #[wasm_bindgen(unchecked_param_type = "PointG2")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "PointG2")] q: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG2 = convert_from_js(p)?;
+    let q: PointG2 = convert_from_js(q)?;
+    Ok(convert_to_js(&p.add(&q)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG2")]
+#[allow(non_snake_case)]
+pub fn pointG2Sub(
+    #[wasm_bindgen(unchecked_param_type = "PointG2")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "PointG2")] q: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG2 = convert_from_js(p)?;
+    let q: PointG2 = convert_from_js(q)?;
+    Ok(convert_to_js(&p.sub(&q)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "PointG2")]
+#[allow(non_snake_case)]
+pub fn pointG2Mul(
+    #[wasm_bindgen(unchecked_param_type = "PointG2")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] e: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG2 = convert_from_js(p)?;
+    let e: GroupOrderElement = convert_from_js(e)?;
+    Ok(convert_to_js(&p.mul(&e)?)?)
+}
+
+/// Random element of 0, ..., GroupOrder-1.
+#[wasm_bindgen(unchecked_return_type = "GroupOrderElement")]
+#[allow(non_snake_case)]
+pub fn groupOrderElementNew() -> Result<JsValue, JsValue> {
+    Ok(convert_to_js(&GroupOrderElement::new()?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "GroupOrderElement")]
+#[allow(non_snake_case)]
+pub fn groupOrderElementAddMod(
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] b: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: GroupOrderElement = convert_from_js(a)?;
+    let b: GroupOrderElement = convert_from_js(b)?;
+    Ok(convert_to_js(&a.add_mod(&b)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "GroupOrderElement")]
+#[allow(non_snake_case)]
+pub fn groupOrderElementSubMod(
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] b: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: GroupOrderElement = convert_from_js(a)?;
+    let b: GroupOrderElement = convert_from_js(b)?;
+    Ok(convert_to_js(&a.sub_mod(&b)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "GroupOrderElement")]
+#[allow(non_snake_case)]
+pub fn groupOrderElementMulMod(
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] b: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: GroupOrderElement = convert_from_js(a)?;
+    let b: GroupOrderElement = convert_from_js(b)?;
+    Ok(convert_to_js(&a.mul_mod(&b)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "GroupOrderElement")]
+#[allow(non_snake_case)]
+pub fn groupOrderElementInverse(
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] a: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: GroupOrderElement = convert_from_js(a)?;
+    Ok(convert_to_js(&a.inverse()?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "GroupOrderElement")]
+#[allow(non_snake_case)]
+pub fn groupOrderElementModNeg(
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] a: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: GroupOrderElement = convert_from_js(a)?;
+    Ok(convert_to_js(&a.mod_neg()?)?)
+}
+
+/// e(p, q)
+#[wasm_bindgen(unchecked_return_type = "Pair")]
+#[allow(non_snake_case)]
+pub fn pairPair(
+    #[wasm_bindgen(unchecked_param_type = "PointG1")] p: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "PointG2")] q: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let p: PointG1 = convert_from_js(p)?;
+    let q: PointG2 = convert_from_js(q)?;
+    Ok(convert_to_js(&Pair::pair(&p, &q)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "Pair")]
+#[allow(non_snake_case)]
+pub fn pairMul(
+    #[wasm_bindgen(unchecked_param_type = "Pair")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Pair")] b: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: Pair = convert_from_js(a)?;
+    let b: Pair = convert_from_js(b)?;
+    Ok(convert_to_js(&a.mul(&b)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "Pair")]
+#[allow(non_snake_case)]
+pub fn pairPow(
+    #[wasm_bindgen(unchecked_param_type = "Pair")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "GroupOrderElement")] e: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let a: Pair = convert_from_js(a)?;
+    let e: GroupOrderElement = convert_from_js(e)?;
+    Ok(convert_to_js(&a.pow(&e)?)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "Pair")]
+#[allow(non_snake_case)]
+pub fn pairInverse(#[wasm_bindgen(unchecked_param_type = "Pair")] a: &JsValue) -> Result<JsValue, JsValue> {
+    let a: Pair = convert_from_js(a)?;
+    Ok(convert_to_js(&a.inverse()?)?)
+}
+
+/// Compares pairs by value, hex strings of equal pairs are not guaranteed to be equal.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn pairEquals(
+    #[wasm_bindgen(unchecked_param_type = "Pair")] a: &JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Pair")] b: &JsValue,
+) -> Result<bool, JsValue> {
+    let a: Pair = convert_from_js(a)?;
+    let b: Pair = convert_from_js(b)?;
+    Ok(a == b)
+}
@@ -16,7 +16,9 @@ pub const PROVER_ID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFV
 
 mod test {
     use super::*;
-    use crate::indy_crypto::errors::ErrorCode;
+    use crate::indy_crypto::cl::*;
+    use crate::indy_crypto::cl::{issuer, verifier};
+    use crate::indy_crypto::errors::{ErrorCode, ErrorKind, IndyCryptoError};
     use crate::indy_crypto::errors::ToErrorCode;
 
     #[test]
@@ -3231,82 +3233,1587 @@ mod test {
                                                        None, None, None);
         assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err().to_error_code());
     }
-}
 
-mod helpers {
-    use super::*;
-    use crate::indy_crypto::cl::*;
+    #[test]
+    fn multiple_predicates() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("funds_sold_and_securities_purchased").unwrap();
+        credential_schema_builder.add_attr("other_earning_assets").unwrap();
+        credential_schema_builder.add_attr("cash").unwrap();
+        credential_schema_builder.add_attr("allowance").unwrap();
+        credential_schema_builder.add_attr("total_assets").unwrap();
+        credential_schema_builder.add_attr("domestic_interest_bearing_deposits").unwrap();
+        credential_schema_builder.add_attr("funds_purchased").unwrap();
+        credential_schema_builder.add_attr("long_term_debt").unwrap();
+        credential_schema_builder.add_attr("non_interest_bearing_liabilities").unwrap();
+        credential_schema_builder.add_attr("shareholder_equity").unwrap();
+        credential_schema_builder.add_attr("total_liabilities").unwrap();
 
-    pub fn gvt_credential_schema() -> CredentialSchema {
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_nonce = new_nonce().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("funds_sold_and_securities_purchased", "50").unwrap();
+        credential_values_builder.add_dec_known("other_earning_assets", "60").unwrap();
+        credential_values_builder.add_dec_known("cash", "70").unwrap();
+        credential_values_builder.add_dec_known("allowance", "80").unwrap();
+        credential_values_builder.add_dec_known("total_assets", "260").unwrap();
+
+        credential_values_builder.add_dec_known("domestic_interest_bearing_deposits", "10").unwrap();
+        credential_values_builder.add_dec_known("funds_purchased", "20").unwrap();
+        credential_values_builder.add_dec_known("long_term_debt", "30").unwrap();
+        credential_values_builder.add_dec_known("non_interest_bearing_liabilities", "40").unwrap();
+        credential_values_builder.add_dec_known("shareholder_equity", "50").unwrap();
+        credential_values_builder.add_dec_known("total_liabilities", "150").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key,
+                                        &cred_key_correctness_proof,
+                                        &cred_values,
+                                        &credential_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("b977afe22b5b446109797ad925d9f133fc33c1914081071295d2ac1ddce3385d",
+                                                                                        &blinded_credential_secrets,
+                                                                                        &blinded_credential_secrets_correctness_proof,
+                                                                                        &credential_nonce,
+                                                                                        &cred_issuance_nonce,
+                                                                                        &cred_values,
+                                                                                        &cred_pub_key,
+                                                                                        &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("total_liabilities").unwrap();
+
+        sub_proof_request_builder.add_predicate("funds_sold_and_securities_purchased", "LT", 100).unwrap();
+        sub_proof_request_builder.add_predicate("funds_sold_and_securities_purchased", "GT", 0).unwrap();
+        sub_proof_request_builder.add_predicate("other_earning_assets", "LT", 100).unwrap();
+        sub_proof_request_builder.add_predicate("cash", "LT", 100).unwrap();
+        sub_proof_request_builder.add_predicate("allowance", "LT", 100).unwrap();
+        sub_proof_request_builder.add_predicate("total_assets", "GT", 100).unwrap();
+
+        sub_proof_request_builder.add_predicate("domestic_interest_bearing_deposits", "LE", 100).unwrap();
+        sub_proof_request_builder.add_predicate("funds_purchased", "LE", 100).unwrap();
+        sub_proof_request_builder.add_predicate("long_term_debt", "LE", 100).unwrap();
+        sub_proof_request_builder.add_predicate("non_interest_bearing_liabilities", "LE", 100).unwrap();
+        sub_proof_request_builder.add_predicate("shareholder_equity", "LE", 100).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    // Proves predicates of sub proof request over credential with "age" 28, Err if Prover can't build the proof
+    fn _prove_age_predicates<F>(credential_schema: &CredentialSchema,
+                                non_credential_schema: &NonCredentialSchema,
+                                cred_pub_key: &CredentialPublicKey,
+                                cred_signature: &CredentialSignature,
+                                cred_values: &CredentialValues,
+                                add_predicates: F) -> Result<bool, IndyCryptoError> where F: FnOnce(&mut SubProofRequestBuilder) {
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        add_predicates(&mut sub_proof_request_builder);
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            credential_schema,
+                                            non_credential_schema,
+                                            cred_signature,
+                                            cred_values,
+                                            cred_pub_key,
+                                            None,
+                                            None)?;
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce)?;
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             credential_schema,
+                                             non_credential_schema,
+                                             cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        proof_verifier.verify(&proof, &proof_request_nonce)
+    }
+
+    #[test]
+    fn predicates_work_for_boundary_values() {
         let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
-        credential_schema_builder.add_attr("name").unwrap();
-        credential_schema_builder.add_attr("sex").unwrap();
         credential_schema_builder.add_attr("age").unwrap();
-        credential_schema_builder.add_attr("height").unwrap();
-        credential_schema_builder.finalize().unwrap()
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_nonce = new_nonce().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("b977afe22b5b446109797ad925d9f133fc33c1914081071295d2ac1ddce3385d",
+                                                                                        &blinded_credential_secrets,
+                                                                                        &blinded_credential_secrets_correctness_proof,
+                                                                                        &credential_nonce,
+                                                                                        &cred_issuance_nonce,
+                                                                                        &cred_values,
+                                                                                        &cred_pub_key,
+                                                                                        &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        let prove = |add_predicates: &dyn Fn(&mut SubProofRequestBuilder)| {
+            _prove_age_predicates(&credential_schema, &non_credential_schema, &cred_pub_key, &cred_signature, &cred_values,
+                                  |builder| add_predicates(builder))
+        };
+
+        // boundary value is satisfied, off-by-one isn't
+        for &(p_type, satisfied, off_by_one) in &[("GE", 28, 29), ("GT", 27, 28), ("LE", 28, 27), ("LT", 29, 28)] {
+            assert!(prove(&|builder| builder.add_predicate("age", p_type, satisfied).unwrap()).unwrap(), "{} {}", p_type, satisfied);
+
+            let err = prove(&|builder| builder.add_predicate("age", p_type, off_by_one).unwrap()).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, err.kind(), "{} {}", p_type, off_by_one);
+        }
+
+        assert!(prove(&|builder| builder.add_range_predicate("age", 28, 28).unwrap()).unwrap());
+        assert!(prove(&|builder| builder.add_range_predicate("age", 18, 65).unwrap()).unwrap());
+        assert_eq!(ErrorKind::InvalidStructure, prove(&|builder| builder.add_range_predicate("age", 29, 65).unwrap()).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, prove(&|builder| builder.add_range_predicate("age", 18, 27).unwrap()).unwrap_err().kind());
     }
 
-    pub fn xyz_credential_schema() -> CredentialSchema {
+    #[test]
+    fn predicates_work_for_extreme_values() {
         let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
-        credential_schema_builder.add_attr("status").unwrap();
-        credential_schema_builder.add_attr("period").unwrap();
-        credential_schema_builder.finalize().unwrap()
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let prove = |age: &str, p_type: &str, value: i32| {
+            let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+            credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+            credential_values_builder.add_dec_known("age", age).unwrap();
+            let cred_values = credential_values_builder.finalize().unwrap();
+            let cred_signature = helpers::issue_credential(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &cred_values);
+
+            _prove_age_predicates(&credential_schema, &non_credential_schema, &cred_pub_key, &cred_signature, &cred_values,
+                                  |builder| builder.add_predicate("age", p_type, value).unwrap())
+        };
+
+        let (max, min) = (i32::MAX.to_string(), i32::MIN.to_string());
+
+        // deltas of 2^32 - 1 at the ends of the range
+        assert!(prove(&max, "GE", i32::MIN).unwrap());
+        assert!(prove(&min, "LE", i32::MAX).unwrap());
+        assert!(prove(&max, "GT", i32::MAX - 1).unwrap());
+        assert!(prove(&min, "LT", i32::MIN + 1).unwrap());
+        assert_eq!(ErrorKind::InvalidStructure, prove(&min, "GE", i32::MAX).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, prove(&max, "LT", i32::MAX).unwrap_err().kind());
+
+        // negative values
+        assert!(prove("-5", "GE", -10).unwrap());
+        assert!(prove("-5", "LT", 0).unwrap());
+        assert_eq!(ErrorKind::InvalidStructure, prove("-5", "GT", -5).unwrap_err().kind());
+
+        // values out of i32 range aren't predicate values
+        for age in &[(i32::MAX as i64 + 1).to_string(), (i32::MIN as i64 - 1).to_string(), i64::MAX.to_string(), i64::MIN.to_string()] {
+            let err = prove(age, "GE", 0).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, err.kind(), "{}", age);
+            assert!(err.to_string().contains("isn't integer within i32 range"), "{}", age);
+        }
     }
 
-    pub fn non_credential_schema() -> NonCredentialSchema {
+    #[test]
+    fn demo_with_validated_credential_values() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
         let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
         non_credential_schema_builder.add_attr("master_secret").unwrap();
-        non_credential_schema_builder.finalize().unwrap()
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let name = encode_attribute("Alex", AttributeKind::Text).unwrap();
+
+        // Issuer knows only known values
+        let issuer_cred_values = CredentialValues::try_from_pairs(&[("name", &name), ("age", "28")]).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &name).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize_for_schema(&credential_schema, &non_credential_schema).unwrap();
+
+        let credential_nonce = new_nonce().unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("b977afe22b5b446109797ad925d9f133fc33c1914081071295d2ac1ddce3385d",
+                                                                                        &blinded_credential_secrets,
+                                                                                        &blinded_credential_secrets_correctness_proof,
+                                                                                        &credential_nonce,
+                                                                                        &cred_issuance_nonce,
+                                                                                        &issuer_cred_values,
+                                                                                        &cred_pub_key,
+                                                                                        &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        assert!(_prove_age_predicates(&credential_schema, &non_credential_schema, &cred_pub_key, &cred_signature, &cred_values, |builder| {
+            builder.add_revealed_attr("name").unwrap();
+            builder.add_predicate("age", "GE", 18).unwrap();
+        }).unwrap());
     }
 
-    pub fn gvt_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+    #[test]
+    fn demo_with_credentials_batch() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let names = ["Alex", "Bob", "Carol"];
+        let prover_ids = ["CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW", "VsKV7grR1BUE29mG2Fm2kX", "Th7MpTaRZVRYnPiabds81Y"];
+
+        let mut holders = Vec::new();
+        for name in &names {
+            let master_secret = Prover::new_master_secret().unwrap();
+            let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+            credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+            credential_values_builder.add_dec_known("name", &encode_attribute(name, AttributeKind::Text).unwrap()).unwrap();
+            credential_values_builder.add_dec_known("age", "28").unwrap();
+            let cred_values = credential_values_builder.finalize().unwrap();
+
+            let credential_nonce = new_nonce().unwrap();
+            let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+                Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+            holders.push((cred_values, credential_nonce, blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof));
+        }
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let requests: Vec<issuer::CredentialSignRequest> = holders
+            .iter()
+            .zip(prover_ids.iter())
+            .map(|(&(ref cred_values, ref credential_nonce, ref blinded_credential_secrets, _, ref blinded_credential_secrets_correctness_proof), prover_id)|
+                issuer::CredentialSignRequest {
+                    prover_id,
+                    blinded_credential_secrets,
+                    blinded_credential_secrets_correctness_proof,
+                    credential_nonce,
+                    credential_issuance_nonce: &cred_issuance_nonce,
+                    credential_values: cred_values,
+                })
+            .collect();
+
+        let signatures = Issuer::sign_credentials_batch(&requests, &cred_pub_key, &cred_priv_key).unwrap();
+        assert_eq!(names.len(), signatures.len());
+
+        for (signature, holder) in signatures.into_iter().zip(holders.iter()) {
+            let (mut cred_signature, signature_correctness_proof) = signature.unwrap();
+            let &(ref cred_values, _, _, ref credential_secrets_blinding_factors, _) = holder;
+
+            Prover::process_credential_signature(&mut cred_signature,
+                                                 cred_values,
+                                                 &signature_correctness_proof,
+                                                 credential_secrets_blinding_factors,
+                                                 &cred_pub_key,
+                                                 &cred_issuance_nonce,
+                                                 None,
+                                                 None,
+                                                 None).unwrap();
+
+            assert!(_prove_age_predicates(&credential_schema, &non_credential_schema, &cred_pub_key, &cred_signature, cred_values, |builder| {
+                builder.add_revealed_attr("name").unwrap();
+                builder.add_predicate("age", "GE", 18).unwrap();
+            }).unwrap());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn demo_with_parallel_key_generation() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        // Explicit pool, so safe primes are searched by several workers on a single core machine too
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let mut primes_found = 0;
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = pool.install(|| {
+            Issuer::new_credential_def_with_progress(&credential_schema, &non_credential_schema, false, &mut |event| {
+                if event == issuer::KeyGenProgress::PrimeFound {
+                    primes_found += 1;
+                }
+                true
+            })
+        }).unwrap();
+        assert_eq!(2, primes_found);
+
+        let master_secret = Prover::new_master_secret().unwrap();
         let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
-        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
-        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
         credential_values_builder.add_dec_known("age", "28").unwrap();
-        credential_values_builder.add_dec_known("height", "175").unwrap();
-        credential_values_builder.finalize().unwrap()
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let cred_signature = helpers::issue_credential(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &cred_values);
+
+        assert!(_prove_age_predicates(&credential_schema, &non_credential_schema, &cred_pub_key, &cred_signature, &cred_values, |builder| {
+            builder.add_revealed_attr("name").unwrap();
+            builder.add_predicate("age", "GE", 18).unwrap();
+        }).unwrap());
     }
 
-    pub fn xyz_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+    #[test]
+    #[cfg(feature = "seeded_keys")]
+    fn demo_with_seeded_credential_def() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let seed = b"seed of integration test issuer!";
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, true, seed).unwrap();
+        let (same_cred_pub_key, same_cred_priv_key, same_cred_key_correctness_proof) =
+            Issuer::new_credential_def_from_seed(&credential_schema, &non_credential_schema, true, seed).unwrap();
+
+        assert_eq!(cred_pub_key, same_cred_pub_key);
+        assert_eq!(serde_json::to_string(&cred_priv_key).unwrap(), serde_json::to_string(&same_cred_priv_key).unwrap());
+        assert_eq!(cred_key_correctness_proof, same_cred_key_correctness_proof);
+
+        let master_secret = Prover::new_master_secret().unwrap();
         let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
-        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
-        credential_values_builder.add_dec_known("status", "51792877103171595686471452153480627530895").unwrap();
-        credential_values_builder.add_dec_known("period", "8").unwrap();
-        credential_values_builder.finalize().unwrap()
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let cred_signature = helpers::issue_credential(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &cred_values);
+
+        assert!(_prove_age_predicates(&credential_schema, &non_credential_schema, &cred_pub_key, &cred_signature, &cred_values, |builder| {
+            builder.add_revealed_attr("name").unwrap();
+            builder.add_predicate("age", "GE", 18).unwrap();
+        }).unwrap());
     }
 
-    pub fn gvt_sub_proof_request() -> SubProofRequest {
-        let mut gvt_sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
-        gvt_sub_proof_request_builder.add_revealed_attr("name").unwrap();
-        gvt_sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
-        gvt_sub_proof_request_builder.finalize().unwrap()
+    #[test]
+    fn demo_with_linked_sub_proofs() {
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let mut name_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        name_schema_builder.add_attr("name").unwrap();
+        let name_schema = name_schema_builder.finalize().unwrap();
+        let (name_pub_key, name_priv_key, name_key_correctness_proof) =
+            Issuer::new_credential_def(&name_schema, &non_credential_schema, false).unwrap();
+
+        let mut age_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        age_schema_builder.add_attr("age").unwrap();
+        let age_schema = age_schema_builder.finalize().unwrap();
+        let (age_pub_key, age_priv_key, age_key_correctness_proof) =
+            Issuer::new_credential_def(&age_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let other_master_secret = Prover::new_master_secret().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        let name_values = credential_values_builder.finalize().unwrap();
+        let name_signature = helpers::issue_credential(&name_pub_key, &name_priv_key, &name_key_correctness_proof, &name_values);
+
+        let age_values = |master_secret: &MasterSecret| {
+            let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+            credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+            credential_values_builder.add_dec_known("age", "28").unwrap();
+            credential_values_builder.finalize().unwrap()
+        };
+        let own_age_values = age_values(&master_secret);
+        let own_age_signature = helpers::issue_credential(&age_pub_key, &age_priv_key, &age_key_correctness_proof, &own_age_values);
+        // credential of other holder, that has other master secret
+        let other_age_values = age_values(&other_master_secret);
+        let other_age_signature = helpers::issue_credential(&age_pub_key, &age_priv_key, &age_key_correctness_proof, &other_age_values);
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let name_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let age_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let new_proof_builder = |age_signature: &CredentialSignature, age_values: &CredentialValues| {
+            let mut proof_builder = Prover::new_proof_builder().unwrap();
+            proof_builder.add_common_attribute("master_secret").unwrap();
+            proof_builder.add_sub_proof_request(&name_sub_proof_request, &name_schema, &non_credential_schema,
+                                                &name_signature, &name_values, &name_pub_key, None, None).unwrap();
+            proof_builder.add_sub_proof_request(&age_sub_proof_request, &age_schema, &non_credential_schema,
+                                                age_signature, age_values, &age_pub_key, None, None).unwrap();
+            proof_builder
+        };
+
+        let new_proof_verifier = |linked: bool| {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&name_sub_proof_request, &name_schema, &non_credential_schema,
+                                                 &name_pub_key, None, None).unwrap();
+            proof_verifier.add_sub_proof_request(&age_sub_proof_request, &age_schema, &non_credential_schema,
+                                                 &age_pub_key, None, None).unwrap();
+            if linked {
+                proof_verifier.add_linked_sub_proofs("master_secret", &[0, 1]).unwrap();
+            }
+            proof_verifier
+        };
+
+        // honest holder
+        let mut proof_builder = new_proof_builder(&own_age_signature, &own_age_values);
+        proof_builder.add_linked_sub_proofs("master_secret", &[0, 1]).unwrap();
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+        assert!(new_proof_verifier(true).verify(&proof, &nonce).unwrap());
+
+        // malicious holder combines credentials of different master secrets
+        let mut proof_builder = new_proof_builder(&other_age_signature, &other_age_values);
+        let err = proof_builder.add_linked_sub_proofs("master_secret", &[0, 1]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("don't share the value of 'master_secret'"));
+
+        let nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&nonce).unwrap();
+        assert!(new_proof_verifier(false).verify(&proof, &nonce).unwrap());
+
+        let err = new_proof_verifier(true).verify(&proof, &nonce).unwrap_err();
+        assert_eq!(ErrorKind::AnoncredsProofRejected, err.kind());
+        assert!(err.to_string().contains("Sub proofs 0 and 1 don't share the value of 'master_secret'"));
+        assert_eq!(ErrorKind::AnoncredsProofRejected,
+                   new_proof_verifier(true).verify_with_cache(&proof, &nonce, &verifier::NonRevocVerificationCache::new().unwrap()).unwrap_err().kind());
+
+        // attribute without common m_tilde
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_sub_proof_request(&name_sub_proof_request, &name_schema, &non_credential_schema,
+                                            &name_signature, &name_values, &name_pub_key, None, None).unwrap();
+        proof_builder.add_sub_proof_request(&age_sub_proof_request, &age_schema, &non_credential_schema,
+                                            &own_age_signature, &own_age_values, &age_pub_key, None, None).unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, proof_builder.add_linked_sub_proofs("master_secret", &[0, 1]).unwrap_err().kind());
     }
 
-    pub fn xyz_sub_proof_request() -> SubProofRequest {
-        let mut xyz_sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
-        xyz_sub_proof_request_builder.add_revealed_attr("status").unwrap();
-        xyz_sub_proof_request_builder.add_predicate("period", "GE", 4).unwrap();
-        xyz_sub_proof_request_builder.finalize().unwrap()
+    #[test]
+    fn demo_with_equality_predicates() {
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let mut passport_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        passport_schema_builder.add_attr("name").unwrap();
+        passport_schema_builder.add_attr("passport_no").unwrap();
+        let passport_schema = passport_schema_builder.finalize().unwrap();
+        let (passport_pub_key, passport_priv_key, passport_key_correctness_proof) =
+            Issuer::new_credential_def(&passport_schema, &non_credential_schema, false).unwrap();
+
+        let mut visa_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        visa_schema_builder.add_attr("country").unwrap();
+        visa_schema_builder.add_attr("passport_number").unwrap();
+        let visa_schema = visa_schema_builder.finalize().unwrap();
+        let (visa_pub_key, visa_priv_key, visa_key_correctness_proof) =
+            Issuer::new_credential_def(&visa_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("passport_no", &encode_attribute("AB123456", AttributeKind::Text).unwrap()).unwrap();
+        let passport_values = credential_values_builder.finalize().unwrap();
+        let passport_signature = helpers::issue_credential(&passport_pub_key, &passport_priv_key, &passport_key_correctness_proof, &passport_values);
+
+        let visa_values = |passport_number: &str| {
+            let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+            credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+            credential_values_builder.add_dec_known("country", &encode_attribute("Canada", AttributeKind::Text).unwrap()).unwrap();
+            credential_values_builder.add_dec_known("passport_number", &encode_attribute(passport_number, AttributeKind::Text).unwrap()).unwrap();
+            credential_values_builder.finalize().unwrap()
+        };
+        let own_visa_values = visa_values("AB123456");
+        let own_visa_signature = helpers::issue_credential(&visa_pub_key, &visa_priv_key, &visa_key_correctness_proof, &own_visa_values);
+        // visa issued for other passport
+        let other_visa_values = visa_values("CD654321");
+        let other_visa_signature = helpers::issue_credential(&visa_pub_key, &visa_priv_key, &visa_key_correctness_proof, &other_visa_values);
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let passport_sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let visa_sub_proof_request = |equal_passport: bool| {
+            let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+            sub_proof_request_builder.add_revealed_attr("country").unwrap();
+            if equal_passport {
+                sub_proof_request_builder.add_equality_predicate("passport_number", 0, "passport_no").unwrap();
+            }
+            sub_proof_request_builder.finalize().unwrap()
+        };
+
+        let new_proof = |visa_sub_proof_request: &SubProofRequest, visa_signature: &CredentialSignature, visa_values: &CredentialValues| {
+            let mut proof_builder = Prover::new_proof_builder().unwrap();
+            proof_builder.add_common_attribute("master_secret").unwrap();
+            proof_builder.add_sub_proof_request(&passport_sub_proof_request, &passport_schema, &non_credential_schema,
+                                                &passport_signature, &passport_values, &passport_pub_key, None, None).unwrap();
+            proof_builder.add_sub_proof_request(visa_sub_proof_request, &visa_schema, &non_credential_schema,
+                                                visa_signature, visa_values, &visa_pub_key, None, None)?;
+            let nonce = new_nonce().unwrap();
+            Ok::<_, IndyCryptoError>((proof_builder.finalize(&nonce).unwrap(), nonce))
+        };
+
+        let new_proof_verifier = |equal_passport: bool| {
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&passport_sub_proof_request, &passport_schema, &non_credential_schema,
+                                                 &passport_pub_key, None, None).unwrap();
+            proof_verifier.add_sub_proof_request(&visa_sub_proof_request(equal_passport), &visa_schema, &non_credential_schema,
+                                                 &visa_pub_key, None, None).unwrap();
+            proof_verifier
+        };
+
+        // honest holder, the proof keeps the predicate through serialization
+        let (proof, nonce) = new_proof(&visa_sub_proof_request(true), &own_visa_signature, &own_visa_values).unwrap();
+        let honest_proof_json = serde_json::to_string(&proof).unwrap();
+        assert!(honest_proof_json.contains("equality_predicates"));
+        let proof: Proof = serde_json::from_str(&honest_proof_json).unwrap();
+        assert!(new_proof_verifier(true).verify(&proof, &nonce).unwrap());
+        assert!(new_proof_verifier(true).verify_constant_outcome(&proof, &nonce, None).unwrap());
+        assert!(new_proof_verifier(false).verify(&proof, &nonce).unwrap());
+
+        // prover refuses to prove equality of different values
+        let err = new_proof(&visa_sub_proof_request(true), &other_visa_signature, &other_visa_values).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("Attribute 'passport_number' of sub proof 1 isn't equal to attribute 'passport_no' of sub proof 0"));
+
+        // proof without the predicate, like proofs of earlier versions, is rejected only if the predicate is requested
+        let (proof, nonce) = new_proof(&visa_sub_proof_request(false), &other_visa_signature, &other_visa_values).unwrap();
+        let proof_json = serde_json::to_string(&proof).unwrap();
+        assert!(!proof_json.contains("equality_predicates"));
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+        assert!(new_proof_verifier(false).verify(&proof, &nonce).unwrap());
+
+        let err = new_proof_verifier(true).verify(&proof, &nonce).unwrap_err();
+        assert_eq!(ErrorKind::AnoncredsProofRejected, err.kind());
+        assert!(err.to_string().contains("Sub proof 1 doesn't prove equality of attribute 'passport_number'"));
+        assert!(!new_proof_verifier(true).verify_constant_outcome(&proof, &nonce, None).unwrap());
+
+        // the predicate added to the proof of different values
+        let mut proof_value: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+        proof_value["proofs"][1]["equality_predicates"] =
+            serde_json::from_str::<serde_json::Value>(&honest_proof_json).unwrap()["proofs"][1]["equality_predicates"].clone();
+        let proof: Proof = serde_json::from_value(proof_value).unwrap();
+
+        let err = new_proof_verifier(true).verify(&proof, &nonce).unwrap_err();
+        assert_eq!(ErrorKind::AnoncredsProofRejected, err.kind());
+        assert!(err.to_string().contains("Attribute 'passport_number' of sub proof 1 isn't equal to attribute 'passport_no' of sub proof 0"));
+        assert!(!new_proof_verifier(true).verify_constant_outcome(&proof, &nonce, None).unwrap());
     }
-}
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct RegistryDelta {
-    prev_accum: Option<PointG2>,
-    accum: PointG2,
-    #[serde(skip_serializing_if = "HashSet::is_empty")]
-    #[serde(default)]
-    issued: HashSet<u32>,
-    #[serde(skip_serializing_if = "HashSet::is_empty")]
-    #[serde(default)]
-    revoked: HashSet<u32>
-}
+    #[test]
+    fn equality_predicates_work_for_invalid_requests() {
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
 
-impl RegistryDelta {
-    fn from_rev_reg(rev_reg: &RevocationRegistry) -> RegistryDelta {
-        serde_json::from_str::<RegistryDelta>(&serde_json::to_string(&rev_reg).unwrap()).unwrap()
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) =
+            Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &Prover::new_master_secret().unwrap().value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+        let cred_signature = helpers::issue_credential(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &cred_values);
+
+        let sub_proof_request = |revealed_attr: &str, equality_predicate: Option<(&str, usize, &str)>| {
+            let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+            sub_proof_request_builder.add_revealed_attr(revealed_attr).unwrap();
+            if let Some((attr_name, other_sub_proof_idx, other_attr_name)) = equality_predicate {
+                sub_proof_request_builder.add_equality_predicate(attr_name, other_sub_proof_idx, other_attr_name).unwrap();
+            }
+            sub_proof_request_builder.finalize().unwrap()
+        };
+
+        let check = |first: &SubProofRequest, second: &SubProofRequest| {
+            let mut proof_builder = Prover::new_proof_builder().unwrap();
+            proof_builder.add_sub_proof_request(first, &credential_schema, &non_credential_schema,
+                                                &cred_signature, &cred_values, &cred_pub_key, None, None).unwrap();
+            let prover_err = proof_builder.add_sub_proof_request(second, &credential_schema, &non_credential_schema,
+                                                                 &cred_signature, &cred_values, &cred_pub_key, None, None).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, prover_err.kind());
+
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(first, &credential_schema, &non_credential_schema, &cred_pub_key, None, None).unwrap();
+            let verifier_err = proof_verifier.add_sub_proof_request(second, &credential_schema, &non_credential_schema,
+                                                                    &cred_pub_key, None, None).unwrap_err();
+            assert_eq!(ErrorKind::InvalidStructure, verifier_err.kind());
+        };
+
+        // the other sub proof is not added before
+        check(&sub_proof_request("name", None), &sub_proof_request("name", Some(("age", 1, "age"))));
+        // the other attribute is revealed
+        check(&sub_proof_request("age", None), &sub_proof_request("name", Some(("master_secret", 0, "age"))));
+        // the attribute is revealed
+        check(&sub_proof_request("name", None), &sub_proof_request("age", Some(("age", 0, "age"))));
+        // the attribute is not in the schema
+        check(&sub_proof_request("name", None), &sub_proof_request("name", Some(("height", 0, "age"))));
+
+        let mut schema_sub_proof_request_builder = SchemaSubProofRequestBuilder::new(&credential_schema).unwrap();
+        schema_sub_proof_request_builder.add_equality_predicate("age", 0, "age").unwrap();
+        assert_eq!(ErrorKind::InvalidStructure, schema_sub_proof_request_builder.add_revealed_attr("age").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidStructure, schema_sub_proof_request_builder.add_equality_predicate("height", 0, "age").unwrap_err().kind());
+        schema_sub_proof_request_builder.finalize().unwrap();
+    }
+
+    #[test]
+    fn demo_with_schema_sub_proof_request() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("sex", &encode_attribute("male", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let cred_signature = helpers::issue_credential(&cred_pub_key, &cred_priv_key, &cred_key_correctness_proof, &cred_values);
+
+        let mut sub_proof_request_builder = Verifier::new_schema_sub_proof_request_builder(&credential_schema).unwrap();
+        sub_proof_request_builder.add_revealed_attr_group("identity", &["name", "sex"]).unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            None,
+                                            None).unwrap();
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+
+        let proof = serde_json::to_value(&proof).unwrap();
+        let cred_values = serde_json::to_value(&cred_values).unwrap();
+        for attr in &sub_proof_request.revealed_attr_groups()["identity"] {
+            assert_eq!(cred_values["attrs_values"][attr]["Known"]["value"], proof["proofs"][0]["primary_proof"]["eq_proof"]["revealed_attrs"][attr]);
+        }
+    }
+
+    #[test]
+    fn demo_with_resumed_issuance() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, false).unwrap();
+        let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        // Prover blinds secrets and stores the blinding state, Issuer receives blinded secrets as json
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", &encode_attribute("Alex", AttributeKind::Text).unwrap()).unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let credential_nonce = new_nonce().unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+        let cred_values_json = cred_values.to_json().unwrap();
+        let credential_secrets_blinding_factors_json = credential_secrets_blinding_factors.to_json().unwrap();
+        let blinded_credential_secrets_json = blinded_credential_secrets.to_json().unwrap();
+        let blinded_credential_secrets_correctness_proof_json = blinded_credential_secrets_correctness_proof.to_json().unwrap();
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+        let rev_idx = 1;
+        let (cred_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                               &BlindedCredentialSecrets::from_json(&blinded_credential_secrets_json).unwrap(),
+                                               &BlindedCredentialSecretsCorrectnessProof::from_json(&blinded_credential_secrets_correctness_proof_json).unwrap(),
+                                               &credential_nonce,
+                                               &credential_issuance_nonce,
+                                               &CredentialValues::from_json(&cred_values_json).unwrap(),
+                                               &cred_pub_key,
+                                               &cred_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               false,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &simple_tail_accessor).unwrap();
+        let witness = Witness::new(rev_idx, max_cred_num, false, &rev_reg_delta.unwrap(), &simple_tail_accessor).unwrap();
+
+        let cred_signature_json = serde_json::to_string(&cred_signature).unwrap();
+        let mut in_memory_cred_signature = cred_signature;
+        Prover::process_credential_signature(&mut in_memory_cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        // Prover restarts and reloads the blinding state to process the signature
+        drop((master_secret, cred_values, blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof));
+
+        let cred_values = CredentialValues::from_json(&cred_values_json).unwrap();
+        let credential_secrets_blinding_factors = CredentialSecretsBlindingFactors::from_json(&credential_secrets_blinding_factors_json).unwrap();
+        assert_eq!(credential_secrets_blinding_factors_json, credential_secrets_blinding_factors.to_json().unwrap());
+
+        let mut cred_signature: CredentialSignature = serde_json::from_str(&cred_signature_json).unwrap();
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+        assert_eq!(serde_json::to_string(&in_memory_cred_signature).unwrap(), serde_json::to_string(&cred_signature).unwrap());
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_with_seeded_nonces() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let credential_nonce = new_nonce_from_seed(b"credential nonce").unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce_from_seed(b"credential issuance nonce").unwrap();
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("b977afe22b5b446109797ad925d9f133fc33c1914081071295d2ac1ddce3385d",
+                                                                                        &blinded_credential_secrets,
+                                                                                        &blinded_credential_secrets_correctness_proof,
+                                                                                        &credential_nonce,
+                                                                                        &cred_issuance_nonce,
+                                                                                        &cred_values,
+                                                                                        &cred_pub_key,
+                                                                                        &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        // Verifier takes the seed from outside, e.g. from HSM
+        let proof_request_nonce = new_nonce_from_seed(b"proof request nonce").unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &new_nonce_from_seed(b"proof request nonce").unwrap()).unwrap());
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(!proof_verifier.verify(&proof, &new_nonce_from_seed(b"other proof request nonce").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn demo() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let credential_nonce = new_nonce().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        credential_values_builder.add_dec_known("height", "175").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key,
+                                        &cred_key_correctness_proof,
+                                        &cred_values,
+                                        &credential_nonce).unwrap();
+
+
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+
+        let (mut cred_signature, signature_correctness_proof) = Issuer::sign_credential("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                                                        &blinded_credential_secrets,
+                                                                                        &blinded_credential_secrets_correctness_proof,
+                                                                                        &credential_nonce,
+                                                                                        &cred_issuance_nonce,
+                                                                                        &cred_values,
+                                                                                        &cred_pub_key,
+                                                                                        &cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            None,
+                                            None).unwrap();
+
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             None,
+                                             None).unwrap();
+        assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_revocation() {
+        _demo_revocation(|rev_tails_generator| SimpleTailsAccessor::new(rev_tails_generator).unwrap());
+    }
+
+
+    #[test]
+    fn demo_revocation_with_streamed_tails() {
+        _demo_revocation(|rev_tails_generator| helpers::TailsFileAccessor { bytes: helpers::write_tails_file(rev_tails_generator, 4) });
+    }
+
+    #[test]
+    fn demo_revocation_with_resize() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let max_cred_num = 4;
+        let issuance_by_default = false;
+        let (mut rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
+        let mut tails_file = helpers::write_tails_file(&mut rev_tails_generator, 5);
+        let tails_accessor = helpers::TailsFileAccessor { bytes: tails_file.clone() };
+
+        let master_secret = Prover::new_master_secret().unwrap();
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let mut issue = |rev_idx: u32, max_cred_num: u32, rev_reg: &mut RevocationRegistry, tails_accessor: &helpers::TailsFileAccessor| {
+            let credential_nonce = new_nonce().unwrap();
+            let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+                Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+            let credential_issuance_nonce = new_nonce().unwrap();
+
+            Issuer::sign_credential_with_revoc("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                               &blinded_credential_secrets,
+                                               &blinded_credential_secrets_correctness_proof,
+                                               &credential_nonce,
+                                               &credential_issuance_nonce,
+                                               &cred_values,
+                                               &cred_pub_key,
+                                               &cred_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               rev_reg,
+                                               &rev_key_priv,
+                                               tails_accessor)
+                .map(|(cred_signature, signature_correctness_proof, rev_reg_delta)|
+                    (cred_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_issuance_nonce, rev_reg_delta.unwrap()))
+        };
+
+        // Fill the registry
+        let mut deltas = Vec::new();
+        let mut credentials = Vec::new();
+        for rev_idx in 1..max_cred_num + 1 {
+            let (cred_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_issuance_nonce, rev_reg_delta) =
+                issue(rev_idx, max_cred_num, &mut rev_reg, &tails_accessor).unwrap();
+            deltas.push(rev_reg_delta);
+            credentials.push((cred_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_issuance_nonce));
+        }
+        let err = issue(max_cred_num + 1, max_cred_num, &mut rev_reg, &tails_accessor).unwrap_err();
+        assert_eq!(ErrorKind::AnoncredsRevocationAccumulatorIsFull, err.kind());
+
+        let old_rev_idx = 2;
+        let (mut old_cred_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_issuance_nonce) = credentials.remove(1);
+        let old_witness = Witness::new(old_rev_idx, max_cred_num, issuance_by_default, &helpers::merge_all(&deltas), &tails_accessor).unwrap();
+        Prover::process_credential_signature(&mut old_cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&old_witness)).unwrap();
+
+        // Resize and append generated tails to the tails
+        let new_max_cred_num = 8;
+        let (rev_reg_delta, mut rev_tails_generator) =
+            Issuer::resize_revocation_registry(&cred_pub_key, &mut rev_key_pub, &rev_key_priv, &mut rev_reg,
+                                               max_cred_num, new_max_cred_num, issuance_by_default).unwrap();
+        deltas.push(rev_reg_delta);
+        tails_file.extend(helpers::write_tails_file(&mut rev_tails_generator, 5));
+        let tails_accessor = helpers::TailsFileAccessor { bytes: tails_file };
+
+        // Witness of the credential issued before resize is created anew, then updated as usual
+        let mut old_witness = Witness::new(old_rev_idx, new_max_cred_num, issuance_by_default, &helpers::merge_all(&deltas), &tails_accessor).unwrap();
+
+        // Issue and revoke in the new range
+        let mut update_deltas = Vec::new();
+        let new_rev_idx = 6;
+        let (mut new_cred_signature, signature_correctness_proof, credential_secrets_blinding_factors, credential_issuance_nonce, rev_reg_delta) =
+            issue(new_rev_idx, new_max_cred_num, &mut rev_reg, &tails_accessor).unwrap();
+        update_deltas.push(rev_reg_delta);
+        update_deltas.push(issue(7, new_max_cred_num, &mut rev_reg, &tails_accessor).unwrap().4);
+        update_deltas.push(Issuer::revoke_credential(&mut rev_reg, new_max_cred_num, 7, &tails_accessor).unwrap());
+        update_deltas.push(Issuer::revoke_credential(&mut rev_reg, new_max_cred_num, 3, &tails_accessor).unwrap());
+
+        old_witness.update_with_deltas(old_rev_idx, new_max_cred_num, &update_deltas, &tails_accessor).unwrap();
+
+        deltas.extend(update_deltas);
+        let new_witness = Witness::new(new_rev_idx, new_max_cred_num, issuance_by_default, &helpers::merge_all(&deltas), &tails_accessor).unwrap();
+        Prover::process_credential_signature(&mut new_cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&new_witness)).unwrap();
+
+        for (cred_signature, witness) in [(&old_cred_signature, &old_witness), (&new_cred_signature, &new_witness)].iter() {
+            let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+            sub_proof_request_builder.add_revealed_attr("name").unwrap();
+            let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+            let mut proof_builder = Prover::new_proof_builder().unwrap();
+            proof_builder.add_common_attribute("master_secret").unwrap();
+            proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                &credential_schema,
+                                                &non_credential_schema,
+                                                cred_signature,
+                                                &cred_values,
+                                                &cred_pub_key,
+                                                Some(&rev_reg),
+                                                Some(witness)).unwrap();
+            let proof_request_nonce = new_nonce().unwrap();
+            let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+            let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                                 &credential_schema,
+                                                 &non_credential_schema,
+                                                 &cred_pub_key,
+                                                 Some(&rev_key_pub),
+                                                 Some(&rev_reg)).unwrap();
+            assert!(proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+        }
+    }
+
+    fn _demo_revocation<RTA, F>(tails_accessor: F) where RTA: RevocationTailsAccessor, F: FnOnce(&mut RevocationTailsGenerator) -> RTA {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let max_cred_num = 5;
+        let issuance_by_default = false;
+        let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+            Issuer::new_revocation_registry_def(&cred_pub_key, max_cred_num, issuance_by_default).unwrap();
+
+        let tail_accessor = tails_accessor(&mut rev_tails_generator);
+
+        let master_secret = Prover::new_master_secret().unwrap();
+
+        let credential_nonce = new_nonce().unwrap();
+
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        credential_values_builder.add_dec_known("height", "175").unwrap();
+        let cred_values = credential_values_builder.finalize().unwrap();
+
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(&cred_pub_key,
+                                        &cred_key_correctness_proof,
+                                        &cred_values,
+                                        &credential_nonce).unwrap();
+
+
+
+        let credential_issuance_nonce = new_nonce().unwrap();
+
+        let rev_idx = 1;
+        let (mut cred_signature, signature_correctness_proof, rev_reg_delta) =
+            Issuer::sign_credential_with_revoc("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                               &blinded_credential_secrets,
+                                               &blinded_credential_secrets_correctness_proof,
+                                               &credential_nonce,
+                                               &credential_issuance_nonce,
+                                               &cred_values,
+                                               &cred_pub_key,
+                                               &cred_priv_key,
+                                               rev_idx,
+                                               max_cred_num,
+                                               issuance_by_default,
+                                               &mut rev_reg,
+                                               &rev_key_priv,
+                                               &tail_accessor).unwrap();
+
+        let witness = Witness::new(rev_idx, max_cred_num, issuance_by_default, &rev_reg_delta.unwrap(), &tail_accessor).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             &cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             &cred_pub_key,
+                                             &credential_issuance_nonce,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg),
+                                             Some(&witness)).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+        let mut proof_builder = Prover::new_proof_builder().unwrap();
+        proof_builder.add_common_attribute("master_secret").unwrap();
+        proof_builder.add_sub_proof_request(&sub_proof_request,
+                                            &credential_schema,
+                                            &non_credential_schema,
+                                            &cred_signature,
+                                            &cred_values,
+                                            &cred_pub_key,
+                                            Some(&rev_reg),
+                                            Some(&witness)).unwrap();
+        let proof_request_nonce = new_nonce().unwrap();
+        let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+        let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             &non_credential_schema,
+                                             &cred_pub_key,
+                                             Some(&rev_key_pub),
+                                             Some(&rev_reg)).unwrap();
+        assert_eq!(true, proof_verifier.verify(&proof, &proof_request_nonce).unwrap());
+    }
+
+    #[test]
+    fn demo_revocation_for_issuance_types() {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = NonCredentialSchemaBuilder::new().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (cred_pub_key, cred_priv_key, cred_key_correctness_proof) = Issuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let mut sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+        for &(issuance_type, name) in &[(IssuanceType::IssuanceByDefault, "ISSUANCE_BY_DEFAULT"), (IssuanceType::IssuanceOnDemand, "ISSUANCE_ON_DEMAND")] {
+            let max_cred_num = 5;
+            let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+                Issuer::new_revocation_registry_def_with_issuance_type(&cred_pub_key, max_cred_num, issuance_type).unwrap();
+            let simple_tail_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+            assert_eq!(!issuance_type.is_by_default(), RegistryDelta::from_rev_reg(&rev_reg).accum.is_inf().unwrap());
+
+            // Prover and verifier take type of issuance from published registry definition
+            let rev_key_pub_json = serde_json::to_string(&rev_key_pub).unwrap();
+            assert!(rev_key_pub_json.contains(name));
+            let rev_key_pub: RevocationKeyPublic = serde_json::from_str(&rev_key_pub_json).unwrap();
+            assert_eq!(Some(issuance_type), rev_key_pub.issuance_type());
+
+            let master_secret = Prover::new_master_secret().unwrap();
+            let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+            credential_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+            credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+            credential_values_builder.add_dec_known("age", "28").unwrap();
+            let cred_values = credential_values_builder.finalize().unwrap();
+
+            let credential_nonce = new_nonce().unwrap();
+            let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+                Prover::blind_credential_secrets(&cred_pub_key, &cred_key_correctness_proof, &cred_values, &credential_nonce).unwrap();
+
+            let initial_rev_reg = rev_reg.clone();
+            let credential_issuance_nonce = new_nonce().unwrap();
+            let rev_idx = 2;
+            let (mut cred_signature, signature_correctness_proof, issuance_delta) =
+                Issuer::sign_credential_with_revoc_for_registry("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+                                                                &blinded_credential_secrets,
+                                                                &blinded_credential_secrets_correctness_proof,
+                                                                &credential_nonce,
+                                                                &credential_issuance_nonce,
+                                                                &cred_values,
+                                                                &cred_pub_key,
+                                                                &cred_priv_key,
+                                                                rev_idx,
+                                                                max_cred_num,
+                                                                &rev_key_pub,
+                                                                &mut rev_reg,
+                                                                &rev_key_priv,
+                                                                &simple_tail_accessor).unwrap();
+
+            // Issuance by default doesn't change registry, prover builds witness over the whole registry
+            let rev_reg_delta = match issuance_delta {
+                Some(issuance_delta) => {
+                    assert!(!issuance_type.is_by_default());
+                    assert_eq!(HashSet::from([rev_idx]), RegistryDelta::from_delta(&issuance_delta).issued);
+                    assert!(RegistryDelta::from_delta(&issuance_delta).revoked.is_empty());
+                    issuance_delta
+                }
+                None => {
+                    assert!(issuance_type.is_by_default());
+                    assert_eq!(RegistryDelta::from_rev_reg(&initial_rev_reg).accum, RegistryDelta::from_rev_reg(&rev_reg).accum);
+                    RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &HashSet::new())
+                }
+            };
+
+            let mut witness = Witness::new_for_registry(rev_idx, max_cred_num, &rev_key_pub, &rev_reg_delta, &simple_tail_accessor).unwrap();
+
+            Prover::process_credential_signature(&mut cred_signature,
+                                                 &cred_values,
+                                                 &signature_correctness_proof,
+                                                 &credential_secrets_blinding_factors,
+                                                 &cred_pub_key,
+                                                 &credential_issuance_nonce,
+                                                 Some(&rev_key_pub),
+                                                 Some(&rev_reg),
+                                                 Some(&witness)).unwrap();
+
+            let prove = |rev_reg: &RevocationRegistry, witness: &Witness| -> bool {
+                let mut proof_builder = Prover::new_proof_builder().unwrap();
+                proof_builder.add_common_attribute("master_secret").unwrap();
+                proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                    &credential_schema,
+                                                    &non_credential_schema,
+                                                    &cred_signature,
+                                                    &cred_values,
+                                                    &cred_pub_key,
+                                                    Some(rev_reg),
+                                                    Some(witness)).unwrap();
+                let proof_request_nonce = new_nonce().unwrap();
+                let proof = proof_builder.finalize(&proof_request_nonce).unwrap();
+
+                let mut proof_verifier = Verifier::new_proof_verifier().unwrap();
+                proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                                     &credential_schema,
+                                                     &non_credential_schema,
+                                                     &cred_pub_key,
+                                                     Some(&rev_key_pub),
+                                                     Some(rev_reg)).unwrap();
+                proof_verifier.verify(&proof, &proof_request_nonce).unwrap()
+            };
+            assert!(prove(&rev_reg, &witness));
+
+            let revocation_delta = Issuer::revoke_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap();
+            assert_eq!(HashSet::from([rev_idx]), RegistryDelta::from_delta(&revocation_delta).revoked);
+            assert!(RegistryDelta::from_delta(&revocation_delta).issued.is_empty());
+            witness.update(rev_idx, max_cred_num, &revocation_delta, &simple_tail_accessor).unwrap();
+            assert!(!prove(&rev_reg, &witness));
+
+            let recovery_delta = Issuer::recovery_credential(&mut rev_reg, max_cred_num, rev_idx, &simple_tail_accessor).unwrap();
+            assert_eq!(HashSet::from([rev_idx]), RegistryDelta::from_delta(&recovery_delta).issued);
+            assert!(RegistryDelta::from_delta(&recovery_delta).revoked.is_empty());
+            witness.update(rev_idx, max_cred_num, &recovery_delta, &simple_tail_accessor).unwrap();
+            assert!(prove(&rev_reg, &witness));
+        }
+    }
+}
+
+mod helpers {
+    use super::*;
+    use crate::indy_crypto::cl::*;
+    use crate::indy_crypto::errors::IndyCryptoError;
+
+    // Issues credential without revocation, as Issuer and Prover do it
+    pub fn issue_credential(cred_pub_key: &CredentialPublicKey,
+                            cred_priv_key: &CredentialPrivateKey,
+                            cred_key_correctness_proof: &CredentialKeyCorrectnessProof,
+                            cred_values: &CredentialValues) -> CredentialSignature {
+        let credential_nonce = new_nonce().unwrap();
+        let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+            Prover::blind_credential_secrets(cred_pub_key, cred_key_correctness_proof, cred_values, &credential_nonce).unwrap();
+
+        let cred_issuance_nonce = new_nonce().unwrap();
+        let (mut cred_signature, signature_correctness_proof) =
+            Issuer::sign_credential(PROVER_ID,
+                                    &blinded_credential_secrets,
+                                    &blinded_credential_secrets_correctness_proof,
+                                    &credential_nonce,
+                                    &cred_issuance_nonce,
+                                    cred_values,
+                                    cred_pub_key,
+                                    cred_priv_key).unwrap();
+
+        Prover::process_credential_signature(&mut cred_signature,
+                                             cred_values,
+                                             &signature_correctness_proof,
+                                             &credential_secrets_blinding_factors,
+                                             cred_pub_key,
+                                             &cred_issuance_nonce,
+                                             None,
+                                             None,
+                                             None).unwrap();
+        cred_signature
+    }
+
+    pub fn merge_all(deltas: &[RevocationRegistryDelta]) -> RevocationRegistryDelta {
+        deltas[1..].iter().fold(deltas[0].clone(), |merged, delta| merged.merged(delta).unwrap())
+    }
+
+    pub fn write_tails_file(rev_tails_generator: &mut RevocationTailsGenerator, chunk_size: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for chunk in rev_tails_generator.chunks(chunk_size) {
+            for tail in chunk.unwrap() {
+                bytes.extend(tail.to_bytes().unwrap());
+            }
+        }
+        bytes
+    }
+
+    // Tails accessor over tails file of tails written as bytes one after another
+    pub struct TailsFileAccessor {
+        pub bytes: Vec<u8>
+    }
+
+    impl RevocationTailsAccessor for TailsFileAccessor {
+        fn access_tail(&self, tail_id: u32, accessor: &mut dyn FnMut(&Tail)) -> Result<(), IndyCryptoError> {
+            let start = tail_id as usize * Tail::BYTES_REPR_SIZE;
+            accessor(&Tail::from_bytes(&self.bytes[start..start + Tail::BYTES_REPR_SIZE])?);
+            Ok(())
+        }
+    }
+
+    pub fn gvt_credential_schema() -> CredentialSchema {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        credential_schema_builder.add_attr("sex").unwrap();
+        credential_schema_builder.add_attr("age").unwrap();
+        credential_schema_builder.add_attr("height").unwrap();
+        credential_schema_builder.finalize().unwrap()
+    }
+
+    pub fn xyz_credential_schema() -> CredentialSchema {
+        let mut credential_schema_builder = Issuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("status").unwrap();
+        credential_schema_builder.add_attr("period").unwrap();
+        credential_schema_builder.finalize().unwrap()
+    }
+
+    pub fn non_credential_schema() -> NonCredentialSchema {
+        let mut non_credential_schema_builder = Issuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        non_credential_schema_builder.finalize().unwrap()
+    }
+
+    pub fn gvt_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("name", "1139481716457488690172217916278103335").unwrap();
+        credential_values_builder.add_dec_known("sex", "5944657099558967239210949258394887428692050081607692519917050011144233115103").unwrap();
+        credential_values_builder.add_dec_known("age", "28").unwrap();
+        credential_values_builder.add_dec_known("height", "175").unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
+    pub fn xyz_credential_values(master_secret: &MasterSecret) -> CredentialValues {
+        let mut credential_values_builder = Issuer::new_credential_values_builder().unwrap();
+        credential_values_builder.add_value_known("master_secret", &master_secret.value().unwrap()).unwrap();
+        credential_values_builder.add_dec_known("status", "51792877103171595686471452153480627530895").unwrap();
+        credential_values_builder.add_dec_known("period", "8").unwrap();
+        credential_values_builder.finalize().unwrap()
+    }
+
+    pub fn gvt_sub_proof_request() -> SubProofRequest {
+        let mut gvt_sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        gvt_sub_proof_request_builder.add_revealed_attr("name").unwrap();
+        gvt_sub_proof_request_builder.add_predicate("age", "GE", 18).unwrap();
+        gvt_sub_proof_request_builder.finalize().unwrap()
+    }
+
+    pub fn xyz_sub_proof_request() -> SubProofRequest {
+        let mut xyz_sub_proof_request_builder = Verifier::new_sub_proof_request_builder().unwrap();
+        xyz_sub_proof_request_builder.add_revealed_attr("status").unwrap();
+        xyz_sub_proof_request_builder.add_predicate("period", "GE", 4).unwrap();
+        xyz_sub_proof_request_builder.finalize().unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct RegistryDelta {
+    prev_accum: Option<PointG2>,
+    accum: PointG2,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    #[serde(default)]
+    issued: HashSet<u32>,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    #[serde(default)]
+    revoked: HashSet<u32>
+}
+
+impl RegistryDelta {
+    fn from_rev_reg(rev_reg: &RevocationRegistry) -> RegistryDelta {
+        serde_json::from_str::<RegistryDelta>(&serde_json::to_string(&rev_reg).unwrap()).unwrap()
+    }
+
+    fn from_delta(rev_reg_delta: &RevocationRegistryDelta) -> RegistryDelta {
+        serde_json::from_str::<RegistryDelta>(&serde_json::to_string(&rev_reg_delta).unwrap()).unwrap()
     }
 
     fn to_delta(&self) -> RevocationRegistryDelta {
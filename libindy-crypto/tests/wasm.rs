@@ -0,0 +1,1223 @@
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+extern crate indy_crypto;
+extern crate js_sys;
+extern crate serde_json;
+extern crate wasm_bindgen;
+extern crate wasm_bindgen_test;
+
+use crate::indy_crypto::bls;
+use crate::indy_crypto::errors::ErrorCode;
+use crate::indy_crypto::utils::{base64, hex};
+use crate::indy_crypto::wasm::bls::*;
+use crate::indy_crypto::wasm::bls_classes::*;
+use crate::indy_crypto::wasm::bls_json::*;
+use crate::indy_crypto::wasm::pair::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+// Counts allocations of the test module for the allocation benchmarks
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_of<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+// Checks that js objects have the shape of the serde_json representation in both directions
+macro_rules! assert_shape_round_trip {
+    ($js:expr, $type:ty, $as_bytes:ident, $fields:expr) => {{
+        let js: JsValue = $js;
+        let keys: Vec<String> = js_sys::Object::keys(&js.clone().into()).iter().map(|key| key.as_string().unwrap()).collect();
+        assert_eq!($fields.iter().map(|field: &&str| field.to_string()).collect::<Vec<_>>(), keys);
+
+        let json = String::from(js_sys::JSON::stringify(&js).unwrap());
+        let rust: $type = serde_json::from_str(&json).unwrap();
+        assert_eq!($as_bytes(&js).unwrap().to_vec(), rust.as_bytes().to_vec());
+
+        let parsed = js_sys::JSON::parse(&serde_json::to_string(&rust).unwrap()).unwrap();
+        assert_eq!(rust.as_bytes().to_vec(), $as_bytes(&parsed).unwrap().to_vec());
+    }};
+}
+
+#[wasm_bindgen_test]
+fn objects_keep_json_shape() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+    let signature = blsSign(&[1, 2, 3], &sign_key).unwrap();
+
+    assert_shape_round_trip!(gen.clone(), bls::Generator, blsGeneratorAsBytes, ["point", "bytes"]);
+    assert_shape_round_trip!(sign_key.clone(), bls::SignKey, blsSignKeyAsBytes, ["group_order_element", "bytes"]);
+    assert_shape_round_trip!(ver_key.clone(), bls::VerKey, blsVerKeyAsBytes, ["point", "bytes"]);
+    assert_shape_round_trip!(blsProofOfPossession(&ver_key, &sign_key).unwrap(), bls::ProofOfPossession, blsProofOfPossessionAsBytes, ["point", "bytes"]);
+    assert_shape_round_trip!(signature.clone(), bls::Signature, blsSignatureAsBytes, ["point", "bytes"]);
+    assert_shape_round_trip!(blsMultiSignature(vec![signature]).unwrap(), bls::MultiSignature, blsMultiSignatureAsBytes, ["point", "bytes"]);
+    assert_shape_round_trip!(blsCreateAggregatedVerKey(vec![ver_key]).unwrap(), bls::AggregatedVerKey, blsAggregatedVerKeyAsBytes, ["point", "bytes"]);
+
+    assert!(get(&gen, "point").as_string().unwrap().starts_with("false "));
+    assert!(js_sys::Array::is_array(&get(&gen, "bytes")));
+}
+
+#[wasm_bindgen_test]
+fn ver_key_from_bytes_returns_error_details_for_garbage() {
+    let err = blsVerKeyFromBytes(&[1; 128]).unwrap_err();
+
+    let error = err.dyn_ref::<js_sys::Error>().unwrap();
+    assert_eq!(Some(ErrorCode::CryptoInvalidKey as usize as f64), get(&err, "code").as_f64());
+    assert_eq!(Some("CryptoInvalidKey".to_string()), get(&err, "name").as_string());
+    assert_eq!(String::from(error.message()), get(&err, "message").as_string().unwrap());
+    assert!(String::from(error.message()).contains("Invalid verification key"));
+}
+
+#[wasm_bindgen_test]
+fn ver_key_from_bytes_returns_error_details_for_wrong_length() {
+    let err = blsVerKeyFromBytes(&[1, 2, 3]).unwrap_err();
+
+    assert!(err.is_instance_of::<js_sys::Error>());
+    assert_eq!(Some(ErrorCode::CryptoInvalidKey as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn verify_multi_sig_with_aggregated_ver_key_works_for_cached_key() {
+    let gen = blsGenerator().unwrap();
+    let sign_key1 = blsSignKey(None).unwrap();
+    let sign_key2 = blsSignKey(None).unwrap();
+    let ver_key1 = blsVerKey(&gen, &sign_key1).unwrap();
+    let ver_key2 = blsVerKey(&gen, &sign_key2).unwrap();
+
+    let agg_ver_key = blsCreateAggregatedVerKey(vec![ver_key1, ver_key2]).unwrap();
+    let agg_ver_key = blsAggregatedVerKeyFromBytes(&blsAggregatedVerKeyAsBytes(&agg_ver_key).unwrap()).unwrap();
+
+    for message in &[vec![1u8, 2, 3], vec![4u8, 5, 6]] {
+        let signature1 = blsSign(message, &sign_key1).unwrap();
+        let signature2 = blsSign(message, &sign_key2).unwrap();
+        let multi_sig = blsMultiSignature(vec![signature1, signature2]).unwrap();
+
+        assert!(blsVerifyMultiSigWithAggregatedVerKey(&multi_sig, Some(message.to_vec()), &agg_ver_key, &gen).unwrap());
+    }
+
+    let signature1 = blsSign(&[7, 8, 9], &sign_key1).unwrap();
+    let multi_sig = blsMultiSignature(vec![signature1]).unwrap();
+    assert!(!blsVerifyMultiSigWithAggregatedVerKey(&multi_sig, Some(vec![7, 8, 9]), &agg_ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn create_aggregated_ver_key_returns_error_for_invalid_entry() {
+    let gen = blsGenerator().unwrap();
+    let ver_key = blsVerKey(&gen, &blsSignKey(None).unwrap()).unwrap();
+
+    let err = blsCreateAggregatedVerKey(vec![ver_key, JsValue::from_str("garbage")]).unwrap_err();
+
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("index 1"));
+}
+
+#[wasm_bindgen_test]
+fn verify_returns_error_message_for_malformed_signature() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+
+    let malformed = js_sys::JSON::parse(r#"{"point": 42}"#).unwrap();
+    let err = blsVerify(&malformed, Some(vec![1, 2, 3]), &ver_key, &gen).unwrap_err();
+
+    assert!(err.is_instance_of::<js_sys::Error>());
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    let message = get(&err, "message").as_string().unwrap();
+    assert!(message.starts_with("Invalid structure: Invalid argument `signature`: "));
+}
+
+#[wasm_bindgen_test]
+fn verify_multi_sig_returns_error_for_malformed_ver_key() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let multi_sig = blsMultiSignature(vec![blsSign(&[1, 2, 3], &sign_key).unwrap()]).unwrap();
+
+    let err = blsVerifyMultiSig(&multi_sig, Some(vec![1, 2, 3]), vec![JsValue::NULL], &gen).unwrap_err();
+
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKeys` at index 0"));
+}
+
+#[wasm_bindgen_test]
+fn multi_signature_returns_error_for_malformed_signature() {
+    let sign_key = blsSignKey(None).unwrap();
+    let missing_fields = js_sys::JSON::parse(r#"{"bytes": [1, 2, 3]}"#).unwrap();
+
+    for malformed in [JsValue::NULL, JsValue::from_f64(1.0), missing_fields] {
+        let signature1 = blsSign(&[1, 2, 3], &sign_key).unwrap();
+        let signature2 = blsSign(&[1, 2, 3], &sign_key).unwrap();
+
+        let err = blsMultiSignature(vec![signature1, signature2, malformed]).unwrap_err();
+
+        assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+        assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument at index 2"));
+    }
+}
+
+#[wasm_bindgen_test]
+fn verify_multi_sig_works() {
+    let gen = blsGenerator().unwrap();
+    let sign_key1 = blsSignKey(None).unwrap();
+    let sign_key2 = blsSignKey(None).unwrap();
+    let ver_key1 = blsVerKey(&gen, &sign_key1).unwrap();
+    let ver_key2 = blsVerKey(&gen, &sign_key2).unwrap();
+
+    let multi_sig = blsMultiSignature(vec![blsSign(&[1, 2, 3], &sign_key1).unwrap(), blsSign(&[1, 2, 3], &sign_key2).unwrap()]).unwrap();
+
+    assert!(blsVerifyMultiSig(&multi_sig, Some(vec![1, 2, 3]), vec![ver_key1.clone(), ver_key2.clone()], &gen).unwrap());
+    assert!(!blsVerifyMultiSig(&multi_sig, Some(vec![4, 5, 6]), vec![ver_key1, ver_key2], &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_multi_sig_returns_error_for_empty_ver_keys() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let multi_sig = blsMultiSignature(vec![blsSign(&[1, 2, 3], &sign_key).unwrap()]).unwrap();
+
+    let err = blsVerifyMultiSig(&multi_sig, Some(vec![1, 2, 3]), vec![], &gen).unwrap_err();
+
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKeys`: array is empty"));
+}
+
+#[wasm_bindgen_test]
+fn verify_multi_sig_returns_error_for_corrupt_ver_key() {
+    let gen = blsGenerator().unwrap();
+    let sign_key1 = blsSignKey(None).unwrap();
+    let sign_key2 = blsSignKey(None).unwrap();
+    let ver_key1 = blsVerKey(&gen, &sign_key1).unwrap();
+    let multi_sig = blsMultiSignature(vec![blsSign(&[1, 2, 3], &sign_key1).unwrap(), blsSign(&[1, 2, 3], &sign_key2).unwrap()]).unwrap();
+
+    let corrupt = js_sys::JSON::parse(r#"{"point": "garbage", "bytes": [1, 2, 3]}"#).unwrap();
+    let err = blsVerifyMultiSig(&multi_sig, Some(vec![1, 2, 3]), vec![ver_key1, corrupt], &gen).unwrap_err();
+
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKeys` at index 1"));
+}
+
+#[wasm_bindgen_test]
+fn verify_works_for_empty_message() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let signature = blsSign(&[], &get(&key_pair, "signKey")).unwrap();
+
+    assert!(blsVerify(&signature, Some(vec![]), &get(&key_pair, "verKey"), &gen).unwrap());
+    assert!(!blsVerify(&signature, Some(vec![0]), &get(&key_pair, "verKey"), &gen).unwrap());
+
+    let multi_sig = blsMultiSignature(vec![signature]).unwrap();
+    assert!(blsVerifyMultiSig(&multi_sig, Some(vec![]), vec![get(&key_pair, "verKey")], &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_returns_error_for_absent_message() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let ver_key = get(&key_pair, "verKey");
+    let signature = blsSign(&[1, 2, 3], &get(&key_pair, "signKey")).unwrap();
+    let multi_sig = blsMultiSignature(vec![signature.clone()]).unwrap();
+    let agg_ver_key = blsCreateAggregatedVerKey(vec![ver_key.clone()]).unwrap();
+
+    for err in [
+        blsVerify(&signature, None, &ver_key, &gen).unwrap_err(),
+        blsVerifyMultiSig(&multi_sig, None, vec![ver_key.clone()], &gen).unwrap_err(),
+        blsVerifyMultiSigWithAggregatedVerKey(&multi_sig, None, &agg_ver_key, &gen).unwrap_err(),
+    ] {
+        assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+        assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `message`: value is missing"));
+    }
+}
+
+#[wasm_bindgen_test]
+fn verify_returns_error_naming_the_invalid_argument() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let ver_key = get(&key_pair, "verKey");
+    let signature = blsSign(&[1, 2, 3], &get(&key_pair, "signKey")).unwrap();
+
+    let err = blsVerify(&signature, Some(vec![1, 2, 3]), &JsValue::UNDEFINED, &gen).unwrap_err();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKey`: value is missing"));
+
+    let err = blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key, &js_sys::JSON::parse(r#"{"bytes": []}"#).unwrap()).unwrap_err();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `generator`: "));
+
+    // Truncated bytes are rejected by the length check before any pairing
+    let truncated = js_sys::Array::from(&get(&ver_key, "bytes")).slice(0, 10);
+    let ver_key_with_wrong_bytes = object(&[("point", get(&ver_key, "point")), ("bytes", truncated.into())]);
+    let err = blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key_with_wrong_bytes, &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKey`: invalid len of bytes"));
+
+    let item = object(&[("signature", signature), ("message", JsValue::from(js_sys::Uint8Array::new_with_length(0))), ("verKey", ver_key_with_wrong_bytes)]);
+    let err = blsVerifyBatch(vec![item], &gen).unwrap_err();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `items` at index 0: verKey: invalid len of bytes"));
+}
+
+#[wasm_bindgen_test]
+fn verify_returns_error_for_bytes_of_invalid_or_other_point() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let ver_key = get(&key_pair, "verKey");
+    let signature = blsSign(&[1, 2, 3], &get(&key_pair, "signKey")).unwrap();
+
+    // Bytes of the right length, but not a point on the curve
+    let garbage = js_sys::Array::from(&get(&ver_key, "bytes")).map(&mut |_, _, _| JsValue::from(0xFF));
+    let ver_key_with_garbage = object(&[("point", get(&ver_key, "point")), ("bytes", garbage.into())]);
+    let err = blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key_with_garbage, &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKey`: invalid bytes"));
+
+    // Valid bytes of another key, the pairing would use `point`
+    let other_ver_key = get(&blsKeyPair(&gen, None).unwrap(), "verKey");
+    let ver_key_with_other_bytes = object(&[("point", get(&ver_key, "point")), ("bytes", get(&other_ver_key, "bytes"))]);
+    let err = blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key_with_other_bytes, &gen).unwrap_err();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `verKey`: point doesn't match bytes"));
+
+    let item = object(&[("signature", signature), ("message", JsValue::from(js_sys::Uint8Array::new_with_length(0))), ("verKey", ver_key_with_other_bytes)]);
+    let err = blsVerifyBatch(vec![item], &gen).unwrap_err();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `items` at index 0: verKey: point doesn't match bytes"));
+}
+
+#[wasm_bindgen_test]
+fn sign_bytes_and_verify_bytes_work() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+
+    let gen_bytes = blsGeneratorAsBytes(&gen).unwrap();
+    let sign_key_bytes = blsSignKeyAsBytes(&sign_key).unwrap().to_vec();
+    let ver_key_bytes = blsVerKeyAsBytes(&ver_key).unwrap();
+
+    let signature_bytes = blsSignBytes(&[1, 2, 3], sign_key_bytes).unwrap();
+    assert_eq!(blsSignatureAsBytes(&blsSign(&[1, 2, 3], &sign_key).unwrap()).unwrap(), signature_bytes);
+
+    assert!(blsVerifyBytes(&signature_bytes, &[1, 2, 3], &ver_key_bytes, &gen_bytes).unwrap());
+    assert!(!blsVerifyBytes(&signature_bytes, &[4, 5, 6], &ver_key_bytes, &gen_bytes).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_bytes_returns_error_for_garbage_ver_key() {
+    let gen_bytes = blsGeneratorAsBytes(&blsGenerator().unwrap()).unwrap();
+    let sign_key_bytes = blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec();
+    let signature_bytes = blsSignBytes(&[1, 2, 3], sign_key_bytes).unwrap();
+
+    let err = blsVerifyBytes(&signature_bytes, &[1, 2, 3], &[1, 2, 3], &gen_bytes).unwrap_err();
+
+    assert_eq!(Some(ErrorCode::CryptoInvalidKey as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn allocations_of_raw_api_are_fewer() {
+    let gen = blsGenerator().unwrap();
+    let gen_bytes = blsGeneratorAsBytes(&gen).unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let sign_key_bytes = blsSignKeyAsBytes(&sign_key).unwrap().to_vec();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+    let ver_key_bytes = blsVerKeyAsBytes(&ver_key).unwrap();
+    let message = vec![7u8; 64 * 1024];
+
+    let mut signature = JsValue::NULL;
+    let object_sign = allocations_of(|| signature = blsSign(&message, &sign_key).unwrap());
+    let mut signature_bytes = Vec::new();
+    let raw_sign = allocations_of(|| signature_bytes = blsSignRaw(&message, sign_key_bytes).unwrap());
+    assert!(raw_sign < object_sign, "raw sign {} allocations, object sign {}", raw_sign, object_sign);
+
+    // Owned arguments are copied before measuring, as wasm-bindgen copies them before the call
+    let message_arg = Some(message.clone());
+    let object_verify = allocations_of(|| assert!(blsVerify(&signature, message_arg, &ver_key, &gen).unwrap()));
+    let raw_verify = allocations_of(|| assert!(blsVerifyRaw(&signature_bytes, &message, &ver_key_bytes, &gen_bytes).unwrap()));
+    assert!(raw_verify < object_verify, "raw verify {} allocations, object verify {}", raw_verify, object_verify);
+}
+
+#[wasm_bindgen_test]
+fn raw_api_agrees_with_object_api() {
+    let gen = blsGenerator().unwrap();
+    let gen_bytes = blsGeneratorAsBytes(&gen).unwrap();
+    let sign_keys = [blsSignKey(None).unwrap(), blsSignKey(None).unwrap()];
+    let ver_keys: Vec<JsValue> = sign_keys.iter().map(|sign_key| blsVerKey(&gen, sign_key).unwrap()).collect();
+
+    let signatures: Vec<JsValue> = sign_keys.iter().map(|sign_key| blsSign(&[1, 2, 3], sign_key).unwrap()).collect();
+    let raw_signatures: Vec<Vec<u8>> = sign_keys.iter()
+        .map(|sign_key| blsSignRaw(&[1, 2, 3], blsSignKeyAsBytes(sign_key).unwrap().to_vec()).unwrap())
+        .collect();
+    for (signature, raw_signature) in signatures.iter().zip(raw_signatures.iter()) {
+        assert_eq!(blsSignatureAsBytes(signature).unwrap(), *raw_signature);
+    }
+
+    for (signature, ver_key) in signatures.iter().zip(ver_keys.iter()) {
+        let ver_key_bytes = blsVerKeyAsBytes(ver_key).unwrap();
+        let signature_bytes = blsSignatureAsBytes(signature).unwrap();
+        for message in [vec![1, 2, 3], vec![4, 5, 6]] {
+            assert_eq!(blsVerify(signature, Some(message.clone()), ver_key, &gen).unwrap(),
+                       blsVerifyRaw(&signature_bytes, &message, &ver_key_bytes, &gen_bytes).unwrap());
+        }
+    }
+
+    let multi_sig = blsMultiSignature(signatures).unwrap();
+    let raw_multi_sig = blsMultiSignatureRaw(raw_signatures.iter().map(|bytes| js_sys::Uint8Array::from(&bytes[..])).collect()).unwrap();
+    assert_eq!(blsMultiSignatureAsBytes(&multi_sig).unwrap(), raw_multi_sig);
+    assert!(blsVerifyMultiSig(&blsMultiSignatureFromBytes(&raw_multi_sig).unwrap(), Some(vec![1, 2, 3]), ver_keys, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn multi_signature_raw_returns_error_for_invalid_signatures() {
+    let signature_bytes = blsSignRaw(&[1, 2, 3], blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec()).unwrap();
+
+    let err = blsMultiSignatureRaw(vec![]).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `signatureBytesArray`: array is empty"));
+
+    let err = blsMultiSignatureRaw(vec![js_sys::Uint8Array::from(&signature_bytes[..]), js_sys::Uint8Array::from(&[1u8, 2, 3][..])]).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `signatureBytesArray` at index 1: "));
+}
+
+#[wasm_bindgen_test]
+fn key_pair_works() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let sign_key = get(&key_pair, "signKey");
+    let ver_key = get(&key_pair, "verKey");
+    let pop = get(&key_pair, "proofOfPossession");
+
+    assert!(blsVerifyProofOfPossession(&pop, &ver_key, &gen).unwrap());
+
+    let signature = blsSign(&[1, 2, 3], &sign_key).unwrap();
+    assert!(blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn key_pair_works_for_seed() {
+    let gen = blsGenerator().unwrap();
+    let seed = vec![1u8; 32];
+    let key_pair = blsKeyPair(&gen, Some(seed.clone())).unwrap();
+
+    let sign_key = blsSignKey(Some(seed)).unwrap();
+    assert_eq!(blsSignKeyAsBytes(&get(&key_pair, "signKey")).unwrap().to_vec(), blsSignKeyAsBytes(&sign_key).unwrap().to_vec());
+    assert!(blsVerifyProofOfPossession(&get(&key_pair, "proofOfPossession"), &blsVerKey(&gen, &sign_key).unwrap(), &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn key_pair_returns_error_for_invalid_generator() {
+    let err = blsKeyPair(&JsValue::from_str("garbage"), None).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn sign_key_works_for_seeds_of_any_length() {
+    let seeds = [vec![1u8; 16], vec![1u8; 32], vec![1u8; 64]];
+    let keys: Vec<Vec<u8>> = seeds
+        .iter()
+        .map(|seed| blsSignKeyAsBytes(&blsSignKey(Some(seed.clone())).unwrap()).unwrap().to_vec())
+        .collect();
+
+    for (seed, key) in seeds.iter().zip(keys.iter()) {
+        assert_eq!(key, &blsSignKeyAsBytes(&blsSignKey(Some(seed.clone())).unwrap()).unwrap().to_vec());
+        assert_eq!(key, &BlsSignKey::new(Some(seed.clone())).unwrap().as_bytes().unwrap().to_vec());
+    }
+    assert_ne!(keys[0], keys[1]);
+    assert_ne!(keys[1], keys[2]);
+    assert_ne!(keys[0], keys[2]);
+}
+
+#[wasm_bindgen_test]
+fn sign_key_returns_error_for_empty_seed() {
+    let err = blsSignKey(Some(vec![])).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn sign_key_from_hex_seed_works() {
+    let from_hex = blsSignKeyFromHexSeed(&"0aFF".repeat(8)).unwrap();
+    let from_bytes = blsSignKey(Some([0x0a, 0xff].repeat(8))).unwrap();
+    assert_eq!(blsSignKeyAsBytes(&from_bytes).unwrap().to_vec(), blsSignKeyAsBytes(&from_hex).unwrap().to_vec());
+}
+
+#[wasm_bindgen_test]
+fn sign_key_from_hex_seed_returns_error_for_invalid_hex() {
+    for seed in ["", "abc", "zz00", "0é"] {
+        let err = blsSignKeyFromHexSeed(seed).unwrap_err();
+        assert!(err.is_instance_of::<js_sys::Error>());
+        assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    }
+}
+
+macro_rules! assert_hex_round_trip {
+    ($value:expr, $to_hex:ident, $from_hex:ident, $as_bytes:ident) => {{
+        let value = $value;
+        let hex = $to_hex(&value).unwrap();
+        assert_eq!(hex, hex.to_lowercase());
+        assert_eq!($as_bytes(&value).unwrap().to_vec(), $as_bytes(&$from_hex(&hex).unwrap()).unwrap().to_vec());
+        assert_eq!($as_bytes(&value).unwrap().to_vec(), $as_bytes(&$from_hex(&hex.to_uppercase()).unwrap()).unwrap().to_vec());
+    }};
+}
+
+#[wasm_bindgen_test]
+fn hex_conversions_work() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+    let signature = blsSign(&[1, 2, 3], &sign_key).unwrap();
+
+    assert_hex_round_trip!(gen.clone(), blsGeneratorToHex, blsGeneratorFromHex, blsGeneratorAsBytes);
+    assert_hex_round_trip!(ver_key, blsVerKeyToHex, blsVerKeyFromHex, blsVerKeyAsBytes);
+    assert_hex_round_trip!(signature.clone(), blsSignatureToHex, blsSignatureFromHex, blsSignatureAsBytes);
+    assert_hex_round_trip!(blsMultiSignature(vec![signature]).unwrap(), blsMultiSignatureToHex, blsMultiSignatureFromHex, blsMultiSignatureAsBytes);
+}
+
+#[wasm_bindgen_test]
+fn hex_conversions_are_compatible_with_rust_side() {
+    let gen = bls::Generator::new().unwrap();
+    let sign_key = bls::SignKey::new(None).unwrap();
+    let ver_key = bls::VerKey::new(&gen, &sign_key).unwrap();
+    let signature = bls::Bls::sign(&[1, 2, 3], &sign_key).unwrap();
+
+    let signature_js = blsSignatureFromHex(&hex::to_hex(signature.as_bytes())).unwrap();
+    let ver_key_js = blsVerKeyFromHex(&hex::to_hex(ver_key.as_bytes())).unwrap();
+    let gen_js = blsGeneratorFromHex(&hex::to_hex(gen.as_bytes())).unwrap();
+    assert!(blsVerify(&signature_js, Some(vec![1, 2, 3]), &ver_key_js, &gen_js).unwrap());
+
+    let signature_hex = blsSignatureToHex(&blsSign(&[4, 5, 6], &blsSignKeyFromBytes(sign_key.as_bytes().to_vec()).unwrap()).unwrap()).unwrap();
+    let signature = bls::Signature::from_bytes(&hex::from_hex(&signature_hex).unwrap()).unwrap();
+    assert!(bls::Bls::verify(&signature, &[4, 5, 6], &ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn hex_conversions_return_error_for_invalid_hex() {
+    for hex in ["abc", "zz00", "0é", "+f"] {
+        let err = blsVerKeyFromHex(hex).unwrap_err();
+        assert!(err.is_instance_of::<js_sys::Error>());
+        assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    }
+
+    // Well formed hex of wrong length is rejected as the bytes are
+    let ver_key_hex = blsVerKeyToHex(&blsVerKey(&blsGenerator().unwrap(), &blsSignKey(None).unwrap()).unwrap()).unwrap();
+    for hex in ["", &ver_key_hex[2..]] {
+        let err = blsVerKeyFromHex(hex).unwrap_err();
+        assert_eq!(Some(ErrorCode::CryptoInvalidKey as usize as f64), get(&err, "code").as_f64());
+    }
+}
+
+#[wasm_bindgen_test]
+fn key_generation_sign_and_verify_work_with_platform_entropy() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+    let signature = blsSign(&[1, 2, 3], &sign_key).unwrap();
+
+    assert!(blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key, &gen).unwrap());
+    assert_ne!(blsSignKeyAsBytes(&sign_key).unwrap().to_vec(), blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec());
+}
+
+#[wasm_bindgen_test]
+fn set_entropy_works() {
+    blsSetEntropy(Some(vec![7; 32])).unwrap();
+    let sign_key1 = blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec();
+    let sign_key2 = blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec();
+
+    blsSetEntropy(Some(vec![7; 32])).unwrap();
+    let same_entropy_key = blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec();
+
+    blsSetEntropy(None).unwrap();
+    let platform_entropy_key = blsSignKeyAsBytes(&blsSignKey(None).unwrap()).unwrap().to_vec();
+
+    assert_ne!(sign_key1, sign_key2);
+    assert_eq!(sign_key1, same_entropy_key);
+    assert_ne!(sign_key1, platform_entropy_key);
+}
+
+#[wasm_bindgen_test]
+fn set_entropy_returns_error_for_short_entropy() {
+    let err = blsSetEntropy(Some(vec![7; 31])).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+fn object(entries: &[(&str, JsValue)]) -> JsValue {
+    let object = js_sys::Object::new();
+    for (key, value) in entries {
+        js_sys::Reflect::set(&object, &JsValue::from_str(key), value).unwrap();
+    }
+    object.into()
+}
+
+fn batch_item(message: &[u8], sign_key: &JsValue, ver_key: &JsValue) -> JsValue {
+    object(&[
+        ("signature", blsSign(message, sign_key).unwrap()),
+        ("message", js_sys::Uint8Array::from(message).into()),
+        ("verKey", ver_key.clone()),
+    ])
+}
+
+#[wasm_bindgen_test]
+fn verify_batch_works() {
+    let gen = blsGenerator().unwrap();
+    let key_pair1 = blsKeyPair(&gen, None).unwrap();
+    let key_pair2 = blsKeyPair(&gen, None).unwrap();
+
+    let items = vec![
+        batch_item(&[1, 2, 3], &get(&key_pair1, "signKey"), &get(&key_pair1, "verKey")),
+        batch_item(&[4, 5, 6], &get(&key_pair2, "signKey"), &get(&key_pair2, "verKey")),
+    ];
+
+    let result = blsVerifyBatch(items, &gen).unwrap();
+    assert_eq!(Some(true), get(&result, "valid").as_bool());
+    assert!(get(&result, "failedIndex").is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn verify_batch_returns_index_of_first_invalid_item() {
+    let gen = blsGenerator().unwrap();
+    let key_pair1 = blsKeyPair(&gen, None).unwrap();
+    let key_pair2 = blsKeyPair(&gen, None).unwrap();
+
+    let items = vec![
+        batch_item(&[1, 2, 3], &get(&key_pair1, "signKey"), &get(&key_pair1, "verKey")),
+        batch_item(&[4, 5, 6], &get(&key_pair1, "signKey"), &get(&key_pair2, "verKey")),
+        batch_item(&[7, 8, 9], &get(&key_pair2, "signKey"), &get(&key_pair1, "verKey")),
+    ];
+
+    let result = blsVerifyBatch(items, &gen).unwrap();
+    assert_eq!(Some(false), get(&result, "valid").as_bool());
+    assert_eq!(Some(1.0), get(&result, "failedIndex").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn verify_batch_accepts_message_as_array() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let item = batch_item(&[1, 2, 3], &get(&key_pair, "signKey"), &get(&key_pair, "verKey"));
+    js_sys::Reflect::set(&item, &JsValue::from_str("message"), &js_sys::JSON::parse("[1, 2, 3]").unwrap()).unwrap();
+
+    let result = blsVerifyBatch(vec![item], &gen).unwrap();
+    assert_eq!(Some(true), get(&result, "valid").as_bool());
+}
+
+#[wasm_bindgen_test]
+fn verify_batch_returns_error_for_empty_items() {
+    let gen = blsGenerator().unwrap();
+    let err = blsVerifyBatch(vec![], &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn verify_batch_returns_error_for_malformed_item() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+
+    let items = vec![
+        batch_item(&[1, 2, 3], &get(&key_pair, "signKey"), &get(&key_pair, "verKey")),
+        object(&[("signature", blsSign(&[1], &get(&key_pair, "signKey")).unwrap())]),
+    ];
+
+    let err = blsVerifyBatch(items, &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("index 1"));
+}
+
+#[wasm_bindgen_test]
+fn verify_pop_batch_works() {
+    let gen = blsGenerator().unwrap();
+    let key_pair1 = blsKeyPair(&gen, None).unwrap();
+    let key_pair2 = blsKeyPair(&gen, None).unwrap();
+
+    let valid_items = vec![
+        object(&[("proofOfPossession", get(&key_pair1, "proofOfPossession")), ("verKey", get(&key_pair1, "verKey"))]),
+        object(&[("proofOfPossession", get(&key_pair2, "proofOfPossession")), ("verKey", get(&key_pair2, "verKey"))]),
+    ];
+    let result = blsVerifyPopBatch(valid_items, &gen).unwrap();
+    assert_eq!(Some(true), get(&result, "valid").as_bool());
+
+    let mixed_items = vec![
+        object(&[("proofOfPossession", get(&key_pair1, "proofOfPossession")), ("verKey", get(&key_pair1, "verKey"))]),
+        object(&[("proofOfPossession", get(&key_pair1, "proofOfPossession")), ("verKey", get(&key_pair2, "verKey"))]),
+    ];
+    let result = blsVerifyPopBatch(mixed_items, &gen).unwrap();
+    assert_eq!(Some(false), get(&result, "valid").as_bool());
+    assert_eq!(Some(1.0), get(&result, "failedIndex").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn verify_pop_batch_returns_error_for_empty_items() {
+    let gen = blsGenerator().unwrap();
+    let err = blsVerifyPopBatch(vec![], &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+fn aggregate_item(message: &[u8], ver_key: &JsValue) -> JsValue {
+    object(&[
+        ("message", js_sys::Uint8Array::from(message).into()),
+        ("verKey", ver_key.clone()),
+    ])
+}
+
+#[wasm_bindgen_test]
+fn verify_aggregate_works() {
+    let gen = blsGenerator().unwrap();
+    let key_pair1 = blsKeyPair(&gen, None).unwrap();
+    let key_pair2 = blsKeyPair(&gen, None).unwrap();
+
+    let signature1 = blsSign(&[1, 2, 3], &get(&key_pair1, "signKey")).unwrap();
+    let signature2 = blsSign(&[4, 5, 6], &get(&key_pair2, "signKey")).unwrap();
+    let multi_sig = blsMultiSignature(vec![signature1, signature2]).unwrap();
+
+    let items = vec![
+        aggregate_item(&[1, 2, 3], &get(&key_pair1, "verKey")),
+        aggregate_item(&[4, 5, 6], &get(&key_pair2, "verKey")),
+    ];
+    assert!(blsVerifyAggregate(&multi_sig, items, &gen).unwrap());
+
+    let swapped_items = vec![
+        aggregate_item(&[4, 5, 6], &get(&key_pair1, "verKey")),
+        aggregate_item(&[1, 2, 3], &get(&key_pair2, "verKey")),
+    ];
+    assert!(!blsVerifyAggregate(&multi_sig, swapped_items, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_aggregate_fails_for_missing_signer() {
+    let gen = blsGenerator().unwrap();
+    let key_pair1 = blsKeyPair(&gen, None).unwrap();
+    let key_pair2 = blsKeyPair(&gen, None).unwrap();
+
+    let signature1 = blsSign(&[1, 2, 3], &get(&key_pair1, "signKey")).unwrap();
+    let multi_sig = blsMultiSignature(vec![signature1]).unwrap();
+
+    let items = vec![
+        aggregate_item(&[1, 2, 3], &get(&key_pair1, "verKey")),
+        aggregate_item(&[4, 5, 6], &get(&key_pair2, "verKey")),
+    ];
+    assert!(!blsVerifyAggregate(&multi_sig, items, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_aggregate_returns_error_for_empty_or_malformed_items() {
+    let gen = blsGenerator().unwrap();
+    let key_pair = blsKeyPair(&gen, None).unwrap();
+    let multi_sig = blsMultiSignature(vec![blsSign(&[1, 2, 3], &get(&key_pair, "signKey")).unwrap()]).unwrap();
+
+    let err = blsVerifyAggregate(&multi_sig, vec![], &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+
+    let items = vec![
+        aggregate_item(&[1, 2, 3], &get(&key_pair, "verKey")),
+        object(&[("message", js_sys::Uint8Array::from(&[4, 5, 6][..]).into())]),
+    ];
+    let err = blsVerifyAggregate(&multi_sig, items, &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("index 1"));
+}
+
+#[wasm_bindgen_test]
+fn class_api_sign_and_verify_work() {
+    let gen = BlsGenerator::new().unwrap();
+    let sign_key = BlsSignKey::new(None).unwrap();
+    let ver_key = BlsVerKey::new(&gen, &sign_key).unwrap();
+
+    let signature = sign_key.sign(&[1, 2, 3]).unwrap();
+
+    assert!(ver_key.verify(&signature, &[1, 2, 3], &gen).unwrap());
+    assert!(signature.verify(&[1, 2, 3], &ver_key, &gen).unwrap());
+    assert!(!signature.verify(&[4, 5, 6], &ver_key, &gen).unwrap());
+
+    let signature = BlsSignature::from_bytes(&signature.as_bytes()).unwrap();
+    let ver_key = BlsVerKey::from_bytes(&ver_key.as_bytes()).unwrap();
+    assert!(signature.verify(&[1, 2, 3], &ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn class_api_multi_sig_works() {
+    let gen = BlsGenerator::new().unwrap();
+    let sign_key1 = BlsSignKey::new(None).unwrap();
+    let sign_key2 = BlsSignKey::new(None).unwrap();
+
+    let mut agg_ver_key = BlsAggregatedVerKey::new().unwrap();
+    agg_ver_key.add(&BlsVerKey::new(&gen, &sign_key1).unwrap()).unwrap();
+    agg_ver_key.add(&BlsVerKey::new(&gen, &sign_key2).unwrap()).unwrap();
+
+    let mut multi_sig = BlsMultiSignature::new().unwrap();
+    multi_sig.add(&sign_key1.sign(&[1, 2, 3]).unwrap()).unwrap();
+    assert!(!multi_sig.verify(&[1, 2, 3], &agg_ver_key, &gen).unwrap());
+
+    multi_sig.add(&sign_key2.sign(&[1, 2, 3]).unwrap()).unwrap();
+    assert!(multi_sig.verify(&[1, 2, 3], &agg_ver_key, &gen).unwrap());
+
+    let multi_sig = BlsMultiSignature::from_bytes(&multi_sig.as_bytes()).unwrap();
+    assert!(multi_sig.verify(&[1, 2, 3], &agg_ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn class_api_converts_to_and_from_function_api() {
+    let gen = BlsGenerator::new().unwrap();
+    let sign_key = BlsSignKey::from_json(&blsSignKey(None).unwrap()).unwrap();
+    let ver_key = BlsVerKey::new(&gen, &sign_key).unwrap();
+    let signature = sign_key.sign(&[1, 2, 3]).unwrap();
+
+    let valid = blsVerify(&signature.to_json().unwrap(), Some(vec![1, 2, 3]), &ver_key.to_json().unwrap(), &gen.to_json().unwrap()).unwrap();
+    assert!(valid);
+
+    let multi_sig = blsMultiSignature(vec![signature.to_json().unwrap()]).unwrap();
+    assert_eq!(blsMultiSignatureAsBytes(&multi_sig).unwrap(), BlsMultiSignature::from_json(&multi_sig).unwrap().as_bytes());
+}
+
+#[wasm_bindgen_test]
+fn hasher_works_for_chunks_of_any_size() {
+    let gen = BlsGenerator::new().unwrap();
+    let sign_key = BlsSignKey::new(None).unwrap();
+    let ver_key = BlsVerKey::new(&gen, &sign_key).unwrap();
+    let message: Vec<u8> = (0..=255).collect();
+
+    let mut hasher = BlsHasher::new();
+    for chunk in message.chunks(1) {
+        hasher.update(chunk).unwrap();
+    }
+    let byte_chunks_signature = hasher.finalize_sign(&sign_key).unwrap();
+
+    let mut hasher = BlsHasher::new();
+    hasher.update(&message).unwrap();
+    let one_chunk_signature = hasher.finalize_sign(&sign_key).unwrap();
+
+    assert_eq!(one_chunk_signature.as_bytes(), byte_chunks_signature.as_bytes());
+    assert_eq!(blsSignatureAsBytes(&blsSign(&message, &sign_key.to_json().unwrap()).unwrap()).unwrap(), one_chunk_signature.as_bytes());
+
+    let mut hasher = BlsHasher::new();
+    hasher.update(&message[..100]).unwrap();
+    hasher.update(&message[100..]).unwrap();
+    assert!(hasher.finalize_verify(&one_chunk_signature, &ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn hasher_returns_error_after_finalize() {
+    let sign_key = BlsSignKey::new(None).unwrap();
+    let mut hasher = BlsHasher::new();
+    hasher.finalize_sign(&sign_key).unwrap();
+
+    let err = hasher.update(&[1, 2, 3]).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidState as usize as f64), get(&err, "code").as_f64());
+    let err = hasher.finalize_sign(&sign_key).err().unwrap();
+    assert_eq!(Some(ErrorCode::CommonInvalidState as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn sign_key_zeroize_makes_sign_key_unusable() {
+    let sign_key = blsSignKey(None).unwrap();
+    assert!(blsSign(&[1, 2, 3], &sign_key).is_ok());
+    let bytes = get(&sign_key, "bytes");
+
+    blsSignKeyZeroize(&sign_key).unwrap();
+
+    assert!(js_sys::Array::from(&bytes).iter().all(|b| b.as_f64() == Some(0.0)));
+    let err = blsSign(&[1, 2, 3], &sign_key).unwrap_err();
+    assert!(err.is_instance_of::<js_sys::Error>());
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+
+    let err = blsSignKeyZeroize(&JsValue::from_str("sign key")).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn class_api_sign_key_zeroize_makes_handle_unusable() {
+    let gen = BlsGenerator::new().unwrap();
+    let mut sign_key = BlsSignKey::new(None).unwrap();
+    assert!(sign_key.sign(&[1, 2, 3]).is_ok());
+
+    sign_key.zeroize();
+
+    for err in [
+        sign_key.sign(&[1, 2, 3]).err().unwrap(),
+        sign_key.as_bytes().unwrap_err(),
+        sign_key.to_json().unwrap_err(),
+        BlsVerKey::new(&gen, &sign_key).err().unwrap(),
+    ] {
+        assert!(err.is_instance_of::<js_sys::Error>());
+        assert_eq!(Some(ErrorCode::CommonInvalidState as usize as f64), get(&err, "code").as_f64());
+    }
+}
+
+#[wasm_bindgen_test]
+fn pairing_definition_bilinearity() {
+    let a = groupOrderElementNew().unwrap();
+    let b = groupOrderElementNew().unwrap();
+    let p = pointG1New().unwrap();
+    let q = pointG2New().unwrap();
+    let left = pairPair(&pointG1Mul(&p, &a).unwrap(), &pointG2Mul(&q, &b).unwrap()).unwrap();
+    let right = pairPow(&pairPair(&p, &q).unwrap(), &groupOrderElementMulMod(&a, &b).unwrap()).unwrap();
+    assert!(pairEquals(&left, &right).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn pair_primitives_convert_to_and_from_bytes_and_hex() {
+    let p = pointG1New().unwrap();
+    let q = pointG2New().unwrap();
+    let e = groupOrderElementNew().unwrap();
+    let pair = pairPair(&p, &q).unwrap();
+
+    assert_eq!(pointG1ToBytes(&p).unwrap(), pointG1ToBytes(&pointG1FromBytes(&pointG1ToBytes(&p).unwrap()).unwrap()).unwrap());
+    assert_eq!(pointG2ToBytes(&q).unwrap(), pointG2ToBytes(&pointG2FromBytes(&pointG2ToBytes(&q).unwrap()).unwrap()).unwrap());
+    assert_eq!(groupOrderElementToBytes(&e).unwrap(), groupOrderElementToBytes(&groupOrderElementFromBytes(&groupOrderElementToBytes(&e).unwrap()).unwrap()).unwrap());
+    assert!(pairEquals(&pair, &pairFromBytes(&pairToBytes(&pair).unwrap()).unwrap()).unwrap());
+
+    assert_eq!(pointG1ToHex(&p).unwrap(), pointG1ToHex(&pointG1FromHex(&pointG1ToHex(&p).unwrap()).unwrap()).unwrap());
+    assert_eq!(pointG2ToHex(&q).unwrap(), pointG2ToHex(&pointG2FromHex(&pointG2ToHex(&q).unwrap()).unwrap()).unwrap());
+    assert_eq!(groupOrderElementToHex(&e).unwrap(), groupOrderElementToHex(&groupOrderElementFromHex(&groupOrderElementToHex(&e).unwrap()).unwrap()).unwrap());
+    assert!(pairEquals(&pair, &pairFromHex(&pairToHex(&pair).unwrap()).unwrap()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn point_g1_arithmetic_works() {
+    let p = pointG1New().unwrap();
+    let q = pointG1New().unwrap();
+    let sum = pointG1Add(&p, &q).unwrap();
+    assert_eq!(pointG1ToBytes(&p).unwrap(), pointG1ToBytes(&pointG1Sub(&sum, &q).unwrap()).unwrap());
+    assert!(pointG1IsInf(&pointG1NewInf().unwrap()).unwrap());
+    assert!(pointG1IsInf(&pointG1Add(&p, &pointG1Neg(&p).unwrap()).unwrap()).unwrap());
+    assert!(!pointG1IsInf(&sum).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn pair_primitives_return_error_for_malformed_values() {
+    let err = pointG1FromHex("garbage").unwrap_err();
+    assert_eq!(Some(ErrorCode::CryptoInvalidPoint as usize as f64), get(&err, "code").as_f64());
+
+    let err = pointG2FromBytes(&[1, 2, 3]).unwrap_err();
+    assert_eq!(Some(ErrorCode::CryptoInvalidPoint as usize as f64), get(&err, "code").as_f64());
+
+    let err = pairFromBytes(&[1, 2, 3]).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+
+    let err = pointG1Mul(&JsValue::from_f64(1.0), &groupOrderElementNew().unwrap()).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn threshold_sign_and_combine_work() {
+    let gen = blsGenerator().unwrap();
+    let generated = blsGenerateShares(3, 2, None).unwrap();
+    let ver_key = blsVerKey(&gen, &get(&generated, "signKey")).unwrap();
+    let shares: Vec<JsValue> = js_sys::Array::from(&get(&generated, "shares")).iter().collect();
+    assert_eq!(3, shares.len());
+    assert_eq!(Some(1.0), get(&shares[0], "index").as_f64());
+    assert_eq!(Some(2.0), get(&shares[0], "threshold").as_f64());
+
+    let message = [1, 2, 3, 4, 5];
+    let share1 = blsSignShare(&message, &shares[0]).unwrap();
+    let share3 = blsSignShare(&message, &shares[2]).unwrap();
+
+    let signature = blsCombineSignatureShares(vec![share3, share1]).unwrap();
+    assert!(blsVerify(&signature, Some(message.to_vec()), &ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn threshold_shares_convert_to_and_from_bytes() {
+    let generated = blsGenerateShares(3, 2, Some(vec![1, 2, 3])).unwrap();
+    let share = js_sys::Array::from(&get(&generated, "shares")).get(1);
+
+    let restored = blsSignKeyShareFromBytes(blsSignKeyShareAsBytes(&share).unwrap().to_vec()).unwrap();
+    assert_eq!(blsSignKeyShareAsBytes(&share).unwrap().to_vec(), blsSignKeyShareAsBytes(&restored).unwrap().to_vec());
+    assert_eq!(Some(2.0), get(&restored, "index").as_f64());
+
+    let signature_share = blsSignShare(&[1, 2, 3], &restored).unwrap();
+    let restored = blsSignatureShareFromBytes(&blsSignatureShareAsBytes(&signature_share).unwrap()).unwrap();
+    assert_eq!(blsSignatureShareAsBytes(&signature_share).unwrap(), blsSignatureShareAsBytes(&restored).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn threshold_combine_returns_error_for_insufficient_or_duplicate_shares() {
+    let generated = blsGenerateShares(3, 2, None).unwrap();
+    let share = js_sys::Array::from(&get(&generated, "shares")).get(0);
+    let signature_share = blsSignShare(&[1, 2, 3], &share).unwrap();
+
+    let err = blsCombineSignatureShares(vec![signature_share.clone()]).unwrap_err();
+    assert!(err.is_instance_of::<js_sys::Error>());
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Not enough signature shares"));
+
+    let err = blsCombineSignatureShares(vec![signature_share.clone(), signature_share]).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Duplicate signature share index 1"));
+
+    let err = blsGenerateShares(2, 3, None).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+fn stringify(value: &JsValue) -> String {
+    String::from(js_sys::JSON::stringify(value).unwrap())
+}
+
+fn assert_same_json(expected: &str, actual: &str) {
+    let expected: serde_json::Value = serde_json::from_str(expected).unwrap();
+    let actual: serde_json::Value = serde_json::from_str(actual).unwrap();
+    assert_eq!(expected, actual);
+}
+
+#[wasm_bindgen_test]
+fn json_api_round_trips_objects() {
+    let seed = [7u8; 32];
+    let message = [1u8, 2, 3];
+
+    let gen = blsGeneratorJson();
+    let sign_key = blsSignKeyJson(Some(base64::to_base64(&seed)));
+    let ver_key = blsVerKeyJson(&gen, &sign_key);
+    let pop = blsProofOfPossessionJson(&ver_key, &sign_key);
+    let signature = blsSignJson(&base64::to_base64(&message), &sign_key);
+    let multi_sig = blsMultiSignatureJson(&format!("[{}]", signature));
+    let agg_ver_key = blsCreateAggregatedVerKeyJson(&format!("[{}]", ver_key));
+
+    // Strings of the JSON API are accepted by the JsValue API and give back the same JSON
+    let gen_js = js_sys::JSON::parse(&gen).unwrap();
+    assert_same_json(&gen, &stringify(&blsGeneratorFromBytes(&blsGeneratorAsBytes(&gen_js).unwrap()).unwrap()));
+    let sign_key_js = js_sys::JSON::parse(&sign_key).unwrap();
+    assert_same_json(&sign_key, &stringify(&blsSignKeyFromBytes(blsSignKeyAsBytes(&sign_key_js).unwrap().to_vec()).unwrap()));
+    let ver_key_js = js_sys::JSON::parse(&ver_key).unwrap();
+    assert_same_json(&ver_key, &stringify(&blsVerKeyFromBytes(&blsVerKeyAsBytes(&ver_key_js).unwrap()).unwrap()));
+    let pop_js = js_sys::JSON::parse(&pop).unwrap();
+    assert_same_json(&pop, &stringify(&blsProofOfPossessionFromBytes(&blsProofOfPossessionAsBytes(&pop_js).unwrap()).unwrap()));
+    let signature_js = js_sys::JSON::parse(&signature).unwrap();
+    assert_same_json(&signature, &stringify(&blsSignatureFromBytes(&blsSignatureAsBytes(&signature_js).unwrap()).unwrap()));
+    let multi_sig_js = js_sys::JSON::parse(&multi_sig).unwrap();
+    assert_same_json(&multi_sig, &stringify(&blsMultiSignatureFromBytes(&blsMultiSignatureAsBytes(&multi_sig_js).unwrap()).unwrap()));
+    let agg_ver_key_js = js_sys::JSON::parse(&agg_ver_key).unwrap();
+    assert_same_json(&agg_ver_key, &stringify(&blsAggregatedVerKeyFromBytes(&blsAggregatedVerKeyAsBytes(&agg_ver_key_js).unwrap()).unwrap()));
+
+    let message = base64::to_base64(&message);
+    assert_eq!("true", blsVerifyJson(&signature, &message, &ver_key, &gen));
+    assert_eq!("true", blsVerifyProofOfPossessionJson(&pop, &ver_key, &gen));
+    assert_eq!("true", blsVerifyMultiSigJson(&multi_sig, &message, &format!("[{}]", ver_key), &gen));
+    assert_eq!("true", blsVerifyMultiSigWithAggregatedVerKeyJson(&multi_sig, &message, &agg_ver_key, &gen));
+    assert_eq!("false", blsVerifyJson(&signature, &base64::to_base64(&[3, 2, 1]), &ver_key, &gen));
+}
+
+#[wasm_bindgen_test]
+fn json_api_signatures_match_js_value_api() {
+    let seed = [9u8; 32];
+    let message = [4u8, 5, 6];
+
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(Some(seed.to_vec())).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+    let signature = blsSign(&message, &sign_key).unwrap();
+
+    let sign_key_json = blsSignKeyJson(Some(base64::to_base64(&seed)));
+    assert_same_json(&stringify(&sign_key), &sign_key_json);
+    assert_same_json(&stringify(&ver_key), &blsVerKeyJson(&stringify(&gen), &sign_key_json));
+
+    let signature_json = blsSignJson(&base64::to_base64(&message), &sign_key_json);
+    assert_same_json(&stringify(&signature), &signature_json);
+    assert!(blsVerify(&js_sys::JSON::parse(&signature_json).unwrap(), Some(message.to_vec()), &ver_key, &gen).unwrap());
+    assert_eq!("true", blsVerifyJson(&stringify(&signature), &base64::to_base64(&message), &stringify(&ver_key), &stringify(&gen)));
+
+    let multi_sig = blsMultiSignature(vec![signature]).unwrap();
+    assert_same_json(&stringify(&multi_sig), &blsMultiSignatureJson(&format!("[{}]", signature_json)));
+}
+
+#[wasm_bindgen_test]
+fn json_api_sign_key_works_for_seed_of_any_length() {
+    let seed = [3u8; 5];
+
+    let sign_key = blsSignKey(Some(seed.to_vec())).unwrap();
+    assert_same_json(&stringify(&sign_key), &blsSignKeyJson(Some(base64::to_base64(&seed))));
+}
+
+#[wasm_bindgen_test]
+fn json_api_returns_error_object() {
+    let gen = blsGeneratorJson();
+    let sign_key = blsSignKeyJson(None);
+    let ver_key = blsVerKeyJson(&gen, &sign_key);
+    let signature = blsSignJson("", &sign_key);
+
+    let error: serde_json::Value = serde_json::from_str(&blsVerifyJson(&signature, "AQ-", &ver_key, &gen)).unwrap();
+    assert_eq!(ErrorCode::CommonInvalidStructure as u64, error["error"]["code"].as_u64().unwrap());
+    assert_eq!("CommonInvalidStructure", error["error"]["name"].as_str().unwrap());
+    assert!(error["error"]["message"].as_str().unwrap().contains("Invalid argument `messageBase64`: "));
+
+    let error: serde_json::Value = serde_json::from_str(&blsVerKeyJson("{}", &sign_key)).unwrap();
+    assert!(error["error"]["message"].as_str().unwrap().contains("Invalid argument `generatorJson`: "));
+
+    let error: serde_json::Value = serde_json::from_str(&blsVerifyMultiSigJson(&signature, "", "[]", &gen)).unwrap();
+    assert!(error["error"]["message"].as_str().unwrap().contains("Invalid argument `verKeysJson`: array is empty"));
+
+    let error: serde_json::Value = serde_json::from_str(&blsMultiSignatureJson(&format!("[{}, {{}}]", signature))).unwrap();
+    assert!(error["error"]["message"].as_str().unwrap().contains("Invalid argument `signaturesJson` at index 1: "));
+
+    let error: serde_json::Value = serde_json::from_str(&blsSignKeyJson(Some("=".to_string()))).unwrap();
+    assert_eq!(ErrorCode::CommonInvalidStructure as u64, error["error"]["code"].as_u64().unwrap());
+}
+
+#[wasm_bindgen_test]
+fn library_version_works() {
+    let version: serde_json::Value = serde_json::from_str(&blsLibraryVersion()).unwrap();
+
+    assert_eq!(env!("CARGO_PKG_VERSION"), version["version"].as_str().unwrap());
+    let features: Vec<&str> = version["features"].as_array().unwrap().iter().map(|feature| feature.as_str().unwrap()).collect();
+    assert!(features.contains(&"wasm"));
+    assert!(!features.contains(&"ffi"));
+    assert_eq!("BN254", version["curve"].as_str().unwrap());
+}
+
+#[wasm_bindgen_test]
+fn sign_with_dst_works() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+    let message = vec![1, 2, 3];
+
+    let signature = blsSignWithDst(&message, &sign_key, Some(b"DST-A".to_vec())).unwrap();
+    assert!(blsVerifyWithDst(&signature, Some(message.clone()), &ver_key, &gen, Some(b"DST-A".to_vec())).unwrap());
+    assert!(!blsVerifyWithDst(&signature, Some(message.clone()), &ver_key, &gen, Some(b"DST-B".to_vec())).unwrap());
+    assert!(!blsVerify(&signature, Some(message.clone()), &ver_key, &gen).unwrap());
+
+    let signature_bytes = blsSignatureAsBytes(&signature).unwrap();
+    let native = bls::Bls::sign_with_dst(&message, &bls::SignKey::from_bytes(&blsSignKeyAsBytes(&sign_key).unwrap().to_vec()).unwrap(), b"DST-A").unwrap();
+    assert_eq!(native.as_bytes().to_vec(), signature_bytes);
+}
+
+#[wasm_bindgen_test]
+fn sign_with_dst_works_without_dst() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+
+    let signature = blsSignWithDst(&[1, 2, 3], &sign_key, None).unwrap();
+    assert_eq!(blsSignatureAsBytes(&blsSign(&[1, 2, 3], &sign_key).unwrap()).unwrap(), blsSignatureAsBytes(&signature).unwrap());
+    assert!(blsVerifyWithDst(&signature, Some(vec![1, 2, 3]), &ver_key, &gen, None).unwrap());
+    assert!(blsVerifyMultiSigWithDst(&blsMultiSignature(vec![signature]).unwrap(), Some(vec![1, 2, 3]), vec![ver_key], &gen, None).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_multi_sig_with_dst_works() {
+    let gen = blsGenerator().unwrap();
+    let sign_keys = [blsSignKey(None).unwrap(), blsSignKey(None).unwrap()];
+    let ver_keys: Vec<JsValue> = sign_keys.iter().map(|sign_key| blsVerKey(&gen, sign_key).unwrap()).collect();
+    let signatures: Vec<JsValue> = sign_keys.iter()
+        .map(|sign_key| blsSignWithDst(&[1, 2, 3], sign_key, Some(b"DST-A".to_vec())).unwrap())
+        .collect();
+    let multi_sig = blsMultiSignature(signatures).unwrap();
+
+    assert!(blsVerifyMultiSigWithDst(&multi_sig, Some(vec![1, 2, 3]), ver_keys.clone(), &gen, Some(b"DST-A".to_vec())).unwrap());
+    assert!(!blsVerifyMultiSigWithDst(&multi_sig, Some(vec![1, 2, 3]), ver_keys.clone(), &gen, Some(b"DST-B".to_vec())).unwrap());
+    assert!(!blsVerifyMultiSig(&multi_sig, Some(vec![1, 2, 3]), ver_keys, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn sign_with_dst_returns_error_for_empty_dst() {
+    let err = blsSignWithDst(&[1, 2, 3], &blsSignKey(None).unwrap(), Some(vec![])).err().unwrap();
+
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn default_generator_works() {
+    let expected = "061a10bb519eb62feb8d8c7e8c61edb6a4648bbb4898bf0d91ee4224c803fb2b0516aaf9ba737833310aa78c5982aa5b1f4d746bae3784b70d8c34c1e7d54cf3021897a06baf93439a90e096698c822329bd0ae6bdbe09bd19f0e07891cd2b9a0ebb2b0e7c8b15268f6d4456f5f38d37b09006ffd739c9578a2d1aec6b3ace9b";
+
+    let gen_bytes = blsGeneratorAsBytes(&blsDefaultGenerator().unwrap()).unwrap();
+    assert_eq!(expected, hex::to_hex(&gen_bytes));
+    assert_eq!(gen_bytes, blsGeneratorAsBytes(&blsDefaultGenerator().unwrap()).unwrap());
+    assert_eq!(gen_bytes, blsGeneratorAsBytes(&blsGeneratorFromBytes(&gen_bytes).unwrap()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn verify_works_for_default_generator() {
+    let gen = bls::Generator::standard().unwrap();
+    let sign_key = bls::SignKey::new(None).unwrap();
+    let ver_key = blsVerKeyFromBytes(bls::VerKey::new(&gen, &sign_key).unwrap().as_bytes()).unwrap();
+    let signature = blsSignatureFromBytes(bls::Bls::sign(&[1, 2, 3], &sign_key).unwrap().as_bytes()).unwrap();
+
+    assert!(blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key, &JsValue::NULL).unwrap());
+    assert!(blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key, &blsDefaultGenerator().unwrap()).unwrap());
+    assert!(!blsVerify(&signature, Some(vec![1, 2, 3]), &ver_key, &blsGenerator().unwrap()).unwrap());
+
+    let multi_sig = blsMultiSignature(vec![signature]).unwrap();
+    assert!(blsVerifyMultiSig(&multi_sig, Some(vec![1, 2, 3]), vec![ver_key], &JsValue::NULL).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn multi_signature_add_and_merge_work() {
+    let signatures: Vec<JsValue> = (0..5).map(|_| blsSign(&[1, 2, 3], &blsSignKey(None).unwrap()).unwrap()).collect();
+
+    let mut multi_sig1 = blsMultiSignatureEmpty().unwrap();
+    let mut multi_sig2 = blsMultiSignatureEmpty().unwrap();
+    for (index, signature) in signatures.iter().enumerate() {
+        if index.is_multiple_of(2) {
+            multi_sig1 = blsMultiSignatureAdd(&multi_sig1, signature).unwrap();
+        } else {
+            multi_sig2 = blsMultiSignatureAdd(&multi_sig2, signature).unwrap();
+        }
+        if index == 2 {
+            multi_sig1 = blsMultiSignatureMerge(&multi_sig1, &multi_sig2).unwrap();
+            multi_sig2 = blsMultiSignatureEmpty().unwrap();
+        }
+    }
+    let merged = blsMultiSignatureMerge(&multi_sig1, &multi_sig2).unwrap();
+
+    let expected = blsMultiSignature(signatures).unwrap();
+    assert_eq!(blsMultiSignatureAsBytes(&expected).unwrap(), blsMultiSignatureAsBytes(&merged).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn multi_signature_add_leaves_argument_as_is() {
+    let signature = blsSign(&[1, 2, 3], &blsSignKey(None).unwrap()).unwrap();
+    let empty = blsMultiSignatureEmpty().unwrap();
+    let empty_bytes = blsMultiSignatureAsBytes(&empty).unwrap();
+
+    let multi_sig = blsMultiSignatureAdd(&empty, &signature).unwrap();
+
+    assert_eq!(empty_bytes, blsMultiSignatureAsBytes(&empty).unwrap());
+    assert_eq!(blsMultiSignatureAsBytes(&blsMultiSignature(vec![signature]).unwrap()).unwrap(), blsMultiSignatureAsBytes(&multi_sig).unwrap());
+
+    let err = blsMultiSignatureAdd(&multi_sig, &JsValue::NULL).err().unwrap();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `signature`: value is missing"));
+}
+
+#[wasm_bindgen_test]
+fn proof_of_possession_for_challenge_works() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+
+    let pop = blsProofOfPossessionForChallenge(b"nonce1", &ver_key, &sign_key).unwrap();
+
+    assert!(blsVerifyProofOfPossessionForChallenge(&pop, Some(b"nonce1".to_vec()), &ver_key, &gen).unwrap());
+    assert!(!blsVerifyProofOfPossessionForChallenge(&pop, Some(b"nonce2".to_vec()), &ver_key, &gen).unwrap());
+    assert!(!blsVerifyProofOfPossession(&pop, &ver_key, &gen).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn proof_of_possession_for_challenge_returns_error_for_empty_challenge() {
+    let gen = blsGenerator().unwrap();
+    let sign_key = blsSignKey(None).unwrap();
+    let ver_key = blsVerKey(&gen, &sign_key).unwrap();
+
+    let err = blsProofOfPossessionForChallenge(&[], &ver_key, &sign_key).err().unwrap();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+    assert!(get(&err, "message").as_string().unwrap().contains("Challenge is empty"));
+
+    let pop = blsProofOfPossession(&ver_key, &sign_key).unwrap();
+    let err = blsVerifyProofOfPossessionForChallenge(&pop, Some(vec![]), &ver_key, &gen).unwrap_err();
+    assert_eq!(Some(ErrorCode::CommonInvalidStructure as usize as f64), get(&err, "code").as_f64());
+
+    let err = blsVerifyProofOfPossessionForChallenge(&pop, None, &ver_key, &gen).unwrap_err();
+    assert!(get(&err, "message").as_string().unwrap().contains("Invalid argument `challenge`: value is missing"));
+}
@@ -164,3 +164,15 @@ test('blsVerifyMultiSig incorrect message', (t) => {
   
   t.false(verified)
 })
+
+test('pairPair is bilinear', (t) => {
+  const a = indyCrypto.groupOrderElementNew()
+  const p = indyCrypto.pointG1New()
+  const q = indyCrypto.pointG2New()
+
+  const left = indyCrypto.pairPair(indyCrypto.pointG1Mul(p, a), q)
+  const right = indyCrypto.pairPair(p, indyCrypto.pointG2Mul(q, a))
+
+  t.true(indyCrypto.pairEquals(left, right))
+  t.false(indyCrypto.pairEquals(left, indyCrypto.pairPair(p, q)))
+})
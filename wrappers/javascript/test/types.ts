@@ -0,0 +1,121 @@
+// Type level checks of the generated TypeScript definitions, compiled with `npm run test:types`.
+// Nothing here is executed.
+import * as indy from '../dist/node/indy_crypto'
+
+const message: Uint8Array = new Uint8Array([1, 2, 3])
+
+indy.blsSetEntropy(new Uint8Array(32))
+indy.blsSetEntropy()
+
+const generator: indy.Generator = indy.blsGenerator()
+const signKey: indy.SignKey = indy.blsSignKey()
+const seededSignKey: indy.SignKey = indy.blsSignKey(new Uint8Array(32))
+const hexSeededSignKey: indy.SignKey = indy.blsSignKeyFromHexSeed('00ff')
+const verKey: indy.VerKey = indy.blsVerKey(generator, signKey)
+const pop: indy.ProofOfPossession = indy.blsProofOfPossession(verKey, signKey)
+const signature: indy.Signature = indy.blsSign(message, signKey)
+const multiSig: indy.MultiSignature = indy.blsMultiSignature([signature])
+const aggVerKey: indy.AggregatedVerKey = indy.blsCreateAggregatedVerKey([verKey])
+const keyPair: indy.KeyPair = indy.blsKeyPair(generator)
+const keyPairPop: indy.ProofOfPossession = keyPair.proofOfPossession
+
+const point: string = verKey.point
+const bytes: number[] = verKey.bytes
+const groupOrderElement: string = seededSignKey.group_order_element
+
+const valid: boolean = indy.blsVerify(signature, message, verKey, generator)
+const validMultiSig: boolean = indy.blsVerifyMultiSig(multiSig, message, [verKey], generator)
+const validAggregated: boolean = indy.blsVerifyMultiSigWithAggregatedVerKey(multiSig, message, aggVerKey, generator)
+const validPop: boolean = indy.blsVerifyProofOfPossession(pop, verKey, generator)
+const batch: indy.BatchResult = indy.blsVerifyBatch([{ signature, message, verKey }], generator)
+const batchValid: boolean = batch.valid
+const failedIndex: number | undefined = batch.failedIndex
+const popBatch: indy.BatchResult = indy.blsVerifyPopBatch([{ proofOfPossession: pop, verKey }], generator)
+const validAggregate: boolean = indy.blsVerifyAggregate(multiSig, [{ message, verKey }], generator)
+
+const signatureBytes: Uint8Array = indy.blsSignBytes(message, indy.blsSignKeyAsBytes(signKey))
+const validBytes: boolean = indy.blsVerifyBytes(signatureBytes, message, indy.blsVerKeyAsBytes(verKey), indy.blsGeneratorAsBytes(generator))
+const restored: indy.VerKey = indy.blsVerKeyFromBytes(indy.blsVerKeyAsBytes(verKey))
+const signatureHex: string = indy.blsSignatureToHex(signature)
+const fromHex: indy.Signature = indy.blsSignatureFromHex(signatureHex)
+const generatorFromHex: indy.Generator = indy.blsGeneratorFromHex(indy.blsGeneratorToHex(generator))
+const verKeyFromHex: indy.VerKey = indy.blsVerKeyFromHex(indy.blsVerKeyToHex(verKey))
+const multiSigFromHex: indy.MultiSignature = indy.blsMultiSignatureFromHex(indy.blsMultiSignatureToHex(multiSig))
+
+const a: indy.GroupOrderElement = indy.groupOrderElementNew()
+const p: indy.PointG1 = indy.pointG1New()
+const q: indy.PointG2 = indy.pointG2New()
+const e: indy.Pair = indy.pairPair(indy.pointG1Mul(p, a), q)
+// e(aP, Q) = e(P, aQ)
+const bilinear: boolean = indy.pairEquals(e, indy.pairPair(p, indy.pointG2Mul(q, a)))
+const pairBytes: Uint8Array = indy.pairToBytes(e)
+const pointFromHex: indy.PointG1 = indy.pointG1FromHex(indy.pointG1ToHex(p))
+// @ts-expect-error point of G2 is not a point of G1
+indy.pairPair(q, q)
+// @ts-expect-error group order element is not a point
+indy.pointG1Mul(p, p)
+// @ts-expect-error plain strings are parsed by the `FromHex` functions
+indy.pointG1IsInf('1 2 3')
+
+const blsGenerator: indy.BlsGenerator = new indy.BlsGenerator()
+const blsSignKey: indy.BlsSignKey = indy.BlsSignKey.fromJSON(signKey)
+const blsVerKey: indy.BlsVerKey = new indy.BlsVerKey(blsGenerator, blsSignKey)
+const blsSignature: indy.BlsSignature = blsSignKey.sign(message)
+const classValid: boolean = blsVerKey.verify(blsSignature, message, blsGenerator)
+const verKeyJson: indy.VerKey = blsVerKey.toJSON()
+
+const hasher: indy.BlsHasher = new indy.BlsHasher()
+hasher.update(message)
+const streamedSignature: indy.BlsSignature = hasher.finalizeSign(blsSignKey)
+const streamedValid: boolean = new indy.BlsHasher().finalizeVerify(streamedSignature, blsVerKey, blsGenerator)
+
+const disposableSignKey: indy.BlsSignKey = new indy.BlsSignKey()
+disposableSignKey.zeroize()
+indy.blsSignKeyZeroize(indy.blsSignKey())
+
+try {
+  indy.blsVerKeyFromBytes(new Uint8Array(0))
+} catch (e) {
+  const err = e as indy.IndyCryptoError
+  const code: number = err.code
+  const name: string = err.name
+  const text: string = err.message
+}
+
+// @ts-expect-error sign key is not a verification key
+indy.blsVerify(signature, message, signKey, generator)
+// @ts-expect-error messages are bytes
+indy.blsSign('message', signKey)
+// @ts-expect-error verification keys are passed as a list
+indy.blsVerifyMultiSig(multiSig, message, verKey, generator)
+// @ts-expect-error sign key has no point
+seededSignKey.point
+
+const generated: indy.SignKeyShares = indy.blsGenerateShares(3, 2)
+const signatureShare: indy.SignatureShare = indy.blsSignShare(message, generated.shares[0])
+const shareIndex: number = signatureShare.index
+const combined: indy.Signature = indy.blsCombineSignatureShares([signatureShare, indy.blsSignShare(message, generated.shares[1])])
+const storedShare: indy.SignKeyShare = indy.blsSignKeyShareFromBytes(indy.blsSignKeyShareAsBytes(generated.shares[2]))
+const storedSignatureShare: indy.SignatureShare = indy.blsSignatureShareFromBytes(indy.blsSignatureShareAsBytes(signatureShare))
+const emptyValid: boolean = indy.blsVerify(indy.blsSign(new Uint8Array(0), signKey), new Uint8Array(0), verKey, generator)
+
+const generatorJson: string = indy.blsGeneratorJson()
+const signKeyJson: string = indy.blsSignKeyJson('AAECAw==')
+const signatureJson: string = indy.blsSignJson('AQID', signKeyJson)
+const verifiedJson: string = indy.blsVerifyJson(signatureJson, 'AQID', indy.blsVerKeyJson(generatorJson, signKeyJson), generatorJson)
+// @ts-expect-error messages of the JSON API are base64 strings
+indy.blsSignJson(message, signKeyJson)
+const libraryVersion: { version: string } = JSON.parse(indy.blsLibraryVersion())
+const rawSignature: Uint8Array = indy.blsSignRaw(message, indy.blsSignKeyAsBytes(signKey))
+const rawValid: boolean = indy.blsVerifyRaw(rawSignature, message, indy.blsVerKeyAsBytes(verKey), indy.blsGeneratorAsBytes(generator))
+const rawMultiSig: Uint8Array = indy.blsMultiSignatureRaw([rawSignature, signatureBytes])
+const dst = new TextEncoder().encode('INDY-BLS-SIG')
+const dstSignature: indy.Signature = indy.blsSignWithDst(message, signKey, dst)
+const dstValid: boolean = indy.blsVerifyWithDst(dstSignature, message, verKey, generator, dst)
+const dstMultiValid: boolean = indy.blsVerifyMultiSigWithDst(indy.blsMultiSignature([dstSignature]), message, [verKey], generator)
+const defaultGenerator: indy.Generator = indy.blsDefaultGenerator()
+const defaultValid: boolean = indy.blsVerify(signature, message, verKey, null)
+const accumulated: indy.MultiSignature = indy.blsMultiSignatureMerge(indy.blsMultiSignatureAdd(indy.blsMultiSignatureEmpty(), signature), multiSig)
+const challenge = new Uint8Array([1, 2, 3])
+const challengePop: indy.ProofOfPossession = indy.blsProofOfPossessionForChallenge(challenge, verKey, signKey)
+const challengeValid: boolean = indy.blsVerifyProofOfPossessionForChallenge(challengePop, challenge, verKey, generator)